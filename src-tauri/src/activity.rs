@@ -16,6 +16,7 @@
 
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 // ── Types ─────────────────────────────────────────────────────────────────────
@@ -67,6 +68,12 @@ pub enum ActivityEvent {
     FeatureStateChanged,
     /// A feature was permanently deleted.
     FeatureDeleted,
+    /// A reactive sync left the project untouched because it is locked.
+    SyncSkippedLocked,
+    /// A project template was applied to the project.
+    TemplateApplied,
+    /// A file or directory was deleted (or, in a dry run, would have been).
+    PathRemoved,
 }
 
 impl ActivityEvent {
@@ -88,6 +95,9 @@ impl ActivityEvent {
             Self::FeatureUpdated => "feature_updated",
             Self::FeatureStateChanged => "feature_state_changed",
             Self::FeatureDeleted => "feature_deleted",
+            Self::SyncSkippedLocked => "sync_skipped_locked",
+            Self::TemplateApplied => "template_applied",
+            Self::PathRemoved => "path_removed",
         }
     }
 }
@@ -172,8 +182,12 @@ fn log_inner(project: &str, event: ActivityEvent, label: &str, detail: &str) ->
 
     // Keep project's last_activity in sync with the newest activity timestamp.
     // This is best-effort metadata update and should not fail activity logging.
+    // Locked projects are never rewritten, not even for this timestamp.
     if let Ok(raw) = crate::core::read_project(project) {
         if let Ok(mut parsed) = serde_json::from_str::<crate::core::Project>(&raw) {
+            if parsed.locked {
+                return Ok(());
+            }
             parsed.last_activity = Some(ts);
             if let Ok(updated) = serde_json::to_string_pretty(&parsed) {
                 let _ = crate::core::save_project(project, &updated);
@@ -303,6 +317,40 @@ pub fn get_all_activity(limit: usize) -> Result<Vec<ActivityEntry>, String> {
     Ok(entries)
 }
 
+/// Return the timestamp of the most recent `event` for every project that has
+/// one, keyed by project name.
+pub fn last_event_times(event: ActivityEvent) -> Result<HashMap<String, String>, String> {
+    let conn = open_conn()?;
+    last_event_times_in(&conn, event)
+}
+
+fn last_event_times_in(
+    conn: &Connection,
+    event: ActivityEvent,
+) -> Result<HashMap<String, String>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT project, MAX(timestamp)
+             FROM activity
+             WHERE event = ?1
+             GROUP BY project",
+        )
+        .map_err(|e| format!("Failed to prepare last-event query: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![event.as_str()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| format!("Failed to query last events: {}", e))?;
+
+    let mut times = HashMap::new();
+    for row in rows {
+        let (project, ts) = row.map_err(|e| format!("Failed to read activity row: {}", e))?;
+        times.insert(project, ts);
+    }
+    Ok(times)
+}
+
 // ── Path-injectable helpers used by tests ─────────────────────────────────────
 
 #[cfg(test)]
@@ -425,6 +473,21 @@ mod tests {
         assert_eq!(entries[2].event, "memory_stored");
     }
 
+    #[test]
+    fn last_event_times_keeps_the_newest_per_project() {
+        let dir = tempdir().unwrap();
+        let conn = fresh_conn(dir.path());
+
+        insert_into(&conn, "a", ActivityEvent::ProjectSynced, "", "").unwrap();
+        let newest = insert_into(&conn, "a", ActivityEvent::ProjectSynced, "", "").unwrap();
+        insert_into(&conn, "a", ActivityEvent::MemoryStored, "", "").unwrap();
+        insert_into(&conn, "b", ActivityEvent::MemoryStored, "", "").unwrap();
+
+        let times = last_event_times_in(&conn, ActivityEvent::ProjectSynced).unwrap();
+        assert_eq!(times.len(), 1);
+        assert_eq!(times["a"], newest);
+    }
+
     // ── event kind strings ───────────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,197 @@
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{sync_individual_skills, Agent, AgentCapabilities};
+
+/// Aider agent — uses `CONVENTIONS.md` as the project conventions file and
+/// stores skills under `<project>/.agents/skills/<name>/SKILL.md`.
+///
+/// Aider reads conventions only when they are loaded as a read-only file,
+/// usually with `read: CONVENTIONS.md` in `.aider.conf.yml`.  Automatic writes
+/// the conventions file but leaves `.aider.conf.yml` to the user.
+///
+/// **MCP note**: Aider does not speak MCP, so there is no server config for
+/// Automatic to write.
+pub struct Aider;
+
+impl Agent for Aider {
+    // ── Identity ────────────────────────────────────────────────────────
+
+    fn id(&self) -> &'static str {
+        "aider"
+    }
+
+    fn label(&self) -> &'static str {
+        "Aider (Beta)"
+    }
+
+    fn config_description(&self) -> &'static str {
+        "CONVENTIONS.md (no MCP support)"
+    }
+
+    fn project_file_name(&self) -> &'static str {
+        "CONVENTIONS.md"
+    }
+
+    // ── Detection ───────────────────────────────────────────────────────
+
+    fn detect_global_install(&self) -> bool {
+        super::cli_available("aider")
+            || super::home_dir()
+                .map(|h| h.join(".aider.conf.yml").exists())
+                .unwrap_or(false)
+    }
+
+    fn detect_in(&self, dir: &Path) -> bool {
+        // `.aider.conf.yml`, or any of the `.aider*` files Aider leaves in
+        // a repo it has run in (chat history, input history, tags cache).
+        // CONVENTIONS.md alone is too generic a name to count.
+        if dir.join(".aider.conf.yml").exists() {
+            return true;
+        }
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .any(|e| e.file_name().to_string_lossy().starts_with(".aider"))
+            })
+            .unwrap_or(false)
+    }
+
+    fn skill_dirs(&self, dir: &Path) -> Vec<PathBuf> {
+        vec![dir.join(".agents").join("skills")]
+    }
+
+    // ── Capabilities ────────────────────────────────────────────────────
+
+    fn capabilities(&self) -> AgentCapabilities {
+        AgentCapabilities {
+            mcp_servers: false,
+            agents: false,
+            ..Default::default()
+        }
+    }
+
+    // ── MCP note ────────────────────────────────────────────────────────
+
+    fn mcp_note(&self) -> Option<&'static str> {
+        Some(
+            "Aider does not use MCP servers, so Automatic has no MCP config to write for it. \
+             Project conventions and skills are still synced.",
+        )
+    }
+
+    // ── Config writing ──────────────────────────────────────────────────
+
+    /// Aider has no MCP config.  This is intentionally a no-op.
+    fn write_mcp_config(
+        &self,
+        _dir: &Path,
+        _servers: &Map<String, Value>,
+    ) -> Result<String, String> {
+        Ok(String::new())
+    }
+
+    fn sync_skills(
+        &self,
+        dir: &Path,
+        skill_contents: &[(String, String)],
+        selected_names: &[String],
+        local_skill_names: &[String],
+    ) -> Result<Vec<String>, String> {
+        let mut written = Vec::new();
+        let skills_dir = dir.join(".agents").join("skills");
+        sync_individual_skills(
+            &skills_dir,
+            skill_contents,
+            selected_names,
+            local_skill_names,
+            &mut written,
+        )?;
+        Ok(written)
+    }
+
+    // ── Discovery ───────────────────────────────────────────────────────
+
+    /// Aider has no MCP config — nothing to discover.
+    fn discover_mcp_servers(&self, _dir: &Path) -> Map<String, Value> {
+        Map::new()
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detect_conf_file() {
+        let dir = tempdir().unwrap();
+        assert!(!Aider.detect_in(dir.path()));
+
+        fs::write(dir.path().join(".aider.conf.yml"), "read: CONVENTIONS.md\n").unwrap();
+        assert!(Aider.detect_in(dir.path()));
+    }
+
+    #[test]
+    fn test_detect_aider_history_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".aider.chat.history.md"), "").unwrap();
+        assert!(Aider.detect_in(dir.path()));
+
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join(".aider.tags.cache.v3")).unwrap();
+        assert!(Aider.detect_in(dir.path()));
+    }
+
+    #[test]
+    fn test_conventions_alone_is_not_detected() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("CONVENTIONS.md"), "# Conventions\n").unwrap();
+        assert!(!Aider.detect_in(dir.path()));
+    }
+
+    #[test]
+    fn test_write_mcp_config_is_noop() {
+        let dir = tempdir().unwrap();
+        let mut servers = Map::new();
+        servers.insert(
+            "github".to_string(),
+            serde_json::json!({"command": "npx", "args": ["@modelcontextprotocol/server-github"]}),
+        );
+
+        let result = Aider.write_mcp_config(dir.path(), &servers);
+        assert_eq!(result.unwrap(), "");
+
+        // No files should have been written
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_mcp_note_and_capabilities() {
+        assert!(Aider.mcp_note().is_some());
+        let caps = Aider.capabilities();
+        assert!(!caps.mcp_servers);
+        assert!(caps.instructions && caps.skills);
+    }
+
+    #[test]
+    fn test_skill_sync() {
+        let dir = tempdir().unwrap();
+        let skills = vec![("my-skill".to_string(), "# My Skill\n".to_string())];
+        let selected = vec!["my-skill".to_string()];
+
+        let written = Aider
+            .sync_skills(dir.path(), &skills, &selected, &[])
+            .unwrap();
+        assert_eq!(written.len(), 1);
+
+        let content =
+            fs::read_to_string(dir.path().join(".agents/skills/my-skill/SKILL.md")).unwrap();
+        assert_eq!(content, "# My Skill\n");
+    }
+}
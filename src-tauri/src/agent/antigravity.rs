@@ -1,7 +1,7 @@
-use serde_json::{Map, Value};
+use serde_json::{json, Map, Value};
 use std::path::{Path, PathBuf};
 
-use super::{sync_individual_skills, Agent};
+use super::{discover_mcp_servers_from_json, sync_individual_skills, Agent};
 
 /// Google Antigravity agent — stores skills under
 /// `<project>/.agents/skills/<name>/SKILL.md`.
@@ -17,7 +17,9 @@ use super::{sync_individual_skills, Agent};
 /// decision, or by glob pattern.  Rule syncing is not currently supported by
 /// Automatic.
 ///
-/// Note: `.agent/rules/` is retained for backward compatibility.
+/// Note: `.agent/rules/` is retained for backward compatibility, and a
+/// `.agent/rules/` or `.agent/workflows/` directory marks a project as using
+/// Antigravity.
 ///
 /// ## Skills
 ///
@@ -25,25 +27,33 @@ use super::{sync_individual_skills, Agent};
 /// Global skills:    `~/.gemini/antigravity/skills/<name>/SKILL.md`
 ///                   (not synced by Automatic — managed globally)
 ///
-/// Note: `.agent/skills/` is retained for backward compatibility.
+/// Note: `.agent/skills/` is retained for backward compatibility and is
+/// still scanned during autodetection.
+/// See <https://antigravity.google/docs/skills>.
 ///
 /// ## MCP config
 ///
 /// Antigravity manages MCP servers globally through its own UI:
 /// Agent session → "…" → MCP Servers → Manage MCP Servers → View raw config.
-/// The config file is `mcp_config.json` in Antigravity's application data
-/// directory.  There is no project-scoped MCP config file.
+/// The raw config is `~/.gemini/antigravity/mcp_config.json`, shared by every
+/// workspace, so Automatic reads it during global discovery but never writes
+/// it.  There is no project-scoped MCP config file, and Automatic has never
+/// written one for Antigravity, so there is nothing to migrate.
+/// See <https://antigravity.google/docs/mcp>.
 ///
-/// Format uses `mcpServers` with standard stdio entries (no explicit `type`):
+/// Format uses `mcpServers` with standard stdio entries (no explicit `type`);
+/// remote servers use `serverUrl` instead of `url`:
 /// ```json
 /// { "mcpServers": { "my-server": { "command": "npx", "args": ["-y", "..."] } } }
 /// ```
 ///
-/// The exact filesystem path of `mcp_config.json` is pending documentation.
-/// Once confirmed, `write_mcp_config` and `discover_global_mcp_servers` should
-/// be updated to read/write that path directly.
+/// [`Agent::mcp_config_snippet`] produces the project's servers in this
+/// format for pasting into the raw config.
 pub struct Antigravity;
 
+/// Antigravity's MCP config, relative to the home directory.
+const GLOBAL_MCP_CONFIG: [&str; 3] = [".gemini", "antigravity", "mcp_config.json"];
+
 impl Agent for Antigravity {
     // ── Identity ────────────────────────────────────────────────────────
 
@@ -83,22 +93,48 @@ impl Agent for Antigravity {
         Some(
             "Antigravity manages MCP servers via its own UI: Agent session \u{2192} \u{22ef} \u{2192} \
              MCP Servers \u{2192} Manage MCP Servers \u{2192} View raw config. \
-             Automatic cannot write Antigravity\u{2019}s mcp_config.json.",
+             Automatic cannot write Antigravity\u{2019}s global mcp_config.json \u{2014} copy the \
+             project\u{2019}s servers and paste them into the raw config.",
         )
     }
 
+    /// The `mcpServers` object Antigravity's raw config editor accepts.
+    fn mcp_config_snippet(&self, servers: &Map<String, Value>) -> Option<String> {
+        let mut entries = Map::new();
+        for (name, config) in servers {
+            let mut server = config.clone();
+            if let Some(obj) = server.as_object_mut() {
+                obj.remove("type");
+                obj.remove("enabled");
+                obj.remove("timeout");
+                obj.remove("oauth");
+                if let Some(url) = obj.remove("url") {
+                    obj.insert("serverUrl".to_string(), url);
+                }
+            }
+            entries.insert(name.clone(), server);
+        }
+        serde_json::to_string_pretty(&json!({ "mcpServers": entries })).ok()
+    }
+
     // ── Detection ───────────────────────────────────────────────────────
 
     fn detect_in(&self, dir: &Path) -> bool {
         // GEMINI.md is shared with Gemini CLI so we cannot use it as a sole
         // marker — a Gemini-specific indicator must also be present.
         // The .antigravity/ directory is created by the Antigravity app itself
-        // and is the most reliable project-level signal.
+        // and is the most reliable project-level signal; workspace rules and
+        // workflows under .agent/ are Antigravity's own layout.
         dir.join(".antigravity").is_dir()
+            || dir.join(".agent").join("rules").is_dir()
+            || dir.join(".agent").join("workflows").is_dir()
     }
 
     fn skill_dirs(&self, dir: &Path) -> Vec<PathBuf> {
-        vec![dir.join(".agents").join("skills")]
+        vec![
+            dir.join(".agents").join("skills"),
+            dir.join(".agent").join("skills"),
+        ]
     }
 
     // ── Cleanup ─────────────────────────────────────────────────────────
@@ -150,11 +186,25 @@ impl Agent for Antigravity {
     }
 
     fn discover_global_mcp_servers(&self) -> Map<String, Value> {
-        // Antigravity stores its MCP config at a platform-specific app data
-        // path under the name mcp_config.json.  The exact path is not
-        // publicly documented; discovery is not implemented.
-        Map::new()
+        let Some(home) = super::home_dir() else {
+            return Map::new();
+        };
+        let path = GLOBAL_MCP_CONFIG
+            .iter()
+            .fold(home, |path, part| path.join(part));
+        discover_mcp_servers_from_json(&path, "mcpServers", normalise_import)
+    }
+}
+
+/// Antigravity names a remote server's endpoint `serverUrl`.
+pub(super) fn normalise_import(mut config: Value) -> Value {
+    if let Some(obj) = config.as_object_mut() {
+        if let Some(url) = obj.remove("serverUrl") {
+            obj.insert("url".to_string(), url);
+            obj.entry("type").or_insert_with(|| json!("http"));
+        }
     }
+    config
 }
 
 // ── Tests ───────────────────────────────────────────────────────────────────
@@ -203,6 +253,44 @@ mod tests {
         assert!(entries.is_empty(), "no files should be written");
     }
 
+    #[test]
+    fn test_detect_on_workspace_rules() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".agent/workflows")).unwrap();
+        assert!(Antigravity.detect_in(dir.path()));
+    }
+
+    #[test]
+    fn snippet_uses_antigravity_field_names() {
+        let mut servers = Map::new();
+        servers.insert(
+            "github".to_string(),
+            json!({"type": "stdio", "command": "npx", "args": ["-y", "server-github"]}),
+        );
+        servers.insert(
+            "remote".to_string(),
+            json!({"type": "http", "url": "https://api.example.com/mcp", "oauth": {"scope": "read"}}),
+        );
+
+        let snippet = Antigravity.mcp_config_snippet(&servers).unwrap();
+        let parsed: Value = serde_json::from_str(&snippet).unwrap();
+        assert_eq!(
+            parsed["mcpServers"]["github"],
+            json!({"command": "npx", "args": ["-y", "server-github"]})
+        );
+        assert_eq!(
+            parsed["mcpServers"]["remote"],
+            json!({"serverUrl": "https://api.example.com/mcp"})
+        );
+
+        // Importing the pasted config gives back the canonical form.
+        let imported = normalise_import(parsed["mcpServers"]["remote"].clone());
+        assert_eq!(
+            imported,
+            json!({"type": "http", "url": "https://api.example.com/mcp"})
+        );
+    }
+
     #[test]
     fn test_discover_mcp_servers_always_empty() {
         let dir = tempdir().unwrap();
@@ -1,7 +1,10 @@
 use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::core::ClaudeMcpScope;
+
 use super::{discover_mcp_servers_from_json, sync_individual_skills, Agent};
 
 /// Claude Code agent — writes `.mcp.json` and stores skills under
@@ -61,39 +64,60 @@ impl Agent for ClaudeCode {
         vec![dir.join(".mcp.json")]
     }
 
+    fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
+        let mut removed = Vec::new();
+        let mcp_json = dir.join(".mcp.json");
+        if mcp_json.exists() && fs::remove_file(&mcp_json).is_ok() {
+            removed.push(mcp_json.display().to_string());
+        }
+        // Local-scope servers Automatic wrote into ~/.claude.json.
+        if let (Ok(claude_json), Ok(ledger)) = (claude_json_path(), local_scope_ledger_path()) {
+            match write_local_servers_at(&claude_json, &ledger, dir, &Map::new()) {
+                Ok(Some(path)) => removed.push(path),
+                Ok(None) => {}
+                Err(e) => eprintln!("[automatic] claude local MCP cleanup: {}", e),
+            }
+        }
+        removed
+    }
+
+    fn cleanup_mcp_preview(&self, dir: &Path) -> Vec<String> {
+        let mut preview: Vec<String> = self
+            .owned_config_paths(dir)
+            .into_iter()
+            .filter(|p| p.exists())
+            .map(|p| p.display().to_string())
+            .collect();
+        if let (Ok(claude_json), Ok(ledger)) = (claude_json_path(), local_scope_ledger_path()) {
+            if !managed_local_servers_at(&ledger, dir).is_empty() {
+                preview.push(claude_json.display().to_string());
+            }
+        }
+        preview
+    }
+
     // ── Config writing ──────────────────────────────────────────────────
 
     fn write_mcp_config(&self, dir: &Path, servers: &Map<String, Value>) -> Result<String, String> {
         // Claude Code uses Automatic's JSON format directly, with one tweak:
         // strip "type" from stdio entries for Claude Desktop backward-compat.
-        let mut claude_servers = Map::new();
-
-        for (name, config) in servers {
-            let transport = config
-                .get("type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("stdio");
-
-            let mut server = config.clone();
-            if let Some(obj) = server.as_object_mut() {
-                if transport == "stdio" {
-                    obj.remove("type");
-                    obj.remove("enabled");
-                    obj.remove("timeout");
-                }
-            }
-            claude_servers.insert(name.clone(), server);
-        }
+        let claude_servers: Map<String, Value> = servers
+            .iter()
+            .map(|(name, config)| (name.clone(), claude_server_entry(config)))
+            .collect();
 
         let output = json!({ "mcpServers": Value::Object(claude_servers) });
         let path = dir.join(".mcp.json");
-        let content =
-            serde_json::to_string_pretty(&output).map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&output)?;
         fs::write(&path, content).map_err(|e| format!("Failed to write .mcp.json: {}", e))?;
 
         Ok(path.display().to_string())
     }
 
+    fn annotated_config_path(&self, dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(".mcp.json"))
+    }
+
     fn sync_skills(
         &self,
         dir: &Path,
@@ -116,12 +140,19 @@ impl Agent for ClaudeCode {
     // ── Discovery ───────────────────────────────────────────────────────
 
     fn discover_mcp_servers(&self, dir: &Path) -> Map<String, Value> {
+        // Local-scope servers from ~/.claude.json first, so a project-scope
+        // entry with the same name wins.
+        let mut servers = discover_claude_local_servers(dir);
         let path = dir.join(".mcp.json");
-        if !path.exists() {
-            return Map::new();
+        if path.exists() {
+            // Claude's format is already canonical — no normalisation needed.
+            servers.extend(discover_mcp_servers_from_json(
+                &path,
+                "mcpServers",
+                identity,
+            ));
         }
-        // Claude's format is already canonical — no normalisation needed.
-        discover_mcp_servers_from_json(&path, "mcpServers", identity)
+        servers
     }
 
     fn detect_global_install(&self) -> bool {
@@ -170,6 +201,256 @@ fn identity(v: Value) -> Value {
     v
 }
 
+/// Claude Code's form of a canonical server config: stdio entries drop
+/// `type` (for Claude Desktop backward-compat) and the fields Claude does
+/// not understand.
+fn claude_server_entry(config: &Value) -> Value {
+    let transport = config
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("stdio");
+
+    let mut server = config.clone();
+    if let Some(obj) = server.as_object_mut() {
+        if transport == "stdio" {
+            obj.remove("type");
+            obj.remove("enabled");
+            obj.remove("timeout");
+        }
+    }
+    server
+}
+
+// ── Local-scope MCP servers ─────────────────────────────────────────────────
+//
+// Claude Code keeps per-user ("local scope") servers in `~/.claude.json`
+// under `projects["<abs project path>"].mcpServers`, out of the checked-in
+// `.mcp.json`.  Servers a project marks `local` are written there.  The file
+// belongs to Claude Code and holds far more than MCP config, so it is merged
+// rather than rewritten, and a ledger in `~/.automatic` records which entries
+// Automatic wrote so cleanup never strips servers the user added with
+// `claude mcp add`.
+
+/// Ledger of local-scope servers Automatic wrote, keyed by project path.
+const LOCAL_SCOPE_LEDGER: &str = "claude_local_mcp.json";
+
+type LocalScopeLedger = BTreeMap<String, Vec<String>>;
+
+fn claude_json_path() -> Result<PathBuf, String> {
+    super::home_dir()
+        .map(|h| h.join(".claude.json"))
+        .ok_or_else(|| "Could not determine home directory".to_string())
+}
+
+fn local_scope_ledger_path() -> Result<PathBuf, String> {
+    Ok(crate::core::get_automatic_dir()?.join(LOCAL_SCOPE_LEDGER))
+}
+
+/// The key Claude Code files a project under in `~/.claude.json`.
+fn project_key(dir: &Path) -> String {
+    dir.display().to_string()
+}
+
+/// Split `servers` into `(project scope, local scope)` according to `scopes`.
+pub fn split_claude_servers_by_scope(
+    servers: &Map<String, Value>,
+    scopes: &HashMap<String, ClaudeMcpScope>,
+) -> (Map<String, Value>, Map<String, Value>) {
+    servers
+        .iter()
+        .map(|(name, config)| (name.clone(), config.clone()))
+        .partition(|(name, _)| scopes.get(name) != Some(&ClaudeMcpScope::Local))
+}
+
+/// Write the project's servers for Claude Code: project-scope ones to
+/// `.mcp.json`, local-scope ones into `~/.claude.json`.  With no local-scope
+/// servers (and none written before) `~/.claude.json` is not touched.
+/// Returns the paths written.
+pub fn write_claude_scoped_mcp_config(
+    dir: &Path,
+    servers: &Map<String, Value>,
+    scopes: &HashMap<String, ClaudeMcpScope>,
+) -> Result<Vec<String>, String> {
+    let (project, local) = split_claude_servers_by_scope(servers, scopes);
+    let mut written = vec![ClaudeCode.write_mcp_config(dir, &project)?];
+    if let Some(path) = write_local_servers_at(
+        &claude_json_path()?,
+        &local_scope_ledger_path()?,
+        dir,
+        &local,
+    )? {
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Local-scope servers Claude Code has for `dir`, whoever added them.
+pub fn discover_claude_local_servers(dir: &Path) -> Map<String, Value> {
+    claude_json_path()
+        .map(|path| read_local_servers_at(&path, dir))
+        .unwrap_or_default()
+}
+
+/// Local-scope servers for `dir` that Automatic wrote.
+pub fn managed_claude_local_servers(dir: &Path) -> Vec<String> {
+    local_scope_ledger_path()
+        .map(|ledger| managed_local_servers_at(&ledger, dir))
+        .unwrap_or_default()
+}
+
+/// `(expected, actual)` local-scope entries for `dir`: what a sync would
+/// write and what `~/.claude.json` currently holds for those servers plus
+/// any Automatic wrote earlier.  Equal maps mean no drift.
+pub fn claude_local_scope_state(
+    dir: &Path,
+    servers: &Map<String, Value>,
+    scopes: &HashMap<String, ClaudeMcpScope>,
+) -> (Map<String, Value>, Map<String, Value>) {
+    match (claude_json_path(), local_scope_ledger_path()) {
+        (Ok(claude_json), Ok(ledger)) => {
+            local_scope_state_at(&claude_json, &ledger, dir, servers, scopes)
+        }
+        _ => (Map::new(), Map::new()),
+    }
+}
+
+fn local_scope_state_at(
+    claude_json: &Path,
+    ledger: &Path,
+    dir: &Path,
+    servers: &Map<String, Value>,
+    scopes: &HashMap<String, ClaudeMcpScope>,
+) -> (Map<String, Value>, Map<String, Value>) {
+    let (_, local) = split_claude_servers_by_scope(servers, scopes);
+    let expected: Map<String, Value> = local
+        .iter()
+        .map(|(name, config)| (name.clone(), claude_server_entry(config)))
+        .collect();
+
+    let managed = managed_local_servers_at(ledger, dir);
+    let actual = read_local_servers_at(claude_json, dir)
+        .into_iter()
+        .filter(|(name, _)| expected.contains_key(name) || managed.contains(name))
+        .collect();
+    (expected, actual)
+}
+
+fn read_local_servers_at(claude_json: &Path, dir: &Path) -> Map<String, Value> {
+    let Ok(root) = read_json_object(claude_json) else {
+        return Map::new();
+    };
+    let Some(servers) = root
+        .get("projects")
+        .and_then(|p| p.get(project_key(dir)))
+        .and_then(|p| p.get("mcpServers"))
+        .and_then(|s| s.as_object())
+    else {
+        return Map::new();
+    };
+    servers
+        .iter()
+        // Same exclusions as discover_mcp_servers_from_json.
+        .filter(|(name, _)| !crate::core::is_managed_name(name) && crate::core::is_valid_name(name))
+        .map(|(name, config)| (name.clone(), config.clone()))
+        .collect()
+}
+
+fn managed_local_servers_at(ledger: &Path, dir: &Path) -> Vec<String> {
+    read_ledger(ledger)
+        .remove(&project_key(dir))
+        .unwrap_or_default()
+}
+
+/// Make `local` the set of Automatic-managed local-scope servers for `dir`
+/// in `claude_json`: entries written earlier but no longer wanted are
+/// removed, everything else in the file is preserved.  Returns the file's
+/// path when it changed.
+fn write_local_servers_at(
+    claude_json: &Path,
+    ledger_path: &Path,
+    dir: &Path,
+    local: &Map<String, Value>,
+) -> Result<Option<String>, String> {
+    let key = project_key(dir);
+    let mut ledger = read_ledger(ledger_path);
+    let previous = ledger.remove(&key).unwrap_or_default();
+    if local.is_empty() && previous.is_empty() {
+        return Ok(None);
+    }
+
+    let original = read_json_object(claude_json)?;
+    let mut root = original.clone();
+    if let Some(servers) = root
+        .get_mut("projects")
+        .and_then(|p| p.get_mut(&key))
+        .and_then(|p| p.get_mut("mcpServers"))
+        .and_then(|s| s.as_object_mut())
+    {
+        for name in &previous {
+            servers.remove(name);
+        }
+    }
+    if !local.is_empty() {
+        let project = root
+            .as_object_mut()
+            .ok_or("~/.claude.json is not a JSON object")?
+            .entry("projects")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or("~/.claude.json: `projects` is not an object")?
+            .entry(key.clone())
+            .or_insert_with(|| json!({}));
+        let servers = project
+            .as_object_mut()
+            .ok_or("~/.claude.json: project entry is not an object")?
+            .entry("mcpServers")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or("~/.claude.json: `mcpServers` is not an object")?;
+        for (name, config) in local {
+            servers.insert(name.clone(), claude_server_entry(config));
+        }
+        ledger.insert(key, local.keys().cloned().collect());
+    }
+
+    write_ledger(ledger_path, &ledger)?;
+    if root == original {
+        return Ok(None);
+    }
+    let content = serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?;
+    fs::write(claude_json, content)
+        .map_err(|e| format!("Failed to write {}: {}", claude_json.display(), e))?;
+    Ok(Some(claude_json.display().to_string()))
+}
+
+/// Read a JSON object file; a missing file reads as `{}`.  A malformed file
+/// is an error so it is never overwritten.
+fn read_json_object(path: &Path) -> Result<Value, String> {
+    match fs::read_to_string(path) {
+        Ok(raw) => match serde_json::from_str::<Value>(&raw) {
+            Ok(value @ Value::Object(_)) => Ok(value),
+            _ => Err(format!("{} is not a JSON object", path.display())),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(json!({})),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn read_ledger(path: &Path) -> LocalScopeLedger {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_ledger(path: &Path, ledger: &LocalScopeLedger) -> Result<(), String> {
+    if ledger.is_empty() && !path.exists() {
+        return Ok(());
+    }
+    let content = serde_json::to_string_pretty(ledger).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -408,4 +689,178 @@ mod tests {
             "local-scoped server must not be imported globally"
         );
     }
+
+    // ── Local-scope servers ─────────────────────────────────────────────────
+
+    const PROJECT: &str = "/work/shop";
+
+    /// A `~/.claude.json` with an unrelated project, a user-added local
+    /// server in our project, and unrelated top-level state.
+    fn claude_json_fixture(home: &Path) -> PathBuf {
+        let path = home.join(".claude.json");
+        let fixture = json!({
+            "numStartups": 42,
+            "mcpServers": { "user-tool": { "command": "user-tool" } },
+            "projects": {
+                "/work/other": {
+                    "allowedTools": ["Bash"],
+                    "mcpServers": { "other-db": { "command": "other" } }
+                },
+                PROJECT: {
+                    "history": [{ "display": "hello" }],
+                    "mcpServers": { "my-notes": { "command": "notes" } }
+                }
+            }
+        });
+        fs::write(&path, serde_json::to_string_pretty(&fixture).unwrap()).unwrap();
+        path
+    }
+
+    fn local_db() -> Map<String, Value> {
+        let mut s = Map::new();
+        s.insert(
+            "db-inspector".to_string(),
+            json!({"type":"stdio","command":"db-inspector","env":{"DB_PASSWORD":"hunter2"}}),
+        );
+        s
+    }
+
+    fn read(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn servers_are_split_by_scope() {
+        let mut servers = stdio_servers();
+        servers.extend(local_db());
+        let scopes = HashMap::from([
+            ("db-inspector".to_string(), ClaudeMcpScope::Local),
+            ("github".to_string(), ClaudeMcpScope::Project),
+        ]);
+        let (project, local) = split_claude_servers_by_scope(&servers, &scopes);
+        assert!(project.contains_key("automatic") && project.contains_key("github"));
+        assert_eq!(local.keys().collect::<Vec<_>>(), vec!["db-inspector"]);
+    }
+
+    #[test]
+    fn local_servers_are_merged_without_disturbing_other_content() {
+        let home = tempdir().unwrap();
+        let claude_json = claude_json_fixture(home.path());
+        let ledger = home.path().join(LOCAL_SCOPE_LEDGER);
+
+        let changed =
+            write_local_servers_at(&claude_json, &ledger, Path::new(PROJECT), &local_db()).unwrap();
+        assert_eq!(changed, Some(claude_json.display().to_string()));
+
+        let root = read(&claude_json);
+        let ours = &root["projects"][PROJECT];
+        assert_eq!(
+            ours["mcpServers"]["db-inspector"]["command"],
+            "db-inspector"
+        );
+        assert!(ours["mcpServers"]["db-inspector"]["type"].is_null());
+        assert_eq!(ours["mcpServers"]["my-notes"]["command"], "notes");
+        assert_eq!(ours["history"][0]["display"], "hello");
+        assert_eq!(
+            root["projects"]["/work/other"]["mcpServers"]["other-db"]["command"],
+            "other"
+        );
+        assert_eq!(root["projects"]["/work/other"]["allowedTools"][0], "Bash");
+        assert_eq!(root["numStartups"], 42);
+        assert_eq!(root["mcpServers"]["user-tool"]["command"], "user-tool");
+        assert_eq!(
+            managed_local_servers_at(&ledger, Path::new(PROJECT)),
+            vec!["db-inspector".to_string()]
+        );
+
+        // Writing the same servers again leaves the file alone.
+        assert_eq!(
+            write_local_servers_at(&claude_json, &ledger, Path::new(PROJECT), &local_db()),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn cleanup_strips_only_managed_entries() {
+        let home = tempdir().unwrap();
+        let claude_json = claude_json_fixture(home.path());
+        let ledger = home.path().join(LOCAL_SCOPE_LEDGER);
+        let dir = Path::new(PROJECT);
+        write_local_servers_at(&claude_json, &ledger, dir, &local_db()).unwrap();
+
+        let changed = write_local_servers_at(&claude_json, &ledger, dir, &Map::new()).unwrap();
+        assert!(changed.is_some());
+        let root = read(&claude_json);
+        let servers = root["projects"][PROJECT]["mcpServers"].as_object().unwrap();
+        assert!(!servers.contains_key("db-inspector"));
+        assert_eq!(servers["my-notes"]["command"], "notes");
+        assert!(managed_local_servers_at(&ledger, dir).is_empty());
+
+        // Nothing managed any more: the file is not touched again.
+        assert_eq!(
+            write_local_servers_at(&claude_json, &ledger, dir, &Map::new()),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn nothing_is_written_without_local_servers() {
+        let home = tempdir().unwrap();
+        let claude_json = home.path().join(".claude.json");
+        let ledger = home.path().join(LOCAL_SCOPE_LEDGER);
+        assert_eq!(
+            write_local_servers_at(&claude_json, &ledger, Path::new(PROJECT), &Map::new()),
+            Ok(None)
+        );
+        assert!(!claude_json.exists());
+        assert!(!ledger.exists());
+    }
+
+    #[test]
+    fn malformed_claude_json_is_left_alone() {
+        let home = tempdir().unwrap();
+        let claude_json = home.path().join(".claude.json");
+        fs::write(&claude_json, "{ not json").unwrap();
+        let ledger = home.path().join(LOCAL_SCOPE_LEDGER);
+        assert!(
+            write_local_servers_at(&claude_json, &ledger, Path::new(PROJECT), &local_db()).is_err()
+        );
+        assert_eq!(fs::read_to_string(&claude_json).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn local_servers_are_discovered_per_project() {
+        let home = tempdir().unwrap();
+        let claude_json = claude_json_fixture(home.path());
+        let found = read_local_servers_at(&claude_json, Path::new(PROJECT));
+        assert_eq!(found.keys().collect::<Vec<_>>(), vec!["my-notes"]);
+        assert!(read_local_servers_at(&claude_json, Path::new("/work/none")).is_empty());
+    }
+
+    #[test]
+    fn local_scope_state_reports_drift() {
+        let home = tempdir().unwrap();
+        let claude_json = claude_json_fixture(home.path());
+        let ledger = home.path().join(LOCAL_SCOPE_LEDGER);
+        let dir = Path::new(PROJECT);
+        let scopes = HashMap::from([("db-inspector".to_string(), ClaudeMcpScope::Local)]);
+        let mut servers = stdio_servers();
+        servers.extend(local_db());
+
+        let (expected, actual) =
+            local_scope_state_at(&claude_json, &ledger, dir, &servers, &scopes);
+        assert!(actual.is_empty());
+        assert_eq!(expected.keys().collect::<Vec<_>>(), vec!["db-inspector"]);
+
+        write_local_servers_at(&claude_json, &ledger, dir, &local_db()).unwrap();
+        let (expected, actual) =
+            local_scope_state_at(&claude_json, &ledger, dir, &servers, &scopes);
+        assert_eq!(expected, actual);
+
+        // Switched back to project scope: the managed entry is now stale.
+        let (expected, actual) =
+            local_scope_state_at(&claude_json, &ledger, dir, &servers, &HashMap::new());
+        assert!(expected.is_empty());
+        assert!(actual.contains_key("db-inspector"));
+    }
 }
@@ -9,7 +9,8 @@ use super::{discover_mcp_servers_from_json, sync_individual_skills, Agent};
 ///
 /// Cline's project-level instructions live in `.clinerules` (a file or
 /// directory at the project root).  Global rules live in
-/// `~/Documents/Cline/Rules/`.  Automatic syncs skills to `.cline/skills/`.
+/// `~/Documents/Cline/Rules/`.  Automatic syncs skills to `.cline/skills/`,
+/// and writes one file per rule when `.clinerules` is a directory.
 pub struct Cline;
 
 impl Agent for Cline {
@@ -92,13 +93,16 @@ impl Agent for Cline {
         }
 
         let path = cline_dir.join("mcp.json");
-        let content =
-            serde_json::to_string_pretty(&output).map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&output)?;
         fs::write(&path, content).map_err(|e| format!("Failed to write .cline/mcp.json: {}", e))?;
 
         Ok(path.display().to_string())
     }
 
+    fn annotated_config_path(&self, dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(".cline").join("mcp.json"))
+    }
+
     fn sync_skills(
         &self,
         dir: &Path,
@@ -118,6 +122,19 @@ impl Agent for Cline {
         Ok(written)
     }
 
+    // ── Native rules ────────────────────────────────────────────────────
+
+    fn rules_dir(&self, dir: &Path) -> Option<PathBuf> {
+        // A plain `.clinerules` file already carries the rules section; only
+        // the directory form takes one file per rule.
+        let rules_dir = dir.join(".clinerules");
+        rules_dir.is_dir().then_some(rules_dir)
+    }
+
+    fn write_rules(&self, dir: &Path, rules: &[(String, String)]) -> Result<Vec<String>, String> {
+        super::sync_rule_files(self, dir, rules)
+    }
+
     // ── Discovery ───────────────────────────────────────────────────────
 
     fn discover_mcp_servers(&self, dir: &Path) -> Map<String, Value> {
@@ -233,6 +250,22 @@ mod tests {
         assert!(Cline.detect_in(dir.path()));
     }
 
+    #[test]
+    fn test_rules_written_only_into_clinerules_directory() {
+        let dir = tempdir().unwrap();
+        let rules = vec![("style".to_string(), "Use tabs.".to_string())];
+        fs::write(dir.path().join(".clinerules"), "# Rules\n").unwrap();
+        assert!(Cline.write_rules(dir.path(), &rules).unwrap().is_empty());
+
+        fs::remove_file(dir.path().join(".clinerules")).unwrap();
+        fs::create_dir(dir.path().join(".clinerules")).unwrap();
+        Cline.write_rules(dir.path(), &rules).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".clinerules/automatic-style.md")).unwrap(),
+            "Use tabs.\n"
+        );
+    }
+
     #[test]
     fn test_write_stdio() {
         let dir = tempdir().unwrap();
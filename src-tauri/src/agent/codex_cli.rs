@@ -48,7 +48,9 @@ impl Agent for CodexCli {
 
         let mut toml_content = String::new();
 
-        for (name, config) in servers {
+        // Sorted so the generated TOML is byte-identical between runs.
+        let sorted = crate::core::sort_json_keys(Value::Object(servers.clone()));
+        for (name, config) in sorted.as_object().into_iter().flatten() {
             let config = config.clone();
             let transport = config
                 .get("type")
@@ -248,7 +250,7 @@ fn discover_codex_global_config(path: &std::path::Path) -> Map<String, Value> {
     };
 
     for (name, entry) in servers {
-        if !crate::core::is_valid_name(name) || name == "automatic" || name == "nexus" {
+        if !crate::core::is_valid_name(name) || crate::core::is_managed_name(name) {
             continue;
         }
         let table = match entry.as_table() {
@@ -480,6 +482,24 @@ mod tests {
         assert!(content.contains("Authorization"));
     }
 
+    #[test]
+    fn test_global_discovery_skips_managed_names() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[mcp_servers.automatic]\ncommand = \"automatic\"\n\n\
+             [mcp_servers.nexus]\ncommand = \"nexus\"\n\n\
+             [mcp_servers.github]\ncommand = \"gh-mcp\"\n",
+        )
+        .unwrap();
+
+        let servers = discover_codex_global_config(&path);
+        let names: Vec<&String> = servers.keys().collect();
+        assert_eq!(names, vec!["github"]);
+        assert!(names.iter().all(|n| !crate::core::is_managed_name(n)));
+    }
+
     #[test]
     fn test_toml_merge() {
         let existing =
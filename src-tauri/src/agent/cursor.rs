@@ -39,6 +39,14 @@ impl Agent for Cursor {
         vec![dir.join(".agents").join("skills")]
     }
 
+    // ── Capabilities ────────────────────────────────────────────────────
+
+    fn recommended_max_servers(&self) -> Option<usize> {
+        // Cursor only enables the first 40 tools across all servers; at a
+        // typical five to ten tools each, later servers silently go unused.
+        Some(6)
+    }
+
     // ── Cleanup ─────────────────────────────────────────────────────────
 
     fn owned_config_paths(&self, dir: &Path) -> Vec<PathBuf> {
@@ -77,14 +85,17 @@ impl Agent for Cursor {
         }
 
         let path = cursor_dir.join("mcp.json");
-        let content =
-            serde_json::to_string_pretty(&output).map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&output)?;
         fs::write(&path, content)
             .map_err(|e| format!("Failed to write .cursor/mcp.json: {}", e))?;
 
         Ok(path.display().to_string())
     }
 
+    fn annotated_config_path(&self, dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(".cursor").join("mcp.json"))
+    }
+
     fn sync_skills(
         &self,
         dir: &Path,
@@ -125,17 +136,178 @@ impl Agent for Cursor {
     }
 
     fn discover_global_mcp_servers(&self) -> Map<String, Value> {
-        let Some(home) = super::home_dir() else {
-            return Map::new();
-        };
-        // ~/.cursor/mcp.json — user-level Cursor MCP config
-        let path = home.join(".cursor").join("mcp.json");
-        discover_mcp_servers_from_json(&path, "mcpServers", identity)
+        // ~/.cursor/mcp.json — user-level Cursor MCP config.  Only used by the
+        // import-from-agents flow; per-project autodetect reads the project
+        // scope exclusively via `discover_mcp_servers`.
+        match global_mcp_config_path() {
+            Some(path) => discover_cursor_global_config(&path),
+            None => Map::new(),
+        }
     }
 
     fn agents_dir(&self, dir: &Path) -> Option<PathBuf> {
         Some(dir.join(".cursor").join("agents"))
     }
+
+    // ── Native rules ────────────────────────────────────────────────────
+
+    fn rules_dir(&self, dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(".cursor").join("rules"))
+    }
+
+    fn rule_file(&self, name: &str, content: &str) -> (String, String) {
+        // Project rules in Automatic apply everywhere, so the `.mdc` rule is
+        // always attached rather than left for Cursor to pick by description.
+        (
+            format!("{}{}.mdc", super::MANAGED_RULE_PREFIX, name),
+            format!(
+                "---\ndescription: {}\nalwaysApply: true\n---\n{}\n",
+                name,
+                content.trim_end()
+            ),
+        )
+    }
+
+    fn write_rules(&self, dir: &Path, rules: &[(String, String)]) -> Result<Vec<String>, String> {
+        super::sync_rule_files(self, dir, rules)
+    }
+}
+
+// ── Global scope ────────────────────────────────────────────────────────────
+
+/// A rule file found in Cursor's user-level rules directory that can be
+/// imported as an Automatic rule.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CursorRuleCandidate {
+    /// Suggested Automatic machine name (lowercase slug of the file stem).
+    pub machine_name: String,
+    /// Human-readable display name (the original file stem).
+    pub name: String,
+    /// Rule body with any `.mdc` frontmatter stripped.
+    pub content: String,
+    /// Absolute path of the source file.
+    pub source_path: String,
+}
+
+/// `~/.cursor/mcp.json` — the user-level (global) Cursor MCP config.
+pub fn global_mcp_config_path() -> Option<PathBuf> {
+    super::home_dir().map(|h| h.join(".cursor").join("mcp.json"))
+}
+
+/// `~/.cursor/rules` — the user-level (global) Cursor rules directory.
+pub fn global_rules_dir() -> Option<PathBuf> {
+    super::home_dir().map(|h| h.join(".cursor").join("rules"))
+}
+
+/// Read MCP servers from a Cursor global config file at `path`.
+pub fn discover_cursor_global_config(path: &Path) -> Map<String, Value> {
+    discover_mcp_servers_from_json(path, "mcpServers", identity)
+}
+
+/// List the `.mdc` / `.md` rule files in `dir` as Automatic rule candidates.
+///
+/// Files whose stem cannot be turned into a valid machine name or whose body
+/// is empty are skipped.  Results are sorted by machine name.
+pub fn discover_cursor_global_rules(dir: &Path) -> Vec<CursorRuleCandidate> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut candidates = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let is_rule_file = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e == "mdc" || e == "md");
+        if !is_rule_file {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let machine_name = rule_machine_name(stem);
+        if !crate::core::is_valid_machine_name(&machine_name) {
+            continue;
+        }
+        let Ok(raw) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let (_, body) = super::parse_frontmatter(&raw);
+        let content = body.trim();
+        if content.is_empty() {
+            continue;
+        }
+        candidates.push(CursorRuleCandidate {
+            machine_name,
+            name: stem.to_string(),
+            content: content.to_string(),
+            source_path: path.display().to_string(),
+        });
+    }
+
+    candidates.sort_by(|a, b| a.machine_name.cmp(&b.machine_name));
+    candidates
+}
+
+/// Remove `names` from the `mcpServers` object of the Cursor config at
+/// `path`, writing a timestamped backup of the original file first.
+///
+/// Other top-level keys are preserved.  Returns the backup path, or `None`
+/// when none of the names were present (the file is left untouched).
+pub fn remove_servers_from_cursor_config(
+    path: &Path,
+    names: &[String],
+) -> Result<Option<PathBuf>, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut config: Value = serde_json::from_str(&raw)
+        .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+
+    let Some(servers) = config.get_mut("mcpServers").and_then(|v| v.as_object_mut()) else {
+        return Ok(None);
+    };
+    let before = servers.len();
+    servers.retain(|name, _| !names.contains(name));
+    if servers.len() == before {
+        return Ok(None);
+    }
+
+    let timestamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("mcp.json");
+    let backup = path.with_file_name(format!("{}.{}.bak", file_name, timestamp));
+    fs::write(&backup, &raw).map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    let content = crate::core::to_stable_json(&config)?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(Some(backup))
+}
+
+/// Turn a Cursor rule file stem into an Automatic rule machine name:
+/// lowercase, runs of non-alphanumeric characters collapsed to a single
+/// hyphen, and prefixed with `cursor-` if it would not start with a letter.
+fn rule_machine_name(stem: &str) -> String {
+    let mut slug = String::new();
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-').to_string();
+    if slug.starts_with(|c: char| c.is_ascii_lowercase()) {
+        slug
+    } else {
+        format!("cursor-{}", slug)
+    }
 }
 
 /// Pass-through normaliser: Cursor's format is already canonical.
@@ -231,4 +403,133 @@ mod tests {
             "https://api.example.com/mcp"
         );
     }
+
+    fn write_global_fixture(dir: &Path) -> PathBuf {
+        let path = dir.join("mcp.json");
+        let config = json!({
+            "mcpServers": {
+                "automatic": {"command": "/usr/local/bin/automatic", "args": ["mcp-serve"]},
+                "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]},
+                "linear": {"url": "https://mcp.linear.app/sse"}
+            },
+            "otherSetting": true
+        });
+        fs::write(&path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_discover_global_config() {
+        let dir = tempdir().unwrap();
+        let path = write_global_fixture(dir.path());
+
+        let servers = discover_cursor_global_config(&path);
+        assert_eq!(servers.len(), 2);
+        assert!(servers.contains_key("github"));
+        assert!(servers.contains_key("linear"));
+        // The Automatic server itself is never offered for import.
+        assert!(!servers.contains_key("automatic"));
+    }
+
+    #[test]
+    fn test_project_discovery_ignores_global_scope() {
+        // A project inside the home directory, with `~/.cursor/mcp.json`
+        // holding servers: only the project's own `.cursor/mcp.json` counts.
+        let home = tempdir().unwrap();
+        let global_dir = home.path().join(".cursor");
+        fs::create_dir_all(&global_dir).unwrap();
+        let global = write_global_fixture(&global_dir);
+        assert!(!discover_cursor_global_config(&global).is_empty());
+
+        let project = home.path().join("work").join("app");
+        fs::create_dir_all(&project).unwrap();
+        assert!(Cursor.discover_mcp_servers(&project).is_empty());
+    }
+
+    #[test]
+    fn test_remove_servers_writes_backup() {
+        let dir = tempdir().unwrap();
+        let path = write_global_fixture(dir.path());
+        let original = fs::read_to_string(&path).unwrap();
+
+        let backup = remove_servers_from_cursor_config(&path, &["github".to_string()])
+            .unwrap()
+            .expect("backup expected");
+        assert_eq!(fs::read_to_string(&backup).unwrap(), original);
+
+        let parsed: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(parsed["mcpServers"]["github"].is_null());
+        assert!(parsed["mcpServers"]["linear"].is_object());
+        assert_eq!(parsed["otherSetting"], json!(true));
+    }
+
+    #[test]
+    fn test_remove_servers_noop_when_absent() {
+        let dir = tempdir().unwrap();
+        let path = write_global_fixture(dir.path());
+        let result = remove_servers_from_cursor_config(&path, &["missing".to_string()]).unwrap();
+        assert!(result.is_none());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_discover_global_rules() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Code Style.mdc"),
+            "---\ndescription: Style guide\nalwaysApply: true\n---\nUse tabs.\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("testing.md"), "Write tests first.").unwrap();
+        fs::write(
+            dir.path().join("empty.mdc"),
+            "---\nalwaysApply: true\n---\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let rules = discover_cursor_global_rules(dir.path());
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].machine_name, "code-style");
+        assert_eq!(rules[0].name, "Code Style");
+        assert_eq!(rules[0].content, "Use tabs.");
+        assert_eq!(rules[1].machine_name, "testing");
+    }
+
+    #[test]
+    fn test_write_rules_replaces_only_managed_files() {
+        let dir = tempdir().unwrap();
+        let rules_dir = dir.path().join(".cursor/rules");
+        fs::create_dir_all(&rules_dir).unwrap();
+        fs::write(rules_dir.join("team.mdc"), "Our own rule.").unwrap();
+
+        let rules = vec![
+            ("style".to_string(), "Use tabs.\n".to_string()),
+            ("testing".to_string(), "Write tests first.".to_string()),
+        ];
+        let written = Cursor.write_rules(dir.path(), &rules).unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(
+            fs::read_to_string(rules_dir.join("automatic-style.mdc")).unwrap(),
+            "---\ndescription: style\nalwaysApply: true\n---\nUse tabs.\n"
+        );
+        assert!(Cursor.write_rules(dir.path(), &rules).unwrap().is_empty());
+
+        let written = Cursor.write_rules(dir.path(), &rules[..1]).unwrap();
+        assert_eq!(written.len(), 1);
+        assert!(!rules_dir.join("automatic-testing.mdc").exists());
+
+        Cursor.write_rules(dir.path(), &[]).unwrap();
+        assert!(!rules_dir.join("automatic-style.mdc").exists());
+        assert_eq!(
+            fs::read_to_string(rules_dir.join("team.mdc")).unwrap(),
+            "Our own rule."
+        );
+    }
+
+    #[test]
+    fn test_rule_machine_name() {
+        assert_eq!(rule_machine_name("My_Rule--v2"), "my-rule-v2");
+        assert_eq!(rule_machine_name("2024 notes"), "cursor-2024-notes");
+    }
 }
@@ -106,14 +106,17 @@ impl Agent for Droid {
         }
 
         let path = factory_dir.join("mcp.json");
-        let content =
-            serde_json::to_string_pretty(&output).map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&output)?;
         fs::write(&path, content)
             .map_err(|e| format!("Failed to write .factory/mcp.json: {}", e))?;
 
         Ok(path.display().to_string())
     }
 
+    fn annotated_config_path(&self, dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(".factory").join("mcp.json"))
+    }
+
     fn sync_skills(
         &self,
         dir: &Path,
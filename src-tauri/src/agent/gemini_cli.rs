@@ -7,8 +7,23 @@ use super::{discover_mcp_servers_from_json, sync_individual_skills, Agent};
 /// Gemini CLI agent — writes MCP servers into `.gemini/settings.json`
 /// under the `mcpServers` key, preserving other settings.  Stores skills
 /// under `<project>/.agents/skills/<name>/SKILL.md`.
+///
+/// Projects can instead opt into extension mode (see
+/// [`write_gemini_extension`]), where the managed payload is written as a
+/// Gemini CLI extension under `.gemini/extensions/automatic/`.
 pub struct GeminiCli;
 
+/// Name of the extension Automatic owns under `.gemini/extensions/`.
+pub const GEMINI_EXTENSION_NAME: &str = "automatic";
+
+/// Manifest filename Gemini CLI looks for inside each extension directory.
+const EXTENSION_MANIFEST: &str = "gemini-extension.json";
+
+/// Context file bundled with the Automatic extension.
+const EXTENSION_CONTEXT_FILE: &str = "GEMINI.md";
+
+const EXTENSION_MANAGED_HEADER: &str = "<!-- managed by Automatic — do not edit by hand -->\n\n";
+
 impl Agent for GeminiCli {
     // ── Identity ────────────────────────────────────────────────────────
 
@@ -75,42 +90,14 @@ impl Agent for GeminiCli {
         let path = gemini_dir.join("settings.json");
 
         // Read existing settings (if any)
-        let mut root: Map<String, Value> = if path.exists() {
-            let raw = fs::read_to_string(&path)
-                .map_err(|e| format!("Failed to read .gemini/settings.json: {}", e))?;
-            match serde_json::from_str::<Value>(&raw) {
-                Ok(Value::Object(m)) => m,
-                _ => Map::new(),
-            }
-        } else {
-            Map::new()
-        };
-
-        // Build the mcpServers object — Gemini uses the same format as
-        // Claude Code (command/args/env, no "type" for stdio).
-        let mut gemini_servers = Map::new();
-
-        for (name, config) in servers {
-            let transport = config
-                .get("type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("stdio");
-
-            let mut server = config.clone();
-            if let Some(obj) = server.as_object_mut() {
-                if transport == "stdio" {
-                    obj.remove("type");
-                    obj.remove("enabled");
-                    obj.remove("timeout");
-                }
-            }
-            gemini_servers.insert(name.clone(), server);
-        }
+        let mut root = read_settings(&path)?;
 
-        root.insert("mcpServers".to_string(), Value::Object(gemini_servers));
+        root.insert(
+            "mcpServers".to_string(),
+            Value::Object(to_gemini_servers(servers)),
+        );
 
-        let content = serde_json::to_string_pretty(&Value::Object(root))
-            .map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&Value::Object(root))?;
         fs::write(&path, content)
             .map_err(|e| format!("Failed to write .gemini/settings.json: {}", e))?;
 
@@ -140,58 +127,42 @@ impl Agent for GeminiCli {
 
     /// Gemini CLI merges into `.gemini/settings.json` which may contain user
     /// auth or model settings.  Strip only the `mcpServers` key rather than
-    /// deleting the whole file.
+    /// deleting the whole file.  In extension mode only the `automatic`
+    /// extension directory is removed; other extensions are left alone.
     fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
-        let path = dir.join(".gemini").join("settings.json");
-        if !path.exists() {
-            return vec![];
-        }
-        let raw = match fs::read_to_string(&path) {
-            Ok(r) => r,
-            Err(_) => return vec![],
-        };
-        let mut root: Map<String, Value> = match serde_json::from_str::<Value>(&raw) {
-            Ok(Value::Object(m)) => m,
-            _ => return vec![],
-        };
-        if root.remove("mcpServers").is_none() {
-            // Nothing to remove
-            return vec![];
-        }
-        if root.is_empty() {
-            // File would become `{}` — delete it entirely
-            if fs::remove_file(&path).is_ok() {
-                return vec![path.display().to_string()];
-            }
-        } else {
-            let content = match serde_json::to_string_pretty(&Value::Object(root)) {
-                Ok(c) => c,
-                Err(_) => return vec![],
-            };
-            if fs::write(&path, content).is_ok() {
-                return vec![path.display().to_string()];
-            }
-        }
-        vec![]
+        let mut removed = remove_gemini_extension(dir);
+        removed.extend(cleanup_settings_servers(dir));
+        removed
     }
 
     fn cleanup_mcp_preview(&self, dir: &Path) -> Vec<String> {
+        let mut preview = Vec::new();
+        let ext_dir = gemini_extension_dir(dir);
+        if ext_dir.is_dir() {
+            preview.push(ext_dir.display().to_string());
+        }
         let path = dir.join(".gemini").join("settings.json");
         if path.exists() {
-            vec![path.display().to_string()]
-        } else {
-            vec![]
+            preview.push(path.display().to_string());
         }
+        preview
     }
 
     // ── Discovery ───────────────────────────────────────────────────────
 
     fn discover_mcp_servers(&self, dir: &Path) -> Map<String, Value> {
         let path = dir.join(".gemini").join("settings.json");
-        if !path.exists() {
-            return Map::new();
+        let mut servers = if path.exists() {
+            discover_mcp_servers_from_json(&path, "mcpServers", identity)
+        } else {
+            Map::new()
+        };
+        // Servers bundled by installed extensions.  settings.json wins on a
+        // name clash, matching Gemini CLI's own precedence.
+        for (name, config) in discover_gemini_extension_servers(dir) {
+            servers.entry(name).or_insert(config);
         }
-        discover_mcp_servers_from_json(&path, "mcpServers", identity)
+        servers
     }
 
     fn detect_global_install(&self) -> bool {
@@ -215,6 +186,209 @@ impl Agent for GeminiCli {
     }
 }
 
+// ── Extension mode ──────────────────────────────────────────────────────────
+
+/// Directory of the Automatic-owned extension inside a project.
+pub fn gemini_extension_dir(dir: &Path) -> PathBuf {
+    dir.join(".gemini")
+        .join("extensions")
+        .join(GEMINI_EXTENSION_NAME)
+}
+
+/// The files that make up the Automatic extension, as
+/// `(path relative to the project dir, content)` pairs.  Shared by the
+/// writer and drift detection so both agree byte-for-byte.
+///
+/// `rule_names` are listed in the bundled context file; the rule text itself
+/// stays in the project `GEMINI.md`, which other agents may also read.
+pub fn gemini_extension_files(
+    servers: &Map<String, Value>,
+    rule_names: &[String],
+) -> Result<Vec<(String, String)>, String> {
+    let manifest = serde_json::json!({
+        "name": GEMINI_EXTENSION_NAME,
+        "version": "1.0.0",
+        "contextFileName": EXTENSION_CONTEXT_FILE,
+        "mcpServers": Value::Object(to_gemini_servers(servers)),
+    });
+    let manifest = crate::core::to_stable_json(&manifest)?;
+
+    let mut context = String::from(EXTENSION_MANAGED_HEADER);
+    context.push_str("# Automatic\n\n");
+    context.push_str("MCP servers and rules for this project are managed by Automatic.\n");
+    if !rule_names.is_empty() {
+        context
+            .push_str("\nRules attached to this project (full text in the project GEMINI.md):\n\n");
+        for name in rule_names {
+            context.push_str(&format!("- `{}`\n", name));
+        }
+    }
+
+    let rel = format!(".gemini/extensions/{}", GEMINI_EXTENSION_NAME);
+    Ok(vec![
+        (format!("{}/{}", rel, EXTENSION_MANIFEST), manifest),
+        (format!("{}/{}", rel, EXTENSION_CONTEXT_FILE), context),
+    ])
+}
+
+/// Write Automatic's managed payload as a Gemini CLI extension at
+/// `.gemini/extensions/automatic/`.  Servers it now provides are removed
+/// from `.gemini/settings.json` so Gemini does not load them twice; any
+/// other settings and servers are left alone.  Returns the files written.
+pub fn write_gemini_extension(
+    dir: &Path,
+    servers: &Map<String, Value>,
+    rule_names: &[String],
+) -> Result<Vec<String>, String> {
+    let ext_dir = gemini_extension_dir(dir);
+    fs::create_dir_all(&ext_dir)
+        .map_err(|e| format!("Failed to create {}: {}", ext_dir.display(), e))?;
+
+    let mut written = Vec::new();
+    for (rel, content) in gemini_extension_files(servers, rule_names)? {
+        let path = dir.join(&rel);
+        if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+            fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", rel, e))?;
+        }
+        written.push(path.display().to_string());
+    }
+
+    let settings = dir.join(".gemini").join("settings.json");
+    if settings.exists() {
+        let mut root = read_settings(&settings)?;
+        let mut changed = false;
+        if let Some(existing) = root.get_mut("mcpServers").and_then(|v| v.as_object_mut()) {
+            let before = existing.len();
+            existing.retain(|name, _| !servers.contains_key(name));
+            changed = existing.len() != before;
+            if existing.is_empty() {
+                root.remove("mcpServers");
+            }
+        }
+        if changed {
+            if root.is_empty() {
+                fs::remove_file(&settings)
+                    .map_err(|e| format!("Failed to remove .gemini/settings.json: {}", e))?;
+            } else {
+                let content = crate::core::to_stable_json(&Value::Object(root))?;
+                fs::write(&settings, content)
+                    .map_err(|e| format!("Failed to write .gemini/settings.json: {}", e))?;
+            }
+            written.push(settings.display().to_string());
+        }
+    }
+
+    Ok(written)
+}
+
+/// Remove the Automatic extension directory, leaving every other extension
+/// untouched.  Returns the removed path, or an empty vec if it was absent.
+pub fn remove_gemini_extension(dir: &Path) -> Vec<String> {
+    let ext_dir = gemini_extension_dir(dir);
+    if ext_dir.is_dir()
+        && crate::core::remove_guarded(&ext_dir, &crate::core::RemovalContext::project(dir)).is_ok()
+    {
+        // Drop the now-empty parent so cleanup leaves no trace behind.
+        let _ = fs::remove_dir(dir.join(".gemini").join("extensions"));
+        return vec![ext_dir.display().to_string()];
+    }
+    vec![]
+}
+
+/// Read `mcpServers` from every `.gemini/extensions/<name>/gemini-extension.json`
+/// in the project, in directory-name order.
+pub fn discover_gemini_extension_servers(dir: &Path) -> Map<String, Value> {
+    let mut servers = Map::new();
+    let Ok(entries) = fs::read_dir(dir.join(".gemini").join("extensions")) else {
+        return servers;
+    };
+    let mut manifests: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path().join(EXTENSION_MANIFEST))
+        .filter(|p| p.is_file())
+        .collect();
+    manifests.sort();
+    for manifest in manifests {
+        for (name, config) in discover_mcp_servers_from_json(&manifest, "mcpServers", identity) {
+            servers.entry(name).or_insert(config);
+        }
+    }
+    servers
+}
+
+/// Build the `mcpServers` object — Gemini uses the same format as Claude
+/// Code (command/args/env, no "type" for stdio).
+fn to_gemini_servers(servers: &Map<String, Value>) -> Map<String, Value> {
+    let mut gemini_servers = Map::new();
+
+    for (name, config) in servers {
+        let transport = config
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("stdio");
+
+        let mut server = config.clone();
+        if let Some(obj) = server.as_object_mut() {
+            if transport == "stdio" {
+                obj.remove("type");
+                obj.remove("enabled");
+                obj.remove("timeout");
+            }
+        }
+        gemini_servers.insert(name.clone(), server);
+    }
+
+    gemini_servers
+}
+
+/// Strip the `mcpServers` key from `.gemini/settings.json`, deleting the file
+/// when nothing else is left in it.
+fn cleanup_settings_servers(dir: &Path) -> Vec<String> {
+    let path = dir.join(".gemini").join("settings.json");
+    if !path.exists() {
+        return vec![];
+    }
+    let raw = match fs::read_to_string(&path) {
+        Ok(r) => r,
+        Err(_) => return vec![],
+    };
+    let mut root: Map<String, Value> = match serde_json::from_str::<Value>(&raw) {
+        Ok(Value::Object(m)) => m,
+        _ => return vec![],
+    };
+    if root.remove("mcpServers").is_none() {
+        // Nothing to remove
+        return vec![];
+    }
+    if root.is_empty() {
+        // File would become `{}` — delete it entirely
+        if fs::remove_file(&path).is_ok() {
+            return vec![path.display().to_string()];
+        }
+    } else {
+        let content = match crate::core::to_stable_json(&Value::Object(root)) {
+            Ok(c) => c,
+            Err(_) => return vec![],
+        };
+        if fs::write(&path, content).is_ok() {
+            return vec![path.display().to_string()];
+        }
+    }
+    vec![]
+}
+
+fn read_settings(path: &Path) -> Result<Map<String, Value>, String> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read .gemini/settings.json: {}", e))?;
+    Ok(match serde_json::from_str::<Value>(&raw) {
+        Ok(Value::Object(m)) => m,
+        _ => Map::new(),
+    })
+}
+
 fn convert_md_command_to_gemini_toml(content: &str) -> String {
     let (frontmatter, body) = super::parse_frontmatter(content);
     let mut toml = String::from("automatic_managed = true\n");
@@ -257,6 +431,15 @@ mod tests {
         s
     }
 
+    fn github_servers() -> Map<String, Value> {
+        let mut s = Map::new();
+        s.insert(
+            "github".to_string(),
+            json!({"type":"stdio","command":"npx","args":["-y","@modelcontextprotocol/server-github"]}),
+        );
+        s
+    }
+
     #[test]
     fn test_detect() {
         let dir = tempdir().unwrap();
@@ -311,4 +494,87 @@ mod tests {
             .unwrap()
             .contains("automatic"));
     }
+
+    #[test]
+    fn test_settings_mode_round_trip() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".gemini")).unwrap();
+        fs::write(
+            dir.path().join(".gemini/settings.json"),
+            r#"{"theme": "dark"}"#,
+        )
+        .unwrap();
+
+        GeminiCli
+            .write_mcp_config(dir.path(), &github_servers())
+            .unwrap();
+        let discovered = GeminiCli.discover_mcp_servers(dir.path());
+        assert_eq!(discovered["github"]["command"], "npx");
+        assert!(!gemini_extension_dir(dir.path()).exists());
+
+        GeminiCli.cleanup_mcp_config(dir.path());
+        assert!(GeminiCli.discover_mcp_servers(dir.path()).is_empty());
+        let settings = fs::read_to_string(dir.path().join(".gemini/settings.json")).unwrap();
+        assert!(settings.contains("dark"));
+    }
+
+    #[test]
+    fn test_extension_mode_round_trip() {
+        let dir = tempdir().unwrap();
+        let gemini_dir = dir.path().join(".gemini");
+        fs::create_dir_all(&gemini_dir).unwrap();
+        // A previous settings-mode sync plus a server the user added by hand.
+        fs::write(
+            gemini_dir.join("settings.json"),
+            r#"{"theme": "dark", "mcpServers": {"github": {"command": "old"}, "mine": {"command": "mine"}}}"#,
+        )
+        .unwrap();
+        // A third-party extension that bundles its own server.
+        let other = gemini_dir.join("extensions").join("linter");
+        fs::create_dir_all(&other).unwrap();
+        fs::write(
+            other.join("gemini-extension.json"),
+            r#"{"name": "linter", "version": "0.1.0", "mcpServers": {"lint": {"command": "lint-mcp"}}}"#,
+        )
+        .unwrap();
+
+        let rules = vec!["automatic-service".to_string(), "style".to_string()];
+        write_gemini_extension(dir.path(), &github_servers(), &rules).unwrap();
+
+        let ext_dir = gemini_extension_dir(dir.path());
+        let manifest: Value = serde_json::from_str(
+            &fs::read_to_string(ext_dir.join("gemini-extension.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifest["name"], "automatic");
+        assert_eq!(manifest["contextFileName"], "GEMINI.md");
+        assert_eq!(manifest["mcpServers"]["github"]["command"], "npx");
+        assert!(manifest["mcpServers"]["github"]["type"].is_null());
+        let context = fs::read_to_string(ext_dir.join("GEMINI.md")).unwrap();
+        assert!(context.contains("- `style`"));
+
+        // The managed server moved out of settings.json; the user's stayed.
+        let settings: Value =
+            serde_json::from_str(&fs::read_to_string(gemini_dir.join("settings.json")).unwrap())
+                .unwrap();
+        assert!(settings["mcpServers"]["github"].is_null());
+        assert_eq!(settings["mcpServers"]["mine"]["command"], "mine");
+
+        let discovered = GeminiCli.discover_mcp_servers(dir.path());
+        assert_eq!(discovered["github"]["command"], "npx");
+        assert_eq!(discovered["lint"]["command"], "lint-mcp");
+        assert_eq!(discovered["mine"]["command"], "mine");
+
+        // Cleanup removes only Automatic's extension.
+        assert!(GeminiCli
+            .cleanup_mcp_preview(dir.path())
+            .contains(&ext_dir.display().to_string()));
+        GeminiCli.cleanup_mcp_config(dir.path());
+        assert!(!ext_dir.exists());
+        assert!(other.join("gemini-extension.json").exists());
+        assert_eq!(
+            discover_gemini_extension_servers(dir.path())["lint"]["command"],
+            "lint-mcp"
+        );
+    }
 }
@@ -12,6 +12,106 @@ use super::{discover_mcp_servers_from_json, sync_individual_skills, Agent};
 /// omit the `"type"` field; http entries include `"type": "http"`.
 pub struct GitHubCopilot;
 
+/// Directory (relative to the project root) holding Copilot's path-scoped
+/// instruction files.
+pub const COPILOT_INSTRUCTIONS_DIR: &str = ".github/instructions";
+
+/// Directory (relative to the project root) holding Copilot's prompt files.
+pub const COPILOT_PROMPTS_DIR: &str = ".github/prompts";
+
+/// Frontmatter seeded into newly created instruction files so Copilot applies
+/// them to every file until the user narrows the `applyTo` glob.
+const DEFAULT_INSTRUCTION_FRONTMATTER: &str = "---\napplyTo: \"**\"\n---\n";
+
+/// Returns `true` if `filename` names a Copilot instruction file directly
+/// inside `.github/instructions/` (e.g. `.github/instructions/api.instructions.md`).
+/// Rule files Automatic writes there are not instruction files.
+pub fn is_copilot_instruction_file(filename: &str) -> bool {
+    let Some(rest) = filename.strip_prefix(".github/instructions/") else {
+        return false;
+    };
+    let Some(stem) = rest.strip_suffix(".instructions.md") else {
+        return false;
+    };
+    !stem.is_empty()
+        && !stem.starts_with('.')
+        && !stem.starts_with(super::MANAGED_RULE_PREFIX)
+        && !stem.contains('/')
+        && !stem.contains('\\')
+}
+
+/// List the `.instructions.md` files present in `<dir>/.github/instructions/`,
+/// returned as sorted project-relative paths.
+pub fn list_copilot_instruction_files(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir.join(COPILOT_INSTRUCTIONS_DIR)) else {
+        return vec![];
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| e.file_name().to_str().map(|n| n.to_string()))
+        .map(|n| format!("{}/{}", COPILOT_INSTRUCTIONS_DIR, n))
+        .filter(|f| is_copilot_instruction_file(f))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Returns `true` if `filename` names a Copilot prompt file directly inside
+/// `.github/prompts/` (e.g. `.github/prompts/review.prompt.md`).
+pub fn is_copilot_prompt_file(filename: &str) -> bool {
+    let Some(rest) = filename.strip_prefix(".github/prompts/") else {
+        return false;
+    };
+    let Some(stem) = rest.strip_suffix(".prompt.md") else {
+        return false;
+    };
+    !stem.is_empty() && !stem.starts_with('.') && !stem.contains('/') && !stem.contains('\\')
+}
+
+/// Returns `true` for the files Copilot scopes on their own: path-scoped
+/// instruction files and prompt files.  They take only the rules assigned to
+/// their exact path and are never replicated in unified mode.
+pub fn is_copilot_scoped_file(filename: &str) -> bool {
+    is_copilot_instruction_file(filename) || is_copilot_prompt_file(filename)
+}
+
+/// List the team-authored `.prompt.md` files in `<dir>/.github/prompts/`,
+/// returned as sorted project-relative paths.  Prompt files Automatic writes
+/// for commands are left out; sync regenerates them.
+pub fn list_copilot_prompt_files(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir.join(COPILOT_PROMPTS_DIR)) else {
+        return vec![];
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && !super::is_managed_command_file(p))
+        .filter_map(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.to_string())
+        })
+        .map(|n| format!("{}/{}", COPILOT_PROMPTS_DIR, n))
+        .filter(|f| is_copilot_prompt_file(f))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Prepend the default `applyTo` frontmatter unless `content` already starts
+/// with a frontmatter block.  Used only when creating a new instruction file;
+/// existing files keep whatever frontmatter the team wrote.
+pub fn with_default_instruction_frontmatter(content: &str) -> String {
+    if content.trim_start().starts_with("---") {
+        content.to_string()
+    } else if content.trim().is_empty() {
+        DEFAULT_INSTRUCTION_FRONTMATTER.to_string()
+    } else {
+        format!("{}\n{}", DEFAULT_INSTRUCTION_FRONTMATTER, content)
+    }
+}
+
 impl Agent for GitHubCopilot {
     // ── Identity ────────────────────────────────────────────────────────
 
@@ -31,12 +131,19 @@ impl Agent for GitHubCopilot {
         ".github/copilot-instructions.md"
     }
 
+    fn extra_instruction_files(&self, dir: &Path) -> Vec<String> {
+        let mut files = list_copilot_instruction_files(dir);
+        files.extend(list_copilot_prompt_files(dir));
+        files
+    }
+
     // ── Detection ───────────────────────────────────────────────────────
 
     fn detect_in(&self, dir: &Path) -> bool {
         dir.join(".github").join("copilot-instructions.md").exists()
             || dir.join(".vscode").join("mcp.json").exists()
-            || dir.join(".github").join("prompts").exists()
+            || dir.join(COPILOT_PROMPTS_DIR).exists()
+            || !list_copilot_instruction_files(dir).is_empty()
     }
 
     fn skill_dirs(&self, dir: &Path) -> Vec<PathBuf> {
@@ -53,14 +160,40 @@ impl Agent for GitHubCopilot {
         }
     }
 
+    fn recommended_max_servers(&self) -> Option<usize> {
+        // VS Code sends at most 128 tools per request.
+        Some(15)
+    }
+
     fn commands_dir(&self, dir: &Path) -> Option<PathBuf> {
-        Some(dir.join(".github").join("prompts"))
+        Some(dir.join(COPILOT_PROMPTS_DIR))
     }
 
     fn command_file_name(&self, machine_name: &str) -> String {
         format!("{machine_name}.prompt.md")
     }
 
+    // ── Native rules ────────────────────────────────────────────────────
+
+    fn rules_dir(&self, dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(COPILOT_INSTRUCTIONS_DIR))
+    }
+
+    fn rule_file(&self, name: &str, content: &str) -> (String, String) {
+        (
+            format!("{}{}.instructions.md", super::MANAGED_RULE_PREFIX, name),
+            format!(
+                "{}\n{}\n",
+                DEFAULT_INSTRUCTION_FRONTMATTER,
+                content.trim_end()
+            ),
+        )
+    }
+
+    fn write_rules(&self, dir: &Path, rules: &[(String, String)]) -> Result<Vec<String>, String> {
+        super::sync_rule_files(self, dir, rules)
+    }
+
     // ── Config writing ──────────────────────────────────────────────────
 
     fn write_mcp_config(&self, dir: &Path, servers: &Map<String, Value>) -> Result<String, String> {
@@ -110,8 +243,7 @@ impl Agent for GitHubCopilot {
 
         root.insert("servers".to_string(), Value::Object(copilot_servers));
 
-        let content = serde_json::to_string_pretty(&Value::Object(root))
-            .map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&Value::Object(root))?;
         fs::write(&path, content)
             .map_err(|e| format!("Failed to write .vscode/mcp.json: {}", e))?;
 
@@ -163,7 +295,7 @@ impl Agent for GitHubCopilot {
                 return vec![path.display().to_string()];
             }
         } else {
-            let content = match serde_json::to_string_pretty(&Value::Object(root)) {
+            let content = match crate::core::to_stable_json(&Value::Object(root)) {
                 Ok(c) => c,
                 Err(_) => return vec![],
             };
@@ -263,6 +395,139 @@ mod tests {
         assert!(GitHubCopilot.detect_in(dir.path()));
     }
 
+    #[test]
+    fn test_detect_instruction_files() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".github/instructions")).unwrap();
+        fs::write(dir.path().join(".github/instructions/notes.md"), "").unwrap();
+        assert!(!GitHubCopilot.detect_in(dir.path()));
+
+        fs::write(
+            dir.path().join(".github/instructions/api.instructions.md"),
+            "---\napplyTo: \"src/api/**\"\n---\n",
+        )
+        .unwrap();
+        assert!(GitHubCopilot.detect_in(dir.path()));
+    }
+
+    #[test]
+    fn test_list_instruction_files() {
+        let dir = tempdir().unwrap();
+        let inst = dir.path().join(".github/instructions");
+        fs::create_dir_all(inst.join("nested")).unwrap();
+        fs::write(inst.join("web.instructions.md"), "").unwrap();
+        fs::write(inst.join("api.instructions.md"), "").unwrap();
+        fs::write(inst.join("README.md"), "").unwrap();
+
+        assert_eq!(
+            GitHubCopilot.extra_instruction_files(dir.path()),
+            vec![
+                ".github/instructions/api.instructions.md",
+                ".github/instructions/web.instructions.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_prompt_files_skips_managed_commands() {
+        let dir = tempdir().unwrap();
+        let prompts = dir.path().join(".github/prompts");
+        fs::create_dir_all(&prompts).unwrap();
+        fs::write(
+            prompts.join("review.prompt.md"),
+            "---\nmode: agent\n---\nReview the diff.\n",
+        )
+        .unwrap();
+        fs::write(
+            prompts.join("deploy.prompt.md"),
+            "---\nautomatic-managed: true\n---\nDeploy.\n",
+        )
+        .unwrap();
+        fs::write(prompts.join("notes.md"), "").unwrap();
+        fs::create_dir_all(dir.path().join(".github/instructions")).unwrap();
+        fs::write(
+            dir.path().join(".github/instructions/api.instructions.md"),
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(
+            list_copilot_prompt_files(dir.path()),
+            vec![".github/prompts/review.prompt.md"]
+        );
+        assert_eq!(
+            GitHubCopilot.extra_instruction_files(dir.path()),
+            vec![
+                ".github/instructions/api.instructions.md",
+                ".github/prompts/review.prompt.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_copilot_prompt_file() {
+        assert!(is_copilot_prompt_file(".github/prompts/review.prompt.md"));
+        assert!(is_copilot_scoped_file(".github/prompts/review.prompt.md"));
+        assert!(!is_copilot_prompt_file(".github/prompts/review.md"));
+        assert!(!is_copilot_prompt_file(".github/prompts/.prompt.md"));
+        assert!(!is_copilot_prompt_file(".github/prompts/a/b.prompt.md"));
+        assert!(!is_copilot_prompt_file(
+            ".github/instructions/api.instructions.md"
+        ));
+    }
+
+    #[test]
+    fn test_is_copilot_instruction_file() {
+        assert!(is_copilot_instruction_file(
+            ".github/instructions/api.instructions.md"
+        ));
+        assert!(!is_copilot_instruction_file(
+            ".github/copilot-instructions.md"
+        ));
+        assert!(!is_copilot_instruction_file(
+            ".github/instructions/.instructions.md"
+        ));
+        assert!(!is_copilot_instruction_file(
+            ".github/instructions/../x.instructions.md"
+        ));
+        assert!(!is_copilot_instruction_file(
+            ".github/instructions/a/b.instructions.md"
+        ));
+        assert!(!is_copilot_instruction_file(
+            ".github/instructions/automatic-style.instructions.md"
+        ));
+    }
+
+    #[test]
+    fn test_rule_files_are_not_listed_as_instruction_files() {
+        let dir = tempdir().unwrap();
+        let rules = vec![("style".to_string(), "Use tabs.".to_string())];
+        GitHubCopilot.write_rules(dir.path(), &rules).unwrap();
+
+        let path = dir
+            .path()
+            .join(".github/instructions/automatic-style.instructions.md");
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "---\napplyTo: \"**\"\n---\n\nUse tabs.\n"
+        );
+        assert!(GitHubCopilot.extra_instruction_files(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_default_frontmatter_only_added_when_missing() {
+        assert_eq!(
+            with_default_instruction_frontmatter(""),
+            "---\napplyTo: \"**\"\n---\n"
+        );
+        assert_eq!(
+            with_default_instruction_frontmatter("# API\n"),
+            "---\napplyTo: \"**\"\n---\n\n# API\n"
+        );
+        let existing = "---\napplyTo: \"src/**/*.ts\"\n---\n# TS\n";
+        assert_eq!(with_default_instruction_frontmatter(existing), existing);
+    }
+
     #[test]
     fn test_write_stdio() {
         let dir = tempdir().unwrap();
@@ -1,4 +1,7 @@
-use serde_json::{Map, Value};
+use serde_json::{json, Map, Value};
+use serde_yaml::{Mapping, Value as YamlValue};
+use std::collections::BTreeMap;
+use std::fs;
 use std::path::{Path, PathBuf};
 
 use super::{sync_individual_skills, Agent, AgentCapabilities};
@@ -15,14 +18,16 @@ use super::{sync_individual_skills, Agent, AgentCapabilities};
 ///
 /// ## MCP / extensions
 ///
-/// Goose manages extensions entirely through its own YAML config file:
-/// `~/.config/goose/config.yaml`.  Extensions are registered under the
-/// `extensions` key as stdio commands or remote HTTP endpoints.  There is no
-/// project-scoped MCP config file; all extensions are global.
+/// Goose registers MCP servers as extensions under the `extensions` key of
+/// its YAML config: globally in `~/.config/goose/config.yaml` and per project
+/// in `<project>/.goose/config.yaml`.  Discovery reads both, the project's
+/// entries overriding global ones of the same name.
 ///
-/// Automatic can discover existing extensions from the global config (YAML
-/// parsing) but **cannot write** Goose extension config — extensions must be
-/// added through the `goose configure` CLI or the Goose Desktop GUI.
+/// Automatic writes only the project config, and merges into it: the
+/// extensions it manages are listed under `AUTOMATIC_MANAGED_EXTENSIONS`, and
+/// sync and cleanup replace or remove just those.  Other extensions and keys
+/// are kept.  The file is rewritten only when the managed entries change;
+/// when it is, YAML comments are lost and anchors are written out in full.
 pub struct Goose;
 
 impl Agent for Goose {
@@ -37,7 +42,7 @@ impl Agent for Goose {
     }
 
     fn config_description(&self) -> &'static str {
-        "AGENTS.md (MCP configured via goose CLI/Desktop)"
+        ".goose/config.yaml"
     }
 
     fn project_file_name(&self) -> &'static str {
@@ -49,31 +54,18 @@ impl Agent for Goose {
     // ── Capabilities ────────────────────────────────────────────────────
 
     fn capabilities(&self) -> AgentCapabilities {
-        // Goose has no project-level MCP config file.  Extensions are managed
-        // globally via ~/.config/goose/config.yaml through the Goose CLI/Desktop.
         AgentCapabilities {
-            mcp_servers: false,
             agents: false,
             ..Default::default()
         }
     }
 
-    // ── MCP note ────────────────────────────────────────────────────────
-
-    fn mcp_note(&self) -> Option<&'static str> {
-        Some(
-            "Goose manages extensions globally via ~/.config/goose/config.yaml. \
-             Use `goose configure` or the Goose Desktop to add MCP servers \u{2014} \
-             Automatic cannot write Goose\u{2019}s extension config.",
-        )
-    }
-
     // ── Detection ───────────────────────────────────────────────────────
 
     fn detect_in(&self, dir: &Path) -> bool {
         // .goosehints is the most unique Goose-specific marker.
         // AGENTS.md is too generic to use as a sole trigger.
-        dir.join(".goosehints").exists()
+        dir.join(".goosehints").exists() || project_config_path(dir).exists()
     }
 
     fn skill_dirs(&self, dir: &Path) -> Vec<PathBuf> {
@@ -82,20 +74,49 @@ impl Agent for Goose {
 
     // ── Cleanup ─────────────────────────────────────────────────────────
 
-    // No MCP config files to clean up — Goose MCP is managed externally.
-    // owned_config_paths defaults to empty vec, which is correct here.
+    fn owned_config_paths(&self, dir: &Path) -> Vec<PathBuf> {
+        vec![project_config_path(dir)]
+    }
+
+    /// `.goose/config.yaml` may hold the user's own extensions and settings.
+    /// Strip only the extensions Automatic manages, and delete the file only
+    /// when nothing else is left.
+    fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
+        let path = project_config_path(dir);
+        let Ok(Some(original)) = read_goose_config(&path) else {
+            return vec![];
+        };
+        let mut root = original.clone();
+        merge_managed_extensions(&mut root, &Map::new());
+        if root == original {
+            return vec![];
+        }
+        let only_empty_extensions = root.len() == 1
+            && root
+                .get("extensions")
+                .and_then(YamlValue::as_mapping)
+                .is_some_and(Mapping::is_empty);
+        if root.is_empty() || only_empty_extensions {
+            if fs::remove_file(&path).is_ok() {
+                return vec![path.display().to_string()];
+            }
+        } else if write_goose_config(&path, Some(&original), root).is_ok() {
+            return vec![path.display().to_string()];
+        }
+        vec![]
+    }
 
     // ── Config writing ──────────────────────────────────────────────────
 
-    /// Goose does not use a project-level MCP config file.
-    /// This is intentionally a no-op; extensions must be configured through
-    /// the Goose CLI (`goose configure`) or Goose Desktop.
-    fn write_mcp_config(
-        &self,
-        _dir: &Path,
-        _servers: &Map<String, Value>,
-    ) -> Result<String, String> {
-        Ok(String::new())
+    /// Merge `servers` into `.goose/config.yaml` as the extensions Automatic
+    /// manages.  A config that is not valid YAML is left untouched.
+    fn write_mcp_config(&self, dir: &Path, servers: &Map<String, Value>) -> Result<String, String> {
+        let path = project_config_path(dir);
+        let original = read_goose_config(&path)?;
+        let mut root = original.clone().unwrap_or_default();
+        merge_managed_extensions(&mut root, servers);
+        write_goose_config(&path, original.as_ref(), root)?;
+        Ok(path.display().to_string())
     }
 
     fn sync_skills(
@@ -119,9 +140,8 @@ impl Agent for Goose {
 
     // ── Discovery ───────────────────────────────────────────────────────
 
-    /// Goose has no project-level MCP config file to discover from.
-    fn discover_mcp_servers(&self, _dir: &Path) -> Map<String, Value> {
-        Map::new()
+    fn discover_mcp_servers(&self, dir: &Path) -> Map<String, Value> {
+        discover_goose_servers(global_config_path().as_deref(), &project_config_path(dir))
     }
 
     fn detect_global_install(&self) -> bool {
@@ -132,20 +152,174 @@ impl Agent for Goose {
     }
 
     fn discover_global_mcp_servers(&self) -> Map<String, Value> {
-        let Some(home) = super::home_dir() else {
-            return Map::new();
-        };
-        // ~/.config/goose/config.yaml — Goose's global extension config.
-        // Extensions are listed under the `extensions` map; each entry with
-        // `type: "stdio"` or `type: "streamable_http"` maps onto Automatic's
-        // canonical MCP server format.
-        let path = home.join(".config").join("goose").join("config.yaml");
-        discover_goose_global_config(&path)
+        match global_config_path() {
+            Some(path) => discover_goose_config(&path),
+            None => Map::new(),
+        }
     }
 }
 
-/// Parse Goose's global `~/.config/goose/config.yaml` and extract stdio/sse
-/// extensions as Automatic canonical MCP server configs.
+// ── Goose config ─────────────────────────────────────────────────────────────
+
+/// Top-level key of a Goose config listing the extensions Automatic wrote,
+/// so that sync and cleanup touch only those.
+const MANAGED_EXTENSIONS_KEY: &str = "AUTOMATIC_MANAGED_EXTENSIONS";
+
+/// Goose's default extension timeout, in seconds.
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+fn project_config_path(dir: &Path) -> PathBuf {
+    dir.join(".goose").join("config.yaml")
+}
+
+fn global_config_path() -> Option<PathBuf> {
+    super::home_dir().map(|h| h.join(".config").join("goose").join("config.yaml"))
+}
+
+/// The Goose config at `path`, or `None` when the file does not exist.  An
+/// empty file reads as an empty mapping; anything else that is not a YAML
+/// mapping is an error, so a broken config is never overwritten.
+///
+/// Anchors and aliases are expanded when parsing; `<<` merge keys are left as
+/// they are so that writing the config back keeps them.
+fn read_goose_config(path: &Path) -> Result<Option<Mapping>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    match serde_yaml::from_str::<YamlValue>(&raw) {
+        Ok(YamlValue::Mapping(root)) => Ok(Some(root)),
+        Ok(YamlValue::Null) => Ok(Some(Mapping::new())),
+        Ok(_) => Err(format!("{} is not a YAML mapping", path.display())),
+        Err(e) => Err(format!("Invalid YAML in {}: {}", path.display(), e)),
+    }
+}
+
+/// Write `root` to `path` unless it equals `original`, so that an in-sync
+/// config keeps its comments and formatting.
+fn write_goose_config(
+    path: &Path,
+    original: Option<&Mapping>,
+    root: Mapping,
+) -> Result<(), String> {
+    if original == Some(&root) {
+        return Ok(());
+    }
+    let content = serde_yaml::to_string(&YamlValue::Mapping(root))
+        .map_err(|e| format!("Failed to serialise {}: {}", path.display(), e))?;
+    crate::core::write_atomic(path, content)
+}
+
+/// Names listed under [`MANAGED_EXTENSIONS_KEY`].
+fn managed_extension_names(root: &Mapping) -> Vec<String> {
+    root.get(MANAGED_EXTENSIONS_KEY)
+        .and_then(YamlValue::as_sequence)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(YamlValue::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace the extensions Automatic manages in `root` with `servers`.
+///
+/// Extensions the user added are kept, in place, unless a server in
+/// `servers` takes the same name; previously managed extensions that are no
+/// longer in `servers` are dropped.  Every other key of `root` is left alone.
+fn merge_managed_extensions(root: &mut Mapping, servers: &Map<String, Value>) {
+    let previous = managed_extension_names(root);
+    let mut entries: BTreeMap<String, YamlValue> = servers
+        .iter()
+        .filter_map(|(name, config)| Some((name.clone(), to_goose_extension(name, config)?)))
+        .collect();
+    let managed: Vec<YamlValue> = entries.keys().cloned().map(YamlValue::String).collect();
+
+    let existing = root
+        .get("extensions")
+        .and_then(YamlValue::as_mapping)
+        .cloned()
+        .unwrap_or_default();
+    let mut extensions = Mapping::new();
+    for (key, value) in existing {
+        match key.as_str().map(str::to_string) {
+            Some(name) if entries.contains_key(&name) => {
+                let replacement = entries.remove(&name).unwrap_or(value);
+                extensions.insert(key, replacement);
+            }
+            Some(name) if previous.contains(&name) => {}
+            _ => {
+                extensions.insert(key, value);
+            }
+        }
+    }
+    for (name, extension) in entries {
+        extensions.insert(YamlValue::String(name), extension);
+    }
+
+    if !extensions.is_empty() || root.contains_key("extensions") {
+        root.insert(
+            YamlValue::String("extensions".into()),
+            YamlValue::Mapping(extensions),
+        );
+    }
+    if managed.is_empty() {
+        root.remove(MANAGED_EXTENSIONS_KEY);
+    } else {
+        root.insert(
+            YamlValue::String(MANAGED_EXTENSIONS_KEY.into()),
+            YamlValue::Sequence(managed),
+        );
+    }
+}
+
+/// A canonical MCP server config as a Goose extension entry.  `None` for
+/// configs without a command or URL.
+fn to_goose_extension(name: &str, config: &Value) -> Option<YamlValue> {
+    let transport = config
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("stdio");
+    let mut extension = Map::new();
+    extension.insert("name".into(), json!(name));
+    match transport {
+        "http" | "sse" => {
+            let url = config.get("url")?.as_str()?;
+            let goose_type = if transport == "http" {
+                "streamable_http"
+            } else {
+                "sse"
+            };
+            extension.insert("type".into(), json!(goose_type));
+            extension.insert("uri".into(), json!(url));
+            if let Some(headers) = config.get("headers").filter(|h| h.is_object()) {
+                extension.insert("headers".into(), headers.clone());
+            }
+        }
+        _ => {
+            let command = config.get("command")?.as_str()?;
+            extension.insert("type".into(), json!("stdio"));
+            extension.insert("cmd".into(), json!(command));
+            extension.insert(
+                "args".into(),
+                config.get("args").cloned().unwrap_or_else(|| json!([])),
+            );
+            extension.insert(
+                "envs".into(),
+                config.get("env").cloned().unwrap_or_else(|| json!({})),
+            );
+        }
+    }
+    extension.insert("enabled".into(), json!(true));
+    extension.insert("timeout".into(), json!(DEFAULT_TIMEOUT_SECS));
+    serde_yaml::to_value(Value::Object(extension)).ok()
+}
+
+/// The extensions of the Goose config at `path` as Automatic canonical MCP
+/// server configs.  A missing or unreadable file yields no servers.
 ///
 /// Goose config YAML (extensions section):
 /// ```yaml
@@ -158,266 +332,105 @@ impl Agent for Goose {
 ///       API_KEY: secret
 ///     enabled: true
 /// ```
-///
-/// We parse this without an external YAML library using a line-oriented state
-/// machine that handles the common indented-block structure.  Deeply nested or
-/// non-standard YAML constructs are skipped safely.
-fn discover_goose_global_config(path: &std::path::Path) -> Map<String, Value> {
-    use std::fs;
-
+fn discover_goose_config(path: &Path) -> Map<String, Value> {
     let mut result = Map::new();
-
-    let content = match fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(_) => return result,
-    };
-
-    // State machine phases
-    #[derive(PartialEq)]
-    enum Phase {
-        TopLevel,
-        InExtensions,
-        InEntry,
-        InEnvs,
-    }
-
-    let mut phase = Phase::TopLevel;
-    let mut current_name = String::new();
-    let mut entry_type = String::new(); // "stdio", "sse", "streamable_http", etc.
-    let mut entry_cmd = String::new(); // stdio: cmd field; http: uri field
-    let mut entry_args: Vec<String> = Vec::new();
-    let mut entry_envs: Map<String, Value> = Map::new();
-    let mut in_block_args = false; // true while collecting `- item` lines for args
-
-    /// Strip an inline YAML comment and leading/trailing whitespace.
-    fn strip_comment(s: &str) -> &str {
-        // A '#' that is not inside quotes is a comment start.
-        // For our simple values (strings, plain scalars) we can just split on
-        // the first unquoted '#'.
-        let bytes = s.as_bytes();
-        let mut in_quote = false;
-        let mut quote_char = b'"';
-        for (i, &b) in bytes.iter().enumerate() {
-            if in_quote {
-                if b == quote_char {
-                    in_quote = false;
-                }
-            } else if b == b'"' || b == b'\'' {
-                in_quote = true;
-                quote_char = b;
-            } else if b == b'#' {
-                return s[..i].trim_end();
-            }
-        }
-        s.trim_end()
-    }
-
-    /// Unquote a YAML scalar value (remove surrounding ' or ").
-    fn unquote(s: &str) -> String {
-        let s = s.trim();
-        if (s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')) {
-            s[1..s.len() - 1].to_string()
-        } else {
-            s.to_string()
+    let root = match read_goose_config(path) {
+        Ok(Some(root)) => root,
+        Ok(None) => return result,
+        Err(e) => {
+            tracing::warn!("Skipping Goose config: {}", e);
+            return result;
         }
+    };
+    let mut doc = YamlValue::Mapping(root);
+    if let Err(e) = doc.apply_merge() {
+        tracing::warn!("Failed to apply merge keys in {}: {}", path.display(), e);
     }
-
-    fn flush(
-        name: &str,
-        entry_type: &str,
-        cmd: &str, // stdio: command binary; streamable_http: uri
-        args: &[String],
-        envs: &Map<String, Value>,
-        result: &mut Map<String, Value>,
-    ) {
-        if name.is_empty() || cmd.is_empty() {
-            return;
-        }
-        if !crate::core::is_valid_name(name) {
-            return;
+    let Some(extensions) = doc.get("extensions").and_then(YamlValue::as_mapping) else {
+        return result;
+    };
+    for (key, entry) in extensions {
+        let Some(name) = key.as_str() else {
+            continue;
+        };
+        if !crate::core::is_valid_name(name) || crate::core::is_managed_name(name) {
+            continue;
         }
-        // Only import stdio and streamable_http entries.
-        // - "builtin" / "platform" / "frontend" / "inline_python" don't map to MCP.
-        // - "sse" is deprecated in Goose (warns on load); skip it.
-        // - empty type defaults to stdio behaviour.
-        match entry_type {
-            "stdio" | "" => {
-                let mut server = serde_json::Map::new();
-                server.insert("command".to_string(), Value::String(cmd.to_string()));
-                if !args.is_empty() {
-                    server.insert(
-                        "args".to_string(),
-                        Value::Array(args.iter().map(|a| Value::String(a.clone())).collect()),
-                    );
-                }
-                if !envs.is_empty() {
-                    server.insert("env".to_string(), Value::Object(envs.clone()));
-                }
-                result.insert(name.to_string(), Value::Object(server));
-            }
-            "streamable_http" => {
-                // `cmd` holds the `uri:` value for HTTP extensions.
-                let mut server = serde_json::Map::new();
-                server.insert("type".to_string(), Value::String("http".to_string()));
-                server.insert("url".to_string(), Value::String(cmd.to_string()));
-                if !envs.is_empty() {
-                    server.insert("env".to_string(), Value::Object(envs.clone()));
-                }
-                result.insert(name.to_string(), Value::Object(server));
-            }
-            _ => {} // sse (deprecated), builtin, platform, etc — skip
+        let Ok(entry) = serde_json::to_value(entry) else {
+            continue;
+        };
+        if let Some(server) = normalise_goose_extension(&entry) {
+            result.insert(name.to_string(), server);
         }
     }
+    result
+}
 
-    for raw_line in content.lines() {
-        let indent = raw_line.len() - raw_line.trim_start().len();
-        let line = raw_line.trim();
-
-        // Skip blank lines and comments
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
+/// Servers from the global config overlaid with the project's own.
+fn discover_goose_servers(global: Option<&Path>, project: &Path) -> Map<String, Value> {
+    let mut servers = global.map(discover_goose_config).unwrap_or_default();
+    servers.extend(discover_goose_config(project));
+    servers
+}
 
-        match phase {
-            Phase::TopLevel => {
-                if line.starts_with("extensions:") {
-                    phase = Phase::InExtensions;
-                }
+/// A Goose extension entry in Automatic's canonical format.  Only stdio and
+/// `streamable_http` extensions map onto MCP servers:
+/// - "builtin" / "platform" / "frontend" / "inline_python" don't map to MCP.
+/// - "sse" is deprecated in Goose (warns on load); skip it.
+/// - a missing type defaults to stdio behaviour.
+fn normalise_goose_extension(entry: &Value) -> Option<Value> {
+    let entry_type = entry.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    let envs = entry
+        .get("envs")
+        .and_then(|v| v.as_object())
+        .map(|envs| {
+            envs.iter()
+                .filter_map(|(k, v)| Some((k.clone(), Value::String(scalar_string(v)?))))
+                .collect::<Map<String, Value>>()
+        })
+        .unwrap_or_default();
+
+    let mut server = Map::new();
+    match entry_type {
+        "stdio" | "" => {
+            let cmd = entry.get("cmd").and_then(scalar_string)?;
+            server.insert("command".into(), Value::String(cmd));
+            let args: Vec<Value> = entry
+                .get("args")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|a| scalar_string(a).map(Value::String))
+                .collect();
+            if !args.is_empty() {
+                server.insert("args".into(), Value::Array(args));
             }
-            Phase::InExtensions => {
-                // Return to top-level on any non-indented non-comment line
-                if indent == 0 && !line.starts_with('-') {
-                    phase = Phase::TopLevel;
-                    continue;
-                }
-                // Extension name: two-space indent, ends with ':'
-                if indent == 2 && line.ends_with(':') {
-                    // Flush previous entry
-                    flush(
-                        &current_name,
-                        &entry_type,
-                        &entry_cmd,
-                        &entry_args,
-                        &entry_envs,
-                        &mut result,
-                    );
-                    current_name = line.trim_end_matches(':').to_string();
-                    entry_type.clear();
-                    entry_cmd.clear();
-                    entry_args.clear();
-                    entry_envs.clear();
-                    in_block_args = false;
-                    phase = Phase::InEntry;
-                }
-            }
-            Phase::InEntry => {
-                if indent <= 2 && !line.starts_with('-') {
-                    // End of entry (new key at same or lower indent)
-                    if indent == 2 && line.ends_with(':') {
-                        flush(
-                            &current_name,
-                            &entry_type,
-                            &entry_cmd,
-                            &entry_args,
-                            &entry_envs,
-                            &mut result,
-                        );
-                        current_name = line.trim_end_matches(':').to_string();
-                        entry_type.clear();
-                        entry_cmd.clear();
-                        entry_args.clear();
-                        entry_envs.clear();
-                        in_block_args = false;
-                        // stay in InEntry
-                    } else if indent == 0 {
-                        flush(
-                            &current_name,
-                            &entry_type,
-                            &entry_cmd,
-                            &entry_args,
-                            &entry_envs,
-                            &mut result,
-                        );
-                        in_block_args = false;
-                        phase = Phase::TopLevel;
-                    }
-                    continue;
-                }
-
-                if let Some(rest) = line.strip_prefix("type:") {
-                    entry_type = unquote(strip_comment(rest.trim()));
-                    in_block_args = false;
-                } else if let Some(rest) = line.strip_prefix("cmd:") {
-                    entry_cmd = unquote(strip_comment(rest.trim()));
-                    in_block_args = false;
-                } else if let Some(rest) = line.strip_prefix("uri:") {
-                    // streamable_http extensions use `uri:` instead of `cmd:`.
-                    // Store it in entry_cmd so flush() can handle it uniformly.
-                    entry_cmd = unquote(strip_comment(rest.trim()));
-                    in_block_args = false;
-                } else if let Some(rest) = line.strip_prefix("args:") {
-                    in_block_args = false;
-                    // Inline flow sequence: args: [-y, "@scope/pkg"]  or  args: ["a", "b"]
-                    let list_str = strip_comment(rest.trim());
-                    if list_str.starts_with('[') && list_str.ends_with(']') {
-                        let inner = &list_str[1..list_str.len() - 1];
-                        entry_args = inner
-                            .split(',')
-                            .map(|s| unquote(s.trim()))
-                            .filter(|s| !s.is_empty())
-                            .collect();
-                    } else if list_str.is_empty() {
-                        // Block sequence follows on subsequent lines
-                        in_block_args = true;
-                    }
-                } else if line.starts_with("- ") && in_block_args {
-                    // Block-style args list item
-                    entry_args.push(unquote(line[2..].trim()));
-                } else if line.starts_with("envs:") {
-                    in_block_args = false;
-                    phase = Phase::InEnvs;
-                } else {
-                    in_block_args = false;
-                }
-            }
-            Phase::InEnvs => {
-                if indent <= 4 && !line.starts_with('-') {
-                    // Back to entry level
-                    phase = Phase::InEntry;
-                    in_block_args = false;
-                    // Re-process this line as an entry field
-                    if let Some(rest) = line.strip_prefix("type:") {
-                        entry_type = unquote(strip_comment(rest.trim()));
-                    } else if let Some(rest) = line.strip_prefix("cmd:") {
-                        entry_cmd = unquote(strip_comment(rest.trim()));
-                    } else if let Some(rest) = line.strip_prefix("uri:") {
-                        entry_cmd = unquote(strip_comment(rest.trim()));
-                    }
-                    continue;
-                }
-                // KEY: VALUE pairs inside envs block
-                if let Some((k, v)) = line.split_once(':') {
-                    let key = k.trim().to_string();
-                    let val = unquote(strip_comment(v.trim()));
-                    if !key.is_empty() {
-                        entry_envs.insert(key, Value::String(val));
-                    }
-                }
+        }
+        "streamable_http" => {
+            let uri = entry.get("uri").and_then(scalar_string)?;
+            server.insert("type".into(), json!("http"));
+            server.insert("url".into(), Value::String(uri));
+            if let Some(headers) = entry.get("headers").filter(|h| h.is_object()) {
+                server.insert("headers".into(), headers.clone());
             }
         }
+        _ => return None,
     }
+    if !envs.is_empty() {
+        server.insert("env".into(), Value::Object(envs));
+    }
+    Some(Value::Object(server))
+}
 
-    // Flush final entry
-    flush(
-        &current_name,
-        &entry_type,
-        &entry_cmd,
-        &entry_args,
-        &entry_envs,
-        &mut result,
-    );
-
-    result
+/// A YAML scalar as a string; unquoted `8080` or `true` parse as numbers
+/// and booleans.
+fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
 }
 
 // ── Tests ───────────────────────────────────────────────────────────────────
@@ -444,39 +457,156 @@ mod tests {
     }
 
     #[test]
-    fn test_mcp_capability_disabled() {
-        assert!(!Goose.capabilities().mcp_servers);
+    fn test_mcp_capability_enabled() {
+        assert!(Goose.capabilities().mcp_servers);
+        assert!(Goose.mcp_note().is_none());
     }
 
-    #[test]
-    fn test_mcp_note_is_some() {
-        assert!(Goose.mcp_note().is_some());
+    fn user_config() -> &'static str {
+        r#"
+GOOSE_PROVIDER: anthropic
+defaults: &defaults
+  enabled: true
+  timeout: 300
+extensions:
+  developer:
+    name: developer
+    type: builtin
+    enabled: true
+  notes:
+    <<: *defaults
+    name: notes
+    type: stdio
+    cmd: notes-mcp
+"#
     }
 
     #[test]
-    fn test_write_mcp_config_is_noop() {
+    fn write_merges_managed_extensions_and_keeps_the_rest() {
         let dir = tempdir().unwrap();
+        let path = dir.path().join(".goose/config.yaml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, user_config()).unwrap();
+
         let mut servers = Map::new();
         servers.insert(
             "github".to_string(),
-            serde_json::json!({"command": "npx", "args": ["@modelcontextprotocol/server-github"]}),
+            json!({"command": "npx", "args": ["-y", "server-github"], "env": {"TOKEN": "t"}}),
+        );
+        servers.insert(
+            "linear".to_string(),
+            json!({"type": "http", "url": "https://mcp.linear.app/mcp"}),
         );
+        Goose.write_mcp_config(dir.path(), &servers).unwrap();
 
-        let result = Goose.write_mcp_config(dir.path(), &servers);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "");
+        let root = read_goose_config(&path).unwrap().unwrap();
+        assert_eq!(root["GOOSE_PROVIDER"], YamlValue::from("anthropic"));
+        assert_eq!(
+            managed_extension_names(&root),
+            ["github".to_string(), "linear".to_string()]
+        );
+        let extensions = root["extensions"].as_mapping().unwrap();
+        assert_eq!(extensions["developer"]["type"], YamlValue::from("builtin"));
+        assert_eq!(extensions["github"]["cmd"], YamlValue::from("npx"));
+        assert_eq!(extensions["github"]["envs"]["TOKEN"], YamlValue::from("t"));
+        assert_eq!(
+            extensions["linear"]["type"],
+            YamlValue::from("streamable_http")
+        );
+
+        // The anchored user extension still reads back, merge key and all.
+        let discovered = discover_goose_config(&path);
+        assert_eq!(discovered["notes"], json!({"command": "notes-mcp"}));
+        assert_eq!(discovered["github"]["env"], json!({"TOKEN": "t"}));
+
+        // Dropping a server removes only its managed entry.
+        servers.remove("linear");
+        Goose.write_mcp_config(dir.path(), &servers).unwrap();
+        let root = read_goose_config(&path).unwrap().unwrap();
+        let extensions = root["extensions"].as_mapping().unwrap();
+        assert!(!extensions.contains_key("linear"));
+        assert!(extensions.contains_key("notes"));
+        assert_eq!(managed_extension_names(&root), ["github".to_string()]);
+
+        // An unchanged config is not rewritten.
+        let before = fs::read_to_string(&path).unwrap();
+        fs::write(&path, format!("# kept\n{}", before)).unwrap();
+        Goose.write_mcp_config(dir.path(), &servers).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().starts_with("# kept\n"));
+    }
+
+    #[test]
+    fn write_refuses_invalid_yaml() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".goose/config.yaml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "extensions: [unclosed").unwrap();
+
+        assert!(Goose.write_mcp_config(dir.path(), &Map::new()).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "extensions: [unclosed");
+    }
 
-        // No files should have been written
-        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
-        assert!(entries.is_empty());
+    #[test]
+    fn cleanup_strips_only_managed_extensions() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".goose/config.yaml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, user_config()).unwrap();
+        let mut servers = Map::new();
+        servers.insert("github".to_string(), json!({"command": "npx"}));
+        Goose.write_mcp_config(dir.path(), &servers).unwrap();
+
+        assert_eq!(Goose.cleanup_mcp_config(dir.path()).len(), 1);
+        let root = read_goose_config(&path).unwrap().unwrap();
+        assert!(!root.contains_key(MANAGED_EXTENSIONS_KEY));
+        let extensions = root["extensions"].as_mapping().unwrap();
+        assert!(!extensions.contains_key("github"));
+        assert!(extensions.contains_key("developer"));
+        assert!(extensions.contains_key("notes"));
+
+        // Nothing managed is left, so a second cleanup changes nothing.
+        assert!(Goose.cleanup_mcp_config(dir.path()).is_empty());
+
+        // A file holding only Automatic's entries is removed.
+        let other = tempdir().unwrap();
+        Goose.write_mcp_config(other.path(), &servers).unwrap();
+        assert_eq!(Goose.cleanup_mcp_config(other.path()).len(), 1);
+        assert!(!other.path().join(".goose/config.yaml").exists());
     }
 
     #[test]
-    fn test_discover_mcp_servers_always_empty() {
+    fn discovery_overlays_project_config_on_global() {
         let dir = tempdir().unwrap();
-        // Even if someone manually put a .goose/mcp.json there, we no longer
-        // read it — project-level MCP discovery is not supported for Goose.
-        assert!(Goose.discover_mcp_servers(dir.path()).is_empty());
+        let global = dir.path().join("global.yaml");
+        fs::write(
+            &global,
+            r#"
+extensions:
+  github:
+    type: stdio
+    cmd: npx
+  memory:
+    type: stdio
+    cmd: memory-mcp
+"#,
+        )
+        .unwrap();
+        let project = dir.path().join("project.yaml");
+        fs::write(
+            &project,
+            r#"
+extensions:
+  github:
+    type: stdio
+    cmd: gh-mcp
+"#,
+        )
+        .unwrap();
+
+        let servers = discover_goose_servers(Some(&global), &project);
+        assert_eq!(servers["github"], json!({"command": "gh-mcp"}));
+        assert_eq!(servers["memory"], json!({"command": "memory-mcp"}));
+        assert!(discover_goose_servers(None, &dir.path().join("missing.yaml")).is_empty());
     }
 
     #[test]
@@ -522,7 +652,7 @@ extensions:
     timeout: 300
 "#;
         fs::write(dir.path().join("config.yaml"), config).unwrap();
-        let result = discover_goose_global_config(&dir.path().join("config.yaml"));
+        let result = discover_goose_config(&dir.path().join("config.yaml"));
 
         assert!(result.contains_key("github"));
         let gh = &result["github"];
@@ -559,7 +689,7 @@ extensions:
     timeout: 300
 "#;
         fs::write(dir.path().join("config.yaml"), config).unwrap();
-        let result = discover_goose_global_config(&dir.path().join("config.yaml"));
+        let result = discover_goose_config(&dir.path().join("config.yaml"));
 
         assert!(result.contains_key("mytool"), "mytool should be parsed");
         let tool = &result["mytool"];
@@ -584,7 +714,7 @@ extensions:
     timeout: 300
 "#;
         fs::write(dir.path().join("config.yaml"), config).unwrap();
-        let result = discover_goose_global_config(&dir.path().join("config.yaml"));
+        let result = discover_goose_config(&dir.path().join("config.yaml"));
 
         assert!(
             result.contains_key("linear"),
@@ -607,7 +737,7 @@ extensions:
     timeout: 300
 "#;
         fs::write(dir.path().join("config.yaml"), config).unwrap();
-        let result = discover_goose_global_config(&dir.path().join("config.yaml"));
+        let result = discover_goose_config(&dir.path().join("config.yaml"));
 
         assert!(
             !result.contains_key("developer"),
@@ -649,7 +779,7 @@ extensions:
     timeout: 300
 "#;
         fs::write(dir.path().join("config.yaml"), config).unwrap();
-        let result = discover_goose_global_config(&dir.path().join("config.yaml"));
+        let result = discover_goose_config(&dir.path().join("config.yaml"));
 
         // builtin should be absent
         assert!(!result.contains_key("developer"));
@@ -4,10 +4,46 @@ use std::path::{Path, PathBuf};
 
 use super::{discover_mcp_servers_from_json, sync_individual_skills, Agent};
 
-/// JetBrains Junie agent — writes `.junie/mcp.json` and stores skills under
-/// `<project>/.agents/skills/<name>/SKILL.md`.
+/// JetBrains Junie agent — writes `.junie/mcp/mcp.json` and stores skills
+/// under `<project>/.agents/skills/<name>/SKILL.md`.
+///
+/// ## MCP config
+///
+/// Junie reads project servers from `.junie/mcp/mcp.json` and user servers
+/// from `~/.junie/mcp/mcp.json`, both in the `mcpServers` format shared with
+/// Claude Code.  See <https://www.jetbrains.com/help/junie/model-context-protocol-mcp.html>.
+///
+/// Earlier versions of Automatic wrote `.junie/mcp.json`, which Junie never
+/// reads.  It is still discovered so its servers are not lost, and removed
+/// the next time the project is synced.
+///
+/// ## Project instructions
+///
+/// `.junie/guidelines.md`; see <https://www.jetbrains.com/help/junie/customize-guidelines.html>.
 pub struct Junie;
 
+/// Project MCP config, relative to the project root.
+const MCP_CONFIG: [&str; 3] = [".junie", "mcp", "mcp.json"];
+/// Where earlier versions of Automatic wrote the MCP config.
+const LEGACY_MCP_CONFIG: [&str; 2] = [".junie", "mcp.json"];
+
+fn config_path(dir: &Path, parts: &[&str]) -> PathBuf {
+    parts
+        .iter()
+        .fold(dir.to_path_buf(), |path, part| path.join(part))
+}
+
+/// Delete the `.junie/mcp.json` written by earlier versions.  Returns the
+/// path when a file was removed.
+fn remove_legacy_mcp_config(dir: &Path) -> Option<String> {
+    let path = config_path(dir, &LEGACY_MCP_CONFIG);
+    if path.is_file() && fs::remove_file(&path).is_ok() {
+        Some(path.display().to_string())
+    } else {
+        None
+    }
+}
+
 impl Agent for Junie {
     // ── Identity ────────────────────────────────────────────────────────
 
@@ -20,7 +56,7 @@ impl Agent for Junie {
     }
 
     fn config_description(&self) -> &'static str {
-        ".junie/mcp.json"
+        ".junie/mcp/mcp.json"
     }
 
     fn project_file_name(&self) -> &'static str {
@@ -30,7 +66,8 @@ impl Agent for Junie {
     // ── Detection ───────────────────────────────────────────────────────
 
     fn detect_in(&self, dir: &Path) -> bool {
-        dir.join(".junie").join("mcp.json").exists()
+        config_path(dir, &MCP_CONFIG).exists()
+            || config_path(dir, &LEGACY_MCP_CONFIG).exists()
             || dir.join(".junie").join("guidelines.md").exists()
     }
 
@@ -56,7 +93,8 @@ impl Agent for Junie {
     fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
         let junie_dir = dir.join(".junie");
         if junie_dir.exists() {
-            if fs::remove_dir_all(&junie_dir).is_ok() {
+            let context = crate::core::RemovalContext::project(dir);
+            if crate::core::remove_guarded(&junie_dir, &context).is_ok() {
                 return vec![junie_dir.display().to_string()];
             }
         }
@@ -97,20 +135,28 @@ impl Agent for Junie {
 
         let output = json!({ "mcpServers": Value::Object(junie_servers) });
 
-        let junie_dir = dir.join(".junie");
-        if !junie_dir.exists() {
-            fs::create_dir_all(&junie_dir)
-                .map_err(|e| format!("Failed to create .junie/: {}", e))?;
+        let path = config_path(dir, &MCP_CONFIG);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create .junie/mcp/: {}", e))?;
         }
 
-        let path = junie_dir.join("mcp.json");
-        let content =
-            serde_json::to_string_pretty(&output).map_err(|e| format!("JSON error: {}", e))?;
-        fs::write(&path, content).map_err(|e| format!("Failed to write .junie/mcp.json: {}", e))?;
+        let content = crate::core::to_stable_json(&output)?;
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write .junie/mcp/mcp.json: {}", e))?;
+
+        // Autodetection has already imported anything in the old file.
+        if let Some(removed) = remove_legacy_mcp_config(dir) {
+            eprintln!("[automatic] junie: removed unread {}", removed);
+        }
 
         Ok(path.display().to_string())
     }
 
+    fn annotated_config_path(&self, dir: &Path) -> Option<PathBuf> {
+        Some(config_path(dir, &MCP_CONFIG))
+    }
+
     fn sync_skills(
         &self,
         dir: &Path,
@@ -133,12 +179,20 @@ impl Agent for Junie {
     // ── Discovery ───────────────────────────────────────────────────────
 
     fn discover_mcp_servers(&self, dir: &Path) -> Map<String, Value> {
-        let path = dir.join(".junie").join("mcp.json");
-        if !path.exists() {
-            return Map::new();
+        // Junie's format matches Claude's — no normalisation needed.  The
+        // current file wins over the legacy one.
+        let mut servers = Map::new();
+        for parts in [&LEGACY_MCP_CONFIG[..], &MCP_CONFIG[..]] {
+            let path = config_path(dir, parts);
+            if path.exists() {
+                servers.extend(discover_mcp_servers_from_json(
+                    &path,
+                    "mcpServers",
+                    identity,
+                ));
+            }
         }
-        // Junie's format matches Claude's — no normalisation needed.
-        discover_mcp_servers_from_json(&path, "mcpServers", identity)
+        servers
     }
 
     fn detect_global_install(&self) -> bool {
@@ -159,8 +213,7 @@ impl Agent for Junie {
         let Some(home) = super::home_dir() else {
             return Map::new();
         };
-        // ~/.junie/mcp.json — speculative global config path (not yet documented)
-        let path = home.join(".junie").join("mcp.json");
+        let path = config_path(&home, &MCP_CONFIG);
         discover_mcp_servers_from_json(&path, "mcpServers", identity)
     }
 }
@@ -205,8 +258,8 @@ mod tests {
         let dir = tempdir().unwrap();
         assert!(!Junie.detect_in(dir.path()));
 
-        fs::create_dir_all(dir.path().join(".junie")).unwrap();
-        fs::write(dir.path().join(".junie/mcp.json"), "{}").unwrap();
+        fs::create_dir_all(dir.path().join(".junie/mcp")).unwrap();
+        fs::write(dir.path().join(".junie/mcp/mcp.json"), "{}").unwrap();
         assert!(Junie.detect_in(dir.path()));
     }
 
@@ -252,7 +305,7 @@ mod tests {
             .write_mcp_config(dir.path(), &stdio_servers())
             .unwrap();
 
-        let content = fs::read_to_string(dir.path().join(".junie/mcp.json")).unwrap();
+        let content = fs::read_to_string(dir.path().join(".junie/mcp/mcp.json")).unwrap();
         let parsed: Value = serde_json::from_str(&content).unwrap();
 
         // stdio entries should have "type" stripped
@@ -272,7 +325,7 @@ mod tests {
         let dir = tempdir().unwrap();
         Junie.write_mcp_config(dir.path(), &http_servers()).unwrap();
 
-        let content = fs::read_to_string(dir.path().join(".junie/mcp.json")).unwrap();
+        let content = fs::read_to_string(dir.path().join(".junie/mcp/mcp.json")).unwrap();
         let parsed: Value = serde_json::from_str(&content).unwrap();
 
         assert_eq!(
@@ -284,4 +337,55 @@ mod tests {
             "https://api.example.com/mcp"
         );
     }
+
+    #[test]
+    fn sync_migrates_the_unread_legacy_config() {
+        let dir = tempdir().unwrap();
+        let legacy = dir.path().join(".junie/mcp.json");
+        fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        fs::write(
+            &legacy,
+            r#"{"mcpServers":{"old":{"command":"old-server"},"github":{"command":"stale"}}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join(".junie/guidelines.md"), "# Guidelines").unwrap();
+
+        // Autodetection still finds the servers the old file listed.
+        assert!(Junie.detect_in(dir.path()));
+        let found = Junie.discover_mcp_servers(dir.path());
+        assert_eq!(found["old"]["command"], "old-server");
+
+        let written = Junie
+            .write_mcp_config(dir.path(), &stdio_servers())
+            .unwrap();
+        assert_eq!(
+            written,
+            dir.path().join(".junie/mcp/mcp.json").display().to_string()
+        );
+        assert!(!legacy.exists(), "legacy .junie/mcp.json should be removed");
+        assert!(dir.path().join(".junie/mcp/mcp.json").exists());
+        assert!(dir.path().join(".junie/guidelines.md").exists());
+
+        // Only the current file is left, so discovery reads what sync wrote.
+        let found = Junie.discover_mcp_servers(dir.path());
+        assert_eq!(found["github"]["command"], "npx");
+        assert!(found.get("old").is_none());
+        assert_eq!(remove_legacy_mcp_config(dir.path()), None);
+    }
+
+    #[test]
+    fn cleanup_removes_legacy_and_current_configs() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".junie/mcp")).unwrap();
+        fs::write(dir.path().join(".junie/mcp.json"), "{}").unwrap();
+        fs::write(dir.path().join(".junie/mcp/mcp.json"), "{}").unwrap();
+
+        let preview = Junie.cleanup_mcp_preview(dir.path());
+        assert_eq!(
+            preview,
+            vec![dir.path().join(".junie").display().to_string()]
+        );
+        Junie.cleanup_mcp_config(dir.path());
+        assert!(!dir.path().join(".junie").exists());
+    }
 }
@@ -54,7 +54,8 @@ impl Agent for Kiro {
     fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
         let kiro_dir = dir.join(".kiro");
         if kiro_dir.exists() {
-            if fs::remove_dir_all(&kiro_dir).is_ok() {
+            let context = crate::core::RemovalContext::project(dir);
+            if crate::core::remove_guarded(&kiro_dir, &context).is_ok() {
                 return vec![kiro_dir.display().to_string()];
             }
         }
@@ -103,14 +104,17 @@ impl Agent for Kiro {
         }
 
         let path = settings_dir.join("mcp.json");
-        let content =
-            serde_json::to_string_pretty(&output).map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&output)?;
         fs::write(&path, content)
             .map_err(|e| format!("Failed to write .kiro/settings/mcp.json: {}", e))?;
 
         Ok(path.display().to_string())
     }
 
+    fn annotated_config_path(&self, dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(".kiro").join("settings").join("mcp.json"))
+    }
+
     fn sync_skills(
         &self,
         dir: &Path,
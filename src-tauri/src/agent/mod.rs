@@ -11,6 +11,7 @@
 //! Everything else (sync, autodetect, the frontend agent list) picks it up
 //! automatically.
 
+mod aider;
 mod antigravity;
 mod claude_code;
 mod cline;
@@ -33,21 +34,34 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub use aider::Aider;
 pub use antigravity::Antigravity;
-pub use claude_code::ClaudeCode;
+pub use claude_code::{
+    claude_local_scope_state, discover_claude_local_servers, managed_claude_local_servers,
+    split_claude_servers_by_scope, write_claude_scoped_mcp_config, ClaudeCode,
+};
 pub use cline::Cline;
 pub use codex_cli::CodexCli;
-pub use cursor::Cursor;
+pub use cursor::{
+    discover_cursor_global_config, discover_cursor_global_rules, global_mcp_config_path,
+    global_rules_dir, remove_servers_from_cursor_config, Cursor, CursorRuleCandidate,
+};
 pub use droid::Droid;
-pub use gemini_cli::GeminiCli;
-pub use github_copilot::GitHubCopilot;
+pub use gemini_cli::{
+    discover_gemini_extension_servers, gemini_extension_dir, gemini_extension_files,
+    remove_gemini_extension, write_gemini_extension, GeminiCli, GEMINI_EXTENSION_NAME,
+};
+pub use github_copilot::{
+    is_copilot_instruction_file, is_copilot_scoped_file, list_copilot_instruction_files,
+    list_copilot_prompt_files, with_default_instruction_frontmatter, GitHubCopilot,
+};
 pub use goose::Goose;
 pub use junie::Junie;
 pub use kilo_code::KiloCode;
 pub use kiro::Kiro;
 pub use opencode::{
-    clean_opencode_snapshots, clear_opencode_cache, CleanSnapshotsResult, ClearCacheResult,
-    OpenCode,
+    clean_opencode_snapshots, clear_opencode_cache, opencode_skill_permission_state,
+    write_opencode_skill_permissions, CleanSnapshotsResult, ClearCacheResult, OpenCode,
 };
 pub use warp::Warp;
 pub use zed::Zed;
@@ -92,6 +106,18 @@ impl Default for AgentCapabilities {
     }
 }
 
+/// A project's MCP servers ready to paste into an agent whose MCP config
+/// Automatic cannot write (see [`Agent::export_mcp_setup`]).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AgentMcpExport {
+    /// Format of `content`, e.g. `"json"`.
+    pub format: &'static str,
+    /// Text to paste, including Automatic's own server.
+    pub content: String,
+    /// Where in the agent to paste it.
+    pub instructions: String,
+}
+
 // ── Trait ────────────────────────────────────────────────────────────────────
 
 /// The contract every agent type must fulfil.
@@ -114,6 +140,17 @@ pub trait Agent: Send + Sync {
     /// (e.g. `"CLAUDE.md"` for Claude Code, `"AGENTS.md"` for Codex).
     fn project_file_name(&self) -> &'static str;
 
+    /// Additional instruction files this agent reads alongside
+    /// [`project_file_name`], as paths relative to `dir` (e.g. Copilot's
+    /// `.github/instructions/*.instructions.md`).  They are listed in the
+    /// instructions editor and receive per-file rules during sync, but
+    /// Automatic never replicates or deletes them.
+    ///
+    /// Default: empty vec.
+    fn extra_instruction_files(&self, _dir: &Path) -> Vec<String> {
+        vec![]
+    }
+
     // ── Detection ───────────────────────────────────────────────────────
 
     /// Returns `true` if this agent appears to be in use in `dir`.
@@ -171,6 +208,46 @@ pub trait Agent: Send + Sync {
         None
     }
 
+    /// For agents Automatic cannot configure but whose MCP settings accept
+    /// pasted JSON: `servers` in that format, for the user to copy.
+    ///
+    /// `None` (the default) means there is nothing to paste.
+    fn mcp_config_snippet(&self, _servers: &Map<String, Value>) -> Option<String> {
+        None
+    }
+
+    /// [`mcp_config_snippet`](Agent::mcp_config_snippet) with instructions
+    /// for where to paste it.  The default uses the [`mcp_note`](Agent::mcp_note)
+    /// as the instructions.
+    ///
+    /// `None` when the agent has no snippet format.
+    fn export_mcp_setup(&self, servers: &Map<String, Value>) -> Option<AgentMcpExport> {
+        let content = self.mcp_config_snippet(servers)?;
+        Some(AgentMcpExport {
+            format: "json",
+            content,
+            instructions: self.mcp_note().unwrap_or_default().to_string(),
+        })
+    }
+
+    /// Advisory number of MCP servers past which this agent is known to
+    /// degrade (every server's tool list is sent with each request).  Only
+    /// used to warn; sync writes every selected server regardless.
+    ///
+    /// `None` (the default) means no known limit.
+    fn recommended_max_servers(&self) -> Option<usize> {
+        None
+    }
+
+    /// The generated JSON MCP config that may carry Automatic's
+    /// `"$automatic"` provenance key.  Only agents that ignore unknown
+    /// top-level keys in that file return a path.
+    ///
+    /// `None` (the default) leaves the config unannotated.
+    fn annotated_config_path(&self, _dir: &Path) -> Option<PathBuf> {
+        None
+    }
+
     // ── Discovery ───────────────────────────────────────────────────────
 
     /// Scan this agent's config files in `dir` for MCP server definitions.
@@ -261,6 +338,35 @@ pub trait Agent: Send + Sync {
         render_markdown_command(content)
     }
 
+    // ── Native rules ────────────────────────────────────────────────────
+
+    /// Return the directory where this agent reads one file per rule (e.g.
+    /// Cursor's `.cursor/rules/`).  Returns `None` if this agent only takes
+    /// rules through its instruction file.
+    fn rules_dir(&self, _dir: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    /// Render rule `name` as the file Automatic writes into [`rules_dir`],
+    /// as `(file name, content)`.  File names must start with
+    /// [`MANAGED_RULE_PREFIX`] so cleanup can tell them from the user's own.
+    fn rule_file(&self, name: &str, content: &str) -> (String, String) {
+        (
+            format!("{MANAGED_RULE_PREFIX}{name}.md"),
+            format!("{}\n", content.trim_end()),
+        )
+    }
+
+    /// Write the project's rules, as `(name, content)`, into this agent's
+    /// native rules location, and remove Automatic-managed rule files that
+    /// are no longer assigned.  Returns the paths written or removed.
+    ///
+    /// Default: no-op — rules reach the agent through its instruction file
+    /// only.  Agents with a [`rules_dir`] delegate to [`sync_rule_files`].
+    fn write_rules(&self, _dir: &Path, _rules: &[(String, String)]) -> Result<Vec<String>, String> {
+        Ok(vec![])
+    }
+
     // ── Cleanup ─────────────────────────────────────────────────────────
 
     /// Paths of MCP config files that are exclusively owned by Automatic for
@@ -280,17 +386,18 @@ pub trait Agent: Send + Sync {
     /// Remove MCP configuration written by this agent from the project directory.
     /// Called when the agent is removed from a project.
     ///
-    /// The default implementation deletes every file returned by
-    /// [`owned_config_paths`] that exists on disk.  Agents that merge into
-    /// shared config files should override this to strip only their managed
-    /// sections rather than deleting the whole file.
+    /// The default implementation deletes (via `remove_guarded`) every file
+    /// returned by [`owned_config_paths`] that exists on disk.  Agents that
+    /// merge into shared config files should override this to strip only
+    /// their managed sections rather than deleting the whole file.
     ///
     /// Returns paths of files deleted or modified.
     fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
         let mut removed = Vec::new();
+        let context = crate::core::RemovalContext::project(dir);
         for path in self.owned_config_paths(dir) {
             if path.exists() {
-                if fs::remove_file(&path).is_ok() {
+                if crate::core::remove_guarded(&path, &context).is_ok() {
                     removed.push(path.display().to_string());
                 }
             }
@@ -325,6 +432,11 @@ pub struct AgentInfo {
     /// Human-readable note about MCP limitations, if any.
     /// `None` means Automatic manages MCP config for this agent normally.
     pub mcp_note: Option<String>,
+    /// Whether the agent accepts a pasted MCP config snippet
+    /// (see [`Agent::mcp_config_snippet`]).
+    pub mcp_snippet: bool,
+    /// See [`Agent::recommended_max_servers`].
+    pub recommended_max_servers: Option<usize>,
 }
 
 impl AgentInfo {
@@ -335,10 +447,139 @@ impl AgentInfo {
             description: agent.config_description().to_string(),
             capabilities: agent.capabilities(),
             mcp_note: agent.mcp_note().map(|s| s.to_string()),
+            mcp_snippet: agent.mcp_config_snippet(&Map::new()).is_some(),
+            recommended_max_servers: agent.recommended_max_servers(),
         }
     }
 }
 
+/// One agent with the projects that use it, for the Agents page and the
+/// dashboard.
+#[derive(Debug, Serialize)]
+pub struct AgentWithProjects {
+    pub info: AgentInfo,
+    pub project_file: String,
+    pub projects: Vec<AgentProjectRef>,
+    pub totals: AgentTotals,
+}
+
+/// A project that has the agent enabled.
+#[derive(Debug, Serialize)]
+pub struct AgentProjectRef {
+    pub name: String,
+    pub directory: String,
+    /// Skills delivered to the agent by this project (global and local).
+    pub skill_count: usize,
+    /// Timestamp of the last recorded sync, when known.
+    pub last_sync: Option<String>,
+    /// Whether the agent's files had drifted at the last drift check; `None`
+    /// when the project has not been checked in this session.
+    pub drifted: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct AgentTotals {
+    pub project_count: usize,
+    /// Projects whose last drift check found this agent drifted; `None` when
+    /// none of the projects has been checked yet.
+    pub drifted_count: Option<usize>,
+    /// Distinct skills delivered to the agent across all its projects.
+    pub skill_count: usize,
+}
+
+/// Ordering for [`agents_with_projects`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AgentSort {
+    #[default]
+    Label,
+    /// Most projects first, then by label.
+    ProjectCount,
+}
+
+impl AgentSort {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "label" => Ok(Self::Label),
+            "project_count" => Ok(Self::ProjectCount),
+            other => Err(format!(
+                "Unknown sort '{}'; expected 'label' or 'project_count'",
+                other
+            )),
+        }
+    }
+}
+
+/// Pair each agent with the `projects` that enable it.  `last_sync` maps
+/// project names to their last sync time; `drifted_agents` returns the
+/// cached drift result for a project, if any.  Neither is required to be
+/// complete, and no drift check is run here.
+pub fn agents_with_projects(
+    agents: &[&dyn Agent],
+    projects: &[crate::core::Project],
+    last_sync: &HashMap<String, String>,
+    drifted_agents: impl Fn(&str) -> Option<Vec<String>>,
+    sort: AgentSort,
+) -> Vec<AgentWithProjects> {
+    let drift: HashMap<&str, Vec<String>> = projects
+        .iter()
+        .filter_map(|p| drifted_agents(&p.name).map(|d| (p.name.as_str(), d)))
+        .collect();
+
+    let mut result: Vec<AgentWithProjects> = agents
+        .iter()
+        .map(|a| {
+            let mut skills: HashSet<&str> = HashSet::new();
+            let refs: Vec<AgentProjectRef> = projects
+                .iter()
+                .filter(|p| p.agents.iter().any(|id| id == a.id()))
+                .map(|p| {
+                    let project_skills: HashSet<&str> = p
+                        .skills
+                        .iter()
+                        .chain(p.local_skills.iter())
+                        .map(String::as_str)
+                        .collect();
+                    skills.extend(&project_skills);
+                    AgentProjectRef {
+                        name: p.name.clone(),
+                        directory: p.directory.clone(),
+                        skill_count: project_skills.len(),
+                        last_sync: last_sync.get(&p.name).cloned(),
+                        drifted: drift
+                            .get(p.name.as_str())
+                            .map(|ids| ids.iter().any(|id| id == a.id())),
+                    }
+                })
+                .collect();
+
+            let checked: Vec<bool> = refs.iter().filter_map(|r| r.drifted).collect();
+            let totals = AgentTotals {
+                project_count: refs.len(),
+                drifted_count: (!checked.is_empty())
+                    .then(|| checked.iter().filter(|d| **d).count()),
+                skill_count: skills.len(),
+            };
+            AgentWithProjects {
+                info: AgentInfo::from_agent(*a),
+                project_file: a.project_file_name().to_string(),
+                projects: refs,
+                totals,
+            }
+        })
+        .collect();
+
+    match sort {
+        AgentSort::Label => result.sort_by(|a, b| a.info.label.cmp(&b.info.label)),
+        AgentSort::ProjectCount => result.sort_by(|a, b| {
+            b.totals
+                .project_count
+                .cmp(&a.totals.project_count)
+                .then_with(|| a.info.label.cmp(&b.info.label))
+        }),
+    }
+    result
+}
+
 // ── Registry ────────────────────────────────────────────────────────────────
 
 /// Returns every registered agent instance, sorted alphabetically by label.
@@ -347,6 +588,7 @@ impl AgentInfo {
 /// the vec is sorted before it is returned).
 pub fn all() -> Vec<&'static dyn Agent> {
     let mut agents: Vec<&'static dyn Agent> = vec![
+        &Aider,
         &ClaudeCode,
         &Cursor,
         &GitHubCopilot,
@@ -412,7 +654,9 @@ pub(crate) fn sync_individual_skills(
 /// directories will symlink to.
 ///
 /// Each skill directory is copied recursively so that companion files
-/// (`scripts/`, `docs/`, etc.) are included, not just `SKILL.md`.
+/// (`scripts/`, `docs/`, etc.) are included, not just `SKILL.md`.  Entries the
+/// copier had to leave out are reported in `written` as
+/// `"<dir> (partial copy, skipped <entry>)"`.
 ///
 /// `skill_contents` is used as a fallback: if a skill's source directory
 /// cannot be found in the global registry, the SKILL.md content is written
@@ -430,22 +674,21 @@ pub(crate) fn copy_skills_to_project(
         let target_dir = project_skills_dir.join(name);
 
         // Remove existing entry so we get a clean copy
-        if let Ok(meta) = target_dir.symlink_metadata() {
-            if meta.file_type().is_symlink() {
-                let _ = fs::remove_file(&target_dir);
-            } else if meta.is_dir() {
-                let _ = fs::remove_dir_all(&target_dir);
-            }
-        }
+        let _ = crate::core::remove_guarded(
+            &target_dir,
+            &crate::core::RemovalContext::project(project_skills_dir),
+        );
 
         // Try to copy the full directory from the global registry
-        let copied = if let Ok(Some(src_dir)) = crate::core::get_skill_dir(name) {
-            copy_dir_recursive(&src_dir, &target_dir).is_ok()
-        } else {
-            false
+        let (copied, skipped) = match crate::core::get_skill_dir(name) {
+            Ok(Some(src_dir)) => match crate::core::copy_skill_dir(&src_dir, &target_dir) {
+                Ok(report) => (true, report.skipped),
+                Err(_) => (false, Vec::new()),
+            },
+            _ => (false, Vec::new()),
         };
 
-        if !copied {
+        if !copied || !target_dir.join("SKILL.md").is_file() {
             // Fallback: write just SKILL.md
             fs::create_dir_all(&target_dir)
                 .map_err(|e| format!("Failed to create skill dir: {}", e))?;
@@ -454,6 +697,13 @@ pub(crate) fn copy_skills_to_project(
         }
 
         written.push(target_dir.display().to_string());
+        for entry in skipped {
+            written.push(format!(
+                "{} (partial copy, skipped {})",
+                target_dir.display(),
+                entry
+            ));
+        }
     }
     Ok(())
 }
@@ -477,20 +727,18 @@ pub(crate) fn symlink_skills_from_project(
     cleanup_skill_dir(agent_skills_dir, selected_skill_names, preserve_names)?;
 
     let settings = crate::core::read_settings().unwrap_or_default();
-    let use_symlink = settings.sync_mode == "symlink";
+    let use_symlink = skill_delivery(agent_skills_dir, project_skills_dir, &settings.sync_mode)
+        == SkillDelivery::Symlink;
 
     for (name, content) in skills {
         let link_path = agent_skills_dir.join(name);
         let target_dir = project_skills_dir.join(name);
 
         // Remove existing entry
-        if let Ok(meta) = link_path.symlink_metadata() {
-            if meta.file_type().is_symlink() {
-                let _ = fs::remove_file(&link_path);
-            } else if meta.is_dir() {
-                let _ = fs::remove_dir_all(&link_path);
-            }
-        }
+        let _ = crate::core::remove_guarded(
+            &link_path,
+            &crate::core::RemovalContext::project(agent_skills_dir),
+        );
 
         let mut linked = false;
         if use_symlink && target_dir.exists() {
@@ -521,6 +769,36 @@ pub(crate) fn symlink_skills_from_project(
     Ok(())
 }
 
+/// How sync delivers a skill into one agent skill directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillDelivery {
+    /// The directory is the project hub (`.agents/skills/`): the whole skill
+    /// directory, companion files included, is copied from the registry.
+    HubCopy,
+    /// A directory symlink pointing at the hub copy.
+    Symlink,
+    /// A standalone copy of `SKILL.md` only (`sync_mode` is `"copy"`).
+    Copy,
+}
+
+/// Decide how [`copy_skills_to_project`] / [`symlink_skills_from_project`]
+/// deliver skills into `agent_skills_dir`.  Pure, so previews can use the
+/// same rule as sync.
+pub(crate) fn skill_delivery(
+    agent_skills_dir: &Path,
+    project_skills_dir: &Path,
+    sync_mode: &str,
+) -> SkillDelivery {
+    if agent_skills_dir == project_skills_dir {
+        SkillDelivery::HubCopy
+    } else if sync_mode == "symlink" {
+        SkillDelivery::Symlink
+    } else {
+        SkillDelivery::Copy
+    }
+}
+
 /// Remove skill entries from `base_dir` that are not in the selected set
 /// and not in the preserve set.  Handles both real directories and symlinks.
 fn cleanup_skill_dir(
@@ -535,6 +813,7 @@ fn cleanup_skill_dir(
         return Ok(());
     }
 
+    let context = crate::core::RemovalContext::project(base_dir);
     for entry in fs::read_dir(base_dir)
         .map_err(|e| format!("Failed to read {}: {}", base_dir.display(), e))?
     {
@@ -555,15 +834,7 @@ fn cleanup_skill_dir(
                 && !selected.contains(name)
                 && !preserved.contains(name)
             {
-                if meta.file_type().is_symlink() {
-                    fs::remove_file(&path).map_err(|e| {
-                        format!("Failed to remove skill symlink '{}': {}", path.display(), e)
-                    })?;
-                } else {
-                    fs::remove_dir_all(&path).map_err(|e| {
-                        format!("Failed to remove skill dir '{}': {}", path.display(), e)
-                    })?;
-                }
+                crate::core::remove_guarded(&path, &context)?;
             }
         }
     }
@@ -754,6 +1025,96 @@ pub(crate) fn sync_commands_to_dir(
     Ok(written)
 }
 
+/// Prefix of every rule file Automatic writes into an agent's
+/// [`Agent::rules_dir`].  Files without it belong to the user and are never
+/// touched.
+pub const MANAGED_RULE_PREFIX: &str = "automatic-";
+
+/// The rule files `agent_instance` should have for `rules`, as absolute
+/// paths with their content.  Empty when the agent has no rules directory.
+pub(crate) fn expected_rule_files(
+    agent_instance: &dyn Agent,
+    dir: &Path,
+    rules: &[(String, String)],
+) -> Vec<(PathBuf, String)> {
+    let Some(rules_dir) = agent_instance.rules_dir(dir) else {
+        return vec![];
+    };
+    rules
+        .iter()
+        .filter(|(_, content)| !content.trim().is_empty())
+        .map(|(name, content)| {
+            let (file_name, rendered) = agent_instance.rule_file(name, content);
+            (rules_dir.join(file_name), rendered)
+        })
+        .collect()
+}
+
+/// Automatic-managed rule files currently in the agent's rules directory,
+/// sorted.
+pub(crate) fn managed_rule_files(agent_instance: &dyn Agent, dir: &Path) -> Vec<PathBuf> {
+    let Some(rules_dir) = agent_instance.rules_dir(dir) else {
+        return vec![];
+    };
+    let Ok(entries) = fs::read_dir(&rules_dir) else {
+        return vec![];
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(MANAGED_RULE_PREFIX))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Shared [`Agent::write_rules`] for agents with a rules directory: write
+/// each rule file whose content changed, delete managed files for rules no
+/// longer assigned, and remove the directory once it is empty.
+pub(crate) fn sync_rule_files(
+    agent_instance: &dyn Agent,
+    dir: &Path,
+    rules: &[(String, String)],
+) -> Result<Vec<String>, String> {
+    let Some(rules_dir) = agent_instance.rules_dir(dir) else {
+        return Ok(vec![]);
+    };
+    let expected = expected_rule_files(agent_instance, dir, rules);
+    let mut touched = Vec::new();
+
+    for path in managed_rule_files(agent_instance, dir) {
+        if expected.iter().any(|(p, _)| *p == path) {
+            continue;
+        }
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove stale rule '{}': {}", path.display(), e))?;
+        touched.push(path.display().to_string());
+    }
+
+    if !expected.is_empty() {
+        fs::create_dir_all(&rules_dir)
+            .map_err(|e| format!("Failed to create {}: {}", rules_dir.display(), e))?;
+    }
+    for (path, content) in &expected {
+        if fs::read_to_string(path).ok().as_deref() == Some(content.as_str()) {
+            continue;
+        }
+        fs::write(path, content)
+            .map_err(|e| format!("Failed to write rule '{}': {}", path.display(), e))?;
+        touched.push(path.display().to_string());
+    }
+
+    if expected.is_empty() {
+        let _ = fs::remove_dir(&rules_dir); // silently ignored when non-empty
+    }
+    Ok(touched)
+}
+
 fn cleanup_command_files(agent_instance: &dyn Agent, dir: &Path) -> Vec<String> {
     let Some(commands_dir) = agent_instance.commands_dir(dir) else {
         return vec![];
@@ -803,34 +1164,6 @@ fn cleanup_command_preview(agent_instance: &dyn Agent, dir: &Path) -> Vec<String
     preview
 }
 
-/// Recursively copy a directory and all its contents.
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
-    fs::create_dir_all(dst)
-        .map_err(|e| format!("Failed to create dir '{}': {}", dst.display(), e))?;
-
-    for entry in
-        fs::read_dir(src).map_err(|e| format!("Failed to read dir '{}': {}", src.display(), e))?
-    {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
-
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            fs::copy(&src_path, &dst_path).map_err(|e| {
-                format!(
-                    "Failed to copy '{}' -> '{}': {}",
-                    src_path.display(),
-                    dst_path.display(),
-                    e
-                )
-            })?;
-        }
-    }
-    Ok(())
-}
-
 /// Remove all Automatic-managed resources for a specific agent from a project
 /// directory.  Called after the user confirms removal of an agent.
 ///
@@ -854,11 +1187,16 @@ pub(crate) fn cleanup_agent_from_project(
     // 1. Clean up MCP config
     removed.extend(agent_instance.cleanup_mcp_config(dir));
     removed.extend(cleanup_command_files(agent_instance, dir));
+    match agent_instance.write_rules(dir, &[]) {
+        Ok(touched) => removed.extend(touched),
+        Err(e) => eprintln!("[automatic] Failed to remove managed rule files: {}", e),
+    }
 
     // 2. Remove agent-specific skill directories (never the shared hub)
+    let context = crate::core::RemovalContext::project(dir);
     for skill_dir in agent_instance.skill_dirs(dir) {
         if skill_dir != hub && skill_dir.exists() {
-            if fs::remove_dir_all(&skill_dir).is_ok() {
+            if crate::core::remove_guarded(&skill_dir, &context).is_ok() {
                 removed.push(skill_dir.display().to_string());
             }
         }
@@ -870,7 +1208,7 @@ pub(crate) fn cleanup_agent_from_project(
         .any(|id| from_id(id).map_or(false, |a| a.skill_dirs(dir).iter().any(|d| d == &hub)));
 
     if !remaining_uses_hub && hub.exists() {
-        if fs::remove_dir_all(&hub).is_ok() {
+        if crate::core::remove_guarded(&hub, &context).is_ok() {
             removed.push(hub.display().to_string());
             // Attempt to remove the parent .agents/ dir if it is now empty
             let agents_dir = dir.join(".agents");
@@ -895,6 +1233,11 @@ pub(crate) fn cleanup_agent_preview(
     // MCP config files
     preview.extend(agent_instance.cleanup_mcp_preview(dir));
     preview.extend(cleanup_command_preview(agent_instance, dir));
+    preview.extend(
+        managed_rule_files(agent_instance, dir)
+            .into_iter()
+            .map(|p| p.display().to_string()),
+    );
 
     // Agent-specific skill directories
     for skill_dir in agent_instance.skill_dirs(dir) {
@@ -1004,12 +1347,11 @@ pub(crate) fn discover_mcp_servers_from_json(
     };
 
     for (name, config) in servers_obj {
-        // Skip Automatic-managed entries — "automatic" (current) and "nexus"
-        // (legacy name, pre-rename) are always injected at sync/drift-check
-        // time from the live binary path and project name.  Storing them in
-        // the shared registry would pollute every other project's drift
-        // baseline with a stale project name.
-        if name == "automatic" || name == "nexus" || !crate::core::is_valid_name(name) {
+        // Skip Automatic-managed entries — they are always injected at
+        // sync/drift-check time from the live binary path and project name.
+        // Storing them in the shared registry would pollute every other
+        // project's drift baseline with a stale project name.
+        if crate::core::is_managed_name(name) || !crate::core::is_valid_name(name) {
             continue;
         }
         result.insert(name.clone(), normalise(config.clone()));
@@ -1018,6 +1360,16 @@ pub(crate) fn discover_mcp_servers_from_json(
     result
 }
 
+/// Convert a server definition copied from any agent's config to
+/// Automatic's canonical format.  Each agent's import normaliser only acts
+/// on the fields peculiar to that agent, so they are applied in turn; Zed's
+/// last, as it fills in the `type` most pasted snippets leave out.
+pub(crate) fn normalise_foreign_server(config: Value) -> Value {
+    zed::normalise_zed_server(antigravity::normalise_import(opencode::normalise_import(
+        config,
+    )))
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -1041,4 +1393,154 @@ mod tests {
         let unique: HashSet<&str> = ids.iter().copied().collect();
         assert_eq!(ids.len(), unique.len());
     }
+
+    fn project(name: &str, agents: &[&str], skills: &[&str]) -> crate::core::Project {
+        crate::core::Project {
+            name: name.into(),
+            directory: format!("/work/{}", name),
+            agents: agents.iter().map(|a| a.to_string()).collect(),
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn fixture_projects() -> Vec<crate::core::Project> {
+        let mut web = project("web", &["claude", "cursor"], &["react", "testing"]);
+        web.local_skills = vec!["house-style".into()];
+        vec![
+            web,
+            project("api", &["claude"], &["testing", "sql"]),
+            project("docs", &["cursor"], &[]),
+        ]
+    }
+
+    #[test]
+    fn agents_with_projects_counts_projects_and_skills() {
+        let agents: Vec<&dyn Agent> = vec![&ClaudeCode, &Cursor, &Zed];
+        let last_sync = HashMap::from([("web".to_string(), "2026-01-02T00:00:00Z".to_string())]);
+        let result = agents_with_projects(
+            &agents,
+            &fixture_projects(),
+            &last_sync,
+            |_| None,
+            AgentSort::Label,
+        );
+
+        let claude = result.iter().find(|a| a.info.id == "claude").unwrap();
+        assert_eq!(
+            claude.totals,
+            AgentTotals {
+                project_count: 2,
+                drifted_count: None,
+                skill_count: 4,
+            }
+        );
+        let web = &claude.projects[0];
+        assert_eq!(web.skill_count, 3);
+        assert_eq!(web.last_sync.as_deref(), Some("2026-01-02T00:00:00Z"));
+        assert_eq!(claude.projects[1].last_sync, None);
+
+        let zed = result.iter().find(|a| a.info.id == "zed").unwrap();
+        assert_eq!(zed.totals, AgentTotals::default());
+    }
+
+    #[test]
+    fn agents_with_projects_joins_cached_drift_only() {
+        let agents: Vec<&dyn Agent> = vec![&ClaudeCode, &Cursor];
+        let result = agents_with_projects(
+            &agents,
+            &fixture_projects(),
+            &HashMap::new(),
+            |name| (name == "web").then(|| vec!["cursor".to_string()]),
+            AgentSort::Label,
+        );
+
+        let claude = result.iter().find(|a| a.info.id == "claude").unwrap();
+        assert_eq!(claude.totals.drifted_count, Some(0));
+        assert_eq!(claude.projects[0].drifted, Some(false));
+        assert_eq!(claude.projects[1].drifted, None);
+
+        let cursor = result.iter().find(|a| a.info.id == "cursor").unwrap();
+        assert_eq!(cursor.totals.drifted_count, Some(1));
+    }
+
+    #[test]
+    fn agents_with_projects_sorts_by_label_or_project_count() {
+        let agents: Vec<&dyn Agent> = vec![&Zed, &Cursor, &ClaudeCode];
+        let mut projects = fixture_projects();
+        projects.push(project("extra", &["cursor"], &[]));
+        let ids = |sort| -> Vec<String> {
+            agents_with_projects(&agents, &projects, &HashMap::new(), |_| None, sort)
+                .into_iter()
+                .map(|a| a.info.id)
+                .collect()
+        };
+        assert_eq!(ids(AgentSort::Label), vec!["claude", "cursor", "zed"]);
+        assert_eq!(
+            ids(AgentSort::ProjectCount),
+            vec!["cursor", "claude", "zed"]
+        );
+
+        assert_eq!(
+            AgentSort::parse("project_count"),
+            Ok(AgentSort::ProjectCount)
+        );
+        assert!(AgentSort::parse("size").is_err());
+    }
+
+    #[test]
+    fn only_lenient_json_configs_are_annotated() {
+        let dir = Path::new("/work/web");
+        let mut annotated: Vec<&str> = all()
+            .into_iter()
+            .filter(|a| a.annotated_config_path(dir).is_some())
+            .map(|a| a.id())
+            .collect();
+        annotated.sort_unstable();
+        assert_eq!(
+            annotated,
+            vec!["claude", "cline", "cursor", "droid", "junie", "kilo", "kiro"]
+        );
+    }
+
+    #[test]
+    fn discovery_skips_the_provenance_key() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".cursor")).unwrap();
+        let path = dir.path().join(".cursor").join("mcp.json");
+        std::fs::write(&path, r#"{"mcpServers":{"db":{"command":"db-mcp"}}}"#).unwrap();
+        crate::core::annotate_json_config(&path, "web", None).unwrap();
+
+        let servers = discover_mcp_servers_from_json(&path, "mcpServers", |v| v);
+        assert_eq!(servers.keys().collect::<Vec<_>>(), vec!["db"]);
+        let discovered = Cursor.discover_mcp_servers(dir.path());
+        assert!(!discovered.contains_key(crate::core::CONFIG_METADATA_KEY));
+        assert!(discovered.contains_key("db"));
+    }
+
+    #[test]
+    fn discovery_skips_exactly_the_managed_names() {
+        let names = ["automatic", "nexus", "Automatic", "automatic-dev", "github"];
+        let servers: Map<String, Value> = names
+            .iter()
+            .map(|n| (n.to_string(), serde_json::json!({"command": "x"})))
+            .collect();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".mcp.json");
+        std::fs::write(
+            &path,
+            serde_json::json!({ "mcpServers": servers }).to_string(),
+        )
+        .unwrap();
+
+        let discovered = discover_mcp_servers_from_json(&path, "mcpServers", |v| v);
+        for name in names {
+            assert_eq!(
+                discovered.contains_key(name),
+                !crate::core::is_managed_name(name),
+                "{}",
+                name
+            );
+        }
+    }
 }
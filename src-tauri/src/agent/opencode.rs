@@ -1,5 +1,6 @@
 use rusqlite::Connection;
 use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -58,6 +59,14 @@ impl Agent for OpenCode {
         vec![dir.join("opencode.json")]
     }
 
+    /// `opencode.json` may hold the user's own OpenCode settings, so only
+    /// the `mcp` key is stripped and the skill permission Automatic set is
+    /// put back the way the user had it.  The file is deleted when nothing
+    /// else is left in it.
+    fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
+        cleanup_config_at(skill_permission_ledger_path().ok().as_deref(), dir)
+    }
+
     // ── Config writing ──────────────────────────────────────────────────
 
     fn write_mcp_config(&self, dir: &Path, servers: &Map<String, Value>) -> Result<String, String> {
@@ -125,10 +134,13 @@ impl Agent for OpenCode {
             oc_servers.insert(name.clone(), Value::Object(server));
         }
 
-        let output = json!({ "$schema": "https://opencode.ai/config.json", "mcp": Value::Object(oc_servers) });
+        // Merge into the existing file: it may hold the user's own OpenCode
+        // settings and the skill permission written at sync.
         let path = dir.join("opencode.json");
-        let content =
-            serde_json::to_string_pretty(&output).map_err(|e| format!("JSON error: {}", e))?;
+        let mut root = read_config(&path)?;
+        root.insert("$schema".to_string(), json!(OPENCODE_SCHEMA));
+        root.insert("mcp".to_string(), Value::Object(oc_servers));
+        let content = crate::core::to_stable_json(&Value::Object(root))?;
         fs::write(&path, content).map_err(|e| format!("Failed to write opencode.json: {}", e))?;
 
         Ok(path.display().to_string())
@@ -218,12 +230,187 @@ impl Agent for OpenCode {
     }
 }
 
+// ── Project-scoped skills ────────────────────────────────────────────────────
+//
+// OpenCode loads every skill in `~/.agents/skills` (and `~/.claude/skills`)
+// on top of the project's own, so Automatic's per-project selection would
+// not hold.  Skill discovery paths are not configurable, but skill access is:
+// sync sets `permission.skill` in `opencode.json` to deny every skill except
+// the ones synced into the project.  The user's own `permission.skill`, if
+// any, is recorded in a ledger in `~/.automatic` the first time it is
+// replaced, and put back when OpenCode is removed from the project.
+
+const OPENCODE_SCHEMA: &str = "https://opencode.ai/config.json";
+
+/// Ledger of the `permission.skill` values Automatic replaced, keyed by
+/// project path.  `null` records that the project had none.
+const SKILL_PERMISSION_LEDGER: &str = "opencode_skill_permissions.json";
+
+type SkillPermissionLedger = BTreeMap<String, Value>;
+
+fn skill_permission_ledger_path() -> Result<PathBuf, String> {
+    Ok(crate::core::get_automatic_dir()?.join(SKILL_PERMISSION_LEDGER))
+}
+
+/// The `permission.skill` value that limits OpenCode to `allowed`.  The
+/// `*` rule sorts first, so the per-skill rules after it take precedence.
+pub fn opencode_skill_permission(allowed: &[String]) -> Value {
+    let mut rules = Map::new();
+    rules.insert("*".to_string(), json!("deny"));
+    for name in allowed {
+        rules.insert(name.clone(), json!("allow"));
+    }
+    Value::Object(rules)
+}
+
+/// Limit OpenCode in `dir` to the `allowed` skills.  Returns the path of
+/// `opencode.json` when it changed.
+pub fn write_opencode_skill_permissions(
+    dir: &Path,
+    allowed: &[String],
+) -> Result<Option<String>, String> {
+    write_skill_permission_at(&skill_permission_ledger_path()?, dir, allowed)
+}
+
+/// `(expected, actual)` `permission.skill` values for `dir`: what a sync
+/// would write and what `opencode.json` holds.  Equal values mean no drift.
+pub fn opencode_skill_permission_state(dir: &Path, allowed: &[String]) -> (Value, Option<Value>) {
+    let actual = read_config(&dir.join("opencode.json"))
+        .ok()
+        .and_then(|root| root.get("permission")?.get("skill").cloned());
+    (opencode_skill_permission(allowed), actual)
+}
+
+fn write_skill_permission_at(
+    ledger_path: &Path,
+    dir: &Path,
+    allowed: &[String],
+) -> Result<Option<String>, String> {
+    let path = dir.join("opencode.json");
+    let mut root = read_config(&path)?;
+    let expected = opencode_skill_permission(allowed);
+    let permission = root
+        .entry("permission")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or("opencode.json: `permission` is not an object")?;
+    if permission.get("skill") == Some(&expected) {
+        return Ok(None);
+    }
+
+    let mut ledger = read_ledger(ledger_path);
+    ledger
+        .entry(dir.display().to_string())
+        .or_insert_with(|| permission.get("skill").cloned().unwrap_or(Value::Null));
+    write_ledger(ledger_path, &ledger)?;
+
+    permission.insert("skill".to_string(), expected);
+    root.entry("$schema")
+        .or_insert_with(|| json!(OPENCODE_SCHEMA));
+    let content = crate::core::to_stable_json(&Value::Object(root))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write opencode.json: {}", e))?;
+    Ok(Some(path.display().to_string()))
+}
+
+/// Put back the `permission.skill` value recorded for `dir`.  Nothing
+/// happens when the ledger has no entry, i.e. Automatic never replaced it.
+fn restore_skill_permission_at(ledger_path: &Path, dir: &Path) -> Result<Option<String>, String> {
+    let mut ledger = read_ledger(ledger_path);
+    let Some(original) = ledger.remove(&dir.display().to_string()) else {
+        return Ok(None);
+    };
+    write_ledger(ledger_path, &ledger)?;
+
+    let path = dir.join("opencode.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut root = read_config(&path)?;
+    let Some(permission) = root.get_mut("permission").and_then(Value::as_object_mut) else {
+        return Ok(None);
+    };
+    if original.is_null() {
+        permission.remove("skill");
+        if permission.is_empty() {
+            root.remove("permission");
+        }
+    } else {
+        permission.insert("skill".to_string(), original);
+    }
+    let content = crate::core::to_stable_json(&Value::Object(root))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write opencode.json: {}", e))?;
+    Ok(Some(path.display().to_string()))
+}
+
+/// Strip the `mcp` key from `opencode.json` in `dir` and restore the
+/// recorded skill permission.  Returns the path when the file changed or
+/// was deleted.
+fn cleanup_config_at(ledger: Option<&Path>, dir: &Path) -> Vec<String> {
+    if let Some(ledger) = ledger {
+        if let Err(e) = restore_skill_permission_at(ledger, dir) {
+            tracing::warn!("OpenCode skill permission cleanup failed: {}", e);
+        }
+    }
+
+    let path = dir.join("opencode.json");
+    let Ok(mut root) = read_config(&path) else {
+        return vec![];
+    };
+    if !path.exists() {
+        return vec![];
+    }
+    root.remove("mcp");
+    let changed = if root.keys().all(|k| k == "$schema") {
+        fs::remove_file(&path).is_ok()
+    } else {
+        crate::core::to_stable_json(&Value::Object(root))
+            .ok()
+            .is_some_and(|content| fs::write(&path, content).is_ok())
+    };
+    if changed {
+        vec![path.display().to_string()]
+    } else {
+        vec![]
+    }
+}
+
+/// Read `opencode.json`; a missing file reads as empty.  A malformed file
+/// is an error so it is never overwritten.
+fn read_config(path: &Path) -> Result<Map<String, Value>, String> {
+    match fs::read_to_string(path) {
+        Ok(raw) => match serde_json::from_str::<Value>(&raw) {
+            Ok(Value::Object(map)) => Ok(map),
+            _ => Err(format!("{} is not a JSON object", path.display())),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Map::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn read_ledger(path: &Path) -> SkillPermissionLedger {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_ledger(path: &Path, ledger: &SkillPermissionLedger) -> Result<(), String> {
+    if ledger.is_empty() && !path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(ledger).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
 /// Convert an OpenCode MCP server config to Automatic's canonical format.
 ///
 /// - `type: "local"` → `type: "stdio"`, command array → command + args
 /// - `type: "remote"` → `type: "http"`
 /// - `environment` → `env`
-fn normalise_import(mut config: Value) -> Value {
+pub(super) fn normalise_import(mut config: Value) -> Value {
     if let Some(obj) = config.as_object_mut() {
         if let Some(Value::String(t)) = obj.get("type") {
             if t == "local" {
@@ -512,7 +699,8 @@ fn remove_storage_entries_by_id(
                     .unwrap_or("")
                     .to_string();
                 if !keep.contains(&name) {
-                    if fs::remove_dir_all(&path).is_ok() {
+                    let context = crate::core::RemovalContext::external(dir);
+                    if crate::core::remove_guarded(&path, &context).is_ok() {
                         removed += 1;
                     }
                 }
@@ -630,7 +818,9 @@ pub fn clean_opencode_snapshots() -> Result<CleanSnapshotsResult, String> {
         if dir_name != "global" {
             if let Some(ref known) = known_project_ids {
                 if !known.contains(&dir_name) {
-                    if fs::remove_dir_all(&path).is_ok() {
+                    // Snapshot dirs are git repos, so the removal is forced.
+                    let context = crate::core::RemovalContext::external(&snapshot_dir).forced();
+                    if crate::core::remove_guarded(&path, &context).is_ok() {
                         orphans_removed += 1;
                     }
                     continue; // nothing more to do for this dir
@@ -827,4 +1017,80 @@ mod tests {
             "client_123"
         );
     }
+
+    #[test]
+    fn skill_permission_is_added_and_removed_without_existing_config() {
+        let dir = tempdir().unwrap();
+        let ledger = dir.path().join("ledger.json");
+        let project = dir.path().join("project");
+        fs::create_dir(&project).unwrap();
+        let allowed = vec!["rust".to_string()];
+
+        let written = write_skill_permission_at(&ledger, &project, &allowed).unwrap();
+        assert!(written.is_some());
+        OpenCode
+            .write_mcp_config(&project, &stdio_servers())
+            .unwrap();
+        let parsed: Value =
+            serde_json::from_str(&fs::read_to_string(project.join("opencode.json")).unwrap())
+                .unwrap();
+        assert_eq!(
+            parsed["permission"]["skill"],
+            json!({"*": "deny", "rust": "allow"})
+        );
+        assert_eq!(parsed["mcp"]["automatic"]["type"], "local");
+        assert_eq!(
+            write_skill_permission_at(&ledger, &project, &allowed).unwrap(),
+            None
+        );
+
+        cleanup_config_at(Some(&ledger), &project);
+        assert!(!project.join("opencode.json").exists());
+        assert!(read_ledger(&ledger).is_empty());
+    }
+
+    #[test]
+    fn user_settings_survive_sync_and_their_skill_permission_is_restored() {
+        let dir = tempdir().unwrap();
+        let ledger = dir.path().join("ledger.json");
+        let project = dir.path().join("project");
+        fs::create_dir(&project).unwrap();
+        let config = project.join("opencode.json");
+        fs::write(
+            &config,
+            r#"{"theme": "tokyonight", "permission": {"edit": "ask", "skill": {"internal-*": "deny"}}}"#,
+        )
+        .unwrap();
+        let allowed = vec!["lint".to_string()];
+
+        OpenCode
+            .write_mcp_config(&project, &http_servers())
+            .unwrap();
+        write_skill_permission_at(&ledger, &project, &allowed).unwrap();
+        let parsed: Value = serde_json::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed["theme"], "tokyonight");
+        assert_eq!(parsed["permission"]["edit"], "ask");
+        assert_eq!(
+            parsed["permission"]["skill"],
+            json!({"*": "deny", "lint": "allow"})
+        );
+        assert_eq!(parsed["mcp"]["remote-api"]["type"], "remote");
+
+        let (expected, actual) = opencode_skill_permission_state(&project, &allowed);
+        assert_eq!(actual.as_ref(), Some(&expected));
+        let (expected, actual) =
+            opencode_skill_permission_state(&project, &["lint".into(), "rust".into()]);
+        assert_ne!(actual.as_ref(), Some(&expected));
+
+        assert_eq!(cleanup_config_at(Some(&ledger), &project).len(), 1);
+        let parsed: Value = serde_json::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(
+            parsed,
+            json!({
+                "$schema": OPENCODE_SCHEMA,
+                "theme": "tokyonight",
+                "permission": {"edit": "ask", "skill": {"internal-*": "deny"}}
+            })
+        );
+    }
 }
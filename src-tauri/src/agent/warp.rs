@@ -1,7 +1,7 @@
-use serde_json::{Map, Value};
+use serde_json::{json, Map, Value};
 use std::path::{Path, PathBuf};
 
-use super::{sync_individual_skills, Agent, AgentCapabilities};
+use super::{sync_individual_skills, Agent, AgentCapabilities, AgentMcpExport};
 
 /// Warp agent — uses `AGENTS.md` as the project rules file and stores
 /// skills under `<project>/.agents/skills/<name>/SKILL.md`.
@@ -15,7 +15,12 @@ use super::{sync_individual_skills, Agent, AgentCapabilities};
 /// **MCP note**: Warp manages MCP servers through its own GUI and internal
 /// database — there is no project-level config file that Automatic can write.
 /// MCP servers must be configured manually inside the Warp app
-/// (Settings › MCP Servers or Warp Drive › MCP Servers).
+/// (Settings › MCP Servers or Warp Drive › MCP Servers), whose "Add" dialog
+/// accepts pasted JSON.  [`Agent::export_mcp_setup`] produces the project's
+/// servers in that format:
+/// ```json
+/// { "mcpServers": { "my-server": { "command": "npx", "args": ["-y", "..."] } } }
+/// ```
 pub struct Warp;
 
 impl Agent for Warp {
@@ -80,10 +85,38 @@ impl Agent for Warp {
         Some(
             "Warp manages MCP servers through its own app (Settings \u{203a} AI \u{203a} MCP Servers \
              or Warp Drive \u{203a} MCP Servers). Automatic cannot write Warp\u{2019}s MCP config \
-             \u{2014} add servers manually in Warp.",
+             \u{2014} copy the project\u{2019}s servers and paste them into Warp.",
         )
     }
 
+    /// The `mcpServers` object Warp's MCP "Add" dialog accepts.  Warp has
+    /// no notion of Automatic's `type`, `enabled`, `timeout` or `oauth`
+    /// fields, so they are dropped.
+    fn mcp_config_snippet(&self, servers: &Map<String, Value>) -> Option<String> {
+        let mut entries = Map::new();
+        for (name, config) in servers {
+            let mut server = config.clone();
+            if let Some(obj) = server.as_object_mut() {
+                for key in ["type", "enabled", "timeout", "oauth"] {
+                    obj.remove(key);
+                }
+            }
+            entries.insert(name.clone(), server);
+        }
+        serde_json::to_string_pretty(&json!({ "mcpServers": entries })).ok()
+    }
+
+    fn export_mcp_setup(&self, servers: &Map<String, Value>) -> Option<AgentMcpExport> {
+        Some(AgentMcpExport {
+            format: "json",
+            content: self.mcp_config_snippet(servers)?,
+            instructions: "In Warp, open Settings \u{203a} AI \u{203a} MCP Servers, click \
+                           \u{201c}+ Add\u{201d} and paste the JSON. Servers already added \
+                           in Warp are left as they are."
+                .to_string(),
+        })
+    }
+
     // ── Cleanup ─────────────────────────────────────────────────────────
 
     /// Return both the current canonical file (`AGENTS.md`) and the legacy
@@ -184,6 +217,29 @@ mod tests {
         assert!(Warp.mcp_note().is_some());
     }
 
+    #[test]
+    fn export_uses_warp_field_names() {
+        let mut servers = Map::new();
+        servers.insert(
+            "automatic".to_string(),
+            json!({"command": "/usr/local/bin/automatic", "args": ["mcp-serve"], "env": {"AUTOMATIC_PROJECT": "web"}}),
+        );
+        servers.insert(
+            "remote".to_string(),
+            json!({"type": "http", "url": "https://api.example.com/mcp", "timeout": 30}),
+        );
+
+        let export = Warp.export_mcp_setup(&servers).unwrap();
+        assert_eq!(export.format, "json");
+        assert!(!export.instructions.is_empty());
+        let parsed: Value = serde_json::from_str(&export.content).unwrap();
+        assert_eq!(parsed["mcpServers"]["automatic"], servers["automatic"]);
+        assert_eq!(
+            parsed["mcpServers"]["remote"],
+            json!({"url": "https://api.example.com/mcp"})
+        );
+    }
+
     #[test]
     fn test_owned_config_paths_includes_both_files() {
         let dir = tempdir().unwrap();
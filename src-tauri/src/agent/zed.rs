@@ -8,6 +8,10 @@ use super::{discover_mcp_servers_from_json, sync_individual_skills, Agent};
 /// `context_servers` key, preserving other settings.  Also writes global
 /// config to `~/.config/zed/settings.json`.  Stores skills under
 /// `<project>/.agents/skills/<name>/SKILL.md`.
+///
+/// Entries Zed extensions add to `context_servers` (`"source": "extension"`)
+/// belong to Zed: sync keeps them, cleanup leaves them, and discovery skips
+/// them.  Every other entry is Automatic's.
 pub struct Zed;
 
 impl Agent for Zed {
@@ -64,8 +68,10 @@ impl Agent for Zed {
             Map::new()
         };
 
-        // Build the context_servers object — Zed uses command/args/env directly
-        let mut zed_servers = Map::new();
+        // Build the context_servers object — Zed uses command/args/env directly.
+        // Extension-provided entries are carried over unless a synced server
+        // takes the same name.
+        let mut zed_servers = extension_servers(&root);
 
         for (name, config) in servers {
             let transport = config
@@ -97,8 +103,7 @@ impl Agent for Zed {
             Value::Object(zed_servers),
         );
 
-        let content = serde_json::to_string_pretty(&Value::Object(root))
-            .map_err(|e| format!("JSON error: {}", e))?;
+        let content = crate::core::to_stable_json(&Value::Object(root))?;
         fs::write(&path, content)
             .map_err(|e| format!("Failed to write .zed/settings.json: {}", e))?;
 
@@ -127,8 +132,8 @@ impl Agent for Zed {
     // ── Cleanup ─────────────────────────────────────────────────────────
 
     /// Zed merges into `.zed/settings.json` which may contain user agent,
-    /// font, and theme settings.  Strip only the `context_servers` key
-    /// rather than deleting the whole file.
+    /// font, and theme settings.  Strip only Automatic's `context_servers`
+    /// entries rather than deleting the whole file.
     fn cleanup_mcp_config(&self, dir: &Path) -> Vec<String> {
         let path = dir.join(".zed").join("settings.json");
         if !path.exists() {
@@ -142,15 +147,24 @@ impl Agent for Zed {
             Ok(Value::Object(m)) => m,
             _ => return vec![],
         };
-        if root.remove("context_servers").is_none() {
+        let Some(Value::Object(servers)) = root.get("context_servers") else {
+            return vec![];
+        };
+        if servers.values().all(is_extension_server) {
             return vec![];
         }
+        let kept = extension_servers(&root);
+        if kept.is_empty() {
+            root.remove("context_servers");
+        } else {
+            root.insert("context_servers".to_string(), Value::Object(kept));
+        }
         if root.is_empty() {
             if fs::remove_file(&path).is_ok() {
                 return vec![path.display().to_string()];
             }
         } else {
-            let content = match serde_json::to_string_pretty(&Value::Object(root)) {
+            let content = match crate::core::to_stable_json(&Value::Object(root)) {
                 Ok(c) => c,
                 Err(_) => return vec![],
             };
@@ -177,7 +191,10 @@ impl Agent for Zed {
         if !path.exists() {
             return Map::new();
         }
-        discover_mcp_servers_from_json(&path, "context_servers", normalise_zed_server)
+        let mut servers =
+            discover_mcp_servers_from_json(&path, "context_servers", normalise_zed_server);
+        servers.retain(|_, config| !is_extension_server(config));
+        servers
     }
 
     fn detect_global_install(&self) -> bool {
@@ -192,7 +209,10 @@ impl Agent for Zed {
             return Map::new();
         };
         let path = config_dir.join("settings.json");
-        discover_mcp_servers_from_json(&path, "context_servers", normalise_zed_server)
+        let mut servers =
+            discover_mcp_servers_from_json(&path, "context_servers", normalise_zed_server);
+        servers.retain(|_, config| !is_extension_server(config));
+        servers
     }
 
     fn agents_dir(&self, dir: &Path) -> Option<PathBuf> {
@@ -207,16 +227,38 @@ fn global_config_dir() -> Option<PathBuf> {
     super::home_dir().map(|h| h.join(".config").join("zed"))
 }
 
+/// Returns `true` for a `context_servers` entry installed by a Zed
+/// extension rather than configured by hand or by Automatic.
+fn is_extension_server(config: &Value) -> bool {
+    config.get("source").and_then(|v| v.as_str()) == Some("extension")
+}
+
+/// The extension-provided entries of the `context_servers` object in `root`.
+fn extension_servers(root: &Map<String, Value>) -> Map<String, Value> {
+    match root.get("context_servers") {
+        Some(Value::Object(servers)) => servers
+            .iter()
+            .filter(|(_, config)| is_extension_server(config))
+            .map(|(name, config)| (name.clone(), config.clone()))
+            .collect(),
+        _ => Map::new(),
+    }
+}
+
 /// Normalise a Zed `context_servers` entry to Automatic's canonical format.
 ///
 /// Zed's stdio servers already use `command`/`args`/`env` which matches the
 /// canonical format.  Remote servers use `url`/`headers`.  We add a `type`
-/// field so downstream code can distinguish transport.
-fn normalise_zed_server(v: Value) -> Value {
+/// field so downstream code can distinguish transport, and drop the
+/// `"source": "custom"` marker newer Zed versions write.
+pub(super) fn normalise_zed_server(v: Value) -> Value {
     let Some(obj) = v.as_object() else {
         return v;
     };
     let mut out = obj.clone();
+    if out.get("source").and_then(|v| v.as_str()) == Some("custom") {
+        out.remove("source");
+    }
 
     if !out.contains_key("type") {
         if out.contains_key("url") {
@@ -371,6 +413,52 @@ mod tests {
         assert_eq!(parsed["ui_font_size"].as_u64().unwrap(), 16);
     }
 
+    #[test]
+    fn test_extension_servers_survive_sync_and_cleanup() {
+        let dir = tempdir().unwrap();
+        let zed_dir = dir.path().join(".zed");
+        fs::create_dir_all(&zed_dir).unwrap();
+
+        let existing = json!({
+            "context_servers": {
+                "postgres": { "source": "extension", "settings": { "database_url": "pg://" } },
+                "old": { "source": "custom", "command": "old" }
+            }
+        });
+        fs::write(
+            zed_dir.join("settings.json"),
+            serde_json::to_string_pretty(&existing).unwrap(),
+        )
+        .unwrap();
+
+        let servers = Zed.discover_mcp_servers(dir.path());
+        assert_eq!(servers.keys().collect::<Vec<_>>(), vec!["old"]);
+        assert!(servers["old"].get("source").is_none());
+
+        Zed.write_mcp_config(dir.path(), &stdio_servers()).unwrap();
+        let first = fs::read_to_string(zed_dir.join("settings.json")).unwrap();
+        let parsed: Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(parsed["context_servers"]["postgres"]["source"], "extension");
+        assert!(parsed["context_servers"]["old"].is_null());
+        assert!(parsed["context_servers"]["github"].is_object());
+
+        // Writing the same servers again leaves the file byte-identical.
+        Zed.write_mcp_config(dir.path(), &stdio_servers()).unwrap();
+        let second = fs::read_to_string(zed_dir.join("settings.json")).unwrap();
+        assert_eq!(first, second);
+
+        assert_eq!(Zed.cleanup_mcp_config(dir.path()).len(), 1);
+        let content = fs::read_to_string(zed_dir.join("settings.json")).unwrap();
+        let parsed: Value = serde_json::from_str(&content).unwrap();
+        let names: Vec<&String> = parsed["context_servers"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect();
+        assert_eq!(names, vec!["postgres"]);
+        assert!(Zed.cleanup_mcp_config(dir.path()).is_empty());
+    }
+
     #[test]
     fn test_cleanup_deletes_empty_file() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,372 @@
+//! Command-line interface for the `automatic` binary.
+//!
+//! Running `automatic` with no subcommand launches the desktop app.  The
+//! subcommands run headless and never touch the GUI.  Subcommands that
+//! report results accept `--json`, and `--schema` prints the JSON Schema of
+//! that output (e.g. [`DoctorReport`]) so scripts can rely on its shape.
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use rmcp::schemars::{self, JsonSchema};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::core;
+
+#[derive(Debug, Parser)]
+#[command(name = "automatic", version, about = "Manage your AI dependencies")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the Automatic MCP server on stdio.
+    McpServe {
+        /// Serve the project in `<dir>/.automatic/project.json`, even when it
+        /// is not registered (e.g. CI or review worktrees).
+        #[arg(long, value_name = "DIR")]
+        project_dir: Option<PathBuf>,
+        /// Register only tools that read configuration; nothing that writes
+        /// is advertised or callable.  Also enabled by
+        /// `AUTOMATIC_MCP_READ_ONLY=1`.
+        #[arg(long)]
+        read_only: bool,
+        /// With `--read-only`, still serve stored API keys.
+        #[arg(long, requires = "read_only")]
+        allow_credentials: bool,
+    },
+    /// Proxy stdio MCP traffic to a remote HTTP server using keychain auth.
+    McpProxy {
+        /// Name of the remote MCP server in the registry.
+        server_name: String,
+    },
+    /// Print a shell completion script to stdout.
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Deregister a finished agent session and delete its session-scoped
+    /// memories.  Run by the plugin's SessionEnd hook.
+    SessionEnd {
+        /// The session id reported by the agent.
+        session_id: String,
+    },
+    /// Check plugin health and project integrity.  Exits non-zero when any
+    /// check fails.
+    Doctor {
+        /// Check only the project in `<dir>/.automatic/project.json`, even
+        /// when it is not registered.
+        #[arg(long, value_name = "DIR")]
+        project_dir: Option<PathBuf>,
+        /// Print the report as JSON.
+        #[arg(long)]
+        json: bool,
+        /// Print the JSON Schema of the `--json` output and exit.
+        #[arg(long, conflicts_with = "json")]
+        schema: bool,
+    },
+}
+
+/// True when `arg` names one of the CLI subcommands (or a built-in flag such
+/// as `--help`).  Anything else on the command line is left to the desktop
+/// app, as launchers may pass arguments of their own.
+pub fn is_cli_invocation(arg: &str) -> bool {
+    matches!(arg, "-h" | "--help" | "-V" | "--version" | "help")
+        || Cli::command().find_subcommand(arg).is_some()
+}
+
+/// Write the completion script for `shell` to stdout.
+pub fn print_completions(shell: Shell) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
+
+// ── Doctor ───────────────────────────────────────────────────────────────────
+
+/// Outcome of a single doctor check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Worth a look, but does not fail the run (e.g. drifted configs).
+    Warning,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DoctorCheck {
+    /// `"plugin"` or `"project"`.
+    pub category: String,
+    /// Plugin id or project name.
+    pub subject: String,
+    pub status: CheckStatus,
+    /// Human-readable findings; empty when the check passed.
+    pub details: Vec<String>,
+}
+
+/// Output of `automatic doctor --json`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    /// Number of checks with status `failed`.  The exit code is non-zero
+    /// whenever this is.
+    pub failures: usize,
+}
+
+impl DoctorCheck {
+    fn new(category: &str, subject: &str, failed: Vec<String>, warnings: Vec<String>) -> Self {
+        let status = if !failed.is_empty() {
+            CheckStatus::Failed
+        } else if !warnings.is_empty() {
+            CheckStatus::Warning
+        } else {
+            CheckStatus::Ok
+        };
+        DoctorCheck {
+            category: category.to_string(),
+            subject: subject.to_string(),
+            status,
+            details: failed.into_iter().chain(warnings).collect(),
+        }
+    }
+}
+
+/// Run every doctor check.  With `project_dir`, the project checks cover
+/// only the project stored in that directory instead of the registry.
+pub fn run_doctor(project_dir: Option<&Path>) -> DoctorReport {
+    let mut checks = Vec::new();
+
+    // ── Plugin health: enabled plugins have their resources installed ───────
+    match core::list_app_plugins() {
+        Ok(plugins) => {
+            for plugin in plugins.iter().filter(|p| p.enabled) {
+                let manifest = &plugin.manifest;
+                let mut failed = Vec::new();
+                if let Some(tool) = &manifest.tool {
+                    if core::tools::read_tool_definition(&tool.name).is_err() {
+                        failed.push(format!("tool '{}' is not installed", tool.name));
+                    }
+                }
+                for skill in &manifest.skills {
+                    if core::read_skill(&skill.name).is_err() {
+                        failed.push(format!("skill '{}' is not installed", skill.name));
+                    }
+                }
+                for rule in &manifest.rules {
+                    if core::read_rule(&rule.machine_name).is_err() {
+                        failed.push(format!("rule '{}' is not installed", rule.machine_name));
+                    }
+                }
+                checks.push(DoctorCheck::new("plugin", &manifest.id, failed, vec![]));
+            }
+        }
+        Err(e) => checks.push(DoctorCheck::new(
+            "plugin",
+            "plugins",
+            vec![format!("cannot read plugin state: {}", e)],
+            vec![],
+        )),
+    }
+
+    // ── Project integrity: config parses, directory exists, no drift ────────
+    if let Some(dir) = project_dir {
+        let dir = dir.display().to_string();
+        checks.push(match core::load_project_from_dir(&dir) {
+            Ok(project) => check_loaded_project(&project.name, &project),
+            Err(e) => DoctorCheck::new("project", &dir, vec![e], vec![]),
+        });
+    } else {
+        match core::list_projects() {
+            Ok(names) => {
+                for name in names {
+                    checks.push(check_project(&name));
+                }
+            }
+            Err(e) => checks.push(DoctorCheck::new(
+                "project",
+                "projects",
+                vec![format!("cannot list projects: {}", e)],
+                vec![],
+            )),
+        }
+    }
+
+    let failures = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Failed)
+        .count();
+    DoctorReport { checks, failures }
+}
+
+fn check_project(name: &str) -> DoctorCheck {
+    let project = match core::read_project(name)
+        .and_then(|raw| serde_json::from_str::<core::Project>(&raw).map_err(|e| e.to_string()))
+    {
+        Ok(project) => project,
+        Err(e) => {
+            return DoctorCheck::new(
+                "project",
+                name,
+                vec![format!("invalid project config: {}", e)],
+                vec![],
+            )
+        }
+    };
+    check_loaded_project(name, &project)
+}
+
+fn check_loaded_project(name: &str, project: &core::Project) -> DoctorCheck {
+    if project.directory.is_empty() {
+        return DoctorCheck::new(
+            "project",
+            name,
+            vec![],
+            vec!["no directory configured".into()],
+        );
+    }
+    if !std::path::Path::new(&project.directory).is_dir() {
+        return DoctorCheck::new(
+            "project",
+            name,
+            vec![format!("directory '{}' does not exist", project.directory)],
+            vec![],
+        );
+    }
+
+    let mut warnings = Vec::new();
+    match crate::sync::check_project_drift(project, false) {
+        Ok(report) => {
+            for agent in &report.agents {
+                for file in &agent.files {
+                    let reason = if file.details.is_empty() {
+                        file.reason.clone()
+                    } else {
+                        format!("{}: {}", file.reason, file.details.join("; "))
+                    };
+                    warnings.push(format!("{}: {} ({})", agent.agent_label, file.path, reason));
+                }
+            }
+        }
+        Err(e) => warnings.push(format!("drift check failed: {}", e)),
+    }
+    for conflict in &project.skill_conflicts {
+        warnings.push(format!(
+            "skill '{}' differs from the registry ({})",
+            conflict.name, conflict.path
+        ));
+    }
+    for warning in core::project_skill_requirement_warnings(project) {
+        warnings.push(warning.message());
+    }
+    for warning in core::project_mcp_budget_warnings(project) {
+        warnings.push(warning.message());
+    }
+    DoctorCheck::new("project", name, vec![], warnings)
+}
+
+/// Print `report` for humans.
+pub fn print_doctor_report(report: &DoctorReport) {
+    for check in &report.checks {
+        let mark = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warn",
+            CheckStatus::Failed => "FAIL",
+        };
+        println!("[{:>4}] {} {}", mark, check.category, check.subject);
+        for detail in &check.details {
+            println!("         {}", detail);
+        }
+    }
+    if report.failures == 0 {
+        println!("All checks passed.");
+    } else {
+        println!("{} check(s) failed.", report.failures);
+    }
+}
+
+/// JSON Schema of the `doctor --json` output.
+pub fn doctor_report_schema() -> String {
+    let schema = schemars::schema_for!(DoctorReport);
+    serde_json::to_string_pretty(&schema).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn bare_mcp_serve_still_parses() {
+        let cli = Cli::try_parse_from(["automatic", "mcp-serve"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::McpServe {
+                project_dir: None,
+                read_only: false,
+                allow_credentials: false,
+            })
+        ));
+
+        let cli =
+            Cli::try_parse_from(["automatic", "mcp-serve", "--project-dir=/tmp/repo"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::McpServe { project_dir: Some(ref d), .. }) if d == &PathBuf::from("/tmp/repo")
+        ));
+    }
+
+    #[test]
+    fn doctor_accepts_project_dir() {
+        let cli =
+            Cli::try_parse_from(["automatic", "doctor", "--project-dir", "/tmp/repo"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Doctor { project_dir: Some(ref d), .. }) if d == &PathBuf::from("/tmp/repo")
+        ));
+    }
+
+    #[test]
+    fn mcp_serve_read_only_flags() {
+        let cli = Cli::try_parse_from([
+            "automatic",
+            "mcp-serve",
+            "--read-only",
+            "--allow-credentials",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::McpServe {
+                read_only: true,
+                allow_credentials: true,
+                ..
+            })
+        ));
+
+        // Credentials are only an exception to read-only mode.
+        assert!(Cli::try_parse_from(["automatic", "mcp-serve", "--allow-credentials"]).is_err());
+    }
+
+    #[test]
+    fn unknown_arguments_are_left_to_the_app() {
+        assert!(is_cli_invocation("mcp-serve"));
+        assert!(is_cli_invocation("--version"));
+        assert!(!is_cli_invocation("-psn_0_12345"));
+    }
+
+    #[test]
+    fn failed_checks_take_precedence_over_warnings() {
+        let check = DoctorCheck::new("project", "p", vec!["bad".into()], vec!["meh".into()]);
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert_eq!(check.details, vec!["bad", "meh"]);
+        let check = DoctorCheck::new("project", "p", vec![], vec![]);
+        assert_eq!(check.status, CheckStatus::Ok);
+    }
+}
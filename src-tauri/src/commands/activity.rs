@@ -1,4 +1,5 @@
 use crate::activity;
+use crate::core;
 
 /// Return the N most-recent activity entries for a specific project.
 /// `limit` defaults to 20 if 0 is passed.
@@ -36,3 +37,13 @@ pub fn get_all_activity(limit: usize) -> Result<String, String> {
     let entries = activity::get_all_activity(n)?;
     serde_json::to_string(&entries).map_err(|e| e.to_string())
 }
+
+/// Return the N most-recent sync history entries for a project, newest
+/// first: what Automatic wrote into or removed from it, and why.
+#[tauri::command]
+pub fn get_sync_history(
+    name: &str,
+    limit: Option<usize>,
+) -> Result<Vec<core::SyncHistoryEntry>, String> {
+    core::read_sync_history(name, limit.unwrap_or(core::DEFAULT_SYNC_HISTORY_LIMIT))
+}
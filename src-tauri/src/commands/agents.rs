@@ -1,5 +1,6 @@
-use crate::agent;
+use crate::agent::{self, Agent};
 use crate::core;
+use crate::sync;
 use serde_json::Value;
 
 // ── Agents ───────────────────────────────────────────────────────────────────
@@ -12,10 +13,49 @@ pub fn list_agents() -> Vec<agent::AgentInfo> {
         .collect()
 }
 
+/// The project's MCP servers in the paste-in format of `agent_id`, for
+/// agents whose MCP config Automatic cannot write.
+#[tauri::command]
+pub fn get_agent_mcp_snippet(name: &str, agent_id: &str) -> Result<Option<String>, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    sync::agent_mcp_snippet(&project, agent_id)
+}
+
+/// The project's MCP servers in the paste-in format of `agent_id`, with
+/// instructions for where to paste them.  `None` when the agent has no such
+/// format.
+#[tauri::command]
+pub fn get_agent_mcp_export(
+    name: &str,
+    agent_id: &str,
+) -> Result<Option<agent::AgentMcpExport>, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    sync::agent_mcp_export(&project, agent_id)
+}
+
 /// Returns each agent with the list of projects that reference it.
+///
+/// `version` selects the response shape so the frontend can migrate in a
+/// controlled way:
+/// - `1` (default): a JSON array of flat agent objects
+///   (`id`, `label`, …, `projects: [{ name, directory }]`).
+/// - `2`: `{ "version": 2, "agents": [AgentWithProjects] }` with per-project
+///   skill counts, last sync time, cached drift state, and per-agent totals.
+///
+/// `sort` is `"label"` (default) or `"project_count"`.
 #[tauri::command]
-pub fn list_agents_with_projects() -> Result<String, String> {
-    let agents = agent::all();
+pub fn list_agents_with_projects(
+    version: Option<u32>,
+    sort: Option<String>,
+) -> Result<String, String> {
+    let sort = match sort.as_deref() {
+        Some(s) => agent::AgentSort::parse(s)?,
+        None => agent::AgentSort::default(),
+    };
     let project_names = core::list_projects().unwrap_or_default();
 
     // Read all projects once
@@ -28,33 +68,46 @@ pub fn list_agents_with_projects() -> Result<String, String> {
         })
         .collect();
 
-    let result: Vec<serde_json::Value> = agents
-        .iter()
-        .map(|a| {
-            let agent_projects: Vec<serde_json::Value> = projects
+    // Last sync times are best-effort: a missing activity DB just leaves them
+    // empty.
+    let last_sync =
+        crate::activity::last_event_times(crate::activity::ActivityEvent::ProjectSynced)
+            .unwrap_or_default();
+    let agents = agent::agents_with_projects(
+        &agent::all(),
+        &projects,
+        &last_sync,
+        sync::cached_drifted_agents,
+        sort,
+    );
+
+    match version.unwrap_or(1) {
+        1 => {
+            let result: Vec<Value> = agents
                 .iter()
-                .filter(|p| p.agents.iter().any(|id| id == a.id()))
-                .map(|p| {
+                .map(|a| {
+                    let projects: Vec<Value> = a
+                        .projects
+                        .iter()
+                        .map(|p| serde_json::json!({ "name": p.name, "directory": p.directory }))
+                        .collect();
                     serde_json::json!({
-                        "name": p.name,
-                        "directory": p.directory,
+                        "id": a.info.id,
+                        "label": a.info.label,
+                        "description": a.info.description,
+                        "project_file": a.project_file,
+                        "capabilities": a.info.capabilities,
+                        "mcp_note": a.info.mcp_note,
+                        "projects": projects,
                     })
                 })
                 .collect();
-
-            serde_json::json!({
-                "id": a.id(),
-                "label": a.label(),
-                "description": a.config_description(),
-                "project_file": a.project_file_name(),
-                "capabilities": a.capabilities(),
-                "mcp_note": a.mcp_note(),
-                "projects": agent_projects,
-            })
-        })
-        .collect();
-
-    serde_json::to_string(&result).map_err(|e| e.to_string())
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+        2 => serde_json::to_string(&serde_json::json!({ "version": 2, "agents": agents }))
+            .map_err(|e| e.to_string()),
+        other => Err(format!("Unsupported response version {}", other)),
+    }
 }
 
 /// Detect which agents are installed on the current machine by running each
@@ -128,17 +181,23 @@ pub fn detect_agent_global_configs(agent_ids: Vec<String>) -> Result<String, Str
 /// Import MCP server configs discovered in the user-level (global) config of
 /// each requested agent into Automatic's global MCP server registry.
 ///
-/// This is idempotent: existing registry entries with the same name are
-/// overwritten with the freshly-read config so that the registry stays in sync
-/// with what the agent currently has configured.
+/// Configs for the same server name found in several agents are merged when
+/// they are equivalent after canonicalization (see
+/// [`sync::canonicalize_mcp_config`]); genuinely different variants are not
+/// written and are reported as conflicts instead.  Registry entries are only
+/// rewritten when the discovered config actually differs from them.
 ///
 /// `agent_ids` is the JSON-serialised array of agent id strings.  The special
 /// value `"other"` is silently ignored.
 ///
-/// Returns a JSON array of the server names that were imported.
+/// Returns a JSON object:
+/// ```json
+/// { "imported": ["github", "linear"],
+///   "conflicts": [{ "name": "db", "variants": [{ "agent_id": "claude", "config": {} }] }] }
+/// ```
 #[tauri::command]
 pub fn import_agent_global_configs(agent_ids: Vec<String>) -> Result<String, String> {
-    let mut imported: Vec<String> = Vec::new();
+    let mut found = Vec::new();
 
     for id in &agent_ids {
         if id == "other" {
@@ -149,20 +208,24 @@ pub fn import_agent_global_configs(agent_ids: Vec<String>) -> Result<String, Str
             None => continue,
         };
 
-        let servers = agent.discover_global_mcp_servers();
-        for (name, config) in servers {
-            let config_str = serde_json::to_string_pretty(&config)
-                .map_err(|e| format!("Failed to serialise config for '{}': {}", name, e))?;
-            core::save_mcp_server_config(&name, &config_str)?;
-            imported.push(name);
+        for (name, config) in agent.discover_global_mcp_servers() {
+            found.push((agent.id().to_string(), name, config));
         }
     }
 
-    // Deduplicate (multiple agents may have the same server name)
+    let merged = sync::merge_discovered_servers(found);
+    let mut imported: Vec<String> = Vec::new();
+    for (name, config) in merged.servers {
+        sync::save_mcp_server_if_changed(&name, &config)?;
+        imported.push(name);
+    }
     imported.sort();
-    imported.dedup();
 
-    serde_json::to_string(&imported).map_err(|e| e.to_string())
+    let result = serde_json::json!({
+        "imported": imported,
+        "conflicts": merged.conflicts,
+    });
+    serde_json::to_string(&result).map_err(|e| e.to_string())
 }
 
 /// Import skills found in agent-specific extra global skill directories
@@ -202,3 +265,86 @@ pub fn import_agent_global_skills(agent_ids: Vec<String>) -> Result<String, Stri
 
     serde_json::to_string(&imported).map_err(|e| e.to_string())
 }
+
+// ── Cursor global scope ──────────────────────────────────────────────────────
+
+/// Describe what is configured in Cursor's user-level (global) scope:
+/// MCP servers in `~/.cursor/mcp.json` and rule files in `~/.cursor/rules/`.
+///
+/// Per-project autodetect only ever reads the project's `.cursor/mcp.json`;
+/// this is the entry point for surfacing the global scope so the user can
+/// choose what to migrate.
+///
+/// Returns a JSON object:
+/// ```json
+/// { "mcp_config_path": "/home/me/.cursor/mcp.json",
+///   "server_names": ["github"],
+///   "rules": [{ "machine_name": "code-style", "name": "Code Style",
+///               "content": "...", "source_path": "..." }] }
+/// ```
+#[tauri::command]
+pub fn detect_cursor_global_config() -> Result<String, String> {
+    let servers = agent::Cursor.discover_global_mcp_servers();
+    let mut server_names: Vec<&String> = servers.keys().collect();
+    server_names.sort();
+
+    let rules = agent::global_rules_dir()
+        .map(|dir| agent::discover_cursor_global_rules(&dir))
+        .unwrap_or_default();
+
+    let result = serde_json::json!({
+        "mcp_config_path": agent::global_mcp_config_path().map(|p| p.display().to_string()),
+        "server_names": server_names,
+        "rules": rules,
+    });
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
+
+/// Copy the selected global Cursor MCP servers into the Automatic registry
+/// and assign them to `name`.  When `remove_from_global` is `true` the
+/// servers are also removed from `~/.cursor/mcp.json` (a timestamped backup
+/// is written alongside it first).
+///
+/// Returns a JSON-serialised [`sync::CursorMigrationResult`].
+#[tauri::command]
+pub fn migrate_cursor_global_to_project(
+    name: &str,
+    server_names: Vec<String>,
+    remove_from_global: bool,
+) -> Result<String, String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let result =
+        sync::migrate_cursor_global_to_project(&mut project, &server_names, remove_from_global)?;
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
+
+/// Import the selected rule files from `~/.cursor/rules/` as Automatic rules.
+///
+/// `machine_names` are the candidate machine names returned by
+/// [`detect_cursor_global_config`].  Rules that already exist in Automatic
+/// are skipped rather than overwritten.
+///
+/// Returns a JSON array of the machine names that were imported.
+#[tauri::command]
+pub fn import_cursor_global_rules(machine_names: Vec<String>) -> Result<String, String> {
+    let Some(dir) = agent::global_rules_dir() else {
+        return Err("Could not determine home directory".into());
+    };
+    let existing: std::collections::HashSet<String> =
+        core::list_rules()?.into_iter().map(|r| r.id).collect();
+
+    let mut imported: Vec<String> = Vec::new();
+    for candidate in agent::discover_cursor_global_rules(&dir) {
+        if !machine_names.contains(&candidate.machine_name)
+            || existing.contains(&candidate.machine_name)
+        {
+            continue;
+        }
+        core::save_rule(&candidate.machine_name, &candidate.name, &candidate.content)?;
+        imported.push(candidate.machine_name);
+    }
+
+    serde_json::to_string(&imported).map_err(|e| e.to_string())
+}
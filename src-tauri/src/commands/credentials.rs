@@ -22,6 +22,13 @@ pub fn delete_api_key(provider: &str) -> Result<(), String> {
     core::delete_api_key(provider)
 }
 
+/// Names of the stored API keys, for autocompleting `${keychain:<name>}`
+/// placeholders in MCP server configs.  Never returns key values.
+#[tauri::command]
+pub fn list_api_key_providers() -> Result<Vec<String>, String> {
+    core::list_api_key_providers()
+}
+
 /// Returns true if an AI key is resolvable through the full resolution chain
 /// (env var → .env file in debug → OS keychain). This matches the same logic
 /// used by `ai::resolve_api_key` so the frontend accurately reflects whether
@@ -36,6 +36,37 @@ pub fn delete_mcp_server_config(name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Check that an MCP server starts and speaks MCP before it is saved.
+/// `name_or_json` is either a registry server name or a config as JSON.
+#[tauri::command]
+pub async fn test_mcp_server(name_or_json: String) -> Result<core::McpTestResult, String> {
+    let data = if name_or_json.trim_start().starts_with('{') {
+        name_or_json
+    } else {
+        core::read_mcp_server_config(&name_or_json)?
+    };
+    Ok(core::test_mcp_server_config(&data).await)
+}
+
+/// Find the MCP server definitions in a pasted config snippet.
+#[tauri::command]
+pub fn parse_mcp_snippet(raw: &str) -> Result<Vec<core::McpSnippetCandidate>, String> {
+    core::parse_mcp_snippet(raw)
+}
+
+/// Save servers from a pasted config snippet to the registry.
+#[tauri::command]
+pub fn import_mcp_snippet(
+    raw: &str,
+    overrides: Vec<core::McpSnippetOverride>,
+) -> Result<Vec<String>, String> {
+    let saved = core::import_mcp_snippet(raw, &overrides)?;
+    for name in &saved {
+        sync_projects_referencing_mcp_server(name);
+    }
+    Ok(saved)
+}
+
 // ── MCP Marketplace ──────────────────────────────────────────────────────────
 
 /// Return all MCP server marketplace entries matching `query` as a JSON array.
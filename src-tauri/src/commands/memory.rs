@@ -14,28 +14,35 @@ pub fn store_memory(
     key: &str,
     value: &str,
     source: Option<&str>,
+    ttl_seconds: Option<u64>,
 ) -> Result<String, String> {
-    memory::store_memory(project, key, value, source)
+    memory::store_memory(project, key, value, source, None, ttl_seconds, None)
 }
 
 #[tauri::command]
 pub fn get_memory(project: &str, key: &str) -> Result<String, String> {
-    memory::get_memory(project, key)
+    memory::get_memory(project, key, memory::SessionScope::default(), None)
 }
 
 #[tauri::command]
 pub fn list_memories(project: &str, pattern: Option<&str>) -> Result<String, String> {
-    memory::list_memories(project, pattern)
+    memory::list_memories(
+        project,
+        pattern,
+        memory::SessionScope::default(),
+        None,
+        false,
+    )
 }
 
 #[tauri::command]
 pub fn search_memories(project: &str, query: &str) -> Result<String, String> {
-    memory::search_memories(project, query)
+    memory::search_memories(project, query, memory::SessionScope::default(), None)
 }
 
 #[tauri::command]
 pub fn delete_memory(project: &str, key: &str) -> Result<String, String> {
-    memory::delete_memory(project, key)
+    memory::delete_memory(project, key, None)
 }
 
 #[tauri::command]
@@ -44,7 +51,24 @@ pub fn clear_memories(
     pattern: Option<&str>,
     confirm: bool,
 ) -> Result<String, String> {
-    memory::clear_memories(project, pattern, confirm)
+    memory::clear_memories(project, pattern, confirm, None)
+}
+
+/// The project's long-term memories as a versioned JSON document.
+#[tauri::command]
+pub fn export_memories(project: &str) -> Result<String, String> {
+    memory::export_memories(project)
+}
+
+/// Import a document produced by [`export_memories`].  `strategy` is
+/// `"merge"`, `"overwrite"` or `"fail_on_conflict"`.
+#[tauri::command]
+pub fn import_memories(
+    project: &str,
+    data: &str,
+    strategy: memory::MemoryImportStrategy,
+) -> Result<memory::MemoryImportReport, String> {
+    memory::import_memories(project, data, strategy)
 }
 
 /// Returns Claude Code's auto-memory content for the given project.
@@ -1,3 +1,8 @@
+use std::sync::{Mutex, OnceLock};
+
+use tauri::Emitter;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
 use crate::core;
 
 // ── Author Resolution ─────────────────────────────────────────────────────────
@@ -40,7 +45,7 @@ pub fn open_in_editor(editor_id: &str, path: &str) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn get_editor_icon(editor_id: &str) -> Result<String, String> {
+pub fn get_editor_icon(editor_id: &str) -> Result<Option<String>, String> {
     core::get_editor_icon(editor_id)
 }
 
@@ -73,13 +78,138 @@ pub fn install_plugin_marketplace() -> Result<String, String> {
     core::install_plugin_marketplace()
 }
 
+/// Active sessions as a JSON object keyed by session id.  A corrupt store is
+/// moved aside and reported with a `sessions-store-recovered` event carrying
+/// the path it was moved to, so the user can run `repair_sessions_store`.
+#[tauri::command]
+pub fn get_sessions(app: tauri::AppHandle) -> Result<String, String> {
+    let read = core::read_sessions()?;
+    if let Some(moved) = &read.quarantined {
+        let _ = app.emit("sessions-store-recovered", moved.display().to_string());
+    }
+    serde_json::to_string_pretty(&read.sessions).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn repair_sessions_store() -> Result<core::SessionsRepair, String> {
+    core::repair_sessions_store()
+}
+
+// ── Diagnostics ───────────────────────────────────────────────────────────────
+
+/// Recent log entries for the diagnostics panel, oldest first.
+/// `level_filter` keeps entries at that level or more severe.
+#[tauri::command]
+pub fn get_recent_logs(
+    limit: Option<usize>,
+    level_filter: Option<String>,
+) -> Result<Vec<core::LogEntry>, String> {
+    core::recent_logs(limit, level_filter.as_deref())
+}
+
+/// The log file currently written to, for attaching to bug reports.
 #[tauri::command]
-pub fn get_sessions() -> Result<String, String> {
-    core::list_sessions()
+pub fn get_log_file_path() -> Result<String, String> {
+    Ok(core::get_log_file_path()?.display().to_string())
 }
 
 // ── App Updates ───────────────────────────────────────────────────────────────
 
+/// Update found by the last `check_for_updates`, waiting for `install_update`.
+fn pending_update() -> &'static Mutex<Option<Update>> {
+    static PENDING: OnceLock<Mutex<Option<Update>>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(None))
+}
+
+/// Query the manifest for the channel chosen in Settings and report the
+/// available update without installing it.  Returns `None` when up to date.
+/// An older release (after leaving the beta channel) is only offered when
+/// `force` is set.
+#[tauri::command]
+pub async fn check_for_updates(
+    app: tauri::AppHandle,
+    force: Option<bool>,
+) -> Result<Option<core::UpdateCheck>, String> {
+    let channel = core::UpdateChannel::parse(&core::read_settings()?.update_channel);
+    let force = force.unwrap_or(false);
+    let endpoint = url::Url::parse(&channel.endpoint()).map_err(|e| e.to_string())?;
+
+    let updater = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .map_err(|e| e.to_string())?
+        .version_comparator(move |current, release| {
+            core::is_offered_update(&current, &release.version, force)
+        })
+        .build()
+        .map_err(|e| e.to_string())?;
+    let Some(update) = updater.check().await.map_err(|e| e.to_string())? else {
+        *pending_update().lock().unwrap_or_else(|e| e.into_inner()) = None;
+        return Ok(None);
+    };
+
+    // The manifest does not carry the installer size; ask the server.
+    let download_size = reqwest::Client::new()
+        .head(update.download_url.clone())
+        .send()
+        .await
+        .ok()
+        .and_then(|r| r.headers().get(reqwest::header::CONTENT_LENGTH).cloned())
+        .and_then(|v| v.to_str().ok()?.parse().ok());
+    let downgrade = match (
+        semver::Version::parse(&update.version),
+        semver::Version::parse(&update.current_version),
+    ) {
+        (Ok(remote), Ok(current)) => remote < current,
+        _ => false,
+    };
+    let check = core::UpdateCheck {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        channel: channel.as_str().to_string(),
+        notes: update.body.clone(),
+        published_at: update
+            .raw_json
+            .get("pub_date")
+            .and_then(|d| d.as_str())
+            .map(str::to_string),
+        download_size,
+        downgrade,
+    };
+    *pending_update().lock().unwrap_or_else(|e| e.into_inner()) = Some(update);
+    Ok(Some(check))
+}
+
+/// Download and install the update found by `check_for_updates`, emitting
+/// `update-download-progress` as bytes arrive and `update-download-finished`
+/// when the download completes.  `restart_app` applies it.
+#[tauri::command]
+pub async fn install_update(app: tauri::AppHandle) -> Result<(), String> {
+    let update = pending_update()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .ok_or("No update is pending — check for updates first")?;
+
+    let mut downloaded: u64 = 0;
+    let progress_handle = app.clone();
+    update
+        .download_and_install(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let _ = progress_handle.emit(
+                    "update-download-progress",
+                    &core::UpdateProgress { downloaded, total },
+                );
+            },
+            || {
+                let _ = app.emit("update-download-finished", ());
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Restart the application to apply a freshly-installed update.
 #[tauri::command]
 pub fn restart_app(app: tauri::AppHandle) {
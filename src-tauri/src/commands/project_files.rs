@@ -3,76 +3,14 @@ use crate::core;
 
 // ── Project Files ────────────────────────────────────────────────────────────
 
-/// Returns JSON array of unique project file info objects for the project's agents.
-/// Each entry: { filename, agents: ["Claude Code", ...] }
+/// Returns a JSON array of the project's instruction files (see
+/// [`core::project_file_info`]).
 #[tauri::command]
 pub fn get_project_file_info(name: &str) -> Result<String, String> {
     let raw = core::read_project(name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
-
-    let project_dir = std::path::Path::new(&project.directory);
-
-    // Collect all unique agent filenames and their labels
-    let mut files: Vec<serde_json::Value> = Vec::new();
-    let mut seen_filenames: Vec<String> = Vec::new();
-
-    for agent_id in &project.agents {
-        if let Some(a) = agent::from_id(agent_id) {
-            let filename = a.project_file_name().to_string();
-            let exists = project_dir.join(&filename).exists();
-
-            if !seen_filenames.contains(&filename) {
-                seen_filenames.push(filename.clone());
-                files.push(serde_json::json!({
-                    "filename": filename,
-                    "agents": [a.label()],
-                    "exists": exists
-                }));
-            } else {
-                // Append agent label to existing entry
-                for file in &mut files {
-                    if file["filename"].as_str() == Some(&filename) {
-                        if let Some(agents) = file["agents"].as_array_mut() {
-                            agents.push(serde_json::json!(a.label()));
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    if project.instruction_mode == "unified" {
-        // In unified mode return a single virtual entry that targets all agent files
-        let empty_vec = vec![];
-        let all_agents: Vec<String> = files
-            .iter()
-            .flat_map(|f| {
-                f["agents"]
-                    .as_array()
-                    .unwrap_or(&empty_vec)
-                    .iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-            })
-            .collect();
-        let all_filenames: Vec<String> = seen_filenames.clone();
-        let any_exists = files.iter().any(|f| f["exists"].as_bool().unwrap_or(false));
-
-        let unified = serde_json::json!({
-            "filename": "_unified",
-            "agents": all_agents,
-            "exists": any_exists,
-            "target_files": all_filenames
-        });
-        serde_json::to_string(&vec![unified]).map_err(|e| e.to_string())
-    } else {
-        files.sort_by(|a, b| {
-            let fa = a["filename"].as_str().unwrap_or("");
-            let fb = b["filename"].as_str().unwrap_or("");
-            fa.cmp(fb)
-        });
-        serde_json::to_string(&files).map_err(|e| e.to_string())
-    }
+    serde_json::to_string(&core::project_file_info(&project)).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -407,7 +345,17 @@ pub async fn ai_update_instruction(
 
 // ── Doc Notes ────────────────────────────────────────────────────────────────
 
-/// Read the contents of a Markdown note stored in `{project_dir}/.automatic/docs/<name>.md`.
+/// Directory holding the project's notes: `{project_dir}/.automatic/docs/`,
+/// or `~/.automatic/projects-data/<project>/docs/` when the project keeps its
+/// config out of the repository.
+fn notes_dir(project: &core::Project) -> Result<std::path::PathBuf, String> {
+    core::project_data_dir(project)
+        .map(|dir| dir.join("docs"))
+        .ok_or_else(|| "Project has no directory configured".to_string())
+}
+
+/// Read the contents of a Markdown note stored in the project's notes
+/// directory (see `notes_dir`).
 ///
 /// Returns an empty string if the file does not exist yet (so the frontend
 /// can treat it as a new note without an extra existence check).
@@ -417,14 +365,7 @@ pub fn read_doc_note(name: &str, note_name: &str) -> Result<String, String> {
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
 
-    if project.directory.is_empty() {
-        return Err("Project has no directory configured".into());
-    }
-
-    let note_path = std::path::PathBuf::from(&project.directory)
-        .join(".automatic")
-        .join("docs")
-        .join(note_name);
+    let note_path = notes_dir(&project)?.join(note_name);
 
     if !note_path.exists() {
         return Ok(String::new());
@@ -433,22 +374,15 @@ pub fn read_doc_note(name: &str, note_name: &str) -> Result<String, String> {
     std::fs::read_to_string(&note_path).map_err(|e| format!("Failed to read note: {}", e))
 }
 
-/// Write a Markdown note to `{project_dir}/.automatic/docs/<name>.md`.
-///
-/// Creates the `.automatic/docs/` directory if it does not exist.
+/// Write a Markdown note to the project's notes directory, creating it if
+/// it does not exist.
 #[tauri::command]
 pub fn save_doc_note(name: &str, note_name: &str, content: &str) -> Result<(), String> {
     let raw = core::read_project(name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
 
-    if project.directory.is_empty() {
-        return Err("Project has no directory configured".into());
-    }
-
-    let docs_dir = std::path::PathBuf::from(&project.directory)
-        .join(".automatic")
-        .join("docs");
+    let docs_dir = notes_dir(&project)?;
 
     std::fs::create_dir_all(&docs_dir)
         .map_err(|e| format!("Failed to create docs directory: {}", e))?;
@@ -457,7 +391,7 @@ pub fn save_doc_note(name: &str, note_name: &str, content: &str) -> Result<(), S
     std::fs::write(&note_path, content).map_err(|e| format!("Failed to save note: {}", e))
 }
 
-/// Delete a Markdown note file from `{project_dir}/.automatic/docs/<name>.md`.
+/// Delete a Markdown note file from the project's notes directory.
 ///
 /// Returns `Ok(())` if the file did not exist (idempotent).
 #[tauri::command]
@@ -466,14 +400,7 @@ pub fn delete_doc_note(name: &str, note_name: &str) -> Result<(), String> {
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
 
-    if project.directory.is_empty() {
-        return Err("Project has no directory configured".into());
-    }
-
-    let note_path = std::path::PathBuf::from(&project.directory)
-        .join(".automatic")
-        .join("docs")
-        .join(note_name);
+    let note_path = notes_dir(&project)?.join(note_name);
 
     if note_path.exists() {
         std::fs::remove_file(&note_path).map_err(|e| format!("Failed to delete note: {}", e))?;
@@ -4,6 +4,7 @@ use crate::core;
 use crate::sync;
 use serde::Serialize;
 use std::collections::HashMap;
+use tauri::Emitter;
 
 // ── Projects ─────────────────────────────────────────────────────────────────
 
@@ -17,6 +18,105 @@ pub fn read_project(name: &str) -> Result<String, String> {
     core::read_project(name)
 }
 
+#[derive(Serialize)]
+struct ProjectSummary {
+    name: String,
+    directory: String,
+    agents: Vec<String>,
+    locked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locked_reason: Option<String>,
+    /// Notice when the project was saved by a newer Automatic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_notice: Option<String>,
+    read_only: bool,
+    /// Agents autodetection is told to leave alone.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    disabled_agents: Vec<String>,
+}
+
+/// List every project with the fields the project list needs to render
+/// without reading each project individually.  Projects that fail to parse
+/// are listed by name only.
+#[tauri::command]
+pub fn get_projects_detailed() -> Result<String, String> {
+    let summaries: Vec<ProjectSummary> = core::list_projects()?
+        .into_iter()
+        .map(|name| {
+            match core::read_project(&name)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<core::Project>(&raw).ok())
+            {
+                Some(p) => {
+                    let compat = core::version_compat(
+                        &format!("Project '{}'", name),
+                        p.min_app_version.as_deref(),
+                    );
+                    ProjectSummary {
+                        version_notice: compat.message().map(str::to_string),
+                        read_only: compat.is_read_only(),
+                        name,
+                        directory: p.directory,
+                        agents: p.agents,
+                        disabled_agents: p.disabled_agents,
+                        locked: p.locked,
+                        locked_reason: p.locked_reason,
+                    }
+                }
+                None => ProjectSummary {
+                    name,
+                    directory: String::new(),
+                    agents: Vec::new(),
+                    disabled_agents: Vec::new(),
+                    locked: false,
+                    locked_reason: None,
+                    version_notice: None,
+                    read_only: false,
+                },
+            }
+        })
+        .collect();
+    serde_json::to_string_pretty(&summaries).map_err(|e| e.to_string())
+}
+
+/// Write-protect a project: syncs, agent removal, repair/adopt and
+/// registry-triggered updates leave its directory untouched until it is
+/// unlocked.  Reading and drift checks keep working.
+#[tauri::command]
+pub fn lock_project(name: &str, reason: Option<String>) -> Result<(), String> {
+    set_project_lock(name, true, reason.filter(|r| !r.trim().is_empty()))
+}
+
+#[tauri::command]
+pub fn unlock_project(name: &str) -> Result<(), String> {
+    set_project_lock(name, false, None)
+}
+
+fn set_project_lock(name: &str, locked: bool, reason: Option<String>) -> Result<(), String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.locked = locked;
+    project.locked_reason = reason;
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+
+    let data = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
+    core::save_project(name, &data)?;
+
+    let label = if locked {
+        "Project locked"
+    } else {
+        "Project unlocked"
+    };
+    activity::log(
+        name,
+        ActivityEvent::ProjectUpdated,
+        label,
+        project.locked_reason.as_deref().unwrap_or(""),
+    );
+    Ok(())
+}
+
 #[derive(Serialize)]
 struct RebuildPreviewCategory {
     key: &'static str,
@@ -73,13 +173,39 @@ pub fn preview_rebuild_project(name: &str) -> Result<String, String> {
     serde_json::to_string_pretty(&preview).map_err(|e| e.to_string())
 }
 
+/// Runs off the main thread, emitting `autodetect-progress` after each step;
+/// `cancel_autodetect` stops it.
 #[tauri::command]
-pub fn autodetect_project_dependencies(name: &str) -> Result<String, String> {
-    let raw = core::read_project(name)?;
+pub async fn autodetect_project_dependencies(
+    app: tauri::AppHandle,
+    name: String,
+) -> Result<String, String> {
+    let raw = core::read_project(&name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
-    let updated = sync::autodetect_project_dependencies(&project)?;
-    serde_json::to_string_pretty(&updated).map_err(|e| e.to_string())
+    let cancel = sync::begin_autodetect(&name);
+    let limits = sync::AutodetectLimits::from_settings();
+    let detected = tauri::async_runtime::spawn_blocking(move || {
+        sync::autodetect_project_dependencies_with_progress(&project, limits, &cancel, |progress| {
+            let _ = app.emit("autodetect-progress", &progress);
+        })
+    })
+    .await;
+    sync::finish_autodetect(&name);
+    let (updated, conflicts) = detected.map_err(|e| e.to_string())??;
+    // Conflicting MCP server variants ride along as an extra top-level field
+    // so callers that only read the project shape are unaffected.
+    let mut result = serde_json::to_value(&updated).map_err(|e| e.to_string())?;
+    if !conflicts.is_empty() {
+        result["mcp_server_conflicts"] =
+            serde_json::to_value(&conflicts).map_err(|e| e.to_string())?;
+    }
+    serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cancel_autodetect(name: &str) -> bool {
+    sync::cancel_autodetect(name)
 }
 
 #[tauri::command]
@@ -147,6 +273,46 @@ pub fn save_project(name: &str, data: &str) -> Result<(), String> {
 
         let mut enriched = incoming.clone();
 
+        // The lock is only changed through lock_project / unlock_project, and
+        // a locked project's config cannot be edited at all.
+        if let Some(ref existing) = existing_project {
+            existing.ensure_unlocked()?;
+            enriched.locked = existing.locked;
+            enriched.locked_reason = existing.locked_reason.clone();
+            // MCP server origins and overrides are backend-managed too; keep
+            // those of servers the project still lists.
+            enriched.mcp_server_origins = existing
+                .mcp_server_origins
+                .iter()
+                .filter(|(server, _)| incoming.mcp_servers.contains(server))
+                .map(|(server, agents)| (server.clone(), agents.clone()))
+                .collect();
+            enriched.mcp_server_overrides = existing
+                .mcp_server_overrides
+                .iter()
+                .filter(|(server, _)| incoming.mcp_servers.contains(server))
+                .map(|(server, patch)| (server.clone(), patch.clone()))
+                .collect();
+            // Skill shadow state is only changed by sync and
+            // resolve_skill_shadow.
+            enriched.skill_hashes = existing.skill_hashes.clone();
+            enriched.skill_conflicts = existing.skill_conflicts.clone();
+            enriched.preserved_skills = existing.preserved_skills.clone();
+            // Ignored agents change through remove_agent_from_project and
+            // unignore_agent only.
+            enriched.disabled_agents = existing.disabled_agents.clone();
+            enriched.validate_disabled_agents()?;
+            // Rule candidates change through import_rule_candidate and
+            // dismiss_rule_candidate; keep any autodetection just found.
+            let mut candidates = existing.rule_candidates.clone();
+            for candidate in std::mem::take(&mut enriched.rule_candidates) {
+                if !candidates.iter().any(|c| c.content == candidate.content) {
+                    candidates.push(candidate);
+                }
+            }
+            enriched.rule_candidates = candidates;
+        }
+
         if let Some(ref existing) = existing_project {
             // ── Diff and log agent changes ───────────────────────────────
             for agent in incoming
@@ -217,15 +383,36 @@ pub fn save_project(name: &str, data: &str) -> Result<(), String> {
                 let dir = std::path::PathBuf::from(&incoming.directory);
                 let discovered = sync::discover_new_agent_mcp_configs(&dir, &new_agent_ids);
 
-                for (server_name, config_str) in discovered {
-                    // Add the server name to the project's selection list.
-                    if !enriched.mcp_servers.contains(&server_name) {
-                        enriched.mcp_servers.push(server_name.clone());
+                for (server_name, agents) in &discovered.origins {
+                    if discovered.servers.iter().any(|(n, _)| n == server_name) {
+                        // Add the server name to the project's selection
+                        // list, remembering which agents brought it in.
+                        enriched.add_discovered_mcp_server(server_name, agents);
+                    }
+                }
+                for (server_name, config) in discovered.servers {
+                    // Overridden servers are written with the project's
+                    // merged config; keep it out of the registry.
+                    if existing.mcp_server_overrides.contains_key(&server_name) {
+                        continue;
                     }
                     // Persist the config to the global registry so that
                     // sync_project_without_autodetect can include it when
                     // building the mcpServers map written to disk.
-                    let _ = core::save_mcp_server_config(&server_name, &config_str);
+                    let _ = sync::save_mcp_server_if_changed(&server_name, &config);
+                }
+                for conflict in discovered.conflicts {
+                    // Keep whatever the registry already has; the user
+                    // resolves the conflict from autodetect results.
+                    tracing::warn!(
+                        "MCP server '{}' differs between agents {:?}; registry left unchanged",
+                        conflict.name,
+                        conflict
+                            .variants
+                            .iter()
+                            .map(|v| v.agent_id.as_str())
+                            .collect::<Vec<_>>()
+                    );
                 }
             }
 
@@ -289,10 +476,7 @@ pub fn save_project(name: &str, data: &str) -> Result<(), String> {
         let is_new_project = is_new;
         tauri::async_runtime::spawn(async move {
             if let Err(e) = run_ai_recommendations_bg(&project_name, is_new_project).await {
-                eprintln!(
-                    "[automatic] AI recommendations skipped for '{}': {}",
-                    project_name, e
-                );
+                tracing::warn!("AI recommendations skipped for '{}': {}", project_name, e);
             }
         });
     }
@@ -317,6 +501,31 @@ async fn run_ai_recommendations_bg(project: &str, force: bool) -> Result<(), Str
     Ok(())
 }
 
+/// Create project `new_name` in the existing `new_directory` with the
+/// configuration of `source_name`, and sync it.  Returns the new project and
+/// the files written.
+#[tauri::command]
+pub fn duplicate_project(
+    source_name: &str,
+    new_name: &str,
+    new_directory: &str,
+    copy_instructions: Option<bool>,
+) -> Result<core::DuplicatedProject, String> {
+    let duplicated = core::duplicate_project(
+        source_name,
+        new_name,
+        new_directory,
+        copy_instructions.unwrap_or(false),
+    )?;
+    activity::log(
+        new_name,
+        ActivityEvent::ProjectCreated,
+        "Project created",
+        &format!("Duplicated from {}", source_name),
+    );
+    Ok(duplicated)
+}
+
 #[tauri::command]
 pub fn rename_project(old_name: &str, new_name: &str) -> Result<(), String> {
     core::rename_project(old_name, new_name)?;
@@ -418,6 +627,7 @@ pub fn save_project_context_raw(name: &str, content: &str) -> Result<(), String>
     if project.directory.is_empty() {
         return Err("Project has no directory configured".into());
     }
+    core::check_repo_storage(&project, "Project context")?;
 
     let mut value: serde_json::Value =
         serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
@@ -474,6 +684,7 @@ pub fn save_project_docs_raw(name: &str, content: &str) -> Result<(), String> {
     let raw = core::read_project(name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    core::check_repo_storage(&project, "The docs index")?;
     let docs: HashMap<String, context::DocEntry> =
         serde_json::from_str(content).map_err(|e| format!("Invalid docs JSON: {}", e))?;
 
@@ -719,12 +930,15 @@ fn convert_arrays_to_context_maps(ai: &serde_json::Value) -> Result<serde_json::
 
 // ── Project Sync ─────────────────────────────────────────────────────────────
 
+/// Sync a project and report the files written and any MCP servers left out
+/// for want of a keychain entry.
 #[tauri::command]
-pub fn sync_project(name: &str) -> Result<String, String> {
+pub fn sync_project(name: &str) -> Result<sync::SyncReport, String> {
     let raw = core::read_project(name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
-    let written = sync::sync_project(&project)?;
+    let report = sync::sync_project_with_report(&project)?;
+    let written = &report.files;
     if !written.is_empty() {
         let detail = format!(
             "{} file{}",
@@ -738,7 +952,56 @@ pub fn sync_project(name: &str) -> Result<String, String> {
             &detail,
         );
     }
-    serde_json::to_string_pretty(&written).map_err(|e| e.to_string())
+    Ok(report)
+}
+
+/// Sync every registered project.  Projects without a usable directory and
+/// locked projects are skipped; one failing project does not stop the rest.
+#[tauri::command]
+pub async fn sync_all_projects() -> Result<Vec<sync::ProjectSyncResult>, String> {
+    tauri::async_runtime::spawn_blocking(sync::sync_all_projects)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Search `root_dirs` for directories that already hold agent configs, for
+/// bulk onboarding.  Bounded in time; see [`sync::ProjectScan::truncated`].
+#[tauri::command]
+pub async fn scan_for_projects(
+    root_dirs: Vec<String>,
+    max_depth: Option<usize>,
+) -> Result<sync::ProjectScan, String> {
+    let max_depth = max_depth.unwrap_or(sync::DEFAULT_SCAN_MAX_DEPTH);
+    tauri::async_runtime::spawn_blocking(move || sync::scan_for_projects(root_dirs, max_depth))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cancel_project_scan() -> bool {
+    sync::cancel_project_scan()
+}
+
+/// Register and sync the discovered projects the user selected.
+#[tauri::command]
+pub async fn adopt_discovered_projects(
+    selected: Vec<sync::DiscoveredProject>,
+) -> Result<Vec<core::AdoptedProject>, String> {
+    let adopted =
+        tauri::async_runtime::spawn_blocking(move || core::adopt_discovered_projects(selected))
+            .await
+            .map_err(|e| e.to_string())?;
+    for result in &adopted {
+        if let (core::AdoptOutcome::Adopted, Some(name)) = (result.outcome, &result.project) {
+            activity::log(
+                name,
+                ActivityEvent::ProjectCreated,
+                "Project created",
+                &format!("Adopted from {}", result.directory),
+            );
+        }
+    }
+    Ok(adopted)
 }
 
 #[tauri::command]
@@ -746,6 +1009,7 @@ pub fn rebuild_project(name: &str) -> Result<String, String> {
     let raw = core::read_project(name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.ensure_unlocked()?;
 
     let mut rebuilt = sync::rebuild_project_state(&project)?;
     let rebuilt_json = serde_json::to_string_pretty(&rebuilt).map_err(|e| e.to_string())?;
@@ -829,9 +1093,10 @@ fn custom_command_names(project: &core::Project) -> Vec<String> {
         .unwrap_or_default()
 }
 
-/// Return the list of file/directory paths that would be removed if the given
-/// agent were removed from the project.  Read-only -- used to populate the
-/// confirmation dialog before the user commits to the removal.
+/// Return the file/directory paths that would be removed if the given agent
+/// were removed from the project, plus the MCP servers only that agent brought
+/// in.  Read-only -- used to populate the confirmation dialog before the user
+/// commits to the removal.
 #[tauri::command]
 pub fn get_agent_cleanup_preview(name: &str, agent_id: &str) -> Result<String, String> {
     let raw = core::read_project(name)?;
@@ -842,16 +1107,99 @@ pub fn get_agent_cleanup_preview(name: &str, agent_id: &str) -> Result<String, S
 }
 
 /// Remove an agent from a project and delete all files it wrote.
-/// The project config is persisted and remaining agents are re-synced.
-/// Returns a JSON array of paths that were removed or modified.
+/// `unique_servers` decides whether MCP servers discovered only from this
+/// agent stay in the project (the default) or are removed with it.
+/// `disable_detection` additionally stops autodetection from re-adding the
+/// agent.  The project config is persisted and remaining agents are re-synced.
+/// Returns a JSON-serialised [`sync::AgentRemoval`].
 #[tauri::command]
-pub fn remove_agent_from_project(name: &str, agent_id: &str) -> Result<String, String> {
+pub fn remove_agent_from_project(
+    name: &str,
+    agent_id: &str,
+    unique_servers: Option<sync::UniqueMcpServers>,
+    disable_detection: Option<bool>,
+) -> Result<String, String> {
     let raw = core::read_project(name)?;
     let mut project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
-    let removed = sync::remove_agent_from_project(&mut project, agent_id)?;
+    let removal = sync::remove_agent_from_project(
+        &mut project,
+        agent_id,
+        unique_servers.unwrap_or_default(),
+        disable_detection.unwrap_or(false),
+    )?;
     activity::log(name, ActivityEvent::AgentRemoved, "Agent removed", agent_id);
-    serde_json::to_string(&removed).map_err(|e| e.to_string())
+    for server in &removal.removed_mcp_servers {
+        activity::log(
+            name,
+            ActivityEvent::McpServerRemoved,
+            "MCP server removed",
+            server,
+        );
+    }
+    serde_json::to_string(&removal).map_err(|e| e.to_string())
+}
+
+/// Files and directories [`unsync_project`] would remove or modify.
+/// Read-only -- used to populate the confirmation dialog.
+#[tauri::command]
+pub fn get_unsync_project_preview(name: &str) -> Result<Vec<String>, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    sync::unsync_project_preview(&project)
+}
+
+/// Remove everything Automatic wrote into the project directory (agent
+/// configs, skills, managed instruction sections and `.automatic/`) while
+/// keeping the project in the local registry.  The project is locked
+/// afterwards; unlocking and syncing writes the files again.  Returns the
+/// paths removed or modified.
+#[tauri::command]
+pub fn unsync_project(name: &str) -> Result<Vec<String>, String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let removed = sync::unsync_project(&mut project)?;
+    activity::log(
+        name,
+        ActivityEvent::ProjectUpdated,
+        "Project unsynced",
+        &format!(
+            "{} path{} removed or modified",
+            removed.len(),
+            if removed.len() == 1 { "" } else { "s" }
+        ),
+    );
+    Ok(removed)
+}
+
+/// List the backups taken before agents were removed from the project,
+/// newest first.  Each one can be undone with [`restore_agent_removal`]
+/// until it is pruned after [`sync::TRASH_RETENTION_DAYS`] days.
+#[tauri::command]
+pub fn list_removal_backups(name: &str) -> Result<Vec<sync::RemovalBackupSummary>, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    sync::list_removal_backups(&project)
+}
+
+/// Undo an agent removal: restore the files saved under `token` and put the
+/// agent and the MCP servers that left with it back into the project.
+#[tauri::command]
+pub fn restore_agent_removal(name: &str, token: &str) -> Result<sync::AgentRemovalRestore, String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let restored = sync::restore_agent_removal(&mut project, token)?;
+    activity::log(
+        name,
+        ActivityEvent::AgentAdded,
+        "Agent removal undone",
+        &restored.agent,
+    );
+    Ok(restored)
 }
 
 /// Check whether the on-disk agent configs have drifted from what Automatic would
@@ -862,10 +1210,211 @@ pub fn check_project_drift(name: &str) -> Result<String, String> {
     let raw = core::read_project(name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
-    let report = sync::check_project_drift(&project)?;
+    let report = sync::check_project_drift(&project, false)?;
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+/// [`check_project_drift`] with a unified diff for every modified file, so
+/// the user can see exactly what was hand-edited before re-syncing over it.
+#[tauri::command]
+pub fn check_project_drift_detailed(name: &str) -> Result<String, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let report = sync::check_project_drift(&project, true)?;
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+/// Settle drifted files one by one.  `resolutions_json` is a JSON object
+/// mapping each drifted path to `"keep_disk"` (adopt the file's MCP servers
+/// or skill content into the registry) or `"keep_registry"` (rewrite the
+/// file).  Returns a JSON [`sync::DriftResolutionReport`].
+#[tauri::command]
+pub fn resolve_project_drift(name: &str, resolutions_json: &str) -> Result<String, String> {
+    let resolutions: std::collections::BTreeMap<String, sync::DriftResolution> =
+        serde_json::from_str(resolutions_json)
+            .map_err(|e| format!("Invalid drift resolutions: {}", e))?;
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let report = sync::resolve_drift(&mut project, &resolutions)?;
+    activity::log(
+        name,
+        ActivityEvent::ProjectUpdated,
+        "Resolved drifted files",
+        &format!(
+            "{} kept from disk, {} rewritten",
+            resolutions
+                .values()
+                .filter(|r| **r == sync::DriftResolution::KeepDisk)
+                .count(),
+            report.rewritten_files.len()
+        ),
+    );
     serde_json::to_string(&report).map_err(|e| e.to_string())
 }
 
+/// Watch a project's agent config files and emit `project-drift-detected`
+/// when they are edited by hand.  The choice is remembered across launches.
+#[tauri::command]
+pub fn enable_drift_watch(name: &str) -> Result<(), String> {
+    sync::enable_drift_watch(name)
+}
+
+/// Stop watching a project for drift.
+#[tauri::command]
+pub fn disable_drift_watch(name: &str) -> Result<(), String> {
+    sync::disable_drift_watch(name)
+}
+
+/// The project's override for MCP server `server`, or `null` when the
+/// registry config is used unchanged.
+#[tauri::command]
+pub fn get_project_mcp_override(
+    name: &str,
+    server: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    Ok(project.mcp_server_overrides.get(server).cloned())
+}
+
+/// Set the project's override for MCP server `server`.  `json` is a JSON
+/// merge patch object applied over the registry config at sync time
+/// (`null` values remove fields); an empty string, `null` or `{}` removes
+/// the override.  Re-syncs the project so its agent configs pick it up.
+#[tauri::command]
+pub fn set_project_mcp_override(name: &str, server: &str, json: &str) -> Result<(), String> {
+    let patch: serde_json::Value = if json.trim().is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_str(json).map_err(|e| format!("Invalid override JSON: {}", e))?
+    };
+    let clear = match &patch {
+        serde_json::Value::Null => true,
+        serde_json::Value::Object(fields) => fields.is_empty(),
+        _ => return Err("An MCP server override must be a JSON object".into()),
+    };
+
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.ensure_unlocked()?;
+    if !project.mcp_servers.iter().any(|s| s == server) {
+        return Err(format!(
+            "MCP server '{}' is not part of project '{}'",
+            server, name
+        ));
+    }
+
+    let detail = if clear {
+        if project.mcp_server_overrides.remove(server).is_none() {
+            return Ok(());
+        }
+        format!("{} (cleared)", server)
+    } else {
+        project
+            .mcp_server_overrides
+            .insert(server.to_string(), patch);
+        server.to_string()
+    };
+
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    let data = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
+    core::save_project(name, &data)?;
+
+    activity::log(
+        name,
+        ActivityEvent::ProjectUpdated,
+        "MCP server override updated",
+        &detail,
+    );
+
+    if !project.directory.is_empty() && !project.agents.is_empty() {
+        sync::sync_project_without_autodetect(&mut project)?;
+    }
+
+    Ok(())
+}
+
+/// Whether everything the project selects exists: its directory, the
+/// registry entries behind its skills, MCP servers and rules, its agents'
+/// instruction files and the skills in `.agents/skills/`.  Read-only.
+#[tauri::command]
+pub fn check_project_health(name: &str) -> Result<sync::ProjectHealthReport, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    sync::check_project_health(&project)
+}
+
+/// Setup checklist for a project: instruction files, rules, sync state, MCP
+/// server configuration, the Claude plugin, .gitignore coverage and pending
+/// conflicts, with a 0–100 completeness score.  Read-only.
+#[tauri::command]
+pub fn get_project_checklist(name: &str) -> Result<core::ProjectChecklist, String> {
+    core::get_project_checklist(name)
+}
+
+/// Sync generations recorded for a project, newest first.
+#[tauri::command]
+pub fn list_sync_generations(name: &str) -> Result<Vec<sync::GenerationSummary>, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    sync::list_generations(&project)
+}
+
+/// Roll every Automatic-managed file of a project back to a sync
+/// generation.  The project is not re-synced afterwards; reactive syncs
+/// leave it alone until the user syncs explicitly.
+#[tauri::command]
+pub fn restore_sync_generation(
+    name: &str,
+    generation: &str,
+) -> Result<sync::GenerationRestoreReport, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let report = sync::restore_generation(&project, generation)?;
+    activity::log(
+        name,
+        ActivityEvent::ProjectUpdated,
+        "Restored sync generation",
+        generation,
+    );
+    Ok(report)
+}
+
+/// Add or remove one skill, MCP server or rule across many projects.
+/// Changed projects are persisted and synced in the background; the result
+/// lists what happened to each project.
+#[tauri::command]
+pub fn bulk_update_projects(
+    op: core::BulkOp,
+    item: &str,
+    targets: core::BulkTargets,
+) -> Result<Vec<core::BulkProjectResult>, String> {
+    core::bulk_update_projects(op, item, &targets)
+}
+
+/// Resolve a skill whose project copy differs from the global registry
+/// version (listed in the project's `skill_conflicts`).  The project is
+/// persisted and re-synced.  Returns a JSON array of paths that were written.
+#[tauri::command]
+pub fn resolve_skill_shadow(
+    name: &str,
+    skill: &str,
+    resolution: sync::SkillShadowResolution,
+) -> Result<String, String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let written = sync::resolve_skill_shadow(&mut project, skill, resolution)?;
+    serde_json::to_string(&written).map_err(|e| e.to_string())
+}
+
 /// Adopt a stale skill by adding it to the project's skill list and re-syncing.
 ///
 /// `skill_name` is the bare skill name (e.g. `"my-skill"`).  The skill must
@@ -880,6 +1429,7 @@ pub fn adopt_stale_skill(name: &str, skill_name: &str) -> Result<(), String> {
     let raw = core::read_project(name)?;
     let mut project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.ensure_unlocked()?;
 
     // Only add if not already present.
     if !project.skills.contains(&skill_name.to_string()) {
@@ -918,6 +1468,7 @@ pub fn remove_stale_skill(name: &str, skill_name: &str) -> Result<(), String> {
     let raw = core::read_project(name)?;
     let project: core::Project =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.ensure_unlocked()?;
 
     if project.directory.is_empty() {
         return Err("Project has no directory configured".into());
@@ -929,14 +1480,14 @@ pub fn remove_stale_skill(name: &str, skill_name: &str) -> Result<(), String> {
     }
 
     let mut removed_any = false;
+    let context = core::RemovalContext::project(&dir);
 
     for agent_id in &project.agents {
         if let Some(agent_instance) = crate::agent::from_id(agent_id) {
             for skill_dir in agent_instance.skill_dirs(&dir) {
                 let target = skill_dir.join(skill_name);
                 if target.is_dir() {
-                    std::fs::remove_dir_all(&target)
-                        .map_err(|e| format!("Failed to remove {}: {}", target.display(), e))?;
+                    core::remove_guarded(&target, &context)?;
                     removed_any = true;
                 }
             }
@@ -946,8 +1497,7 @@ pub fn remove_stale_skill(name: &str, skill_name: &str) -> Result<(), String> {
     // Also check the project hub (.agents/skills/)
     let hub_dir = dir.join(".agents").join("skills").join(skill_name);
     if hub_dir.is_dir() {
-        std::fs::remove_dir_all(&hub_dir)
-            .map_err(|e| format!("Failed to remove {}: {}", hub_dir.display(), e))?;
+        core::remove_guarded(&hub_dir, &context)?;
         removed_any = true;
     }
 
@@ -973,59 +1523,10 @@ pub fn remove_stale_skill(name: &str, skill_name: &str) -> Result<(), String> {
 // These are used by skills, rules, mcp_servers, and skill_store modules when
 // a registry item is saved or deleted and projects referencing it need updating.
 
-pub(crate) fn with_each_project_mut<F>(mut f: F)
-where
-    F: FnMut(&str, &mut core::Project),
-{
-    let project_names = match core::list_projects() {
-        Ok(names) => names,
-        Err(e) => {
-            eprintln!("Failed to list projects for config updates: {}", e);
-            return;
-        }
-    };
-
-    for project_name in project_names {
-        let raw = match core::read_project(&project_name) {
-            Ok(raw) => raw,
-            Err(e) => {
-                eprintln!("Failed to read project '{}': {}", project_name, e);
-                continue;
-            }
-        };
-
-        let mut project: core::Project = match serde_json::from_str(&raw) {
-            Ok(project) => project,
-            Err(e) => {
-                eprintln!("Failed to parse project '{}': {}", project_name, e);
-                continue;
-            }
-        };
-
-        f(&project_name, &mut project);
-    }
-}
-
-pub(crate) fn sync_project_if_configured(project_name: &str, project: &mut core::Project) {
-    if project.directory.is_empty() || project.agents.is_empty() {
-        return;
-    }
-
-    if let Err(e) = sync::sync_project_without_autodetect(project) {
-        eprintln!(
-            "Failed to sync project '{}' after registry update: {}",
-            project_name, e
-        );
-    }
-}
-
-pub(crate) fn sync_projects_referencing_skill(skill_name: &str) {
-    with_each_project_mut(|project_name, project| {
-        if project.skills.iter().any(|skill| skill == skill_name) {
-            sync_project_if_configured(project_name, project);
-        }
-    });
-}
+pub(crate) use crate::sync::{
+    record_locked_skip, sync_project_if_configured, sync_projects_referencing_skill,
+    sync_projects_referencing_skill_set, sync_projects_referencing_skills, with_each_project_mut,
+};
 
 pub(crate) fn sync_projects_referencing_mcp_server(server_name: &str) {
     with_each_project_mut(|project_name, project| {
@@ -1041,6 +1542,12 @@ pub(crate) fn sync_projects_referencing_mcp_server(server_name: &str) {
 
 pub(crate) fn prune_skill_from_projects(skill_name: &str) {
     with_each_project_mut(|project_name, project| {
+        if project.locked {
+            if project.skills.iter().any(|skill| skill == skill_name) {
+                record_locked_skip(project_name, project);
+            }
+            return;
+        }
         let before = project.skills.len();
         project.skills.retain(|skill| skill != skill_name);
 
@@ -1049,11 +1556,11 @@ pub(crate) fn prune_skill_from_projects(skill_name: &str) {
             match serde_json::to_string_pretty(project).map_err(|e| e.to_string()) {
                 Ok(data) => {
                     if let Err(e) = core::save_project(project_name, &data) {
-                        eprintln!("Failed to update project '{}': {}", project_name, e);
+                        tracing::error!("Failed to update project '{}': {}", project_name, e);
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to serialize project '{}': {}", project_name, e);
+                    tracing::error!("Failed to serialize project '{}': {}", project_name, e);
                 }
             }
             sync_project_if_configured(project_name, project);
@@ -1061,8 +1568,45 @@ pub(crate) fn prune_skill_from_projects(skill_name: &str) {
     });
 }
 
+/// Drop a deleted skill set from every project that applies it.  The
+/// projects are not re-synced: the set's skills stay on disk until the next
+/// sync recomputes the selection.
+pub(crate) fn prune_skill_set_from_projects(set_name: &str) {
+    with_each_project_mut(|project_name, project| {
+        if !project.skill_sets.iter().any(|set| set == set_name) {
+            return;
+        }
+        if project.locked {
+            record_locked_skip(project_name, project);
+            return;
+        }
+        project.skill_sets.retain(|set| set != set_name);
+        project.updated_at = chrono::Utc::now().to_rfc3339();
+        match serde_json::to_string_pretty(project).map_err(|e| e.to_string()) {
+            Ok(data) => {
+                if let Err(e) = core::save_project(project_name, &data) {
+                    tracing::error!("Failed to update project '{}': {}", project_name, e);
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to serialize project '{}': {}", project_name, e);
+            }
+        }
+    });
+}
+
 pub(crate) fn prune_mcp_server_from_projects(server_name: &str) {
     with_each_project_mut(|project_name, project| {
+        if project.locked {
+            if project
+                .mcp_servers
+                .iter()
+                .any(|server| server == server_name)
+            {
+                record_locked_skip(project_name, project);
+            }
+            return;
+        }
         let before = project.mcp_servers.len();
         project.mcp_servers.retain(|server| server != server_name);
         project
@@ -1074,11 +1618,11 @@ pub(crate) fn prune_mcp_server_from_projects(server_name: &str) {
             match serde_json::to_string_pretty(project).map_err(|e| e.to_string()) {
                 Ok(data) => {
                     if let Err(e) = core::save_project(project_name, &data) {
-                        eprintln!("Failed to update project '{}': {}", project_name, e);
+                        tracing::error!("Failed to update project '{}': {}", project_name, e);
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to serialize project '{}': {}", project_name, e);
+                    tracing::error!("Failed to serialize project '{}': {}", project_name, e);
                 }
             }
             sync_project_if_configured(project_name, project);
@@ -1088,6 +1632,16 @@ pub(crate) fn prune_mcp_server_from_projects(server_name: &str) {
 
 pub(crate) fn prune_rule_from_projects(rule_name: &str) {
     with_each_project_mut(|project_name, project| {
+        if project.locked {
+            if project
+                .file_rules
+                .values()
+                .any(|rules| rules.iter().any(|r| r == rule_name))
+            {
+                record_locked_skip(project_name, project);
+            }
+            return;
+        }
         let mut changed = false;
         for rules in project.file_rules.values_mut() {
             let before = rules.len();
@@ -1104,22 +1658,149 @@ pub(crate) fn prune_rule_from_projects(rule_name: &str) {
             match serde_json::to_string_pretty(project).map_err(|e| e.to_string()) {
                 Ok(data) => {
                     if let Err(e) = core::save_project(project_name, &data) {
-                        eprintln!("Failed to update project '{}': {}", project_name, e);
+                        tracing::error!("Failed to update project '{}': {}", project_name, e);
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to serialize project '{}': {}", project_name, e);
+                    tracing::error!("Failed to serialize project '{}': {}", project_name, e);
+                }
+            }
+            reinject_project_rules(project);
+            sync_project_if_configured(project_name, project);
+        }
+    });
+}
+
+/// Re-inject rules into every file with a `file_rules` entry, skipping any
+/// file whose rules are managed via .claude/rules/ (inline injection must
+/// not happen there), and record the files rewritten in sync history.
+fn reinject_project_rules(project: &core::Project) {
+    let mut written = Vec::new();
+    for (filename, rules) in &project.file_rules {
+        if core::project_uses_dot_claude_rules(project, filename) {
+            continue;
+        }
+        if let Ok(true) = core::inject_rules_into_project_file(&project.directory, filename, rules)
+        {
+            written.push(
+                std::path::PathBuf::from(&project.directory)
+                    .join(filename)
+                    .display()
+                    .to_string(),
+            );
+        }
+    }
+    core::log_sync_history(
+        project,
+        core::SyncAction::RulesInjection,
+        None,
+        &written,
+        &[],
+    );
+}
+
+/// Replace `old_name` with `new_name` in `names`, dropping it instead when
+/// `new_name` is already listed.  Returns whether `old_name` was present.
+fn rename_in_list(names: &mut Vec<String>, old_name: &str, new_name: &str) -> bool {
+    let Some(pos) = names.iter().position(|n| n == old_name) else {
+        return false;
+    };
+    if names.iter().any(|n| n == new_name) {
+        names.remove(pos);
+    } else {
+        names[pos] = new_name.to_string();
+    }
+    true
+}
+
+pub(crate) fn rename_skill_in_projects(old_name: &str, new_name: &str) {
+    with_each_project_mut(|project_name, project| {
+        if project.locked {
+            if project.skills.iter().any(|skill| skill == old_name) {
+                record_locked_skip(project_name, project);
+            }
+            return;
+        }
+        if !rename_in_list(&mut project.skills, old_name, new_name) {
+            return;
+        }
+        rename_in_list(&mut project.preserved_skills, old_name, new_name);
+        if let Some(hash) = project.skill_hashes.remove(old_name) {
+            project.skill_hashes.insert(new_name.to_string(), hash);
+        }
+
+        project.updated_at = chrono::Utc::now().to_rfc3339();
+        match serde_json::to_string_pretty(project).map_err(|e| e.to_string()) {
+            Ok(data) => {
+                if let Err(e) = core::save_project(project_name, &data) {
+                    tracing::error!("Failed to update project '{}': {}", project_name, e);
                 }
             }
-            // Re-inject rules for affected files, skipping any file whose rules
-            // are managed via .claude/rules/ (inline injection must not happen there).
-            for (filename, rules) in &project.file_rules {
-                if core::project_uses_dot_claude_rules(project, filename) {
-                    continue;
+            Err(e) => {
+                tracing::error!("Failed to serialize project '{}': {}", project_name, e);
+            }
+        }
+        sync_project_if_configured(project_name, project);
+    });
+}
+
+pub(crate) fn rename_rule_in_projects(old_name: &str, new_name: &str) {
+    with_each_project_mut(|project_name, project| {
+        if project.locked {
+            if project
+                .file_rules
+                .values()
+                .any(|rules| rules.iter().any(|r| r == old_name))
+            {
+                record_locked_skip(project_name, project);
+            }
+            return;
+        }
+        let mut changed = false;
+        for rules in project.file_rules.values_mut() {
+            changed |= rename_in_list(rules, old_name, new_name);
+        }
+
+        if changed {
+            project.updated_at = chrono::Utc::now().to_rfc3339();
+            match serde_json::to_string_pretty(project).map_err(|e| e.to_string()) {
+                Ok(data) => {
+                    if let Err(e) = core::save_project(project_name, &data) {
+                        tracing::error!("Failed to update project '{}': {}", project_name, e);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to serialize project '{}': {}", project_name, e);
                 }
-                let _ = core::inject_rules_into_project_file(&project.directory, filename, rules);
             }
+            reinject_project_rules(project);
             sync_project_if_configured(project_name, project);
         }
     });
 }
+
+/// Whether this build can safely edit the project, with the notice to show
+/// when it was saved by a newer Automatic.
+#[tauri::command]
+pub fn get_project_version_compat(name: &str) -> Result<core::VersionCompat, String> {
+    let raw = core::read_project(name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    Ok(core::version_compat(
+        &format!("Project '{}'", name),
+        project.min_app_version.as_deref(),
+    ))
+}
+
+/// Let autodetection manage an agent the user previously ignored.  The agent
+/// is not added back by this call; the next sync re-adds it if its files are
+/// present.
+#[tauri::command]
+pub fn unignore_agent(name: &str, agent_id: &str) -> Result<(), String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.ensure_unlocked()?;
+    project.disabled_agents.retain(|a| a != agent_id);
+    core::persist_project(&project)
+}
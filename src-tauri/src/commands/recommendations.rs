@@ -336,7 +336,7 @@ async fn verify_marketplace_recommendation(
 ) -> Result<Option<VerifiedMarketplaceItem>, String> {
     match kind {
         "skill" => {
-            let results = crate::core::search_remote_skills(title).await?;
+            let results = crate::core::search_remote_skills(title, &Default::default()).await?;
             let wanted = normalize_slugish(title);
             let selected = results.iter().find(|r| {
                 eq_ignore_case(&r.name, title)
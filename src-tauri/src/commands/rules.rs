@@ -3,7 +3,8 @@ use std::path::PathBuf;
 use crate::core;
 
 use super::projects::{
-    prune_rule_from_projects, sync_project_if_configured, with_each_project_mut,
+    prune_rule_from_projects, rename_rule_in_projects, sync_project_if_configured,
+    with_each_project_mut,
 };
 
 // ── Rules ────────────────────────────────────────────────────────────────────
@@ -28,6 +29,123 @@ pub fn save_rule(machine_name: &str, name: &str, content: &str) -> Result<(), St
     core::save_rule(machine_name, name, content)
 }
 
+/// Overwrite a bundled rule with the default shipped with this release.
+/// Like `save_rule`, referencing projects are not re-synced here.
+#[tauri::command]
+pub fn reset_rule_to_default(machine_name: &str) -> Result<(), String> {
+    core::reset_rule_to_default(machine_name)
+}
+
+/// Compare a bundled rule with the default shipped with this release.
+#[tauri::command]
+pub fn diff_rule_against_default(machine_name: &str) -> Result<core::RuleDefaultDiff, String> {
+    core::diff_rule_against_default(machine_name)
+}
+
+/// Add a rule candidate recovered by autodetection to the registry and
+/// attach it to the project where it was found.
+#[tauri::command]
+pub fn import_rule_candidate(name: &str, machine_name: &str) -> Result<(), String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.ensure_unlocked()?;
+
+    let index = project
+        .rule_candidates
+        .iter()
+        .position(|c| c.machine_name == machine_name)
+        .ok_or_else(|| format!("No rule candidate '{}' in project '{}'", machine_name, name))?;
+    if core::list_rules()?.iter().any(|r| r.id == machine_name) {
+        return Err(format!("Rule '{}' already exists", machine_name));
+    }
+    let candidate = project.rule_candidates.remove(index);
+    core::save_rule(&candidate.machine_name, &candidate.name, &candidate.content)?;
+
+    // Follow the project's existing layout: per-file keys when it already
+    // uses them, otherwise the project-wide list.
+    let per_file = project.instruction_mode != "unified"
+        && !project
+            .file_rules
+            .get("_project")
+            .is_some_and(|rules| !rules.is_empty())
+        && project
+            .file_rules
+            .iter()
+            .any(|(key, rules)| !key.starts_with('_') && !rules.is_empty());
+    let keys = if per_file {
+        candidate.files.clone()
+    } else {
+        vec!["_project".to_string()]
+    };
+    for key in keys {
+        let rules = project.file_rules.entry(key).or_default();
+        if !rules.contains(&candidate.machine_name) {
+            rules.push(candidate.machine_name.clone());
+        }
+    }
+
+    core::persist_project(&project)?;
+    sync_project_if_configured(name, &mut project);
+    Ok(())
+}
+
+/// Decline a recovered rule candidate.  It stays recorded as dismissed so
+/// autodetection does not offer it again.
+#[tauri::command]
+pub fn dismiss_rule_candidate(name: &str, machine_name: &str) -> Result<(), String> {
+    let raw = core::read_project(name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    project.ensure_unlocked()?;
+
+    let candidate = project
+        .rule_candidates
+        .iter_mut()
+        .find(|c| c.machine_name == machine_name)
+        .ok_or_else(|| format!("No rule candidate '{}' in project '{}'", machine_name, name))?;
+    candidate.dismissed = true;
+    core::persist_project(&project)
+}
+
+/// The rules section `rule_names` would produce, with the status of each
+/// rule so dead references show up before a sync.
+#[tauri::command]
+pub fn preview_rules_section(rule_names: Vec<String>) -> Result<core::RulesPreview, String> {
+    core::build_rules_section(&rule_names)
+}
+
+/// The rules attached to one `file_rules` key of a project, in the order
+/// they are written into the file.
+#[tauri::command]
+pub fn get_project_file_rules(project: &str, filename: &str) -> Result<Vec<String>, String> {
+    let raw = core::read_project(project)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    Ok(project
+        .file_rules
+        .get(filename)
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Replace the rules attached to one `file_rules` key with `ordered_rules`,
+/// in that order, then rewrite the file and re-sync the project.  An empty
+/// list detaches every rule from the key.
+#[tauri::command]
+pub fn set_project_file_rules(
+    project: &str,
+    filename: &str,
+    ordered_rules: Vec<String>,
+) -> Result<(), String> {
+    let raw = core::read_project(project)?;
+    let mut updated: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    core::set_project_file_rules(&mut updated, filename, &ordered_rules)?;
+    sync_project_if_configured(project, &mut updated);
+    Ok(())
+}
+
 #[tauri::command]
 pub fn delete_rule(machine_name: &str) -> Result<(), String> {
     core::delete_rule(machine_name)?;
@@ -35,6 +153,14 @@ pub fn delete_rule(machine_name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Rename a rule and update every project that uses it.
+#[tauri::command]
+pub fn rename_rule(old_name: &str, new_name: &str) -> Result<(), String> {
+    core::rename_rule(old_name, new_name)?;
+    rename_rule_in_projects(old_name, new_name);
+    Ok(())
+}
+
 /// A project that references a rule, with its current sync status.
 #[derive(serde::Serialize)]
 pub struct RuleProjectStatus {
@@ -125,6 +251,7 @@ pub fn sync_rule_to_project(rule_name: &str, project_name: &str) -> Result<(), S
             project_name, rule_name
         ));
     }
+    project.ensure_unlocked()?;
 
     sync_project_if_configured(project_name, &mut project);
     Ok(())
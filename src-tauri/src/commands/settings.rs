@@ -1,5 +1,8 @@
+use tauri::Emitter;
+
 use crate::agent;
 use crate::core;
+use crate::sync;
 
 // ── Settings ─────────────────────────────────────────────────────────────────
 
@@ -28,6 +31,74 @@ pub fn erase_app_data() -> Result<(), String> {
     core::erase_app_data()
 }
 
+/// The directory Automatic keeps its data in.
+#[tauri::command]
+pub fn get_data_dir() -> Result<String, String> {
+    Ok(core::data_root()?.display().to_string())
+}
+
+/// Move the data directory to `new_path`.  With `dry_run`, only lists what
+/// would move.  The Claude Code plugin lives in the data directory, so it is
+/// re-registered from its new location afterwards.
+#[tauri::command]
+pub async fn migrate_data_dir(
+    new_path: String,
+    dry_run: Option<bool>,
+) -> Result<core::DataDirMigration, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut report = core::migrate_data_dir(&new_path, dry_run)?;
+        if !dry_run {
+            if let Err(e) = core::install_plugin_marketplace() {
+                tracing::warn!("plugin reinstall after data dir move failed: {}", e);
+                report.warnings.push(format!(
+                    "The Claude Code plugin could not be reinstalled: {}",
+                    e
+                ));
+            }
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// ── Backup & Restore ─────────────────────────────────────────────────────────
+
+/// Write a full backup to `dest`, emitting `backup-progress` per file.
+#[tauri::command]
+pub async fn export_backup(
+    app: tauri::AppHandle,
+    dest: String,
+    options: Option<core::BackupOptions>,
+) -> Result<core::BackupManifest, String> {
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        core::export_all_with_progress(&dest, &options, |progress| {
+            let _ = app.emit("backup-progress", &progress);
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Restore the backup at `path`, emitting `backup-progress` per file or
+/// skill.  With `strategy.dry_run` only the report is produced.
+#[tauri::command]
+pub async fn restore_backup(
+    app: tauri::AppHandle,
+    path: String,
+    strategy: core::RestoreStrategy,
+) -> Result<core::RestoreReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        core::restore_all_with_progress(&path, &strategy, |progress| {
+            let _ = app.emit("backup-progress", &progress);
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 pub fn dismiss_welcome() -> Result<(), String> {
     core::dismiss_welcome()
@@ -42,3 +113,10 @@ pub fn clear_opencode_cache() -> Result<agent::ClearCacheResult, String> {
 pub fn clean_opencode_snapshots() -> Result<agent::CleanSnapshotsResult, String> {
     agent::clean_opencode_snapshots()
 }
+
+/// Re-run the migration from the legacy `nexus` naming on demand.  Safe to
+/// run repeatedly; returns what was changed.
+#[tauri::command]
+pub fn migrate_legacy_nexus() -> Result<sync::LegacyMigrationReport, String> {
+    sync::migrate_legacy_nexus()
+}
@@ -1,12 +1,25 @@
 use crate::core;
 
-use super::projects::sync_projects_referencing_skill;
+use super::projects::{sync_projects_referencing_skill, sync_projects_referencing_skills};
 
 // ── Skills Store ─────────────────────────────────────────────────────────────
 
+/// Search skills.sh.  `limit` defaults to 20 and `offset` to 0; `sort` is
+/// relevance (the default) or installs.
 #[tauri::command]
-pub async fn search_remote_skills(query: String) -> Result<Vec<core::RemoteSkillResult>, String> {
-    core::search_remote_skills(&query).await
+pub async fn search_remote_skills(
+    query: String,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    sort: Option<core::SkillSearchSort>,
+) -> Result<Vec<core::RemoteSkillResult>, String> {
+    let defaults = core::SkillSearchOptions::default();
+    let options = core::SkillSearchOptions {
+        limit: limit.unwrap_or(defaults.limit),
+        offset: offset.unwrap_or(defaults.offset),
+        sort: sort.unwrap_or_default(),
+    };
+    core::search_remote_skills(&query, &options).await
 }
 
 #[tauri::command]
@@ -15,15 +28,25 @@ pub async fn fetch_remote_skill_content(source: String, name: String) -> Result<
 }
 
 /// Import a skill from skills.sh: save content + record its remote origin.
+/// Also used to apply updates: a skill edited locally since it was installed
+/// is only overwritten with `force`.
 #[tauri::command]
 pub async fn import_remote_skill(
     name: String,
     content: String,
     source: String,
     id: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
+    if !force.unwrap_or(false) && core::skill_local_modifications(&name) {
+        return Err(format!(
+            "Skill '{}' has local changes that would be overwritten; pass force to replace them",
+            name
+        ));
+    }
     core::save_skill(&name, &content)?;
     core::record_skill_source(&name, &source, &id, "github")?;
+    core::capture_remote_license(&name, &source).await;
     sync_projects_referencing_skill(&name);
     // Mark getting-started flag; best-effort — never block the install.
     if let Err(e) = core::mark_skill_installed() {
@@ -32,9 +55,71 @@ pub async fn import_remote_skill(
     Ok(())
 }
 
+/// Import several skills from skills.sh at once.  `json` is an array of
+/// `{ name, source, id?, force? }`.  Returns one [`core::ImportResult`] per
+/// skill; projects using the imported skills are re-synced once at the end.
+#[tauri::command]
+pub async fn import_remote_skills(json: String) -> Result<Vec<core::ImportResult>, String> {
+    let requests: Vec<core::RemoteSkillRef> =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid skill list: {}", e))?;
+    let results = core::import_remote_skills(requests).await;
+    let imported: Vec<String> = results
+        .iter()
+        .filter(|r| r.ok)
+        .map(|r| r.name.clone())
+        .collect();
+    if !imported.is_empty() {
+        sync_projects_referencing_skills(&imported);
+        if let Err(e) = core::mark_skill_installed() {
+            eprintln!("[automatic] Failed to mark skill_installed flag: {}", e);
+        }
+    }
+    Ok(results)
+}
+
 /// Return all entries from ~/.automatic/skills.json as a JSON object.
 #[tauri::command]
 pub fn get_skill_sources() -> Result<String, String> {
     let registry = core::read_skill_sources()?;
     serde_json::to_string(&registry).map_err(|e| e.to_string())
 }
+
+/// Every installed skill with its recorded license and source, for the
+/// compliance view.  Informational only.
+#[tauri::command]
+pub fn get_skill_license_report() -> Result<Vec<core::SkillLicenseEntry>, String> {
+    core::license_report()
+}
+
+/// Export the license report as `"csv"` or `"markdown"`.
+#[tauri::command]
+pub fn export_skill_license_report(format: String) -> Result<String, String> {
+    let report = core::license_report()?;
+    match format.as_str() {
+        "csv" => Ok(core::license_report_csv(&report)),
+        "markdown" | "md" => Ok(core::license_report_markdown(&report)),
+        other => Err(format!("Unsupported report format '{}'", other)),
+    }
+}
+
+// ── Skill Updates ────────────────────────────────────────────────────────────
+
+/// Check all imported skills for upstream changes now, ignoring the
+/// background schedule.  Returns a [`core::SkillUpdateReport`].
+#[tauri::command]
+pub async fn check_skill_updates_now() -> Result<core::SkillUpdateReport, String> {
+    core::check_skill_updates_now().await
+}
+
+/// Return the persisted state of the last update check, including the list
+/// of skills with updates available.
+#[tauri::command]
+pub fn get_skill_update_status() -> Result<core::SkillUpdateState, String> {
+    core::read_skill_update_state()
+}
+
+/// Pin or unpin an imported skill so the update check skips it.
+#[tauri::command]
+pub fn set_skill_pinned(name: String, pinned: bool) -> Result<(), String> {
+    core::set_skill_pinned(&name, pinned)
+}
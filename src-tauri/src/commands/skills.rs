@@ -12,7 +12,7 @@ pub fn get_skills() -> Result<Vec<core::SkillEntry>, String> {
 
 #[tauri::command]
 pub fn list_skill_directories() -> Result<Vec<core::SkillSourceDir>, String> {
-    Ok(core::get_all_skill_sources())
+    Ok(core::get_skill_lookup_sources())
 }
 
 #[tauri::command]
@@ -20,11 +20,20 @@ pub fn read_skill(name: &str) -> Result<String, String> {
     core::read_skill(name)
 }
 
+/// Save a skill and re-sync the projects using it.  Frontmatter problems
+/// do not stop the save; they come back as warnings.
 #[tauri::command]
-pub fn save_skill(name: &str, content: &str) -> Result<(), String> {
-    core::save_skill(name, content)?;
+pub fn save_skill(name: &str, content: &str) -> Result<core::SkillSaveResult, String> {
+    let result = core::save_skill_checked(name, content)?;
     sync_projects_referencing_skill(name);
-    Ok(())
+    Ok(result)
+}
+
+/// Check a skill's SKILL.md frontmatter.  `name_or_content` is either an
+/// installed skill's name or raw SKILL.md content.
+#[tauri::command]
+pub fn validate_skill(name_or_content: &str) -> Result<Vec<core::SkillValidationIssue>, String> {
+    core::validate_skill(name_or_content)
 }
 
 #[tauri::command]
@@ -39,10 +48,29 @@ pub fn delete_skill(name: &str) -> Result<(), String> {
         ));
     }
     core::delete_skill(name)?;
+    core::remove_skill_from_skill_sets(name);
     super::projects::prune_skill_from_projects(name);
     Ok(())
 }
 
+/// Rename a skill in every global directory and every project that uses it.
+#[tauri::command]
+pub fn rename_skill(old_name: &str, new_name: &str) -> Result<(), String> {
+    if core::is_builtin_skill(old_name) {
+        return Err(format!("Cannot rename built-in skill '{}'", old_name));
+    }
+    if let Some(pid) = core::plugin_id_for_skill(old_name) {
+        return Err(format!(
+            "Cannot rename skill '{}' — it is provided by plugin '{}'",
+            old_name, pid
+        ));
+    }
+    core::rename_skill(old_name, new_name)?;
+    core::rename_skill_in_skill_sets(old_name, new_name);
+    super::projects::rename_skill_in_projects(old_name, new_name);
+    Ok(())
+}
+
 /// Sync a single skill across both global directories (~/.agents/skills/ and
 /// ~/.claude/skills/).
 #[tauri::command]
@@ -57,11 +85,60 @@ pub fn sync_all_skills() -> Result<Vec<String>, String> {
     core::sync_all_skills()
 }
 
+/// Move a library skill into ~/.agents/skills/ so every agent sees it.
+#[tauri::command]
+pub fn promote_skill_to_global(name: &str) -> Result<(), String> {
+    core::promote_skill_to_global(name)
+}
+
+/// Move a skill out of the global directories into the skill library.
+/// Projects that use it keep their copies.
+#[tauri::command]
+pub fn demote_skill_to_library(name: &str) -> Result<(), String> {
+    if core::is_builtin_skill(name) {
+        return Err(format!("Cannot move built-in skill '{}'", name));
+    }
+    if let Some(pid) = core::plugin_id_for_skill(name) {
+        return Err(format!(
+            "Cannot move skill '{}' — it is provided by plugin '{}'",
+            name, pid
+        ));
+    }
+    core::demote_skill_to_library(name)
+}
+
 #[tauri::command]
 pub fn get_skill_resources(name: &str) -> Result<core::SkillResources, String> {
     core::list_skill_resources(name)
 }
 
+#[tauri::command]
+pub fn read_skill_resource(name: &str, path: &str) -> Result<String, String> {
+    core::read_skill_resource(name, path)
+}
+
+#[tauri::command]
+pub fn save_skill_resource(name: &str, path: &str, content: &str) -> Result<(), String> {
+    core::save_skill_resource(name, path, content)?;
+    sync_projects_referencing_skill(name);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_skill_resource(name: &str, path: &str) -> Result<(), String> {
+    core::delete_skill_resource(name, path)?;
+    sync_projects_referencing_skill(name);
+    Ok(())
+}
+
+/// Per-agent preview of the files a project sync would produce for a skill
+/// (the skill editor's "How agents see this" tab).  Writes nothing.
+#[tauri::command]
+pub fn preview_skill_for_agents(skill_name: &str, project_name: &str) -> Result<String, String> {
+    let preview = core::preview_skill_for_agents(skill_name, project_name)?;
+    serde_json::to_string_pretty(&preview).map_err(|e| e.to_string())
+}
+
 // ── Local Skills ─────────────────────────────────────────────────────────
 
 /// Import a local skill into the global registry and promote it to a normal
@@ -161,6 +238,22 @@ pub fn import_skill_from_package(path: String) -> Result<String, String> {
     serde_json::to_string_pretty(&imported).map_err(|e| e.to_string())
 }
 
+/// Install every skill from an offline skill bundle, skipping skills that
+/// already exist.  Used where skills.sh and GitHub are unreachable.
+#[tauri::command]
+pub fn import_skill_bundle(path: String) -> Result<core::SkillBundleImport, String> {
+    core::import_skill_bundle(&path)
+}
+
+/// Write the named skills to an offline skill bundle at `dest`.
+#[tauri::command]
+pub fn export_skill_bundle(
+    skill_names: Vec<String>,
+    dest: String,
+) -> Result<core::SkillBundleIndex, String> {
+    core::export_skill_bundle(&skill_names, &dest)
+}
+
 // ── Skill Collections ─────────────────────────────────────────────────────
 
 /// Return all skill collections with their member skill names.
@@ -180,3 +273,32 @@ pub fn set_skill_collection(skill_name: String, collection: String) -> Result<()
 pub fn remove_skill_collection(skill_name: String) -> Result<(), String> {
     core::remove_skill_collection(&skill_name)
 }
+
+// ── Skill Sets ────────────────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn list_skill_sets() -> Result<Vec<core::SkillSet>, String> {
+    core::list_skill_sets()
+}
+
+#[tauri::command]
+pub fn read_skill_set(name: &str) -> Result<core::SkillSet, String> {
+    core::read_skill_set(name)
+}
+
+/// Save a skill set and re-sync the projects that apply it.
+#[tauri::command]
+pub fn save_skill_set(set: core::SkillSet) -> Result<core::SkillSet, String> {
+    let saved = core::save_skill_set(&set)?;
+    super::projects::sync_projects_referencing_skill_set(&saved.name);
+    Ok(saved)
+}
+
+/// Delete a skill set and remove it from every project.  Skills it added
+/// stay in project directories until those projects are next synced.
+#[tauri::command]
+pub fn delete_skill_set(name: &str) -> Result<(), String> {
+    core::delete_skill_set(name)?;
+    super::projects::prune_skill_set_from_projects(name);
+    Ok(())
+}
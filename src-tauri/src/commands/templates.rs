@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use crate::activity::{self, ActivityEvent};
 use crate::core;
 
 // ── Instruction Templates ────────────────────────────────────────────────────
@@ -22,6 +25,29 @@ pub fn delete_template(name: &str) -> Result<(), String> {
     core::delete_template(name)
 }
 
+/// Render instruction template `template` for project `project`.  The
+/// context holds the project's name, directory, agents, skills and today's
+/// date, plus any `variables` (which win over the built-in keys).
+/// Placeholders without a value are left as written and listed in
+/// `errors`; with `strict`, any such placeholder fails the call instead.
+#[tauri::command]
+pub fn render_template_for_project(
+    template: &str,
+    project: &str,
+    variables: Option<HashMap<String, String>>,
+    strict: Option<bool>,
+) -> Result<core::RenderedTemplate, String> {
+    let raw = core::read_project(project)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let context = core::project_template_context(&project, &variables.unwrap_or_default());
+    let rendered = core::render_template(template, &context)?;
+    if strict.unwrap_or(false) && !rendered.errors.is_empty() {
+        return Err(rendered.errors.join("; "));
+    }
+    Ok(rendered)
+}
+
 // ── Project Templates ─────────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -49,11 +75,76 @@ pub fn rename_project_template(old_name: &str, new_name: &str) -> Result<(), Str
     core::rename_project_template(old_name, new_name)
 }
 
+/// Apply a saved project template to an existing project.  Agent configs are
+/// only synced when `sync` is set; otherwise the returned JSON
+/// [`core::TemplateApplyReport`] lets the user review what changed (and
+/// install unresolved dependencies) first.
+#[tauri::command]
+pub fn apply_project_template(
+    template_name: &str,
+    project_name: &str,
+    mode: Option<core::TemplateApplyMode>,
+    sync: Option<bool>,
+) -> Result<String, String> {
+    let raw = core::read_project(project_name)?;
+    let mut project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let mut report =
+        core::apply_project_template(template_name, &mut project, mode.unwrap_or_default())?;
+    activity::log(
+        project_name,
+        ActivityEvent::TemplateApplied,
+        "Template applied",
+        &report.summary(),
+    );
+    if sync.unwrap_or(false) {
+        match crate::sync::sync_project_without_autodetect(&mut project) {
+            Ok(files) => report.synced_files = Some(files),
+            Err(e) => report.warnings.push(format!("Sync failed: {}", e)),
+        }
+    }
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+/// Save an existing project's agents, skills, MCP servers and instruction
+/// files as a new project template.  Returns the JSON
+/// [`core::SavedProjectTemplate`], which lists the local skills left out.
+#[tauri::command]
+pub fn save_project_as_template(
+    project_name: &str,
+    template_name: &str,
+    overwrite: Option<bool>,
+) -> Result<String, String> {
+    let raw = core::read_project(project_name)?;
+    let project: core::Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let saved =
+        core::save_project_as_template(&project, template_name, overwrite.unwrap_or(false))?;
+    serde_json::to_string(&saved).map_err(|e| e.to_string())
+}
+
 // ── Template Marketplace (bundled) ────────────────────────────────────────────
 
+/// Bundled templates plus those in `~/.automatic/marketplace_templates/` and
+/// the remote index, with a warning for each local file that could not be
+/// used.  The remote index is refreshed first when its cache has expired.
 #[tauri::command]
-pub fn list_bundled_project_templates() -> Result<String, String> {
-    core::list_bundled_project_templates()
+pub async fn list_bundled_project_templates() -> Result<core::MarketplaceTemplates, String> {
+    marketplace_listing(false).await
+}
+
+/// [`list_bundled_project_templates`], downloading the remote index even when
+/// the cached copy is recent.
+#[tauri::command]
+pub async fn refresh_remote_project_templates() -> Result<core::MarketplaceTemplates, String> {
+    marketplace_listing(true).await
+}
+
+async fn marketplace_listing(force: bool) -> Result<core::MarketplaceTemplates, String> {
+    let remote = core::fetch_remote_project_templates(force).await;
+    let mut listing = core::list_marketplace_templates()?;
+    listing.warnings.extend(remote.warnings);
+    Ok(listing)
 }
 
 #[tauri::command]
@@ -61,19 +152,58 @@ pub fn read_bundled_project_template(name: &str) -> Result<String, String> {
     core::read_bundled_project_template(name)
 }
 
+/// Import a bundled template.  Returns a conflict instead of overwriting a
+/// local template with different content; call again with `overwrite` or
+/// `import_as` to resolve it.
+#[tauri::command]
+pub async fn import_bundled_project_template(
+    name: String,
+    overwrite: Option<bool>,
+    import_as: Option<String>,
+) -> Result<core::TemplateImportOutcome, String> {
+    let outcome = core::import_bundled_project_template(
+        &name,
+        overwrite.unwrap_or(false),
+        import_as.as_deref(),
+    )
+    .await?;
+    if matches!(outcome, core::TemplateImportOutcome::Imported { .. }) {
+        // Mark getting-started flag; best-effort — never block the import.
+        if let Err(e) = core::mark_template_imported() {
+            tracing::warn!("Failed to mark template_imported flag: {}", e);
+        }
+    }
+    Ok(outcome)
+}
+
+/// [`import_bundled_project_template`], reporting how each of the template's
+/// skills was installed.  With `install_missing`, skills the template gives
+/// no source for are looked up on skills.sh by exact name.
 #[tauri::command]
-pub async fn import_bundled_project_template(name: String) -> Result<(), String> {
-    core::import_bundled_project_template(&name).await?;
-    // Mark getting-started flag; best-effort — never block the import.
-    if let Err(e) = core::mark_template_imported() {
-        eprintln!("[automatic] Failed to mark template_imported flag: {}", e);
+pub async fn import_bundled_project_template_with_skills(
+    name: String,
+    install_missing: bool,
+    overwrite: Option<bool>,
+    import_as: Option<String>,
+) -> Result<core::TemplateImportReport, String> {
+    let report = core::import_bundled_project_template_with_skills(
+        &name,
+        overwrite.unwrap_or(false),
+        import_as.as_deref(),
+        install_missing,
+    )
+    .await?;
+    if matches!(report.outcome, core::TemplateImportOutcome::Imported { .. }) {
+        if let Err(e) = core::mark_template_imported() {
+            tracing::warn!("Failed to mark template_imported flag: {}", e);
+        }
     }
-    Ok(())
+    Ok(report)
 }
 
 #[tauri::command]
-pub fn search_bundled_project_templates(query: &str) -> Result<String, String> {
-    core::search_bundled_project_templates(query)
+pub fn search_bundled_project_templates(query: &str) -> Result<core::MarketplaceTemplates, String> {
+    core::search_marketplace_templates(query)
 }
 
 /// Check which skills / MCP servers a bundled template requires are missing
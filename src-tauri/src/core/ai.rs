@@ -582,7 +582,7 @@ fn search_templates_marketplace_tool_def() -> Value {
 
 async fn execute_search_skills_marketplace(input: &Value) -> String {
     let query = input.get("query").and_then(|v| v.as_str()).unwrap_or("");
-    match crate::core::search_remote_skills(query).await {
+    match crate::core::search_remote_skills(query, &Default::default()).await {
         Ok(results) => serde_json::to_string_pretty(&results).unwrap_or_else(|_| "[]".to_string()),
         Err(e) => format!("Error searching skills marketplace: {}", e),
     }
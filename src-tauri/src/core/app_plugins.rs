@@ -309,6 +309,7 @@ fn sync_plugin_skills(manifests: &[PluginManifest], state: &PluginState) {
                                             name, e
                                         );
                                     }
+                                    super::skill_store::capture_remote_license(name, source).await;
                                 }
                             }
                             Err(e) => {
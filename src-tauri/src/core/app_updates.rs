@@ -0,0 +1,117 @@
+use semver::Version;
+use serde::Serialize;
+
+// ── App Updates ──────────────────────────────────────────────────────────────
+//
+// The updater plugin reads its endpoint from tauri.conf.json, which only knows
+// about stable releases.  The `update_channel` setting picks the manifest at
+// check time instead: stable builds come from the latest GitHub release, beta
+// builds from the manifest attached to the rolling `beta` release.
+
+const RELEASES_URL: &str = "https://github.com/velvet-tiger/automatic/releases";
+
+/// The `update_channel` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    /// Parse the settings value; anything unrecognised means `Stable`.
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "beta" => Self::Beta,
+            _ => Self::Stable,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+        }
+    }
+
+    /// URL of the updater manifest (`latest.json`) for this channel.
+    pub fn endpoint(self) -> String {
+        match self {
+            Self::Stable => format!("{}/latest/download/latest.json", RELEASES_URL),
+            Self::Beta => format!("{}/download/beta/latest.json", RELEASES_URL),
+        }
+    }
+}
+
+/// Whether `remote` should be offered to a build running `current`.
+///
+/// Only newer releases are offered.  Switching from beta back to stable
+/// usually finds an older release; `allow_downgrade` offers it anyway.
+pub fn is_offered_update(current: &Version, remote: &Version, allow_downgrade: bool) -> bool {
+    remote > current || (allow_downgrade && remote != current)
+}
+
+/// An available update, as reported by `check_for_updates` before anything
+/// is downloaded.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheck {
+    pub version: String,
+    pub current_version: String,
+    pub channel: String,
+    pub notes: Option<String>,
+    /// RFC 3339 publish date from the manifest, when it has one.
+    pub published_at: Option<String>,
+    /// Size of the installer in bytes, when the server reports it.
+    pub download_size: Option<u64>,
+    /// `true` when installing would move to an older version.
+    pub downgrade: bool,
+}
+
+/// Payload of the `update-download-progress` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateProgress {
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> Version {
+        Version::parse(s).unwrap()
+    }
+
+    #[test]
+    fn only_newer_releases_are_offered_unless_forced() {
+        let current = v("1.0.0-beta.3");
+        assert!(is_offered_update(&current, &v("1.0.0-beta.4"), false));
+        assert!(is_offered_update(&current, &v("1.0.0"), false));
+        assert!(!is_offered_update(&current, &v("1.0.0-beta.3"), false));
+
+        // Leaving the beta channel: stable is still on an older release.
+        let older = v("0.9.2");
+        assert!(!is_offered_update(&current, &older, false));
+        assert!(is_offered_update(&current, &older, true));
+        assert!(!is_offered_update(&current, &current, true));
+    }
+
+    #[test]
+    fn channels_map_to_their_manifests() {
+        assert_eq!(UpdateChannel::parse("beta"), UpdateChannel::Beta);
+        assert_eq!(UpdateChannel::parse("stable"), UpdateChannel::Stable);
+        assert_eq!(UpdateChannel::parse("nightly"), UpdateChannel::Stable);
+        assert_eq!(UpdateChannel::Beta.as_str(), "beta");
+
+        assert_eq!(
+            UpdateChannel::Stable.endpoint(),
+            "https://github.com/velvet-tiger/automatic/releases/latest/download/latest.json"
+        );
+        assert_eq!(
+            UpdateChannel::Beta.endpoint(),
+            "https://github.com/velvet-tiger/automatic/releases/download/beta/latest.json"
+        );
+        assert!(url::Url::parse(&UpdateChannel::Beta.endpoint()).is_ok());
+    }
+}
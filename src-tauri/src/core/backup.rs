@@ -0,0 +1,1051 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use super::env_crypto::is_encrypted;
+use super::*;
+
+// ── Full Backup & Restore ────────────────────────────────────────────────────
+//
+// A backup carries everything Automatic keeps outside project repositories,
+// for disaster recovery or moving to a new machine.  It is a zip archive
+// (conventionally `*.automatic-backup`) laid out as:
+//
+//   manifest.json          {"format": 1, "categories": [...], ...}
+//   <category>/<path>      one directory per category, e.g. `rules/style.md`
+//                          or `skills/lint/scripts/run.sh`
+//
+// The manifest indexes every file by category and lists the secrets that were
+// left out.  Keychain entries (API keys, MCP OAuth tokens, the key encrypting
+// MCP `env` values) are never read, only probed for existence, so the user
+// knows what to re-enter after restoring.
+//
+// Restoring validates the whole archive first, the same way skill bundles
+// are validated, then applies each category with its own strategy.
+
+/// Version of the backup layout written by [`export_all`].
+pub const BACKUP_FORMAT: u32 = 1;
+
+const BACKUP_MANIFEST: &str = "manifest.json";
+const MAX_BACKUP_ENTRIES: usize = 200_000;
+const MAX_BACKUP_FILE_BYTES: u64 = 50 * 1024 * 1024;
+const MAX_BACKUP_TOTAL_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+/// One kind of data a backup can carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupCategory {
+    Settings,
+    Profile,
+    Projects,
+    Skills,
+    SkillSources,
+    Rules,
+    Templates,
+    ProjectTemplates,
+    Agents,
+    Commands,
+    Tools,
+    McpServers,
+    Memory,
+}
+
+impl BackupCategory {
+    pub const ALL: [BackupCategory; 13] = [
+        Self::Settings,
+        Self::Profile,
+        Self::Projects,
+        Self::Skills,
+        Self::SkillSources,
+        Self::Rules,
+        Self::Templates,
+        Self::ProjectTemplates,
+        Self::Agents,
+        Self::Commands,
+        Self::Tools,
+        Self::McpServers,
+        Self::Memory,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Settings => "settings",
+            Self::Profile => "profile",
+            Self::Projects => "projects",
+            Self::Skills => "skills",
+            Self::SkillSources => "skill_sources",
+            Self::Rules => "rules",
+            Self::Templates => "templates",
+            Self::ProjectTemplates => "project_templates",
+            Self::Agents => "agents",
+            Self::Commands => "commands",
+            Self::Tools => "tools",
+            Self::McpServers => "mcp_servers",
+            Self::Memory => "memory",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == value)
+    }
+
+    /// Where the category lives: a base directory and, for categories made
+    /// of loose files in `~/.automatic`, the file names.  `None` means the
+    /// whole directory tree.
+    fn layout(self, roots: &BackupRoots) -> (PathBuf, Option<&'static [&'static str]>) {
+        let dir = |name: &str| (roots.automatic.join(name), None);
+        match self {
+            Self::Settings => (roots.automatic.clone(), Some(&["settings.json"])),
+            Self::Profile => (roots.automatic.clone(), Some(&["profile.json"])),
+            Self::SkillSources => (
+                roots.automatic.clone(),
+                Some(&["skills.json", "skill-collections.json"]),
+            ),
+            Self::Skills => (roots.skills.clone(), None),
+            Self::Projects => dir("projects"),
+            Self::Rules => dir("rules"),
+            Self::Templates => dir("templates"),
+            Self::ProjectTemplates => dir("project_templates"),
+            Self::Agents => dir("agents"),
+            Self::Commands => dir("commands"),
+            Self::Tools => dir("tools"),
+            Self::McpServers => dir("mcp_servers"),
+            Self::Memory => dir("memory"),
+        }
+    }
+
+    /// Where restore may delete files of this category: the skills
+    /// directory for skills, the app data directory for everything else.
+    fn removal_context(self, roots: &BackupRoots) -> RemovalContext {
+        match self {
+            Self::Skills => RemovalContext::skills(vec![roots.skills.clone()]),
+            _ => RemovalContext::app_data(),
+        }
+    }
+
+    /// Whether file permissions travel with the backup.  Only skills keep
+    /// theirs, so companion scripts stay executable; everything else is
+    /// restored readable by the user alone.
+    fn keeps_permissions(self) -> bool {
+        matches!(self, Self::Skills)
+    }
+
+    /// The unit merged or replaced as a whole, for a file path within the
+    /// category.  A skill is its directory, so a restore never mixes files
+    /// from two versions of one skill; everything else is a single file.
+    fn unit_of(self, relative: &str) -> String {
+        match self {
+            Self::Skills => relative.split('/').next().unwrap_or(relative).to_string(),
+            _ => relative.to_string(),
+        }
+    }
+}
+
+/// Base directories a backup reads from and restores into.
+pub(crate) struct BackupRoots {
+    /// `~/.automatic`
+    pub automatic: PathBuf,
+    /// `~/.agents/skills`
+    pub skills: PathBuf,
+}
+
+impl BackupRoots {
+    fn current() -> Result<Self, String> {
+        Ok(Self {
+            automatic: get_automatic_dir()?,
+            skills: get_agents_skills_dir()?,
+        })
+    }
+}
+
+/// What [`export_all`] includes.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BackupOptions {
+    /// Categories to export; empty means all of them.
+    #[serde(default)]
+    pub categories: Vec<BackupCategory>,
+}
+
+/// Kind of secret left out of a backup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretKind {
+    /// An AI provider API key, named by provider.
+    ApiKey,
+    /// An MCP server's OAuth token, named by server.
+    McpOauth,
+    /// Encrypted `env` values of an MCP server.  The key that decrypts them
+    /// stays in this machine's keychain.
+    McpEnv,
+}
+
+/// A secret the user has to re-enter after restoring on another machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExcludedSecret {
+    pub kind: SecretKind,
+    pub name: String,
+}
+
+/// The files one category contributed to a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupCategoryIndex {
+    pub category: BackupCategory,
+    /// Paths relative to the category, `/`-separated and sorted.
+    pub files: Vec<String>,
+    pub bytes: u64,
+}
+
+/// The `manifest.json` at the root of a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format: u32,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub app_version: String,
+    pub categories: Vec<BackupCategoryIndex>,
+    #[serde(default)]
+    pub excluded_secrets: Vec<ExcludedSecret>,
+}
+
+/// Payload of the `backup-progress` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupProgress {
+    /// `"export"` or `"restore"`.
+    pub phase: &'static str,
+    pub category: BackupCategory,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// How [`restore_all`] treats one category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreMode {
+    /// Add what is missing; anything that already exists is kept as-is.
+    #[default]
+    Merge,
+    /// Make the category match the backup, removing what it does not have.
+    Overwrite,
+    /// Leave the category untouched.
+    Skip,
+}
+
+/// Restore strategy: a default mode, per-category overrides, and whether to
+/// only report what would change.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RestoreStrategy {
+    #[serde(default)]
+    pub default: RestoreMode,
+    #[serde(default)]
+    pub categories: HashMap<BackupCategory, RestoreMode>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl RestoreStrategy {
+    fn mode_for(&self, category: BackupCategory) -> RestoreMode {
+        self.categories
+            .get(&category)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreAction {
+    /// Not present locally; restored from the backup.
+    Create,
+    /// Present locally with different content; replaced by the backup.
+    Replace,
+    /// Present locally with different content; kept (merge).
+    Keep,
+    /// Present locally but not in the backup; removed (overwrite).
+    Remove,
+}
+
+/// One change made (or, in a dry run, that would be made) by a restore.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RestoreChange {
+    pub category: BackupCategory,
+    /// File path, or skill name for `skills`, relative to the category.
+    pub path: String,
+    pub action: RestoreAction,
+}
+
+/// Result of [`restore_all`].
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    pub dry_run: bool,
+    /// Changes in category order.  Units identical on both sides are omitted.
+    pub changes: Vec<RestoreChange>,
+    /// Secrets the backup did not carry, for the user to re-enter.
+    pub excluded_secrets: Vec<ExcludedSecret>,
+}
+
+// ── Export ───────────────────────────────────────────────────────────────────
+
+/// Write a backup of the selected categories to `dest`.
+pub fn export_all(dest: &str, options: &BackupOptions) -> Result<BackupManifest, String> {
+    export_all_with_progress(dest, options, |_| {})
+}
+
+/// [`export_all`], reporting each file written.
+pub fn export_all_with_progress(
+    dest: &str,
+    options: &BackupOptions,
+    on_progress: impl FnMut(BackupProgress),
+) -> Result<BackupManifest, String> {
+    let roots = BackupRoots::current()?;
+    let has_secret = |kind: SecretKind, name: &str| match kind {
+        SecretKind::ApiKey => has_api_key(name),
+        SecretKind::McpOauth => crate::proxy::has_oauth_token(name),
+        SecretKind::McpEnv => false,
+    };
+    let api_key_providers = list_api_key_providers()?;
+    export_to(
+        &roots,
+        &api_key_providers,
+        &has_secret,
+        Path::new(dest),
+        options,
+        on_progress,
+    )
+}
+
+fn export_to(
+    roots: &BackupRoots,
+    api_key_providers: &[String],
+    has_secret: &dyn Fn(SecretKind, &str) -> bool,
+    dest: &Path,
+    options: &BackupOptions,
+    mut on_progress: impl FnMut(BackupProgress),
+) -> Result<BackupManifest, String> {
+    let selected: Vec<BackupCategory> = BackupCategory::ALL
+        .into_iter()
+        .filter(|c| options.categories.is_empty() || options.categories.contains(c))
+        .collect();
+
+    let mut categories = Vec::new();
+    for category in selected {
+        let (base, _) = category.layout(roots);
+        let files = category_files(category, roots);
+        let bytes = files
+            .iter()
+            .filter_map(|f| fs::metadata(base.join(f)).ok())
+            .map(|m| m.len())
+            .sum();
+        categories.push(BackupCategoryIndex {
+            category,
+            files,
+            bytes,
+        });
+    }
+
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        app_version: APP_VERSION.to_string(),
+        categories,
+        excluded_secrets: excluded_secrets(roots, api_key_providers, has_secret),
+    };
+
+    let file = fs::File::create(dest)
+        .map_err(|e| format!("Failed to create '{}': {}", dest.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().large_file(true);
+
+    zip.start_file(BACKUP_MANIFEST, options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(to_stable_json(&manifest)?.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    for index in &manifest.categories {
+        let (base, _) = index.category.layout(roots);
+        for (done, relative) in index.files.iter().enumerate() {
+            let entry_name = format!("{}/{}", index.category.as_str(), relative);
+            let path = base.join(relative);
+            let data =
+                fs::read(&path).map_err(|e| format!("Failed to read {}: {}", entry_name, e))?;
+            let entry_options = if index.category.keeps_permissions() {
+                zip_entry_options(&path).large_file(true)
+            } else {
+                options
+            };
+            zip.start_file(entry_name, entry_options)
+                .map_err(|e| e.to_string())?;
+            zip.write_all(&data).map_err(|e| e.to_string())?;
+            on_progress(BackupProgress {
+                phase: "export",
+                category: index.category,
+                done: done + 1,
+                total: index.files.len(),
+            });
+        }
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(manifest)
+}
+
+/// Files of `category` on disk, relative to its base, `/`-separated and
+/// sorted.  Symlinks are left out.
+fn category_files(category: BackupCategory, roots: &BackupRoots) -> Vec<String> {
+    let (base, names) = category.layout(roots);
+    let mut files: Vec<PathBuf> = match names {
+        Some(names) => names
+            .iter()
+            .filter(|name| base.join(name).is_file())
+            .map(PathBuf::from)
+            .collect(),
+        None => {
+            let mut files = Vec::new();
+            collect_bundle_files(&base, &base, &mut files);
+            files
+        }
+    };
+    files.sort();
+    files
+        .into_iter()
+        .map(|f| f.to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+/// Keychain entries and encrypted values a backup cannot carry.
+/// `api_key_providers` lists the API keys known to the keychain.
+fn excluded_secrets(
+    roots: &BackupRoots,
+    api_key_providers: &[String],
+    has_secret: &dyn Fn(SecretKind, &str) -> bool,
+) -> Vec<ExcludedSecret> {
+    let mut secrets: Vec<ExcludedSecret> = api_key_providers
+        .iter()
+        .filter(|provider| has_secret(SecretKind::ApiKey, provider))
+        .map(|provider| ExcludedSecret {
+            kind: SecretKind::ApiKey,
+            name: provider.clone(),
+        })
+        .collect();
+
+    let (servers_dir, _) = BackupCategory::McpServers.layout(roots);
+    for relative in category_files(BackupCategory::McpServers, roots) {
+        let Some(server) = relative.strip_suffix(".json").filter(|s| is_valid_name(s)) else {
+            continue;
+        };
+        if has_secret(SecretKind::McpOauth, server) {
+            secrets.push(ExcludedSecret {
+                kind: SecretKind::McpOauth,
+                name: server.to_string(),
+            });
+        }
+        let encrypted = fs::read_to_string(servers_dir.join(&relative))
+            .ok()
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            .is_some_and(|config| has_encrypted_value(&config));
+        if encrypted {
+            secrets.push(ExcludedSecret {
+                kind: SecretKind::McpEnv,
+                name: server.to_string(),
+            });
+        }
+    }
+    secrets
+}
+
+fn has_encrypted_value(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::String(s) => is_encrypted(s),
+        serde_json::Value::Array(items) => items.iter().any(has_encrypted_value),
+        serde_json::Value::Object(map) => map.values().any(has_encrypted_value),
+        _ => false,
+    }
+}
+
+// ── Restore ──────────────────────────────────────────────────────────────────
+
+/// Restore the backup at `path` according to `strategy`.  With
+/// `strategy.dry_run` nothing is written and the report lists what would
+/// change.
+pub fn restore_all(path: &str, strategy: &RestoreStrategy) -> Result<RestoreReport, String> {
+    restore_all_with_progress(path, strategy, |_| {})
+}
+
+/// [`restore_all`], reporting each unit (file or skill) processed.
+pub fn restore_all_with_progress(
+    path: &str,
+    strategy: &RestoreStrategy,
+    on_progress: impl FnMut(BackupProgress),
+) -> Result<RestoreReport, String> {
+    restore_from(
+        &BackupRoots::current()?,
+        Path::new(path),
+        strategy,
+        on_progress,
+    )
+}
+
+fn restore_from(
+    roots: &BackupRoots,
+    backup: &Path,
+    strategy: &RestoreStrategy,
+    mut on_progress: impl FnMut(BackupProgress),
+) -> Result<RestoreReport, String> {
+    let file = fs::File::open(backup)
+        .map_err(|e| format!("Failed to open backup '{}': {}", backup.display(), e))?;
+    let mut archive = zip::ZipArchive::new(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to read backup: {}", e))?;
+
+    let staging = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    extract_backup(&mut archive, staging.path())?;
+    let manifest = read_backup_manifest(staging.path())?;
+
+    let mut changes = Vec::new();
+    for index in &manifest.categories {
+        let category = index.category;
+        let mode = strategy.mode_for(category);
+        if mode == RestoreMode::Skip {
+            continue;
+        }
+        let (base, _) = category.layout(roots);
+        let staged = staging.path().join(category.as_str());
+        let removal = category.removal_context(roots);
+
+        let backup_units = group_by_unit(category, index.files.iter().cloned());
+        let local_units = group_by_unit(category, category_files(category, roots));
+
+        let mut planned: Vec<(String, RestoreAction)> = Vec::new();
+        for (unit, files) in &backup_units {
+            let action = match local_units.get(unit) {
+                None if !base.join(unit).exists() => Some(RestoreAction::Create),
+                Some(local) if units_match(&staged, files, &base, local) => None,
+                _ if mode == RestoreMode::Merge => Some(RestoreAction::Keep),
+                _ => Some(RestoreAction::Replace),
+            };
+            if let Some(action) = action {
+                planned.push((unit.clone(), action));
+            }
+        }
+        if mode == RestoreMode::Overwrite {
+            for unit in local_units.keys() {
+                if !backup_units.contains_key(unit) {
+                    planned.push((unit.clone(), RestoreAction::Remove));
+                }
+            }
+        }
+
+        let total = planned.len();
+        for (done, (unit, action)) in planned.into_iter().enumerate() {
+            if !strategy.dry_run {
+                let empty = Vec::new();
+                let local = local_units.get(&unit).unwrap_or(&empty);
+                match action {
+                    RestoreAction::Create | RestoreAction::Replace => {
+                        let files = backup_units.get(&unit).unwrap_or(&empty);
+                        for stale in local.iter().filter(|f| !files.contains(f)) {
+                            remove_restored_file(&base, stale, &removal)?;
+                        }
+                        for relative in files {
+                            let source = staged.join(relative);
+                            let target = base.join(relative);
+                            let contents = fs::read(&source)
+                                .map_err(|e| format!("Failed to restore {}: {}", relative, e))?;
+                            write_atomic(&target, contents)?;
+                            if category.keeps_permissions() {
+                                let permissions = fs::metadata(&source)
+                                    .map_err(|e| e.to_string())?
+                                    .permissions();
+                                fs::set_permissions(&target, permissions).map_err(|e| {
+                                    format!("Failed to restore {}: {}", relative, e)
+                                })?;
+                            }
+                        }
+                    }
+                    RestoreAction::Remove => {
+                        for relative in local {
+                            remove_restored_file(&base, relative, &removal)?;
+                        }
+                    }
+                    RestoreAction::Keep => {}
+                }
+            }
+            changes.push(RestoreChange {
+                category,
+                path: unit,
+                action,
+            });
+            on_progress(BackupProgress {
+                phase: "restore",
+                category,
+                done: done + 1,
+                total,
+            });
+        }
+    }
+
+    Ok(RestoreReport {
+        dry_run: strategy.dry_run,
+        changes,
+        excluded_secrets: manifest.excluded_secrets,
+    })
+}
+
+fn read_backup_manifest(staging: &Path) -> Result<BackupManifest, String> {
+    let raw = fs::read_to_string(staging.join(BACKUP_MANIFEST))
+        .map_err(|_| "Backup has no manifest.json".to_string())?;
+    let manifest: BackupManifest =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid backup manifest: {}", e))?;
+    if manifest.format > BACKUP_FORMAT {
+        return Err(format!(
+            "Backup format {} is newer than this version of Automatic supports",
+            manifest.format
+        ));
+    }
+    for index in &manifest.categories {
+        for relative in &index.files {
+            let name = format!("{}/{}", index.category.as_str(), relative);
+            if backup_entry_path(&name)?.is_none() || !staging.join(&name).is_file() {
+                return Err(format!("Backup is missing '{}'", name));
+            }
+        }
+    }
+    Ok(manifest)
+}
+
+/// Group `/`-separated relative paths by restore unit.
+fn group_by_unit(
+    category: BackupCategory,
+    files: impl IntoIterator<Item = String>,
+) -> BTreeMap<String, Vec<String>> {
+    let mut units: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for relative in files {
+        units
+            .entry(category.unit_of(&relative))
+            .or_default()
+            .push(relative);
+    }
+    units
+}
+
+/// Whether a unit holds the same files with the same bytes on both sides.
+fn units_match(staged: &Path, backup: &[String], base: &Path, local: &[String]) -> bool {
+    let mut a = backup.to_vec();
+    let mut b = local.to_vec();
+    a.sort();
+    b.sort();
+    a == b
+        && a.iter().all(|relative| {
+            matches!(
+                (fs::read(staged.join(relative)), fs::read(base.join(relative))),
+                (Ok(x), Ok(y)) if x == y
+            )
+        })
+}
+
+/// Remove a restored category file and any directories it leaves empty,
+/// up to (not including) `base`.  The file goes through [`remove_guarded`],
+/// so the `destructive_ops` setting applies; in a dry run it stays put.
+fn remove_restored_file(
+    base: &Path,
+    relative: &str,
+    context: &RemovalContext,
+) -> Result<(), String> {
+    let path = base.join(relative);
+    if !matches!(
+        remove_guarded(&path, context)?,
+        RemovalOutcome::Removed { .. }
+    ) {
+        return Ok(());
+    }
+    let mut dir = path.parent();
+    while let Some(d) = dir.filter(|d| *d != base && d.starts_with(base)) {
+        if fs::remove_dir(d).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+    Ok(())
+}
+
+/// Validate every entry of `archive` and unpack it under `dest`.
+fn extract_backup<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    dest: &Path,
+) -> Result<(), String> {
+    if archive.len() > MAX_BACKUP_ENTRIES {
+        return Err(format!(
+            "Backup has too many entries ({}, limit {})",
+            archive.len(),
+            MAX_BACKUP_ENTRIES
+        ));
+    }
+
+    let mut total: u64 = 0;
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read backup entry {}: {}", i, e))?;
+        let name = file.name().to_string();
+        if file.is_symlink() {
+            return Err(format!("Backup entry '{}' is a symlink", name));
+        }
+        if file.is_dir() {
+            continue;
+        }
+        let category = if name == BACKUP_MANIFEST {
+            None
+        } else {
+            match backup_entry_path(&name)? {
+                Some((category, _)) => Some(category),
+                None => return Err(format!("Unsafe path in backup: '{}'", name)),
+            }
+        };
+        if file.size() > MAX_BACKUP_FILE_BYTES {
+            return Err(format!("Backup entry '{}' is too large", name));
+        }
+
+        let out_path = dest.join(&name);
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let mode = file.unix_mode();
+        let mut out = fs::File::create(&out_path)
+            .map_err(|e| format!("Failed to create file {}: {}", name, e))?;
+        // The declared size can lie; cap what is actually decompressed.
+        let written = std::io::copy(&mut (&mut file).take(MAX_BACKUP_FILE_BYTES + 1), &mut out)
+            .map_err(|e| format!("Failed to extract {}: {}", name, e))?;
+        if written > MAX_BACKUP_FILE_BYTES {
+            return Err(format!("Backup entry '{}' is too large", name));
+        }
+        total += written;
+        if total > MAX_BACKUP_TOTAL_BYTES {
+            return Err("Backup exceeds the maximum unpacked size".into());
+        }
+        if category.is_some_and(BackupCategory::keeps_permissions) {
+            set_zip_entry_mode(&out_path, mode)?;
+        }
+    }
+    Ok(())
+}
+
+/// Split an archive entry name into its category and relative path.
+/// Returns `None` for anything that is not `<category>/<plain components>`,
+/// or a loose-file category entry that is not one of its known files.
+fn backup_entry_path(name: &str) -> Result<Option<(BackupCategory, String)>, String> {
+    if name.contains('\\') || name.contains('\0') {
+        return Ok(None);
+    }
+    let mut parts = Vec::new();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => match part.to_str() {
+                Some(part) => parts.push(part),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        }
+    }
+    let Some((first, rest)) = parts.split_first() else {
+        return Ok(None);
+    };
+    let Some(category) = BackupCategory::parse(first) else {
+        return Ok(None);
+    };
+    if rest.is_empty() {
+        return Ok(None);
+    }
+    let relative = rest.join("/");
+    // Only the layout matters here, not where it points.
+    let roots = BackupRoots {
+        automatic: PathBuf::new(),
+        skills: PathBuf::new(),
+    };
+    if let (_, Some(names)) = category.layout(&roots) {
+        if !names.contains(&relative.as_str()) {
+            return Ok(None);
+        }
+    }
+    Ok(Some((category, relative)))
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::{tempdir, TempDir};
+
+    const API_KEY: &str = "sk-ant-backup-test-secret";
+    const OAUTH_TOKEN: &str = "gho_backup_test_token";
+
+    /// A fixture home with data in every category.
+    fn fixture_home() -> (TempDir, BackupRoots) {
+        let home = tempdir().unwrap();
+        let roots = BackupRoots {
+            automatic: home.path().join(".automatic"),
+            skills: home.path().join(".agents").join("skills"),
+        };
+        let write = |path: PathBuf, content: &[u8]| {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        let a = &roots.automatic;
+        write(a.join("settings.json"), b"{\"update_channel\": \"beta\"}\n");
+        write(a.join("profile.json"), b"{\"name\": \"Dana\"}\n");
+        write(a.join("skills.json"), b"{\"lint\": {}}\n");
+        write(a.join("projects/web.json"), b"{\"name\": \"web\"}\n");
+        write(a.join("rules/style.md"), b"# Style\r\n\r\nTabs.\r\n");
+        write(a.join("templates/basic.md"), b"# Basic\n");
+        write(a.join("project_templates/api.json"), b"{}\n");
+        write(a.join("agents/reviewer.md"), b"# Reviewer\n");
+        write(a.join("commands/ship.md"), b"# Ship\n");
+        write(a.join("tools/jq.json"), b"{}\n");
+        write(
+            a.join("mcp_servers/github.json"),
+            b"{\"env\": {\"GITHUB_TOKEN\": \"enc:v1:AAAA\"}}\n",
+        );
+        write(a.join("mcp_servers/files.json"), b"{\"command\": \"fs\"}\n");
+        write(a.join("memory/web.json"), b"{}\n");
+        // Not part of any category.
+        write(a.join("task_log.json"), b"[]\n");
+        write(
+            roots.skills.join("lint/SKILL.md"),
+            b"---\nname: lint\n---\n",
+        );
+        write(
+            roots.skills.join("lint/scripts/run.sh"),
+            &[0, 159, 146, 150],
+        );
+        (home, roots)
+    }
+
+    /// Stand-in keychain: an Anthropic key and an OAuth token for `github`.
+    fn stored_secret(kind: SecretKind, name: &str) -> Option<&'static str> {
+        match (kind, name) {
+            (SecretKind::ApiKey, "anthropic") => Some(API_KEY),
+            (SecretKind::McpOauth, "github") => Some(OAUTH_TOKEN),
+            _ => None,
+        }
+    }
+
+    fn keychain(kind: SecretKind, name: &str) -> bool {
+        stored_secret(kind, name).is_some()
+    }
+
+    /// API key providers the stand-in keychain is asked about.
+    fn providers() -> Vec<String> {
+        vec!["anthropic".to_string(), "mistral".to_string()]
+    }
+
+    fn export(roots: &BackupRoots, dest: &Path) -> BackupManifest {
+        export_to(
+            roots,
+            &providers(),
+            &keychain,
+            dest,
+            &BackupOptions::default(),
+            |_| {},
+        )
+        .unwrap()
+    }
+
+    fn empty_roots(home: &Path) -> BackupRoots {
+        BackupRoots {
+            automatic: home.join(".automatic"),
+            skills: home.join(".agents").join("skills"),
+        }
+    }
+
+    fn snapshot(roots: &BackupRoots) -> BTreeMap<String, Vec<u8>> {
+        let mut files = BTreeMap::new();
+        for category in BackupCategory::ALL {
+            let (base, _) = category.layout(roots);
+            for relative in category_files(category, roots) {
+                let data = fs::read(base.join(&relative)).unwrap();
+                files.insert(format!("{}/{}", category.as_str(), relative), data);
+            }
+        }
+        files
+    }
+
+    fn overwrite_all() -> RestoreStrategy {
+        RestoreStrategy {
+            default: RestoreMode::Overwrite,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn round_trip_restores_identical_bytes() {
+        let (home, roots) = fixture_home();
+        let backup = home.path().join("all.automatic-backup");
+        let mut progress = Vec::new();
+        let manifest = export_to(
+            &roots,
+            &providers(),
+            &keychain,
+            &backup,
+            &BackupOptions::default(),
+            |p| progress.push((p.category, p.done, p.total)),
+        )
+        .unwrap();
+        assert_eq!(manifest.categories.len(), BackupCategory::ALL.len());
+        assert_eq!(progress.len(), snapshot(&roots).len());
+
+        let target = tempdir().unwrap();
+        let restored = empty_roots(target.path());
+        let report = restore_from(&restored, &backup, &overwrite_all(), |_| {}).unwrap();
+        assert!(report
+            .changes
+            .iter()
+            .all(|c| c.action == RestoreAction::Create));
+        assert!(report.changes.contains(&RestoreChange {
+            category: BackupCategory::Skills,
+            path: "lint".into(),
+            action: RestoreAction::Create,
+        }));
+        assert_eq!(snapshot(&restored), snapshot(&roots));
+        assert!(!restored.automatic.join("task_log.json").exists());
+
+        // Restoring again changes nothing.
+        let again = restore_from(&restored, &backup, &overwrite_all(), |_| {}).unwrap();
+        assert!(again.changes.is_empty(), "{:?}", again.changes);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skill_permissions_survive_a_round_trip() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (home, roots) = fixture_home();
+        let script = roots.skills.join("lint/scripts/run.sh");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let backup = home.path().join("all.automatic-backup");
+        export(&roots, &backup);
+
+        let target = tempdir().unwrap();
+        let restored = empty_roots(target.path());
+        restore_from(&restored, &backup, &overwrite_all(), |_| {}).unwrap();
+        let mode = |path: PathBuf| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(restored.skills.join("lint/scripts/run.sh")), 0o755);
+        assert_eq!(mode(restored.automatic.join("settings.json")), 0o600);
+    }
+
+    #[test]
+    fn keychain_material_is_listed_but_never_archived() {
+        let (home, roots) = fixture_home();
+        let backup = home.path().join("all.automatic-backup");
+        let manifest = export(&roots, &backup);
+
+        let secret = |kind, name: &str| ExcludedSecret {
+            kind,
+            name: name.into(),
+        };
+        assert_eq!(
+            manifest.excluded_secrets,
+            vec![
+                secret(SecretKind::ApiKey, "anthropic"),
+                secret(SecretKind::McpOauth, "github"),
+                secret(SecretKind::McpEnv, "github"),
+            ]
+        );
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&backup).unwrap()).unwrap();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            let text = String::from_utf8_lossy(&data);
+            assert!(!text.contains(API_KEY), "{}", entry.name());
+            assert!(!text.contains(OAUTH_TOKEN), "{}", entry.name());
+        }
+    }
+
+    #[test]
+    fn merge_keeps_local_changes_and_dry_run_writes_nothing() {
+        let (home, roots) = fixture_home();
+        let backup = home.path().join("all.automatic-backup");
+        export(&roots, &backup);
+
+        let target = tempdir().unwrap();
+        let local = empty_roots(target.path());
+        fs::create_dir_all(local.skills.join("mine")).unwrap();
+        fs::write(local.skills.join("mine/SKILL.md"), "# mine\n").unwrap();
+        fs::create_dir_all(&local.automatic).unwrap();
+        fs::write(local.automatic.join("settings.json"), "{}\n").unwrap();
+        let before = snapshot(&local);
+
+        let dry_run = RestoreStrategy {
+            categories: HashMap::from([(BackupCategory::Skills, RestoreMode::Overwrite)]),
+            dry_run: true,
+            ..Default::default()
+        };
+        let report = restore_from(&local, &backup, &dry_run, |_| {}).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(snapshot(&local), before);
+        let action = |category, path: &str| {
+            report
+                .changes
+                .iter()
+                .find(|c| c.category == category && c.path == path)
+                .map(|c| c.action)
+        };
+        assert_eq!(
+            action(BackupCategory::Settings, "settings.json"),
+            Some(RestoreAction::Keep)
+        );
+        assert_eq!(
+            action(BackupCategory::Rules, "style.md"),
+            Some(RestoreAction::Create)
+        );
+        assert_eq!(
+            action(BackupCategory::Skills, "mine"),
+            Some(RestoreAction::Remove)
+        );
+
+        let applied = RestoreStrategy {
+            dry_run: false,
+            ..dry_run
+        };
+        restore_from(&local, &backup, &applied, |_| {}).unwrap();
+        assert_eq!(
+            fs::read_to_string(local.automatic.join("settings.json")).unwrap(),
+            "{}\n"
+        );
+        assert!(!local.skills.join("mine").exists());
+        assert_eq!(
+            fs::read(local.skills.join("lint/scripts/run.sh")).unwrap(),
+            vec![0, 159, 146, 150]
+        );
+    }
+
+    #[test]
+    fn unsafe_entries_reject_the_whole_backup() {
+        let dir = tempdir().unwrap();
+        let roots = empty_roots(dir.path());
+        let manifest = r#"{"format":1,"categories":[]}"#;
+        for evil in [
+            "rules/../../escaped.txt",
+            "../escaped.txt",
+            "/tmp/escaped.txt",
+            "settings/passwords.txt",
+            "keychain/anthropic",
+            "rules\\..\\escaped.txt",
+        ] {
+            let backup = dir.path().join("evil.automatic-backup");
+            let mut zip = zip::ZipWriter::new(fs::File::create(&backup).unwrap());
+            let options = zip::write::SimpleFileOptions::default();
+            for (name, content) in [(BACKUP_MANIFEST, manifest), (evil, "pwned")] {
+                zip.start_file(name, options).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+
+            let err = restore_from(&roots, &backup, &overwrite_all(), |_| {}).unwrap_err();
+            assert!(err.contains("Unsafe path"), "{}: {}", evil, err);
+            assert!(!dir.path().join("escaped.txt").exists());
+            assert!(!roots.automatic.exists(), "{}", evil);
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::*;
+
+// ── Bulk Project Updates ─────────────────────────────────────────────────────
+//
+// Applies one change (add a skill, detach a rule, …) to many projects at once.
+// The item is validated once up front; after that every project is handled on
+// its own, so one project that cannot be updated (missing directory, locked,
+// unreadable config) is reported without stopping the rest.  Updated projects
+// are synced together on a single background thread once all edits are
+// persisted, so the caller never waits for N syncs in a row.
+
+/// The change to apply.  Every operation takes the name of one registry item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkOp {
+    AddSkill,
+    RemoveSkill,
+    AddMcpServer,
+    RemoveMcpServer,
+    AttachRule,
+    DetachRule,
+}
+
+/// Which projects to change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum BulkTargets {
+    Projects(Vec<String>),
+    /// Every project in the named project group.
+    Group(String),
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkOutcome {
+    Updated,
+    /// Adding: the project already had the item.
+    AlreadyPresent,
+    /// Removing: the project did not reference the item.
+    NotReferenced,
+    SkippedLocked,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkProjectResult {
+    pub project: String,
+    pub outcome: BulkOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Advisory notes about the updated project, e.g. that an agent now gets
+    /// more MCP servers than it handles well.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Apply `op` for `item` to every project in `targets`, persist the changed
+/// projects and queue a sync for them.  Only an unknown item or an
+/// unresolvable target list is an error; per-project failures are reported
+/// in the results.
+pub fn bulk_update_projects(
+    op: BulkOp,
+    item: &str,
+    targets: &BulkTargets,
+) -> Result<Vec<BulkProjectResult>, String> {
+    validate_bulk_item(op, item)?;
+    let projects = resolve_bulk_targets(targets)?
+        .into_iter()
+        .map(|name| {
+            let project = read_project(&name)
+                .and_then(|raw| serde_json::from_str::<Project>(&raw).map_err(|e| e.to_string()));
+            (name, project)
+        })
+        .collect();
+
+    let (mut results, updated) = bulk_update_with(projects, op, item, persist_project);
+    if op == BulkOp::AddMcpServer {
+        for project in &updated {
+            if let Some(result) = results.iter_mut().find(|r| r.project == project.name) {
+                result.warnings = project_mcp_budget_warnings(project)
+                    .iter()
+                    .map(McpBudgetWarning::message)
+                    .collect();
+            }
+        }
+    }
+    queue_bulk_syncs(updated);
+    Ok(results)
+}
+
+/// Adding needs the item to exist in the registry.  Removing does not, so a
+/// reference to an item that was deleted elsewhere can still be cleaned up.
+fn validate_bulk_item(op: BulkOp, item: &str) -> Result<(), String> {
+    let exists = match op {
+        BulkOp::AddSkill => list_skill_names()?.iter().any(|s| s == item),
+        BulkOp::AddMcpServer => list_mcp_server_configs()?.iter().any(|s| s == item),
+        BulkOp::AttachRule => list_rules()?.iter().any(|r| r.id == item),
+        BulkOp::RemoveSkill | BulkOp::RemoveMcpServer | BulkOp::DetachRule => is_valid_name(item),
+    };
+    if exists {
+        Ok(())
+    } else {
+        Err(format!("'{}' not found", item))
+    }
+}
+
+fn resolve_bulk_targets(targets: &BulkTargets) -> Result<Vec<String>, String> {
+    match targets {
+        BulkTargets::Projects(names) => {
+            let mut seen = std::collections::HashSet::new();
+            Ok(names
+                .iter()
+                .filter(|n| seen.insert(n.as_str()))
+                .cloned()
+                .collect())
+        }
+        BulkTargets::Group(group) => {
+            let raw = read_group(group)?;
+            let group: ProjectGroup = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+            Ok(group.projects)
+        }
+        BulkTargets::All => list_projects(),
+    }
+}
+
+/// Apply `op` to each loaded project, saving changed ones with `persist`.
+/// Returns the per-project results and the projects that changed.
+fn bulk_update_with(
+    projects: Vec<(String, Result<Project, String>)>,
+    op: BulkOp,
+    item: &str,
+    mut persist: impl FnMut(&Project) -> Result<(), String>,
+) -> (Vec<BulkProjectResult>, Vec<Project>) {
+    let mut results = Vec::new();
+    let mut updated = Vec::new();
+
+    for (name, project) in projects {
+        let result = |outcome, error| BulkProjectResult {
+            project: name.clone(),
+            outcome,
+            error,
+            warnings: Vec::new(),
+        };
+        let mut project = match project {
+            Ok(p) => p,
+            Err(e) => {
+                results.push(result(BulkOutcome::Failed, Some(e)));
+                continue;
+            }
+        };
+        if project.locked {
+            results.push(result(
+                BulkOutcome::SkippedLocked,
+                project.locked_reason.clone(),
+            ));
+            continue;
+        }
+        if !project.directory.is_empty() && !Path::new(&project.directory).is_dir() {
+            let error = format!("Directory '{}' does not exist", project.directory);
+            results.push(result(BulkOutcome::Failed, Some(error)));
+            continue;
+        }
+
+        let outcome = apply_bulk_op(&mut project, op, item);
+        if outcome == BulkOutcome::Updated {
+            project.updated_at = chrono::Utc::now().to_rfc3339();
+            if let Err(e) = persist(&project) {
+                results.push(result(BulkOutcome::Failed, Some(e)));
+                continue;
+            }
+            updated.push(project);
+        }
+        results.push(result(outcome, None));
+    }
+
+    (results, updated)
+}
+
+/// Apply `op` to one project in memory.
+fn apply_bulk_op(project: &mut Project, op: BulkOp, item: &str) -> BulkOutcome {
+    match op {
+        BulkOp::AddSkill => add_to(&mut project.skills, item),
+        BulkOp::RemoveSkill => remove_from(&mut project.skills, item),
+        BulkOp::AddMcpServer => add_to(&mut project.mcp_servers, item),
+        BulkOp::RemoveMcpServer => {
+            project.disabled_mcp_servers.retain(|s| s != item);
+            remove_from(&mut project.mcp_servers, item)
+        }
+        BulkOp::AttachRule => {
+            if attach_rule(project, item) {
+                BulkOutcome::Updated
+            } else {
+                BulkOutcome::AlreadyPresent
+            }
+        }
+        BulkOp::DetachRule => {
+            if detach_rule(project, item) {
+                BulkOutcome::Updated
+            } else {
+                BulkOutcome::NotReferenced
+            }
+        }
+    }
+}
+
+fn add_to(list: &mut Vec<String>, item: &str) -> BulkOutcome {
+    if list.iter().any(|i| i == item) {
+        return BulkOutcome::AlreadyPresent;
+    }
+    list.push(item.to_string());
+    BulkOutcome::Updated
+}
+
+fn remove_from(list: &mut Vec<String>, item: &str) -> BulkOutcome {
+    let before = list.len();
+    list.retain(|i| i != item);
+    if list.len() == before {
+        BulkOutcome::NotReferenced
+    } else {
+        BulkOutcome::Updated
+    }
+}
+
+/// Attach a global rule to every instruction file.  Project-level rules
+/// (`_project`) are used unless the project still keeps per-file or unified
+/// rules, in which case each of those lists gets the rule so it applies
+/// whichever list sync reads.  Returns whether anything changed.
+fn attach_rule(project: &mut Project, rule: &str) -> bool {
+    let uses_project_rules = project
+        .file_rules
+        .get("_project")
+        .is_some_and(|r| !r.is_empty());
+    if uses_project_rules || project.file_rules.values().all(|r| r.is_empty()) {
+        let rules = project.file_rules.entry("_project".into()).or_default();
+        return add_to(rules, rule) == BulkOutcome::Updated;
+    }
+    let mut changed = false;
+    for rules in project.file_rules.values_mut().filter(|r| !r.is_empty()) {
+        changed |= add_to(rules, rule) == BulkOutcome::Updated;
+    }
+    changed
+}
+
+fn detach_rule(project: &mut Project, rule: &str) -> bool {
+    let mut changed = false;
+    for rules in project.file_rules.values_mut() {
+        changed |= remove_from(rules, rule) == BulkOutcome::Updated;
+    }
+    project.file_rules.retain(|_, rules| !rules.is_empty());
+    changed
+}
+
+/// Sync the changed projects one after another on a background thread.
+fn queue_bulk_syncs(projects: Vec<Project>) {
+    if projects.is_empty() {
+        return;
+    }
+    std::thread::spawn(move || {
+        for mut project in projects {
+            if let crate::sync::ReactiveSync::Failed(e) =
+                crate::sync::reactive_sync_project(&mut project)
+            {
+                eprintln!(
+                    "[automatic] bulk update: failed to sync '{}': {}",
+                    project.name, e
+                );
+            }
+        }
+    });
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn project(name: &str, dir: &Path) -> Project {
+        Project {
+            name: name.into(),
+            directory: dir.display().to_string(),
+            skills: vec!["react".into()],
+            ..Default::default()
+        }
+    }
+
+    fn outcomes(results: &[BulkProjectResult]) -> Vec<(&str, BulkOutcome)> {
+        results
+            .iter()
+            .map(|r| (r.project.as_str(), r.outcome))
+            .collect()
+    }
+
+    #[test]
+    fn missing_directory_does_not_abort_the_rest() {
+        let a = tempdir().unwrap();
+        let c = tempdir().unwrap();
+        let gone = a.path().join("deleted-checkout");
+        let mut with_skill = project("c", c.path());
+        with_skill.skills.push("terraform".into());
+        let projects = vec![
+            ("a".to_string(), Ok(project("a", a.path()))),
+            ("b".to_string(), Ok(project("b", &gone))),
+            ("c".to_string(), Ok(with_skill)),
+            ("d".to_string(), Err("Project 'd' not found".to_string())),
+        ];
+
+        let mut saved = Vec::new();
+        let (results, updated) =
+            bulk_update_with(projects, BulkOp::AddSkill, "terraform", |p: &Project| {
+                saved.push(p.name.clone());
+                Ok(())
+            });
+
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                ("a", BulkOutcome::Updated),
+                ("b", BulkOutcome::Failed),
+                ("c", BulkOutcome::AlreadyPresent),
+                ("d", BulkOutcome::Failed),
+            ]
+        );
+        assert!(results[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("does not exist"));
+        assert!(!gone.exists(), "a missing directory must not be recreated");
+        assert_eq!(saved, vec!["a"]);
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].skills, vec!["react", "terraform"]);
+    }
+
+    #[test]
+    fn persist_failure_and_locked_projects_are_reported() {
+        let dir = tempdir().unwrap();
+        let mut locked = project("locked", dir.path());
+        locked.locked = true;
+        locked.locked_reason = Some("release freeze".into());
+        let projects = vec![
+            ("locked".to_string(), Ok(locked)),
+            ("broken".to_string(), Ok(project("broken", dir.path()))),
+            ("fine".to_string(), Ok(project("fine", dir.path()))),
+        ];
+
+        let (results, updated) =
+            bulk_update_with(projects, BulkOp::RemoveSkill, "react", |p: &Project| {
+                if p.name == "broken" {
+                    Err("disk full".into())
+                } else {
+                    Ok(())
+                }
+            });
+
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                ("locked", BulkOutcome::SkippedLocked),
+                ("broken", BulkOutcome::Failed),
+                ("fine", BulkOutcome::Updated),
+            ]
+        );
+        assert_eq!(results[0].error.as_deref(), Some("release freeze"));
+        assert_eq!(results[1].error.as_deref(), Some("disk full"));
+        assert_eq!(updated.len(), 1);
+        assert!(updated[0].skills.is_empty());
+    }
+
+    #[test]
+    fn removing_unreferenced_items_is_a_no_op() {
+        let mut p = Project {
+            mcp_servers: vec!["github".into()],
+            disabled_mcp_servers: vec!["github".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            apply_bulk_op(&mut p, BulkOp::RemoveMcpServer, "sentry"),
+            BulkOutcome::NotReferenced
+        );
+        assert_eq!(
+            apply_bulk_op(&mut p, BulkOp::RemoveMcpServer, "github"),
+            BulkOutcome::Updated
+        );
+        assert!(p.mcp_servers.is_empty() && p.disabled_mcp_servers.is_empty());
+        assert_eq!(
+            apply_bulk_op(&mut p, BulkOp::DetachRule, "automatic-general"),
+            BulkOutcome::NotReferenced
+        );
+    }
+
+    #[test]
+    fn rules_attach_to_project_level_list_or_existing_per_file_lists() {
+        let mut fresh = Project::default();
+        assert_eq!(
+            apply_bulk_op(&mut fresh, BulkOp::AttachRule, "security"),
+            BulkOutcome::Updated
+        );
+        assert_eq!(fresh.file_rules["_project"], vec!["security"]);
+        assert_eq!(
+            apply_bulk_op(&mut fresh, BulkOp::AttachRule, "security"),
+            BulkOutcome::AlreadyPresent
+        );
+
+        let mut legacy = Project::default();
+        legacy
+            .file_rules
+            .insert("CLAUDE.md".into(), vec!["style".into()]);
+        legacy
+            .file_rules
+            .insert("AGENTS.md".into(), vec!["style".into(), "security".into()]);
+        assert_eq!(
+            apply_bulk_op(&mut legacy, BulkOp::AttachRule, "security"),
+            BulkOutcome::Updated
+        );
+        assert!(!legacy.file_rules.contains_key("_project"));
+        assert_eq!(legacy.file_rules["CLAUDE.md"], vec!["style", "security"]);
+        assert_eq!(legacy.file_rules["AGENTS.md"], vec!["style", "security"]);
+
+        assert_eq!(
+            apply_bulk_op(&mut legacy, BulkOp::DetachRule, "style"),
+            BulkOutcome::Updated
+        );
+        assert_eq!(legacy.file_rules["CLAUDE.md"], vec!["security"]);
+        apply_bulk_op(&mut legacy, BulkOp::DetachRule, "security");
+        assert!(legacy.file_rules.is_empty());
+    }
+}
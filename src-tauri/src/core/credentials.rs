@@ -1,10 +1,15 @@
 use keyring::Entry;
+use std::path::{Path, PathBuf};
+
+use super::get_automatic_dir;
 
 // ── API Keys ─────────────────────────────────────────────────────────────────
 
 pub fn save_api_key(provider: &str, key: &str) -> Result<(), String> {
     let entry = Entry::new(crate::core::KEYCHAIN_SERVICE, provider).map_err(|e| e.to_string())?;
-    entry.set_password(key).map_err(|e| e.to_string())
+    entry.set_password(key).map_err(|e| e.to_string())?;
+    record_provider(provider, true);
+    Ok(())
 }
 
 pub fn get_api_key(provider: &str) -> Result<String, String> {
@@ -24,5 +29,107 @@ pub fn has_api_key(provider: &str) -> bool {
 /// Remove a stored API key from the keyring.
 pub fn delete_api_key(provider: &str) -> Result<(), String> {
     let entry = Entry::new(crate::core::KEYCHAIN_SERVICE, provider).map_err(|e| e.to_string())?;
-    entry.delete_credential().map_err(|e| e.to_string())
+    entry.delete_credential().map_err(|e| e.to_string())?;
+    record_provider(provider, false);
+    Ok(())
+}
+
+// ── Provider Index ───────────────────────────────────────────────────────────
+//
+// The OS keychain cannot be enumerated, so the names of the API keys saved
+// through Automatic are kept in `~/.automatic/api_key_providers.json`.  Only
+// names are stored there, never values.
+
+/// Providers the app itself stores keys for; listed when present even if
+/// they were saved before the index existed.
+const BUILT_IN_PROVIDERS: &[&str] = &["anthropic", "openai"];
+
+fn provider_index_path() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("api_key_providers.json"))
+}
+
+fn read_provider_index(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn update_provider_index(path: &Path, provider: &str, present: bool) -> Result<(), String> {
+    let mut providers = read_provider_index(path);
+    let listed = providers.iter().any(|p| p == provider);
+    if listed == present {
+        return Ok(());
+    }
+    if present {
+        providers.push(provider.to_string());
+        providers.sort();
+    } else {
+        providers.retain(|p| p != provider);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(&providers).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+/// Keep the index in step with the keychain.  The key itself is already
+/// saved or deleted, so a failure here is only logged.
+fn record_provider(provider: &str, present: bool) {
+    if let Err(e) =
+        provider_index_path().and_then(|path| update_provider_index(&path, provider, present))
+    {
+        eprintln!("[automatic] Failed to update API key provider index: {}", e);
+    }
+}
+
+/// Names of the API keys stored in the keychain, sorted.  These are the
+/// names `${keychain:<name>}` placeholders in MCP server configs can use.
+pub fn list_api_key_providers() -> Result<Vec<String>, String> {
+    let indexed = read_provider_index(&provider_index_path()?);
+    Ok(available_providers(&indexed, &has_api_key))
+}
+
+fn available_providers(indexed: &[String], has_key: &dyn Fn(&str) -> bool) -> Vec<String> {
+    let mut providers: Vec<String> = BUILT_IN_PROVIDERS
+        .iter()
+        .map(|p| p.to_string())
+        .chain(indexed.iter().cloned())
+        .filter(|p| has_key(p))
+        .collect();
+    providers.sort();
+    providers.dedup();
+    providers
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_index_tracks_saved_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("api_key_providers.json");
+
+        update_provider_index(&path, "linear", true).unwrap();
+        update_provider_index(&path, "github_token", true).unwrap();
+        update_provider_index(&path, "linear", true).unwrap();
+        assert_eq!(read_provider_index(&path), vec!["github_token", "linear"]);
+
+        update_provider_index(&path, "linear", false).unwrap();
+        assert_eq!(read_provider_index(&path), vec!["github_token"]);
+    }
+
+    #[test]
+    fn only_providers_with_a_stored_key_are_listed() {
+        let indexed = vec!["github_token".to_string(), "stale".to_string()];
+        let has_key = |p: &str| p == "github_token" || p == "anthropic";
+        assert_eq!(
+            available_providers(&indexed, &has_key),
+            vec!["anthropic", "github_token"]
+        );
+    }
 }
@@ -0,0 +1,259 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+// ── Data Directory Migration ─────────────────────────────────────────────────
+//
+// Moves everything under the data root (see `paths::data_root`) to a
+// directory the user picks and records the new location in the bootstrap
+// file, so the app, the MCP server and the CLI all resolve to it from then
+// on.  The move is a rename when both locations share a filesystem; across
+// filesystems the tree is copied first and the old one removed only once the
+// copy is complete, so a failure never leaves the data split between the two.
+
+/// Result of [`migrate_data_dir`].
+#[derive(Debug, Clone, Serialize)]
+pub struct DataDirMigration {
+    pub from: String,
+    pub to: String,
+    /// Top-level entries of the data directory, sorted.
+    pub entries: Vec<String>,
+    /// Files under the data directory, at any depth.
+    pub files: usize,
+    /// `true` when nothing was moved.
+    pub dry_run: bool,
+    /// Problems that did not stop the move, such as an old directory left in
+    /// place after copying.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Move the data directory to `new_path` and record it in the bootstrap
+/// file.  With `dry_run`, only checks the target and lists what would move.
+pub fn migrate_data_dir(new_path: &str, dry_run: bool) -> Result<DataDirMigration, String> {
+    if data_root_from_env() {
+        return Err(format!(
+            "The data directory is set by ${}; unset it before moving the data",
+            DATA_DIR_ENV
+        ));
+    }
+    let from = data_root()?;
+    let to = check_migration_target(&from, new_path)?;
+
+    let mut report = DataDirMigration {
+        from: from.display().to_string(),
+        to: to.display().to_string(),
+        entries: list_entries(&from)?,
+        files: count_files(&from),
+        dry_run,
+        warnings: Vec::new(),
+    };
+    if dry_run {
+        return Ok(report);
+    }
+
+    let copied = if from.exists() {
+        move_tree(&from, &to)?
+    } else {
+        fs::create_dir_all(&to)
+            .map_err(|e| format!("Failed to create '{}': {}", to.display(), e))?;
+        false
+    };
+    set_data_root_override(&to)?;
+    // Removed only after switching, so the removal is logged in the new
+    // location instead of recreating the old one.
+    if copied {
+        let leftover = match remove_guarded(&from, &RemovalContext::external(&from).forced()) {
+            Ok(RemovalOutcome::WouldRemove { .. }) => {
+                Some("destructive operations are set to dry run".to_string())
+            }
+            Ok(_) => None,
+            Err(e) => Some(e),
+        };
+        if let Some(reason) = leftover {
+            let warning = format!(
+                "The data was copied to '{}' but '{}' was left in place: {}",
+                to.display(),
+                from.display(),
+                reason
+            );
+            tracing::warn!("{}", warning);
+            report.warnings.push(warning);
+        }
+    }
+    Ok(report)
+}
+
+/// `new_path` with `~` expanded, provided it is absolute, is not inside the
+/// current data directory (or the other way round) and is either missing
+/// or an empty directory.
+fn check_migration_target(from: &Path, new_path: &str) -> Result<PathBuf, String> {
+    let trimmed = new_path.trim();
+    let to = match (Path::new(trimmed).strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(trimmed),
+    };
+    if trimmed.is_empty() || !to.is_absolute() {
+        return Err(format!("'{}' is not an absolute path", new_path));
+    }
+    if same_path(from, &to) {
+        return Err(format!("'{}' is already the data directory", to.display()));
+    }
+    let (from_key, to_key) = (canonical_key(from), canonical_key(&to));
+    if Path::new(&to_key).starts_with(&from_key) || Path::new(&from_key).starts_with(&to_key) {
+        return Err(format!(
+            "'{}' and the data directory '{}' must not contain each other",
+            to.display(),
+            from.display()
+        ));
+    }
+    if to.exists() {
+        if !to.is_dir() {
+            return Err(format!("'{}' is not a directory", to.display()));
+        }
+        let mut entries =
+            fs::read_dir(&to).map_err(|e| format!("Failed to read '{}': {}", to.display(), e))?;
+        if entries.next().is_some() {
+            return Err(format!("'{}' is not empty", to.display()));
+        }
+    }
+    Ok(to)
+}
+
+fn list_entries(dir: &Path) -> Result<Vec<String>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut entries: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read '{}': {}", dir.display(), e))?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+fn count_files(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| match e.file_type() {
+            Ok(t) if t.is_dir() => count_files(&e.path()),
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Move `from` to `to`, renaming when possible and copying otherwise.
+/// Returns `true` when `from` was copied and still has to be removed.
+fn move_tree(from: &Path, to: &Path) -> Result<bool, String> {
+    if to.exists() {
+        // Checked empty above; a rename cannot replace it on every platform.
+        fs::remove_dir(to).map_err(|e| format!("Failed to prepare '{}': {}", to.display(), e))?;
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(false);
+    }
+
+    // Different filesystems: copy now, remove the original once switched.
+    if let Err(e) = copy_tree(from, to) {
+        let _ = remove_guarded(to, &RemovalContext::external(to).forced());
+        return Err(format!(
+            "Failed to copy '{}' to '{}': {}",
+            from.display(),
+            to.display(),
+            e
+        ));
+    }
+    Ok(true)
+}
+
+fn copy_tree(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_tree(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+            #[cfg(not(unix))]
+            fs::copy(entry.path(), &target).map(|_| ())?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn target_must_be_absolute_empty_and_separate() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("data");
+        fs::create_dir_all(from.join("projects")).unwrap();
+        let target = |p: &Path| p.display().to_string();
+
+        assert!(check_migration_target(&from, "relative/dir").is_err());
+        assert!(check_migration_target(&from, &target(&from)).is_err());
+        assert!(check_migration_target(&from, &target(&from.join("projects"))).is_err());
+        assert!(check_migration_target(&from, &target(dir.path())).is_err());
+
+        let busy = dir.path().join("busy");
+        fs::create_dir_all(busy.join("x")).unwrap();
+        assert!(check_migration_target(&from, &target(&busy))
+            .unwrap_err()
+            .contains("not empty"));
+
+        let empty = dir.path().join("empty");
+        fs::create_dir(&empty).unwrap();
+        assert_eq!(
+            check_migration_target(&from, &target(&empty)).unwrap(),
+            empty
+        );
+        let fresh = dir.path().join("new").join("data");
+        assert_eq!(
+            check_migration_target(&from, &target(&fresh)).unwrap(),
+            fresh
+        );
+    }
+
+    #[test]
+    fn tree_is_moved_whole() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("data");
+        fs::create_dir_all(from.join("projects")).unwrap();
+        fs::write(from.join("settings.json"), "{}").unwrap();
+        fs::write(from.join("projects").join("web.json"), "{}").unwrap();
+        assert_eq!(list_entries(&from).unwrap(), ["projects", "settings.json"]);
+        assert_eq!(count_files(&from), 2);
+
+        let copied = dir.path().join("copied");
+        copy_tree(&from, &copied).unwrap();
+        assert_eq!(count_files(&copied), 2);
+
+        let to = dir.path().join("elsewhere").join("data");
+        assert!(!move_tree(&from, &to).unwrap());
+        assert!(!from.exists());
+        assert_eq!(
+            fs::read_to_string(to.join("projects").join("web.json")).unwrap(),
+            "{}"
+        );
+    }
+}
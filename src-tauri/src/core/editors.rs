@@ -1,8 +1,21 @@
 use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+use super::get_cache_dir;
 
 // ── Editor Detection & Open ───────────────────────────────────────────────────
+//
+// Each editor is found where its installer puts it on the current platform:
+// the app bundle on macOS, the install directory under `%LOCALAPPDATA%\Programs`
+// or `%ProgramFiles%` on Windows, a `.desktop` file on Linux.  An editor whose
+// CLI launcher is on PATH counts as installed everywhere.
 
-/// Known editors with their detection strategy (macOS-first, cross-platform fallback).
+/// Known editors with their installation status on this machine.
 #[derive(Debug, Clone, Serialize)]
 pub struct EditorInfo {
     /// Stable identifier used when calling `open_in_editor`.
@@ -13,109 +26,358 @@ pub struct EditorInfo {
     pub installed: bool,
 }
 
-/// Probe whether a given app bundle path exists OR a CLI command is on PATH.
-fn app_installed(app_path: &str, cli_name: Option<&str>) -> bool {
-    if std::path::Path::new(app_path).exists() {
-        return true;
+/// Where one editor lives on each platform.
+struct EditorSpec {
+    id: &'static str,
+    label: &'static str,
+    /// CLI launcher the editor can put on PATH.
+    cli: Option<&'static str>,
+    /// macOS app bundle name under `/Applications`, without `.app`.
+    mac_app: &'static str,
+    /// Icon file in the bundle's `Contents/Resources`.
+    mac_icns: &'static str,
+    /// Windows install directory (a prefix, as JetBrains directories carry
+    /// the version) and the executable inside it.
+    windows: Option<(&'static str, &'static str)>,
+    /// Linux `.desktop` file names: native packages, then snap and flatpak.
+    linux_desktop: &'static [&'static str],
+}
+
+/// The file manager keeps the `finder` id it had when only macOS was
+/// supported.
+const FILE_MANAGER_ID: &str = "finder";
+
+/// Desktop entries of common Linux file managers, used for the icon only;
+/// opening goes through `xdg-open`.
+const LINUX_FILE_MANAGERS: &[&str] = &[
+    "org.gnome.Nautilus.desktop",
+    "org.kde.dolphin.desktop",
+    "nemo.desktop",
+    "thunar.desktop",
+    "pcmanfm.desktop",
+];
+
+const EDITORS: &[EditorSpec] = &[
+    EditorSpec {
+        id: "vscode",
+        label: "VS Code",
+        cli: Some("code"),
+        mac_app: "Visual Studio Code",
+        mac_icns: "Code.icns",
+        windows: Some(("Microsoft VS Code", "Code.exe")),
+        linux_desktop: &[
+            "code.desktop",
+            "code_code.desktop",
+            "com.visualstudio.code.desktop",
+        ],
+    },
+    EditorSpec {
+        id: "cursor",
+        label: "Cursor",
+        cli: Some("cursor"),
+        mac_app: "Cursor",
+        mac_icns: "Cursor.icns",
+        windows: Some(("cursor", "Cursor.exe")),
+        linux_desktop: &["cursor.desktop", "cursor_cursor.desktop"],
+    },
+    EditorSpec {
+        id: "zed",
+        label: "Zed",
+        cli: Some("zed"),
+        mac_app: "Zed",
+        mac_icns: "Zed.icns",
+        windows: Some(("Zed", "Zed.exe")),
+        linux_desktop: &["dev.zed.Zed.desktop", "zed.desktop"],
+    },
+    EditorSpec {
+        id: "textmate",
+        label: "TextMate",
+        cli: Some("mate"),
+        mac_app: "TextMate",
+        mac_icns: "TextMate.icns",
+        windows: None,
+        linux_desktop: &[],
+    },
+    EditorSpec {
+        id: "antigravity",
+        label: "Antigravity",
+        cli: None,
+        mac_app: "Antigravity",
+        mac_icns: "Antigravity.icns",
+        windows: Some(("Antigravity", "Antigravity.exe")),
+        linux_desktop: &["antigravity.desktop"],
+    },
+    EditorSpec {
+        id: "xcode",
+        label: "Xcode",
+        cli: Some("xed"),
+        mac_app: "Xcode",
+        mac_icns: "Xcode.icns",
+        windows: None,
+        linux_desktop: &[],
+    },
+    // ── JetBrains IDEs ──────────────────────────────────────────────────
+    EditorSpec {
+        id: "intellij",
+        label: "IntelliJ IDEA",
+        cli: Some("idea"),
+        mac_app: "IntelliJ IDEA",
+        mac_icns: "idea.icns",
+        windows: Some(("IntelliJ IDEA", "bin/idea64.exe")),
+        linux_desktop: &[
+            "jetbrains-idea.desktop",
+            "jetbrains-idea-ce.desktop",
+            "intellij-idea-ultimate_intellij-idea-ultimate.desktop",
+            "intellij-idea-community_intellij-idea-community.desktop",
+        ],
+    },
+    EditorSpec {
+        id: "phpstorm",
+        label: "PhpStorm",
+        cli: Some("phpstorm"),
+        mac_app: "PhpStorm",
+        mac_icns: "PhpStorm.icns",
+        windows: Some(("PhpStorm", "bin/phpstorm64.exe")),
+        linux_desktop: &["jetbrains-phpstorm.desktop", "phpstorm_phpstorm.desktop"],
+    },
+    EditorSpec {
+        id: "webstorm",
+        label: "WebStorm",
+        cli: Some("webstorm"),
+        mac_app: "WebStorm",
+        mac_icns: "webstorm.icns",
+        windows: Some(("WebStorm", "bin/webstorm64.exe")),
+        linux_desktop: &["jetbrains-webstorm.desktop", "webstorm_webstorm.desktop"],
+    },
+    EditorSpec {
+        id: "pycharm",
+        label: "PyCharm",
+        cli: Some("pycharm"),
+        mac_app: "PyCharm",
+        mac_icns: "PyCharm.icns",
+        windows: Some(("PyCharm", "bin/pycharm64.exe")),
+        linux_desktop: &[
+            "jetbrains-pycharm.desktop",
+            "jetbrains-pycharm-ce.desktop",
+            "pycharm-professional_pycharm-professional.desktop",
+            "pycharm-community_pycharm-community.desktop",
+        ],
+    },
+    EditorSpec {
+        id: "rustrover",
+        label: "RustRover",
+        cli: Some("rustrover"),
+        mac_app: "RustRover",
+        mac_icns: "RustRover.icns",
+        windows: Some(("RustRover", "bin/rustrover64.exe")),
+        linux_desktop: &["jetbrains-rustrover.desktop", "rustrover_rustrover.desktop"],
+    },
+    EditorSpec {
+        id: "clion",
+        label: "CLion",
+        cli: Some("clion"),
+        mac_app: "CLion",
+        mac_icns: "CLion.icns",
+        windows: Some(("CLion", "bin/clion64.exe")),
+        linux_desktop: &["jetbrains-clion.desktop", "clion_clion.desktop"],
+    },
+    EditorSpec {
+        id: "goland",
+        label: "GoLand",
+        cli: Some("goland"),
+        mac_app: "GoLand",
+        mac_icns: "GoLand.icns",
+        windows: Some(("GoLand", "bin/goland64.exe")),
+        linux_desktop: &["jetbrains-goland.desktop", "goland_goland.desktop"],
+    },
+    EditorSpec {
+        id: "datagrip",
+        label: "DataGrip",
+        cli: Some("datagrip"),
+        mac_app: "DataGrip",
+        mac_icns: "DataGrip.icns",
+        windows: Some(("DataGrip", "bin/datagrip64.exe")),
+        linux_desktop: &["jetbrains-datagrip.desktop", "datagrip_datagrip.desktop"],
+    },
+    EditorSpec {
+        id: "rider",
+        label: "Rider",
+        cli: Some("rider"),
+        mac_app: "Rider",
+        mac_icns: "Rider.icns",
+        windows: Some(("JetBrains Rider", "bin/rider64.exe")),
+        linux_desktop: &["jetbrains-rider.desktop", "rider_rider.desktop"],
+    },
+];
+
+fn editor_spec(editor_id: &str) -> Result<&'static EditorSpec, String> {
+    EDITORS
+        .iter()
+        .find(|spec| spec.id == editor_id)
+        .ok_or_else(|| format!("Unknown editor id: {}", editor_id))
+}
+
+/// Directories Windows installers put applications in.
+fn windows_install_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+        roots.push(PathBuf::from(&local).join("Programs"));
+        roots.push(PathBuf::from(local).join("Programs").join("JetBrains"));
     }
-    if let Some(cli) = cli_name {
-        // Use `which` to check if the CLI is on PATH
-        std::process::Command::new("which")
-            .arg(cli)
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+    for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+        if let Some(dir) = std::env::var_os(var) {
+            roots.push(PathBuf::from(&dir));
+            roots.push(PathBuf::from(dir).join("JetBrains"));
+        }
+    }
+    roots
+}
+
+/// The executable `exe` inside the first directory under `roots` whose name
+/// starts with `dir_prefix` (ignoring case).
+fn find_windows_install(dir_prefix: &str, exe: &str, roots: &[PathBuf]) -> Option<PathBuf> {
+    let prefix = dir_prefix.to_lowercase();
+    roots.iter().find_map(|root| {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(root)
+            .ok()?
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .to_lowercase()
+                    .starts_with(&prefix)
+            })
+            .map(|entry| entry.path())
+            .collect();
+        // Newest version first for versioned directories.
+        dirs.sort();
+        dirs.into_iter()
+            .rev()
+            .map(|dir| dir.join(exe))
+            .find(|path| path.is_file())
+    })
+}
+
+/// Directories holding `.desktop` files, per the XDG base directory spec,
+/// plus the snap and flatpak export locations.
+fn linux_application_dirs() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+
+    let mut dirs = vec![data_home.clone()];
+    dirs.extend(data_dirs.split(':').map(PathBuf::from));
+    dirs.push(data_home.join("flatpak/exports/share"));
+    dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+    let mut apps: Vec<PathBuf> = dirs.iter().map(|d| d.join("applications")).collect();
+    apps.push(PathBuf::from("/var/lib/snapd/desktop/applications"));
+    apps.dedup();
+    apps
+}
+
+/// The first of `names` found in `dirs`.
+fn find_desktop_file(names: &[&str], dirs: &[PathBuf]) -> Option<PathBuf> {
+    names
+        .iter()
+        .flat_map(|name| dirs.iter().map(move |dir| dir.join(name)))
+        .find(|path| path.is_file())
+}
+
+/// A key of the `[Desktop Entry]` group of a `.desktop` file (unlocalised).
+fn desktop_entry_value(content: &str, key: &str) -> Option<String> {
+    let mut in_entry = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry {
+            continue;
+        }
+        if let Some((k, v)) = line.split_once('=') {
+            if k.trim() == key {
+                return Some(v.trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+/// The program and arguments of a desktop entry `Exec` line, with field
+/// codes (`%F`, `%U`, …) dropped so a path can be appended.
+fn desktop_exec_command(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            '\\' if in_quotes => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+    args.into_iter()
+        .filter(|arg| !(arg.len() == 2 && arg.starts_with('%') && arg != "%%"))
+        .map(|arg| arg.replace("%%", "%"))
+        .collect()
+}
+
+/// Where `spec` is installed on this platform, not counting its CLI.
+fn installed_location(spec: &EditorSpec) -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        let bundle = PathBuf::from(format!("/Applications/{}.app", spec.mac_app));
+        bundle.exists().then_some(bundle)
+    } else if cfg!(windows) {
+        let (dir, exe) = spec.windows?;
+        find_windows_install(dir, exe, &windows_install_roots())
     } else {
-        false
+        find_desktop_file(spec.linux_desktop, &linux_application_dirs())
     }
 }
 
 /// Return all supported editors with their installation status.
 pub fn check_installed_editors() -> Vec<EditorInfo> {
-    vec![
-        EditorInfo {
-            id: "finder".into(),
-            label: "Finder".into(),
-            // Finder is always available on macOS
-            installed: cfg!(target_os = "macos"),
-        },
-        EditorInfo {
-            id: "vscode".into(),
-            label: "VS Code".into(),
-            installed: app_installed("/Applications/Visual Studio Code.app", Some("code")),
-        },
-        EditorInfo {
-            id: "cursor".into(),
-            label: "Cursor".into(),
-            installed: app_installed("/Applications/Cursor.app", Some("cursor")),
-        },
-        EditorInfo {
-            id: "zed".into(),
-            label: "Zed".into(),
-            installed: app_installed("/Applications/Zed.app", Some("zed")),
-        },
-        EditorInfo {
-            id: "textmate".into(),
-            label: "TextMate".into(),
-            installed: app_installed("/Applications/TextMate.app", Some("mate")),
-        },
-        EditorInfo {
-            id: "antigravity".into(),
-            label: "Antigravity".into(),
-            installed: app_installed("/Applications/Antigravity.app", None),
-        },
-        EditorInfo {
-            id: "xcode".into(),
-            label: "Xcode".into(),
-            installed: app_installed("/Applications/Xcode.app", Some("xed")),
-        },
-        // ── JetBrains IDEs ──────────────────────────────────────────────
-        EditorInfo {
-            id: "intellij".into(),
-            label: "IntelliJ IDEA".into(),
-            installed: app_installed("/Applications/IntelliJ IDEA.app", Some("idea")),
-        },
-        EditorInfo {
-            id: "phpstorm".into(),
-            label: "PhpStorm".into(),
-            installed: app_installed("/Applications/PhpStorm.app", Some("phpstorm")),
-        },
-        EditorInfo {
-            id: "webstorm".into(),
-            label: "WebStorm".into(),
-            installed: app_installed("/Applications/WebStorm.app", Some("webstorm")),
-        },
-        EditorInfo {
-            id: "pycharm".into(),
-            label: "PyCharm".into(),
-            installed: app_installed("/Applications/PyCharm.app", Some("pycharm")),
-        },
-        EditorInfo {
-            id: "rustrover".into(),
-            label: "RustRover".into(),
-            installed: app_installed("/Applications/RustRover.app", Some("rustrover")),
-        },
-        EditorInfo {
-            id: "clion".into(),
-            label: "CLion".into(),
-            installed: app_installed("/Applications/CLion.app", Some("clion")),
-        },
-        EditorInfo {
-            id: "goland".into(),
-            label: "GoLand".into(),
-            installed: app_installed("/Applications/GoLand.app", Some("goland")),
-        },
-        EditorInfo {
-            id: "datagrip".into(),
-            label: "DataGrip".into(),
-            installed: app_installed("/Applications/DataGrip.app", Some("datagrip")),
-        },
-        EditorInfo {
-            id: "rider".into(),
-            label: "Rider".into(),
-            installed: app_installed("/Applications/Rider.app", Some("rider")),
-        },
-    ]
+    let file_manager = EditorInfo {
+        id: FILE_MANAGER_ID.into(),
+        label: "File Manager".into(),
+        // Finder and Explorer are always there; Linux desktops go through
+        // xdg-open.
+        installed: cfg!(any(target_os = "macos", windows)) || which_available("xdg-open"),
+    };
+    std::iter::once(file_manager)
+        .chain(EDITORS.iter().map(|spec| EditorInfo {
+            id: spec.id.into(),
+            label: spec.label.into(),
+            installed: installed_location(spec).is_some() || spec.cli.is_some_and(which_available),
+        }))
+        .collect()
 }
 
 /// Open a directory in the specified editor.
@@ -127,224 +389,559 @@ pub fn open_in_editor(editor_id: &str, path: &str) -> Result<(), String> {
         return Err("No project directory set".into());
     }
 
-    let status = match editor_id {
-        "finder" => {
-            // `open` on macOS opens Finder at the directory
-            std::process::Command::new("open")
-                .arg(path)
-                .spawn()
-                .map_err(|e| format!("Failed to open Finder: {}", e))?;
-            return Ok(());
-        }
-        "vscode" => {
-            // Prefer the CLI; fall back to `open -a`
-            if which_available("code") {
-                std::process::Command::new("code").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "Visual Studio Code", path])
-                    .spawn()
-            }
-        }
-        "cursor" => {
-            if which_available("cursor") {
-                std::process::Command::new("cursor").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "Cursor", path])
-                    .spawn()
-            }
-        }
-        "zed" => {
-            if which_available("zed") {
-                std::process::Command::new("zed").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "Zed", path])
-                    .spawn()
-            }
-        }
-        "textmate" => {
-            if which_available("mate") {
-                std::process::Command::new("mate").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "TextMate", path])
-                    .spawn()
-            }
-        }
-        "antigravity" => std::process::Command::new("open")
-            .args(["-a", "Antigravity", path])
-            .spawn(),
-        "xcode" => {
-            if which_available("xed") {
-                std::process::Command::new("xed").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "Xcode", path])
-                    .spawn()
-            }
-        }
-        // ── JetBrains IDEs ──────────────────────────────────────────────
-        "intellij" => {
-            if which_available("idea") {
-                std::process::Command::new("idea").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "IntelliJ IDEA", path])
-                    .spawn()
-            }
-        }
-        "phpstorm" => {
-            if which_available("phpstorm") {
-                std::process::Command::new("phpstorm").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "PhpStorm", path])
-                    .spawn()
-            }
-        }
-        "webstorm" => {
-            if which_available("webstorm") {
-                std::process::Command::new("webstorm").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "WebStorm", path])
-                    .spawn()
-            }
-        }
-        "pycharm" => {
-            if which_available("pycharm") {
-                std::process::Command::new("pycharm").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "PyCharm", path])
-                    .spawn()
-            }
-        }
-        "rustrover" => {
-            if which_available("rustrover") {
-                std::process::Command::new("rustrover").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "RustRover", path])
-                    .spawn()
-            }
-        }
-        "clion" => {
-            if which_available("clion") {
-                std::process::Command::new("clion").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "CLion", path])
-                    .spawn()
-            }
-        }
-        "goland" => {
-            if which_available("goland") {
-                std::process::Command::new("goland").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "GoLand", path])
-                    .spawn()
-            }
-        }
-        "datagrip" => {
-            if which_available("datagrip") {
-                std::process::Command::new("datagrip").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "DataGrip", path])
-                    .spawn()
-            }
-        }
-        "rider" => {
-            if which_available("rider") {
-                std::process::Command::new("rider").arg(path).spawn()
-            } else {
-                std::process::Command::new("open")
-                    .args(["-a", "Rider", path])
-                    .spawn()
-            }
+    if editor_id == FILE_MANAGER_ID {
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else if cfg!(windows) {
+            "explorer"
+        } else {
+            "xdg-open"
+        };
+        Command::new(opener)
+            .arg(path)
+            .spawn()
+            .map_err(|e| format!("Failed to open file manager: {}", e))?;
+        return Ok(());
+    }
+
+    let spec = editor_spec(editor_id)?;
+    editor_command(spec, path)?
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", spec.label, e))
+}
+
+/// The command that opens `path` in `spec`: its CLI when on PATH, otherwise
+/// the platform's way of launching the installed app.
+fn editor_command(spec: &EditorSpec, path: &str) -> Result<Command, String> {
+    let cli = spec.cli.filter(|cli| which_available(cli));
+    if cfg!(windows) {
+        // CLI launchers are `.cmd` scripts, which only `cmd` can run.
+        let mut cmd = Command::new("cmd");
+        if let Some(cli) = cli {
+            cmd.args(["/C", cli, path]);
+        } else {
+            let exe = installed_location(spec)
+                .ok_or_else(|| format!("{} is not installed", spec.label))?;
+            cmd.args(["/C", "start", ""]).arg(exe).arg(path);
         }
-        other => return Err(format!("Unknown editor id: {}", other)),
+        return Ok(cmd);
+    }
+    if let Some(cli) = cli {
+        let mut cmd = Command::new(cli);
+        cmd.arg(path);
+        return Ok(cmd);
+    }
+    if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("open");
+        cmd.args(["-a", spec.mac_app, path]);
+        return Ok(cmd);
+    }
+    let desktop =
+        installed_location(spec).ok_or_else(|| format!("{} is not installed", spec.label))?;
+    let content = fs::read_to_string(&desktop).map_err(|e| e.to_string())?;
+    let exec = desktop_entry_value(&content, "Exec").map(|e| desktop_exec_command(&e));
+    let Some((program, args)) = exec.as_deref().and_then(|e| e.split_first()) else {
+        return Err(format!("{} has no Exec line", desktop.display()));
     };
+    let mut cmd = Command::new(program);
+    cmd.args(args).arg(path);
+    Ok(cmd)
+}
+
+// ── Editor Icons ─────────────────────────────────────────────────────────────
+//
+// Icons come from each editor's app bundle `.icns` on macOS and from the
+// PNG its desktop entry names on Linux.  Converted PNGs are cached under the
+// app cache directory (owner-only permissions), keyed by the source file's
+// mtime and size so an editor update that changes its icon is picked up, and
+// memoised in memory for the rest of the session.  macOS converts with
+// `sips`; elsewhere, or when `sips` fails, the PNG embedded in modern `.icns`
+// files is extracted directly.  A missing or unconvertible icon is not an
+// error: the frontend falls back to its SVG approximations, as it does on
+// Windows.
+
+/// Converts the icon at the first path into a PNG at the second.
+type IconConverter<'a> = dyn Fn(&Path, &Path) -> Result<(), String> + 'a;
+
+/// What a cached icon was converted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IconSource {
+    modified_nanos: u128,
+    len: u64,
+}
 
-    status.map(|_| ()).map_err(|e| e.to_string())
+impl IconSource {
+    fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        let modified_nanos = meta
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        Some(Self {
+            modified_nanos,
+            len: meta.len(),
+        })
+    }
+
+    /// Cache file name for `editor_id`'s icon converted from this source.
+    fn cache_file_name(&self, editor_id: &str) -> String {
+        format!("{}-{:x}-{:x}.png", editor_id, self.modified_nanos, self.len)
+    }
+}
+
+/// Editor id → the source it was converted from and the resulting data URI
+/// (`None` when the icon could not be converted).
+type IconMemo = Mutex<HashMap<String, (IconSource, Option<String>)>>;
+
+fn icon_memo() -> &'static IconMemo {
+    static MEMO: OnceLock<IconMemo> = OnceLock::new();
+    MEMO.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The icon file of `editor_id` on this platform: the `.icns` in the macOS
+/// app bundle, or the PNG named by the Linux desktop entry.  Windows has no
+/// icon file to read; the frontend shows its own there.
+fn editor_icon_path(editor_id: &str) -> Result<Option<PathBuf>, String> {
+    if editor_id == FILE_MANAGER_ID {
+        return Ok(if cfg!(target_os = "macos") {
+            Some(PathBuf::from(
+                "/System/Library/CoreServices/Finder.app/Contents/Resources/Finder.icns",
+            ))
+        } else if cfg!(windows) {
+            None
+        } else {
+            linux_desktop_icon(LINUX_FILE_MANAGERS)
+        });
+    }
+    let spec = editor_spec(editor_id)?;
+    Ok(if cfg!(target_os = "macos") {
+        Some(PathBuf::from(format!(
+            "/Applications/{}.app/Contents/Resources/{}",
+            spec.mac_app, spec.mac_icns
+        )))
+    } else if cfg!(windows) {
+        None
+    } else {
+        linux_desktop_icon(spec.linux_desktop)
+    })
 }
 
-/// Convert the `.icns` file for `editor_id` to a PNG and return it as a
-/// base64-encoded string suitable for use in a `data:image/png;base64,...` URL.
+fn linux_desktop_icon(desktop_names: &[&str]) -> Option<PathBuf> {
+    let apps = linux_application_dirs();
+    let desktop = find_desktop_file(desktop_names, &apps)?;
+    let icon = desktop_entry_value(&fs::read_to_string(desktop).ok()?, "Icon")?;
+    let data_dirs: Vec<PathBuf> = apps
+        .iter()
+        .filter_map(|dir| dir.parent().map(Path::to_path_buf))
+        .collect();
+    resolve_linux_icon(&icon, &data_dirs)
+}
+
+/// Theme sizes tried for a named icon, best fit for the UI first.
+const LINUX_ICON_SIZES: &[&str] = &["128x128", "256x256", "96x96", "64x64", "512x512", "48x48"];
+
+/// The PNG for a desktop entry's `Icon` value: an absolute path, or a name
+/// looked up in the hicolor theme and `pixmaps` under `data_dirs`.
+fn resolve_linux_icon(icon: &str, data_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let is_png = |p: &Path| p.extension().is_some_and(|e| e == "png") && p.is_file();
+    let direct = Path::new(icon);
+    if direct.is_absolute() {
+        return is_png(direct).then(|| direct.to_path_buf());
+    }
+    let file = format!("{}.png", icon);
+    LINUX_ICON_SIZES
+        .iter()
+        .flat_map(|size| {
+            data_dirs
+                .iter()
+                .map(move |dir| dir.join("icons/hicolor").join(size).join("apps"))
+        })
+        .chain(data_dirs.iter().map(|dir| dir.join("pixmaps")))
+        .map(|dir| dir.join(&file))
+        .find(|path| is_png(path))
+}
+
+/// Return the icon for `editor_id` as a `data:image/png;base64,...` URI the
+/// frontend can embed directly without the Tauri asset protocol.
 ///
-/// The PNG is cached in `/tmp/automatic-icons/` to avoid re-running `sips` on
-/// every call.  Uses macOS `sips` (always available on macOS) to do the
-/// conversion.  Returns an error string if the editor id is unknown, the
-/// `.icns` file does not exist, or `sips` / IO fails.
-pub fn get_editor_icon(editor_id: &str) -> Result<String, String> {
-    let icns_path: &str = match editor_id {
-        "finder" => "/System/Library/CoreServices/Finder.app/Contents/Resources/Finder.icns",
-        "vscode" => "/Applications/Visual Studio Code.app/Contents/Resources/Code.icns",
-        "cursor" => "/Applications/Cursor.app/Contents/Resources/Cursor.icns",
-        "zed" => "/Applications/Zed.app/Contents/Resources/Zed.icns",
-        "textmate" => "/Applications/TextMate.app/Contents/Resources/TextMate.icns",
-        "antigravity" => "/Applications/Antigravity.app/Contents/Resources/Antigravity.icns",
-        "xcode" => "/Applications/Xcode.app/Contents/Resources/Xcode.icns",
-        // JetBrains IDEs
-        "intellij" => "/Applications/IntelliJ IDEA.app/Contents/Resources/idea.icns",
-        "phpstorm" => "/Applications/PhpStorm.app/Contents/Resources/PhpStorm.icns",
-        "webstorm" => "/Applications/WebStorm.app/Contents/Resources/webstorm.icns",
-        "pycharm" => "/Applications/PyCharm.app/Contents/Resources/PyCharm.icns",
-        "rustrover" => "/Applications/RustRover.app/Contents/Resources/RustRover.icns",
-        "clion" => "/Applications/CLion.app/Contents/Resources/CLion.icns",
-        "goland" => "/Applications/GoLand.app/Contents/Resources/GoLand.icns",
-        "datagrip" => "/Applications/DataGrip.app/Contents/Resources/DataGrip.icns",
-        "rider" => "/Applications/Rider.app/Contents/Resources/Rider.icns",
-        other => return Err(format!("Unknown editor id: {}", other)),
+/// `Ok(None)` means there is no icon to show: the editor is not installed,
+/// the platform has no icon file for it, or the icon could not be converted.
+/// Only an unknown editor id is an error.
+pub fn get_editor_icon(editor_id: &str) -> Result<Option<String>, String> {
+    let Some(icon) = editor_icon_path(editor_id)? else {
+        return Ok(None);
     };
+    let cache_dir = get_cache_dir()?.join("editor-icons");
+    editor_icon_with(editor_id, &icon, &cache_dir, icon_memo(), &convert_icon)
+}
 
-    if !std::path::Path::new(icns_path).exists() {
-        return Err(format!("Icon file not found: {}", icns_path));
+fn editor_icon_with(
+    editor_id: &str,
+    icns: &Path,
+    cache_dir: &Path,
+    memo: &IconMemo,
+    convert: &IconConverter<'_>,
+) -> Result<Option<String>, String> {
+    let Some(source) = IconSource::of(icns) else {
+        return Ok(None);
+    };
+    if let Some((cached_source, uri)) = memo.lock().unwrap().get(editor_id) {
+        if *cached_source == source {
+            return Ok(uri.clone());
+        }
     }
 
-    let cache_dir = std::path::Path::new("/tmp/automatic-icons");
-    std::fs::create_dir_all(cache_dir)
-        .map_err(|e| format!("Failed to create icon cache dir: {}", e))?;
+    let uri = match cached_icon_png(editor_id, icns, &source, cache_dir, convert) {
+        Ok(bytes) => {
+            use base64::{engine::general_purpose::STANDARD, Engine as _};
+            Some(format!("data:image/png;base64,{}", STANDARD.encode(bytes)))
+        }
+        Err(e) => {
+            eprintln!("[automatic] no icon for editor '{}': {}", editor_id, e);
+            None
+        }
+    };
+    memo.lock()
+        .unwrap()
+        .insert(editor_id.to_string(), (source, uri.clone()));
+    Ok(uri)
+}
+
+/// PNG bytes for the icon, converting into the cache on a miss and removing
+/// conversions of older versions of the same icon.
+fn cached_icon_png(
+    editor_id: &str,
+    icns: &Path,
+    source: &IconSource,
+    cache_dir: &Path,
+    convert: &IconConverter<'_>,
+) -> Result<Vec<u8>, String> {
+    ensure_private_dir(cache_dir)?;
+    let file_name = source.cache_file_name(editor_id);
+    let out_path = cache_dir.join(&file_name);
+    if !out_path.is_file() {
+        convert(icns, &out_path)?;
+        let prefix = format!("{}-", editor_id);
+        for entry in fs::read_dir(cache_dir).into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name != file_name && name.starts_with(&prefix) && name.ends_with(".png") {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    fs::read(&out_path).map_err(|e| format!("Failed to read cached icon: {}", e))
+}
 
-    let out_path = cache_dir.join(format!("{}.png", editor_id));
-    let out_str = out_path
-        .to_str()
-        .ok_or_else(|| "Invalid output path".to_string())?
-        .to_string();
+/// Create `dir` if needed and make sure it is a real directory only the
+/// current user can write to.  Changing its mode fails when another user
+/// owns it, so a pre-created directory is rejected rather than trusted.
+fn ensure_private_dir(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create icon cache dir: {}", e))?;
+    let meta =
+        fs::symlink_metadata(dir).map_err(|e| format!("Failed to read icon cache dir: {}", e))?;
+    if !meta.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            .map_err(|e| format!("Failed to secure {}: {}", dir.display(), e))?;
+    }
+    Ok(())
+}
 
-    // Convert .icns → PNG if not already cached
-    if !out_path.exists() {
-        let output = std::process::Command::new("sips")
-            .args(["-s", "format", "png", icns_path, "--out", &out_str])
+/// Copy PNG icons as they are; convert `.icns` files with `sips` on macOS,
+/// falling back to the PNG embedded in the file.
+fn convert_icon(icns: &Path, out: &Path) -> Result<(), String> {
+    if icns.extension().is_some_and(|e| e == "png") {
+        return fs::copy(icns, out)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy {}: {}", icns.display(), e));
+    }
+    if cfg!(target_os = "macos") {
+        let converted = Command::new("sips")
+            .arg("-s")
+            .arg("format")
+            .arg("png")
+            .arg(icns)
+            .arg("--out")
+            .arg(out)
             .output()
-            .map_err(|e| format!("Failed to run sips: {}", e))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("sips failed: {}", stderr));
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if converted {
+            return Ok(());
         }
     }
+    let data = fs::read(icns).map_err(|e| format!("Failed to read {}: {}", icns.display(), e))?;
+    let png = embedded_icns_png(&data)
+        .ok_or_else(|| format!("{} has no embedded PNG", icns.display()))?;
+    fs::write(out, png).map_err(|e| format!("Failed to write {}: {}", out.display(), e))
+}
 
-    // Read the PNG and return it as a base64 data URI so the frontend can
-    // embed it directly without needing the Tauri asset protocol.
-    let bytes =
-        std::fs::read(&out_path).map_err(|e| format!("Failed to read cached icon: {}", e))?;
+/// Element types of `.icns` files that may hold PNG data, in order of
+/// preference: big enough for a crisp toolbar icon, small enough to embed.
+const ICNS_PNG_TYPES: [&[u8; 4]; 8] = [
+    b"ic07", b"ic13", b"ic08", b"ic12", b"ic14", b"ic09", b"ic10", b"ic11",
+];
 
-    use base64::{engine::general_purpose::STANDARD, Engine as _};
-    let b64 = STANDARD.encode(&bytes);
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
 
-    Ok(format!("data:image/png;base64,{}", b64))
+/// The preferred PNG-encoded element of an `.icns` file, if it has one.
+/// Older icons store only raw bitmaps and yield `None`.
+fn embedded_icns_png(data: &[u8]) -> Option<&[u8]> {
+    if data.len() < 8 || &data[..4] != b"icns" {
+        return None;
+    }
+    let mut pngs: Vec<(&[u8], &[u8])> = Vec::new();
+    let mut offset = 8;
+    while offset + 8 <= data.len() {
+        let kind = &data[offset..offset + 4];
+        let len = u32::from_be_bytes(data[offset + 4..offset + 8].try_into().ok()?) as usize;
+        if len < 8 || offset + len > data.len() {
+            break;
+        }
+        let body = &data[offset + 8..offset + len];
+        if body.starts_with(PNG_SIGNATURE) {
+            pngs.push((kind, body));
+        }
+        offset += len;
+    }
+    ICNS_PNG_TYPES.iter().find_map(|wanted| {
+        pngs.iter()
+            .find(|(kind, _)| kind == &wanted.as_slice())
+            .map(|(_, body)| *body)
+    })
 }
 
-/// Return true when `name` resolves to an executable via `which`.
-fn which_available(name: &str) -> bool {
-    std::process::Command::new("which")
+/// Return true when `name` resolves to an executable via `which` (`where`
+/// on Windows).
+pub(crate) fn which_available(name: &str) -> bool {
+    Command::new(if cfg!(windows) { "where" } else { "which" })
         .arg(name)
         .output()
         .map(|o| o.status.success())
         .unwrap_or(false)
 }
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::time::{Duration, SystemTime};
+    use tempfile::tempdir;
+
+    /// A minimal `.icns` with the given `(type, body)` elements.
+    fn icns(elements: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (kind, data) in elements {
+            body.extend_from_slice(*kind);
+            body.extend_from_slice(&((data.len() + 8) as u32).to_be_bytes());
+            body.extend_from_slice(data);
+        }
+        let mut out = b"icns".to_vec();
+        out.extend_from_slice(&((body.len() + 8) as u32).to_be_bytes());
+        out.extend(body);
+        out
+    }
+
+    fn png(tag: &str) -> Vec<u8> {
+        [PNG_SIGNATURE, tag.as_bytes()].concat()
+    }
+
+    fn set_mtime(path: &Path, secs: u64) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn cache_key_tracks_the_source_file() {
+        let a = IconSource {
+            modified_nanos: 1,
+            len: 10,
+        };
+        let touched = IconSource {
+            modified_nanos: 2,
+            ..a
+        };
+        assert_eq!(a.cache_file_name("zed"), "zed-1-a.png");
+        assert_ne!(a.cache_file_name("zed"), touched.cache_file_name("zed"));
+        assert_ne!(a.cache_file_name("zed"), a.cache_file_name("cursor"));
+    }
+
+    #[test]
+    fn icons_are_memoised_and_reconverted_when_the_source_changes() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("Zed.icns");
+        let cache = dir.path().join("cache");
+        fs::write(&source, b"v1").unwrap();
+        set_mtime(&source, 1_000);
+
+        let calls = Cell::new(0);
+        let convert = |icns: &Path, out: &Path| -> Result<(), String> {
+            calls.set(calls.get() + 1);
+            fs::write(out, fs::read(icns).unwrap()).map_err(|e| e.to_string())
+        };
+        let memo = IconMemo::default();
+
+        let first = editor_icon_with("zed", &source, &cache, &memo, &convert).unwrap();
+        assert!(first.unwrap().starts_with("data:image/png;base64,"));
+        editor_icon_with("zed", &source, &cache, &memo, &convert).unwrap();
+        assert_eq!(calls.get(), 1);
+
+        // A fresh session reuses the on-disk cache.
+        let memo = IconMemo::default();
+        editor_icon_with("zed", &source, &cache, &memo, &convert).unwrap();
+        assert_eq!(calls.get(), 1);
+
+        // An app update replaces the icon: converted again, old PNG dropped.
+        fs::write(&source, b"v2").unwrap();
+        set_mtime(&source, 2_000);
+        editor_icon_with("zed", &source, &cache, &memo, &convert).unwrap();
+        assert_eq!(calls.get(), 2);
+        let cached: Vec<_> = fs::read_dir(&cache).unwrap().collect();
+        assert_eq!(cached.len(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&cache).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+    }
+
+    #[test]
+    fn missing_or_unconvertible_icons_are_not_errors() {
+        let dir = tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        let memo = IconMemo::default();
+        let failing = |_: &Path, _: &Path| -> Result<(), String> { Err("no converter".into()) };
+
+        let missing = dir.path().join("Missing.icns");
+        assert_eq!(
+            editor_icon_with("zed", &missing, &cache, &memo, &failing),
+            Ok(None)
+        );
+
+        let source = dir.path().join("Zed.icns");
+        fs::write(&source, b"raw").unwrap();
+        assert_eq!(
+            editor_icon_with("zed", &source, &cache, &memo, &failing),
+            Ok(None)
+        );
+        assert!(get_editor_icon("notepad").is_err());
+    }
+
+    #[test]
+    fn embedded_png_prefers_mid_sized_elements() {
+        let large = png("1024");
+        let mid = png("128");
+        let data = icns(&[(b"ic10", &large), (b"is32", b"raw bitmap"), (b"ic07", &mid)]);
+        assert_eq!(embedded_icns_png(&data), Some(mid.as_slice()));
+
+        let only_large = icns(&[(b"ic10", &large)]);
+        assert_eq!(embedded_icns_png(&only_large), Some(large.as_slice()));
+
+        assert_eq!(embedded_icns_png(&icns(&[(b"is32", b"raw")])), None);
+        assert_eq!(embedded_icns_png(b"not an icns file"), None);
+        // A truncated element stops the scan instead of panicking.
+        let mut truncated = icns(&[(b"ic07", &mid)]);
+        truncated.truncate(20);
+        assert_eq!(embedded_icns_png(&truncated), None);
+    }
+
+    #[test]
+    fn desktop_entries_yield_a_launch_command() {
+        let content = "[Desktop Entry]\nName=Visual Studio Code\nExec=/usr/share/code/code --unity-launch %F\nIcon=vscode\n\n[Desktop Action new-empty-window]\nExec=/usr/share/code/code --new-window %F\n";
+        let exec = desktop_entry_value(content, "Exec").unwrap();
+        assert_eq!(
+            desktop_exec_command(&exec),
+            vec!["/usr/share/code/code", "--unity-launch"]
+        );
+        assert_eq!(
+            desktop_entry_value(content, "Icon").as_deref(),
+            Some("vscode")
+        );
+        assert_eq!(desktop_entry_value(content, "Terminal"), None);
+
+        assert_eq!(
+            desktop_exec_command(r#""/opt/My Editor/bin/editor.sh" --flag "a \"b\"" 100%% %U"#),
+            vec!["/opt/My Editor/bin/editor.sh", "--flag", "a \"b\"", "100%"]
+        );
+    }
+
+    #[test]
+    fn installs_are_found_per_platform_layout() {
+        let dir = tempdir().unwrap();
+        let user_apps = dir.path().join("home/applications");
+        let system_apps = dir.path().join("usr/applications");
+        fs::create_dir_all(&user_apps).unwrap();
+        fs::create_dir_all(&system_apps).unwrap();
+        fs::write(system_apps.join("code_code.desktop"), "").unwrap();
+        let apps = [user_apps.clone(), system_apps.clone()];
+        assert_eq!(
+            find_desktop_file(&["code.desktop", "code_code.desktop"], &apps),
+            Some(system_apps.join("code_code.desktop"))
+        );
+        fs::write(user_apps.join("code.desktop"), "").unwrap();
+        assert_eq!(
+            find_desktop_file(&["code.desktop", "code_code.desktop"], &apps),
+            Some(user_apps.join("code.desktop"))
+        );
+
+        let programs = dir.path().join("Programs");
+        for version in ["PyCharm 2023.3", "PyCharm 2024.2"] {
+            let bin = programs.join(version).join("bin");
+            fs::create_dir_all(&bin).unwrap();
+            fs::write(bin.join("pycharm64.exe"), "").unwrap();
+        }
+        fs::create_dir_all(programs.join("cursor")).unwrap();
+        let roots = [dir.path().join("missing"), programs.clone()];
+        assert_eq!(
+            find_windows_install("PyCharm", "bin/pycharm64.exe", &roots),
+            Some(programs.join("PyCharm 2024.2/bin/pycharm64.exe"))
+        );
+        // The directory alone is not an install.
+        assert_eq!(find_windows_install("Cursor", "Cursor.exe", &roots), None);
+    }
+
+    #[test]
+    fn linux_icons_resolve_from_the_icon_theme() {
+        let dir = tempdir().unwrap();
+        let share = dir.path().join("share");
+        let themed = share.join("icons/hicolor/256x256/apps");
+        fs::create_dir_all(&themed).unwrap();
+        fs::write(themed.join("zed.png"), png("zed")).unwrap();
+        fs::create_dir_all(share.join("pixmaps")).unwrap();
+        fs::write(share.join("pixmaps/code.png"), png("code")).unwrap();
+        fs::write(share.join("pixmaps/vector.svg"), "<svg/>").unwrap();
+        let data_dirs = [share.clone()];
+
+        assert_eq!(
+            resolve_linux_icon("zed", &data_dirs),
+            Some(themed.join("zed.png"))
+        );
+        assert_eq!(
+            resolve_linux_icon("code", &data_dirs),
+            Some(share.join("pixmaps/code.png"))
+        );
+        let absolute = share.join("pixmaps/code.png");
+        assert_eq!(
+            resolve_linux_icon(absolute.to_str().unwrap(), &data_dirs),
+            Some(absolute)
+        );
+        assert_eq!(resolve_linux_icon("vector", &data_dirs), None);
+
+        // PNG sources are cached as they are.
+        let out = dir.path().join("out.png");
+        convert_icon(&themed.join("zed.png"), &out).unwrap();
+        assert_eq!(fs::read(out).unwrap(), png("zed"));
+    }
+}
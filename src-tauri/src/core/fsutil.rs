@@ -0,0 +1,595 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{get_automatic_dir, read_settings};
+use crate::activity::{self, ActivityEvent};
+
+// ── Guarded Removal ──────────────────────────────────────────────────────────
+//
+// Every recursive delete Automatic performs goes through `remove_guarded`.
+// Before touching the disk it re-checks that the path (after resolving
+// symlinked parents) lies inside a root the caller is allowed to clean, and
+// refuses to delete anything holding a `.git` directory or more files than
+// the configured limit unless the caller explicitly forces it.  The global
+// `destructive_ops` setting can turn deletions into a logged dry run or
+// disable them entirely.  Every deletion, real or simulated, is recorded in
+// the activity log.
+
+/// Default for `Settings::destructive_max_files`.
+pub const DEFAULT_DESTRUCTIVE_MAX_FILES: usize = 1000;
+
+/// Activity-log key for removals; deliberately not a valid project name.
+const REMOVAL_ACTIVITY_KEY: &str = "(automatic)";
+
+/// Where a removal is allowed to happen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemovalScope {
+    /// Inside a project directory, or a managed subdirectory of one.
+    Project(PathBuf),
+    /// Inside one of the given skill source directories.
+    Skills(Vec<PathBuf>),
+    /// Inside Automatic's own data directory.
+    AppData,
+    /// Inside the system temp directory.
+    Temp,
+    /// Inside another tool's data directory that Automatic cleans up.
+    External(PathBuf),
+}
+
+/// Why a path is being removed and how far the guard may be relaxed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemovalContext {
+    pub scope: RemovalScope,
+    /// Skips the `.git` and file-count checks and allows removing the root
+    /// itself.  The allowed-root check and the `destructive_ops` setting
+    /// still apply.
+    pub force: bool,
+}
+
+impl RemovalContext {
+    pub fn project(dir: &Path) -> Self {
+        Self::new(RemovalScope::Project(dir.to_path_buf()))
+    }
+
+    pub fn skills(roots: Vec<PathBuf>) -> Self {
+        Self::new(RemovalScope::Skills(roots))
+    }
+
+    pub fn app_data() -> Self {
+        Self::new(RemovalScope::AppData)
+    }
+
+    pub fn temp() -> Self {
+        Self::new(RemovalScope::Temp)
+    }
+
+    pub fn external(root: &Path) -> Self {
+        Self::new(RemovalScope::External(root.to_path_buf()))
+    }
+
+    pub fn forced(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    fn new(scope: RemovalScope) -> Self {
+        Self {
+            scope,
+            force: false,
+        }
+    }
+
+    fn roots(&self) -> Vec<PathBuf> {
+        match &self.scope {
+            RemovalScope::Project(dir) | RemovalScope::External(dir) => vec![dir.clone()],
+            RemovalScope::Skills(roots) => roots.clone(),
+            RemovalScope::AppData => get_automatic_dir().into_iter().collect(),
+            RemovalScope::Temp => vec![std::env::temp_dir()],
+        }
+    }
+}
+
+/// The `destructive_ops` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestructiveMode {
+    Allow,
+    DryRun,
+    Deny,
+}
+
+impl DestructiveMode {
+    /// Parse the settings value; anything unrecognised means `Allow`.
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "dry_run" => Self::DryRun,
+            "deny" => Self::Deny,
+            _ => Self::Allow,
+        }
+    }
+}
+
+/// What [`remove_guarded`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalOutcome {
+    /// The path was deleted; `files` counts the files and links inside it.
+    Removed { files: usize },
+    /// Dry run: the path would have been deleted and was left in place.
+    WouldRemove { files: usize },
+    /// Nothing existed at the path.
+    Missing,
+}
+
+impl RemovalOutcome {
+    /// True when the path was (or, in a dry run, would have been) removed.
+    pub fn is_removal(&self) -> bool {
+        !matches!(self, Self::Missing)
+    }
+}
+
+struct RemovalPolicy {
+    mode: DestructiveMode,
+    max_files: usize,
+}
+
+/// Delete the file, symlink or directory at `path` if `context` allows it.
+/// A symlink is removed as a link; its target is never touched.
+pub fn remove_guarded(path: &Path, context: &RemovalContext) -> Result<RemovalOutcome, String> {
+    let settings = read_settings().unwrap_or_default();
+    let policy = RemovalPolicy {
+        mode: DestructiveMode::parse(&settings.destructive_ops),
+        max_files: settings.destructive_max_files,
+    };
+    remove_guarded_with(path, context, &policy, &mut |path, outcome| {
+        record_removal(path, outcome)
+    })
+}
+
+fn remove_guarded_with(
+    path: &Path,
+    context: &RemovalContext,
+    policy: &RemovalPolicy,
+    record: &mut dyn FnMut(&Path, RemovalOutcome),
+) -> Result<RemovalOutcome, String> {
+    let Ok(meta) = path.symlink_metadata() else {
+        return Ok(RemovalOutcome::Missing);
+    };
+    if policy.mode == DestructiveMode::Deny {
+        return Err(format!(
+            "Not removing '{}': destructive operations are disabled in settings",
+            path.display()
+        ));
+    }
+
+    let resolved = resolve_for_removal(path)?;
+    if !inside_allowed_root(&resolved, &context.roots(), context.force) {
+        return Err(format!(
+            "Refusing to remove '{}': it is outside the directories this operation may clean",
+            path.display()
+        ));
+    }
+
+    let is_dir = meta.is_dir() && !meta.file_type().is_symlink();
+    let files = if is_dir {
+        // A forced removal skips the guards, so count everything.
+        let limit = if context.force {
+            usize::MAX
+        } else {
+            policy.max_files
+        };
+        let survey = survey_dir(path, limit)
+            .map_err(|e| format!("Failed to inspect '{}': {}", path.display(), e))?;
+        if !context.force {
+            if survey.has_git {
+                return Err(format!(
+                    "Refusing to remove '{}': it contains a .git directory",
+                    path.display()
+                ));
+            }
+            if survey.files > policy.max_files {
+                return Err(format!(
+                    "Refusing to remove '{}': it holds more than {} files",
+                    path.display(),
+                    policy.max_files
+                ));
+            }
+        }
+        survey.files
+    } else {
+        1
+    };
+
+    let outcome = if policy.mode == DestructiveMode::DryRun {
+        tracing::info!("Dry run: would remove {} ({} files)", path.display(), files);
+        RemovalOutcome::WouldRemove { files }
+    } else {
+        let removed = if is_dir {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        };
+        removed.map_err(|e| format!("Failed to remove '{}': {}", path.display(), e))?;
+        RemovalOutcome::Removed { files }
+    };
+    record(path, outcome);
+    Ok(outcome)
+}
+
+fn record_removal(path: &Path, outcome: RemovalOutcome) {
+    let label = match outcome {
+        RemovalOutcome::Removed { files } => format!("Removed {} files", files),
+        RemovalOutcome::WouldRemove { files } => {
+            format!("Dry run: would remove {} files", files)
+        }
+        RemovalOutcome::Missing => return,
+    };
+    activity::log(
+        REMOVAL_ACTIVITY_KEY,
+        ActivityEvent::PathRemoved,
+        &label,
+        &path.display().to_string(),
+    );
+}
+
+/// Canonicalise the parent of `path` (so a symlinked parent cannot redirect
+/// the delete elsewhere) while keeping the final component as written, so a
+/// symlink at `path` is judged by where the link lives.
+fn resolve_for_removal(path: &Path) -> Result<PathBuf, String> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Refusing to remove '{}'", path.display()))?;
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let parent = fs::canonicalize(parent)
+        .map_err(|e| format!("Failed to resolve '{}': {}", path.display(), e))?;
+    Ok(parent.join(name))
+}
+
+/// Whether `resolved` lies strictly inside one of `roots` (or is a root
+/// itself, when `force` is set).
+fn inside_allowed_root(resolved: &Path, roots: &[PathBuf], force: bool) -> bool {
+    roots
+        .iter()
+        .filter_map(|root| fs::canonicalize(root).ok())
+        .any(|root| resolved.starts_with(&root) && (force || resolved != root))
+}
+
+struct DirSurvey {
+    files: usize,
+    has_git: bool,
+}
+
+/// Count the files under `dir` and look for `.git`, without following
+/// symlinks.  Stops early once `max_files` is exceeded; with no limit the
+/// whole tree is counted.
+fn survey_dir(dir: &Path, max_files: usize) -> io::Result<DirSurvey> {
+    let mut survey = DirSurvey {
+        files: 0,
+        has_git: false,
+    };
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current)? {
+            let entry = entry?;
+            if entry.file_name() == ".git" {
+                survey.has_git = true;
+                if max_files != usize::MAX {
+                    return Ok(survey);
+                }
+            }
+            if entry.file_type()?.is_dir() {
+                pending.push(entry.path());
+            } else {
+                survey.files += 1;
+                if survey.files > max_files {
+                    return Ok(survey);
+                }
+            }
+        }
+    }
+    Ok(survey)
+}
+
+// ── Atomic Writes and File Locks ─────────────────────────────────────────────
+//
+// Shared JSON files (the project registry, `skills.json`, settings, the
+// profile, the memory store) are written by the app, by every agent
+// session's MCP server, and by the session hooks, often at the same moment.
+// Writes go through `write_atomic`, so a reader sees either the old or the
+// new file, never a truncated one.  Read-modify-write sequences run inside
+// `with_file_lock`, which holds an in-process mutex per file (threads of
+// this process) and an advisory OS lock on a `<file>.lock` sidecar (other
+// processes), so concurrent writers merge instead of overwriting each
+// other.  The lock is not re-entrant: code running under it must not take
+// it again for the same file.
+
+/// Replace `path` with `contents` through a temp file in the same directory
+/// and a rename.  Creates the parent directory when missing.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    use std::io::Write;
+
+    let dir = path.parent().ok_or("Invalid file path")?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir).map_err(|e| e.to_string())?;
+    tmp.write_all(contents.as_ref())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    tmp.persist(path)
+        .map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+fn process_lock(path: &Path) -> std::sync::Arc<std::sync::Mutex<()>> {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    static LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    locks.entry(path.to_path_buf()).or_default().clone()
+}
+
+/// Open the `<file>.lock` sidecar of `path` and take an exclusive advisory
+/// lock on it, blocking until any other holder releases it.  The lock is
+/// released when the returned file is dropped.
+pub fn lock_file(path: &Path) -> Result<fs::File, String> {
+    let mut lock_name = path.file_name().ok_or("Invalid file path")?.to_os_string();
+    lock_name.push(".lock");
+    let lock_path = path.with_file_name(lock_name);
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| format!("Failed to open '{}': {}", lock_path.display(), e))?;
+    file.lock()
+        .map_err(|e| format!("Failed to lock '{}': {}", lock_path.display(), e))?;
+    Ok(file)
+}
+
+/// Run `f` holding both locks for `path`.
+pub fn with_file_lock<T>(path: &Path, f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let in_process = process_lock(path);
+    let _guard = in_process.lock().unwrap_or_else(|e| e.into_inner());
+    let _file_lock = lock_file(path)?;
+    f()
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn policy(mode: DestructiveMode) -> RemovalPolicy {
+        RemovalPolicy { mode, max_files: 3 }
+    }
+
+    fn remove(
+        path: &Path,
+        context: &RemovalContext,
+        mode: DestructiveMode,
+    ) -> (
+        Result<RemovalOutcome, String>,
+        Vec<(PathBuf, RemovalOutcome)>,
+    ) {
+        let mut recorded = Vec::new();
+        let result = remove_guarded_with(path, context, &policy(mode), &mut |p, o| {
+            recorded.push((p.to_path_buf(), o))
+        });
+        (result, recorded)
+    }
+
+    fn skill_dir(root: &Path, files: usize) -> PathBuf {
+        let dir = root.join("skills").join("react");
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        for i in 0..files {
+            fs::write(dir.join("scripts").join(format!("{}.sh", i)), "x").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn removes_inside_root_and_records_it() {
+        let tmp = tempdir().unwrap();
+        let dir = skill_dir(tmp.path(), 2);
+        let (result, recorded) = remove(
+            &dir,
+            &RemovalContext::project(tmp.path()),
+            DestructiveMode::Allow,
+        );
+        assert_eq!(result, Ok(RemovalOutcome::Removed { files: 2 }));
+        assert!(!dir.exists());
+        assert_eq!(recorded, vec![(dir, RemovalOutcome::Removed { files: 2 })]);
+
+        let (result, recorded) = remove(
+            &tmp.path().join("gone"),
+            &RemovalContext::project(tmp.path()),
+            DestructiveMode::Allow,
+        );
+        assert_eq!(result, Ok(RemovalOutcome::Missing));
+        assert!(recorded.is_empty());
+    }
+
+    #[test]
+    fn paths_outside_the_allowed_roots_are_refused() {
+        let project = tempdir().unwrap();
+        let elsewhere = tempdir().unwrap();
+        let dir = skill_dir(elsewhere.path(), 1);
+
+        let (result, recorded) = remove(
+            &dir,
+            &RemovalContext::project(project.path()),
+            DestructiveMode::Allow,
+        );
+        assert!(result.unwrap_err().contains("outside"));
+        assert!(dir.exists());
+        assert!(recorded.is_empty());
+
+        // `..` cannot climb out of the root either.
+        let sneaky = project
+            .path()
+            .join("..")
+            .join(elsewhere.path().file_name().expect("tempdir has a name"));
+        let (result, _) = remove(
+            &sneaky,
+            &RemovalContext::project(project.path()),
+            DestructiveMode::Allow,
+        );
+        assert!(result.is_err());
+        assert!(elsewhere.path().exists());
+    }
+
+    #[test]
+    fn the_root_itself_needs_force() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path().join("data");
+        fs::create_dir_all(&root).unwrap();
+
+        let context = RemovalContext::external(&root);
+        let (result, _) = remove(&root, &context, DestructiveMode::Allow);
+        assert!(result.is_err());
+        assert!(root.exists());
+
+        let (result, _) = remove(&root, &context.forced(), DestructiveMode::Allow);
+        assert_eq!(result, Ok(RemovalOutcome::Removed { files: 0 }));
+        assert!(!root.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_parents_are_resolved_before_the_root_check() {
+        let project = tempdir().unwrap();
+        let source = tempdir().unwrap();
+        let real = skill_dir(source.path(), 1);
+        std::os::unix::fs::symlink(source.path(), project.path().join("linked")).unwrap();
+
+        let via_link = project.path().join("linked/skills/react");
+        let (result, _) = remove(
+            &via_link,
+            &RemovalContext::project(project.path()),
+            DestructiveMode::Allow,
+        );
+        assert!(result.is_err());
+        assert!(real.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_removed_as_links() {
+        let project = tempdir().unwrap();
+        let source = tempdir().unwrap();
+        let real = skill_dir(source.path(), 1);
+        let link = project.path().join("react");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let (result, _) = remove(
+            &link,
+            &RemovalContext::project(project.path()),
+            DestructiveMode::Allow,
+        );
+        assert_eq!(result, Ok(RemovalOutcome::Removed { files: 1 }));
+        assert!(link.symlink_metadata().is_err());
+        assert!(real.join("scripts/0.sh").exists());
+    }
+
+    #[test]
+    fn directories_with_git_are_refused_unless_forced() {
+        let tmp = tempdir().unwrap();
+        let dir = skill_dir(tmp.path(), 1);
+        fs::create_dir_all(dir.join("vendor/lib/.git")).unwrap();
+        let context = RemovalContext::project(tmp.path());
+
+        let (result, recorded) = remove(&dir, &context, DestructiveMode::Allow);
+        assert!(result.unwrap_err().contains(".git"));
+        assert!(dir.join("vendor/lib/.git").exists());
+        assert!(recorded.is_empty());
+
+        let (result, _) = remove(&dir, &context.forced(), DestructiveMode::Allow);
+        assert!(result.unwrap().is_removal());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn large_directories_are_refused_unless_forced() {
+        let tmp = tempdir().unwrap();
+        let dir = skill_dir(tmp.path(), 4);
+        let context = RemovalContext::project(tmp.path());
+
+        let (result, _) = remove(&dir, &context, DestructiveMode::Allow);
+        assert!(result.unwrap_err().contains("more than 3 files"));
+        assert!(dir.exists());
+
+        let (result, _) = remove(&dir, &context.forced(), DestructiveMode::Allow);
+        assert_eq!(result, Ok(RemovalOutcome::Removed { files: 4 }));
+    }
+
+    #[test]
+    fn dry_run_reports_and_records_without_deleting() {
+        let tmp = tempdir().unwrap();
+        let dir = skill_dir(tmp.path(), 2);
+        let file = tmp.path().join("notes.md");
+        fs::write(&file, "keep").unwrap();
+        let context = RemovalContext::project(tmp.path());
+
+        let (result, recorded) = remove(&dir, &context, DestructiveMode::DryRun);
+        assert_eq!(result, Ok(RemovalOutcome::WouldRemove { files: 2 }));
+        assert!(dir.join("scripts/1.sh").exists());
+        let (file_result, file_recorded) = remove(&file, &context, DestructiveMode::DryRun);
+        assert_eq!(file_result, Ok(RemovalOutcome::WouldRemove { files: 1 }));
+        assert!(file.exists());
+
+        let mut all = recorded;
+        all.extend(file_recorded);
+        assert_eq!(
+            all,
+            vec![
+                (dir, RemovalOutcome::WouldRemove { files: 2 }),
+                (file, RemovalOutcome::WouldRemove { files: 1 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn dry_run_still_applies_the_guards() {
+        let tmp = tempdir().unwrap();
+        let dir = skill_dir(tmp.path(), 1);
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        let (result, recorded) = remove(
+            &dir,
+            &RemovalContext::project(tmp.path()),
+            DestructiveMode::DryRun,
+        );
+        assert!(result.is_err());
+        assert!(recorded.is_empty());
+    }
+
+    #[test]
+    fn deny_refuses_every_removal() {
+        let tmp = tempdir().unwrap();
+        let dir = skill_dir(tmp.path(), 1);
+        let (result, recorded) = remove(
+            &dir,
+            &RemovalContext::project(tmp.path()).forced(),
+            DestructiveMode::Deny,
+        );
+        assert!(result.unwrap_err().contains("disabled"));
+        assert!(dir.exists());
+        assert!(recorded.is_empty());
+    }
+
+    #[test]
+    fn mode_parsing_defaults_to_allow() {
+        assert_eq!(DestructiveMode::parse("dry_run"), DestructiveMode::DryRun);
+        assert_eq!(DestructiveMode::parse("deny"), DestructiveMode::Deny);
+        assert_eq!(DestructiveMode::parse("allow"), DestructiveMode::Allow);
+        assert_eq!(DestructiveMode::parse(""), DestructiveMode::Allow);
+    }
+}
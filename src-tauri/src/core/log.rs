@@ -0,0 +1,267 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+
+use super::paths::get_automatic_dir;
+
+// ── Logging ──────────────────────────────────────────────────────────────────
+//
+// The desktop app has no console, so diagnostics written to stderr were lost.
+// `init_logging` installs one `tracing` subscriber that writes every event to
+// three places:
+//   - stderr, as before (the MCP server keeps stdout for the protocol);
+//   - `~/.automatic/logs/automatic.log.<date>`, rotated daily, keeping a week;
+//   - an in-memory ring buffer the diagnostics panel reads through
+//     `recent_logs`.
+// The app and every `mcp-serve` process call it, so their output ends up in
+// the same log directory.
+
+const LOG_FILE_PREFIX: &str = "automatic.log";
+const MAX_LOG_FILES: usize = 7;
+const LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// Entries returned by [`recent_logs`] when no limit is given.
+pub const DEFAULT_RECENT_LOGS: usize = 200;
+
+/// One logged event, as kept in the ring buffer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogEntry {
+    /// RFC 3339 time the event was logged.
+    pub timestamp: String,
+    /// "error", "warn", "info", "debug" or "trace".
+    pub level: String,
+    /// Module that logged the event, e.g. `automatic_lib::sync::engine`.
+    pub target: String,
+    /// The message, followed by any structured fields as `key=value`.
+    pub message: String,
+}
+
+/// Directory holding the log files.
+pub fn get_logs_dir() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("logs"))
+}
+
+/// The log file currently written to.  Files roll over at midnight UTC.
+pub fn get_log_file_path() -> Result<PathBuf, String> {
+    Ok(get_logs_dir()?.join(format!(
+        "{}.{}",
+        LOG_FILE_PREFIX,
+        chrono::Utc::now().format("%Y-%m-%d")
+    )))
+}
+
+/// Install the global subscriber.  Safe to call more than once; only the
+/// first call has an effect.  File logging is skipped, with a note on
+/// stderr, when the log directory cannot be created.
+pub fn init_logging() {
+    static INIT: OnceLock<()> = OnceLock::new();
+    INIT.get_or_init(|| {
+        let file_layer = match open_log_file() {
+            Ok(appender) => Some(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(appender),
+            ),
+            Err(e) => {
+                eprintln!("[automatic] file logging disabled: {}", e);
+                None
+            }
+        };
+        let result = tracing_subscriber::registry()
+            .with(LevelFilter::INFO)
+            .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+            .with(file_layer)
+            .with(BufferLayer)
+            .try_init();
+        if let Err(e) = result {
+            eprintln!("[automatic] logging not initialised: {}", e);
+        }
+    });
+}
+
+fn open_log_file() -> Result<RollingFileAppender, String> {
+    let dir = get_logs_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .map_err(|e| format!("Failed to open log file in '{}': {}", dir.display(), e))
+}
+
+/// The most recent `limit` buffered entries (default
+/// [`DEFAULT_RECENT_LOGS`]), oldest first.  `level_filter` keeps entries at
+/// that level or more severe: "warn" returns warnings and errors.
+pub fn recent_logs(
+    limit: Option<usize>,
+    level_filter: Option<&str>,
+) -> Result<Vec<LogEntry>, String> {
+    let max_severity = level_filter.map(severity).transpose()?;
+    let buffer = log_buffer().lock().unwrap_or_else(|e| e.into_inner());
+    Ok(buffer.recent(limit.unwrap_or(DEFAULT_RECENT_LOGS), max_severity))
+}
+
+/// Rank of a level name, most severe first.
+fn severity(level: &str) -> Result<u8, String> {
+    match level.trim().to_ascii_lowercase().as_str() {
+        "error" => Ok(0),
+        "warn" | "warning" => Ok(1),
+        "info" => Ok(2),
+        "debug" => Ok(3),
+        "trace" => Ok(4),
+        other => Err(format!(
+            "Unknown log level '{}'; expected error, warn, info, debug or trace",
+            other
+        )),
+    }
+}
+
+// ── Ring Buffer ──────────────────────────────────────────────────────────────
+
+struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    fn recent(&self, limit: usize, max_severity: Option<u8>) -> Vec<LogEntry> {
+        let mut entries: Vec<LogEntry> = self
+            .entries
+            .iter()
+            .rev()
+            .filter(|e| max_severity.map_or(true, |max| severity(&e.level).unwrap_or(0) <= max))
+            .take(limit)
+            .cloned()
+            .collect();
+        entries.reverse();
+        entries
+    }
+}
+
+fn log_buffer() -> &'static Mutex<LogBuffer> {
+    static BUFFER: OnceLock<Mutex<LogBuffer>> = OnceLock::new();
+    BUFFER.get_or_init(|| Mutex::new(LogBuffer::new(LOG_BUFFER_CAPACITY)))
+}
+
+/// Subscriber layer that copies every event into the ring buffer.
+struct BufferLayer;
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let entry = LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: metadata.level().to_string().to_ascii_lowercase(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        };
+        log_buffer()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(entry);
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else {
+            format!("{} {}", self.message, self.fields.join(" "))
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: String::new(),
+            level: level.to_string(),
+            target: "automatic_lib::sync".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn buffer_keeps_the_newest_entries_and_filters_by_severity() {
+        let mut buffer = LogBuffer::new(3);
+        for (level, message) in [
+            ("info", "one"),
+            ("error", "two"),
+            ("warn", "three"),
+            ("debug", "four"),
+        ] {
+            buffer.push(entry(level, message));
+        }
+
+        let messages = |entries: Vec<LogEntry>| -> Vec<String> {
+            entries.into_iter().map(|e| e.message).collect()
+        };
+        assert_eq!(messages(buffer.recent(10, None)), ["two", "three", "four"]);
+        assert_eq!(messages(buffer.recent(2, None)), ["three", "four"]);
+        assert_eq!(
+            messages(buffer.recent(10, Some(severity("warn").unwrap()))),
+            ["two", "three"]
+        );
+        assert_eq!(
+            messages(buffer.recent(10, Some(severity("ERROR").unwrap()))),
+            ["two"]
+        );
+        assert!(severity("verbose").is_err());
+    }
+}
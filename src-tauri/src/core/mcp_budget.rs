@@ -0,0 +1,176 @@
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::agent::{self, Agent};
+
+use super::*;
+
+// ── MCP Server Budget ────────────────────────────────────────────────────────
+//
+// Some agents send every configured server's tool list with each request and
+// get slower, or silently drop tools, once a project has stacked up servers
+// from templates, bulk edits and autodetection.  Agents that are known to
+// degrade declare `Agent::recommended_max_servers`; sync, `automatic doctor`,
+// the project checklist, template application and bulk updates report when a
+// project goes past it.  Like skill requirements, this is advisory only.
+//
+// Servers no selected skill requires (`requires_mcp`) are suggested for
+// pruning.  Automatic's own server counts towards the total but is never
+// suggested.
+
+/// An agent that receives more MCP servers than it handles well.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct McpBudgetWarning {
+    pub agent: String,
+    pub agent_label: String,
+    /// Servers written to the agent's config, Automatic's own included.
+    pub server_count: usize,
+    pub recommended_max: usize,
+    /// Servers to consider removing, enough to get back under the limit when
+    /// there are that many.  Servers a selected skill requires are never
+    /// suggested.
+    pub prune_candidates: Vec<String>,
+}
+
+impl McpBudgetWarning {
+    /// One-line description for logs and reports.
+    pub fn message(&self) -> String {
+        let mut message = format!(
+            "{} gets {} MCP servers, more than the {} it handles well",
+            self.agent_label, self.server_count, self.recommended_max
+        );
+        if !self.prune_candidates.is_empty() {
+            message.push_str(&format!(
+                "; no selected skill requires {}",
+                self.prune_candidates.join(", ")
+            ));
+        }
+        message
+    }
+}
+
+/// Budget warnings for `agents` when each is given `servers` (the enabled
+/// project servers, without Automatic's own) and the selected skills are
+/// `skills` (`(name, SKILL.md content)` pairs).
+pub fn check_mcp_budget(
+    agents: &[&dyn Agent],
+    servers: &[String],
+    skills: &[(String, String)],
+) -> Vec<McpBudgetWarning> {
+    let required: HashSet<String> = skills
+        .iter()
+        .flat_map(|(_, content)| parse_skill_requirements(content).mcp_servers)
+        .collect();
+    // Automatic's own server is always written alongside the project's.
+    let server_count = servers.len() + 1;
+
+    agents
+        .iter()
+        .filter(|a| a.capabilities().mcp_servers)
+        .filter_map(|a| {
+            let max = a.recommended_max_servers()?;
+            if server_count <= max {
+                return None;
+            }
+            let prune_candidates = servers
+                .iter()
+                .filter(|s| !required.contains(*s))
+                .take(server_count - max)
+                .cloned()
+                .collect();
+            Some(McpBudgetWarning {
+                agent: a.id().to_string(),
+                agent_label: a.label().to_string(),
+                server_count,
+                recommended_max: max,
+                prune_candidates,
+            })
+        })
+        .collect()
+}
+
+/// Budget warnings for the agents and enabled servers of `project`.
+pub fn project_mcp_budget_warnings(project: &Project) -> Vec<McpBudgetWarning> {
+    let agents: Vec<&dyn Agent> = project
+        .agents
+        .iter()
+        .filter_map(|id| agent::from_id(id))
+        .collect();
+    let servers = project.enabled_mcp_servers();
+    if agents.iter().all(|a| a.recommended_max_servers().is_none()) {
+        return Vec::new();
+    }
+    check_mcp_budget(&agents, &servers, &project_skill_contents(project))
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agents(ids: &[&str]) -> Vec<&'static dyn Agent> {
+        ids.iter().map(|id| agent::from_id(id).unwrap()).collect()
+    }
+
+    fn servers(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("server-{}", i)).collect()
+    }
+
+    fn cursor_max() -> usize {
+        agent::from_id("cursor")
+            .unwrap()
+            .recommended_max_servers()
+            .unwrap()
+    }
+
+    #[test]
+    fn projects_within_the_recommendation_are_quiet() {
+        // Automatic's own server fills the last slot.
+        let servers = servers(cursor_max() - 1);
+        assert!(check_mcp_budget(&agents(&["cursor", "claude"]), &servers, &[]).is_empty());
+    }
+
+    #[test]
+    fn projects_over_the_recommendation_warn_per_limited_agent() {
+        let servers = servers(cursor_max() + 2);
+        let warnings = check_mcp_budget(&agents(&["claude", "cursor"]), &servers, &[]);
+
+        assert_eq!(warnings.len(), 1, "Claude Code has no recommendation");
+        let warning = &warnings[0];
+        assert_eq!(warning.agent, "cursor");
+        assert_eq!(warning.server_count, cursor_max() + 3);
+        assert_eq!(warning.prune_candidates.len(), 3);
+        assert!(warning.message().contains("Cursor"));
+        assert!(warning.message().contains("server-1, server-2, server-3"));
+    }
+
+    #[test]
+    fn servers_required_by_skills_are_never_suggested() {
+        let servers = servers(cursor_max() + 1);
+        let skills = vec![(
+            "triage".to_string(),
+            "---\nname: triage\nrequires_mcp: [server-1, server-3]\n---\nBody".to_string(),
+        )];
+        let warnings = check_mcp_budget(&agents(&["cursor"]), &servers, &skills);
+
+        assert_eq!(warnings[0].prune_candidates, vec!["server-2", "server-4"]);
+    }
+
+    #[test]
+    fn nothing_is_suggested_when_every_server_is_required() {
+        let servers = servers(cursor_max());
+        let skills = vec![(
+            "all".to_string(),
+            format!(
+                "---\nname: all\nrequires_mcp: [{}]\n---\n",
+                servers.join(", ")
+            ),
+        )];
+        let warnings = check_mcp_budget(&agents(&["cursor"]), &servers, &skills);
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].prune_candidates.is_empty());
+        assert!(!warnings[0].message().contains("requires"));
+    }
+}
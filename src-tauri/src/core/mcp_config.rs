@@ -0,0 +1,302 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// ── Typed MCP Server Configs ─────────────────────────────────────────────────
+//
+// Registry configs are written into every agent's config almost verbatim, so
+// a typo such as `comand` used to turn into a broken entry everywhere.
+// Saving now goes through `McpServerConfig`, which knows the two shapes a
+// server can take and names the offending field when a config fits neither.
+// Fields it does not model (`variants`, `timeout`, `cwd`, internal `_`
+// fields, agent-specific extensions) are kept exactly as given.
+//
+// Legacy shapes that agents or older releases wrote are rewritten on the way
+// in rather than rejected:
+//   - `serverUrl` (Antigravity) and `httpUrl` (Gemini) become `url`;
+//   - a `command` list (`["npx", "-y", "pkg"]`) is split into command + args;
+//   - Zed's `command: { path, args, env }` object is flattened;
+//   - number and boolean env values become strings.
+
+/// A registry MCP server config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged, try_from = "Value")]
+pub enum McpServerConfig {
+    /// Started by the agent as a child process.
+    Stdio {
+        command: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        args: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        env: Option<Map<String, Value>>,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// Reached over HTTP or SSE.
+    Remote {
+        url: String,
+        #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+        transport: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        headers: Option<Map<String, Value>>,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+}
+
+impl TryFrom<Value> for McpServerConfig {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, String> {
+        Self::from_value(value)
+    }
+}
+
+impl McpServerConfig {
+    /// Validate and normalise a config, naming the offending field when it
+    /// cannot be used.
+    pub fn from_value(value: Value) -> Result<Self, String> {
+        let Value::Object(mut obj) = value else {
+            return Err("MCP server config must be a JSON object".into());
+        };
+        normalize_legacy(&mut obj);
+
+        match (obj.contains_key("command"), obj.contains_key("url")) {
+            (true, true) => Err(
+                "MCP server config has both 'command' and 'url'; keep 'command' for a stdio \
+                 server or 'url' for a remote one"
+                    .into(),
+            ),
+            (true, false) => Ok(Self::Stdio {
+                command: take_string(&mut obj, "command", "stdio")?,
+                args: take_string_list(&mut obj, "args")?,
+                env: take_env(&mut obj)?,
+                extra: obj,
+            }),
+            (false, true) => Ok(Self::Remote {
+                url: take_string(&mut obj, "url", "remote")?,
+                transport: match obj.remove("type") {
+                    None => None,
+                    Some(Value::String(t)) => Some(t),
+                    Some(_) => return Err("field 'type' must be a string".into()),
+                },
+                headers: take_object(&mut obj, "headers")?,
+                extra: obj,
+            }),
+            (false, false) => Err(missing_field_error(&obj)),
+        }
+    }
+
+    /// The config as stored in the registry.
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+fn normalize_legacy(obj: &mut Map<String, Value>) {
+    if !obj.contains_key("url") {
+        if let Some(url) = obj.remove("serverUrl").or_else(|| obj.remove("httpUrl")) {
+            obj.insert("url".into(), url);
+        }
+    }
+
+    match obj.remove("command") {
+        Some(Value::Array(parts)) => {
+            let mut parts = parts.into_iter();
+            let program = parts.next().unwrap_or(Value::Null);
+            let mut args: Vec<Value> = parts.collect();
+            match obj.remove("args") {
+                Some(Value::Array(rest)) => args.extend(rest),
+                Some(other) => {
+                    obj.insert("args".into(), other);
+                }
+                None => {}
+            }
+            if !args.is_empty() && !obj.contains_key("args") {
+                obj.insert("args".into(), Value::Array(args));
+            }
+            obj.insert("command".into(), program);
+        }
+        Some(Value::Object(mut zed)) => {
+            for key in ["args", "env"] {
+                if let Some(value) = zed.remove(key) {
+                    obj.entry(key).or_insert(value);
+                }
+            }
+            obj.insert("command".into(), zed.remove("path").unwrap_or(Value::Null));
+        }
+        Some(command) => {
+            obj.insert("command".into(), command);
+        }
+        None => {}
+    }
+}
+
+fn take_string(obj: &mut Map<String, Value>, key: &str, kind: &str) -> Result<String, String> {
+    match obj.remove(key) {
+        Some(Value::String(s)) if !s.trim().is_empty() => Ok(s),
+        _ => Err(format!(
+            "field '{}' must be a non-empty string for {} server",
+            key, kind
+        )),
+    }
+}
+
+fn take_string_list(
+    obj: &mut Map<String, Value>,
+    key: &str,
+) -> Result<Option<Vec<String>>, String> {
+    let Some(value) = obj.remove(key) else {
+        return Ok(None);
+    };
+    let error = || format!("field '{}' must be a list of strings", key);
+    let Value::Array(items) = value else {
+        return Err(error());
+    };
+    items
+        .into_iter()
+        .map(|item| match item {
+            Value::String(s) => Ok(s),
+            _ => Err(error()),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+fn take_object(
+    obj: &mut Map<String, Value>,
+    key: &str,
+) -> Result<Option<Map<String, Value>>, String> {
+    match obj.remove(key) {
+        None => Ok(None),
+        Some(Value::Object(map)) => Ok(Some(map)),
+        Some(_) => Err(format!("field '{}' must be an object", key)),
+    }
+}
+
+fn take_env(obj: &mut Map<String, Value>) -> Result<Option<Map<String, Value>>, String> {
+    let Some(mut env) = take_object(obj, "env")? else {
+        return Ok(None);
+    };
+    for (name, value) in env.iter_mut() {
+        match value {
+            Value::String(_) => {}
+            Value::Number(n) => *value = Value::String(n.to_string()),
+            Value::Bool(b) => *value = Value::String(b.to_string()),
+            _ => {
+                return Err(format!(
+                    "env value for '{}' must be a string, number or boolean",
+                    name
+                ))
+            }
+        }
+    }
+    Ok(Some(env))
+}
+
+/// The error for a config with neither `command` nor `url`, pointing at the
+/// server kind its other fields suggest.
+fn missing_field_error(obj: &Map<String, Value>) -> String {
+    let transport = obj.get("type").and_then(Value::as_str);
+    let looks_stdio =
+        obj.contains_key("args") || obj.contains_key("env") || transport == Some("stdio");
+    let looks_remote = obj.contains_key("headers")
+        || matches!(
+            transport,
+            Some("http" | "sse" | "streamable-http" | "streamableHttp")
+        );
+    match (looks_stdio, looks_remote) {
+        (true, false) => "missing required field 'command' for stdio server".into(),
+        (false, true) => "missing required field 'url' for remote server".into(),
+        _ => {
+            let found: Vec<String> = obj
+                .keys()
+                .filter(|k| !k.starts_with('_'))
+                .map(|k| format!("'{}'", k))
+                .collect();
+            let mut error = "missing required field 'command' (stdio server) or 'url' \
+                             (remote server)"
+                .to_string();
+            if !found.is_empty() {
+                error.push_str(&format!("; found {}", found.join(", ")));
+            }
+            error
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(value: Value) -> Result<Value, String> {
+        McpServerConfig::from_value(value).map(|c| c.to_value())
+    }
+
+    #[test]
+    fn typos_are_reported_by_field() {
+        assert_eq!(
+            parse(json!({"comand": "npx"})).unwrap_err(),
+            "missing required field 'command' (stdio server) or 'url' (remote server); \
+             found 'comand'"
+        );
+        assert_eq!(
+            parse(json!({"comand": "npx", "args": ["-y", "pkg"]})).unwrap_err(),
+            "missing required field 'command' for stdio server"
+        );
+        assert_eq!(
+            parse(json!({"type": "http", "headers": {}})).unwrap_err(),
+            "missing required field 'url' for remote server"
+        );
+        assert!(parse(json!({"command": "npx", "args": "-y pkg"}))
+            .unwrap_err()
+            .contains("'args'"));
+        assert!(parse(json!({"command": "npx", "url": "https://x"})).is_err());
+        assert!(parse(json!(["npx"])).is_err());
+    }
+
+    #[test]
+    fn unknown_fields_pass_through() {
+        let config = json!({
+            "command": "npx",
+            "args": ["-y", "pkg"],
+            "env": {"TOKEN": "t"},
+            "timeout": 30,
+            "_source": "cursor",
+            "variants": {"codex": {"command": "/usr/bin/pkg"}}
+        });
+        assert_eq!(parse(config.clone()).unwrap(), config);
+
+        let remote = json!({"url": "https://mcp.example.com", "type": "http", "oauth": true});
+        assert_eq!(parse(remote.clone()).unwrap(), remote);
+    }
+
+    #[test]
+    fn legacy_shapes_are_normalised() {
+        assert_eq!(
+            parse(json!({"serverUrl": "https://mcp.example.com"})).unwrap(),
+            json!({"url": "https://mcp.example.com"})
+        );
+        assert_eq!(
+            parse(json!({"command": ["npx", "-y"], "args": ["pkg"]})).unwrap(),
+            json!({"command": "npx", "args": ["-y", "pkg"]})
+        );
+        assert_eq!(
+            parse(json!({"command": {"path": "/bin/srv", "args": ["--stdio"]}})).unwrap(),
+            json!({"command": "/bin/srv", "args": ["--stdio"]})
+        );
+        assert_eq!(
+            parse(json!({"command": "srv", "env": {"PORT": 8080, "DEBUG": true}})).unwrap(),
+            json!({"command": "srv", "env": {"PORT": "8080", "DEBUG": "true"}})
+        );
+    }
+
+    #[test]
+    fn deserializes_through_serde() {
+        let config: McpServerConfig = serde_json::from_str(r#"{"url": "https://x/mcp"}"#).unwrap();
+        assert!(matches!(config, McpServerConfig::Remote { .. }));
+        assert!(serde_json::from_str::<McpServerConfig>(r#"{"comand": "npx"}"#).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use super::*;
+
+// ── Keychain Placeholders ────────────────────────────────────────────────────
+//
+// Registry MCP server configs may reference API keys stored in the OS keychain
+// instead of holding them in plain text:
+//
+//     "env": { "GITHUB_TOKEN": "${keychain:github_token}" }
+//
+// Sync (and drift detection, so both agree on the expected files) replaces
+// each placeholder with the key saved under that provider name by
+// `save_api_key`.  A server with a placeholder that cannot be resolved is left
+// out of the agent configs rather than written with the literal placeholder,
+// and reported as a warning.
+
+const PLACEHOLDER_PREFIX: &str = "${keychain:";
+
+/// A server left out of a sync because its config names keychain entries
+/// that do not exist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct McpSecretWarning {
+    pub server: String,
+    /// Keychain entry names that could not be resolved.
+    pub missing: Vec<String>,
+}
+
+impl McpSecretWarning {
+    /// One-line description for logs and reports.
+    pub fn message(&self) -> String {
+        format!(
+            "MCP server '{}' was not written: no keychain entry for {}",
+            self.server,
+            self.missing
+                .iter()
+                .map(|name| format!("'{}'", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Replace every `${keychain:<name>}` placeholder in the string values of
+/// `config` with the stored API key.  Returns the names that have no
+/// keychain entry; their placeholders are left in place.
+pub fn resolve_keychain_placeholders(config: &mut Value) -> Vec<String> {
+    resolve_keychain_placeholders_with(config, &|name| get_api_key(name).ok())
+}
+
+/// [`resolve_keychain_placeholders`] with the keychain lookup injected.
+pub fn resolve_keychain_placeholders_with(
+    config: &mut Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    substitute_placeholders(config, &mut |name| {
+        let value = lookup(name);
+        if value.is_none() && !missing.iter().any(|n| n == name) {
+            missing.push(name.to_string());
+        }
+        value
+    });
+    missing
+}
+
+fn substitute_placeholders(value: &mut Value, lookup: &mut dyn FnMut(&str) -> Option<String>) {
+    match value {
+        Value::String(text) if text.contains(PLACEHOLDER_PREFIX) => {
+            *text = substitute_in_str(text, lookup);
+        }
+        Value::Array(items) => {
+            for item in items {
+                substitute_placeholders(item, lookup);
+            }
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                substitute_placeholders(field, lookup);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `text` with each well-formed placeholder replaced by its lookup result.
+/// Placeholders without a value, and malformed ones, are kept verbatim.
+fn substitute_in_str(text: &str, lookup: &mut dyn FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(PLACEHOLDER_PREFIX) {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + PLACEHOLDER_PREFIX.len()..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &after[..end];
+        let placeholder = &rest[start..start + PLACEHOLDER_PREFIX.len() + end + 1];
+        match is_placeholder_name(name).then(|| lookup(name)).flatten() {
+            Some(secret) => out.push_str(&secret),
+            None => out.push_str(placeholder),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn placeholders_are_resolved_from_the_keychain() {
+        let lookup = |name: &str| (name == "github_token").then(|| "ghp_123".to_string());
+        let mut config = json!({
+            "command": "npx",
+            "args": ["--token", "${keychain:github_token}"],
+            "env": {
+                "GITHUB_TOKEN": "${keychain:github_token}",
+                "AUTH": "Bearer ${keychain:github_token}",
+                "LINEAR_KEY": "${keychain:linear}",
+                "PLAIN": "${keychain:not a name}",
+            },
+        });
+
+        let missing = resolve_keychain_placeholders_with(&mut config, &lookup);
+
+        assert_eq!(missing, vec!["linear"]);
+        assert_eq!(config["args"], json!(["--token", "ghp_123"]));
+        assert_eq!(config["env"]["GITHUB_TOKEN"], "ghp_123");
+        assert_eq!(config["env"]["AUTH"], "Bearer ghp_123");
+        assert_eq!(config["env"]["LINEAR_KEY"], "${keychain:linear}");
+        assert_eq!(config["env"]["PLAIN"], "${keychain:not a name}");
+    }
+
+    #[test]
+    fn missing_names_are_reported_once() {
+        let mut config = json!({
+            "env": { "A": "${keychain:a}", "B": "${keychain:b}${keychain:a}", "C": "${keychain:" },
+        });
+        let missing = resolve_keychain_placeholders_with(&mut config, &|_| None);
+        assert_eq!(missing, vec!["a", "b"]);
+        assert_eq!(config["env"]["C"], "${keychain:");
+        assert_eq!(
+            McpSecretWarning {
+                server: "github".into(),
+                missing: vec!["a".into(), "b".into()],
+            }
+            .message(),
+            "MCP server 'github' was not written: no keychain entry for 'a', 'b'"
+        );
+    }
+}
@@ -2,7 +2,10 @@ use std::fs;
 use std::path::PathBuf;
 
 use super::env_crypto;
+use super::mcp_config::McpServerConfig;
 use super::paths::{get_automatic_dir, is_valid_name};
+use super::self_mcp::{automatic_command, managed_entry, AUTOMATIC_SERVER_NAME};
+use super::stable_json::to_stable_json;
 
 // ── MCP Servers ──────────────────────────────────────────────────────────────
 
@@ -67,13 +70,16 @@ pub fn read_mcp_server_config(name: &str) -> Result<String, String> {
 ///
 /// The `data` parameter is the raw JSON string from the frontend (env values
 /// are plaintext at this point — the frontend never sees the encrypted form).
+/// It must fit [`McpServerConfig`]; legacy shapes are normalised on save.
 pub fn save_mcp_server_config(name: &str, data: &str) -> Result<(), String> {
     if !is_valid_name(name) {
         return Err("Invalid server name".into());
     }
 
-    let mut config: serde_json::Value =
+    let raw: serde_json::Value =
         serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let mut config = McpServerConfig::from_value(raw)?.to_value();
+    validate_mcp_server_variants(&config)?;
 
     // Encrypt env values before writing to disk.
     if let Some(env) = config.get_mut("env") {
@@ -86,10 +92,89 @@ pub fn save_mcp_server_config(name: &str, data: &str) -> Result<(), String> {
     }
 
     let path = dir.join(format!("{}.json", name));
-    let serialized = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    let serialized = to_stable_json(&config)?;
     fs::write(path, serialized).map_err(|e| e.to_string())
 }
 
+// ── Per-agent command variants ──────────────────────────────────────────────
+//
+// A server config may carry `"variants": { "<agent_id>": { command, args } }`
+// for agents that cannot run the base command form, e.g. one without `node`
+// on its PATH that needs the absolute binary instead of `npx`.  Sync writes
+// the variant into that agent's config and the base form everywhere else;
+// drift detection expects the same.
+
+/// Check the `variants` of a server config: keys must be known agent ids and
+/// each variant an object with a non-empty `command` and/or string `args`.
+pub fn validate_mcp_server_variants(config: &serde_json::Value) -> Result<(), String> {
+    let Some(variants) = config.get("variants") else {
+        return Ok(());
+    };
+    let variants = variants
+        .as_object()
+        .ok_or("`variants` must be an object keyed by agent id")?;
+    if !variants.is_empty() && config.get("url").is_some() {
+        return Err("Command variants only apply to servers started with a command".into());
+    }
+    for (agent_id, variant) in variants {
+        if crate::agent::from_id(agent_id).is_none() {
+            return Err(format!("Unknown agent '{}' in `variants`", agent_id));
+        }
+        let variant = variant
+            .as_object()
+            .ok_or_else(|| format!("Variant for '{}' must be an object", agent_id))?;
+        if let Some(key) = variant.keys().find(|k| *k != "command" && *k != "args") {
+            return Err(format!(
+                "Variant for '{}' has unsupported field '{}' (only `command` and `args`)",
+                agent_id, key
+            ));
+        }
+        let command = variant.get("command");
+        if command.is_some_and(|c| c.as_str().map_or(true, |c| c.trim().is_empty())) {
+            return Err(format!(
+                "Variant for '{}' needs a non-empty `command`",
+                agent_id
+            ));
+        }
+        let args = variant.get("args");
+        if args.is_some_and(|a| {
+            a.as_array()
+                .map_or(true, |items| items.iter().any(|i| !i.is_string()))
+        }) {
+            return Err(format!(
+                "Variant for '{}' needs `args` as a list of strings",
+                agent_id
+            ));
+        }
+        if command.is_none() && args.is_none() {
+            return Err(format!(
+                "Variant for '{}' must set `command` or `args`",
+                agent_id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a server entry for one agent: the agent's variant, if any,
+/// replaces `command`/`args`, and `variants` itself is dropped so it never
+/// reaches an agent config file.
+pub fn apply_mcp_command_variant(config: &mut serde_json::Value, agent_id: &str) {
+    let Some(map) = config.as_object_mut() else {
+        return;
+    };
+    let Some(variants) = map.remove("variants") else {
+        return;
+    };
+    if let Some(variant) = variants.get(agent_id).and_then(|v| v.as_object()) {
+        for key in ["command", "args"] {
+            if let Some(value) = variant.get(key) {
+                map.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+}
+
 pub fn delete_mcp_server_config(name: &str) -> Result<(), String> {
     if !is_valid_name(name) {
         return Err("Invalid server name".into());
@@ -125,10 +210,6 @@ pub fn list_mcp_servers() -> Result<String, String> {
     }
 }
 
-/// The well-known name used for the Automatic MCP server everywhere:
-/// registry files, project assignments, and agent config files.
-pub const AUTOMATIC_SERVER_NAME: &str = "automatic";
-
 /// The well-known name of the bundled "automatic" skill that teaches agents
 /// how to use the Automatic MCP service.  Always assigned to every project.
 pub const AUTOMATIC_SKILL_NAME: &str = "automatic";
@@ -147,7 +228,8 @@ pub const AUTOMATIC_SKILL_NAME: &str = "automatic";
 /// at least one agent.  The caller is responsible for re-syncing these
 /// projects in the background so that:
 /// - The `automatic` MCP server entry in each agent config file reflects the
-///   current binary path (which changes between dev builds and release).
+///   current command (the binary path changes between dev builds and
+///   release; the command also changes with `automatic_mcp_command_mode`).
 /// - Any newly added `automatic` skill files are written to disk.
 ///
 /// The MCP server is exposed to agents via per-project config files written
@@ -156,35 +238,32 @@ pub const AUTOMATIC_SKILL_NAME: &str = "automatic";
 /// having multiple registrations of the same server causes Claude Code to
 /// deduplicate and drop tools.
 ///
-/// The binary path is resolved from the current executable so it always
-/// reflects the installed release binary rather than a hard-coded path.
+/// The command is resolved from the current executable (or the configured
+/// command mode) so it always reflects the installed release binary rather
+/// than a hard-coded path.
 pub fn ensure_automatic_in_global_mcp() -> Result<Vec<String>, String> {
-    let binary = std::env::current_exe()
-        .ok()
-        .and_then(|p| p.to_str().map(|s| s.to_string()))
-        .unwrap_or_else(|| "automatic".to_string());
+    let command = automatic_command();
 
-    // ── 1. Read old registry entry to detect binary path change ──────────
+    // ── 1. Read old registry entry to detect a command change ────────────
     //
-    // If the binary path has changed (e.g. dev→release or after an update),
-    // every project that writes the automatic server entry needs a re-sync.
-    let binary_changed = read_mcp_server_config(AUTOMATIC_SERVER_NAME)
+    // If the command has changed (e.g. dev→release, after an update, or
+    // after switching `automatic_mcp_command_mode`), every project that
+    // writes the automatic server entry needs a re-sync.
+    let command_changed = read_mcp_server_config(AUTOMATIC_SERVER_NAME)
         .ok()
         .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
         .and_then(|v| {
             v.get("command")
                 .and_then(|c| c.as_str())
-                .map(|c| c != binary)
+                .map(|c| c != command)
         })
         .unwrap_or(true); // no existing entry → treat as changed
 
     // ── 2. Registry entry ────────────────────────────────────────────────
-    let registry_config = serde_json::json!({
-        "type": "stdio",
-        "command": binary,
-        "args": ["mcp-serve"],
-        "_builtin": true
-    });
+    // The shared project-less entry plus registry-only metadata.
+    let (_, mut registry_config) = managed_entry(None);
+    registry_config["type"] = serde_json::json!("stdio");
+    registry_config["_builtin"] = serde_json::json!(true);
     let registry_str = serde_json::to_string_pretty(&registry_config).map_err(|e| e.to_string())?;
     // save_mcp_server_config handles directory creation and env encryption.
     save_mcp_server_config(AUTOMATIC_SERVER_NAME, &registry_str)?;
@@ -220,13 +299,13 @@ pub fn ensure_automatic_in_global_mcp() -> Result<Vec<String>, String> {
 
                     // Queue for re-sync if:
                     // - We just added automatic server/skill (changed = true), OR
-                    // - The binary path changed and this project has agents that
+                    // - The command changed and this project has agents that
                     //   would write agent config files containing the path.
                     let has_syncable_config = !project.directory.is_empty()
                         && !project.agents.is_empty()
                         && std::path::Path::new(&project.directory).exists();
 
-                    if has_syncable_config && (changed || binary_changed) {
+                    if has_syncable_config && (changed || command_changed) {
                         projects_to_sync.push(name);
                     }
                 }
@@ -267,6 +346,7 @@ mod tests {
         }
         let mut config: serde_json::Value =
             serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?;
+        validate_mcp_server_variants(&config)?;
         if let Some(env) = config.get_mut("env") {
             env_crypto::encrypt_env_values(env)?;
         }
@@ -274,7 +354,7 @@ mod tests {
             fs::create_dir_all(dir).map_err(|e| e.to_string())?;
         }
         let path = dir.join(format!("{}.json", name));
-        let serialized = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+        let serialized = to_stable_json(&config)?;
         fs::write(path, serialized).map_err(|e| e.to_string())
     }
 
@@ -471,4 +551,64 @@ mod tests {
         let val: serde_json::Value = serde_json::from_str(&raw).expect("parse");
         assert_eq!(val["env"]["PORT"].as_str().unwrap(), "8080");
     }
+
+    // ── per-agent command variants ───────────────────────────────────────────
+
+    #[test]
+    fn variants_are_validated_on_save() {
+        let tmp = tmp();
+        let dir = tmp.path().join("mcp_servers");
+        let ok = r#"{"command": "npx", "args": ["-y", "server-github"],
+            "variants": {"cursor": {"command": "/opt/bin/server-github", "args": []}}}"#;
+        save_at(&dir, "github", ok).expect("save");
+        let saved: serde_json::Value =
+            serde_json::from_str(&read_at(&dir, "github").unwrap()).unwrap();
+        assert_eq!(
+            saved["variants"]["cursor"]["command"],
+            "/opt/bin/server-github"
+        );
+
+        let err = |variants: &str| {
+            let config = format!(r#"{{"command": "npx", "variants": {}}}"#, variants);
+            save_at(&dir, "bad", &config).unwrap_err()
+        };
+        assert!(err(r#"{"no-such-agent": {"command": "x"}}"#).contains("Unknown agent"));
+        assert!(err(r#"{"cursor": {"command": ""}}"#).contains("non-empty"));
+        assert!(err(r#"{"cursor": {"args": "-y"}}"#).contains("list of strings"));
+        assert!(err(r#"{"cursor": {"env": {}}}"#).contains("unsupported field"));
+        assert!(err(r#"{"cursor": {}}"#).contains("must set"));
+        assert!(err(r#"["cursor"]"#).contains("keyed by agent id"));
+        assert!(save_at(
+            &dir,
+            "remote",
+            r#"{"url": "https://x", "variants": {"cursor": {"command": "x"}}}"#
+        )
+        .unwrap_err()
+        .contains("started with a command"));
+        assert!(!dir.join("bad.json").exists());
+    }
+
+    #[test]
+    fn variant_replaces_command_for_its_agent_only() {
+        let config = serde_json::json!({
+            "command": "npx",
+            "args": ["-y", "server-github"],
+            "env": {"TOKEN": ""},
+            "variants": {"cursor": {"command": "/opt/bin/server-github", "args": []}}
+        });
+
+        let mut cursor = config.clone();
+        apply_mcp_command_variant(&mut cursor, "cursor");
+        assert_eq!(
+            cursor,
+            serde_json::json!({"command": "/opt/bin/server-github", "args": [], "env": {"TOKEN": ""}})
+        );
+
+        let mut claude = config;
+        apply_mcp_command_variant(&mut claude, "claude");
+        assert_eq!(
+            claude,
+            serde_json::json!({"command": "npx", "args": ["-y", "server-github"], "env": {"TOKEN": ""}})
+        );
+    }
 }
@@ -0,0 +1,677 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use super::*;
+
+// ── MCP Config Snippets ──────────────────────────────────────────────────────
+//
+// MCP server READMEs show a config snippet for some agent ("add this to your
+// claude_desktop_config.json").  A pasted snippet is searched for JSON —
+// fenced code blocks first, then any balanced `{…}` in the text — and the
+// server definitions in it are found whether they sit under `mcpServers`
+// (Claude, Cursor, …), `mcp` (OpenCode), `servers` (VS Code),
+// `context_servers` (Zed), or are given bare.  Each is converted with the
+// agents' import normalisers, so a snippet lands in the registry in the same
+// shape discovery would have produced.
+//
+// Values such as `YOUR_API_KEY` are placeholders, not secrets.  In `env`
+// they are blanked (an empty value is inherited from the shell at sync time)
+// and the variable is reported as required; anywhere else they are reported
+// so the user can fill them in before saving.
+
+/// Keys agents keep their servers under.
+const WRAPPER_KEYS: &[&str] = &["mcpServers", "mcp", "servers", "context_servers"];
+
+/// Launchers whose first package argument names the server better than the
+/// command does.
+const LAUNCHERS: &[&str] = &[
+    "npx", "bunx", "pnpx", "uvx", "uv", "pipx", "node", "python", "python3", "deno", "docker",
+    "npm", "pnpm", "yarn", "bun",
+];
+
+/// A server found in a pasted snippet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpSnippetCandidate {
+    /// Registry name: the snippet's key, or inferred for a bare definition.
+    pub name: String,
+    /// Canonical server config, with placeholder env values blanked.
+    pub config: Value,
+    /// Env vars the user must supply (placeholder or empty values).
+    pub required_env: Vec<String>,
+    /// Other places still holding a placeholder, e.g. `headers.Authorization`.
+    pub placeholders: Vec<String>,
+    /// A registry server with this name already exists.
+    pub exists: bool,
+}
+
+/// How to save one candidate.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpSnippetOverride {
+    /// Candidate name as returned by [`parse_mcp_snippet`].
+    pub name: String,
+    /// Save under a different registry name.
+    #[serde(default)]
+    pub save_as: Option<String>,
+    /// Env values to set, typically the required ones.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Replace an existing registry server of the same name.
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Find the MCP server definitions in pasted text.
+pub fn parse_mcp_snippet(raw: &str) -> Result<Vec<McpSnippetCandidate>, String> {
+    let existing = list_mcp_server_configs().unwrap_or_default();
+    parse_snippet_candidates(raw, &existing)
+}
+
+/// Save candidates from `raw` to the registry.  With no overrides every
+/// candidate is saved as found; otherwise only the named ones, with the
+/// overrides applied.  Returns the registry names written.  Nothing is saved
+/// if any selected candidate is unknown or would replace an existing server
+/// without `overwrite`.
+pub fn import_mcp_snippet(
+    raw: &str,
+    overrides: &[McpSnippetOverride],
+) -> Result<Vec<String>, String> {
+    let existing = list_mcp_server_configs().unwrap_or_default();
+    let candidates = parse_snippet_candidates(raw, &existing)?;
+    let selected = select_candidates(&candidates, overrides, &existing)?;
+
+    let mut saved = Vec::new();
+    for (name, config) in selected {
+        let data = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+        save_mcp_server_config(&name, &data)?;
+        saved.push(name);
+    }
+    Ok(saved)
+}
+
+fn parse_snippet_candidates(
+    raw: &str,
+    existing: &[String],
+) -> Result<Vec<McpSnippetCandidate>, String> {
+    let mut found: Vec<(String, Value)> = Vec::new();
+    for value in json_values(raw) {
+        collect_servers(&value, &mut found);
+    }
+
+    let mut candidates: Vec<McpSnippetCandidate> = Vec::new();
+    for (name, config) in found {
+        if is_managed_name(&name) || !is_valid_name(&name) {
+            continue;
+        }
+        let name = unique_name(&name, &candidates);
+        let mut config = crate::agent::normalise_foreign_server(config);
+        let (required_env, placeholders) = flag_placeholders(&mut config);
+        candidates.push(McpSnippetCandidate {
+            exists: existing.contains(&name),
+            name,
+            config,
+            required_env,
+            placeholders,
+        });
+    }
+
+    if candidates.is_empty() {
+        return Err("No MCP server definition found in the pasted text".into());
+    }
+    Ok(candidates)
+}
+
+/// `(registry name, config)` for each candidate to save.
+fn select_candidates(
+    candidates: &[McpSnippetCandidate],
+    overrides: &[McpSnippetOverride],
+    existing: &[String],
+) -> Result<Vec<(String, Value)>, String> {
+    let defaults: Vec<McpSnippetOverride>;
+    let overrides = if overrides.is_empty() {
+        defaults = candidates
+            .iter()
+            .map(|c| McpSnippetOverride {
+                name: c.name.clone(),
+                ..Default::default()
+            })
+            .collect();
+        &defaults
+    } else {
+        overrides
+    };
+
+    let mut selected = Vec::new();
+    for over in overrides {
+        let candidate = candidates
+            .iter()
+            .find(|c| c.name == over.name)
+            .ok_or_else(|| format!("The snippet has no server named '{}'", over.name))?;
+        let name = over
+            .save_as
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .unwrap_or(&candidate.name)
+            .to_string();
+        if !is_valid_name(&name) || is_managed_name(&name) {
+            return Err(format!("'{}' cannot be used as an MCP server name", name));
+        }
+        if existing.contains(&name) && !over.overwrite {
+            return Err(format!("MCP server '{}' already exists", name));
+        }
+
+        let mut config = candidate.config.clone();
+        if !over.env.is_empty() {
+            if let Some(obj) = config.as_object_mut() {
+                let env = obj
+                    .entry("env")
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Some(env) = env.as_object_mut() {
+                    for (key, value) in &over.env {
+                        env.insert(key.clone(), Value::String(value.clone()));
+                    }
+                }
+            }
+        }
+        selected.push((name, config));
+    }
+    Ok(selected)
+}
+
+// ── JSON extraction ──────────────────────────────────────────────────────────
+
+/// JSON objects in `raw`: the contents of fenced code blocks when any parse,
+/// else every top-level balanced `{…}` in the text.
+fn json_values(raw: &str) -> Vec<Value> {
+    let fenced: Vec<Value> = fenced_blocks(raw)
+        .iter()
+        .flat_map(|block| objects_in(block))
+        .collect();
+    if !fenced.is_empty() {
+        return fenced;
+    }
+    objects_in(raw)
+}
+
+fn fenced_blocks(raw: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+    for line in raw.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some(String::new()),
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+    blocks
+}
+
+/// Parse `text` as a whole, or as the body of an object (READMEs often show
+/// just `"name": {…}`), or else each balanced top-level `{…}` inside it.
+fn objects_in(text: &str) -> Vec<Value> {
+    let text = text.trim();
+    if let Some(value) = parse_lenient(text) {
+        return vec![value];
+    }
+    if let Some(value) = parse_lenient(&format!("{{{}}}", text)) {
+        return vec![value];
+    }
+    let mut values = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = balanced_len(&rest[start..]) else {
+            break;
+        };
+        let segment = &rest[start..start + len];
+        match parse_lenient(segment) {
+            Some(value) => {
+                values.push(value);
+                rest = &rest[start + len..];
+            }
+            None => rest = &rest[start + 1..],
+        }
+    }
+    values
+}
+
+/// Length of the `{…}` group `text` starts with, skipping braces in strings.
+pub(crate) fn balanced_len(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse a JSON object, tolerating `//` comments and trailing commas.
+fn parse_lenient(text: &str) -> Option<Value> {
+    if !text.starts_with('{') {
+        return None;
+    }
+    let value = serde_json::from_str::<Value>(text)
+        .or_else(|_| serde_json::from_str::<Value>(&strip_jsonc(text)))
+        .ok()?;
+    value.is_object().then_some(value)
+}
+
+/// Drop `//` line comments and commas before `}` or `]`, outside strings.
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    let mut escaped = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ',' => {
+                let trailing = chars
+                    .clone()
+                    .find(|n| !n.is_whitespace())
+                    .is_some_and(|n| n == '}' || n == ']');
+                if !trailing {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// ── Server discovery ─────────────────────────────────────────────────────────
+
+fn is_server_definition(value: &Value) -> bool {
+    value.as_object().is_some_and(|obj| {
+        obj.get("command")
+            .is_some_and(|c| c.is_string() || c.is_array())
+            || obj.get("url").is_some_and(Value::is_string)
+            || obj.get("serverUrl").is_some_and(Value::is_string)
+    })
+}
+
+/// Add the `(name, config)` pairs defined in `value` to `found`.
+fn collect_servers(value: &Value, found: &mut Vec<(String, Value)>) {
+    if is_server_definition(value) {
+        found.push((infer_name(value), value.clone()));
+        return;
+    }
+    let Some(obj) = value.as_object() else {
+        return;
+    };
+
+    let wrapped: Vec<&Value> = WRAPPER_KEYS.iter().filter_map(|k| obj.get(*k)).collect();
+    if !wrapped.is_empty() {
+        for inner in wrapped {
+            // VS Code's settings.json nests `servers` inside `mcp`.
+            if inner
+                .as_object()
+                .is_some_and(|m| m.values().any(is_server_definition))
+            {
+                collect_named(inner, found);
+            } else {
+                collect_servers(inner, found);
+            }
+        }
+        return;
+    }
+    collect_named(value, found);
+}
+
+fn collect_named(map: &Value, found: &mut Vec<(String, Value)>) {
+    let Some(map) = map.as_object() else {
+        return;
+    };
+    for (name, config) in map {
+        if is_server_definition(config) {
+            found.push((name.clone(), config.clone()));
+        }
+    }
+}
+
+/// A registry name for a bare definition: the package it launches, the
+/// command itself, or the URL's host, with `mcp`/`server` affixes removed.
+fn infer_name(config: &Value) -> String {
+    let command = match config.get("command") {
+        Some(Value::String(c)) => Some(c.as_str()),
+        Some(Value::Array(parts)) => parts.first().and_then(Value::as_str),
+        _ => None,
+    };
+    let args: Vec<&str> = match (config.get("command"), config.get("args")) {
+        (Some(Value::Array(parts)), _) => parts.iter().skip(1).filter_map(Value::as_str).collect(),
+        (_, Some(Value::Array(args))) => args.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+
+    let source = match command {
+        Some(command) => {
+            let program = command.rsplit(['/', '\\']).next().unwrap_or(command);
+            if LAUNCHERS.contains(&program) {
+                args.iter()
+                    .copied()
+                    .filter(|a| !a.starts_with('-') && !matches!(*a, "run" | "exec" | "dlx" | "x"))
+                    .find(|a| a.contains(|c: char| c.is_ascii_alphabetic()))
+                    .map(|a| a.rsplit('/').next().unwrap_or(a))
+                    .unwrap_or(program)
+            } else {
+                program
+            }
+        }
+        None => config
+            .get("url")
+            .or_else(|| config.get("serverUrl"))
+            .and_then(Value::as_str)
+            .and_then(url_host)
+            .unwrap_or(""),
+    };
+    tidy_name(source)
+}
+
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    let host = rest.split(['/', ':', '?']).next()?;
+    let labels: Vec<&str> = host
+        .split('.')
+        .filter(|l| !matches!(*l, "mcp" | "www" | "api"))
+        .collect();
+    labels.first().copied()
+}
+
+fn tidy_name(source: &str) -> String {
+    // Drop a version (`pkg@1.2.0`) and any extension (`server.py`).
+    let base = source.split('@').find(|s| !s.is_empty()).unwrap_or("");
+    let base = base.split('.').next().unwrap_or(base);
+    let mut name: String = base
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    for prefix in ["mcp-server-", "server-", "mcp-"] {
+        if let Some(rest) = name.strip_prefix(prefix).filter(|r| !r.is_empty()) {
+            name = rest.to_string();
+            break;
+        }
+    }
+    for suffix in ["-mcp-server", "-mcp", "-server"] {
+        if let Some(rest) = name.strip_suffix(suffix).filter(|r| !r.is_empty()) {
+            name = rest.to_string();
+            break;
+        }
+    }
+    let name = name.trim_matches('-').to_string();
+    if name.is_empty() {
+        "mcp-server".to_string()
+    } else {
+        name
+    }
+}
+
+fn unique_name(name: &str, candidates: &[McpSnippetCandidate]) -> String {
+    let taken = |n: &str| candidates.iter().any(|c| c.name == n);
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|i| format!("{}-{}", name, i))
+        .find(|n| !taken(n))
+        .unwrap_or_else(|| name.to_string())
+}
+
+// ── Placeholders ─────────────────────────────────────────────────────────────
+
+/// Whether `value` is documentation filler rather than a real setting.
+fn is_placeholder(value: &str) -> bool {
+    let v = value.trim();
+    let lower = v.to_ascii_lowercase();
+    (v.starts_with('<') && v.ends_with('>') && v.len() > 2)
+        || lower.contains("your_")
+        || lower.contains("your-")
+        || lower.contains("<your")
+        || lower.contains("xxxx")
+        || lower.contains("replace_me")
+        || lower.contains("changeme")
+        || lower.contains("change_me")
+        || lower.starts_with("${input:")
+}
+
+/// Blank placeholder env values and report them (with already-empty ones)
+/// as required; report placeholders elsewhere by location.
+fn flag_placeholders(config: &mut Value) -> (Vec<String>, Vec<String>) {
+    let mut required_env = Vec::new();
+    let mut placeholders = Vec::new();
+    let Some(obj) = config.as_object_mut() else {
+        return (required_env, placeholders);
+    };
+
+    if let Some(env) = obj.get_mut("env").and_then(Value::as_object_mut) {
+        for (key, value) in env.iter_mut() {
+            let text = value.as_str().unwrap_or_default();
+            if text.is_empty() || is_placeholder(text) {
+                *value = Value::String(String::new());
+                required_env.push(key.clone());
+            }
+        }
+    }
+    for field in ["command", "url"] {
+        if obj
+            .get(field)
+            .and_then(Value::as_str)
+            .is_some_and(is_placeholder)
+        {
+            placeholders.push(field.to_string());
+        }
+    }
+    if let Some(args) = obj.get("args").and_then(Value::as_array) {
+        for (i, arg) in args.iter().enumerate() {
+            if arg.as_str().is_some_and(is_placeholder) {
+                placeholders.push(format!("args[{}]", i));
+            }
+        }
+    }
+    if let Some(headers) = obj.get("headers").and_then(Value::as_object) {
+        for (key, value) in headers {
+            if value.as_str().is_some_and(is_placeholder) {
+                placeholders.push(format!("headers.{}", key));
+            }
+        }
+    }
+    (required_env, placeholders)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(raw: &str) -> Vec<McpSnippetCandidate> {
+        parse_snippet_candidates(raw, &[]).unwrap()
+    }
+
+    fn names(candidates: &[McpSnippetCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn fenced_claude_desktop_snippet_with_prose() {
+        let raw = r#"## Installation
+
+Add this to your `claude_desktop_config.json`:
+
+```json
+{
+  "mcpServers": {
+    "github": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-github"],
+      "env": {
+        "GITHUB_PERSONAL_ACCESS_TOKEN": "<YOUR_TOKEN>"
+      }
+    }
+  }
+}
+```
+
+Then restart Claude Desktop.
+"#;
+        let candidates = parse(raw);
+        assert_eq!(names(&candidates), vec!["github"]);
+        let github = &candidates[0];
+        assert_eq!(
+            github.config,
+            json!({
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-github"],
+                "env": { "GITHUB_PERSONAL_ACCESS_TOKEN": "" }
+            })
+        );
+        assert_eq!(github.required_env, vec!["GITHUB_PERSONAL_ACCESS_TOKEN"]);
+        assert!(github.placeholders.is_empty());
+        assert!(!github.exists);
+    }
+
+    #[test]
+    fn several_servers_and_other_agents_shapes() {
+        // Unfenced, with a trailing comma and a comment (JSONC).
+        let raw = r#"Cursor users can paste this into .cursor/mcp.json:
+{
+  "mcpServers": {
+    // Filesystem access
+    "filesystem": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem", "/path/to/dir"]},
+    "linear": {"url": "https://mcp.linear.app/sse", "headers": {"Authorization": "Bearer YOUR_API_KEY"}},
+    "automatic": {"command": "automatic", "args": ["mcp-serve"]},
+  }
+}"#;
+        let candidates = parse(raw);
+        assert_eq!(names(&candidates), vec!["filesystem", "linear"]);
+        assert_eq!(candidates[1].config["type"], "http");
+        assert_eq!(candidates[1].placeholders, vec!["headers.Authorization"]);
+
+        // OpenCode's `mcp` with a command array, and VS Code's nested servers.
+        let opencode = r#"{"mcp": {"db": {"type": "local", "command": ["db-mcp", "--ro"], "environment": {"DB_URL": ""}}}}"#;
+        let db = &parse(opencode)[0];
+        assert_eq!(db.name, "db");
+        assert_eq!(
+            db.config,
+            json!({"type": "stdio", "command": "db-mcp", "args": ["--ro"], "env": {"DB_URL": ""}})
+        );
+        assert_eq!(db.required_env, vec!["DB_URL"]);
+
+        let vscode = r#"{"mcp": {"servers": {"sentry": {"type": "http", "url": "https://mcp.sentry.dev/mcp"}}}}"#;
+        assert_eq!(names(&parse(vscode)), vec!["sentry"]);
+    }
+
+    #[test]
+    fn bare_definitions_get_inferred_names() {
+        let raw = "Or run it directly:\n\n```\n{\"command\": \"uvx\", \"args\": [\"mcp-server-fetch\"]}\n```\n";
+        let fetch = parse(raw);
+        assert_eq!(names(&fetch), vec!["fetch"]);
+
+        let fragment = r#""postgres": {"command": "docker", "args": ["run", "-i", "--rm", "mcp/postgres", "postgresql://host/db"]}"#;
+        assert_eq!(names(&parse(fragment)), vec!["postgres"]);
+
+        let remote = r#"{"serverUrl": "https://mcp.notion.com/mcp"}"#;
+        let notion = &parse(remote)[0];
+        assert_eq!(notion.name, "notion");
+        assert_eq!(notion.config["url"], "https://mcp.notion.com/mcp");
+
+        let map = r#"{"a": {"command": "a-mcp"}, "b": {"command": "b-mcp"}}"#;
+        assert_eq!(names(&parse(map)), vec!["a", "b"]);
+
+        let twice = "```json\n{\"command\": \"npx\", \"args\": [\"-y\", \"brave-search-mcp\"]}\n```\n\
+                     ```json\n{\"command\": \"npx\", \"args\": [\"-y\", \"brave-search-mcp@2\"]}\n```\n";
+        assert_eq!(names(&parse(twice)), vec!["brave-search", "brave-search-2"]);
+
+        assert!(parse_snippet_candidates("No JSON here. {not json}", &[]).is_err());
+    }
+
+    #[test]
+    fn selection_applies_overrides_and_guards_existing_names() {
+        let raw = r#"{"mcpServers": {"github": {"command": "gh-mcp", "env": {"TOKEN": "YOUR_TOKEN"}}, "fetch": {"command": "fetch-mcp"}}}"#;
+        let existing = vec!["fetch".to_string()];
+        let candidates = parse_snippet_candidates(raw, &existing).unwrap();
+        let exists = |name: &str| candidates.iter().find(|c| c.name == name).unwrap().exists;
+        assert!(!exists("github"));
+        assert!(exists("fetch"));
+
+        // Everything by default — which refuses to replace `fetch`.
+        let err = select_candidates(&candidates, &[], &existing).unwrap_err();
+        assert!(err.contains("'fetch' already exists"), "{}", err);
+
+        let selected = select_candidates(
+            &candidates,
+            &[
+                McpSnippetOverride {
+                    name: "github".into(),
+                    save_as: Some("gh".into()),
+                    env: BTreeMap::from([("TOKEN".to_string(), "ghp_123".to_string())]),
+                    ..Default::default()
+                },
+                McpSnippetOverride {
+                    name: "fetch".into(),
+                    overwrite: true,
+                    ..Default::default()
+                },
+            ],
+            &existing,
+        )
+        .unwrap();
+        assert_eq!(selected[0].0, "gh");
+        assert_eq!(selected[0].1["env"]["TOKEN"], "ghp_123");
+        assert_eq!(selected[1].0, "fetch");
+
+        let unknown = McpSnippetOverride {
+            name: "nope".into(),
+            ..Default::default()
+        };
+        assert!(select_candidates(&candidates, &[unknown], &existing).is_err());
+    }
+}
@@ -0,0 +1,428 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use super::*;
+
+// ── MCP Server Test ──────────────────────────────────────────────────────────
+//
+// A typo'd command in a saved server config only shows up when an agent fails
+// to start the server.  The server editor can check a config first: stdio
+// servers are started and taken through the MCP `initialize` handshake and a
+// `tools/list` call; `url` servers get an HTTP(S) reachability check.
+
+/// How long a stdio server gets to answer `initialize` and `tools/list`.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(15);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// Why a server config failed its test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpTestFailure {
+    /// Not a JSON object, or neither `command` nor `url` is set.
+    InvalidConfig,
+    /// `command` is not on PATH and is not an existing file.
+    CommandNotFound,
+    /// The process could not be started.
+    SpawnFailed,
+    /// The server did not answer within the time limit.
+    HandshakeTimeout,
+    /// The server answered with something other than a valid MCP response,
+    /// or exited before finishing the handshake.
+    ProtocolError,
+    /// The `url` could not be reached.
+    Unreachable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct McpTestResult {
+    pub ok: bool,
+    /// `"stdio"` or `"http"`.
+    pub transport: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_count: Option<usize>,
+    /// HTTP status of the reachability check for `url` servers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<McpTestFailure>,
+    /// Human-readable detail for a failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl McpTestResult {
+    fn passed(transport: &str) -> Self {
+        Self {
+            ok: true,
+            transport: transport.to_string(),
+            server_name: None,
+            server_version: None,
+            tool_count: None,
+            http_status: None,
+            failure: None,
+            message: None,
+        }
+    }
+
+    fn failed(transport: &str, failure: McpTestFailure, message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            failure: Some(failure),
+            message: Some(message.into()),
+            ..Self::passed(transport)
+        }
+    }
+}
+
+/// Test the MCP server config `data` (the JSON stored in the registry).
+pub async fn test_mcp_server_config(data: &str) -> McpTestResult {
+    let mut config: Value = match serde_json::from_str(data) {
+        Ok(config @ Value::Object(_)) => config,
+        Ok(_) => {
+            return McpTestResult::failed(
+                "stdio",
+                McpTestFailure::InvalidConfig,
+                "Server config must be a JSON object",
+            )
+        }
+        Err(e) => {
+            return McpTestResult::failed(
+                "stdio",
+                McpTestFailure::InvalidConfig,
+                format!("Invalid JSON: {}", e),
+            )
+        }
+    };
+
+    let missing = resolve_keychain_placeholders(&mut config);
+    if !missing.is_empty() {
+        return McpTestResult::failed(
+            "stdio",
+            McpTestFailure::InvalidConfig,
+            format!("No keychain entry for {}", missing.join(", ")),
+        );
+    }
+
+    if let Some(url) = config.get("url").and_then(|u| u.as_str()) {
+        return check_reachable(url).await;
+    }
+    match tokio::task::spawn_blocking(move || test_stdio_server(&config, HANDSHAKE_TIMEOUT)).await {
+        Ok(result) => result,
+        Err(e) => McpTestResult::failed("stdio", McpTestFailure::SpawnFailed, e.to_string()),
+    }
+}
+
+/// Any HTTP response counts as reachable: servers commonly answer a bare GET
+/// with 401 or 405.
+async fn check_reachable(url: &str) -> McpTestResult {
+    let client = match reqwest::Client::builder().timeout(HTTP_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            return McpTestResult::failed(
+                "http",
+                McpTestFailure::Unreachable,
+                format!("HTTP client error: {}", e),
+            )
+        }
+    };
+    match client.get(url).send().await {
+        Ok(resp) => McpTestResult {
+            http_status: Some(resp.status().as_u16()),
+            ..McpTestResult::passed("http")
+        },
+        Err(e) if e.is_timeout() => McpTestResult::failed(
+            "http",
+            McpTestFailure::HandshakeTimeout,
+            format!(
+                "No response from {} within {}s",
+                url,
+                HTTP_TIMEOUT.as_secs()
+            ),
+        ),
+        Err(e) => McpTestResult::failed(
+            "http",
+            McpTestFailure::Unreachable,
+            format!("Could not reach {}: {}", url, e),
+        ),
+    }
+}
+
+/// Where `command` runs from: itself when it names a path, else the first
+/// match on PATH.
+fn resolve_command(command: &str) -> Option<PathBuf> {
+    let path = Path::new(command);
+    if path.components().count() > 1 || path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    let extensions: &[&str] = if cfg!(windows) {
+        &["", ".exe", ".cmd", ".bat"]
+    } else {
+        &[""]
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?).find_map(|dir| {
+        extensions
+            .iter()
+            .map(|ext| dir.join(format!("{}{}", command, ext)))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+fn test_stdio_server(config: &Value, timeout: Duration) -> McpTestResult {
+    let Some(command) = config.get("command").and_then(|c| c.as_str()) else {
+        return McpTestResult::failed(
+            "stdio",
+            McpTestFailure::InvalidConfig,
+            "Server config has neither a `command` nor a `url`",
+        );
+    };
+    let Some(program) = resolve_command(command) else {
+        return McpTestResult::failed(
+            "stdio",
+            McpTestFailure::CommandNotFound,
+            format!("'{}' was not found on PATH", command),
+        );
+    };
+
+    let mut cmd = Command::new(program);
+    if let Some(args) = config.get("args").and_then(|a| a.as_array()) {
+        cmd.args(args.iter().filter_map(|a| a.as_str()));
+    }
+    // Empty values are inherited from the environment at runtime, as the
+    // agents do once sync writes them as `${KEY}`.
+    if let Some(env) = config.get("env").and_then(|e| e.as_object()) {
+        for (key, value) in env {
+            if let Some(value) = value.as_str().filter(|v| !v.is_empty()) {
+                cmd.env(key, value);
+            }
+        }
+    }
+    if let Some(cwd) = config.get("cwd").and_then(|c| c.as_str()) {
+        cmd.current_dir(cwd);
+    }
+    let mut child = match cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            return McpTestResult::failed(
+                "stdio",
+                McpTestFailure::SpawnFailed,
+                format!("Could not start '{}': {}", command, e),
+            )
+        }
+    };
+
+    let result = handshake(&mut child, timeout);
+    let _ = child.kill();
+    let _ = child.wait();
+    result.unwrap_or_else(|(failure, message)| McpTestResult::failed("stdio", failure, message))
+}
+
+fn handshake(
+    child: &mut Child,
+    timeout: Duration,
+) -> Result<McpTestResult, (McpTestFailure, String)> {
+    let deadline = Instant::now() + timeout;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let stdout = child.stdout.take().expect("stdout is piped");
+
+    // Lines are read on their own thread so the wait can time out.
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    send(
+        &mut stdin,
+        &json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": { "name": "automatic", "version": APP_VERSION },
+            },
+        }),
+    )?;
+    let init = await_response(&rx, 1, deadline, "initialize")?;
+    let info = init.get("serverInfo");
+    let text = |field: &str| {
+        info.and_then(|i| i.get(field))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    let (server_name, server_version) = (text("name"), text("version"));
+
+    send(
+        &mut stdin,
+        &json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    )?;
+    let has_tools = init
+        .get("capabilities")
+        .is_some_and(|c| c.get("tools").is_some());
+    let tool_count = if has_tools {
+        send(
+            &mut stdin,
+            &json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        )?;
+        let tools = await_response(&rx, 2, deadline, "tools/list")?;
+        tools
+            .get("tools")
+            .and_then(|t| t.as_array())
+            .map_or(0, Vec::len)
+    } else {
+        0
+    };
+
+    Ok(McpTestResult {
+        server_name,
+        server_version,
+        tool_count: Some(tool_count),
+        ..McpTestResult::passed("stdio")
+    })
+}
+
+fn send(stdin: &mut ChildStdin, message: &Value) -> Result<(), (McpTestFailure, String)> {
+    writeln!(stdin, "{}", message)
+        .and_then(|_| stdin.flush())
+        .map_err(|e| {
+            (
+                McpTestFailure::ProtocolError,
+                format!("Server closed its input: {}", e),
+            )
+        })
+}
+
+/// The `result` of the response with `id`.  Notifications, requests from the
+/// server and lines that are not JSON (stray logging) are skipped.
+fn await_response(
+    rx: &mpsc::Receiver<String>,
+    id: u64,
+    deadline: Instant,
+    method: &str,
+) -> Result<Value, (McpTestFailure, String)> {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let line = match rx.recv_timeout(remaining) {
+            Ok(line) => line,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                return Err((
+                    McpTestFailure::HandshakeTimeout,
+                    format!("Server did not answer `{}` in time", method),
+                ))
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err((
+                    McpTestFailure::ProtocolError,
+                    format!("Server exited before answering `{}`", method),
+                ))
+            }
+        };
+        let Ok(message) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if message.get("id").and_then(|i| i.as_u64()) != Some(id) || message.get("method").is_some()
+        {
+            continue;
+        }
+        if let Some(error) = message.get("error") {
+            let detail = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("unknown error");
+            return Err((
+                McpTestFailure::ProtocolError,
+                format!("`{}` failed: {}", method, detail),
+            ));
+        }
+        return message.get("result").cloned().ok_or_else(|| {
+            (
+                McpTestFailure::ProtocolError,
+                format!("`{}` response has no result", method),
+            )
+        });
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INIT_REPLY: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{"tools":{}},"serverInfo":{"name":"fake","version":"1.2.3"}}}"#;
+    const TOOLS_REPLY: &str =
+        r#"{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"a"},{"name":"b"}]}}"#;
+
+    /// A config running `script` with `sh`.
+    fn sh(script: &str) -> Value {
+        json!({ "command": "sh", "args": ["-c", script] })
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stdio_server_is_taken_through_the_handshake() {
+        let script = format!(
+            "read l; echo 'starting up'; echo '{}'; read l; read l; echo '{}'; sleep 5",
+            INIT_REPLY, TOOLS_REPLY
+        );
+        let result = test_stdio_server(&sh(&script), Duration::from_secs(5));
+        assert!(result.ok, "{:?}", result.message);
+        assert_eq!(result.server_name.as_deref(), Some("fake"));
+        assert_eq!(result.server_version.as_deref(), Some("1.2.3"));
+        assert_eq!(result.tool_count, Some(2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn handshake_failures_are_classified() {
+        let silent = test_stdio_server(&sh("sleep 5"), Duration::from_millis(200));
+        assert_eq!(silent.failure, Some(McpTestFailure::HandshakeTimeout));
+
+        let exits = test_stdio_server(&sh("read l; exit 1"), Duration::from_secs(5));
+        assert_eq!(exits.failure, Some(McpTestFailure::ProtocolError));
+
+        let error = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"bad version"}}"#;
+        let refused = test_stdio_server(
+            &sh(&format!("read l; echo '{}'", error)),
+            Duration::from_secs(5),
+        );
+        assert_eq!(refused.failure, Some(McpTestFailure::ProtocolError));
+        assert!(refused.message.unwrap().contains("bad version"));
+    }
+
+    #[test]
+    fn missing_commands_and_bad_configs_are_reported() {
+        let missing = test_stdio_server(
+            &json!({ "command": "automatic-no-such-command" }),
+            Duration::from_secs(1),
+        );
+        assert_eq!(missing.failure, Some(McpTestFailure::CommandNotFound));
+
+        let no_command = test_stdio_server(&json!({ "args": [] }), Duration::from_secs(1));
+        assert_eq!(no_command.failure, Some(McpTestFailure::InvalidConfig));
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let not_json = rt.block_on(test_mcp_server_config("{ nope"));
+        assert_eq!(not_json.failure, Some(McpTestFailure::InvalidConfig));
+    }
+}
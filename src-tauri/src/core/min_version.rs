@@ -0,0 +1,220 @@
+use semver::Version;
+use serde::Serialize;
+
+use super::*;
+
+// ── Minimum App Version ──────────────────────────────────────────────────────
+//
+// Projects and templates record the oldest Automatic build that understands
+// everything in them.  Serde drops fields it does not know, so a build older
+// than that would silently lose settings the next time it saved.  A newer
+// patch or pre-release only earns a warning; a newer major or minor release
+// makes the project read-only here until the app is updated.
+
+/// Version of the running app.
+pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// How a recorded `min_app_version` relates to the running app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum VersionCompat {
+    Compatible,
+    /// Written by a slightly newer build: usable, but settings it added may
+    /// be ignored.
+    Newer {
+        required: String,
+        message: String,
+    },
+    /// Written by a newer release: read-only until the app is updated.
+    UpdateRequired {
+        required: String,
+        message: String,
+    },
+}
+
+impl VersionCompat {
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, Self::UpdateRequired { .. })
+    }
+
+    /// Text for the notification shown to the user, if any.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Self::Compatible => None,
+            Self::Newer { message, .. } | Self::UpdateRequired { message, .. } => Some(message),
+        }
+    }
+}
+
+/// Compare `required` (a recorded `min_app_version`) with the running app.
+/// `subject` names the item in messages, e.g. `"Project 'web'"`.
+pub fn version_compat(subject: &str, required: Option<&str>) -> VersionCompat {
+    version_compat_with(subject, required, APP_VERSION)
+}
+
+fn version_compat_with(subject: &str, required: Option<&str>, running: &str) -> VersionCompat {
+    let Some(required) = required.map(str::trim).filter(|r| !r.is_empty()) else {
+        return VersionCompat::Compatible;
+    };
+    let (Ok(req), Ok(run)) = (Version::parse(required), Version::parse(running)) else {
+        return VersionCompat::Newer {
+            required: required.to_string(),
+            message: format!(
+                "{} declares an unrecognised minimum Automatic version '{}'.",
+                subject, required
+            ),
+        };
+    };
+    if req <= run {
+        VersionCompat::Compatible
+    } else if (req.major, req.minor) == (run.major, run.minor) {
+        VersionCompat::Newer {
+            required: required.to_string(),
+            message: format!(
+                "{} was saved by Automatic {} (this is {}). Some of its settings may be ignored until you update.",
+                subject, req, run
+            ),
+        }
+    } else {
+        VersionCompat::UpdateRequired {
+            required: required.to_string(),
+            message: format!(
+                "{} needs Automatic {} or newer (this is {}). It is read-only until you update Automatic.",
+                subject, req, run
+            ),
+        }
+    }
+}
+
+/// Refuse changes to a project recorded as needing a newer app.
+pub fn check_project_writable(project: &Project) -> Result<(), String> {
+    let subject = format!("Project '{}'", project.name);
+    match version_compat(&subject, project.min_app_version.as_deref()) {
+        VersionCompat::UpdateRequired { message, .. } => Err(message),
+        _ => Ok(()),
+    }
+}
+
+/// Raise `min` to the running version when the item uses settings older
+/// builds would drop.  Never lowers it.
+pub(crate) fn stamp_min_app_version(min: &mut Option<String>, uses_new_settings: bool) {
+    if !uses_new_settings {
+        return;
+    }
+    let running = Version::parse(APP_VERSION).ok();
+    let recorded = min.as_deref().and_then(|v| Version::parse(v).ok());
+    let raise = match (recorded, running) {
+        (Some(recorded), Some(running)) => running > recorded,
+        _ => true,
+    };
+    if raise {
+        *min = Some(APP_VERSION.to_string());
+    }
+}
+
+/// Whether `project` uses settings builds before this one would drop on save.
+pub(crate) fn project_uses_new_settings(project: &Project) -> bool {
+    project.locked
+        || !project.skill_hashes.is_empty()
+        || !project.skill_conflicts.is_empty()
+        || !project.preserved_skills.is_empty()
+        || !project.mcp_server_origins.is_empty()
+        || !project.mcp_server_overrides.is_empty()
+        || !project.disabled_agents.is_empty()
+        || !project.rule_candidates.is_empty()
+        || !project.stores_config_in_repo()
+        || project.agent_options.values().any(|o| {
+            o.gemini_use_extension || o.automatic_mcp_read_only || !o.claude_mcp_scopes.is_empty()
+        })
+}
+
+/// Whether `template` uses settings builds before this one would drop.
+pub(crate) fn template_uses_new_settings(template: &ProjectTemplate) -> bool {
+    template.imported_from.is_some()
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_BUILD: &str = "1.0.0-beta.3";
+
+    #[test]
+    fn compares_against_the_running_version() {
+        let check = |required| version_compat_with("Project 'web'", required, OLD_BUILD);
+        assert_eq!(check(None), VersionCompat::Compatible);
+        assert_eq!(check(Some("")), VersionCompat::Compatible);
+        assert_eq!(check(Some("0.9.0")), VersionCompat::Compatible);
+        assert_eq!(check(Some(OLD_BUILD)), VersionCompat::Compatible);
+
+        let newer = check(Some("1.0.0-beta.5"));
+        assert!(matches!(newer, VersionCompat::Newer { .. }));
+        assert!(!newer.is_read_only());
+        assert!(check(Some("1.0.2")).message().unwrap().contains("1.0.2"));
+
+        let major = check(Some("1.1.0"));
+        assert!(major.is_read_only());
+        assert!(major.message().unwrap().contains("update Automatic"));
+        assert!(check(Some("2.0.0")).is_read_only());
+
+        assert!(!check(Some("next")).is_read_only());
+    }
+
+    #[test]
+    fn older_build_opening_a_newer_project_is_read_only() {
+        // A project.json written by a future release, including a field this
+        // build does not know about.
+        let fixture = r#"{
+            "name": "web",
+            "directory": "/work/web",
+            "agents": ["claude"],
+            "min_app_version": "99.0.0",
+            "future_setting": {"enabled": true}
+        }"#;
+        let project: Project = serde_json::from_str(fixture).unwrap();
+        assert_eq!(project.min_app_version.as_deref(), Some("99.0.0"));
+
+        let compat = version_compat("Project 'web'", project.min_app_version.as_deref());
+        assert!(compat.is_read_only());
+        let err = project.ensure_unlocked().unwrap_err();
+        assert!(err.contains("99.0.0"), "{}", err);
+        assert!(check_project_writable(&project).is_err());
+    }
+
+    #[test]
+    fn stamping_only_raises_the_recorded_version() {
+        let mut min = None;
+        stamp_min_app_version(&mut min, false);
+        assert_eq!(min, None);
+        stamp_min_app_version(&mut min, true);
+        assert_eq!(min.as_deref(), Some(APP_VERSION));
+
+        let mut newer = Some("99.0.0".to_string());
+        stamp_min_app_version(&mut newer, true);
+        assert_eq!(newer.as_deref(), Some("99.0.0"));
+
+        let mut older = Some("0.1.0".to_string());
+        stamp_min_app_version(&mut older, true);
+        assert_eq!(older.as_deref(), Some(APP_VERSION));
+    }
+
+    #[test]
+    fn new_settings_are_detected() {
+        let mut project = Project::default();
+        assert!(!project_uses_new_settings(&project));
+        project
+            .agent_options
+            .entry("claude".into())
+            .or_default()
+            .claude_mcp_scopes
+            .insert("db".into(), ClaudeMcpScope::Local);
+        assert!(project_uses_new_settings(&project));
+
+        let mut template = ProjectTemplate::default();
+        assert!(!template_uses_new_settings(&template));
+        template.imported_from = Some(BundledTemplateOrigin::default());
+        assert!(template_uses_new_settings(&template));
+    }
+}
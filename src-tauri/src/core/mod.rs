@@ -8,29 +8,61 @@ pub const KEYCHAIN_SERVICE: &str = "automatic_desktop";
 
 pub mod ai;
 mod app_plugins;
+mod app_updates;
 mod author;
+mod backup;
+mod bulk_projects;
 mod commands;
 mod credentials;
+mod data_dir;
 mod editors;
 mod env_crypto;
 mod flags;
+mod fsutil;
 mod groups;
 mod integrations;
+mod log;
 mod marketplace;
 mod marketplace_data;
+mod mcp_budget;
+mod mcp_config;
+mod mcp_secrets;
 mod mcp_servers;
+mod mcp_snippet;
+mod mcp_test;
+mod min_version;
 mod paths;
 mod plugins;
 mod profile;
+mod project_adopt;
+mod project_checklist;
+mod project_duplicate;
 mod project_files;
 mod project_templates;
 mod projects;
+mod provenance;
+mod remote_templates;
 mod rules;
 mod rules_injection;
+mod self_mcp;
+mod sessions;
 mod settings;
+mod skill_bundle;
+mod skill_copy;
+mod skill_licenses;
+mod skill_meta;
+mod skill_preview;
+mod skill_requirements;
+mod skill_sets;
 mod skill_store;
+mod skill_updates;
 mod skills;
+mod stable_json;
+mod sync_history;
 pub mod task_log;
+mod template_apply;
+mod template_catalog;
+mod template_render;
 mod templates;
 pub mod tools;
 mod types;
@@ -38,27 +70,59 @@ mod user_agents;
 mod whats_new;
 
 pub use app_plugins::*;
+pub use app_updates::*;
 pub use author::*;
+pub use backup::*;
+pub use bulk_projects::*;
 pub use commands::*;
 pub use credentials::*;
+pub use data_dir::*;
 pub use editors::*;
 pub use flags::*;
+pub use fsutil::*;
 pub use groups::*;
 pub use integrations::*;
+pub use log::*;
 pub use marketplace::*;
 pub use marketplace_data::init_marketplace_files;
+pub use mcp_budget::*;
+pub use mcp_config::*;
+pub use mcp_secrets::*;
 pub use mcp_servers::*;
+pub use mcp_snippet::*;
+pub use mcp_test::*;
+pub use min_version::*;
 pub use paths::*;
 pub use plugins::*;
 pub use profile::*;
+pub use project_adopt::*;
+pub use project_checklist::*;
+pub use project_duplicate::*;
 pub use project_files::*;
 pub use project_templates::*;
 pub use projects::*;
+pub use provenance::*;
+pub use remote_templates::*;
 pub use rules::*;
 pub use rules_injection::*;
+pub use self_mcp::*;
+pub use sessions::*;
 pub use settings::*;
+pub use skill_bundle::*;
+pub use skill_copy::*;
+pub use skill_licenses::*;
+pub use skill_meta::*;
+pub use skill_preview::*;
+pub use skill_requirements::*;
+pub use skill_sets::*;
 pub use skill_store::*;
+pub use skill_updates::*;
 pub use skills::*;
+pub use stable_json::*;
+pub use sync_history::*;
+pub use template_apply::*;
+pub use template_catalog::*;
+pub use template_render::*;
 pub use templates::*;
 pub use tools::*;
 pub use types::*;
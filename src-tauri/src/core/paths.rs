@@ -1,21 +1,137 @@
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+// ── Data Root ────────────────────────────────────────────────────────────────
+//
+// Everything Automatic keeps outside project directories lives under one
+// root.  It is, in order of precedence:
+//
+// 1. `$AUTOMATIC_DATA_DIR`, when set;
+// 2. the directory recorded in the bootstrap file by `migrate_data_dir`
+//    (`<config dir>/automatic/data_dir.json`);
+// 3. on Linux, `$XDG_DATA_HOME/automatic`, when `XDG_DATA_HOME` is set and no
+//    `~/.automatic` already exists, or when that directory already exists;
+// 4. `~/.automatic`.
+//
+// Debug builds (`cargo tauri dev`, `cargo test`, etc.) use `automatic-dev` in
+// place of `automatic` throughout, so dev and production data are always
+// kept separate.  The MCP server and CLI subcommands resolve the root the
+// same way, so they find the data the app wrote.
+
+/// Environment variable that overrides the data root.
+pub const DATA_DIR_ENV: &str = "AUTOMATIC_DATA_DIR";
+
+#[cfg(debug_assertions)]
+const DATA_DIR_NAME: &str = "automatic-dev";
+#[cfg(not(debug_assertions))]
+const DATA_DIR_NAME: &str = "automatic";
+
+/// On-disk shape of the bootstrap file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DataDirBootstrap {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data_dir: Option<PathBuf>,
+}
+
+fn data_root_cache() -> &'static Mutex<Option<PathBuf>> {
+    static CACHE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+/// Returns the root Automatic data directory.  Resolved once per process;
+/// [`set_data_root_override`] replaces it.
+pub fn data_root() -> Result<PathBuf, String> {
+    let mut cache = data_root_cache().lock().map_err(|e| e.to_string())?;
+    if let Some(root) = cache.as_ref() {
+        return Ok(root.clone());
+    }
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    let xdg_data_home = if cfg!(target_os = "linux") {
+        std::env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+    } else {
+        None
+    };
+    let root = resolve_data_root(
+        std::env::var_os(DATA_DIR_ENV).map(PathBuf::from),
+        read_bootstrap_data_dir(),
+        xdg_data_home,
+        &home,
+    );
+    *cache = Some(root.clone());
+    Ok(root)
+}
+
+/// Whether the data root is pinned by `$AUTOMATIC_DATA_DIR`.
+pub fn data_root_from_env() -> bool {
+    std::env::var_os(DATA_DIR_ENV).is_some_and(|v| !v.is_empty())
+}
+
+fn resolve_data_root(
+    env_override: Option<PathBuf>,
+    bootstrap: Option<PathBuf>,
+    xdg_data_home: Option<PathBuf>,
+    home: &Path,
+) -> PathBuf {
+    let absolute =
+        |path: Option<PathBuf>| path.map(|p| expand_home(&p)).filter(|p| p.is_absolute());
+    if let Some(dir) = absolute(env_override) {
+        return dir;
+    }
+    if let Some(dir) = absolute(bootstrap) {
+        return dir;
+    }
+    let legacy = home.join(format!(".{}", DATA_DIR_NAME));
+    // XDG requires an absolute path; a relative one is ignored.
+    if let Some(xdg) = xdg_data_home.filter(|p| p.is_absolute()) {
+        let dir = xdg.join(DATA_DIR_NAME);
+        if dir.is_dir() || !legacy.exists() {
+            return dir;
+        }
+    }
+    legacy
+}
+
+/// The bootstrap file recording a data root chosen by the user.  Kept in the
+/// platform config directory, outside the data root it points to.
+pub fn bootstrap_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(DATA_DIR_NAME).join("data_dir.json"))
+}
+
+fn read_bootstrap_data_dir() -> Option<PathBuf> {
+    let raw = fs::read_to_string(bootstrap_file_path()?).ok()?;
+    match serde_json::from_str::<DataDirBootstrap>(&raw) {
+        Ok(bootstrap) => bootstrap.data_dir,
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable data directory bootstrap file: {}", e);
+            None
+        }
+    }
+}
+
+/// Record `dir` as the data root in the bootstrap file and use it for the
+/// rest of this process.
+pub(crate) fn set_data_root_override(dir: &Path) -> Result<(), String> {
+    let path = bootstrap_file_path().ok_or("Could not find the config directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let bootstrap = DataDirBootstrap {
+        data_dir: Some(dir.to_path_buf()),
+    };
+    let raw = serde_json::to_string_pretty(&bootstrap).map_err(|e| e.to_string())?;
+    super::write_atomic(&path, raw)?;
+    *data_root_cache().lock().map_err(|e| e.to_string())? = Some(dir.to_path_buf());
+    Ok(())
+}
 
 // ── Path Helpers ─────────────────────────────────────────────────────────────
 
-/// Returns the root Automatic data directory.
-///
-/// - **Debug builds** (`cargo tauri dev`, `cargo test`, etc.): `~/.automatic-dev`
-/// - **Release builds**: `~/.automatic`
-///
-/// All other path helpers call this function so that dev and production data
-/// are always kept separate.
+/// Returns the root Automatic data directory (see [`data_root`]).  All other
+/// path helpers call this function.
 pub fn get_automatic_dir() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    #[cfg(debug_assertions)]
-    let dir = home.join(".automatic-dev");
-    #[cfg(not(debug_assertions))]
-    let dir = home.join(".automatic");
-    Ok(dir)
+    data_root()
 }
 
 /// Primary skills directory — the agentskills.io standard location.
@@ -30,10 +146,34 @@ pub fn get_claude_skills_dir() -> Result<PathBuf, String> {
     Ok(home.join(".claude/skills"))
 }
 
+/// Skills imported with the `"library"` install scope.  Kept under the data
+/// directory, out of every agent's global skills path, and only copied into
+/// the projects that select them.
+pub fn get_skill_library_dir() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("skill_library"))
+}
+
+/// Directory for disposable cached data, under the platform cache directory
+/// (`~/Library/Caches` on macOS, `$XDG_CACHE_HOME` on Linux).  Separate for
+/// debug builds like `get_automatic_dir`; falls back to a `cache/` folder
+/// inside it when the platform has no cache directory.
+pub fn get_cache_dir() -> Result<PathBuf, String> {
+    match dirs::cache_dir() {
+        Some(cache) => Ok(cache.join(DATA_DIR_NAME)),
+        None => Ok(get_automatic_dir()?.join("cache")),
+    }
+}
+
 pub fn get_projects_dir() -> Result<PathBuf, String> {
     Ok(get_automatic_dir()?.join("projects"))
 }
 
+/// Per-project data (instruction snapshots, notes) for projects that keep
+/// their config out of the repository.
+pub fn get_projects_data_dir() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("projects-data"))
+}
+
 pub fn get_commands_dir() -> Result<PathBuf, String> {
     Ok(get_automatic_dir()?.join("commands"))
 }
@@ -42,6 +182,201 @@ pub fn get_groups_dir() -> Result<PathBuf, String> {
     Ok(get_automatic_dir()?.join("groups"))
 }
 
+pub fn get_skill_sets_dir() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("skill_sets"))
+}
+
 pub fn is_valid_name(name: &str) -> bool {
     !name.is_empty() && !name.contains('/') && !name.contains('\\') && name != "." && name != ".."
 }
+
+// ── Path Comparison ──────────────────────────────────────────────────────────
+//
+// Project directories are typed, pasted and picked by the user, so the same
+// directory can arrive as `~/Code/MyApp`, `/Users/me/code/myapp/` or through
+// a symlink.  Compare them by `canonical_key`, never as raw strings; keep the
+// user's spelling for display.
+
+/// macOS (APFS/HFS+) and Windows (NTFS) filesystems ignore case by default.
+const CASE_INSENSITIVE_PATHS: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+/// Comparison key for `path`: `~` expanded, made absolute, symlinks resolved
+/// and case folded on platforms whose filesystems ignore case.  A path that
+/// does not exist yet is resolved through its nearest existing ancestor.
+pub fn canonical_key(path: impl AsRef<Path>) -> String {
+    path_key(path.as_ref(), CASE_INSENSITIVE_PATHS)
+}
+
+/// Whether `a` and `b` name the same location (see [`canonical_key`]).
+pub fn same_path(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
+    canonical_key(a) == canonical_key(b)
+}
+
+/// `text` folded the way the platform's filesystems compare path names.
+pub fn fold_path_case(text: &str) -> String {
+    if CASE_INSENSITIVE_PATHS {
+        text.to_lowercase()
+    } else {
+        text.to_string()
+    }
+}
+
+fn path_key(path: &Path, fold_case: bool) -> String {
+    let mut key = resolve_path(&expand_home(path)).display().to_string();
+    // Windows canonical paths carry a verbatim prefix lexical ones lack.
+    if let Some(rest) = key.strip_prefix(r"\\?\") {
+        key = rest.to_string();
+    }
+    while key.len() > 1 && (key.ends_with('/') || key.ends_with('\\')) {
+        key.pop();
+    }
+    if fold_case {
+        key = key.to_lowercase();
+    }
+    key
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Canonicalise the longest existing prefix of `path` and append the rest,
+/// resolving `.` and `..` lexically.
+fn resolve_path(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+    let components: Vec<Component> = absolute.components().collect();
+    for split in (1..=components.len()).rev() {
+        let prefix: PathBuf = components[..split].iter().collect();
+        if let Ok(mut resolved) = fs::canonicalize(&prefix) {
+            push_lexically(&mut resolved, &components[split..]);
+            return resolved;
+        }
+    }
+    let mut resolved = PathBuf::new();
+    push_lexically(&mut resolved, &components);
+    resolved
+}
+
+fn push_lexically(base: &mut PathBuf, components: &[Component]) {
+    for component in components {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                base.pop();
+            }
+            other => base.push(other.as_os_str()),
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn keys_ignore_spelling_of_the_same_directory() {
+        let dir = tempdir().unwrap();
+        let app = dir.path().join("MyApp");
+        fs::create_dir(&app).unwrap();
+
+        let key = path_key(&app, false);
+        assert_eq!(path_key(&dir.path().join("MyApp/"), false), key);
+        assert_eq!(path_key(&app.join("src/.."), false), key);
+        assert_eq!(path_key(&app.join("./"), false), key);
+        #[cfg(unix)]
+        {
+            let link = dir.path().join("link");
+            std::os::unix::fs::symlink(&app, &link).unwrap();
+            assert_eq!(path_key(&link, false), key);
+        }
+
+        // Not created yet: resolved through the existing parent.
+        assert_eq!(
+            path_key(&app.join("new/../new/child"), false),
+            Path::new(&key)
+                .join("new")
+                .join("child")
+                .display()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn case_is_folded_only_for_case_insensitive_filesystems() {
+        let dir = tempdir().unwrap();
+        let upper = dir.path().join("Code").join("MyApp");
+        let lower = dir.path().join("code").join("myapp");
+
+        assert_ne!(path_key(&upper, false), path_key(&lower, false));
+        assert_eq!(path_key(&upper, true), path_key(&lower, true));
+        assert_eq!(
+            same_path(&upper, &lower),
+            CASE_INSENSITIVE_PATHS,
+            "same_path follows the platform"
+        );
+        assert_eq!(fold_path_case("MyApp") == "myapp", CASE_INSENSITIVE_PATHS);
+    }
+
+    #[test]
+    fn data_root_precedence() {
+        let dir = tempdir().unwrap();
+        let home = dir.path().join("home");
+        let xdg = dir.path().join("xdg");
+        let legacy = home.join(format!(".{}", DATA_DIR_NAME));
+        let env = Some(dir.path().join("from-env"));
+        let bootstrap = Some(dir.path().join("from-bootstrap"));
+
+        assert_eq!(
+            resolve_data_root(env.clone(), bootstrap.clone(), Some(xdg.clone()), &home),
+            dir.path().join("from-env")
+        );
+        assert_eq!(
+            resolve_data_root(None, bootstrap.clone(), Some(xdg.clone()), &home),
+            dir.path().join("from-bootstrap")
+        );
+        // Relative overrides are ignored.
+        assert_eq!(
+            resolve_data_root(Some("rel".into()), None, None, &home),
+            legacy
+        );
+        // XDG is used for new installs, not over an existing ~/.automatic.
+        assert_eq!(
+            resolve_data_root(None, None, Some(xdg.clone()), &home),
+            xdg.join(DATA_DIR_NAME)
+        );
+        fs::create_dir_all(&legacy).unwrap();
+        assert_eq!(
+            resolve_data_root(None, None, Some(xdg.clone()), &home),
+            legacy
+        );
+        fs::create_dir_all(xdg.join(DATA_DIR_NAME)).unwrap();
+        assert_eq!(
+            resolve_data_root(None, None, Some(xdg.clone()), &home),
+            xdg.join(DATA_DIR_NAME)
+        );
+        assert_eq!(resolve_data_root(None, None, None, &home), legacy);
+    }
+
+    #[test]
+    fn home_is_expanded() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        assert_eq!(
+            canonical_key("~/no-such-automatic-dir"),
+            canonical_key(home.join("no-such-automatic-dir"))
+        );
+    }
+}
@@ -18,7 +18,13 @@ const MARKETPLACE_NAME: &str = "automatic-plugins";
 
 /// Current plugin version — bump when plugin content changes so Claude Code
 /// picks up updates via its cache.
-const PLUGIN_VERSION: &str = "0.1.0";
+const PLUGIN_VERSION: &str = "0.2.2";
+
+/// Replaced with the path of the running binary when the plugin is written.
+const AUTOMATIC_BIN_PLACEHOLDER: &str = "__AUTOMATIC_BIN__";
+
+/// Replaced with the data directory when the plugin is written.
+const AUTOMATIC_DATA_DIR_PLACEHOLDER: &str = "__AUTOMATIC_DATA_DIR__";
 
 // ── Plugin file contents ────────────────────────────────────────────────────
 
@@ -52,15 +58,12 @@ const HOOKS_JSON: &str = r#"
 const REGISTER_SESSION_SH: &str = r#"#!/usr/bin/env bash
 # register-session.sh — Called by the SessionStart hook.
 # Reads hook JSON from stdin, writes an entry to the Automatic sessions file.
-# Uses .automatic-dev in debug builds (detected via AUTOMATIC_DEV env var),
-# otherwise uses .automatic.
+# Uses $AUTOMATIC_DATA_DIR when set, otherwise the data directory of the
+# app that wrote this script.
 set -euo pipefail
 
-if [ "${AUTOMATIC_DEV:-0}" = "1" ]; then
-  SESSIONS_FILE="$HOME/.automatic-dev/sessions.json"
-else
-  SESSIONS_FILE="$HOME/.automatic/sessions.json"
-fi
+DATA_DIR="${AUTOMATIC_DATA_DIR:-__AUTOMATIC_DATA_DIR__}"
+SESSIONS_FILE="$DATA_DIR/sessions.json"
 
 # Read the full hook input from stdin
 INPUT=$(cat)
@@ -78,14 +81,24 @@ fi
 # Portable UTC timestamp
 TIMESTAMP=$(date -u +"%Y-%m-%dT%H:%M:%SZ")
 
+mkdir -p "$(dirname "$SESSIONS_FILE")"
+
+# Serialise with other writers (the app and other sessions) through the
+# sidecar lock the binary takes.  Without flock(1) (stock macOS) the
+# same-directory temp file + mv still keeps the file whole.
+exec 9>"$SESSIONS_FILE.lock"
+if command -v flock >/dev/null 2>&1; then
+  flock 9
+fi
+
 # Ensure the store file exists
 if [ ! -f "$SESSIONS_FILE" ]; then
-  mkdir -p "$(dirname "$SESSIONS_FILE")"
   echo '{}' > "$SESSIONS_FILE"
 fi
 
-# Add / update this session (atomic via temp file)
-TMPFILE=$(mktemp)
+# Add / update this session (atomic via a temp file next to the store, so
+# mv is a rename rather than a cross-filesystem copy)
+TMPFILE=$(mktemp "$SESSIONS_FILE.XXXXXX")
 jq --arg id "$SESSION_ID" \
    --arg cwd "$CWD" \
    --arg model "$MODEL" \
@@ -101,6 +114,12 @@ jq --arg id "$SESSION_ID" \
     }' \
    "$SESSIONS_FILE" > "$TMPFILE" && mv "$TMPFILE" "$SESSIONS_FILE"
 
+# Expose the session id to the Automatic MCP server and commands started in
+# this session, so session-scoped memories default to it.
+if [ -n "${CLAUDE_ENV_FILE:-}" ]; then
+  echo "export AUTOMATIC_SESSION_ID=$SESSION_ID" >> "$CLAUDE_ENV_FILE"
+fi
+
 # Prune stale sessions (started > 24 h ago).
 # macOS uses -v, GNU date uses -d.  Skip cleanup if neither works.
 CUTOFF=$(date -u -v-24H +"%Y-%m-%dT%H:%M:%SZ" 2>/dev/null \
@@ -108,7 +127,7 @@ CUTOFF=$(date -u -v-24H +"%Y-%m-%dT%H:%M:%SZ" 2>/dev/null \
       || echo "")
 
 if [ -n "$CUTOFF" ]; then
-  TMPFILE=$(mktemp)
+  TMPFILE=$(mktemp "$SESSIONS_FILE.XXXXXX")
   jq --arg cutoff "$CUTOFF" \
      'with_entries(select(.value.started_at >= $cutoff))' \
      "$SESSIONS_FILE" > "$TMPFILE" && mv "$TMPFILE" "$SESSIONS_FILE"
@@ -119,44 +138,45 @@ exit 0
 
 const DEREGISTER_SESSION_SH: &str = r#"#!/usr/bin/env bash
 # deregister-session.sh — Called by the SessionEnd hook.
-# Removes the session entry from the Automatic sessions file.
-# Uses .automatic-dev in debug builds (detected via AUTOMATIC_DEV env var),
-# otherwise uses .automatic.
+# Removes the session entry from the Automatic sessions file and drops the
+# session's ephemeral memories.
+# Uses $AUTOMATIC_DATA_DIR when set, otherwise the data directory of the
+# app that wrote this script.
 set -euo pipefail
 
-if [ "${AUTOMATIC_DEV:-0}" = "1" ]; then
-  SESSIONS_FILE="$HOME/.automatic-dev/sessions.json"
-else
-  SESSIONS_FILE="$HOME/.automatic/sessions.json"
-fi
+DATA_DIR="${AUTOMATIC_DATA_DIR:-__AUTOMATIC_DATA_DIR__}"
+SESSIONS_FILE="$DATA_DIR/sessions.json"
 
 INPUT=$(cat)
 SESSION_ID=$(echo "$INPUT" | jq -r '.session_id // empty')
 
-if [ -z "$SESSION_ID" ] || [ ! -f "$SESSIONS_FILE" ]; then
+if [ -z "$SESSION_ID" ]; then
   exit 0
 fi
 
-TMPFILE=$(mktemp)
+# The binary removes the entry under a lock with an atomic write; jq is the
+# fallback when it is unavailable.
+AUTOMATIC_BIN="__AUTOMATIC_BIN__"
+if [ -x "$AUTOMATIC_BIN" ] && "$AUTOMATIC_BIN" session-end "$SESSION_ID" >/dev/null 2>&1; then
+  exit 0
+fi
+
+if [ ! -f "$SESSIONS_FILE" ]; then
+  exit 0
+fi
+
+exec 9>"$SESSIONS_FILE.lock"
+if command -v flock >/dev/null 2>&1; then
+  flock 9
+fi
+
+TMPFILE=$(mktemp "$SESSIONS_FILE.XXXXXX")
 jq --arg id "$SESSION_ID" 'del(.[$id])' \
    "$SESSIONS_FILE" > "$TMPFILE" && mv "$TMPFILE" "$SESSIONS_FILE"
 
 exit 0
 "#;
 
-// ── Sessions reader ─────────────────────────────────────────────────────────
-
-/// Read active sessions from the store file.  Returns the raw JSON string
-/// (an object keyed by session_id).  Returns "{}" if the file doesn't exist.
-pub fn list_sessions() -> Result<String, String> {
-    let path = get_sessions_path()?;
-    if path.exists() {
-        fs::read_to_string(&path).map_err(|e| e.to_string())
-    } else {
-        Ok("{}".into())
-    }
-}
-
 // ── Plugin writer ───────────────────────────────────────────────────────────
 
 /// Helper: create a directory if it doesn't exist.
@@ -221,12 +241,23 @@ fn write_automatic_plugin(plugin_dir: &std::path::Path) -> Result<(), String> {
     let scripts_dir = plugin_dir.join("scripts");
     ensure_dir(&scripts_dir)?;
 
+    let data_dir = get_automatic_dir()?.display().to_string();
+
     let register_path = scripts_dir.join("register-session.sh");
-    write_file(&register_path, REGISTER_SESSION_SH)?;
+    write_file(
+        &register_path,
+        &REGISTER_SESSION_SH.replace(AUTOMATIC_DATA_DIR_PLACEHOLDER, &data_dir),
+    )?;
     make_executable(&register_path)?;
 
     let deregister_path = scripts_dir.join("deregister-session.sh");
-    write_file(&deregister_path, DEREGISTER_SESSION_SH)?;
+    let automatic_bin = super::automatic_binary();
+    write_file(
+        &deregister_path,
+        &DEREGISTER_SESSION_SH
+            .replace(AUTOMATIC_BIN_PLACEHOLDER, &automatic_bin)
+            .replace(AUTOMATIC_DATA_DIR_PLACEHOLDER, &data_dir),
+    )?;
     make_executable(&deregister_path)?;
 
     Ok(())
@@ -386,3 +417,50 @@ pub fn install_plugin_marketplace() -> Result<String, String> {
 
     Ok("Plugin marketplace registered and plugins installed".into())
 }
+
+/// Plugin and marketplace names registered by installs that predate the
+/// rename to Automatic.
+const LEGACY_PLUGIN_NAME: &str = "nexus";
+const LEGACY_MARKETPLACE_NAME: &str = "nexus-plugins";
+
+/// Unregister the legacy `nexus` plugin and its marketplace from Claude Code.
+/// Returns a description of what was removed, or `None` when the claude CLI
+/// is missing or nothing legacy is registered.
+pub fn remove_legacy_nexus_plugin() -> Result<Option<String>, String> {
+    let claude = match find_claude_binary() {
+        Some(p) => p,
+        None => return Ok(None),
+    };
+
+    let listed = |args: &[&str], name: &str| {
+        std::process::Command::new(&claude)
+            .args(args)
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains(name))
+            .unwrap_or(false)
+    };
+    let has_plugin = listed(&["plugin", "list"], LEGACY_PLUGIN_NAME);
+    let has_marketplace = listed(&["plugin", "marketplace", "list"], LEGACY_MARKETPLACE_NAME);
+
+    let mut removed = Vec::new();
+    if has_plugin {
+        let out = std::process::Command::new(&claude)
+            .args(["plugin", "uninstall", LEGACY_PLUGIN_NAME])
+            .output()
+            .map_err(|e| format!("Failed to run claude plugin uninstall: {}", e))?;
+        if out.status.success() {
+            removed.push(format!("plugin '{}'", LEGACY_PLUGIN_NAME));
+        }
+    }
+    if has_marketplace {
+        let out = std::process::Command::new(&claude)
+            .args(["plugin", "marketplace", "remove", LEGACY_MARKETPLACE_NAME])
+            .output()
+            .map_err(|e| format!("Failed to run claude plugin marketplace remove: {}", e))?;
+        if out.status.success() {
+            removed.push(format!("marketplace '{}'", LEGACY_MARKETPLACE_NAME));
+        }
+    }
+
+    Ok((!removed.is_empty()).then(|| format!("Removed legacy {}", removed.join(" and "))))
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 
+use super::fsutil::{with_file_lock, write_atomic};
 use super::paths::get_automatic_dir;
 
 // ── User Profile (~/.automatic/profile.json) ─────────────────────────────────
@@ -71,33 +72,29 @@ pub fn read_profile() -> Result<Option<UserProfile>, String> {
 /// field is set; subsequent saves only update `updated_at`.
 pub fn save_profile(profile: &UserProfile) -> Result<(), String> {
     let path = get_profile_path()?;
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
-    }
-
-    let mut to_save = profile.clone();
-    let now = chrono::Utc::now().to_rfc3339();
+    with_file_lock(&path, || {
+        let mut to_save = profile.clone();
+        let now = chrono::Utc::now().to_rfc3339();
 
-    // Preserve original created_at if the file already exists.
-    // Read the file directly instead of calling read_profile() to avoid
-    // mutual recursion (read_profile → save_profile → read_profile → …)
-    // which causes a stack overflow on first run when no profile exists.
-    if path.exists() {
-        if let Ok(raw) = fs::read_to_string(&path) {
-            if let Ok(existing) = serde_json::from_str::<UserProfile>(&raw) {
-                if !existing.created_at.is_empty() {
-                    to_save.created_at = existing.created_at;
+        // Preserve original created_at if the file already exists.
+        // Read the file directly instead of calling read_profile() to avoid
+        // mutual recursion (read_profile → save_profile → read_profile → …)
+        // which causes a stack overflow on first run when no profile exists.
+        if path.exists() {
+            if let Ok(raw) = fs::read_to_string(&path) {
+                if let Ok(existing) = serde_json::from_str::<UserProfile>(&raw) {
+                    if !existing.created_at.is_empty() {
+                        to_save.created_at = existing.created_at;
+                    }
                 }
             }
         }
-    }
-    if to_save.created_at.is_empty() {
-        to_save.created_at = now.clone();
-    }
-    to_save.updated_at = now;
+        if to_save.created_at.is_empty() {
+            to_save.created_at = now.clone();
+        }
+        to_save.updated_at = now;
 
-    let raw = serde_json::to_string_pretty(&to_save).map_err(|e| e.to_string())?;
-    fs::write(&path, raw).map_err(|e| e.to_string())
+        let raw = serde_json::to_string_pretty(&to_save).map_err(|e| e.to_string())?;
+        write_atomic(&path, raw)
+    })
 }
@@ -0,0 +1,157 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::sync::DiscoveredProject;
+
+use super::*;
+
+// ── Adopting Discovered Projects ─────────────────────────────────────────────
+//
+// Registers the directories found by `sync::scan_for_projects` that the user
+// selected.  Each one is registered and synced like a project added by hand:
+// the first sync autodetects its agents, skills and MCP servers.  A directory
+// with an `.automatic/project.json` keeps that configuration.  Every project
+// is handled on its own, so one that fails does not stop the rest.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdoptOutcome {
+    Adopted,
+    /// Another registered project already uses the directory.
+    AlreadyRegistered,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdoptedProject {
+    pub directory: String,
+    /// Name the project is registered under.  May differ from the suggested
+    /// name when that was taken.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub outcome: AdoptOutcome,
+    /// Files written by the first sync.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Why adoption failed, or the error the first sync failed with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Register and sync each of `selected`, in order.
+pub fn adopt_discovered_projects(selected: Vec<DiscoveredProject>) -> Vec<AdoptedProject> {
+    let mut registered = registered_project_directories().unwrap_or_default();
+    let mut taken: HashSet<String> = list_projects().unwrap_or_default().into_iter().collect();
+
+    selected
+        .into_iter()
+        .map(|found| {
+            let key = canonical_key(&found.directory);
+            if let Some(name) = registered.get(&key) {
+                return AdoptedProject {
+                    directory: found.directory,
+                    project: Some(name.clone()),
+                    outcome: AdoptOutcome::AlreadyRegistered,
+                    files: Vec::new(),
+                    error: None,
+                };
+            }
+            let name = unique_project_name(&found.name, &taken);
+            match adopt_one(&found, &name) {
+                Ok((files, sync_error)) => {
+                    taken.insert(name.clone());
+                    registered.insert(key, name.clone());
+                    AdoptedProject {
+                        directory: found.directory,
+                        project: Some(name),
+                        outcome: AdoptOutcome::Adopted,
+                        files,
+                        error: sync_error,
+                    }
+                }
+                Err(e) => AdoptedProject {
+                    directory: found.directory,
+                    project: None,
+                    outcome: AdoptOutcome::Failed,
+                    files: Vec::new(),
+                    error: Some(e),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Register `found` as `name` and sync it.  A failed sync leaves the project
+/// registered and is returned alongside the (empty) file list.
+fn adopt_one(
+    found: &DiscoveredProject,
+    name: &str,
+) -> Result<(Vec<String>, Option<String>), String> {
+    if !Path::new(&found.directory).is_dir() {
+        return Err(format!("Directory '{}' does not exist", found.directory));
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut project = if found.has_project_config {
+        load_project_from_dir(&found.directory)?
+    } else {
+        Project {
+            directory: found.directory.clone(),
+            created_at: now.clone(),
+            ..Default::default()
+        }
+    };
+    project.name = name.to_string();
+    project.unregistered = false;
+    if project.created_at.is_empty() {
+        project.created_at = now.clone();
+    }
+    project.updated_at = now;
+
+    save_project(name, &to_stable_json(&project)?)?;
+    let project: Project = serde_json::from_str(&read_project(name)?)
+        .map_err(|e| format!("Invalid project data: {}", e))?;
+    match crate::sync::sync_project(&project) {
+        Ok(files) => Ok((files, None)),
+        Err(e) => Ok((Vec::new(), Some(e))),
+    }
+}
+
+/// `suggested`, made a valid project name and suffixed with `-2`, `-3`, …
+/// until it is not in `taken`.
+fn unique_project_name(suggested: &str, taken: &HashSet<String>) -> String {
+    let base: String = suggested
+        .trim()
+        .chars()
+        .map(|c| if c == '/' || c == '\\' { '-' } else { c })
+        .collect();
+    let base = if is_valid_name(&base) {
+        base
+    } else {
+        "project".to_string()
+    };
+    if !taken.contains(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|name| !taken.contains(name))
+        .unwrap_or(base)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggested_names_are_made_valid_and_unique() {
+        let taken: HashSet<String> = ["api".to_string(), "api-2".to_string()].into();
+        assert_eq!(unique_project_name("web", &taken), "web");
+        assert_eq!(unique_project_name("api", &taken), "api-3");
+        assert_eq!(unique_project_name("a/b", &taken), "a-b");
+        assert_eq!(unique_project_name("..", &taken), "project");
+        assert_eq!(unique_project_name("  ", &taken), "project");
+    }
+}
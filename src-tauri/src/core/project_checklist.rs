@@ -0,0 +1,669 @@
+use serde::Serialize;
+use std::path::Path;
+
+use crate::agent;
+
+use super::*;
+
+// ── Project Setup Checklist ──────────────────────────────────────────────────
+//
+// One answer to "how complete is this project's agent setup?", built from
+// signals other parts of the app already track: instruction files, attached
+// rules, drift (from the drift cache when the project was checked recently),
+// MCP server configuration and budget, skill requirements, the Claude
+// plugin, sync history, .gitignore coverage of files holding credentials,
+// and pending conflicts.  Gathering the signals is kept apart from judging
+// them so the judgement is testable without a real project on disk.
+
+/// Instruction files with less user-authored text than this are flagged as
+/// placeholders.
+const MIN_INSTRUCTION_CHARS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecklistStatus {
+    Ok,
+    Warning,
+    ActionNeeded,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChecklistItem {
+    /// Stable identifier, e.g. `"instructions"`.
+    pub id: &'static str,
+    pub label: &'static str,
+    pub status: ChecklistStatus,
+    pub message: String,
+    /// Name of the command that fixes the item, for the UI to wire up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectChecklist {
+    pub project: String,
+    /// 0–100: ok items count fully, warnings half.
+    pub score: u8,
+    pub items: Vec<ChecklistItem>,
+}
+
+/// Everything the checklist looks at, gathered up front.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChecklistSignals {
+    /// `(filename, user-authored content)` for each instruction file the
+    /// project's agents use; `None` when the file does not exist.
+    pub instruction_files: Vec<(String, Option<String>)>,
+    /// Drifted agent ids, or `None` when drift could not be determined.
+    pub drifted_agents: Option<Vec<String>>,
+    /// Selected MCP servers with no local configuration.
+    pub unconfigured_servers: Vec<String>,
+    /// `"<server>: <ENV_KEY>"` for required env vars without a value.
+    pub missing_env: Vec<String>,
+    /// Agents receiving more MCP servers than they handle well.
+    pub mcp_budget: Vec<McpBudgetWarning>,
+    /// Unmet skill requirements; `None` when no selected skill declares any.
+    pub skill_requirements: Option<Vec<SkillRequirementWarning>>,
+    /// Whether the Claude plugin is installed; `None` when the project does
+    /// not use Claude Code.
+    pub claude_plugin_installed: Option<bool>,
+    /// Agent configs holding credentials that git would commit; `None` when
+    /// the project is not a git repository.
+    pub unignored_secret_files: Option<Vec<String>>,
+    /// Instruction files edited outside Automatic.
+    pub instruction_conflicts: Vec<String>,
+    pub last_sync: Option<String>,
+}
+
+/// Checklist for the registered project `name`.
+pub fn get_project_checklist(name: &str) -> Result<ProjectChecklist, String> {
+    let raw = read_project(name)?;
+    let project: Project = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+    Ok(project_checklist(&project))
+}
+
+/// Checklist for an already loaded project.
+pub fn project_checklist(project: &Project) -> ProjectChecklist {
+    build_checklist(project, &collect_signals(project))
+}
+
+fn collect_signals(project: &Project) -> ChecklistSignals {
+    let dir = Path::new(&project.directory);
+    let agents: Vec<&dyn agent::Agent> = project
+        .agents
+        .iter()
+        .filter_map(|id| agent::from_id(id))
+        .collect();
+
+    let mut filenames: Vec<&str> = agents
+        .iter()
+        .filter(|a| a.capabilities().instructions)
+        .map(|a| a.project_file_name())
+        .collect();
+    filenames.sort_unstable();
+    filenames.dedup();
+    let instruction_files = filenames
+        .into_iter()
+        .map(|f| {
+            let content = dir
+                .join(f)
+                .is_file()
+                .then(|| read_project_file(&project.directory, f).unwrap_or_default());
+            (f.to_string(), content)
+        })
+        .collect();
+
+    let drifted_agents = crate::sync::cached_drifted_agents(&project.name).or_else(|| {
+        crate::sync::check_project_drift(project, false)
+            .ok()
+            .map(|r| r.agents.into_iter().map(|a| a.agent_id).collect())
+    });
+
+    let mut unconfigured_servers = Vec::new();
+    let mut missing_env = Vec::new();
+    for server in &project.mcp_servers {
+        let Ok(raw) = read_mcp_server_config(server) else {
+            unconfigured_servers.push(server.clone());
+            continue;
+        };
+        let config: serde_json::Value = serde_json::from_str(&raw).unwrap_or_default();
+        if let Some(spec) = project.mcp_server_specs.get(server) {
+            for key in &spec.env_keys {
+                let value = config["env"][key].as_str().unwrap_or("");
+                if value.trim().is_empty() {
+                    missing_env.push(format!("{}: {}", server, key));
+                }
+            }
+        }
+    }
+
+    let skill_contents = project_skill_contents(project);
+    let mcp_budget = check_mcp_budget(&agents, &project.enabled_mcp_servers(), &skill_contents);
+    let skill_requirements = skill_contents
+        .iter()
+        .any(|(_, content)| !parse_skill_requirements(content).is_empty())
+        .then(|| {
+            check_skill_requirements(
+                &skill_contents,
+                &project.enabled_mcp_servers(),
+                &which_available,
+            )
+        });
+
+    let claude_plugin_installed = project.agents.iter().any(|a| a == "claude").then(|| {
+        get_plugins_dir()
+            .map(|d| d.join("automatic/hooks/hooks.json").is_file())
+            .unwrap_or(false)
+    });
+
+    let has_secrets = project
+        .mcp_servers
+        .iter()
+        .filter_map(|s| project.mcp_server_specs.get(s))
+        .any(|spec| !spec.env_keys.is_empty());
+    let unignored_secret_files = dir.join(".git").exists().then(|| {
+        if !has_secrets {
+            return Vec::new();
+        }
+        agents
+            .iter()
+            .flat_map(|a| a.owned_config_paths(dir))
+            .filter(|p| p.is_file() && !git_ignores(dir, p))
+            .filter_map(|p| p.strip_prefix(dir).ok().map(|r| r.display().to_string()))
+            .collect()
+    });
+
+    let instruction_conflicts = if dir.is_dir() {
+        crate::sync::collect_instruction_conflicts_pub(project, &dir.to_path_buf())
+            .into_iter()
+            .map(|c| c.filename)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let last_sync =
+        crate::activity::last_event_times(crate::activity::ActivityEvent::ProjectSynced)
+            .ok()
+            .and_then(|mut times| times.remove(&project.name));
+
+    ChecklistSignals {
+        instruction_files,
+        drifted_agents,
+        unconfigured_servers,
+        missing_env,
+        mcp_budget,
+        skill_requirements,
+        claude_plugin_installed,
+        unignored_secret_files,
+        instruction_conflicts,
+        last_sync,
+    }
+}
+
+/// Whether git would ignore `path` inside the repository at `dir`.
+fn git_ignores(dir: &Path, path: &Path) -> bool {
+    std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["check-ignore", "-q"])
+        .arg(path)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+pub(crate) fn build_checklist(project: &Project, signals: &ChecklistSignals) -> ProjectChecklist {
+    use ChecklistStatus::*;
+
+    let mut items = Vec::new();
+    let mut item = |id, label, status, message: String, action| {
+        items.push(ChecklistItem {
+            id,
+            label,
+            status,
+            message,
+            action: (status != Ok).then_some(action),
+        })
+    };
+
+    // ── Instruction files ─────────────────────────────────────────────────
+    let missing: Vec<&str> = signals
+        .instruction_files
+        .iter()
+        .filter(|(_, c)| c.is_none())
+        .map(|(f, _)| f.as_str())
+        .collect();
+    let thin: Vec<&str> = signals
+        .instruction_files
+        .iter()
+        .filter(|(_, c)| {
+            c.as_ref()
+                .is_some_and(|c| c.trim().len() < MIN_INSTRUCTION_CHARS)
+        })
+        .map(|(f, _)| f.as_str())
+        .collect();
+    let (status, message) = if signals.instruction_files.is_empty() {
+        (
+            Warning,
+            "No agent in this project reads an instruction file".to_string(),
+        )
+    } else if !missing.is_empty() {
+        (ActionNeeded, format!("Missing: {}", missing.join(", ")))
+    } else if !thin.is_empty() {
+        (
+            Warning,
+            format!("Little or no project guidance in: {}", thin.join(", ")),
+        )
+    } else {
+        (Ok, "Instruction files are in place".to_string())
+    };
+    item(
+        "instructions",
+        "Instruction files",
+        status,
+        message,
+        "save_project_file",
+    );
+
+    // ── Rules ──────────────────────────────────────────────────────────────
+    let rule_count =
+        project.file_rules.values().map(Vec::len).sum::<usize>() + project.custom_rules.len();
+    if rule_count == 0 {
+        item(
+            "rules",
+            "Rules",
+            Warning,
+            "No rules are attached to the instruction files".to_string(),
+            "save_project",
+        );
+    } else {
+        item(
+            "rules",
+            "Rules",
+            Ok,
+            format!("{} rule(s) attached", rule_count),
+            "save_project",
+        );
+    }
+
+    // ── Sync and drift ─────────────────────────────────────────────────────
+    match (&signals.last_sync, &signals.drifted_agents) {
+        (None, _) => item(
+            "sync",
+            "Synced",
+            ActionNeeded,
+            "The project has never been synced".to_string(),
+            "sync_project",
+        ),
+        (Some(_), Some(drifted)) if !drifted.is_empty() => item(
+            "sync",
+            "Synced",
+            ActionNeeded,
+            format!("Agent configs have drifted: {}", drifted.join(", ")),
+            "sync_project",
+        ),
+        (Some(at), None) => item(
+            "sync",
+            "Synced",
+            Warning,
+            format!("Last synced {}; drift could not be checked", at),
+            "check_project_drift",
+        ),
+        (Some(at), Some(_)) => item(
+            "sync",
+            "Synced",
+            Ok,
+            format!("In sync (last synced {})", at),
+            "sync_project",
+        ),
+    }
+
+    // ── MCP servers ────────────────────────────────────────────────────────
+    if !project.mcp_servers.is_empty() {
+        let mut problems = Vec::new();
+        if !signals.unconfigured_servers.is_empty() {
+            problems.push(format!(
+                "not configured: {}",
+                signals.unconfigured_servers.join(", ")
+            ));
+        }
+        if !signals.missing_env.is_empty() {
+            problems.push(format!("missing env: {}", signals.missing_env.join(", ")));
+        }
+        if problems.is_empty() && !signals.mcp_budget.is_empty() {
+            item(
+                "mcp_servers",
+                "MCP servers",
+                Warning,
+                signals
+                    .mcp_budget
+                    .iter()
+                    .map(McpBudgetWarning::message)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                "save_project",
+            );
+        } else if problems.is_empty() {
+            item(
+                "mcp_servers",
+                "MCP servers",
+                Ok,
+                format!("{} server(s) configured", project.mcp_servers.len()),
+                "save_mcp_server_config",
+            );
+        } else {
+            item(
+                "mcp_servers",
+                "MCP servers",
+                ActionNeeded,
+                problems.join("; "),
+                "save_mcp_server_config",
+            );
+        }
+    }
+
+    // ── Skill requirements ─────────────────────────────────────────────────
+    match &signals.skill_requirements {
+        Some(warnings) if !warnings.is_empty() => item(
+            "skill_requirements",
+            "Skill requirements",
+            Warning,
+            warnings
+                .iter()
+                .map(SkillRequirementWarning::message)
+                .collect::<Vec<_>>()
+                .join("; "),
+            "save_project",
+        ),
+        Some(_) => item(
+            "skill_requirements",
+            "Skill requirements",
+            Ok,
+            "Everything the selected skills need is available".to_string(),
+            "save_project",
+        ),
+        None => {}
+    }
+
+    // ── Claude plugin ──────────────────────────────────────────────────────
+    match signals.claude_plugin_installed {
+        Some(true) => item(
+            "claude_plugin",
+            "Claude Code plugin",
+            Ok,
+            "Session hooks are installed".to_string(),
+            "install_plugin_marketplace",
+        ),
+        Some(false) => item(
+            "claude_plugin",
+            "Claude Code plugin",
+            Warning,
+            "The Automatic plugin for Claude Code is not installed".to_string(),
+            "install_plugin_marketplace",
+        ),
+        None => {}
+    }
+
+    // ── .gitignore ─────────────────────────────────────────────────────────
+    match &signals.unignored_secret_files {
+        Some(files) if !files.is_empty() => item(
+            "gitignore",
+            ".gitignore",
+            ActionNeeded,
+            format!(
+                "Configs holding credentials are not git-ignored: {}",
+                files.join(", ")
+            ),
+            "open_in_editor",
+        ),
+        Some(_) => item(
+            "gitignore",
+            ".gitignore",
+            Ok,
+            "No credentials would be committed".to_string(),
+            "open_in_editor",
+        ),
+        None => {}
+    }
+
+    // ── Conflicts ──────────────────────────────────────────────────────────
+    let mut conflicts: Vec<String> = signals.instruction_conflicts.clone();
+    conflicts.extend(
+        project
+            .skill_conflicts
+            .iter()
+            .map(|c| format!("skill '{}'", c.name)),
+    );
+    if conflicts.is_empty() {
+        item(
+            "conflicts",
+            "Conflicts",
+            Ok,
+            "Nothing waiting to be resolved".to_string(),
+            "get_instruction_file_conflicts",
+        );
+    } else {
+        let action = if signals.instruction_conflicts.is_empty() {
+            "resolve_skill_shadow"
+        } else {
+            "get_instruction_file_conflicts"
+        };
+        item(
+            "conflicts",
+            "Conflicts",
+            ActionNeeded,
+            format!("Pending: {}", conflicts.join(", ")),
+            action,
+        );
+    }
+
+    let score = checklist_score(&items);
+    ProjectChecklist {
+        project: project.name.clone(),
+        score,
+        items,
+    }
+}
+
+fn checklist_score(items: &[ChecklistItem]) -> u8 {
+    if items.is_empty() {
+        return 100;
+    }
+    let points: usize = items
+        .iter()
+        .map(|i| match i.status {
+            ChecklistStatus::Ok => 2,
+            ChecklistStatus::Warning => 1,
+            ChecklistStatus::ActionNeeded => 0,
+        })
+        .sum();
+    (points * 100 / (items.len() * 2)) as u8
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_of(checklist: &ProjectChecklist, id: &str) -> Option<ChecklistStatus> {
+        checklist
+            .items
+            .iter()
+            .find(|i| i.id == id)
+            .map(|i| i.status)
+    }
+
+    fn complete() -> (Project, ChecklistSignals) {
+        let mut project = Project {
+            name: "web".into(),
+            agents: vec!["claude".into()],
+            mcp_servers: vec!["github".into()],
+            ..Default::default()
+        };
+        project
+            .file_rules
+            .insert("CLAUDE.md".into(), vec!["automatic-general".into()]);
+        let signals = ChecklistSignals {
+            instruction_files: vec![("CLAUDE.md".into(), Some("x".repeat(MIN_INSTRUCTION_CHARS)))],
+            drifted_agents: Some(vec![]),
+            claude_plugin_installed: Some(true),
+            unignored_secret_files: Some(vec![]),
+            last_sync: Some("2026-10-01T09:00:00Z".into()),
+            ..Default::default()
+        };
+        (project, signals)
+    }
+
+    #[test]
+    fn complete_project_scores_full_marks() {
+        let (project, signals) = complete();
+        let checklist = build_checklist(&project, &signals);
+        assert_eq!(checklist.score, 100);
+        assert!(checklist
+            .items
+            .iter()
+            .all(|i| i.status == ChecklistStatus::Ok && i.action.is_none()));
+        let ids: Vec<&str> = checklist.items.iter().map(|i| i.id).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "instructions",
+                "rules",
+                "sync",
+                "mcp_servers",
+                "claude_plugin",
+                "gitignore",
+                "conflicts"
+            ]
+        );
+    }
+
+    #[test]
+    fn fresh_project_needs_action_with_fix_hints() {
+        let project = Project {
+            name: "fresh".into(),
+            agents: vec!["claude".into()],
+            ..Default::default()
+        };
+        let signals = ChecklistSignals {
+            instruction_files: vec![("CLAUDE.md".into(), None)],
+            claude_plugin_installed: Some(false),
+            ..Default::default()
+        };
+        let checklist = build_checklist(&project, &signals);
+
+        assert_eq!(
+            status_of(&checklist, "instructions"),
+            Some(ChecklistStatus::ActionNeeded)
+        );
+        assert_eq!(
+            status_of(&checklist, "rules"),
+            Some(ChecklistStatus::Warning)
+        );
+        let sync = checklist.items.iter().find(|i| i.id == "sync").unwrap();
+        assert_eq!(sync.status, ChecklistStatus::ActionNeeded);
+        assert_eq!(sync.action, Some("sync_project"));
+        assert_eq!(
+            status_of(&checklist, "claude_plugin"),
+            Some(ChecklistStatus::Warning)
+        );
+        // Not applicable: no MCP servers selected, not a git repository.
+        assert_eq!(status_of(&checklist, "mcp_servers"), None);
+        assert_eq!(status_of(&checklist, "gitignore"), None);
+        // instructions 0, rules 1, sync 0, plugin 1, conflicts 2 → 4 / 10.
+        assert_eq!(checklist.score, 40);
+    }
+
+    #[test]
+    fn partial_setup_reports_each_gap() {
+        let (mut project, mut signals) = complete();
+        signals.instruction_files[0].1 = Some("# TODO\n".into());
+        signals.drifted_agents = Some(vec!["claude".into()]);
+        signals.missing_env = vec!["github: GITHUB_TOKEN".into()];
+        signals.unignored_secret_files = Some(vec![".mcp.json".into()]);
+        project.skill_conflicts.push(SkillShadow {
+            name: "react".into(),
+            path: ".agents/skills/react".into(),
+            project_hash: "a".into(),
+            registry_hash: "b".into(),
+        });
+        let checklist = build_checklist(&project, &signals);
+
+        let find = |id: &str| checklist.items.iter().find(|i| i.id == id).unwrap();
+        assert_eq!(find("instructions").status, ChecklistStatus::Warning);
+        assert!(find("sync").message.contains("claude"));
+        assert_eq!(find("mcp_servers").status, ChecklistStatus::ActionNeeded);
+        assert!(find("mcp_servers").message.contains("GITHUB_TOKEN"));
+        assert!(find("gitignore").message.contains(".mcp.json"));
+        assert_eq!(find("conflicts").action, Some("resolve_skill_shadow"));
+        assert_eq!(find("rules").status, ChecklistStatus::Ok);
+        assert!(checklist.score < 50, "{}", checklist.score);
+    }
+
+    #[test]
+    fn unmet_skill_requirements_are_a_warning() {
+        let (project, mut signals) = complete();
+        let checklist = build_checklist(&project, &signals);
+        assert_eq!(status_of(&checklist, "skill_requirements"), None);
+
+        signals.skill_requirements = Some(vec![]);
+        let checklist = build_checklist(&project, &signals);
+        assert_eq!(
+            status_of(&checklist, "skill_requirements"),
+            Some(ChecklistStatus::Ok)
+        );
+
+        signals.skill_requirements = Some(vec![SkillRequirementWarning {
+            skill: "pr".into(),
+            kind: RequirementKind::Command,
+            missing: "gh".into(),
+            suggestion: "install 'gh' and make sure it is on PATH".into(),
+        }]);
+        let checklist = build_checklist(&project, &signals);
+        let item = checklist
+            .items
+            .iter()
+            .find(|i| i.id == "skill_requirements")
+            .unwrap();
+        assert_eq!(item.status, ChecklistStatus::Warning);
+        assert!(item.message.contains("skill 'pr' requires command 'gh'"));
+    }
+
+    #[test]
+    fn too_many_servers_for_an_agent_is_a_warning() {
+        let (mut project, mut signals) = complete();
+        project.agents.push("cursor".into());
+        project.mcp_servers = (1..=12).map(|i| format!("server-{}", i)).collect();
+        let cursor = crate::agent::from_id("cursor").unwrap();
+        signals.mcp_budget = check_mcp_budget(&[cursor], &project.mcp_servers, &[]);
+        let checklist = build_checklist(&project, &signals);
+
+        let item = checklist
+            .items
+            .iter()
+            .find(|i| i.id == "mcp_servers")
+            .unwrap();
+        assert_eq!(item.status, ChecklistStatus::Warning);
+        assert!(item.message.contains("Cursor"), "{}", item.message);
+        assert!(item.message.contains("server-1"), "{}", item.message);
+
+        // Missing configuration still takes precedence.
+        signals.unconfigured_servers = vec!["server-12".into()];
+        let checklist = build_checklist(&project, &signals);
+        assert_eq!(
+            status_of(&checklist, "mcp_servers"),
+            Some(ChecklistStatus::ActionNeeded)
+        );
+    }
+
+    #[test]
+    fn unknown_drift_is_a_warning_not_a_failure() {
+        let (project, mut signals) = complete();
+        signals.drifted_agents = None;
+        let checklist = build_checklist(&project, &signals);
+        let sync = checklist.items.iter().find(|i| i.id == "sync").unwrap();
+        assert_eq!(sync.status, ChecklistStatus::Warning);
+        assert_eq!(sync.action, Some("check_project_drift"));
+    }
+}
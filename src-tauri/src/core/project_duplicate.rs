@@ -0,0 +1,227 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+// ── Project Duplication ──────────────────────────────────────────────────────
+//
+// Starts a new project in another directory with the same agents, skills, MCP
+// servers and rules as an existing one.  The copy keeps everything that
+// describes the configuration and drops what describes the old directory:
+// local skills, the hashes sync recorded for files it wrote there, skill
+// copies found there, rule text discovered in its instruction files, and the
+// lock.  The new project is saved to the registry and synced in full, so its
+// directory ends up as if the user had configured it by hand.
+
+/// Result of [`duplicate_project`].
+#[derive(Debug, Serialize, Clone)]
+pub struct DuplicatedProject {
+    pub project: Project,
+    /// Files written into the new directory.
+    pub written: Vec<String>,
+}
+
+/// Copy project `source_name` to `new_name` in `new_directory`, which must
+/// already exist, and sync it.  With `copy_instructions`, the user-authored
+/// part of each instruction file is copied too; managed sections are left
+/// for the sync to write.
+pub fn duplicate_project(
+    source_name: &str,
+    new_name: &str,
+    new_directory: &str,
+    copy_instructions: bool,
+) -> Result<DuplicatedProject, String> {
+    if !is_valid_name(new_name) {
+        return Err("Invalid project name".into());
+    }
+    if read_project(new_name).is_ok() {
+        return Err(format!("A project named '{}' already exists", new_name));
+    }
+    let source: Project = serde_json::from_str(&read_project(source_name)?)
+        .map_err(|e| format!("Invalid project data: {}", e))?;
+    let directory = check_duplicate_directory(new_directory, new_name)?;
+    if !source.directory.is_empty() && same_path(&source.directory, &directory) {
+        return Err(format!(
+            "'{}' is the directory of project '{}'",
+            directory.display(),
+            source_name
+        ));
+    }
+
+    let mut project = duplicated_project(&source, new_name, &directory.display().to_string());
+    let instructions = if copy_instructions && !source.directory.is_empty() {
+        user_instructions(&source)?
+    } else {
+        Vec::new()
+    };
+
+    save_project(new_name, &to_stable_json(&project)?)?;
+    // Read back so the project carries what saving stamped on it.
+    if let Ok(saved) = read_project(new_name) {
+        if let Ok(saved) = serde_json::from_str::<Project>(&saved) {
+            project = saved;
+        }
+    }
+
+    let mut written = Vec::new();
+    for (key, content) in &instructions {
+        save_project_file_for_project(&project, key, content)?;
+        let files = if key == "_unified" {
+            instruction_file_names(&project)
+        } else {
+            vec![key.clone()]
+        };
+        for file in files {
+            push_unique(&mut written, directory.join(file).display().to_string());
+        }
+    }
+
+    let synced = crate::sync::sync_project(&project).map_err(|e| {
+        format!(
+            "Project '{}' was created but its first sync failed: {}",
+            new_name, e
+        )
+    })?;
+    for file in synced {
+        push_unique(&mut written, file);
+    }
+
+    if let Ok(saved) = read_project(new_name) {
+        if let Ok(saved) = serde_json::from_str::<Project>(&saved) {
+            project = saved;
+        }
+    }
+    Ok(DuplicatedProject { project, written })
+}
+
+/// The canonical form of `dir`, provided it is a directory whose
+/// `.automatic/project.json`, if any, belongs to `new_name`.
+fn check_duplicate_directory(dir: &str, new_name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(dir);
+    if !path.is_dir() {
+        return Err(format!("Directory '{}' does not exist", dir));
+    }
+    let canonical = fs::canonicalize(path)
+        .map_err(|e| format!("Directory '{}' is not accessible: {}", dir, e))?;
+
+    let config_path = canonical.join(".automatic").join("project.json");
+    if config_path.exists() {
+        let owner = fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(str::to_string))
+            .unwrap_or_default();
+        if owner != new_name {
+            return Err(format!(
+                "'{}' already contains the configuration of project '{}'",
+                canonical.display(),
+                if owner.is_empty() { "unknown" } else { &owner }
+            ));
+        }
+    }
+    Ok(canonical)
+}
+
+/// `source` as a new project `name` in `directory`.
+fn duplicated_project(source: &Project, name: &str, directory: &str) -> Project {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut project = source.clone();
+    project.name = name.to_string();
+    project.directory = directory.to_string();
+    project.created_at = now.clone();
+    project.updated_at = now;
+    project.last_activity = None;
+    project.local_skills.clear();
+    project.instruction_file_hashes.clear();
+    project.skill_hashes.clear();
+    project.skill_conflicts.clear();
+    project.preserved_skills.clear();
+    project.rule_candidates.clear();
+    project.locked = false;
+    project.locked_reason = None;
+    project.unregistered = false;
+    project
+}
+
+/// The user-authored content of `project`'s instruction files, keyed the
+/// way [`save_project_file_for_project`] expects.  Empty files are skipped.
+fn user_instructions(project: &Project) -> Result<Vec<(String, String)>, String> {
+    let mut instructions = Vec::new();
+    for file in instruction_file_names(project) {
+        let content = read_project_file(&project.directory, &file)?;
+        if content.trim().is_empty() {
+            continue;
+        }
+        if project.instruction_mode == "unified" {
+            // Every file carries the same content; one copy is enough.
+            return Ok(vec![("_unified".to_string(), content)]);
+        }
+        instructions.push((file, content));
+    }
+    Ok(instructions)
+}
+
+fn push_unique(list: &mut Vec<String>, item: String) {
+    if !list.contains(&item) {
+        list.push(item);
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn copy_drops_state_tied_to_the_old_directory() {
+        let source = Project {
+            name: "api".into(),
+            directory: "/work/api".into(),
+            skills: vec!["rust".into()],
+            local_skills: vec!["api-only".into()],
+            agents: vec!["claude".into()],
+            created_at: "2025-01-01T00:00:00Z".into(),
+            locked: true,
+            preserved_skills: vec!["rust".into()],
+            instruction_file_hashes: [("CLAUDE.md".to_string(), "abc".to_string())].into(),
+            ..Default::default()
+        };
+
+        let copy = duplicated_project(&source, "api-v2", "/work/api-v2");
+        assert_eq!(copy.name, "api-v2");
+        assert_eq!(copy.directory, "/work/api-v2");
+        assert_eq!(copy.skills, source.skills);
+        assert_eq!(copy.agents, source.agents);
+        assert_ne!(copy.created_at, source.created_at);
+        assert!(copy.local_skills.is_empty());
+        assert!(copy.preserved_skills.is_empty());
+        assert!(copy.instruction_file_hashes.is_empty());
+        assert!(!copy.locked);
+    }
+
+    #[test]
+    fn target_directory_must_exist_and_not_belong_to_another_project() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().display().to_string();
+        assert!(check_duplicate_directory(&target, "api-v2").is_ok());
+        assert!(
+            check_duplicate_directory(&format!("{}/missing", target), "api-v2")
+                .unwrap_err()
+                .contains("does not exist")
+        );
+
+        fs::create_dir_all(dir.path().join(".automatic")).unwrap();
+        fs::write(
+            dir.path().join(".automatic").join("project.json"),
+            r#"{"name": "web"}"#,
+        )
+        .unwrap();
+        assert!(check_duplicate_directory(&target, "api-v2")
+            .unwrap_err()
+            .contains("project 'web'"));
+        assert!(check_duplicate_directory(&target, "web").is_ok());
+    }
+}
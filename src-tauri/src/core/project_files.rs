@@ -3,6 +3,8 @@ use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
+use serde::Serialize;
+
 use crate::agent;
 
 use super::*;
@@ -42,6 +44,13 @@ pub fn save_project_file(directory: &str, filename: &str, content: &str) -> Resu
     }
 
     let path = dir.join(filename);
+    // Nested instruction files (e.g. `.github/instructions/*.instructions.md`)
+    // may live in a directory that does not exist yet.
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+    }
     fs::write(&path, content).map_err(|e| e.to_string())
 }
 
@@ -62,23 +71,23 @@ pub fn save_project_file_for_project(
     filename: &str,
     user_content: &str,
 ) -> Result<(), String> {
+    project.ensure_unlocked()?;
+    let filename = instruction_file_for(project, filename);
+    let filename = filename.as_str();
+
+    // Copilot path-scoped instruction and prompt files carry their own rule
+    // assignment (keyed by the file path) and are never replicated in unified
+    // mode.
+    if agent::is_copilot_scoped_file(filename) {
+        return save_copilot_scoped_file(project, filename, user_content);
+    }
+
     let is_unified = filename == "_unified" || project.instruction_mode == "unified";
 
-    // Resolve rules: project-level key ("_project") takes precedence over the
-    // legacy per-file / unified keys so that saves are consistent with sync.
-    // Mandatory rules (e.g. automatic-service) are always included.
-    let rules: Vec<String> = ensure_mandatory_rules(&if let Some(r) =
-        project.file_rules.get("_project").filter(|v| !v.is_empty())
-    {
-        r.clone()
-    } else {
-        let rule_key = if is_unified { "_unified" } else { filename };
-        project
-            .file_rules
-            .get(rule_key)
-            .cloned()
-            .unwrap_or_default()
-    });
+    // Resolve rules the same way sync does.  Mandatory rules (e.g.
+    // automatic-service) are always included.
+    let rule_key = if is_unified { "_unified" } else { filename };
+    let rules: Vec<String> = ensure_mandatory_rules(&file_rules_for(project, rule_key));
 
     // Collect inline custom rule content strings from the project.
     // These are project-scoped rules that don't live in the global registry.
@@ -125,42 +134,196 @@ pub fn save_project_file_for_project(
 
         // Persist a snapshot of the user content so drift detection can diff
         // against what Automatic last wrote, not just detect that a change occurred.
-        let _ = save_instruction_snapshot(&project.directory, f, user_content);
+        let _ = save_instruction_snapshot(project, f, user_content);
     }
 
     Ok(())
 }
 
+/// Agents in the project whose instruction file is `filename`, in project
+/// order.  More than one means the file is shared: in per-agent mode it is
+/// still a single file on disk, edited and saved through one entry.
+pub fn agents_sharing_file(project: &Project, filename: &str) -> Vec<String> {
+    project
+        .agents
+        .iter()
+        .filter(|id| agent::from_id(id).is_some_and(|a| a.project_file_name() == filename))
+        .cloned()
+        .collect()
+}
+
+/// The instruction file a save for `key` should write.  `key` is normally a
+/// filename; an agent id of the project maps to that agent's file, so two
+/// agents sharing `AGENTS.md` never get separate copies.
+pub fn instruction_file_for(project: &Project, key: &str) -> String {
+    if project.agents.iter().any(|a| a == key) {
+        if let Some(a) = agent::from_id(key) {
+            return a.project_file_name().to_string();
+        }
+    }
+    key.to_string()
+}
+
+/// User-assigned global rules for an instruction file, without mandatory
+/// rules.  Priority order:
+///   1. `"_project"` — project-level rules set from the Rules tab
+///   2. `"_unified"` — legacy unified-mode key (pass it as `filename`)
+///   3. Legacy per-file rules: the file's own key plus the keys of every
+///      agent sharing it, in order and without duplicates
+pub fn file_rules_for(project: &Project, filename: &str) -> Vec<String> {
+    if let Some(rules) = project.file_rules.get("_project").filter(|v| !v.is_empty()) {
+        return rules.clone();
+    }
+    let mut keys = vec![filename.to_string()];
+    if filename != "_unified" {
+        keys.extend(agents_sharing_file(project, filename));
+    }
+    let mut rules: Vec<String> = Vec::new();
+    for key in keys {
+        for rule in project.file_rules.get(&key).into_iter().flatten() {
+            if !rules.contains(rule) {
+                rules.push(rule.clone());
+            }
+        }
+    }
+    rules
+}
+
+/// The rules carried by one instruction file, as returned by
+/// [`project_rules_by_file`].
+#[derive(Debug, Clone, Serialize)]
+pub struct InstructionFileRules {
+    pub filename: String,
+    /// Registry rules by machine name, in injection order.
+    pub rules: Vec<String>,
+    /// Display names of the project's inline custom rules, injected after
+    /// the registry rules.
+    pub custom_rules: Vec<String>,
+    /// Registry rules that could not be read; they are left out of `content`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+    /// The content of every rule, in order, separated by blank lines.
+    pub content: String,
+}
+
+/// Resolve the rules of each of the project's instruction files the same
+/// way saves do, so an agent can reload them without the surrounding file.
+pub fn project_rules_by_file(project: &Project) -> Vec<InstructionFileRules> {
+    project_rules_by_file_with(project, &read_rule_content)
+}
+
+fn project_rules_by_file_with(
+    project: &Project,
+    read_rule: &dyn Fn(&str) -> Result<String, String>,
+) -> Vec<InstructionFileRules> {
+    let custom: Vec<&CustomRule> = project
+        .custom_rules
+        .iter()
+        .filter(|r| !r.content.trim().is_empty())
+        .collect();
+
+    instruction_file_names(project)
+        .into_iter()
+        .map(|filename| {
+            // Mirrors save_project_file_for_project and
+            // save_copilot_scoped_file.
+            let scoped = agent::is_copilot_scoped_file(&filename);
+            let (rules, custom) = if scoped {
+                let rules = project.file_rules.get(&filename).cloned();
+                (rules.unwrap_or_default(), Vec::new())
+            } else {
+                let key = if project.instruction_mode == "unified" {
+                    "_unified"
+                } else {
+                    filename.as_str()
+                };
+                (
+                    ensure_mandatory_rules(&file_rules_for(project, key)),
+                    custom.clone(),
+                )
+            };
+
+            let mut parts = Vec::new();
+            let mut missing = Vec::new();
+            for rule in &rules {
+                match read_rule(rule) {
+                    Ok(content) if !content.trim().is_empty() => {
+                        parts.push(content.trim().to_string())
+                    }
+                    Ok(_) => {}
+                    Err(_) => missing.push(rule.clone()),
+                }
+            }
+            parts.extend(custom.iter().map(|r| r.content.trim().to_string()));
+
+            InstructionFileRules {
+                filename,
+                rules,
+                custom_rules: custom.iter().map(|r| r.name.clone()).collect(),
+                missing,
+                content: parts.join("\n\n"),
+            }
+        })
+        .collect()
+}
+
+/// Save a `.github/instructions/*.instructions.md` or
+/// `.github/prompts/*.prompt.md` file.  Only rules assigned to this exact path
+/// are injected — project-level rules already reach Copilot through
+/// `.github/copilot-instructions.md`.  A new instruction file is seeded with a
+/// default `applyTo` frontmatter so Copilot picks it up.
+fn save_copilot_scoped_file(
+    project: &Project,
+    filename: &str,
+    user_content: &str,
+) -> Result<(), String> {
+    let rules = project
+        .file_rules
+        .get(filename)
+        .cloned()
+        .unwrap_or_default();
+
+    let is_new = !PathBuf::from(&project.directory).join(filename).exists();
+    let content = if is_new && agent::is_copilot_instruction_file(filename) {
+        agent::with_default_instruction_frontmatter(user_content)
+    } else {
+        user_content.to_string()
+    };
+
+    save_project_file_with_rules_and_custom(&project.directory, filename, &content, &rules, &[])?;
+    let _ = save_instruction_snapshot(project, filename, &content);
+    Ok(())
+}
+
 // ── Instruction file snapshots ───────────────────────────────────────────────
 
-/// Directory (relative to project root) where Automatic stores its snapshots.
-const SNAPSHOT_DIR: &str = ".automatic/snapshots";
+/// Directory (inside the project's data dir) where Automatic stores its
+/// snapshots.
+const SNAPSHOT_DIR: &str = "snapshots";
 
-/// Persist `user_content` for `filename` into `<project>/.automatic/snapshots/<filename>`.
+/// Persist `user_content` for `filename` into `<data dir>/snapshots/<filename>`,
+/// where the data dir is [`project_data_dir`] (usually `<project>/.automatic`).
 ///
 /// This is called every time Automatic writes an instruction file so that
 /// drift detection can diff the on-disk content against what Automatic last wrote.
 pub fn save_instruction_snapshot(
-    directory: &str,
+    project: &Project,
     filename: &str,
     user_content: &str,
 ) -> Result<(), String> {
-    if directory.is_empty() {
+    let Some(data_dir) = project_data_dir(project) else {
         return Ok(());
-    }
-    let snap_dir = PathBuf::from(directory).join(SNAPSHOT_DIR);
-    fs::create_dir_all(&snap_dir).map_err(|e| e.to_string())?;
+    };
+    let snap_dir = data_dir.join(SNAPSHOT_DIR);
     let path = snap_dir.join(filename);
+    fs::create_dir_all(path.parent().unwrap_or(&snap_dir)).map_err(|e| e.to_string())?;
     fs::write(&path, user_content).map_err(|e| e.to_string())
 }
 
-/// Read the snapshot for `filename` from `<project>/.automatic/snapshots/<filename>`.
+/// Read the snapshot for `filename` saved by [`save_instruction_snapshot`].
 /// Returns `None` if no snapshot exists (Automatic has never written this file).
-pub fn read_instruction_snapshot(directory: &str, filename: &str) -> Option<String> {
-    if directory.is_empty() {
-        return None;
-    }
-    let path = PathBuf::from(directory).join(SNAPSHOT_DIR).join(filename);
+pub fn read_instruction_snapshot(project: &Project, filename: &str) -> Option<String> {
+    let path = project_data_dir(project)?.join(SNAPSHOT_DIR).join(filename);
     fs::read_to_string(&path).ok()
 }
 
@@ -204,6 +367,122 @@ fn collect_agent_filenames(project: &Project) -> Vec<String> {
     filenames
 }
 
+/// Every instruction file the project's agents read: each agent's project
+/// file, then agent-specific extras such as Copilot's path-scoped
+/// `.github/instructions/*.instructions.md` and `.github/prompts/*.prompt.md`
+/// files.
+pub fn instruction_file_names(project: &Project) -> Vec<String> {
+    let mut filenames = collect_agent_filenames(project);
+    let dir = PathBuf::from(&project.directory);
+    for agent_id in &project.agents {
+        if let Some(a) = agent::from_id(agent_id) {
+            for f in a.extra_instruction_files(&dir) {
+                if !filenames.contains(&f) {
+                    filenames.push(f);
+                }
+            }
+        }
+    }
+    filenames
+}
+
+/// One entry of [`project_file_info`].
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ProjectFileInfo {
+    /// Path relative to the project directory, or `_unified` for the
+    /// virtual entry that edits every agent's file at once.
+    pub filename: String,
+    /// Labels of the agents that read the file.
+    pub agents: Vec<String>,
+    /// Ids of the agents that read the file.  Empty for the unified entry
+    /// and for agent-specific extra files.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub agent_ids: Vec<String>,
+    pub exists: bool,
+    /// In per-agent mode: more than one selected agent reads the file.  It is
+    /// still one entry, saved once.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub shared: bool,
+    /// For the unified entry: the files a save writes to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_files: Option<Vec<String>>,
+}
+
+/// The instruction files the project manages, for editing.
+///
+/// In per-agent mode there is one entry per distinct project file, sorted by
+/// filename.  In unified mode the agents' project files are folded into a
+/// single `_unified` entry.  Agent-specific extra files (e.g. Copilot's
+/// `.github/instructions/*.instructions.md`) are always listed on their own,
+/// because each one carries its own scope.
+pub fn project_file_info(project: &Project) -> Vec<ProjectFileInfo> {
+    let project_dir = PathBuf::from(&project.directory);
+
+    let mut files: Vec<ProjectFileInfo> = Vec::new();
+    for agent_id in &project.agents {
+        let Some(a) = agent::from_id(agent_id) else {
+            continue;
+        };
+        let filename = a.project_file_name();
+        match files.iter_mut().find(|f| f.filename == filename) {
+            Some(file) => {
+                file.agents.push(a.label().to_string());
+                file.agent_ids.push(a.id().to_string());
+            }
+            None => files.push(ProjectFileInfo {
+                filename: filename.to_string(),
+                agents: vec![a.label().to_string()],
+                agent_ids: vec![a.id().to_string()],
+                exists: project_dir.join(filename).exists(),
+                shared: false,
+                target_files: None,
+            }),
+        }
+    }
+
+    let mut extra_files: Vec<ProjectFileInfo> = Vec::new();
+    for agent_id in &project.agents {
+        let Some(a) = agent::from_id(agent_id) else {
+            continue;
+        };
+        for filename in a.extra_instruction_files(&project_dir) {
+            let listed = |f: &ProjectFileInfo| f.filename == filename;
+            if files.iter().any(listed) || extra_files.iter().any(listed) {
+                continue;
+            }
+            extra_files.push(ProjectFileInfo {
+                filename,
+                agents: vec![a.label().to_string()],
+                agent_ids: Vec::new(),
+                exists: true,
+                shared: false,
+                target_files: None,
+            });
+        }
+    }
+
+    if project.instruction_mode == "unified" {
+        let unified = ProjectFileInfo {
+            filename: "_unified".to_string(),
+            agents: files.iter().flat_map(|f| f.agents.clone()).collect(),
+            agent_ids: Vec::new(),
+            exists: files.iter().any(|f| f.exists),
+            shared: false,
+            target_files: Some(files.into_iter().map(|f| f.filename).collect()),
+        };
+        let mut entries = vec![unified];
+        entries.extend(extra_files);
+        entries
+    } else {
+        for file in &mut files {
+            file.shared = file.agent_ids.len() > 1;
+        }
+        files.extend(extra_files);
+        files.sort_by(|a, b| a.filename.cmp(&b.filename));
+        files
+    }
+}
+
 /// Public wrapper for `strip_managed_section` (used by sync).
 pub fn strip_managed_section_pub(content: &str) -> String {
     strip_managed_section(content)
@@ -280,8 +559,11 @@ pub fn compute_instruction_hashes(project: &Project) -> HashMap<String, String>
 pub fn record_instruction_hashes(project_name: &str, project: &mut Project) {
     project.instruction_file_hashes = compute_instruction_hashes(project);
 
-    // Persist updated hashes to the registry.
-    if let Ok(data) = serde_json::to_string_pretty(project) {
+    // Persist updated hashes to the registry (or only to the project
+    // directory for unregistered projects).
+    if project.unregistered {
+        let _ = persist_project(project);
+    } else if let Ok(data) = serde_json::to_string_pretty(project) {
         let _ = save_project(project_name, &data);
     }
 }
@@ -366,6 +648,70 @@ mod tests {
         );
     }
 
+    // ── Shared instruction files in per-agent mode ──────────────────────────
+
+    #[test]
+    fn shared_agents_md_unions_rules_of_every_sharing_agent() {
+        let mut project = make_project("/tmp/unused", &["codex", "claude", "opencode"]);
+        project
+            .file_rules
+            .insert("AGENTS.md".into(), vec!["style".into()]);
+        project
+            .file_rules
+            .insert("codex".into(), vec!["style".into(), "security".into()]);
+        project
+            .file_rules
+            .insert("opencode".into(), vec!["testing".into()]);
+        project
+            .file_rules
+            .insert("CLAUDE.md".into(), vec!["claude-only".into()]);
+
+        assert_eq!(
+            agents_sharing_file(&project, "AGENTS.md"),
+            vec!["codex", "opencode"]
+        );
+        assert_eq!(agents_sharing_file(&project, "CLAUDE.md"), vec!["claude"]);
+        assert_eq!(
+            file_rules_for(&project, "AGENTS.md"),
+            vec!["style", "security", "testing"]
+        );
+        assert_eq!(file_rules_for(&project, "CLAUDE.md"), vec!["claude-only"]);
+
+        // Project-level rules still take precedence.
+        project
+            .file_rules
+            .insert("_project".into(), vec!["general".into()]);
+        assert_eq!(file_rules_for(&project, "AGENTS.md"), vec!["general"]);
+    }
+
+    #[test]
+    fn saves_for_agents_sharing_a_file_write_it_once() {
+        let dir = tmp();
+        let project = make_project(dir.path().to_str().unwrap(), &["codex", "opencode"]);
+
+        save_project_file_for_project(&project, "codex", "# From the Codex tab").expect("save");
+        save_project_file_for_project(&project, "opencode", "# From the OpenCode tab")
+            .expect("save");
+        save_project_file_for_project(&project, "AGENTS.md", "# Shared").expect("save");
+
+        let mut written: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name != ".automatic")
+            .collect();
+        written.sort();
+        assert_eq!(written, vec!["AGENTS.md"]);
+
+        let on_disk = fs::read_to_string(dir.path().join("AGENTS.md")).unwrap();
+        assert!(on_disk.starts_with("# Shared"), "{:?}", on_disk);
+        assert!(!on_disk.contains("tab"));
+        assert_eq!(
+            read_instruction_snapshot(&project, "AGENTS.md").as_deref(),
+            Some("# Shared")
+        );
+    }
+
     // ── Bug: Unified mode should write to ALL agent files ───────────────────
 
     #[test]
@@ -409,6 +755,7 @@ mod tests {
             "claude".to_string(),
             AgentOptions {
                 claude_rules_in_dot_claude: true,
+                ..Default::default()
             },
         );
 
@@ -484,6 +831,7 @@ mod tests {
             "claude".to_string(),
             AgentOptions {
                 claude_rules_in_dot_claude: true,
+                ..Default::default()
             },
         );
 
@@ -603,4 +951,147 @@ mod tests {
             marker_count, on_disk
         );
     }
+
+    // ── Copilot path-scoped instruction files ───────────────────────────────
+
+    #[test]
+    fn save_new_copilot_instruction_file_seeds_apply_to_frontmatter() {
+        let dir = tmp();
+        let project = make_project(dir.path().to_str().unwrap(), &["copilot"]);
+        let rel = ".github/instructions/api.instructions.md";
+
+        save_project_file_for_project(&project, rel, "# API\n").expect("save");
+
+        let on_disk = fs::read_to_string(dir.path().join(rel)).expect("read");
+        assert!(on_disk.starts_with("---\napplyTo: \"**\"\n---\n"));
+        assert!(on_disk.contains("# API"));
+        // Project-level (mandatory) rules already reach Copilot through
+        // copilot-instructions.md — they are not repeated per instruction file.
+        assert!(!on_disk.contains("<!-- automatic:rules:start -->"));
+    }
+
+    #[test]
+    fn save_new_copilot_prompt_file_is_written_verbatim() {
+        let dir = tmp();
+        let project = make_project(dir.path().to_str().unwrap(), &["copilot"]);
+        let rel = ".github/prompts/review.prompt.md";
+        let authored = "---\nmode: agent\n---\nReview the staged diff.\n";
+
+        save_project_file_for_project(&project, rel, authored).expect("save");
+
+        assert_eq!(fs::read_to_string(dir.path().join(rel)).unwrap(), authored);
+        let copilot = agent::from_id("copilot").unwrap();
+        assert!(copilot
+            .extra_instruction_files(dir.path())
+            .contains(&rel.to_string()));
+    }
+
+    #[test]
+    fn save_existing_copilot_instruction_file_keeps_authored_frontmatter() {
+        let dir = tmp();
+        let project = make_project(dir.path().to_str().unwrap(), &["copilot"]);
+        let rel = ".github/instructions/web.instructions.md";
+        fs::create_dir_all(dir.path().join(".github/instructions")).unwrap();
+        fs::write(dir.path().join(rel), "old").unwrap();
+
+        let authored = "---\napplyTo: \"web/**/*.tsx\"\n---\n# Web\n";
+        save_project_file_for_project(&project, rel, authored).expect("save");
+
+        assert_eq!(
+            read_project_file(&project.directory, rel).unwrap(),
+            authored
+        );
+    }
+
+    #[test]
+    fn instruction_file_names_cover_shared_and_extra_files() {
+        let dir = tmp();
+        let scoped = dir.path().join(".github").join("instructions");
+        fs::create_dir_all(&scoped).unwrap();
+        fs::write(
+            scoped.join("rust.instructions.md"),
+            "---\napplyTo: \"**/*.rs\"\n---\n",
+        )
+        .unwrap();
+        let project = make_project(
+            dir.path().to_str().unwrap(),
+            &["claude", "codex", "opencode", "copilot"],
+        );
+
+        assert_eq!(
+            instruction_file_names(&project),
+            vec![
+                "CLAUDE.md",
+                "AGENTS.md",
+                ".github/copilot-instructions.md",
+                ".github/instructions/rust.instructions.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn project_file_info_groups_agents_by_file() {
+        let dir = tmp();
+        fs::write(dir.path().join("AGENTS.md"), "# Agents\n").unwrap();
+        let directory = dir.path().to_str().unwrap();
+
+        let project = make_project(directory, &["claude", "codex", "opencode"]);
+        let files = project_file_info(&project);
+        let summary: Vec<(&str, usize, bool, bool)> = files
+            .iter()
+            .map(|f| (f.filename.as_str(), f.agent_ids.len(), f.exists, f.shared))
+            .collect();
+        assert_eq!(
+            summary,
+            [("AGENTS.md", 2, true, true), ("CLAUDE.md", 1, false, false)]
+        );
+
+        let unified = project_file_info(&make_unified_project(directory, &["claude", "codex"]));
+        assert_eq!(unified.len(), 1);
+        assert_eq!(unified[0].filename, "_unified");
+        assert!(unified[0].exists);
+        assert_eq!(
+            unified[0].target_files.as_deref(),
+            Some(&["CLAUDE.md".to_string(), "AGENTS.md".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn project_rules_are_resolved_per_instruction_file() {
+        let dir = tmp();
+        let mut project = make_project(dir.path().to_str().unwrap(), &["claude", "codex"]);
+        project
+            .file_rules
+            .insert("CLAUDE.md".into(), vec!["style".into(), "gone".into()]);
+        project.custom_rules.push(CustomRule {
+            name: "Local".into(),
+            content: "Run make check.".into(),
+        });
+        let read = |name: &str| match name {
+            "gone" => Err("not found".to_string()),
+            other => Ok(format!("# {}\n", other)),
+        };
+
+        let files = project_rules_by_file_with(&project, &read);
+        assert_eq!(files.len(), 2);
+        let claude = &files[0];
+        assert_eq!(claude.filename, "CLAUDE.md");
+        assert_eq!(claude.rules, vec![MANDATORY_RULE, "style", "gone"]);
+        assert_eq!(claude.missing, vec!["gone"]);
+        assert_eq!(claude.custom_rules, vec!["Local"]);
+        assert_eq!(
+            claude.content,
+            format!("# {}\n\n# style\n\nRun make check.", MANDATORY_RULE)
+        );
+        assert_eq!(files[1].rules, vec![MANDATORY_RULE]);
+
+        project.instruction_mode = "unified".into();
+        project
+            .file_rules
+            .insert("_unified".into(), vec!["shared".into()]);
+        let files = project_rules_by_file_with(&project, &read);
+        assert!(files
+            .iter()
+            .all(|f| f.rules == vec![MANDATORY_RULE, "shared"]));
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::marketplace_data::read_templates_json;
 use super::*;
@@ -64,6 +64,29 @@ pub struct ProjectTemplate {
     /// without a dedicated struct — shape: `{ type, name?, url?, repo?, ... }`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub _author: Option<serde_json::Value>,
+    /// SPDX license identifier of the template, if declared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Set on templates imported from the marketplace so a later import can
+    /// tell whether the bundled template changed since this copy was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<BundledTemplateOrigin>,
+    /// Oldest Automatic version that understands every setting in this
+    /// template.  Stamped on save.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_app_version: Option<String>,
+}
+
+/// Provenance stamp of a template imported from the marketplace.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BundledTemplateOrigin {
+    /// Name of the bundled template (differs from the local name when it was
+    /// imported as a copy).
+    pub bundled_name: String,
+    /// Hash of the bundled template at import time.
+    pub bundled_hash: String,
+    /// Version of Automatic that performed the import.
+    pub app_version: String,
 }
 
 pub fn get_project_templates_dir() -> Result<PathBuf, String> {
@@ -113,8 +136,10 @@ pub fn save_project_template(name: &str, data: &str) -> Result<(), String> {
     }
 
     // Validate that data is valid JSON for a ProjectTemplate
-    let template: ProjectTemplate =
+    let mut template: ProjectTemplate =
         serde_json::from_str(data).map_err(|e| format!("Invalid template data: {}", e))?;
+    let uses_new_settings = template_uses_new_settings(&template);
+    stamp_min_app_version(&mut template.min_app_version, uses_new_settings);
     let pretty = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
 
     let dir = get_project_templates_dir()?;
@@ -180,6 +205,17 @@ pub fn rename_project_template(old_name: &str, new_name: &str) -> Result<(), Str
 // Templates shipped with the app, compiled in via `include_str!`.
 // These are served to the Template Marketplace UI without any network calls.
 // Users can import them into `~/.automatic/project_templates/` as editable copies.
+//
+// Teams can add their own without a release by dropping files with the same
+// schema into `~/.automatic/marketplace_templates/`.  They are listed, searched
+// and imported alongside the bundled ones, tagged `"source": "local"`.  A
+// local file whose name is already taken is skipped, as is one that does not
+// parse; both are reported in the listing's `warnings`.
+//
+// Templates from the remote index (see `remote_templates`) follow, tagged
+// `"source": "remote"`.  Only the cached copy of the index is read here; a
+// remote entry whose name is taken is dropped silently, since the index may
+// repeat templates the app already ships.
 
 /// A bundled project template marketplace entry (richer than ProjectTemplate).
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -209,12 +245,24 @@ pub struct BundledProjectTemplate {
     /// Author/provider metadata, same shape as `ProjectTemplate::_author`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub _author: Option<serde_json::Value>,
+    /// SPDX license identifier of the template, carried over on import.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
     /// Maps community skill name → GitHub source (e.g. `"wshobson/agents"`).
     /// Used during template import to auto-fetch skills that are not bundled
     /// with the app.  Bundled skills are installed without a network call;
     /// community skills listed here are fetched from raw.githubusercontent.com.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub skill_sources: HashMap<String, String>,
+    /// Maps skill name → https URL of its SKILL.md.  Takes precedence over
+    /// `skill_sources` on import, which remains the fallback when the
+    /// download fails.  Used by remote templates whose skills are not
+    /// published on skills.sh.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub skill_urls: HashMap<String, String>,
+    /// Oldest Automatic version able to import this template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_app_version: Option<String>,
 }
 
 /// All bundled marketplace templates, compiled in at build time.
@@ -284,22 +332,141 @@ pub(super) const BUNDLED_TEMPLATES: &[(&str, &str)] = &[
         "ruby-on-rails-api",
         include_str!("../../assets/marketplace/project-templates/ruby-on-rails-api.json"),
     ),
+    (
+        "go-http-service",
+        include_str!("../../assets/marketplace/project-templates/go-http-service.json"),
+    ),
+    (
+        "nuxt-web-app",
+        include_str!("../../assets/marketplace/project-templates/nuxt-web-app.json"),
+    ),
 ];
 
-/// Return all bundled marketplace templates as JSON array.
-/// Reads from `~/.automatic/marketplace/templates.json` (disk is sole source of truth).
+/// Marketplace templates from every source, each tagged with a `source` of
+/// "bundled", "local" or "remote".
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct MarketplaceTemplates {
+    pub templates: Vec<serde_json::Value>,
+    /// Local template files that were skipped, one message each, and any
+    /// problem refreshing the remote index.
+    pub warnings: Vec<String>,
+}
+
+/// Directory of user-contributed marketplace templates.
+pub fn get_local_marketplace_templates_dir() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("marketplace_templates"))
+}
+
+/// Bundled templates, read from `~/.automatic/marketplace/templates.json`
+/// (disk is sole source of truth), followed by local ones and the cached
+/// remote ones.
+pub fn list_marketplace_templates() -> Result<MarketplaceTemplates, String> {
+    let bundled: Vec<serde_json::Value> = serde_json::from_str(&read_templates_json()?)
+        .map_err(|e| format!("Failed to parse templates: {}", e))?;
+    let (local, warnings) =
+        read_local_marketplace_templates(&get_local_marketplace_templates_dir()?);
+    Ok(merge_marketplace_templates(
+        bundled,
+        local,
+        cached_remote_project_templates(),
+        warnings,
+    ))
+}
+
+/// Parse every `*.json` file in `dir`, in file name order.  Files that fail
+/// to parse or carry an invalid name become warnings.
+fn read_local_marketplace_templates(dir: &Path) -> (Vec<BundledProjectTemplate>, Vec<String>) {
+    let mut templates = Vec::new();
+    let mut warnings = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (templates, warnings);
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let file = path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|raw| {
+                serde_json::from_str::<BundledProjectTemplate>(&raw).map_err(|e| e.to_string())
+            });
+        match parsed {
+            Ok(template) if is_valid_name(&template.name) => templates.push(template),
+            Ok(template) => warnings.push(format!(
+                "{}: invalid template name '{}'",
+                file, template.name
+            )),
+            Err(e) => warnings.push(format!("{}: {}", file, e)),
+        }
+    }
+    (templates, warnings)
+}
+
+fn merge_marketplace_templates(
+    bundled: Vec<serde_json::Value>,
+    local: Vec<BundledProjectTemplate>,
+    remote: Vec<BundledProjectTemplate>,
+    mut warnings: Vec<String>,
+) -> MarketplaceTemplates {
+    let tagged = |mut value: serde_json::Value, source: &str| {
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("source".into(), source.into());
+        }
+        value
+    };
+    let mut names: std::collections::HashSet<String> = bundled
+        .iter()
+        .filter_map(|t| t.get("name").and_then(|v| v.as_str()).map(str::to_string))
+        .collect();
+    let mut templates: Vec<serde_json::Value> =
+        bundled.into_iter().map(|t| tagged(t, "bundled")).collect();
+
+    for template in local {
+        if !names.insert(template.name.clone()) {
+            warnings.push(format!(
+                "Local template '{}' skipped: a marketplace template with that name already exists",
+                template.name
+            ));
+            continue;
+        }
+        match serde_json::to_value(&template) {
+            Ok(value) => templates.push(tagged(value, "local")),
+            Err(e) => warnings.push(format!("Local template '{}': {}", template.name, e)),
+        }
+    }
+    for template in remote {
+        if !names.insert(template.name.clone()) {
+            continue;
+        }
+        match serde_json::to_value(&template) {
+            Ok(value) => templates.push(tagged(value, "remote")),
+            Err(e) => warnings.push(format!("Remote template '{}': {}", template.name, e)),
+        }
+    }
+    MarketplaceTemplates {
+        templates,
+        warnings,
+    }
+}
+
+/// Return all marketplace templates, bundled, local and remote, as JSON
+/// array.
 pub fn list_bundled_project_templates() -> Result<String, String> {
-    read_templates_json()
+    serde_json::to_string(&list_marketplace_templates()?.templates).map_err(|e| e.to_string())
 }
 
-/// Return a single bundled marketplace template by name as JSON.
-/// Reads from `~/.automatic/marketplace/templates.json`.
+/// Return a single marketplace template, bundled, local or remote, by name as
+/// JSON.
 pub fn read_bundled_project_template(name: &str) -> Result<String, String> {
-    let json = read_templates_json()?;
-    let templates: Vec<serde_json::Value> =
-        serde_json::from_str(&json).map_err(|e| format!("Failed to parse templates: {}", e))?;
-
-    for tmpl in &templates {
+    for tmpl in &list_marketplace_templates()?.templates {
         if tmpl.get("name").and_then(|v| v.as_str()) == Some(name) {
             return serde_json::to_string(tmpl).map_err(|e| e.to_string());
         }
@@ -308,131 +475,532 @@ pub fn read_bundled_project_template(name: &str) -> Result<String, String> {
     Err(format!("Bundled template '{}' not found", name))
 }
 
-/// Import a bundled marketplace template into the user's local project templates.
-/// If a template with the same name already exists it is overwritten.
+/// Result of [`import_bundled_project_template`].
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TemplateImportOutcome {
+    /// The template was written to the local library under `name`.
+    Imported { name: String },
+    /// A local template with the same name has different content; nothing
+    /// was installed or written.
+    Conflict(Box<TemplateImportConflict>),
+}
+
+/// A local template that an import would overwrite.  Resolve it by keeping
+/// the local copy, importing again with `overwrite`, or importing under a new
+/// name with `import_as`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct TemplateImportConflict {
+    pub name: String,
+    pub diff: TemplateDiff,
+    /// True when the local copy was imported from an earlier version of this
+    /// bundled template, i.e. the marketplace has something new to offer.
+    pub bundled_changed: bool,
+}
+
+/// Field-level differences between a local template and the bundled one.
+/// `added` lists what replacing the local copy would bring in, `removed`
+/// what it would drop.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct TemplateDiff {
+    pub skills: ListDiff,
+    pub agents: ListDiff,
+    pub mcp_servers: ListDiff,
+    pub files: ListDiff,
+    /// Project files present in both whose content differs.
+    pub changed_files: Vec<String>,
+    pub description_changed: bool,
+    pub instructions_changed: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct ListDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Import a bundled marketplace template into the user's local project
+/// templates, as `import_as` when given.
+///
+/// If a local template with the target name exists and differs from the
+/// bundled one, a [`TemplateImportOutcome::Conflict`] is returned and nothing
+/// is changed, unless `overwrite` is set.
+///
+/// Skills are installed as by [`import_bundled_project_template_with_skills`]
+/// without searching skills.sh.
+pub async fn import_bundled_project_template(
+    name: &str,
+    overwrite: bool,
+    import_as: Option<&str>,
+) -> Result<TemplateImportOutcome, String> {
+    import_bundled_project_template_with_skills(name, overwrite, import_as, false)
+        .await
+        .map(|report| report.outcome)
+}
+
+/// How one of a template's skills was handled on import.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillInstallStatus {
+    AlreadyInstalled,
+    InstalledFromBundle,
+    InstalledFromRemote,
+    /// The template names no source for the skill and skills.sh has no
+    /// skill by that exact name, or was not searched.
+    NotFound,
+    Failed,
+}
+
+/// Outcome of installing one skill during a template import.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct TemplateSkillInstall {
+    pub name: String,
+    pub status: SkillInstallStatus,
+    /// Where a remote skill came from: a GitHub `owner/repo` or a SKILL.md
+    /// URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TemplateSkillInstall {
+    fn new(name: &str, status: SkillInstallStatus) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            source: None,
+            error: None,
+        }
+    }
+}
+
+/// Result of [`import_bundled_project_template_with_skills`].
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct TemplateImportReport {
+    pub outcome: TemplateImportOutcome,
+    /// One entry per skill the template lists; empty on a conflict, when
+    /// nothing is installed.
+    pub skills: Vec<TemplateSkillInstall>,
+}
+
+/// [`import_bundled_project_template`], reporting what happened to each
+/// skill the template lists.
 ///
-/// Install order:
+/// Install order for skills not already installed:
 /// 1. Bundled skills — installed synchronously from the compiled-in binary.
-/// 2. Community skills — fetched asynchronously from raw.githubusercontent.com
-///    using the `skill_sources` map in the template.  Errors are logged but
-///    never propagate; a failed community skill fetch does not abort the import.
-pub async fn import_bundled_project_template(name: &str) -> Result<(), String> {
+/// 2. Other skills — downloaded from the template's `skill_urls`, then from
+///    its `skill_sources`, then, with `install_missing`, from the skills.sh
+///    skill with exactly that name.  Failures are reported per skill and
+///    never abort the import.
+pub async fn import_bundled_project_template_with_skills(
+    name: &str,
+    overwrite: bool,
+    import_as: Option<&str>,
+    install_missing: bool,
+) -> Result<TemplateImportReport, String> {
     let raw = read_bundled_project_template(name)?;
     let bundled: BundledProjectTemplate =
         serde_json::from_str(&raw).map_err(|e| format!("Invalid template: {}", e))?;
+    let target = import_as.unwrap_or(name);
+    if !is_valid_name(target) {
+        return Err("Invalid template name".into());
+    }
+    let subject = format!("Template '{}'", name);
+    if let VersionCompat::UpdateRequired { message, .. } =
+        version_compat(&subject, bundled.min_app_version.as_deref())
+    {
+        return Err(message);
+    }
+
+    let dir = get_project_templates_dir()?;
+    if !overwrite {
+        if let Some(conflict) = check_import_conflict(&dir, &bundled, target)? {
+            return Ok(TemplateImportReport {
+                outcome: TemplateImportOutcome::Conflict(Box::new(conflict)),
+                skills: Vec::new(),
+            });
+        }
+    }
+
+    let skills = install_template_skills(&bundled, install_missing).await;
+
+    write_imported_template(&dir, &bundled, target)?;
+    Ok(TemplateImportReport {
+        outcome: TemplateImportOutcome::Imported {
+            name: target.to_string(),
+        },
+        skills,
+    })
+}
 
-    // Step 1: install skills that are bundled with the app (no network).
-    install_skills_from_bundle(&bundled.skills)?;
-
-    // Step 2: fetch and install community skills (network, best-effort).
-    install_community_skills(&bundled).await;
-
-    // Convert to the standard ProjectTemplate structure for storage.
-    // _author is preserved so the user-local copy retains provenance.
-    let pt = ProjectTemplate {
-        name: bundled.name.clone(),
-        description: bundled.description,
-        skills: bundled.skills,
-        mcp_servers: bundled.mcp_servers,
-        providers: bundled.providers,
-        agents: bundled.agents,
-        project_files: bundled.project_files,
-        unified_instruction: bundled.unified_instruction,
-        unified_rules: bundled.unified_rules,
+/// Convert a bundled template to the standard ProjectTemplate structure for
+/// storage under `name`.  _author and license are preserved so the
+/// user-local copy retains provenance.
+fn imported_project_template(bundled: &BundledProjectTemplate, name: &str) -> ProjectTemplate {
+    let mut template = ProjectTemplate {
+        name: name.to_string(),
+        description: bundled.description.clone(),
+        skills: bundled.skills.clone(),
+        mcp_servers: bundled.mcp_servers.clone(),
+        providers: bundled.providers.clone(),
+        agents: bundled.agents.clone(),
+        project_files: bundled.project_files.clone(),
+        unified_instruction: bundled.unified_instruction.clone(),
+        unified_rules: bundled.unified_rules.clone(),
         user_agents: Vec::new(),
-        user_commands: bundled.user_commands,
-        _author: bundled._author,
+        user_commands: bundled.user_commands.clone(),
+        _author: bundled._author.clone(),
+        license: bundled.license.clone(),
+        imported_from: Some(BundledTemplateOrigin {
+            bundled_name: bundled.name.clone(),
+            bundled_hash: bundled_template_hash(bundled),
+            app_version: APP_VERSION.to_string(),
+        }),
+        min_app_version: bundled.min_app_version.clone(),
     };
+    let uses_new_settings = template_uses_new_settings(&template);
+    stamp_min_app_version(&mut template.min_app_version, uses_new_settings);
+    template
+}
 
-    let json = serde_json::to_string_pretty(&pt).map_err(|e| e.to_string())?;
-    save_project_template(&bundled.name, &json)
+fn bundled_template_hash(bundled: &BundledProjectTemplate) -> String {
+    compute_content_hash(&to_stable_json(bundled).unwrap_or_default())
 }
 
-/// Fetch and install community skills listed in a bundled template.
-///
-/// Only processes skills that are not already in the app bundle and are not
-/// already installed locally.  Each skill is fetched using the source from
-/// `template.skill_sources`.  Skills without a source entry are silently
-/// skipped — they remain listed as "install manually" in the dep panel.
-///
-/// All errors are logged to stderr; none propagate so a single failed fetch
-/// never aborts the overall template import.
-async fn install_community_skills(template: &BundledProjectTemplate) {
-    if template.skill_sources.is_empty() {
-        return;
+/// Compare the local template `name` in `dir` (if any) with what importing
+/// `bundled` would write.  Returns `None` when there is nothing to lose.
+fn check_import_conflict(
+    dir: &Path,
+    bundled: &BundledProjectTemplate,
+    name: &str,
+) -> Result<Option<TemplateImportConflict>, String> {
+    let path = dir.join(format!("{}.json", name));
+    if !path.exists() {
+        return Ok(None);
     }
+    let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let local: ProjectTemplate = serde_json::from_str(&raw).map_err(|e| {
+        format!(
+            "Existing template '{}' is invalid ({}); import with overwrite to replace it",
+            name, e
+        )
+    })?;
+    let incoming = imported_project_template(bundled, name);
 
-    let bundled_names: std::collections::HashSet<&str> =
-        bundled_skill_names().into_iter().collect();
+    let content = |t: &ProjectTemplate| {
+        let mut t = t.clone();
+        t.imported_from = None;
+        serde_json::to_value(t).unwrap_or_default()
+    };
+    if content(&local) == content(&incoming) {
+        return Ok(None);
+    }
 
-    let installed_names: std::collections::HashSet<String> =
+    let bundled_changed = match (&local.imported_from, &incoming.imported_from) {
+        (Some(was), Some(now)) => was.bundled_hash != now.bundled_hash,
+        _ => false,
+    };
+    Ok(Some(TemplateImportConflict {
+        name: name.to_string(),
+        diff: diff_templates(&local, &incoming),
+        bundled_changed,
+    }))
+}
+
+fn diff_templates(local: &ProjectTemplate, incoming: &ProjectTemplate) -> TemplateDiff {
+    let local_files: Vec<String> = local
+        .project_files
+        .iter()
+        .map(|f| f.filename.clone())
+        .collect();
+    let incoming_files: Vec<String> = incoming
+        .project_files
+        .iter()
+        .map(|f| f.filename.clone())
+        .collect();
+    let changed_files = incoming
+        .project_files
+        .iter()
+        .filter(|f| {
+            local
+                .project_files
+                .iter()
+                .any(|l| l.filename == f.filename && l.content != f.content)
+        })
+        .map(|f| f.filename.clone())
+        .collect();
+
+    TemplateDiff {
+        skills: list_diff(&local.skills, &incoming.skills),
+        agents: list_diff(&local.agents, &incoming.agents),
+        mcp_servers: list_diff(&local.mcp_servers, &incoming.mcp_servers),
+        files: list_diff(&local_files, &incoming_files),
+        changed_files,
+        description_changed: local.description != incoming.description,
+        instructions_changed: local.unified_instruction != incoming.unified_instruction
+            || local.unified_rules != incoming.unified_rules,
+    }
+}
+
+fn list_diff(local: &[String], incoming: &[String]) -> ListDiff {
+    ListDiff {
+        added: incoming
+            .iter()
+            .filter(|i| !local.contains(i))
+            .cloned()
+            .collect(),
+        removed: local
+            .iter()
+            .filter(|l| !incoming.contains(l))
+            .cloned()
+            .collect(),
+    }
+}
+
+fn write_imported_template(
+    dir: &Path,
+    bundled: &BundledProjectTemplate,
+    name: &str,
+) -> Result<(), String> {
+    let template = imported_project_template(bundled, name);
+    let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.json", name)), json).map_err(|e| e.to_string())
+}
+
+/// Install every skill `template` lists, in order, and report each one.
+async fn install_template_skills(
+    template: &BundledProjectTemplate,
+    install_missing: bool,
+) -> Vec<TemplateSkillInstall> {
+    let bundled_names: HashSet<&str> = bundled_skill_names().into_iter().collect();
+    let installed_names: HashSet<String> =
         list_skill_names().unwrap_or_default().into_iter().collect();
+    install_template_skills_with(
+        template,
+        install_missing,
+        &bundled_names,
+        &installed_names,
+        &RemoteSkillFetcher,
+    )
+    .await
+}
+
+/// Where a template import gets the skills it installs.  Injected so the
+/// outcome reported for each source can be tested without the network or
+/// the real skill registry.
+trait TemplateSkillFetcher {
+    /// Install `name` from the skills compiled into the app.
+    fn install_from_bundle(&self, name: &str) -> Result<(), String>;
+    /// Download the SKILL.md at `url` and install it as `name`.
+    async fn install_from_url(&self, name: &str, url: &str) -> Result<(), String>;
+    /// Fetch `name` from the GitHub repo `source` and install it.
+    async fn install_from_github(&self, name: &str, source: &str) -> Result<(), String>;
+    /// GitHub source of the skills.sh skill named exactly `name`.
+    async fn find_on_skills_sh(&self, name: &str) -> Result<Option<String>, String>;
+}
+
+/// The real sources: the app bundle, the network and skills.sh.
+struct RemoteSkillFetcher;
+
+impl TemplateSkillFetcher for RemoteSkillFetcher {
+    fn install_from_bundle(&self, name: &str) -> Result<(), String> {
+        install_skills_from_bundle(&[name.to_string()])
+    }
 
+    async fn install_from_url(&self, name: &str, url: &str) -> Result<(), String> {
+        install_skill_from_url(name, url).await
+    }
+
+    async fn install_from_github(&self, name: &str, source: &str) -> Result<(), String> {
+        install_skill_from_github(name, source).await
+    }
+
+    async fn find_on_skills_sh(&self, name: &str) -> Result<Option<String>, String> {
+        find_skill_on_skills_sh(name).await
+    }
+}
+
+/// [`install_template_skills`] against the given bundled and installed
+/// skill names and `fetcher`.
+async fn install_template_skills_with(
+    template: &BundledProjectTemplate,
+    install_missing: bool,
+    bundled_names: &HashSet<&str>,
+    installed_names: &HashSet<String>,
+    fetcher: &impl TemplateSkillFetcher,
+) -> Vec<TemplateSkillInstall> {
+    let mut report = Vec::with_capacity(template.skills.len());
     for skill_name in &template.skills {
-        // Bundled skills are already handled by install_skills_from_bundle.
-        if bundled_names.contains(skill_name.as_str()) {
-            continue;
+        let installed = installed_names.contains(skill_name);
+        let outcome = if bundled_names.contains(skill_name.as_str()) {
+            // Also restores resource files missing from an installed copy.
+            match fetcher.install_from_bundle(skill_name) {
+                Ok(()) if installed => {
+                    TemplateSkillInstall::new(skill_name, SkillInstallStatus::AlreadyInstalled)
+                }
+                Ok(()) => {
+                    TemplateSkillInstall::new(skill_name, SkillInstallStatus::InstalledFromBundle)
+                }
+                Err(e) => TemplateSkillInstall {
+                    error: Some(e),
+                    ..TemplateSkillInstall::new(skill_name, SkillInstallStatus::Failed)
+                },
+            }
+        } else if installed {
+            TemplateSkillInstall::new(skill_name, SkillInstallStatus::AlreadyInstalled)
+        } else {
+            install_remote_template_skill(template, skill_name, install_missing, fetcher).await
+        };
+        if let Some(error) = &outcome.error {
+            tracing::warn!(
+                "Template import: failed to install skill '{}': {}",
+                skill_name,
+                error
+            );
         }
-        // Skip skills the user already has installed.
-        if installed_names.contains(skill_name) {
-            continue;
+        report.push(outcome);
+    }
+    report
+}
+
+/// Install a skill that is neither bundled nor installed, trying each
+/// source in turn.  A skill with no source at all is `NotFound`; one whose
+/// sources all failed is `Failed` with the last error.
+async fn install_remote_template_skill(
+    template: &BundledProjectTemplate,
+    name: &str,
+    search_skills_sh: bool,
+    fetcher: &impl TemplateSkillFetcher,
+) -> TemplateSkillInstall {
+    let mut last_error = None;
+
+    if let Some(url) = template.skill_urls.get(name) {
+        match fetcher.install_from_url(name, url).await {
+            Ok(()) => return installed_from_remote(name, url),
+            Err(e) => last_error = Some(format!("{}: {}", url, e)),
         }
-        let Some(source) = template.skill_sources.get(skill_name) else {
-            // No source info — leave for the user to install manually.
-            continue;
-        };
+    }
 
-        match fetch_remote_skill_content(source, skill_name).await {
-            Ok(content) => {
-                if let Err(e) = save_skill(skill_name, &content) {
-                    eprintln!(
-                        "[automatic] template import: failed to save community skill '{}': {}",
-                        skill_name, e
-                    );
-                } else {
-                    let id = format!("{}/{}", source, skill_name);
-                    if let Err(e) = record_skill_source(skill_name, source, &id, "github") {
-                        eprintln!(
-                            "[automatic] template import: failed to record source for '{}': {}",
-                            skill_name, e
-                        );
-                    }
-                }
-            }
+    let source = match template.skill_sources.get(name) {
+        Some(source) => Some(source.clone()),
+        None if search_skills_sh => match fetcher.find_on_skills_sh(name).await {
+            Ok(found) => found,
             Err(e) => {
-                eprintln!(
-                    "[automatic] template import: failed to fetch community skill '{}' from '{}': {}",
-                    skill_name, source, e
-                );
+                last_error = Some(format!("skills.sh search: {}", e));
+                None
             }
+        },
+        None => None,
+    };
+    if let Some(source) = source {
+        match fetcher.install_from_github(name, &source).await {
+            Ok(()) => return installed_from_remote(name, &source),
+            Err(e) => last_error = Some(format!("{}: {}", source, e)),
         }
     }
+
+    match last_error {
+        Some(error) => TemplateSkillInstall {
+            error: Some(error),
+            ..TemplateSkillInstall::new(name, SkillInstallStatus::Failed)
+        },
+        None => TemplateSkillInstall::new(name, SkillInstallStatus::NotFound),
+    }
 }
 
-/// Search bundled templates by query (matches name, display_name, description, tags, category).
-/// Reads from `~/.automatic/marketplace/templates.json`.
-pub fn search_bundled_project_templates(query: &str) -> Result<String, String> {
-    let json = read_templates_json()?;
-    let templates: Vec<BundledProjectTemplate> =
-        serde_json::from_str(&json).map_err(|e| format!("Failed to parse templates: {}", e))?;
+fn installed_from_remote(name: &str, source: &str) -> TemplateSkillInstall {
+    TemplateSkillInstall {
+        source: Some(source.to_string()),
+        ..TemplateSkillInstall::new(name, SkillInstallStatus::InstalledFromRemote)
+    }
+}
 
+/// GitHub source of the most installed skills.sh skill named exactly `name`.
+async fn find_skill_on_skills_sh(name: &str) -> Result<Option<String>, String> {
+    let results = search_remote_skills(name, &SkillSearchOptions::default()).await?;
+    Ok(results
+        .into_iter()
+        .filter(|r| r.name == name)
+        .max_by_key(|r| r.installs)
+        .map(|r| r.source))
+}
+
+/// Fetch skill `name` from the GitHub repo `source`, save it and record
+/// where it came from.
+async fn install_skill_from_github(name: &str, source: &str) -> Result<(), String> {
+    let content = fetch_remote_skill_content(source, name).await?;
+    save_skill(name, &content)?;
+    let id = format!("{}/{}", source, name);
+    if let Err(e) = record_skill_source(name, source, &id, "github") {
+        tracing::warn!(
+            "Template import: failed to record source for '{}': {}",
+            name,
+            e
+        );
+    }
+    capture_remote_license(name, source).await;
+    Ok(())
+}
+
+/// Download a SKILL.md from `url`, save it as `name` and record the URL as
+/// its source.
+async fn install_skill_from_url(name: &str, url: &str) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
+    let resp = client
+        .get(url)
+        .header("User-Agent", "automatic-desktop/1.0")
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("server returned status {}", resp.status()));
+    }
+    let content = resp
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    save_skill(name, &content)?;
+    if let Err(e) = record_skill_source(name, url, url, "url") {
+        tracing::warn!(
+            "Template import: failed to record source for '{}': {}",
+            name,
+            e
+        );
+    }
+    Ok(())
+}
+
+/// Search marketplace templates, bundled, local and remote, by query (matches name,
+/// display_name, description, tags, category).
+pub fn search_marketplace_templates(query: &str) -> Result<MarketplaceTemplates, String> {
+    let mut listing = list_marketplace_templates()?;
     let q = query.trim().to_lowercase();
     if q.is_empty() {
-        return serde_json::to_string(&templates).map_err(|e| e.to_string());
+        return Ok(listing);
     }
 
-    let filtered: Vec<&BundledProjectTemplate> = templates
-        .iter()
-        .filter(|t| {
-            t.name.to_lowercase().contains(&q)
-                || t.display_name.to_lowercase().contains(&q)
-                || t.description.to_lowercase().contains(&q)
-                || t.category.to_lowercase().contains(&q)
-                || t.tags.iter().any(|tag| tag.to_lowercase().contains(&q))
-        })
-        .collect();
+    listing.templates.retain(|value| {
+        let Ok(t) = serde_json::from_value::<BundledProjectTemplate>(value.clone()) else {
+            return false;
+        };
+        t.name.to_lowercase().contains(&q)
+            || t.display_name.to_lowercase().contains(&q)
+            || t.description.to_lowercase().contains(&q)
+            || t.category.to_lowercase().contains(&q)
+            || t.tags.iter().any(|tag| tag.to_lowercase().contains(&q))
+    });
+    Ok(listing)
+}
 
-    serde_json::to_string(&filtered).map_err(|e| e.to_string())
+/// [`search_marketplace_templates`], returning the matches as JSON array.
+pub fn search_bundled_project_templates(query: &str) -> Result<String, String> {
+    serde_json::to_string(&search_marketplace_templates(query)?.templates)
+        .map_err(|e| e.to_string())
 }
 
 // ── Template Dependency Checking ─────────────────────────────────────────────
@@ -501,3 +1069,393 @@ pub fn check_template_dependencies(template_name: &str) -> Result<String, String
 
     serde_json::to_string(&report).map_err(|e| e.to_string())
 }
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn bundled() -> BundledProjectTemplate {
+        BundledProjectTemplate {
+            name: "nextjs-saas-starter".into(),
+            display_name: "Next.js SaaS Starter".into(),
+            description: "SaaS starter".into(),
+            category: "Web Application".into(),
+            tags: vec![],
+            skills: vec!["react".into(), "stripe".into()],
+            mcp_servers: vec!["github".into()],
+            providers: vec![],
+            agents: vec!["claude".into()],
+            project_files: vec![TemplateProjectFile {
+                filename: "CLAUDE.md".into(),
+                content: "# Starter".into(),
+            }],
+            unified_instruction: String::new(),
+            unified_rules: vec![],
+            user_commands: vec![],
+            icon: None,
+            _author: None,
+            license: None,
+            skill_sources: HashMap::new(),
+            skill_urls: HashMap::new(),
+            min_app_version: None,
+        }
+    }
+
+    fn read(dir: &Path, name: &str) -> ProjectTemplate {
+        let raw = fs::read_to_string(dir.join(format!("{}.json", name))).unwrap();
+        serde_json::from_str(&raw).unwrap()
+    }
+
+    /// A local copy of the bundled template with the user's own edits.
+    fn customise(dir: &Path) {
+        write_imported_template(dir, &bundled(), "nextjs-saas-starter").unwrap();
+        let mut local = read(dir, "nextjs-saas-starter");
+        local.skills = vec!["react".into(), "tailwind".into()];
+        local.project_files[0].content = "# My rules".into();
+        fs::write(
+            dir.join("nextjs-saas-starter.json"),
+            serde_json::to_string_pretty(&local).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn identical_or_missing_local_copy_is_not_a_conflict() {
+        let dir = tempdir().unwrap();
+        let b = bundled();
+        assert_eq!(
+            check_import_conflict(dir.path(), &b, "nextjs-saas-starter"),
+            Ok(None)
+        );
+        write_imported_template(dir.path(), &b, "nextjs-saas-starter").unwrap();
+        assert_eq!(
+            check_import_conflict(dir.path(), &b, "nextjs-saas-starter"),
+            Ok(None)
+        );
+        let stamp = read(dir.path(), "nextjs-saas-starter")
+            .imported_from
+            .unwrap();
+        assert_eq!(stamp.bundled_name, "nextjs-saas-starter");
+        assert_eq!(stamp.app_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn keep_mine_reports_a_diff_and_leaves_the_local_copy() {
+        let dir = tempdir().unwrap();
+        customise(dir.path());
+        let before = fs::read_to_string(dir.path().join("nextjs-saas-starter.json")).unwrap();
+
+        let conflict = check_import_conflict(dir.path(), &bundled(), "nextjs-saas-starter")
+            .unwrap()
+            .expect("conflict");
+        assert_eq!(conflict.diff.skills.added, vec!["stripe"]);
+        assert_eq!(conflict.diff.skills.removed, vec!["tailwind"]);
+        assert_eq!(conflict.diff.changed_files, vec!["CLAUDE.md"]);
+        assert!(conflict.diff.agents.added.is_empty());
+        assert!(!conflict.diff.description_changed);
+        assert!(!conflict.bundled_changed);
+
+        let after = fs::read_to_string(dir.path().join("nextjs-saas-starter.json")).unwrap();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn replace_overwrites_the_local_copy() {
+        let dir = tempdir().unwrap();
+        customise(dir.path());
+        write_imported_template(dir.path(), &bundled(), "nextjs-saas-starter").unwrap();
+
+        let local = read(dir.path(), "nextjs-saas-starter");
+        assert_eq!(local.skills, vec!["react", "stripe"]);
+        assert_eq!(local.project_files[0].content, "# Starter");
+    }
+
+    #[test]
+    fn import_as_copy_keeps_both_templates() {
+        let dir = tempdir().unwrap();
+        customise(dir.path());
+        let b = bundled();
+        assert_eq!(
+            check_import_conflict(dir.path(), &b, "saas-upstream"),
+            Ok(None)
+        );
+        write_imported_template(dir.path(), &b, "saas-upstream").unwrap();
+
+        let copy = read(dir.path(), "saas-upstream");
+        assert_eq!(copy.name, "saas-upstream");
+        assert_eq!(copy.skills, vec!["react", "stripe"]);
+        assert_eq!(
+            copy.imported_from.unwrap().bundled_name,
+            "nextjs-saas-starter"
+        );
+        assert_eq!(
+            read(dir.path(), "nextjs-saas-starter").skills,
+            vec!["react", "tailwind"]
+        );
+    }
+
+    #[test]
+    fn conflict_notes_when_the_bundled_template_changed() {
+        let dir = tempdir().unwrap();
+        customise(dir.path());
+        let mut newer = bundled();
+        newer.description = "SaaS starter, now with billing".into();
+
+        let conflict = check_import_conflict(dir.path(), &newer, "nextjs-saas-starter")
+            .unwrap()
+            .expect("conflict");
+        assert!(conflict.bundled_changed);
+        assert!(conflict.diff.description_changed);
+    }
+
+    #[test]
+    fn local_and_remote_marketplace_templates_are_merged_with_warnings() {
+        let dir = tempdir().unwrap();
+        let mut team = bundled();
+        team.name = "team-service".into();
+        fs::write(
+            dir.path().join("team.json"),
+            serde_json::to_string(&team).unwrap(),
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("clash.json"),
+            serde_json::to_string(&bundled()).unwrap(),
+        )
+        .unwrap();
+        fs::write(dir.path().join("broken.json"), "{ \"name\": ").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a template").unwrap();
+
+        let (local, warnings) = read_local_marketplace_templates(dir.path());
+        assert_eq!(local.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("broken.json: "));
+
+        let mut go_api = bundled();
+        go_api.name = "go-api".into();
+        let remote = vec![bundled(), go_api];
+
+        let bundled_json = vec![serde_json::to_value(bundled()).unwrap()];
+        let listing = merge_marketplace_templates(bundled_json, local, remote, warnings);
+        let sources: Vec<(&str, &str)> = listing
+            .templates
+            .iter()
+            .map(|t| (t["name"].as_str().unwrap(), t["source"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            sources,
+            [
+                ("nextjs-saas-starter", "bundled"),
+                ("team-service", "local"),
+                ("go-api", "remote")
+            ]
+        );
+        assert_eq!(listing.warnings.len(), 2);
+        assert!(listing.warnings[1].contains("'nextjs-saas-starter' skipped"));
+    }
+
+    /// Stand-in sources: installs from the URLs and GitHub sources listed in
+    /// `working` succeed, and skills.sh knows the skills in `skills_sh`.
+    #[derive(Default)]
+    struct FakeFetcher {
+        working: Vec<&'static str>,
+        skills_sh: HashMap<&'static str, &'static str>,
+        skills_sh_down: bool,
+        bundle_broken: bool,
+        searched: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl TemplateSkillFetcher for FakeFetcher {
+        fn install_from_bundle(&self, _name: &str) -> Result<(), String> {
+            if self.bundle_broken {
+                return Err("disk full".into());
+            }
+            Ok(())
+        }
+
+        async fn install_from_url(&self, _name: &str, url: &str) -> Result<(), String> {
+            if self.working.contains(&url) {
+                Ok(())
+            } else {
+                Err("server returned status 404".into())
+            }
+        }
+
+        async fn install_from_github(&self, _name: &str, source: &str) -> Result<(), String> {
+            if self.working.contains(&source) {
+                Ok(())
+            } else {
+                Err("not found".into())
+            }
+        }
+
+        async fn find_on_skills_sh(&self, name: &str) -> Result<Option<String>, String> {
+            self.searched.borrow_mut().push(name.to_string());
+            if self.skills_sh_down {
+                return Err("timed out".into());
+            }
+            Ok(self.skills_sh.get(name).map(|s| s.to_string()))
+        }
+    }
+
+    async fn install(
+        template: &BundledProjectTemplate,
+        install_missing: bool,
+        fetcher: &FakeFetcher,
+    ) -> Vec<(String, SkillInstallStatus, Option<String>, Option<String>)> {
+        let bundled_names: HashSet<&str> = ["react", "tailwind"].into();
+        let installed_names: HashSet<String> = ["react".to_string(), "zod".to_string()].into();
+        install_template_skills_with(
+            template,
+            install_missing,
+            &bundled_names,
+            &installed_names,
+            fetcher,
+        )
+        .await
+        .into_iter()
+        .map(|s| (s.name, s.status, s.source, s.error))
+        .collect()
+    }
+
+    fn outcome(
+        name: &str,
+        status: SkillInstallStatus,
+        source: Option<&str>,
+        error: Option<&str>,
+    ) -> (String, SkillInstallStatus, Option<String>, Option<String>) {
+        (
+            name.into(),
+            status,
+            source.map(str::to_string),
+            error.map(str::to_string),
+        )
+    }
+
+    #[tokio::test]
+    async fn installed_and_bundled_skills_need_no_download() {
+        use SkillInstallStatus::*;
+        let mut template = bundled();
+        template.skills = vec!["react".into(), "tailwind".into(), "zod".into()];
+
+        let fetcher = FakeFetcher::default();
+        assert_eq!(
+            install(&template, true, &fetcher).await,
+            vec![
+                outcome("react", AlreadyInstalled, None, None),
+                outcome("tailwind", InstalledFromBundle, None, None),
+                outcome("zod", AlreadyInstalled, None, None),
+            ]
+        );
+        assert!(fetcher.searched.borrow().is_empty());
+
+        let broken = FakeFetcher {
+            bundle_broken: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            install(&template, true, &broken).await[1],
+            outcome("tailwind", Failed, None, Some("disk full"))
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_skills_try_the_url_then_the_github_source() {
+        use SkillInstallStatus::*;
+        let mut template = bundled();
+        template.skills = vec!["stripe".into(), "prisma".into(), "drizzle".into()];
+        template.skill_urls = HashMap::from([
+            (
+                "stripe".into(),
+                "https://example.com/stripe/SKILL.md".into(),
+            ),
+            ("prisma".into(), "https://example.com/gone/SKILL.md".into()),
+        ]);
+        template.skill_sources = HashMap::from([
+            ("prisma".into(), "acme/skills".into()),
+            ("drizzle".into(), "acme/missing".into()),
+        ]);
+
+        let fetcher = FakeFetcher {
+            working: vec!["https://example.com/stripe/SKILL.md", "acme/skills"],
+            ..Default::default()
+        };
+        assert_eq!(
+            install(&template, true, &fetcher).await,
+            vec![
+                outcome(
+                    "stripe",
+                    InstalledFromRemote,
+                    Some("https://example.com/stripe/SKILL.md"),
+                    None
+                ),
+                outcome("prisma", InstalledFromRemote, Some("acme/skills"), None),
+                outcome("drizzle", Failed, None, Some("acme/missing: not found")),
+            ]
+        );
+        // A listed source is used without searching skills.sh.
+        assert!(fetcher.searched.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unlisted_skills_fall_back_to_an_exact_skills_sh_match() {
+        use SkillInstallStatus::*;
+        let mut template = bundled();
+        template.skills = vec!["stripe".into(), "obscure".into(), "vercel".into()];
+        template.skill_urls = HashMap::from([(
+            "vercel".into(),
+            "https://example.com/vercel/SKILL.md".into(),
+        )]);
+
+        let fetcher = FakeFetcher {
+            working: vec!["stripe/agent-skills"],
+            skills_sh: HashMap::from([("stripe", "stripe/agent-skills")]),
+            ..Default::default()
+        };
+        assert_eq!(
+            install(&template, true, &fetcher).await,
+            vec![
+                outcome(
+                    "stripe",
+                    InstalledFromRemote,
+                    Some("stripe/agent-skills"),
+                    None
+                ),
+                outcome("obscure", NotFound, None, None),
+                outcome(
+                    "vercel",
+                    Failed,
+                    None,
+                    Some("https://example.com/vercel/SKILL.md: server returned status 404")
+                ),
+            ]
+        );
+        assert_eq!(
+            *fetcher.searched.borrow(),
+            vec!["stripe", "obscure", "vercel"]
+        );
+
+        // Without `install_missing` skills.sh is never asked.
+        let offline = FakeFetcher::default();
+        assert_eq!(
+            install(&template, false, &offline).await[..2],
+            [
+                outcome("stripe", NotFound, None, None),
+                outcome("obscure", NotFound, None, None),
+            ]
+        );
+        assert!(offline.searched.borrow().is_empty());
+
+        let down = FakeFetcher {
+            skills_sh_down: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            install(&template, true, &down).await[0],
+            outcome("stripe", Failed, None, Some("skills.sh search: timed out"))
+        );
+    }
+}
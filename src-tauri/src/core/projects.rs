@@ -1,6 +1,7 @@
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::*;
 
@@ -10,6 +11,10 @@ use super::*;
 // A lightweight registry entry at `~/.automatic/projects/{name}.json` maps project
 // names to their directories so we can enumerate them.  When a project has no
 // directory set yet, the full config lives in the registry file as a fallback.
+// Projects with `store_config_in_repo: false` also keep the full config in the
+// registry, and their snapshots and notes under
+// `~/.automatic/projects-data/{name}/`, so nothing is written to the repo's
+// `.automatic/`.
 
 /// Returns the path to the full project config inside the project directory.
 fn project_config_path(directory: &str) -> PathBuf {
@@ -18,6 +23,48 @@ fn project_config_path(directory: &str) -> PathBuf {
         .join("project.json")
 }
 
+/// Where `project` keeps Automatic's own files (instruction snapshots,
+/// notes, sync generations): `<directory>/.automatic/`, or
+/// `~/.automatic/projects-data/<name>/` when it keeps its config out of the
+/// repository.  `None` without a directory.
+pub fn project_data_dir(project: &Project) -> Option<PathBuf> {
+    if project.directory.is_empty() {
+        return None;
+    }
+    if project.stores_config_in_repo() {
+        return Some(PathBuf::from(&project.directory).join(".automatic"));
+    }
+    get_projects_data_dir()
+        .ok()
+        .map(|dir| dir.join(&project.name))
+}
+
+/// Refuse to write `what` into `<directory>/.automatic/` for a project that
+/// keeps its config out of the repository.  Used for files agents read
+/// from the repo (`context.json`, `docs.json`), which cannot be relocated.
+pub fn check_repo_storage(project: &Project, what: &str) -> Result<(), String> {
+    if project.stores_config_in_repo() {
+        return Ok(());
+    }
+    Err(format!(
+        "{} is stored in .automatic/ inside the project directory, and project '{}' is set not to write there. Turn on \"Store config in repository\" to edit it.",
+        what, project.name
+    ))
+}
+
+/// Subdirectories of a project's data dir that move with
+/// `store_config_in_repo`.
+const PROJECT_DATA_ENTRIES: [&str; 3] = ["snapshots", "docs", "backups"];
+
+/// Serialise `project` for `project.json` in its canonical form (see
+/// [`Project::normalize`]), so re-saving an unchanged project writes the same
+/// bytes and a single added item shows up as a one-line diff.
+pub(crate) fn project_json(project: &Project) -> Result<String, String> {
+    let mut project = project.clone();
+    project.normalize();
+    to_stable_json(&project)
+}
+
 pub fn list_projects() -> Result<Vec<String>, String> {
     let projects_dir = get_projects_dir()?;
 
@@ -66,21 +113,7 @@ pub fn read_project(name: &str) -> Result<String, String> {
         },
     };
 
-    // If directory is set, try to read full config from the project directory
-    let mut project = if !registry_project.directory.is_empty() {
-        let config_path = project_config_path(&registry_project.directory);
-        if config_path.exists() {
-            let project_raw = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
-            match serde_json::from_str::<Project>(&project_raw) {
-                Ok(p) => p,
-                Err(_) => registry_project, // fall back to registry data
-            }
-        } else {
-            registry_project
-        }
-    } else {
-        registry_project
-    };
+    let mut project = resolve_project_config(registry_project)?;
 
     // Restore: write project-level metadata back into user-level registries
     // for any entries that are missing locally.  This is the key portability
@@ -91,17 +124,110 @@ pub fn read_project(name: &str) -> Result<String, String> {
     // Enrich with current user-level metadata and persist so the project
     // config stays up-to-date on disk (important for portability).
     enrich_project(&mut project);
-    if !project.directory.is_empty() {
+    if !project.directory.is_empty() && project.stores_config_in_repo() {
         let config_path = project_config_path(&project.directory);
-        if let Ok(pretty) = serde_json::to_string_pretty(&project) {
-            let _ = fs::write(&config_path, &pretty);
+        if let Ok(pretty) = project_json(&project) {
+            let _ = write_atomic(&config_path, &pretty);
         }
     }
 
-    let formatted = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
+    let formatted = to_stable_json(&project)?;
     Ok(formatted)
 }
 
+/// The full config for a registry entry.  With a directory set and the
+/// config kept in the repo, that is `.automatic/project.json` (falling back
+/// to the registry data when it is missing or unreadable).  Otherwise the
+/// registry entry is the full config, and a stale in-repo copy is ignored.
+fn resolve_project_config(registry_project: Project) -> Result<Project, String> {
+    if registry_project.directory.is_empty() || !registry_project.stores_config_in_repo() {
+        return Ok(registry_project);
+    }
+    let config_path = project_config_path(&registry_project.directory);
+    if !config_path.exists() {
+        return Ok(registry_project);
+    }
+    let project_raw = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
+    Ok(serde_json::from_str::<Project>(&project_raw).unwrap_or(registry_project))
+}
+
+/// Write `pretty`, the full config of `project`, to wherever the project
+/// keeps it.  Also completes a change of `store_config_in_repo`: moving out
+/// of the repo removes the in-repo `project.json` and moves snapshots and
+/// notes to `data_root/<name>/`; moving back reverses that.
+fn write_project_config(
+    projects_dir: &Path,
+    data_root: &Path,
+    name: &str,
+    project: &Project,
+    pretty: &str,
+) -> Result<(), String> {
+    if !projects_dir.exists() {
+        fs::create_dir_all(projects_dir).map_err(|e| e.to_string())?;
+    }
+    let registry_path = projects_dir.join(format!("{}.json", name));
+
+    if project.directory.is_empty() {
+        // No directory yet — write full config to registry
+        return write_atomic(&registry_path, pretty);
+    }
+
+    let automatic_dir = PathBuf::from(&project.directory).join(".automatic");
+    let data_dir = data_root.join(name);
+    if project.stores_config_in_repo() {
+        // Write full config to project directory
+        write_atomic(&automatic_dir.join("project.json"), pretty)?;
+        move_project_data(&data_dir, &automatic_dir, &RemovalContext::app_data())?;
+
+        // Write lightweight registry entry
+        let ref_data = serde_json::json!({
+            "name": project.name,
+            "directory": project.directory,
+        });
+        let ref_pretty = to_stable_json(&ref_data)?;
+        write_atomic(&registry_path, &ref_pretty)?;
+        let _ = fs::remove_dir(&data_dir); // only succeeds if empty
+    } else {
+        write_atomic(&registry_path, pretty)?;
+        let context = RemovalContext::project(Path::new(&project.directory));
+        let stale = automatic_dir.join("project.json");
+        if stale.exists() {
+            remove_guarded(&stale, &context)?;
+        }
+        move_project_data(&automatic_dir, &data_dir, &context)?;
+        // Other files (context.json, a .gitignore) keep the directory.
+        let _ = fs::remove_dir(&automatic_dir); // only succeeds if empty
+    }
+    Ok(())
+}
+
+/// Move the `PROJECT_DATA_ENTRIES` of `from` into `to`.  An entry that
+/// already exists at the destination is left where it is.  `context`
+/// guards removing the source after a cross-device copy.
+fn move_project_data(from: &Path, to: &Path, context: &RemovalContext) -> Result<(), String> {
+    for entry in PROJECT_DATA_ENTRIES {
+        let src = from.join(entry);
+        let dst = to.join(entry);
+        if !src.is_dir() || dst.exists() {
+            continue;
+        }
+        fs::create_dir_all(to).map_err(|e| e.to_string())?;
+        if fs::rename(&src, &dst).is_ok() {
+            continue;
+        }
+        let report = copy_skill_dir(&src, &dst)?;
+        if report.is_partial() {
+            return Err(format!(
+                "Could not move {}: {}",
+                src.display(),
+                report.skipped.join(", ")
+            ));
+        }
+        remove_guarded(&src, context)?;
+    }
+    Ok(())
+}
+
 // ── Restore from project to user-level registries ───────────────────────────
 //
 // When a project is opened on a machine that doesn't have the original
@@ -125,6 +251,9 @@ fn restore_skill_sources(project: &Project) {
     for (name, source) in &project.skill_sources {
         if !existing.contains_key(name) {
             let _ = record_skill_source(name, &source.source, &source.id, &source.kind);
+            if let Some(license) = &source.license {
+                let _ = record_skill_license(name, license);
+            }
         }
     }
 }
@@ -310,38 +439,31 @@ pub fn save_project(name: &str, data: &str) -> Result<(), String> {
 
     let mut project: Project =
         serde_json::from_str(data).map_err(|e| format!("Invalid project data: {}", e))?;
+    check_project_writable(&project)?;
+    project.validate_disabled_agents()?;
+    // The incoming data may come from a build that dropped the stamp, so also
+    // honour the one already on disk.
+    if let Ok(existing) = read_project(name) {
+        if let Ok(existing) = serde_json::from_str::<Project>(&existing) {
+            check_project_writable(&existing)?;
+            if project.min_app_version.is_none() {
+                project.min_app_version = existing.min_app_version;
+            }
+        }
+    }
     enrich_project(&mut project);
-    let pretty = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
+    let uses_new_settings = project_uses_new_settings(&project);
+    stamp_min_app_version(&mut project.min_app_version, uses_new_settings);
+    let pretty = project_json(&project)?;
 
+    // Serialise concurrent saves of the same project (app, MCP server,
+    // session hooks) on its registry file.
     let projects_dir = get_projects_dir()?;
-    if !projects_dir.exists() {
-        fs::create_dir_all(&projects_dir).map_err(|e| e.to_string())?;
-    }
-
+    let data_root = get_projects_data_dir()?;
     let registry_path = projects_dir.join(format!("{}.json", name));
-
-    if !project.directory.is_empty() {
-        // Write full config to project directory
-        let automatic_dir = PathBuf::from(&project.directory).join(".automatic");
-        if !automatic_dir.exists() {
-            fs::create_dir_all(&automatic_dir).map_err(|e| e.to_string())?;
-        }
-        let config_path = automatic_dir.join("project.json");
-        fs::write(&config_path, &pretty).map_err(|e| e.to_string())?;
-
-        // Write lightweight registry entry
-        let ref_data = serde_json::json!({
-            "name": project.name,
-            "directory": project.directory,
-        });
-        let ref_pretty = serde_json::to_string_pretty(&ref_data).map_err(|e| e.to_string())?;
-        fs::write(&registry_path, &ref_pretty).map_err(|e| e.to_string())?;
-    } else {
-        // No directory yet — write full config to registry
-        fs::write(&registry_path, &pretty).map_err(|e| e.to_string())?;
-    }
-
-    Ok(())
+    with_file_lock(&registry_path, || {
+        write_project_config(&projects_dir, &data_root, name, &project, &pretty)
+    })
 }
 
 pub fn rename_project(old_name: &str, new_name: &str) -> Result<(), String> {
@@ -355,38 +477,50 @@ pub fn rename_project(old_name: &str, new_name: &str) -> Result<(), String> {
         return Ok(());
     }
 
-    let projects_dir = get_projects_dir()?;
+    // Read the full project (via read_project which resolves directory-based configs)
+    let raw = read_project(old_name)?;
+    let project: Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+
+    rename_project_in(
+        &get_projects_dir()?,
+        &get_projects_data_dir()?,
+        old_name,
+        new_name,
+        project,
+    )
+}
+
+fn rename_project_in(
+    projects_dir: &Path,
+    data_root: &Path,
+    old_name: &str,
+    new_name: &str,
+    mut project: Project,
+) -> Result<(), String> {
     let old_registry = projects_dir.join(format!("{}.json", old_name));
     let new_registry = projects_dir.join(format!("{}.json", new_name));
 
     if !old_registry.exists() {
         return Err(format!("Project '{}' not found", old_name));
     }
-    // Only block if the target file exists and is a genuinely different project
-    // (not just a case change on a case-insensitive filesystem like macOS APFS).
-    if new_registry.exists() {
-        // Compare canonical paths: on a case-insensitive FS, a case-only rename
-        // will resolve both paths to the same inode.
-        let old_canon = old_registry.canonicalize().map_err(|e| e.to_string())?;
-        let new_canon = new_registry.canonicalize().map_err(|e| e.to_string())?;
-        if old_canon != new_canon {
-            return Err(format!("A project named '{}' already exists", new_name));
-        }
+    // On a case-insensitive filesystem (macOS APFS/HFS+, NTFS) a case-only
+    // rename makes both registry paths name the same file.  That is not a
+    // conflict, and the old file must be renamed rather than removed below —
+    // a plain remove would delete the only copy.
+    let case_only = same_path(&old_registry, &new_registry);
+    if new_registry.exists() && !case_only {
+        return Err(format!("A project named '{}' already exists", new_name));
     }
 
-    // Read the full project (via read_project which resolves directory-based configs)
-    let raw = read_project(old_name)?;
-    let mut project: Project =
-        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
-
     // Update the name field
     project.name = new_name.to_string();
     project.updated_at = chrono::Utc::now().to_rfc3339();
 
-    let pretty = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
+    let pretty = project_json(&project)?;
 
     // Write the in-directory config with the updated name
-    if !project.directory.is_empty() {
+    if !project.directory.is_empty() && project.stores_config_in_repo() {
         let config_path = project_config_path(&project.directory);
         if config_path.exists()
             || PathBuf::from(&project.directory)
@@ -405,21 +539,22 @@ pub fn rename_project(old_name: &str, new_name: &str) -> Result<(), String> {
             "name": project.name,
             "directory": project.directory,
         });
-        let ref_pretty = serde_json::to_string_pretty(&ref_data).map_err(|e| e.to_string())?;
+        let ref_pretty = to_stable_json(&ref_data)?;
         fs::write(&new_registry, &ref_pretty).map_err(|e| e.to_string())?;
     } else {
-        // No directory — write full config to new registry entry
+        // No directory, or config kept out of the repo — write full config
+        // to new registry entry
         fs::write(&new_registry, &pretty).map_err(|e| e.to_string())?;
+        let old_data = data_root.join(old_name);
+        let new_data = data_root.join(new_name);
+        if old_data.exists() && (!new_data.exists() || same_path(&old_data, &new_data)) {
+            fs::rename(&old_data, &new_data).map_err(|e| e.to_string())?;
+        }
     }
 
-    // On a case-insensitive filesystem (macOS APFS/HFS+), a case-only rename
-    // means old_registry and new_registry point to the same inode.  In that
-    // case fs::write already updated the content above, so we just need to
-    // rename the file to get the new casing on disk.  A plain remove would
-    // delete the only copy.
-    let same_file = old_registry.canonicalize().ok() == new_registry.canonicalize().ok();
-    if same_file {
-        // fs::rename handles case-only renames correctly on APFS/HFS+
+    if case_only {
+        // fs::write already updated the content; renaming gives the file its
+        // new casing on disk.
         fs::rename(&old_registry, &new_registry).map_err(|e| e.to_string())?;
     } else {
         fs::remove_file(&old_registry).map_err(|e| e.to_string())?;
@@ -439,11 +574,13 @@ pub fn delete_project(name: &str) -> Result<(), String> {
     if registry_path.exists() {
         if let Ok(raw) = fs::read_to_string(&registry_path) {
             if let Ok(project) = serde_json::from_str::<Project>(&raw) {
-                if !project.directory.is_empty() {
+                if !project.directory.is_empty() && !project.stores_config_in_repo() {
+                    let data_dir = get_projects_data_dir()?.join(name);
+                    remove_guarded(&data_dir, &RemovalContext::app_data())?;
+                } else if !project.directory.is_empty() {
                     let config_path = project_config_path(&project.directory);
-                    if config_path.exists() {
-                        let _ = fs::remove_file(&config_path);
-                    }
+                    let context = RemovalContext::project(Path::new(&project.directory));
+                    let _ = remove_guarded(&config_path, &context);
                     // Remove .automatic dir if it's now empty
                     let automatic_dir = PathBuf::from(&project.directory).join(".automatic");
                     if automatic_dir.exists() {
@@ -453,12 +590,120 @@ pub fn delete_project(name: &str) -> Result<(), String> {
             }
         }
 
-        fs::remove_file(&registry_path).map_err(|e| e.to_string())?;
+        remove_guarded(&registry_path, &RemovalContext::app_data())?;
     }
 
     Ok(())
 }
 
+// ── Ad-hoc (unregistered) projects ───────────────────────────────────────────
+//
+// Ephemeral checkouts (CI, codespaces, review worktrees) can be used without a
+// registry entry: the project is loaded straight from `.automatic/project.json`
+// and persisted back there only, so nothing is written under
+// `~/.automatic/projects/`.
+
+/// Load a project directly from `<dir>/.automatic/project.json` without
+/// consulting or writing the registry.  `directory` is set to the canonical
+/// form of `dir` (the stored value may point at another checkout), and the
+/// name falls back to the directory's basename when empty.
+///
+/// The result is flagged [`Project::unregistered`] unless a registry entry
+/// with the same name already points at this exact directory.
+pub fn load_project_from_dir(dir: &str) -> Result<Project, String> {
+    let projects_dir = get_projects_dir().ok();
+    load_project_from_dir_in(dir, projects_dir.as_deref())
+}
+
+fn load_project_from_dir_in(dir: &str, projects_dir: Option<&Path>) -> Result<Project, String> {
+    let canonical = fs::canonicalize(dir)
+        .map_err(|e| format!("Directory '{}' is not accessible: {}", dir, e))?;
+    let directory = canonical.display().to_string();
+
+    let config_path = project_config_path(&directory);
+    if !config_path.exists() {
+        return Err(format!(
+            "No .automatic/project.json found in '{}'",
+            directory
+        ));
+    }
+    let raw = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
+    let mut project: Project = serde_json::from_str(&raw)
+        .map_err(|e| format!("Invalid project data in '{}': {}", config_path.display(), e))?;
+
+    project.directory = directory;
+    if project.name.is_empty() {
+        project.name = canonical
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("project")
+            .to_string();
+    }
+
+    let registered_dir = projects_dir.and_then(|d| registered_directory_in(d, &project.name));
+    project.unregistered = registered_dir.is_none_or(|d| !same_path(d, &canonical));
+
+    Ok(project)
+}
+
+/// The directory recorded in the registry entry for `name`, if any.
+fn registered_directory_in(projects_dir: &Path, name: &str) -> Option<String> {
+    if !is_valid_name(name) {
+        return None;
+    }
+    let raw = fs::read_to_string(projects_dir.join(format!("{}.json", name))).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    value
+        .get("directory")
+        .and_then(|d| d.as_str())
+        .filter(|d| !d.is_empty())
+        .map(|d| d.to_string())
+}
+
+/// Registered project names keyed by the [`canonical_key`] of their
+/// directory.  Projects without a directory are left out.
+pub fn registered_project_directories() -> Result<HashMap<String, String>, String> {
+    let projects_dir = get_projects_dir()?;
+    Ok(list_projects()?
+        .into_iter()
+        .filter_map(|name| {
+            registered_directory_in(&projects_dir, &name).map(|dir| (canonical_key(dir), name))
+        })
+        .collect())
+}
+
+/// Persist a project through the path appropriate for how it was loaded:
+/// registered projects go through [`save_project`]; unregistered ones are
+/// written to `.automatic/project.json` only.
+pub fn persist_project(project: &Project) -> Result<(), String> {
+    if !project.unregistered {
+        let data = to_stable_json(project)?;
+        return save_project(&project.name, &data);
+    }
+
+    let mut project = project.clone();
+    enrich_project(&mut project);
+    let pretty = project_json(&project)?;
+
+    let automatic_dir = PathBuf::from(&project.directory).join(".automatic");
+    write_atomic(&automatic_dir.join("project.json"), pretty)
+}
+
+/// Key under which per-project user data (e.g. memory) is stored.  Registered
+/// projects use their name; unregistered projects use `adhoc-` plus a hash of
+/// the canonical directory, so two checkouts sharing a project name never
+/// share state.
+pub fn project_storage_key(project: &Project) -> String {
+    if !project.unregistered {
+        return project.name.clone();
+    }
+    let canonical = fs::canonicalize(&project.directory)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| project.directory.clone());
+    let digest = Sha256::digest(canonical.as_bytes());
+    format!("adhoc-{}", &hex::encode(digest)[..16])
+}
+
 // ── Test helpers (path-injectable versions of CRUD operations) ────────────────
 
 #[cfg(test)]
@@ -472,32 +717,19 @@ mod test_helpers {
         }
         let project: Project =
             serde_json::from_str(data).map_err(|e| format!("Invalid project data: {}", e))?;
-        let pretty = serde_json::to_string_pretty(&project).map_err(|e| e.to_string())?;
-
-        if !projects_dir.exists() {
-            fs::create_dir_all(projects_dir).map_err(|e| e.to_string())?;
-        }
-
-        let registry_path = projects_dir.join(format!("{}.json", name));
-
-        if !project.directory.is_empty() {
-            let automatic_dir = PathBuf::from(&project.directory).join(".automatic");
-            if !automatic_dir.exists() {
-                fs::create_dir_all(&automatic_dir).map_err(|e| e.to_string())?;
-            }
-            let config_path = automatic_dir.join("project.json");
-            fs::write(&config_path, &pretty).map_err(|e| e.to_string())?;
+        let pretty = project_json(&project)?;
+        write_project_config(
+            projects_dir,
+            &projects_data_dir_for(projects_dir),
+            name,
+            &project,
+            &pretty,
+        )
+    }
 
-            let ref_data = serde_json::json!({
-                "name": project.name,
-                "directory": project.directory,
-            });
-            let ref_pretty = serde_json::to_string_pretty(&ref_data).map_err(|e| e.to_string())?;
-            fs::write(&registry_path, &ref_pretty).map_err(|e| e.to_string())?;
-        } else {
-            fs::write(&registry_path, &pretty).map_err(|e| e.to_string())?;
-        }
-        Ok(())
+    /// The `projects-data` dir next to a test `projects_dir`.
+    pub fn projects_data_dir_for(projects_dir: &Path) -> PathBuf {
+        projects_dir.with_file_name("projects-data")
     }
 
     /// Read a project using an explicit projects dir.
@@ -521,19 +753,7 @@ mod test_helpers {
             },
         };
 
-        if !registry_project.directory.is_empty() {
-            let config_path = project_config_path(&registry_project.directory);
-            if config_path.exists() {
-                let project_raw = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
-                let full_project = match serde_json::from_str::<Project>(&project_raw) {
-                    Ok(p) => p,
-                    Err(_) => registry_project,
-                };
-                return serde_json::to_string_pretty(&full_project).map_err(|e| e.to_string());
-            }
-        }
-
-        serde_json::to_string_pretty(&registry_project).map_err(|e| e.to_string())
+        to_stable_json(&resolve_project_config(registry_project)?)
     }
 
     /// List project names using an explicit projects dir.
@@ -727,6 +947,85 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ── canonical project.json ───────────────────────────────────────────────
+
+    fn project_in_dir(dir: &Path) -> Project {
+        let mut project = Project {
+            name: "canon".to_string(),
+            directory: dir.to_str().unwrap().to_string(),
+            skills: vec!["testing".into(), "deploy".into()],
+            mcp_servers: vec!["github".into(), "automatic".into()],
+            agents: vec!["codex".into(), "claude".into()],
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            ..Default::default()
+        };
+        project
+            .file_rules
+            .insert("_project".into(), vec!["style".into(), "security".into()]);
+        project
+            .mcp_server_origins
+            .insert("github".into(), vec!["codex".into(), "claude".into()]);
+        project
+            .skill_hashes
+            .insert("testing".into(), "abc123".into());
+        project
+            .skill_hashes
+            .insert("deploy".into(), "def456".into());
+        project
+    }
+
+    fn read_config(dir: &Path) -> String {
+        fs::read_to_string(dir.join(".automatic").join("project.json")).expect("read config")
+    }
+
+    #[test]
+    fn resaving_an_unchanged_project_is_byte_identical() {
+        let (tmp, projects_dir) = setup();
+        let project = project_in_dir(tmp.path());
+        let data = serde_json::to_string(&project).unwrap();
+        save_project_at(&projects_dir, "canon", &data).expect("save");
+        let first = read_config(tmp.path());
+
+        // Re-save what was read back, as the UI does.
+        save_project_at(&projects_dir, "canon", &first).expect("resave");
+        assert_eq!(read_config(tmp.path()), first);
+
+        // Set-like lists are sorted; rule order is the user's.
+        let saved: Project = serde_json::from_str(&first).unwrap();
+        assert_eq!(saved.skills, vec!["deploy", "testing"]);
+        assert_eq!(saved.agents, vec!["claude", "codex"]);
+        assert_eq!(saved.mcp_server_origins["github"], vec!["claude", "codex"]);
+        assert_eq!(saved.file_rules["_project"], vec!["style", "security"]);
+    }
+
+    #[test]
+    fn autodetected_skill_is_a_one_line_diff() {
+        let (tmp, projects_dir) = setup();
+        let data = serde_json::to_string(&project_in_dir(tmp.path())).unwrap();
+        save_project_at(&projects_dir, "canon", &data).expect("save");
+        let before = read_config(tmp.path());
+
+        // Autodetection appends newly found skills to the end of the list.
+        let mut project: Project = serde_json::from_str(&before).unwrap();
+        project.skills.push("lint".into());
+        let data = serde_json::to_string(&project).unwrap();
+        save_project_at(&projects_dir, "canon", &data).expect("save");
+        let after = read_config(tmp.path());
+
+        let old: Vec<&str> = before.lines().collect();
+        let new: Vec<&str> = after.lines().collect();
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        assert_eq!(old.len() - prefix - suffix, 0, "no line changed");
+        assert_eq!(new[prefix..new.len() - suffix], ["    \"lint\","]);
+    }
+
     // ── overwrite ────────────────────────────────────────────────────────────
 
     // ── extract_frontmatter_name ──────────────────────────────────────────
@@ -761,6 +1060,123 @@ mod tests {
         assert_eq!(super::extract_frontmatter_name(content), None);
     }
 
+    // ── rename ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn case_only_rename_keeps_a_single_readable_project() {
+        let (_tmp, projects_dir) = setup();
+        let data_root = projects_data_dir_for(&projects_dir);
+        save_project_at(&projects_dir, "web", &minimal_project("web")).expect("save");
+        fs::create_dir_all(data_root.join("web")).unwrap();
+        fs::write(data_root.join("web").join("memory.json"), "{}").unwrap();
+
+        let raw = read_project_at(&projects_dir, "web").unwrap();
+        let project: Project = serde_json::from_str(&raw).unwrap();
+        rename_project_in(&projects_dir, &data_root, "web", "Web", project).expect("rename");
+
+        assert_eq!(list_projects_at(&projects_dir).unwrap(), vec!["Web"]);
+        let raw = read_project_at(&projects_dir, "Web").expect("read renamed");
+        let project: Project = serde_json::from_str(&raw).unwrap();
+        assert_eq!(project.name, "Web");
+        assert!(data_root.join("Web").join("memory.json").exists());
+    }
+
+    #[test]
+    fn rename_onto_another_project_is_refused() {
+        let (_tmp, projects_dir) = setup();
+        let data_root = projects_data_dir_for(&projects_dir);
+        save_project_at(&projects_dir, "web", &minimal_project("web")).expect("save");
+        save_project_at(&projects_dir, "api", &minimal_project("api")).expect("save");
+
+        let raw = read_project_at(&projects_dir, "web").unwrap();
+        let project: Project = serde_json::from_str(&raw).unwrap();
+        let err = rename_project_in(&projects_dir, &data_root, "web", "api", project).unwrap_err();
+
+        assert!(err.contains("already exists"), "{}", err);
+        assert!(projects_dir.join("web.json").exists());
+    }
+
+    // ── ad-hoc projects ──────────────────────────────────────────────────
+
+    fn write_project_json(dir: &Path, name: &str) {
+        fs::create_dir_all(dir.join(".automatic")).expect("mkdir");
+        let project = Project {
+            name: name.into(),
+            directory: "/somewhere/else".into(),
+            ..Default::default()
+        };
+        fs::write(
+            dir.join(".automatic").join("project.json"),
+            serde_json::to_string(&project).unwrap(),
+        )
+        .expect("write");
+    }
+
+    #[test]
+    fn load_from_dir_without_registry_entry_is_unregistered() {
+        let (tmp, projects_dir) = setup();
+        let project_dir = tmp.path().join("worktree");
+        write_project_json(&project_dir, "");
+
+        let project =
+            load_project_from_dir_in(project_dir.to_str().unwrap(), Some(&projects_dir)).unwrap();
+
+        assert!(project.unregistered);
+        assert_eq!(project.name, "worktree");
+        assert_eq!(
+            PathBuf::from(&project.directory),
+            fs::canonicalize(&project_dir).unwrap()
+        );
+        assert!(!projects_dir.exists(), "no registry entry must be written");
+    }
+
+    #[test]
+    fn load_from_dir_matching_registry_entry_is_registered() {
+        let (tmp, projects_dir) = setup();
+        let project_dir = tmp.path().join("main");
+        let other_dir = tmp.path().join("review");
+        write_project_json(&project_dir, "app");
+        write_project_json(&other_dir, "app");
+        fs::create_dir_all(&projects_dir).unwrap();
+        fs::write(
+            projects_dir.join("app.json"),
+            serde_json::json!({"name": "app", "directory": project_dir}).to_string(),
+        )
+        .unwrap();
+
+        let main = load_project_from_dir_in(project_dir.to_str().unwrap(), Some(&projects_dir));
+        assert!(!main.unwrap().unregistered);
+
+        // A second checkout of the same project is not the registered one.
+        let review = load_project_from_dir_in(other_dir.to_str().unwrap(), Some(&projects_dir));
+        assert!(review.unwrap().unregistered);
+    }
+
+    #[test]
+    fn load_from_dir_matches_registry_entry_spelled_differently() {
+        let (tmp, projects_dir) = setup();
+        let project_dir = tmp.path().join("main");
+        write_project_json(&project_dir, "app");
+        fs::create_dir_all(&projects_dir).unwrap();
+        let spelled = format!("{}/src/../", project_dir.display());
+        fs::write(
+            projects_dir.join("app.json"),
+            serde_json::json!({"name": "app", "directory": spelled}).to_string(),
+        )
+        .unwrap();
+
+        let project =
+            load_project_from_dir_in(project_dir.to_str().unwrap(), Some(&projects_dir)).unwrap();
+        assert!(!project.unregistered);
+    }
+
+    #[test]
+    fn load_from_dir_errors_without_project_json() {
+        let (tmp, projects_dir) = setup();
+        let result = load_project_from_dir_in(tmp.path().to_str().unwrap(), Some(&projects_dir));
+        assert!(result.is_err());
+    }
+
     // ── resolved fields roundtrip through save/read ─────────────────────
 
     #[test]
@@ -784,6 +1200,10 @@ mod tests {
                 source: "owner/repo".into(),
                 id: "owner/repo/my-skill".into(),
                 kind: "github".into(),
+                pinned: false,
+                license: None,
+                installed_hash: None,
+                imported_at: None,
             },
         );
         project
@@ -874,4 +1294,101 @@ mod tests {
         let project: Project = serde_json::from_str(&raw).expect("parse");
         assert_eq!(project.description, "v2");
     }
+
+    // ── store_config_in_repo ─────────────────────────────────────────────────
+
+    fn out_of_repo(dir: &Path, description: &str) -> String {
+        serde_json::to_string(&Project {
+            name: "oss".to_string(),
+            description: description.to_string(),
+            directory: dir.display().to_string(),
+            store_config_in_repo: Some(false),
+            ..Default::default()
+        })
+        .expect("serialize")
+    }
+
+    #[test]
+    fn out_of_repo_projects_create_nothing_in_the_directory() {
+        let (tmp, projects_dir) = setup();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(&repo).expect("mkdir");
+
+        save_project_at(&projects_dir, "oss", &out_of_repo(&repo, "mine")).expect("save");
+        assert!(!repo.join(".automatic").exists());
+
+        // The registry holds the full config.
+        let raw = read_project_at(&projects_dir, "oss").expect("read");
+        let project: Project = serde_json::from_str(&raw).expect("parse");
+        assert_eq!(project.description, "mine");
+        assert!(!project.stores_config_in_repo());
+        let data_dir = projects_data_dir_for(&projects_dir).join("oss");
+        assert!(!data_dir.exists());
+    }
+
+    #[test]
+    fn stale_in_repo_config_is_ignored_when_stored_out_of_repo() {
+        let (tmp, projects_dir) = setup();
+        let repo = tmp.path().join("repo");
+        save_project_at(&projects_dir, "oss", &out_of_repo(&repo, "registry")).expect("save");
+        write_project_json(&repo, "oss");
+
+        let raw = read_project_at(&projects_dir, "oss").expect("read");
+        let project: Project = serde_json::from_str(&raw).expect("parse");
+        assert_eq!(project.description, "registry");
+    }
+
+    #[test]
+    fn toggling_moves_config_and_data_between_repo_and_registry() {
+        let (tmp, projects_dir) = setup();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(&repo).expect("mkdir");
+        let data_dir = projects_data_dir_for(&projects_dir).join("oss");
+        let mut project: Project =
+            serde_json::from_str(&out_of_repo(&repo, "shared")).expect("parse");
+        project.store_config_in_repo = None;
+        let in_repo = serde_json::to_string(&project).expect("serialize");
+
+        save_project_at(&projects_dir, "oss", &in_repo).expect("save in repo");
+        let automatic = repo.join(".automatic");
+        fs::create_dir_all(automatic.join("snapshots")).expect("mkdir");
+        fs::write(automatic.join("snapshots").join("AGENTS.md"), "# Mine").expect("write");
+        fs::create_dir_all(automatic.join("docs")).expect("mkdir");
+        fs::write(automatic.join("docs").join("todo.md"), "- ship").expect("write");
+        // A .gitignore the user keeps in .automatic/ is not Automatic's to move.
+        fs::write(automatic.join(".gitignore"), "snapshots/\n").expect("write");
+
+        // In repo → registry.
+        save_project_at(&projects_dir, "oss", &out_of_repo(&repo, "shared")).expect("save out");
+        assert!(!automatic.join("project.json").exists());
+        assert!(!automatic.join("snapshots").exists());
+        assert!(!automatic.join("docs").exists());
+        assert_eq!(
+            fs::read_to_string(automatic.join(".gitignore")).unwrap(),
+            "snapshots/\n"
+        );
+        assert_eq!(
+            fs::read_to_string(data_dir.join("snapshots").join("AGENTS.md")).unwrap(),
+            "# Mine"
+        );
+        assert!(data_dir.join("docs").join("todo.md").exists());
+        let registry = fs::read_to_string(projects_dir.join("oss.json")).unwrap();
+        assert!(
+            registry.contains("\"store_config_in_repo\": false"),
+            "{}",
+            registry
+        );
+
+        // Registry → repo.
+        save_project_at(&projects_dir, "oss", &in_repo).expect("save back");
+        assert!(automatic.join("project.json").exists());
+        assert_eq!(
+            fs::read_to_string(automatic.join("snapshots").join("AGENTS.md")).unwrap(),
+            "# Mine"
+        );
+        assert!(automatic.join("docs").join("todo.md").exists());
+        assert!(!data_dir.exists());
+        let registry = fs::read_to_string(projects_dir.join("oss.json")).unwrap();
+        assert!(!registry.contains("store_config_in_repo"), "{}", registry);
+    }
 }
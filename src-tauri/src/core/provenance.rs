@@ -0,0 +1,214 @@
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+
+use super::*;
+
+// ── Provenance Annotations ───────────────────────────────────────────────────
+//
+// Generated files say who wrote them, so a teammate opening `CLAUDE.md` or
+// `.cursor/mcp.json` knows to change the project in Automatic rather than
+// edit the file.  Markdown marker blocks carry a one-line HTML comment; JSON
+// MCP configs carry a top-level `"$automatic"` object, but only for agents
+// that ignore unknown top-level keys (see `Agent::annotated_config_path`).
+// Agents with strict schemas (OpenCode, VS Code's `.vscode/mcp.json`) or
+// configs shared with the user's own settings (Gemini, Zed, Codex) are left
+// unannotated.
+//
+// The wording lives here and carries `ANNOTATION_VERSION`; drift detection
+// and rule recovery strip annotations before comparing, so rewording them or
+// upgrading the app never shows up as drift.
+
+/// Bumped whenever the annotation wording or metadata fields change.
+pub const ANNOTATION_VERSION: u32 = 1;
+
+/// Top-level key holding Automatic's metadata in generated JSON configs.
+pub const CONFIG_METADATA_KEY: &str = "$automatic";
+
+/// Tag every annotation comment starts with.
+const ANNOTATION_TAG: &str = "automatic:managed";
+
+const ANNOTATION_NOTE: &str =
+    "Managed by Automatic. Edit rules and project settings in the app; manual edits inside these markers will be overwritten.";
+
+const CONFIG_NOTE: &str =
+    "Managed by Automatic. Edit MCP servers in the app; manual edits to this file will be overwritten.";
+
+/// One-line HTML comment placed after the start marker of a managed
+/// Markdown section.
+pub fn marker_annotation() -> String {
+    format!(
+        "<!-- {} v{} — {} -->",
+        ANNOTATION_TAG, ANNOTATION_VERSION, ANNOTATION_NOTE
+    )
+}
+
+/// Whether `line` is an annotation written by any version of Automatic.
+pub fn is_annotation_line(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix("<!--")
+        .is_some_and(|rest| rest.trim_start().starts_with(ANNOTATION_TAG))
+}
+
+/// `content` without annotation lines, for comparisons that should not care
+/// about annotation wording.
+pub fn strip_annotation_lines(content: &str) -> String {
+    if !content.lines().any(is_annotation_line) {
+        return content.to_string();
+    }
+    let mut out = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        if !is_annotation_line(line) {
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+/// The `"$automatic"` object written into JSON configs for `project_name`.
+pub fn config_metadata(project_name: &str) -> Value {
+    json!({
+        "managed": true,
+        "project": project_name,
+        "app_version": APP_VERSION,
+        "annotation_version": ANNOTATION_VERSION,
+        "note": CONFIG_NOTE,
+    })
+}
+
+/// The `"$automatic"` object currently in the JSON config at `path`, if any.
+/// Read before a sync overwrites the file so `annotate_json_config` can keep
+/// it unchanged.
+pub fn read_config_metadata(path: &Path) -> Option<Value> {
+    let raw = fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<Value>(&raw).ok()? {
+        Value::Object(mut map) => map.remove(CONFIG_METADATA_KEY),
+        _ => None,
+    }
+}
+
+/// Add the `"$automatic"` object to the JSON config at `path`.  Does
+/// nothing when the file is missing or not a JSON object.
+///
+/// `previous` is the metadata the file held before this sync.  It is kept
+/// as-is when only `app_version` would change, so upgrading Automatic does
+/// not rewrite every committed config.  Returns `true` if the file changed.
+pub fn annotate_json_config(
+    path: &Path,
+    project_name: &str,
+    previous: Option<&Value>,
+) -> Result<bool, String> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Ok(false);
+    };
+    let Ok(Value::Object(mut map)) = serde_json::from_str::<Value>(&raw) else {
+        return Ok(false);
+    };
+
+    let fresh = config_metadata(project_name);
+    let metadata = match previous {
+        Some(previous) if same_provenance(previous, &fresh) => previous.clone(),
+        _ => fresh,
+    };
+    map.insert(CONFIG_METADATA_KEY.to_string(), metadata);
+
+    let content = to_stable_json(&map)?;
+    if content == raw {
+        return Ok(false);
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(true)
+}
+
+/// Remove the `"$automatic"` object from a parsed JSON config.
+pub fn strip_config_metadata(value: &mut Value) {
+    if let Value::Object(map) = value {
+        map.remove(CONFIG_METADATA_KEY);
+    }
+}
+
+/// Equal apart from the version that wrote them.
+fn same_provenance(a: &Value, b: &Value) -> bool {
+    let without_version = |value: &Value| -> Option<Map<String, Value>> {
+        let mut map = value.as_object()?.clone();
+        map.remove("app_version");
+        Some(map)
+    };
+    match (without_version(a), without_version(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn annotation_lines_are_recognised_and_stripped() {
+        assert!(is_annotation_line(&marker_annotation()));
+        // Older wording is still an annotation.
+        assert!(is_annotation_line(
+            "<!-- automatic:managed v0 — Generated. -->"
+        ));
+        assert!(!is_annotation_line("<!-- automatic:rules:start -->"));
+        assert!(!is_annotation_line("# Code Style"));
+
+        let content = format!(
+            "<!-- automatic:rules:start -->\n{}\nRule.\n<!-- automatic:rules:end -->\n",
+            marker_annotation()
+        );
+        assert_eq!(
+            strip_annotation_lines(&content),
+            "<!-- automatic:rules:start -->\nRule.\n<!-- automatic:rules:end -->\n"
+        );
+        assert_eq!(strip_annotation_lines("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn json_configs_gain_metadata_without_losing_servers() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mcp.json");
+        fs::write(&path, r#"{"mcpServers":{"db":{"command":"db-mcp"}}}"#).unwrap();
+
+        assert!(annotate_json_config(&path, "web", None).unwrap());
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["mcpServers"]["db"]["command"], "db-mcp");
+        assert_eq!(written[CONFIG_METADATA_KEY]["managed"], true);
+        assert_eq!(written[CONFIG_METADATA_KEY]["project"], "web");
+        assert_eq!(written[CONFIG_METADATA_KEY]["app_version"], APP_VERSION);
+        assert_eq!(read_config_metadata(&path), Some(config_metadata("web")));
+
+        // Already annotated: nothing to write.
+        assert!(!annotate_json_config(&path, "web", None).unwrap());
+
+        let mut value = written.clone();
+        strip_config_metadata(&mut value);
+        assert!(value.get(CONFIG_METADATA_KEY).is_none());
+
+        // Missing and non-object files are left alone.
+        assert!(!annotate_json_config(&dir.path().join("none.json"), "web", None).unwrap());
+        fs::write(&path, "[]").unwrap();
+        assert!(!annotate_json_config(&path, "web", None).unwrap());
+    }
+
+    #[test]
+    fn metadata_from_an_older_app_version_is_kept() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mcp.json");
+        let mut older = config_metadata("web");
+        older["app_version"] = json!("0.9.0");
+
+        fs::write(&path, r#"{"mcpServers":{}}"#).unwrap();
+        annotate_json_config(&path, "web", Some(&older)).unwrap();
+        assert_eq!(read_config_metadata(&path), Some(older.clone()));
+
+        // A different project name is real provenance and is rewritten.
+        fs::write(&path, r#"{"mcpServers":{}}"#).unwrap();
+        annotate_json_config(&path, "api", Some(&older)).unwrap();
+        assert_eq!(read_config_metadata(&path), Some(config_metadata("api")));
+    }
+}
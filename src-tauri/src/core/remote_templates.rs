@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::fsutil::{with_file_lock, write_atomic};
+use super::paths::{get_automatic_dir, is_valid_name};
+use super::project_templates::BundledProjectTemplate;
+use super::settings::read_settings;
+
+// ── Remote Template Index (~/.automatic/cache/templates.json) ────────────────
+//
+// Templates published after a release are listed in an index served from
+// `template_index_url` (Settings), a JSON array of `BundledProjectTemplate`
+// entries or an object with a `templates` array.  The index is downloaded at
+// most once per `REMOTE_INDEX_TTL_SECS`, revalidated with its ETag, and the
+// valid entries are cached on disk.  The marketplace listing only ever reads
+// the cache, so a failed download leaves the last good copy in place, and
+// with no cache the marketplace shows bundled and local templates only.
+
+/// Index fetched when the setting is absent.  An empty setting turns remote
+/// templates off.
+pub const DEFAULT_TEMPLATE_INDEX_URL: &str = "https://automatic.sh/marketplace/templates.json";
+
+/// How long a cached index is used before it is revalidated.
+const REMOTE_INDEX_TTL_SECS: i64 = 6 * 60 * 60;
+
+/// The on-disk cache of the remote index.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct RemoteTemplateCache {
+    /// Index URL the cache was fetched from; a cache for another URL is
+    /// ignored.
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    /// RFC 3339 time the index was last downloaded or revalidated.
+    fetched_at: String,
+    templates: Vec<BundledProjectTemplate>,
+}
+
+/// Result of [`fetch_remote_project_templates`].
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct RemoteTemplateFetch {
+    /// The templates now cached, fresh or not.
+    pub templates: Vec<BundledProjectTemplate>,
+    /// Index entries that were rejected, and any download failure.
+    pub warnings: Vec<String>,
+}
+
+fn remote_templates_cache_path() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("cache").join("templates.json"))
+}
+
+fn template_index_url() -> String {
+    read_settings()
+        .map(|s| s.template_index_url)
+        .unwrap_or_else(|_| DEFAULT_TEMPLATE_INDEX_URL.to_string())
+        .trim()
+        .to_string()
+}
+
+/// Refresh the cached remote index, unless it is younger than
+/// `REMOTE_INDEX_TTL_SECS` and `force` is not set.  Never fails: when the
+/// index cannot be downloaded the cached copy is returned with a warning.
+pub async fn fetch_remote_project_templates(force: bool) -> RemoteTemplateFetch {
+    let url = template_index_url();
+    if url.is_empty() {
+        return RemoteTemplateFetch::default();
+    }
+    let path = match remote_templates_cache_path() {
+        Ok(path) => path,
+        Err(e) => {
+            return RemoteTemplateFetch {
+                templates: Vec::new(),
+                warnings: vec![e],
+            }
+        }
+    };
+    let cached = read_cache(&path).filter(|c| c.url == url);
+    if !force && cached.as_ref().is_some_and(is_fresh) {
+        return RemoteTemplateFetch {
+            templates: cached.map(|c| c.templates).unwrap_or_default(),
+            warnings: Vec::new(),
+        };
+    }
+
+    let etag = cached.as_ref().and_then(|c| c.etag.clone());
+    match download_index(&url, etag.as_deref()).await {
+        Ok(IndexResponse::NotModified) => {
+            let mut cache = cached.unwrap_or_default();
+            cache.fetched_at = chrono::Utc::now().to_rfc3339();
+            write_cache(&path, &cache);
+            RemoteTemplateFetch {
+                templates: cache.templates,
+                warnings: Vec::new(),
+            }
+        }
+        Ok(IndexResponse::Index { etag, body }) => match parse_index(&body) {
+            Ok((templates, warnings)) => {
+                let cache = RemoteTemplateCache {
+                    url,
+                    etag,
+                    fetched_at: chrono::Utc::now().to_rfc3339(),
+                    templates,
+                };
+                write_cache(&path, &cache);
+                RemoteTemplateFetch {
+                    templates: cache.templates,
+                    warnings,
+                }
+            }
+            Err(e) => degraded(&url, cached, e),
+        },
+        Err(e) => degraded(&url, cached, e),
+    }
+}
+
+/// Remote templates from the cache, without touching the network.  Empty
+/// when remote templates are off or have never been fetched.
+pub(crate) fn cached_remote_project_templates() -> Vec<BundledProjectTemplate> {
+    let url = template_index_url();
+    if url.is_empty() {
+        return Vec::new();
+    }
+    remote_templates_cache_path()
+        .ok()
+        .and_then(|path| read_cache(&path))
+        .filter(|c| c.url == url)
+        .map(|c| c.templates)
+        .unwrap_or_default()
+}
+
+fn degraded(url: &str, cached: Option<RemoteTemplateCache>, error: String) -> RemoteTemplateFetch {
+    tracing::warn!("Failed to fetch template index '{}': {}", url, error);
+    let warning = match &cached {
+        Some(cache) => format!(
+            "Remote templates could not be refreshed ({}); showing the copy from {}",
+            error, cache.fetched_at
+        ),
+        None => format!("Remote templates are unavailable: {}", error),
+    };
+    RemoteTemplateFetch {
+        templates: cached.map(|c| c.templates).unwrap_or_default(),
+        warnings: vec![warning],
+    }
+}
+
+fn is_fresh(cache: &RemoteTemplateCache) -> bool {
+    chrono::DateTime::parse_from_rfc3339(&cache.fetched_at).is_ok_and(|at| {
+        let age = chrono::Utc::now().signed_duration_since(at);
+        age.num_seconds() >= 0 && age.num_seconds() < REMOTE_INDEX_TTL_SECS
+    })
+}
+
+fn read_cache(path: &Path) -> Option<RemoteTemplateCache> {
+    let raw = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Failing to cache only costs a download next time, so it is logged.
+fn write_cache(path: &Path, cache: &RemoteTemplateCache) {
+    let result = serde_json::to_string_pretty(cache)
+        .map_err(|e| e.to_string())
+        .and_then(|raw| with_file_lock(path, || write_atomic(path, &raw)));
+    if let Err(e) = result {
+        tracing::warn!("Failed to write '{}': {}", path.display(), e);
+    }
+}
+
+enum IndexResponse {
+    NotModified,
+    Index { etag: Option<String>, body: String },
+}
+
+async fn download_index(url: &str, etag: Option<&str>) -> Result<IndexResponse, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
+    let mut request = client
+        .get(url)
+        .header("User-Agent", "automatic-desktop/1.0");
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(IndexResponse::NotModified);
+    }
+    if !resp.status().is_success() {
+        return Err(format!("server returned status {}", resp.status()));
+    }
+    let etag = resp
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let body = resp
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    Ok(IndexResponse::Index { etag, body })
+}
+
+/// Parse an index, keeping the entries that are usable and describing the
+/// rest.  Fails only when the index as a whole is malformed.
+fn parse_index(body: &str) -> Result<(Vec<BundledProjectTemplate>, Vec<String>), String> {
+    let value: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid template index: {}", e))?;
+    let entries = match value {
+        serde_json::Value::Array(entries) => entries,
+        serde_json::Value::Object(mut obj) => match obj.remove("templates") {
+            Some(serde_json::Value::Array(entries)) => entries,
+            _ => return Err("Invalid template index: expected a 'templates' array".into()),
+        },
+        _ => return Err("Invalid template index: expected an array of templates".into()),
+    };
+
+    let mut templates = Vec::new();
+    let mut warnings = Vec::new();
+    let mut names = HashSet::new();
+    for (i, entry) in entries.into_iter().enumerate() {
+        let label = entry
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(|n| format!("'{}'", n))
+            .unwrap_or_else(|| format!("#{}", i + 1));
+        let result = serde_json::from_value::<BundledProjectTemplate>(entry)
+            .map_err(|e| e.to_string())
+            .and_then(|t| validate_remote_template(&t).map(|_| t));
+        match result {
+            Ok(template) if names.insert(template.name.clone()) => templates.push(template),
+            Ok(_) => warnings.push(format!("Remote template {} skipped: duplicate name", label)),
+            Err(e) => warnings.push(format!("Remote template {} skipped: {}", label, e)),
+        }
+    }
+    Ok((templates, warnings))
+}
+
+/// Remote entries are written to disk on import, so every name and path in
+/// them must stay inside the directories they are meant for.
+fn validate_remote_template(template: &BundledProjectTemplate) -> Result<(), String> {
+    if !is_valid_name(&template.name) {
+        return Err(format!("invalid template name '{}'", template.name));
+    }
+    if let Some(skill) = template.skills.iter().find(|s| !is_valid_name(s)) {
+        return Err(format!("invalid skill name '{}'", skill));
+    }
+    for file in &template.project_files {
+        let path = Path::new(&file.filename);
+        if file.filename.is_empty()
+            || path.is_absolute()
+            || path
+                .components()
+                .any(|c| !matches!(c, std::path::Component::Normal(_)))
+        {
+            return Err(format!("invalid project file path '{}'", file.filename));
+        }
+    }
+    if let Some((skill, _)) = template
+        .skill_urls
+        .iter()
+        .find(|(_, url)| !url.starts_with("https://"))
+    {
+        return Err(format!("skill URL for '{}' must use https", skill));
+    }
+    Ok(())
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(name: &str) -> serde_json::Value {
+        json!({
+            "name": name,
+            "display_name": "Remote",
+            "description": "",
+            "category": "Backend",
+            "tags": [],
+            "skills": ["api-design"],
+            "mcp_servers": [],
+            "providers": [],
+            "agents": ["claude"],
+            "project_files": [{"filename": "docs/README.md", "content": "hi"}],
+            "skill_urls": {"api-design": "https://example.com/api-design/SKILL.md"}
+        })
+    }
+
+    #[test]
+    fn index_entries_are_validated_one_by_one() {
+        let mut escaping = entry("escaping");
+        escaping["project_files"][0]["filename"] = json!("../../.bashrc");
+        let mut plain_http = entry("plain-http");
+        plain_http["skill_urls"]["api-design"] = json!("http://example.com/SKILL.md");
+        let index = json!({"templates": [
+            entry("go-api"),
+            escaping,
+            {"name": "incomplete"},
+            plain_http,
+            entry("go-api"),
+        ]});
+
+        let (templates, warnings) = parse_index(&index.to_string()).unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].name, "go-api");
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings[0].contains("'escaping'"));
+        assert!(warnings[3].contains("duplicate"));
+
+        let (templates, _) = parse_index(&json!([entry("bare-array")]).to_string()).unwrap();
+        assert_eq!(templates[0].name, "bare-array");
+        assert!(parse_index("{\"templates\": 3}").is_err());
+        assert!(parse_index("<html>").is_err());
+    }
+
+    #[test]
+    fn cache_freshness_follows_the_fetch_time() {
+        let mut cache = RemoteTemplateCache {
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+            ..Default::default()
+        };
+        assert!(is_fresh(&cache));
+        cache.fetched_at = (chrono::Utc::now() - chrono::Duration::hours(7)).to_rfc3339();
+        assert!(!is_fresh(&cache));
+        cache.fetched_at = "yesterday".into();
+        assert!(!is_fresh(&cache));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("templates.json");
+        cache.url = "https://example.com/index.json".into();
+        write_cache(&path, &cache);
+        assert_eq!(read_cache(&path).unwrap().url, cache.url);
+    }
+}
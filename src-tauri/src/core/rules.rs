@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -35,9 +36,10 @@ pub fn ensure_mandatory_rules(rules: &[String]) -> Vec<String> {
 }
 
 /// A rule stored as JSON in `~/.automatic/rules/{machine_name}.json`.
-/// The machine name (filename stem) is an immutable lowercase slug.
+/// The machine name (filename stem) is a lowercase slug, changed only through
+/// `rename_rule` so project references follow it.
 /// The display `name` can be freely renamed.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Rule {
     /// Human-readable display name (can be renamed).
     pub name: String,
@@ -47,6 +49,14 @@ pub struct Rule {
     /// by the user.  The value is the plugin's unique id.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub plugin_id: Option<String>,
+    /// Set on rules shipped with the app (see `DEFAULT_RULES`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bundled: bool,
+    /// Hash of the bundled content this copy was last installed from.  While
+    /// the content still hashes to it the copy is unedited, so a newer
+    /// default may replace it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundled_hash: Option<String>,
 }
 
 /// Summary returned by `list_rules` — machine name + display name.
@@ -57,6 +67,9 @@ pub struct RuleEntry {
     /// When set, this rule is owned by a plugin and cannot be deleted.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub plugin_id: Option<String>,
+    /// `true` for rules shipped with the app, which can be reset to default.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bundled: bool,
 }
 
 /// Validate a rule machine name: lowercase alphanumeric + hyphens only,
@@ -115,6 +128,7 @@ pub fn list_rules() -> Result<Vec<RuleEntry>, String> {
                                     id: stem.to_string(),
                                     name: rule.name,
                                     plugin_id: rule.plugin_id,
+                                    bundled: rule.bundled,
                                 });
                             }
                         }
@@ -164,13 +178,18 @@ pub fn save_rule(machine_name: &str, name: &str, content: &str) -> Result<(), St
         fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     }
 
+    // Editing a bundled rule keeps its provenance, so the edit is recognised
+    // and preserved when a newer default ships.
+    let path = dir.join(format!("{}.json", machine_name));
+    let existing = read_rule_at(&path).unwrap_or_default();
     let rule = Rule {
         name: name.to_string(),
         content: content.to_string(),
         plugin_id: None,
+        bundled: existing.bundled,
+        bundled_hash: existing.bundled_hash,
     };
     let pretty = serde_json::to_string_pretty(&rule).map_err(|e| e.to_string())?;
-    let path = dir.join(format!("{}.json", machine_name));
     fs::write(path, pretty).map_err(|e| e.to_string())
 }
 
@@ -208,6 +227,55 @@ pub fn delete_rule(machine_name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Give a rule a new machine name.  Project references are updated by the
+/// caller.
+pub fn rename_rule(old_name: &str, new_name: &str) -> Result<(), String> {
+    rename_rule_in(&get_rules_dir()?, old_name, new_name)
+}
+
+fn rename_rule_in(rules_dir: &Path, old_name: &str, new_name: &str) -> Result<(), String> {
+    if !is_valid_machine_name(old_name) || !is_valid_machine_name(new_name) {
+        return Err(
+            "Invalid rule machine name. Use lowercase letters, digits, and hyphens only.".into(),
+        );
+    }
+    if old_name == new_name {
+        return Ok(());
+    }
+    if is_mandatory_rule(old_name) {
+        return Err(format!(
+            "Cannot rename rule '{}' — it is required by Automatic",
+            old_name
+        ));
+    }
+
+    let old_path = rules_dir.join(format!("{}.json", old_name));
+    if !old_path.exists() {
+        return Err(format!("Rule '{}' not found", old_name));
+    }
+    let rule = read_rule_at(&old_path).unwrap_or_default();
+    if rule.plugin_id.is_some() {
+        return Err(format!(
+            "Cannot rename rule '{}' — it is provided by a plugin",
+            old_name
+        ));
+    }
+    // A missing default is reinstalled on the next start, which would leave
+    // both names behind.
+    if rule.bundled {
+        return Err(format!(
+            "Cannot rename rule '{}' — it ships with Automatic",
+            old_name
+        ));
+    }
+
+    let new_path = rules_dir.join(format!("{}.json", new_name));
+    if new_path.exists() {
+        return Err(format!("A rule named '{}' already exists", new_name));
+    }
+    fs::rename(&old_path, &new_path).map_err(|e| e.to_string())
+}
+
 /// Save a rule with an owning plugin id.  Used by the plugin system to
 /// install rules that cannot be deleted by the user.
 pub fn save_plugin_rule(
@@ -234,6 +302,7 @@ pub fn save_plugin_rule(
         name: name.to_string(),
         content: content.to_string(),
         plugin_id: Some(plugin_id.to_string()),
+        ..Default::default()
     };
     let pretty = serde_json::to_string_pretty(&rule).map_err(|e| e.to_string())?;
     let path = dir.join(format!("{}.json", machine_name));
@@ -241,8 +310,8 @@ pub fn save_plugin_rule(
 }
 
 /// Built-in rules shipped with the app.  Each entry is (machine_name, display_name, content).
-/// Written to `~/.automatic/rules/{machine_name}.json` on first run (or when missing),
-/// but never overwrite existing files — user edits are preserved.
+/// Written to `~/.automatic/rules/{machine_name}.json` on first run (or when missing).
+/// Later releases update unedited copies; edited copies are left alone.
 const DEFAULT_RULES: &[(&str, &str, &str)] = &[
     (
         "automatic-general",
@@ -266,68 +335,177 @@ const DEFAULT_RULES: &[(&str, &str, &str)] = &[
     ),
 ];
 
+/// Return the machine names of all rules shipped with the app.
+pub fn bundled_rule_names() -> Vec<&'static str> {
+    DEFAULT_RULES.iter().map(|(name, _, _)| *name).collect()
+}
+
+/// Hash recorded in [`Rule::bundled_hash`].
+fn rule_content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+fn read_rule_at(path: &Path) -> Option<Rule> {
+    let raw = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_rule_at(path: &Path, rule: &Rule) -> Result<(), String> {
+    let pretty = serde_json::to_string_pretty(rule).map_err(|e| e.to_string())?;
+    fs::write(path, pretty).map_err(|e| e.to_string())
+}
+
+fn default_rule(display_name: &str, content: &str) -> Rule {
+    Rule {
+        name: display_name.to_string(),
+        content: content.to_string(),
+        plugin_id: None,
+        bundled: true,
+        bundled_hash: Some(rule_content_hash(content)),
+    }
+}
+
 /// Write default rules to `~/.automatic/rules/`.
 ///
-/// When `force` is `false`, existing files are left untouched so user edits
-/// are preserved.  When `force` is `true`, every bundled rule is overwritten
-/// unconditionally — used by the "Reinstall Defaults" reset path.
-pub fn install_default_rules_inner(force: bool) -> Result<(), String> {
+/// When `force` is `false`, missing rules are installed, unedited copies
+/// are updated to the shipped content, and edited copies are left alone.
+/// Returns the machine names of edited copies whose default has changed
+/// since they were installed ("update available").  When `force` is `true`,
+/// every bundled rule is overwritten unconditionally — used by the
+/// "Reinstall Defaults" reset path.
+pub fn install_default_rules_inner(force: bool) -> Result<Vec<String>, String> {
     let dir = get_rules_dir()?;
+    let updates = install_default_rules_in(&dir, DEFAULT_RULES, force)?;
+
+    // Migration: automatic-checklist was renamed to automatic-process.
+    // 1. Delete the old rule file.
+    // 2. Replace all project file_rules references.
+    // 3. Rename .claude/rules/automatic-checklist.md in project directories.
+    migrate_checklist_to_process(&dir)?;
+
+    Ok(updates)
+}
+
+fn install_default_rules_in(
+    dir: &Path,
+    defaults: &[(&str, &str, &str)],
+    force: bool,
+) -> Result<Vec<String>, String> {
     if !dir.exists() {
-        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
     }
 
-    for (machine_name, display_name, content) in DEFAULT_RULES {
+    let mut updates = Vec::new();
+    for (machine_name, display_name, content) in defaults {
         let path = dir.join(format!("{}.json", machine_name));
         if force || !path.exists() {
-            let rule = Rule {
-                name: display_name.to_string(),
-                content: content.to_string(),
-                plugin_id: None,
-            };
-            let pretty = serde_json::to_string_pretty(&rule).map_err(|e| e.to_string())?;
-            fs::write(&path, pretty).map_err(|e| e.to_string())?;
-        } else if *machine_name == "automatic-service" {
-            // Migration: rename "Automatic MCP Service" → "Automatic"
-            if let Ok(raw) = fs::read_to_string(&path) {
-                if let Ok(mut rule) = serde_json::from_str::<Rule>(&raw) {
-                    if rule.name == "Automatic MCP Service" {
-                        rule.name = "Automatic".to_string();
-                        if let Ok(pretty) = serde_json::to_string_pretty(&rule) {
-                            let _ = fs::write(&path, pretty);
-                        }
-                    }
-                }
-            }
-        } else if *machine_name == "automatic-process" {
-            // Migration: always overwrite with the latest content so the new
-            // process rule replaces the old checklist rule's content on disk.
-            let rule = Rule {
-                name: display_name.to_string(),
-                content: content.to_string(),
-                plugin_id: None,
-            };
-            if let Ok(pretty) = serde_json::to_string_pretty(&rule) {
-                let _ = fs::write(&path, pretty);
-            }
+            write_rule_at(&path, &default_rule(display_name, content))?;
+            continue;
         }
-    }
+        let Some(mut rule) = read_rule_at(&path) else {
+            continue;
+        };
+        let original = serde_json::to_value(&rule).ok();
 
-    // Migration: automatic-checklist was renamed to automatic-process.
-    // 1. Delete the old rule file.
-    // 2. Replace all project file_rules references.
-    // 3. Rename .claude/rules/automatic-checklist.md in project directories.
-    migrate_checklist_to_process(&dir)?;
+        // Migration: rename "Automatic MCP Service" → "Automatic"
+        if *machine_name == "automatic-service" && rule.name == "Automatic MCP Service" {
+            rule.name = display_name.to_string();
+        }
 
-    Ok(())
+        let default_hash = rule_content_hash(content);
+        let current_hash = rule_content_hash(&rule.content);
+        let unedited = rule.bundled_hash.as_deref() == Some(current_hash.as_str());
+        rule.bundled = true;
+        if current_hash == default_hash || unedited {
+            rule.content = content.to_string();
+            rule.bundled_hash = Some(default_hash);
+        } else if rule.bundled_hash.as_deref() != Some(default_hash.as_str()) {
+            // Edited (or installed before provenance was recorded) and the
+            // default has moved on: keep the user's text and report it.
+            updates.push(machine_name.to_string());
+        }
+
+        if serde_json::to_value(&rule).ok() != original {
+            write_rule_at(&path, &rule)?;
+        }
+    }
+    Ok(updates)
 }
 
-/// Write any missing default rules to `~/.automatic/rules/`.
-/// Existing files are left untouched, so user edits are always preserved.
-pub fn install_default_rules() -> Result<(), String> {
+/// Write any missing default rules to `~/.automatic/rules/` and bring
+/// unedited copies up to date.  Returns the bundled rules the user edited
+/// whose default has since changed.
+pub fn install_default_rules() -> Result<Vec<String>, String> {
     install_default_rules_inner(false)
 }
 
+/// An installed bundled rule compared with the default shipped in this release.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RuleDefaultDiff {
+    pub machine_name: String,
+    /// Content currently installed.
+    pub current: String,
+    /// Content shipped with this version of the app.
+    pub default: String,
+    /// The installed copy was edited since it was installed.
+    pub modified: bool,
+    /// The installed copy differs from the shipped default.
+    pub differs: bool,
+}
+
+fn find_default_rule<'a>(
+    defaults: &'a [(&'a str, &'a str, &'a str)],
+    machine_name: &str,
+) -> Result<&'a (&'a str, &'a str, &'a str), String> {
+    defaults
+        .iter()
+        .find(|(name, _, _)| *name == machine_name)
+        .ok_or_else(|| format!("Rule '{}' is not a bundled rule", machine_name))
+}
+
+/// Overwrite a bundled rule with the content shipped with the app.
+pub fn reset_rule_to_default(machine_name: &str) -> Result<(), String> {
+    reset_rule_to_default_in(&get_rules_dir()?, DEFAULT_RULES, machine_name)
+}
+
+fn reset_rule_to_default_in(
+    dir: &Path,
+    defaults: &[(&str, &str, &str)],
+    machine_name: &str,
+) -> Result<(), String> {
+    let (_, display_name, content) = find_default_rule(defaults, machine_name)?;
+    if !dir.exists() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    write_rule_at(
+        &dir.join(format!("{}.json", machine_name)),
+        &default_rule(display_name, content),
+    )
+}
+
+/// Compare an installed bundled rule with the default shipped with the app.
+pub fn diff_rule_against_default(machine_name: &str) -> Result<RuleDefaultDiff, String> {
+    diff_rule_against_default_in(&get_rules_dir()?, DEFAULT_RULES, machine_name)
+}
+
+fn diff_rule_against_default_in(
+    dir: &Path,
+    defaults: &[(&str, &str, &str)],
+    machine_name: &str,
+) -> Result<RuleDefaultDiff, String> {
+    let (_, _, content) = find_default_rule(defaults, machine_name)?;
+    let rule = read_rule_at(&dir.join(format!("{}.json", machine_name)))
+        .ok_or_else(|| format!("Rule '{}' not found", machine_name))?;
+    let current_hash = rule_content_hash(&rule.content);
+    Ok(RuleDefaultDiff {
+        machine_name: machine_name.to_string(),
+        modified: rule.bundled_hash.as_deref() != Some(current_hash.as_str()),
+        differs: rule.content != *content,
+        current: rule.content,
+        default: content.to_string(),
+    })
+}
+
 /// Migrate the `automatic-checklist` rule to `automatic-process` across all
 /// projects and on-disk rule files.
 ///
@@ -497,7 +675,7 @@ mod tests {
         let rule = Rule {
             name: display_name.to_string(),
             content: content.to_string(),
-            plugin_id: None,
+            ..Default::default()
         };
         let json = serde_json::to_string_pretty(&rule).expect("serialize");
         fs::write(rules_dir.join(format!("{}.json", machine_name)), json).expect("write rule");
@@ -638,6 +816,27 @@ mod tests {
         assert!(!path.exists());
     }
 
+    #[test]
+    fn rename_rule_moves_file_and_refuses_existing_target() {
+        let (_tmp, rules_dir) = temp_rules_dir();
+        write_rule(&rules_dir, "old-rule", "Old", "content");
+        write_rule(&rules_dir, "taken", "Taken", "other");
+
+        assert!(rename_rule_in(&rules_dir, "old-rule", "taken")
+            .unwrap_err()
+            .contains("already exists"));
+        assert!(rename_rule_in(&rules_dir, "missing", "fresh").is_err());
+        assert!(rename_rule_in(&rules_dir, "old-rule", "Not Valid").is_err());
+
+        rename_rule_in(&rules_dir, "old-rule", "new-rule").unwrap();
+        assert!(!rules_dir.join("old-rule.json").exists());
+        assert_eq!(
+            read_rule_from_dir(&rules_dir, "new-rule").content,
+            "content"
+        );
+        assert_eq!(read_rule_from_dir(&rules_dir, "taken").content, "other");
+    }
+
     #[test]
     fn rule_with_empty_content_round_trips() {
         let (_tmp, rules_dir) = temp_rules_dir();
@@ -720,4 +919,103 @@ mod tests {
         let result = ensure_mandatory_rules(&rules);
         assert_eq!(result.len(), 1);
     }
+
+    // ── Bundled rule upgrades ────────────────────────────────────────────────
+
+    const V1: &[(&str, &str, &str)] = &[("automatic-general", "General", "Be careful.\n")];
+    const V2: &[(&str, &str, &str)] = &[("automatic-general", "General", "Be very careful.\n")];
+
+    #[test]
+    fn fresh_install_records_provenance() {
+        let (_tmp, rules_dir) = temp_rules_dir();
+        assert!(install_default_rules_in(&rules_dir, V1, false)
+            .unwrap()
+            .is_empty());
+        let rule = read_rule_from_dir(&rules_dir, "automatic-general");
+        assert!(rule.bundled);
+        assert_eq!(rule.bundled_hash, Some(rule_content_hash("Be careful.\n")));
+    }
+
+    #[test]
+    fn unmodified_copy_is_upgraded() {
+        let (_tmp, rules_dir) = temp_rules_dir();
+        install_default_rules_in(&rules_dir, V1, false).unwrap();
+
+        let updates = install_default_rules_in(&rules_dir, V2, false).unwrap();
+        assert!(updates.is_empty());
+        let rule = read_rule_from_dir(&rules_dir, "automatic-general");
+        assert_eq!(rule.content, "Be very careful.\n");
+        assert_eq!(
+            rule.bundled_hash,
+            Some(rule_content_hash("Be very careful.\n"))
+        );
+    }
+
+    #[test]
+    fn modified_copy_is_kept_and_flagged() {
+        let (_tmp, rules_dir) = temp_rules_dir();
+        install_default_rules_in(&rules_dir, V1, false).unwrap();
+        let mut rule = read_rule_from_dir(&rules_dir, "automatic-general");
+        rule.content = "My own wording.\n".into();
+        fs::write(
+            rules_dir.join("automatic-general.json"),
+            serde_json::to_string_pretty(&rule).unwrap(),
+        )
+        .unwrap();
+
+        // Same default: the edit stands and nothing is flagged.
+        assert!(install_default_rules_in(&rules_dir, V1, false)
+            .unwrap()
+            .is_empty());
+
+        // New default: still kept, but reported as an available update.
+        let updates = install_default_rules_in(&rules_dir, V2, false).unwrap();
+        assert_eq!(updates, vec!["automatic-general"]);
+        let kept = read_rule_from_dir(&rules_dir, "automatic-general");
+        assert_eq!(kept.content, "My own wording.\n");
+
+        let diff = diff_rule_against_default_in(&rules_dir, V2, "automatic-general").unwrap();
+        assert!(diff.modified && diff.differs);
+        assert_eq!(diff.default, "Be very careful.\n");
+
+        reset_rule_to_default_in(&rules_dir, V2, "automatic-general").unwrap();
+        let diff = diff_rule_against_default_in(&rules_dir, V2, "automatic-general").unwrap();
+        assert!(!diff.modified && !diff.differs);
+        assert!(install_default_rules_in(&rules_dir, V2, false)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn copies_installed_before_provenance_are_adopted_or_flagged() {
+        let (_tmp, rules_dir) = temp_rules_dir();
+        write_rule(
+            &rules_dir,
+            "automatic-general",
+            "General",
+            "Be very careful.\n",
+        );
+        assert!(install_default_rules_in(&rules_dir, V2, false)
+            .unwrap()
+            .is_empty());
+        assert!(read_rule_from_dir(&rules_dir, "automatic-general")
+            .bundled_hash
+            .is_some());
+
+        write_rule(&rules_dir, "automatic-general", "General", "Old text.\n");
+        let updates = install_default_rules_in(&rules_dir, V2, false).unwrap();
+        assert_eq!(updates, vec!["automatic-general"]);
+        assert_eq!(
+            read_rule_from_dir(&rules_dir, "automatic-general").content,
+            "Old text.\n"
+        );
+    }
+
+    #[test]
+    fn only_bundled_rules_can_be_reset() {
+        let (_tmp, rules_dir) = temp_rules_dir();
+        assert!(reset_rule_to_default_in(&rules_dir, V1, "my-rule")
+            .unwrap_err()
+            .contains("not a bundled rule"));
+    }
 }
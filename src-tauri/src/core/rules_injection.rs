@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
@@ -33,31 +34,108 @@ pub(crate) fn strip_rules_section(content: &str) -> String {
     }
 }
 
-/// Build the rules section content from a list of rule machine names plus any
-/// inline custom rule content strings.  Both sources are combined in order:
-/// global rules first, then custom rules.
-pub fn build_rules_section(rule_names: &[String]) -> Result<String, String> {
-    build_rules_section_with_custom(rule_names, &[])
+/// What became of one registry rule when a rules section was built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleSectionStatus {
+    Included,
+    /// The rule exists but has no content, so nothing was written for it.
+    SkippedEmpty,
+    /// No rule with this machine name is in the registry.
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RulePreviewEntry {
+    pub name: String,
+    pub status: RuleSectionStatus,
+}
+
+/// A compiled rules section together with the status of each rule that was
+/// asked for, in the order given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RulesPreview {
+    /// The marked section as written into instruction files; empty when no
+    /// rule had content.
+    pub section: String,
+    pub rules: Vec<RulePreviewEntry>,
+}
+
+impl RulesPreview {
+    /// The rules left out of the section.
+    pub fn skipped(&self) -> impl Iterator<Item = &RulePreviewEntry> {
+        self.rules
+            .iter()
+            .filter(|r| r.status != RuleSectionStatus::Included)
+    }
+}
+
+/// A rule attached to an instruction file that sync leaves out of the
+/// file's rules section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleWarning {
+    pub file: String,
+    pub rule: String,
+    pub status: RuleSectionStatus,
+}
+
+impl RuleWarning {
+    /// One-line description for logs and reports.
+    pub fn message(&self) -> String {
+        let reason = match self.status {
+            RuleSectionStatus::Missing => "does not exist",
+            _ => "is empty",
+        };
+        format!(
+            "Rule '{}' attached to {} {} and was skipped",
+            self.rule, self.file, reason
+        )
+    }
+}
+
+/// Build the rules section for a list of rule machine names, reporting the
+/// rules that were skipped for being missing or empty.
+pub fn build_rules_section(rule_names: &[String]) -> Result<RulesPreview, String> {
+    Ok(compile_rules_section(rule_names, &[], |name| {
+        read_rule_content(name).ok()
+    }))
 }
 
 /// Build the rules section from global rule machine names and inline custom
-/// rule content strings.  Either slice may be empty.
+/// rule content strings.  Either slice may be empty.  Global rules come
+/// first, then custom rules; missing or empty rules are left out.
 pub fn build_rules_section_with_custom(
     rule_names: &[String],
     custom_contents: &[String],
 ) -> Result<String, String> {
+    Ok(compile_rules_section(rule_names, custom_contents, |name| {
+        read_rule_content(name).ok()
+    })
+    .section)
+}
+
+fn compile_rules_section(
+    rule_names: &[String],
+    custom_contents: &[String],
+    read_rule: impl Fn(&str) -> Option<String>,
+) -> RulesPreview {
     let mut parts: Vec<String> = Vec::new();
+    let mut rules: Vec<RulePreviewEntry> = Vec::new();
 
     // Global rules resolved from the registry
     for machine_name in rule_names {
-        match read_rule_content(machine_name) {
-            Ok(content) if !content.trim().is_empty() => {
+        let status = match read_rule(machine_name) {
+            Some(content) if !content.trim().is_empty() => {
                 parts.push(content);
+                RuleSectionStatus::Included
             }
-            _ => {
-                // Skip missing or empty rules silently
-            }
-        }
+            Some(_) => RuleSectionStatus::SkippedEmpty,
+            None => RuleSectionStatus::Missing,
+        };
+        rules.push(RulePreviewEntry {
+            name: machine_name.clone(),
+            status,
+        });
     }
 
     // Inline custom rules stored directly in the project
@@ -67,13 +145,22 @@ pub fn build_rules_section_with_custom(
         }
     }
 
+    RulesPreview {
+        section: assemble_rules_section(&parts),
+        rules,
+    }
+}
+
+fn assemble_rules_section(parts: &[String]) -> String {
     if parts.is_empty() {
-        return Ok(String::new());
+        return String::new();
     }
 
     let mut section = String::new();
     section.push_str(RULES_START_MARKER);
     section.push('\n');
+    section.push_str(&marker_annotation());
+    section.push('\n');
     for (i, part) in parts.iter().enumerate() {
         if i > 0 {
             section.push('\n');
@@ -83,7 +170,7 @@ pub fn build_rules_section_with_custom(
     }
     section.push_str(RULES_END_MARKER);
 
-    Ok(section)
+    section
 }
 
 /// Write a project file with rules appended.  The user content is written
@@ -144,8 +231,9 @@ pub fn is_file_rules_current_with_custom(
         return Ok(!raw.contains(RULES_START_MARKER));
     }
 
-    // Check if the file contains the exact expected rules section.
-    Ok(raw.contains(&expected_section))
+    // Check if the file contains the exact expected rules section.  The
+    // annotation line is left out so rewording it is not drift.
+    Ok(strip_annotation_lines(&raw).contains(&strip_annotation_lines(&expected_section)))
 }
 
 // ── .claude/rules/ directory-based rules ────────────────────────────────────
@@ -175,6 +263,7 @@ pub fn sync_rules_to_dot_claude_rules(
     // Remove files that were previously managed by Automatic but are no
     // longer in the rule list.  We only remove files whose first line
     // carries the Automatic-managed marker so we never clobber user files.
+    let context = RemovalContext::project(&rules_dir);
     if let Ok(entries) = fs::read_dir(&rules_dir) {
         for entry in entries.flatten() {
             let path = entry.path();
@@ -191,7 +280,7 @@ pub fn sync_rules_to_dot_claude_rules(
             // Only remove files that carry our managed header.
             if let Ok(content) = fs::read_to_string(&path) {
                 if content.starts_with(CLAUDE_RULES_MANAGED_HEADER) {
-                    if fs::remove_file(&path).is_ok() {
+                    if remove_guarded(&path, &context).is_ok() {
                         touched.push(path.display().to_string());
                     }
                 }
@@ -328,6 +417,68 @@ pub fn inject_rules_into_project_file_with_custom(
     }
 }
 
+/// Replace the rules attached to the project's `filename` key with
+/// `ordered_rules`, in that order, persist the project and rewrite the
+/// file's rules section.  An empty list detaches every rule from the key.
+/// Re-syncing the project is left to the caller.
+pub fn set_project_file_rules(
+    project: &mut Project,
+    filename: &str,
+    ordered_rules: &[String],
+) -> Result<(), String> {
+    let known: Vec<String> = list_rules()?.into_iter().map(|r| r.id).collect();
+    attach_file_rules(project, filename, ordered_rules, &known)?;
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    persist_project(project)?;
+
+    // Files whose rules live in .claude/rules/ are written by the sync.
+    if !project_uses_dot_claude_rules(project, filename)
+        && inject_rules_into_project_file(&project.directory, filename, ordered_rules)?
+    {
+        let path = PathBuf::from(&project.directory).join(filename);
+        log_sync_history(
+            project,
+            SyncAction::RulesInjection,
+            None,
+            &[path.display().to_string()],
+            &[],
+        );
+    }
+    Ok(())
+}
+
+/// Check `ordered_rules` against the `known` rule names and record them in
+/// `project.file_rules` under `filename`.
+fn attach_file_rules(
+    project: &mut Project,
+    filename: &str,
+    ordered_rules: &[String],
+    known: &[String],
+) -> Result<(), String> {
+    if filename.trim().is_empty() {
+        return Err("Filename cannot be empty".into());
+    }
+    project.ensure_unlocked()?;
+
+    for (i, rule) in ordered_rules.iter().enumerate() {
+        if !known.contains(rule) {
+            return Err(format!("Rule '{}' not found", rule));
+        }
+        if ordered_rules[..i].contains(rule) {
+            return Err(format!("Rule '{}' is listed more than once", rule));
+        }
+    }
+
+    if ordered_rules.is_empty() {
+        project.file_rules.remove(filename);
+    } else {
+        project
+            .file_rules
+            .insert(filename.to_string(), ordered_rules.to_vec());
+    }
+    Ok(())
+}
+
 // ── Project Group Context Injection ──────────────────────────────────────────
 
 const GROUPS_START_MARKER: &str = "<!-- automatic:groups:start -->";
@@ -441,7 +592,13 @@ pub fn build_groups_section(
     inner.push_str("The following projects are related to this one. They are provided for context — explore or reference them when relevant to the current task.\n\n");
     inner.push_str(&parts.join("\n"));
 
-    format!("{}\n{}\n{}", GROUPS_START_MARKER, inner, GROUPS_END_MARKER)
+    format!(
+        "{}\n{}\n{}\n{}",
+        GROUPS_START_MARKER,
+        marker_annotation(),
+        inner,
+        GROUPS_END_MARKER
+    )
 }
 
 /// Compute a relative path from `from_dir` to `to_dir`.
@@ -464,7 +621,10 @@ pub fn compute_relative_path(from_dir: &str, to_dir: &str) -> String {
     let common_len = from_components
         .iter()
         .zip(to_components.iter())
-        .take_while(|(a, b)| a == b)
+        .take_while(|(a, b)| {
+            fold_path_case(&a.as_os_str().to_string_lossy())
+                == fold_path_case(&b.as_os_str().to_string_lossy())
+        })
         .count();
 
     // If nothing is shared (e.g. different drive letters on Windows), fall back.
@@ -549,6 +709,20 @@ pub fn inject_groups_into_project_file(
     }
 }
 
+/// The text between the rules markers, without the annotation line, or
+/// `None` when the file has no complete rules section.
+pub(crate) fn rules_section_body(content: &str) -> Option<&str> {
+    let start = content.find(RULES_START_MARKER)? + RULES_START_MARKER.len();
+    let end = content[start..].find(RULES_END_MARKER)? + start;
+    let body = &content[start..end];
+    let first = body.trim_start().lines().next().unwrap_or("");
+    if is_annotation_line(first) {
+        let skip = body.find(first).unwrap_or(0) + first.len();
+        return Some(&body[skip..]);
+    }
+    Some(body)
+}
+
 /// Extract the raw `<!-- automatic:rules:start -->...<!-- automatic:rules:end -->` block
 /// (including markers) from a file, or return an empty string if absent.
 fn extract_rules_section(content: &str) -> String {
@@ -562,6 +736,34 @@ fn extract_rules_section(content: &str) -> String {
     }
 }
 
+/// The groups and rules sections of an instruction file, markers included,
+/// laid out as they are written.  Empty when the file has neither.
+pub fn extract_managed_sections(content: &str) -> String {
+    let sections: Vec<String> = [
+        extract_groups_section(content),
+        extract_rules_section(content),
+    ]
+    .into_iter()
+    .filter(|section| !section.is_empty())
+    .collect();
+    if sections.is_empty() {
+        return String::new();
+    }
+    format!("{}\n", sections.join("\n\n"))
+}
+
+/// `current` with its managed sections replaced by `sections` (as returned
+/// by [`extract_managed_sections`]).  The user content of `current` is kept
+/// byte for byte apart from surrounding whitespace.
+pub fn replace_managed_sections(current: &str, sections: &str) -> String {
+    let user_content = strip_groups_section(&strip_rules_section(&strip_managed_section(current)));
+    assemble_file(
+        &user_content,
+        &extract_groups_section(sections),
+        &extract_rules_section(sections),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -670,6 +872,28 @@ mod tests {
         assert!(result.contains("trimmed content"));
     }
 
+    #[test]
+    fn compile_reports_missing_and_empty_rules() {
+        let names: Vec<String> = ["style", "blank", "gone"].map(String::from).to_vec();
+        let preview = compile_rules_section(&names, &[], |name| match name {
+            "style" => Some("Use tabs.".to_string()),
+            "blank" => Some("  \n".to_string()),
+            _ => None,
+        });
+        assert!(preview.section.contains("Use tabs."));
+        let statuses: Vec<_> = preview.rules.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                RuleSectionStatus::Included,
+                RuleSectionStatus::SkippedEmpty,
+                RuleSectionStatus::Missing
+            ]
+        );
+        let skipped: Vec<&str> = preview.skipped().map(|r| r.name.as_str()).collect();
+        assert_eq!(skipped, vec!["blank", "gone"]);
+    }
+
     // ── inject_rules_into_project_file_with_custom ───────────────────────────
 
     #[test]
@@ -797,6 +1021,42 @@ mod tests {
         assert!(!on_disk.contains("Some rule."));
     }
 
+    #[test]
+    fn inject_preserves_apply_to_frontmatter_in_instruction_file() {
+        let dir = tmp();
+        let rel = ".github/instructions/api.instructions.md";
+        let file = dir.path().join(rel);
+        fs::create_dir_all(file.parent().unwrap()).expect("mkdir");
+        let authored = "---\napplyTo: \"src/api/**\"\n---\n\n# API conventions\n";
+        fs::write(&file, authored).expect("write");
+
+        inject_rules_into_project_file_with_custom(
+            dir.path().to_str().unwrap(),
+            rel,
+            &no_rules(),
+            &custom("Validate all request bodies."),
+        )
+        .expect("inject");
+
+        let on_disk = fs::read_to_string(&file).expect("read");
+        assert!(
+            on_disk.starts_with("---\napplyTo: \"src/api/**\"\n---\n\n# API conventions"),
+            "frontmatter must stay at the top of the file, found: {:?}",
+            on_disk
+        );
+        assert!(on_disk.contains("Validate all request bodies."));
+
+        // Removing the rules restores the authored file verbatim.
+        inject_rules_into_project_file_with_custom(
+            dir.path().to_str().unwrap(),
+            rel,
+            &no_rules(),
+            &[],
+        )
+        .expect("inject");
+        assert_eq!(fs::read_to_string(&file).expect("read"), authored);
+    }
+
     // ── is_file_rules_current_with_custom ────────────────────────────────────
 
     #[test]
@@ -831,6 +1091,27 @@ mod tests {
         assert!(!is_current);
     }
 
+    #[test]
+    fn annotation_wording_does_not_affect_rule_checks() {
+        let dir = tmp();
+        let file = dir.path().join("AGENTS.md");
+        let section = build_rules_section_with_custom(&[], &custom("My rule.")).expect("build");
+        assert!(section.contains(&marker_annotation()));
+
+        // Written by an older build with different annotation wording.
+        let older = section.replace(
+            &marker_annotation(),
+            "<!-- automatic:managed v0 — Generated by Automatic. -->",
+        );
+        fs::write(&file, format!("# File\n\n{}\n", older)).expect("write");
+        let is_current = is_file_rules_current_with_custom(&file, &no_rules(), &custom("My rule."))
+            .expect("check");
+        assert!(is_current);
+
+        let body = rules_section_body(&older).expect("body");
+        assert_eq!(body.trim(), "My rule.");
+    }
+
     #[test]
     fn missing_file_is_reported_as_not_current() {
         let dir = tmp();
@@ -886,4 +1167,96 @@ mod tests {
         // User file should be untouched.
         assert!(rules_dir.join("user-file.md").exists());
     }
+
+    #[test]
+    fn managed_sections_can_be_swapped_without_touching_user_content() {
+        let old =
+            "# Mine\n\n<!-- automatic:rules:start -->\nOld rule.\n<!-- automatic:rules:end -->\n";
+        let current =
+            "# Mine, edited\n\n<!-- automatic:rules:start -->\nNew rule.\n<!-- automatic:rules:end -->\n";
+
+        let sections = extract_managed_sections(old);
+        assert_eq!(
+            sections,
+            "<!-- automatic:rules:start -->\nOld rule.\n<!-- automatic:rules:end -->\n"
+        );
+        assert_eq!(
+            replace_managed_sections(current, &sections),
+            "# Mine, edited\n\n<!-- automatic:rules:start -->\nOld rule.\n<!-- automatic:rules:end -->\n"
+        );
+        assert_eq!(replace_managed_sections(current, ""), "# Mine, edited\n");
+        assert_eq!(extract_managed_sections("# Mine\n"), "");
+    }
+
+    // ── attach_file_rules ────────────────────────────────────────────────────
+
+    fn rules_project() -> Project {
+        let mut project = Project {
+            name: "web".into(),
+            ..Default::default()
+        };
+        project
+            .file_rules
+            .insert("AGENTS.md".into(), customs(&["style"]));
+        project
+    }
+
+    fn known() -> Vec<String> {
+        customs(&["style", "testing", "security"])
+    }
+
+    #[test]
+    fn attach_records_rules_in_the_given_order() {
+        let mut project = rules_project();
+        let ordered = customs(&["security", "style"]);
+        attach_file_rules(&mut project, "CLAUDE.md", &ordered, &known()).expect("attach");
+        assert_eq!(project.file_rules["CLAUDE.md"], ordered);
+        assert_eq!(project.file_rules["AGENTS.md"], custom("style"));
+    }
+
+    #[test]
+    fn attach_empty_list_detaches_the_key() {
+        let mut project = rules_project();
+        attach_file_rules(&mut project, "AGENTS.md", &no_rules(), &known()).expect("detach");
+        assert!(!project.file_rules.contains_key("AGENTS.md"));
+    }
+
+    #[test]
+    fn attach_rejects_unknown_rules_without_changes() {
+        let mut project = rules_project();
+        let err = attach_file_rules(
+            &mut project,
+            "AGENTS.md",
+            &customs(&["testing", "missing"]),
+            &known(),
+        )
+        .unwrap_err();
+        assert_eq!(err, "Rule 'missing' not found");
+        assert_eq!(project.file_rules["AGENTS.md"], custom("style"));
+    }
+
+    #[test]
+    fn attach_rejects_duplicate_entries() {
+        let mut project = rules_project();
+        let err = attach_file_rules(
+            &mut project,
+            "AGENTS.md",
+            &customs(&["testing", "style", "testing"]),
+            &known(),
+        )
+        .unwrap_err();
+        assert_eq!(err, "Rule 'testing' is listed more than once");
+        assert_eq!(project.file_rules["AGENTS.md"], custom("style"));
+    }
+
+    #[test]
+    fn attach_rejects_empty_filename_and_locked_projects() {
+        let mut project = rules_project();
+        let err = attach_file_rules(&mut project, "  ", &custom("style"), &known()).unwrap_err();
+        assert_eq!(err, "Filename cannot be empty");
+
+        project.locked = true;
+        assert!(attach_file_rules(&mut project, "CLAUDE.md", &custom("style"), &known()).is_err());
+        assert!(!project.file_rules.contains_key("CLAUDE.md"));
+    }
 }
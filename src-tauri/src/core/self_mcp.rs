@@ -0,0 +1,287 @@
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ── Automatic's Own MCP Server ───────────────────────────────────────────────
+//
+// Every project gets an `automatic` MCP server pointing back at this binary.
+// Its entry is never stored per project: sync and drift checks build it from
+// the configured command (by default the live binary path), discovery
+// ignores it (and the pre-rename `nexus` name) when importing servers from
+// agent configs, and the registry keeps a project-less copy so it shows up
+// in the UI.  All of those go through this
+// module so the entry cannot take a different shape in different places.
+
+/// The well-known name used for the Automatic MCP server everywhere:
+/// registry files, project assignments, and agent config files.
+pub const AUTOMATIC_SERVER_NAME: &str = "automatic";
+
+/// Name the server had before the app was renamed.  Still recognised so old
+/// configs are migrated rather than imported as a user server.
+pub const LEGACY_SERVER_NAME: &str = "nexus";
+
+/// Path of the running Automatic binary, falling back to `automatic` on PATH.
+pub fn automatic_binary() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.to_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| AUTOMATIC_SERVER_NAME.to_string())
+}
+
+/// How the `command` of the injected server is written, from the
+/// `automatic_mcp_command_mode` setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpCommandMode {
+    /// The full path of the running binary.
+    Absolute,
+    /// Bare `automatic`, found through the PATH shim installed at startup.
+    Path,
+    /// A command chosen by the user.
+    Custom(String),
+}
+
+impl McpCommandMode {
+    /// Parse the settings values; anything unrecognised, or `"custom"`
+    /// without a command, means `Absolute`.
+    pub fn parse(mode: &str, custom_command: Option<&str>) -> Self {
+        match (mode, custom_command.map(str::trim)) {
+            ("path", _) => Self::Path,
+            ("custom", Some(command)) if !command.is_empty() => Self::Custom(command.to_string()),
+            _ => Self::Absolute,
+        }
+    }
+
+    fn command(&self, binary: &str) -> String {
+        match self {
+            Self::Absolute => binary.to_string(),
+            Self::Path => AUTOMATIC_SERVER_NAME.to_string(),
+            Self::Custom(command) => command.clone(),
+        }
+    }
+}
+
+/// The configured command mode.
+pub fn mcp_command_mode() -> McpCommandMode {
+    let settings = super::read_settings().unwrap_or_default();
+    McpCommandMode::parse(
+        &settings.automatic_mcp_command_mode,
+        settings.automatic_mcp_command.as_deref(),
+    )
+}
+
+/// The command agent configs use to start Automatic, following the
+/// configured mode.  Written into the `automatic` server and the OAuth proxy
+/// entries, so changing the mode re-targets both.
+pub fn automatic_command() -> String {
+    mcp_command_mode().command(&automatic_binary())
+}
+
+// ── PATH shim ────────────────────────────────────────────────────────────────
+//
+// In `"path"` mode agent configs only say `automatic`, so something named
+// that has to be on PATH.  Startup points a shim at the running binary:
+// a symlink in `~/.local/bin` on Unix, a `.cmd` wrapper in
+// `~/.automatic/bin` on Windows (that directory has to be added to PATH by
+// the user).  Moving or updating the app only moves the shim.
+
+#[cfg(unix)]
+const SHIM_FILE_NAME: &str = "automatic";
+#[cfg(windows)]
+const SHIM_FILE_NAME: &str = "automatic.cmd";
+
+fn shim_dir() -> Result<PathBuf, String> {
+    if cfg!(windows) {
+        Ok(super::get_automatic_dir()?.join("bin"))
+    } else {
+        let home = dirs::home_dir().ok_or("Could not find home directory")?;
+        Ok(home.join(".local").join("bin"))
+    }
+}
+
+/// Install or update the PATH shim when the command mode is `"path"`.
+/// Returns the shim path when it was written, `None` when it was already
+/// current or the mode does not use it.
+pub fn install_path_shim() -> Result<Option<PathBuf>, String> {
+    if mcp_command_mode() != McpCommandMode::Path {
+        return Ok(None);
+    }
+    let binary = std::env::current_exe().map_err(|e| e.to_string())?;
+    install_path_shim_at(&shim_dir()?, &binary)
+}
+
+fn install_path_shim_at(dir: &Path, binary: &Path) -> Result<Option<PathBuf>, String> {
+    let shim = dir.join(SHIM_FILE_NAME);
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+
+    #[cfg(unix)]
+    {
+        if let Ok(meta) = fs::symlink_metadata(&shim) {
+            if !meta.file_type().is_symlink() {
+                // Someone else's `automatic` (e.g. a manual install); leave it.
+                return Err(format!(
+                    "'{}' exists and is not a symlink; not replacing it",
+                    shim.display()
+                ));
+            }
+            if fs::read_link(&shim).ok().as_deref() == Some(binary) {
+                return Ok(None);
+            }
+            fs::remove_file(&shim).map_err(|e| e.to_string())?;
+        }
+        std::os::unix::fs::symlink(binary, &shim).map_err(|e| e.to_string())?;
+    }
+
+    #[cfg(windows)]
+    {
+        let script = format!("@echo off\r\n\"{}\" %*\r\n", binary.display());
+        if fs::read_to_string(&shim).ok().as_deref() == Some(script.as_str()) {
+            return Ok(None);
+        }
+        fs::write(&shim, script).map_err(|e| e.to_string())?;
+    }
+
+    Ok(Some(shim))
+}
+
+/// Whether `name` is a server Automatic injects itself, which must never be
+/// imported into the registry or a project's own server list.
+pub fn is_managed_name(name: &str) -> bool {
+    name == AUTOMATIC_SERVER_NAME || name == LEGACY_SERVER_NAME
+}
+
+/// The `automatic` server entry.  With `project_name`, the entry written into
+/// that project's agent configs, which tells the server which project it
+/// serves via `AUTOMATIC_PROJECT`; without, the project-less entry kept in
+/// the registry.  When the app runs with `$AUTOMATIC_DATA_DIR` set, the
+/// entry passes it on so the server reads the same data.
+pub fn managed_entry(project_name: Option<&str>) -> (&'static str, Value) {
+    let data_dir = if super::data_root_from_env() {
+        super::data_root().ok().map(|d| d.display().to_string())
+    } else {
+        None
+    };
+    managed_entry_for(&automatic_command(), project_name, data_dir.as_deref())
+}
+
+fn managed_entry_for(
+    command: &str,
+    project_name: Option<&str>,
+    data_dir: Option<&str>,
+) -> (&'static str, Value) {
+    let mut entry = json!({
+        "command": command,
+        "args": ["mcp-serve"],
+    });
+    let mut env = serde_json::Map::new();
+    if let Some(project_name) = project_name {
+        env.insert("AUTOMATIC_PROJECT".into(), json!(project_name));
+    }
+    if let Some(data_dir) = data_dir {
+        env.insert(super::DATA_DIR_ENV.into(), json!(data_dir));
+    }
+    if !env.is_empty() {
+        entry["env"] = Value::Object(env);
+    }
+    (AUTOMATIC_SERVER_NAME, entry)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_entry_names_the_project() {
+        let (name, entry) = managed_entry_for("/usr/local/bin/automatic", Some("web"), None);
+        assert_eq!(name, "automatic");
+        assert_eq!(
+            entry,
+            json!({
+                "command": "/usr/local/bin/automatic",
+                "args": ["mcp-serve"],
+                "env": { "AUTOMATIC_PROJECT": "web" }
+            })
+        );
+    }
+
+    #[test]
+    fn data_dir_override_is_passed_on() {
+        let (_, entry) = managed_entry_for(
+            "/usr/local/bin/automatic",
+            Some("web"),
+            Some("/data/automatic"),
+        );
+        assert_eq!(
+            entry["env"],
+            json!({ "AUTOMATIC_PROJECT": "web", "AUTOMATIC_DATA_DIR": "/data/automatic" })
+        );
+    }
+
+    #[test]
+    fn registry_entry_has_no_project() {
+        let (name, entry) = managed_entry_for("/usr/local/bin/automatic", None, None);
+        assert_eq!(name, "automatic");
+        assert_eq!(
+            entry,
+            json!({
+                "command": "/usr/local/bin/automatic",
+                "args": ["mcp-serve"]
+            })
+        );
+    }
+
+    #[test]
+    fn command_follows_mode() {
+        let binary = "/Applications/Automatic.app/Contents/MacOS/automatic";
+        let command =
+            |mode: &str, custom: Option<&str>| McpCommandMode::parse(mode, custom).command(binary);
+        assert_eq!(command("absolute", None), binary);
+        assert_eq!(command("path", None), "automatic");
+        assert_eq!(
+            command("custom", Some(" ~/bin/automatic ")),
+            "~/bin/automatic"
+        );
+        // A custom mode without a command, or an unknown mode, falls back.
+        assert_eq!(command("custom", Some("  ")), binary);
+        assert_eq!(command("custom", None), binary);
+        assert_eq!(command("relative", None), binary);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_shim_is_created_then_retargeted() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("bin");
+        let old = dir.path().join("old-automatic");
+        let new = dir.path().join("new-automatic");
+
+        let shim = install_path_shim_at(&bin, &old).unwrap().unwrap();
+        assert_eq!(fs::read_link(&shim).unwrap(), old);
+        assert_eq!(install_path_shim_at(&bin, &old).unwrap(), None);
+
+        install_path_shim_at(&bin, &new).unwrap();
+        assert_eq!(fs::read_link(&shim).unwrap(), new);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_shim_leaves_a_real_binary_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("automatic"), "#!/bin/sh\n").unwrap();
+        assert!(install_path_shim_at(dir.path(), Path::new("/opt/automatic")).is_err());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("automatic")).unwrap(),
+            "#!/bin/sh\n"
+        );
+    }
+
+    #[test]
+    fn managed_names_are_current_and_legacy_only() {
+        assert!(is_managed_name("automatic"));
+        assert!(is_managed_name("nexus"));
+        assert!(!is_managed_name("Automatic"));
+        assert!(!is_managed_name("automatic-dev"));
+        assert!(!is_managed_name("github"));
+    }
+}
@@ -0,0 +1,392 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+// ── Sessions Store ───────────────────────────────────────────────────────────
+//
+// `sessions.json` is an object keyed by session id, written by the Claude
+// plugin's hooks (jq + mv) from every running session at once.  A crash or an
+// unlucky interleaving can leave it empty or truncated, and the hooks cannot
+// recover from that on their own: jq fails on invalid input, so the file
+// stays broken.  Readers therefore validate it, and move a corrupt file
+// aside (to `sessions.json.<timestamp>.corrupt`) so the hooks start afresh;
+// `repair_sessions_store` later merges back whatever entries can still be
+// parsed.  Writes from this side hold an exclusive lock on
+// `sessions.json.lock` and replace the file atomically.
+
+/// Active sessions read from the store.
+#[derive(Debug, Default)]
+pub struct SessionsRead {
+    pub sessions: Map<String, Value>,
+    /// Where a corrupt store was moved to, when this read found one.
+    pub quarantined: Option<PathBuf>,
+}
+
+/// What [`repair_sessions_store`] recovered.
+#[derive(Debug, Default, Serialize)]
+pub struct SessionsRepair {
+    /// Session entries added back to the store.
+    pub recovered: usize,
+    /// Corrupt files that were read and removed.
+    pub sources: Vec<String>,
+}
+
+/// Read active sessions from the store.  Returns the JSON string (an object
+/// keyed by session_id), `"{}"` if the file doesn't exist or was corrupt.
+pub fn list_sessions() -> Result<String, String> {
+    let read = read_sessions()?;
+    serde_json::to_string_pretty(&read.sessions).map_err(|e| e.to_string())
+}
+
+/// Read and validate the store, moving it aside when it is corrupt.
+pub fn read_sessions() -> Result<SessionsRead, String> {
+    read_sessions_at(&get_sessions_path()?)
+}
+
+/// Remove `session_id` from the store.  Returns whether it was registered.
+pub fn remove_session(session_id: &str) -> Result<bool, String> {
+    remove_session_at(&get_sessions_path()?, session_id)
+}
+
+/// Merge the parseable entries of corrupt stores (the current file and any
+/// moved aside earlier) back into the store.  Entries already in a valid
+/// store win over salvaged ones.
+pub fn repair_sessions_store() -> Result<SessionsRepair, String> {
+    repair_sessions_store_at(&get_sessions_path()?)
+}
+
+fn read_sessions_at(path: &Path) -> Result<SessionsRead, String> {
+    let Some(raw) = read_raw(path)? else {
+        return Ok(SessionsRead::default());
+    };
+    if let Some(sessions) = parse_sessions(&raw) {
+        return Ok(SessionsRead {
+            sessions,
+            quarantined: None,
+        });
+    }
+
+    // Re-check under the lock: a writer may have replaced the file since.
+    with_file_lock(path, || {
+        let Some(raw) = read_raw(path)? else {
+            return Ok(SessionsRead::default());
+        };
+        if let Some(sessions) = parse_sessions(&raw) {
+            return Ok(SessionsRead {
+                sessions,
+                quarantined: None,
+            });
+        }
+        let target = quarantine_path(path);
+        fs::rename(path, &target)
+            .map_err(|e| format!("Failed to move corrupt {} aside: {}", path.display(), e))?;
+        eprintln!(
+            "[automatic] {} was corrupt; moved to {}",
+            path.display(),
+            target.display()
+        );
+        Ok(SessionsRead {
+            sessions: Map::new(),
+            quarantined: Some(target),
+        })
+    })
+}
+
+fn remove_session_at(path: &Path, session_id: &str) -> Result<bool, String> {
+    with_file_lock(path, || {
+        let Some(raw) = read_raw(path)? else {
+            return Ok(false);
+        };
+        let mut sessions = parse_sessions(&raw).unwrap_or_else(|| salvage_sessions(&raw));
+        let removed = sessions.remove(session_id).is_some();
+        if removed {
+            write_sessions_at(path, &sessions)?;
+        }
+        Ok(removed)
+    })
+}
+
+fn repair_sessions_store_at(path: &Path) -> Result<SessionsRepair, String> {
+    with_file_lock(path, || {
+        let mut sources = Vec::new();
+        let mut sessions = match read_raw(path)? {
+            Some(raw) => parse_sessions(&raw).unwrap_or_else(|| {
+                sources.push(path.to_path_buf());
+                salvage_sessions(&raw)
+            }),
+            None => Map::new(),
+        };
+        let mut recovered = if sources.is_empty() {
+            0
+        } else {
+            sessions.len()
+        };
+
+        let mut corrupt = quarantined_files(path);
+        // Newest first, so the latest copy of a session wins.
+        corrupt.sort_by(|a, b| b.cmp(a));
+        for file in corrupt {
+            let raw = fs::read_to_string(&file).unwrap_or_default();
+            for (id, entry) in salvage_sessions(&raw) {
+                if !sessions.contains_key(&id) {
+                    sessions.insert(id, entry);
+                    recovered += 1;
+                }
+            }
+            sources.push(file);
+        }
+
+        if !sources.is_empty() {
+            write_sessions_at(path, &sessions)?;
+        }
+        for file in sources.iter().filter(|f| f.as_path() != path) {
+            let _ = fs::remove_file(file);
+        }
+        Ok(SessionsRepair {
+            recovered,
+            sources: sources.iter().map(|p| p.display().to_string()).collect(),
+        })
+    })
+}
+
+fn read_raw(path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(raw) => Ok(Some(raw)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        // Invalid UTF-8 is corruption too; read it lossily.
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => fs::read(path)
+            .map(|bytes| Some(String::from_utf8_lossy(&bytes).into_owned()))
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The sessions in `raw`, or `None` when it is not a JSON object.
+fn parse_sessions(raw: &str) -> Option<Map<String, Value>> {
+    match serde_json::from_str(raw) {
+        Ok(Value::Object(sessions)) => Some(sessions),
+        _ => None,
+    }
+}
+
+/// Every `"<id>": { … }` entry in `raw` that still parses, ignoring
+/// whatever surrounds them (truncation, garbage, two stores run together).
+fn salvage_sessions(raw: &str) -> Map<String, Value> {
+    let mut sessions = Map::new();
+    let mut rest = raw;
+    while let Some(start) = rest.find('"') {
+        let Some((key, after_key)) = take_string(&rest[start..]) else {
+            break;
+        };
+        let value = after_key
+            .trim_start()
+            .strip_prefix(':')
+            .map(str::trim_start)
+            .filter(|v| v.starts_with('{'));
+        let Some(value) = value else {
+            rest = after_key;
+            continue;
+        };
+        let entry = balanced_len(value).and_then(|len| {
+            match serde_json::from_str::<Value>(&value[..len]) {
+                Ok(entry @ Value::Object(_)) => Some((entry, len)),
+                _ => None,
+            }
+        });
+        match entry {
+            Some((entry, len)) => {
+                sessions.insert(key, entry);
+                rest = &value[len..];
+            }
+            // Truncated or invalid: keep scanning inside it, where the
+            // entries of a store run into this one may start.
+            None => rest = &value[1..],
+        }
+    }
+    sessions
+}
+
+/// The JSON string literal `text` starts with, and the text after it.
+fn take_string(text: &str) -> Option<(String, &str)> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => {
+                let key = serde_json::from_str(&text[..=i]).ok()?;
+                return Some((key, &text[i + 1..]));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn quarantine_path(path: &Path) -> PathBuf {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "sessions.json".into());
+    let mut target = path.with_file_name(format!("{}.{}.corrupt", name, stamp));
+    let mut n = 1;
+    while target.exists() {
+        target = path.with_file_name(format!("{}.{}-{}.corrupt", name, stamp, n));
+        n += 1;
+    }
+    target
+}
+
+/// Files [`read_sessions_at`] moved aside for the store at `path`.
+fn quarantined_files(path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy())
+                .is_some_and(|n| n.starts_with(&prefix) && n.ends_with(".corrupt"))
+        })
+        .collect()
+}
+
+/// Replace the store atomically: readers see the old or the new file, never
+/// a partial one.
+fn write_sessions_at(path: &Path, sessions: &Map<String, Value>) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(sessions).map_err(|e| e.to_string())?;
+    write_atomic(path, json)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    const VALID: &str = r#"{
+  "abc": {"session_id": "abc", "cwd": "/work/web", "started_at": "2026-10-01T09:00:00Z"},
+  "def": {"session_id": "def", "cwd": "/work/api", "started_at": "2026-10-01T10:00:00Z"}
+}"#;
+
+    fn store(dir: &Path, content: &str) -> PathBuf {
+        let path = dir.join("sessions.json");
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn valid_store_is_read_as_is() {
+        let dir = tempdir().unwrap();
+        let path = store(dir.path(), VALID);
+        let read = read_sessions_at(&path).unwrap();
+        assert_eq!(read.sessions.len(), 2);
+        assert!(read.quarantined.is_none());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn missing_store_is_empty() {
+        let dir = tempdir().unwrap();
+        let read = read_sessions_at(&dir.path().join("sessions.json")).unwrap();
+        assert!(read.sessions.is_empty());
+        assert!(read.quarantined.is_none());
+    }
+
+    #[test]
+    fn corrupt_stores_are_moved_aside() {
+        for content in ["", &VALID[..VALID.len() / 2], "\u{0}\u{1}garbage", "[1, 2]"] {
+            let dir = tempdir().unwrap();
+            let path = store(dir.path(), content);
+
+            let read = read_sessions_at(&path).unwrap();
+
+            assert!(read.sessions.is_empty(), "{:?}", content);
+            let moved = read.quarantined.expect("quarantined");
+            assert!(!path.exists(), "hooks must start from a fresh file");
+            assert_eq!(fs::read_to_string(&moved).unwrap(), content);
+            assert!(moved.to_string_lossy().ends_with(".corrupt"));
+        }
+    }
+
+    #[test]
+    fn salvage_keeps_complete_entries_of_a_truncated_store() {
+        let truncated = &VALID[..VALID.find("\"def\": {").unwrap() + 20];
+        let sessions = salvage_sessions(truncated);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions["abc"]["cwd"], "/work/web");
+    }
+
+    #[test]
+    fn salvage_reads_stores_run_together_and_skips_garbage() {
+        let raw = format!(
+            "garbage {{\"x\": 1}} {}{{\"ghi\": {{\"session_id\": \"ghi\"}}}}\n\u{0}",
+            VALID
+        );
+        let sessions = salvage_sessions(&raw);
+        let mut ids: Vec<&String> = sessions.keys().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["abc", "def", "ghi"]);
+        assert!(salvage_sessions("\u{0}{not json at all").is_empty());
+    }
+
+    #[test]
+    fn repair_merges_quarantined_entries_into_the_store() {
+        let dir = tempdir().unwrap();
+        let path = store(dir.path(), &VALID[..VALID.len() - 30]);
+        read_sessions_at(&path).unwrap();
+        // A session registered by a hook after the corrupt file was moved.
+        fs::write(
+            &path,
+            json!({"new": {"session_id": "new"}, "abc": {"session_id": "abc", "cwd": "/moved"}})
+                .to_string(),
+        )
+        .unwrap();
+
+        let repair = repair_sessions_store_at(&path).unwrap();
+
+        assert_eq!(repair.recovered, 0, "abc is already registered");
+        assert_eq!(repair.sources.len(), 1);
+        assert!(quarantined_files(&path).is_empty());
+        let sessions = parse_sessions(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(sessions["abc"]["cwd"], "/moved");
+        assert!(sessions.contains_key("new"));
+    }
+
+    #[test]
+    fn repair_salvages_a_corrupt_current_store() {
+        let dir = tempdir().unwrap();
+        let path = store(dir.path(), &format!("{}{}", VALID, "\n{\"def\": "));
+
+        let repair = repair_sessions_store_at(&path).unwrap();
+
+        assert_eq!(repair.recovered, 2);
+        let sessions = parse_sessions(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(sessions.len(), 2);
+    }
+
+    #[test]
+    fn removing_a_session_rewrites_the_store() {
+        let dir = tempdir().unwrap();
+        let path = store(dir.path(), VALID);
+
+        assert!(remove_session_at(&path, "abc").unwrap());
+        assert!(!remove_session_at(&path, "abc").unwrap());
+
+        let sessions = parse_sessions(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(sessions.keys().collect::<Vec<_>>(), vec!["def"]);
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 
+use super::fsutil::{with_file_lock, write_atomic};
 use super::paths::get_automatic_dir;
 use super::types::AgentOptions;
 
@@ -77,12 +78,120 @@ pub struct Settings {
     /// Used to determine whether a badge/indicator should be shown.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub whats_new_seen_version: Option<String>,
+    /// How often (in days) imported skills are checked for upstream changes
+    /// in the background.  `0` disables the scheduled check; a manual check
+    /// can still be triggered from the Skills page.
+    #[serde(default = "default_skill_update_interval_days")]
+    pub skill_update_interval_days: u32,
+    /// What agents may do through the Automatic MCP server: `"read_write"`
+    /// (default) or `"read_only"`, which refuses tools that create
+    /// configuration such as `automatic_save_project_template`.
+    #[serde(default = "default_mcp_permission")]
+    pub mcp_permission: String,
+    /// Set once the one-time migration from the legacy `nexus` naming has
+    /// run, so startup does not rescan every project again.
+    #[serde(default)]
+    pub legacy_nexus_migrated: bool,
+    /// What recursive deletes do: `"allow"` (default), `"dry_run"`, which
+    /// only logs and reports what would be removed, or `"deny"`.
+    #[serde(default = "default_destructive_ops")]
+    pub destructive_ops: String,
+    /// Directories holding more files than this are only deleted by
+    /// operations that explicitly force it.
+    #[serde(default = "default_destructive_max_files")]
+    pub destructive_max_files: usize,
+    /// Which releases the updater offers: `"stable"` (default) or `"beta"`.
+    #[serde(default = "default_update_channel")]
+    pub update_channel: String,
+    /// Levels below a project's generic `skills/` directory that
+    /// autodetection searches for skills.
+    #[serde(default = "default_autodetect_max_depth")]
+    pub autodetect_max_depth: usize,
+    /// Directories autodetection reads while searching `skills/` before it
+    /// stops.
+    #[serde(default = "default_autodetect_max_dirs")]
+    pub autodetect_max_dirs: usize,
+    /// Sync generations kept per project for time-travel restore.  `0`
+    /// stops recording them.
+    #[serde(default = "default_sync_generations_retained")]
+    pub sync_generations_retained: usize,
+    /// Projects whose agent config files are watched for hand edits, so
+    /// the app can report drift without an explicit check.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drift_watch_projects: Vec<String>,
+    /// How agent configs launch the Automatic MCP server: `"absolute"`
+    /// (default) writes this binary's full path, `"path"` writes
+    /// `automatic` and relies on the PATH shim installed at startup, and
+    /// `"custom"` writes `automatic_mcp_command`.
+    #[serde(default = "default_automatic_mcp_command_mode")]
+    pub automatic_mcp_command_mode: String,
+    /// The command used when `automatic_mcp_command_mode` is `"custom"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automatic_mcp_command: Option<String>,
+    /// URL of the remote project template index merged into the
+    /// marketplace.  Empty turns remote templates off.
+    #[serde(default = "default_template_index_url")]
+    pub template_index_url: String,
+    /// Where imported skills are installed: `"global"` (default) puts them
+    /// in `~/.agents/skills/` like any other skill, `"library"` keeps them
+    /// in the skill library, where agents only see them through projects.
+    #[serde(default = "default_skill_install_scope")]
+    pub skill_install_scope: String,
 }
 
+/// Skills sit directly under `skills/` unless the user asks for more.
+pub const DEFAULT_AUTODETECT_MAX_DEPTH: usize = 1;
+pub const DEFAULT_AUTODETECT_MAX_DIRS: usize = 2000;
+pub const DEFAULT_SYNC_GENERATIONS_RETAINED: usize = 20;
+
 fn default_analytics_enabled() -> bool {
     true
 }
 
+fn default_skill_update_interval_days() -> u32 {
+    super::DEFAULT_SKILL_UPDATE_INTERVAL_DAYS
+}
+
+fn default_mcp_permission() -> String {
+    "read_write".to_string()
+}
+
+fn default_destructive_ops() -> String {
+    "allow".to_string()
+}
+
+fn default_destructive_max_files() -> usize {
+    super::DEFAULT_DESTRUCTIVE_MAX_FILES
+}
+
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
+fn default_autodetect_max_depth() -> usize {
+    DEFAULT_AUTODETECT_MAX_DEPTH
+}
+
+fn default_autodetect_max_dirs() -> usize {
+    DEFAULT_AUTODETECT_MAX_DIRS
+}
+
+fn default_sync_generations_retained() -> usize {
+    DEFAULT_SYNC_GENERATIONS_RETAINED
+}
+
+fn default_automatic_mcp_command_mode() -> String {
+    "absolute".to_string()
+}
+
+fn default_template_index_url() -> String {
+    super::DEFAULT_TEMPLATE_INDEX_URL.to_string()
+}
+
+fn default_skill_install_scope() -> String {
+    "global".to_string()
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
@@ -96,6 +205,20 @@ impl Default for Settings {
             default_agent_options: HashMap::new(),
             bundled_skills_version: None,
             whats_new_seen_version: None,
+            skill_update_interval_days: default_skill_update_interval_days(),
+            mcp_permission: default_mcp_permission(),
+            legacy_nexus_migrated: false,
+            destructive_ops: default_destructive_ops(),
+            destructive_max_files: default_destructive_max_files(),
+            update_channel: default_update_channel(),
+            autodetect_max_depth: default_autodetect_max_depth(),
+            autodetect_max_dirs: default_autodetect_max_dirs(),
+            sync_generations_retained: default_sync_generations_retained(),
+            drift_watch_projects: Vec::new(),
+            automatic_mcp_command_mode: default_automatic_mcp_command_mode(),
+            automatic_mcp_command: None,
+            template_index_url: default_template_index_url(),
+            skill_install_scope: default_skill_install_scope(),
         }
     }
 }
@@ -109,15 +232,17 @@ pub fn read_settings() -> Result<Settings, String> {
     Ok(serde_json::from_str(&raw).unwrap_or_default())
 }
 
+/// True unless the user restricted the MCP server to read-only access.
+pub fn mcp_writes_allowed() -> bool {
+    read_settings()
+        .map(|s| s.mcp_permission != "read_only")
+        .unwrap_or(true)
+}
+
 pub fn write_settings(settings: &Settings) -> Result<(), String> {
     let path = get_automatic_dir()?.join("settings.json");
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
-    }
     let raw = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
-    fs::write(&path, raw).map_err(|e| e.to_string())
+    with_file_lock(&path, || write_atomic(&path, &raw))
 }
 
 pub fn reset_settings() -> Result<(), String> {
@@ -143,8 +268,11 @@ pub fn reinstall_defaults() -> Result<(), String> {
 
 pub fn erase_app_data() -> Result<(), String> {
     let automatic_dir = get_automatic_dir()?;
-    if automatic_dir.exists() {
-        fs::remove_dir_all(&automatic_dir).map_err(|e| e.to_string())?;
+    let outcome =
+        super::remove_guarded(&automatic_dir, &super::RemovalContext::app_data().forced())?;
+    if let super::RemovalOutcome::WouldRemove { .. } = outcome {
+        // Dry run: leave the existing data (and the dry-run setting) alone.
+        return Ok(());
     }
 
     // Recreate a clean baseline immediately so the app still has bundled
@@ -237,6 +365,13 @@ mod tests {
         assert!(!s.wizard_completed);
         assert!(!s.welcome_dismissed);
         assert!(s.default_agents.is_empty());
+        assert_eq!(s.skill_update_interval_days, 7);
+    }
+
+    #[test]
+    fn skill_update_interval_defaults_when_absent() {
+        let loaded: Settings = serde_json::from_str(r#"{"sync_mode":"copy"}"#).unwrap();
+        assert_eq!(loaded.skill_update_interval_days, 7);
     }
 
     // ── Read (missing file) ───────────────────────────────────────────────────
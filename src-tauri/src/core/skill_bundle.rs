@@ -0,0 +1,522 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use super::*;
+
+// ── Offline Skill Bundles ────────────────────────────────────────────────────
+//
+// A skill bundle carries a curated set of skills to machines that cannot
+// reach skills.sh or GitHub.  It is a zip archive (conventionally named
+// `*.skillbundle`) laid out as:
+//
+//   index.json               {"format": 1, "created_at": "...", "skills": [...]}
+//   skills/<name>/SKILL.md   one directory per skill listed in the index
+//   skills/<name>/...        companion files (scripts/, references/, ...)
+//
+// Each index entry records the skill's name, description, and where it
+// originally came from (`source`/`id`, as in `~/.automatic/skills.json`).
+//
+// Bundles are untrusted input.  Every entry is validated before anything is
+// written: only `index.json` and `skills/<valid-name>/...` paths made of
+// plain components are accepted, symlinks are refused, and entry count,
+// per-file size and total size are capped.  A single bad entry rejects the
+// whole bundle.
+
+/// Version of the bundle layout written by [`export_skill_bundle`].
+pub const SKILL_BUNDLE_FORMAT: u32 = 1;
+
+/// Provenance kind recorded for skills installed from a bundle.
+pub const SKILL_BUNDLE_SOURCE_KIND: &str = "bundle";
+
+const MAX_BUNDLE_ENTRIES: usize = 10_000;
+const MAX_BUNDLE_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_BUNDLE_TOTAL_BYTES: u64 = 200 * 1024 * 1024;
+
+/// The `index.json` at the root of a skill bundle.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SkillBundleIndex {
+    pub format: u32,
+    #[serde(default)]
+    pub created_at: String,
+    pub skills: Vec<SkillBundleEntry>,
+}
+
+/// One skill listed in a bundle index.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SkillBundleEntry {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Original source (GitHub owner/repo), empty for locally authored skills.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub source: String,
+    /// Original skills.sh id, empty for locally authored skills.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+}
+
+/// Result of [`import_skill_bundle`].
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct SkillBundleImport {
+    /// Skills copied into `~/.agents/skills/`.
+    pub installed: Vec<String>,
+    /// Skills left alone because one with the same name already exists.
+    pub skipped: Vec<String>,
+}
+
+/// Install every skill in the bundle at `path` into `~/.agents/skills/`.
+/// Skills that already exist are skipped; installed ones are recorded with
+/// source kind `"bundle"`.
+pub fn import_skill_bundle(path: &str) -> Result<SkillBundleImport, String> {
+    let skills_dir = get_agents_skills_dir()?;
+    let (index, report) = import_skill_bundle_into(Path::new(path), &skills_dir)?;
+
+    for entry in index
+        .skills
+        .iter()
+        .filter(|e| report.installed.contains(&e.name))
+    {
+        let source = if entry.source.is_empty() {
+            SKILL_BUNDLE_SOURCE_KIND.to_string()
+        } else {
+            entry.source.clone()
+        };
+        let id = if entry.id.is_empty() {
+            format!("{}/{}", SKILL_BUNDLE_SOURCE_KIND, entry.name)
+        } else {
+            entry.id.clone()
+        };
+        if let Err(e) = record_skill_source(&entry.name, &source, &id, SKILL_BUNDLE_SOURCE_KIND) {
+            eprintln!(
+                "[automatic] Failed to record bundle provenance for '{}': {}",
+                entry.name, e
+            );
+        }
+    }
+    Ok(report)
+}
+
+fn import_skill_bundle_into(
+    bundle: &Path,
+    skills_dir: &Path,
+) -> Result<(SkillBundleIndex, SkillBundleImport), String> {
+    let file = fs::File::open(bundle)
+        .map_err(|e| format!("Failed to open skill bundle '{}': {}", bundle.display(), e))?;
+    let mut archive = zip::ZipArchive::new(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to read skill bundle: {}", e))?;
+
+    let staging = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    extract_bundle(&mut archive, staging.path())?;
+
+    let raw = fs::read_to_string(staging.path().join("index.json"))
+        .map_err(|_| "Skill bundle has no index.json".to_string())?;
+    let index: SkillBundleIndex =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid bundle index.json: {}", e))?;
+    if index.format > SKILL_BUNDLE_FORMAT {
+        return Err(format!(
+            "Skill bundle format {} is newer than this version of Automatic supports",
+            index.format
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for entry in &index.skills {
+        if !is_valid_name(&entry.name) {
+            return Err(format!("Invalid skill name in bundle: '{}'", entry.name));
+        }
+        if !seen.insert(entry.name.as_str()) {
+            return Err(format!(
+                "Skill '{}' is listed twice in the bundle",
+                entry.name
+            ));
+        }
+        if !staging
+            .path()
+            .join("skills")
+            .join(&entry.name)
+            .join("SKILL.md")
+            .is_file()
+        {
+            return Err(format!(
+                "Skill '{}' has no SKILL.md in the bundle",
+                entry.name
+            ));
+        }
+    }
+
+    let mut report = SkillBundleImport::default();
+    for entry in &index.skills {
+        let dest = skills_dir.join(&entry.name);
+        if dest.exists() {
+            report.skipped.push(entry.name.clone());
+            continue;
+        }
+        let copy = copy_skill_dir(&staging.path().join("skills").join(&entry.name), &dest)?;
+        for skipped in &copy.skipped {
+            eprintln!(
+                "[automatic] Skipped file in bundled skill '{}': {}",
+                entry.name, skipped
+            );
+        }
+        report.installed.push(entry.name.clone());
+    }
+    Ok((index, report))
+}
+
+/// Validate every entry of `archive` and unpack it under `dest`.
+fn extract_bundle<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    dest: &Path,
+) -> Result<(), String> {
+    if archive.len() > MAX_BUNDLE_ENTRIES {
+        return Err(format!(
+            "Skill bundle has too many entries ({}, limit {})",
+            archive.len(),
+            MAX_BUNDLE_ENTRIES
+        ));
+    }
+
+    let mut total: u64 = 0;
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read bundle entry {}: {}", i, e))?;
+        let name = file.name().to_string();
+        let relative = bundle_entry_path(&name)?;
+        if file.is_symlink() {
+            return Err(format!("Skill bundle entry '{}' is a symlink", name));
+        }
+        if file.is_dir() {
+            continue;
+        }
+        if file.size() > MAX_BUNDLE_FILE_BYTES {
+            return Err(format!("Skill bundle entry '{}' is too large", name));
+        }
+
+        let out_path = dest.join(&relative);
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let mode = file.unix_mode();
+        let mut out = fs::File::create(&out_path)
+            .map_err(|e| format!("Failed to create file {}: {}", name, e))?;
+        // The declared size can lie; cap what is actually decompressed.
+        let written = std::io::copy(&mut (&mut file).take(MAX_BUNDLE_FILE_BYTES + 1), &mut out)
+            .map_err(|e| format!("Failed to extract {}: {}", name, e))?;
+        if written > MAX_BUNDLE_FILE_BYTES {
+            return Err(format!("Skill bundle entry '{}' is too large", name));
+        }
+        total += written;
+        if total > MAX_BUNDLE_TOTAL_BYTES {
+            return Err("Skill bundle exceeds the maximum unpacked size".into());
+        }
+        set_zip_entry_mode(&out_path, mode)?;
+    }
+    Ok(())
+}
+
+/// Options for the archive entry holding `path`, recording its permission
+/// bits so executable companion scripts stay executable once unpacked.
+pub(crate) fn zip_entry_options(path: &Path) -> zip::write::SimpleFileOptions {
+    let options = zip::write::SimpleFileOptions::default();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(meta) = fs::metadata(path) {
+            return options.unix_permissions(meta.permissions().mode() & 0o777);
+        }
+    }
+    options
+}
+
+/// Apply the permission bits an archive entry recorded (see
+/// [`zip_entry_options`]) to the file unpacked from it.  Entries without
+/// them keep the default mode.
+pub(crate) fn set_zip_entry_mode(path: &Path, mode: Option<u32>) -> Result<(), String> {
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o777))
+            .map_err(|e| format!("Failed to set permissions on {}: {}", path.display(), e))?;
+    }
+    #[cfg(not(unix))]
+    let _ = (path, mode);
+    Ok(())
+}
+
+/// Map an archive entry name to a safe relative path, rejecting anything
+/// outside `index.json` and `skills/<valid-name>/...`.
+fn bundle_entry_path(name: &str) -> Result<PathBuf, String> {
+    let unsafe_entry = || format!("Unsafe path in skill bundle: '{}'", name);
+    if name.contains('\\') || name.contains('\0') {
+        return Err(unsafe_entry());
+    }
+    let path = Path::new(name.trim_end_matches('/'));
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str().ok_or_else(unsafe_entry)?),
+            _ => return Err(unsafe_entry()),
+        }
+    }
+    match parts.as_slice() {
+        ["index.json"] => Ok(path.to_path_buf()),
+        ["skills"] if name.ends_with('/') => Ok(path.to_path_buf()),
+        ["skills", skill, ..] if is_valid_name(skill) => Ok(path.to_path_buf()),
+        _ => Err(unsafe_entry()),
+    }
+}
+
+/// Write the named registry skills to a bundle at `dest` for
+/// [`import_skill_bundle`] on another machine.  Returns the index written.
+pub fn export_skill_bundle(skill_names: &[String], dest: &str) -> Result<SkillBundleIndex, String> {
+    let sources = read_skill_sources().unwrap_or_default();
+    let mut skills = Vec::new();
+    for name in skill_names {
+        let dir = get_skill_dir(name)?.ok_or_else(|| format!("Skill '{}' not found", name))?;
+        let content = read_skill(name)?;
+        let (frontmatter, _) = crate::agent::parse_frontmatter(&content);
+        let source = sources.get(name);
+        let entry = SkillBundleEntry {
+            name: name.clone(),
+            description: frontmatter.get("description").cloned().unwrap_or_default(),
+            source: source.map(|s| s.source.clone()).unwrap_or_default(),
+            id: source.map(|s| s.id.clone()).unwrap_or_default(),
+        };
+        skills.push((entry, dir));
+    }
+    write_skill_bundle(&skills, Path::new(dest))
+}
+
+fn write_skill_bundle(
+    skills: &[(SkillBundleEntry, PathBuf)],
+    dest: &Path,
+) -> Result<SkillBundleIndex, String> {
+    let index = SkillBundleIndex {
+        format: SKILL_BUNDLE_FORMAT,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        skills: skills.iter().map(|(entry, _)| entry.clone()).collect(),
+    };
+
+    let file = fs::File::create(dest)
+        .map_err(|e| format!("Failed to create '{}': {}", dest.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+
+    zip.start_file("index.json", options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(to_stable_json(&index)?.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    for (entry, dir) in skills {
+        let mut files = Vec::new();
+        collect_bundle_files(dir, dir, &mut files);
+        files.sort();
+        for relative in files {
+            let entry_name = format!(
+                "skills/{}/{}",
+                entry.name,
+                relative.to_string_lossy().replace('\\', "/")
+            );
+            let path = dir.join(&relative);
+            let data =
+                fs::read(&path).map_err(|e| format!("Failed to read {}: {}", entry_name, e))?;
+            zip.start_file(entry_name, zip_entry_options(&path))
+                .map_err(|e| e.to_string())?;
+            zip.write_all(&data).map_err(|e| e.to_string())?;
+        }
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(index)
+}
+
+/// Regular files under `dir`, relative to `root`.  Symlinks are left out;
+/// the importer would refuse them.
+pub(crate) fn collect_bundle_files(root: &Path, dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_bundle_files(root, &path, out);
+        } else if file_type.is_file() {
+            if let Ok(relative) = path.strip_prefix(root) {
+                out.push(relative.to_path_buf());
+            }
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn skill_dir(root: &Path, name: &str) -> PathBuf {
+        let dir = root.join(name);
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: {}\ndescription: Test skill\n---\n# {}\n",
+                name, name
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("scripts").join("run.sh"), "echo hi\n").unwrap();
+        dir
+    }
+
+    fn entry(name: &str) -> SkillBundleEntry {
+        SkillBundleEntry {
+            name: name.into(),
+            description: "Test skill".into(),
+            source: "acme/skills".into(),
+            id: format!("acme/skills/{}", name),
+        }
+    }
+
+    /// A raw archive with the given entries, for crafting bad bundles.
+    fn raw_bundle(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, content) in entries {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn export_then_import_round_trips_and_skips_existing() {
+        let source = tempdir().unwrap();
+        let skills = vec![
+            (entry("lint"), skill_dir(source.path(), "lint")),
+            (entry("deploy"), skill_dir(source.path(), "deploy")),
+        ];
+        let bundle = source.path().join("team.skillbundle");
+        let written = write_skill_bundle(&skills, &bundle).unwrap();
+        assert_eq!(written.skills.len(), 2);
+
+        let target = tempdir().unwrap();
+        fs::create_dir_all(target.path().join("deploy")).unwrap();
+        fs::write(target.path().join("deploy").join("SKILL.md"), "# mine\n").unwrap();
+
+        let (index, report) = import_skill_bundle_into(&bundle, target.path()).unwrap();
+        assert_eq!(index.skills[0], entry("lint"));
+        assert_eq!(report.installed, vec!["lint"]);
+        assert_eq!(report.skipped, vec!["deploy"]);
+        assert_eq!(
+            fs::read_to_string(target.path().join("lint/scripts/run.sh")).unwrap(),
+            "echo hi\n"
+        );
+        assert_eq!(
+            fs::read_to_string(target.path().join("deploy/SKILL.md")).unwrap(),
+            "# mine\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn executable_companions_keep_their_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let source = tempdir().unwrap();
+        let dir = skill_dir(source.path(), "lint");
+        let script = dir.join("scripts/run.sh");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let bundle = source.path().join("team.skillbundle");
+        write_skill_bundle(&[(entry("lint"), dir)], &bundle).unwrap();
+
+        let target = tempdir().unwrap();
+        import_skill_bundle_into(&bundle, target.path()).unwrap();
+        let mode = |path: PathBuf| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(target.path().join("lint/scripts/run.sh")), 0o755);
+        assert_eq!(mode(target.path().join("lint/SKILL.md")) & 0o111, 0);
+    }
+
+    #[test]
+    fn traversal_entries_reject_the_whole_bundle() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("skills");
+        fs::create_dir_all(&target).unwrap();
+        let index = r#"{"format":1,"skills":[{"name":"lint"}]}"#;
+
+        for evil in [
+            "skills/lint/../../../escaped.txt",
+            "../escaped.txt",
+            "/tmp/escaped.txt",
+            "skills/../escaped.txt",
+            "skills/lint\\..\\..\\escaped.txt",
+            "notes.txt",
+        ] {
+            let bundle = dir.path().join("evil.skillbundle");
+            raw_bundle(
+                &bundle,
+                &[
+                    ("index.json", index),
+                    ("skills/lint/SKILL.md", "# lint\n"),
+                    (evil, "pwned"),
+                ],
+            );
+            let err = import_skill_bundle_into(&bundle, &target).unwrap_err();
+            assert!(err.contains("Unsafe path"), "{}: {}", evil, err);
+            assert!(!dir.path().join("escaped.txt").exists());
+            assert!(fs::read_dir(&target).unwrap().next().is_none(), "{}", evil);
+        }
+    }
+
+    #[test]
+    fn index_is_validated() {
+        let dir = tempdir().unwrap();
+        let bundle = dir.path().join("bad.skillbundle");
+        let check = |index: &str, files: &[(&str, &str)]| {
+            let mut entries = vec![("index.json", index)];
+            entries.extend_from_slice(files);
+            raw_bundle(&bundle, &entries);
+            import_skill_bundle_into(&bundle, dir.path()).unwrap_err()
+        };
+
+        let lint = [("skills/lint/SKILL.md", "# lint\n")];
+        assert!(
+            check(r#"{"format":1,"skills":[{"name":".."}]}"#, &lint).contains("Invalid skill name")
+        );
+        assert!(check(
+            r#"{"format":1,"skills":[{"name":"lint"},{"name":"lint"}]}"#,
+            &lint
+        )
+        .contains("listed twice"));
+        assert!(check(r#"{"format":1,"skills":[{"name":"docs"}]}"#, &lint).contains("no SKILL.md"));
+        assert!(check(r#"{"format":99,"skills":[]}"#, &lint).contains("newer"));
+
+        raw_bundle(&bundle, &lint);
+        assert!(import_skill_bundle_into(&bundle, dir.path())
+            .unwrap_err()
+            .contains("no index.json"));
+    }
+
+    #[test]
+    fn oversized_entries_are_refused() {
+        let dir = tempdir().unwrap();
+        let bundle = dir.path().join("big.skillbundle");
+        let big = "x".repeat(MAX_BUNDLE_FILE_BYTES as usize + 1);
+        raw_bundle(
+            &bundle,
+            &[
+                ("index.json", r#"{"format":1,"skills":[{"name":"lint"}]}"#),
+                ("skills/lint/SKILL.md", &big),
+            ],
+        );
+        let err = import_skill_bundle_into(&bundle, dir.path()).unwrap_err();
+        assert!(err.contains("too large"), "{}", err);
+    }
+}
@@ -0,0 +1,315 @@
+use std::fs;
+use std::path::{Component, Path};
+
+use super::fsutil::{remove_guarded, RemovalContext, RemovalOutcome};
+
+// ── Skill Directory Copying ──────────────────────────────────────────────────
+//
+// Copies a skill directory (SKILL.md plus companion `scripts/`, `references/`,
+// etc.) without losing what makes the companions usable: file permissions are
+// carried over so `scripts/*.sh` stay executable, and relative symlinks that
+// stay inside the skill are recreated as symlinks.  Files are always copied,
+// never hardlinked, so editing a project copy can never reach back into the
+// global registry.  One bad entry does not abort the copy; it is recorded in
+// the report instead.
+
+/// Outcome of copying a skill directory.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SkillCopyReport {
+    /// Number of files and symlinks created in the destination.
+    pub copied: usize,
+    /// `"<relative path>: <reason>"` for every entry that was left out.
+    pub skipped: Vec<String>,
+}
+
+impl SkillCopyReport {
+    /// True when some entries could not be copied.
+    pub fn is_partial(&self) -> bool {
+        !self.skipped.is_empty()
+    }
+}
+
+/// Recursively copy the skill directory `src` into `dst`.
+///
+/// Only failing to read `src` or to create `dst` is an error; individual
+/// entries that cannot be copied are listed in [`SkillCopyReport::skipped`].
+pub fn copy_skill_dir(src: &Path, dst: &Path) -> Result<SkillCopyReport, String> {
+    fs::create_dir_all(dst)
+        .map_err(|e| format!("Failed to create dir '{}': {}", dst.display(), e))?;
+    let entries =
+        fs::read_dir(src).map_err(|e| format!("Failed to read dir '{}': {}", src.display(), e))?;
+
+    let mut report = SkillCopyReport::default();
+    for entry in entries {
+        match entry {
+            Ok(entry) => copy_entry(src, dst, Path::new(&entry.file_name()), &mut report),
+            Err(e) => report.skipped.push(format!(".: {}", e)),
+        }
+    }
+    Ok(report)
+}
+
+/// Copy only the named top-level entries (files or directories) of the skill
+/// directory `src` into `dst`.  Names that do not exist are ignored.
+/// Symlinks are checked against `src` as a whole, so a link from `scripts/`
+/// into `references/` survives.
+pub(crate) fn copy_skill_entries(
+    src: &Path,
+    dst: &Path,
+    names: &[&str],
+) -> Result<SkillCopyReport, String> {
+    fs::create_dir_all(dst)
+        .map_err(|e| format!("Failed to create dir '{}': {}", dst.display(), e))?;
+
+    let mut report = SkillCopyReport::default();
+    for name in names {
+        if src.join(name).symlink_metadata().is_ok() {
+            copy_entry(src, dst, Path::new(name), &mut report);
+        }
+    }
+    Ok(report)
+}
+
+/// Copy `rel` (relative to both roots) from `src_root` to `dst_root`.
+fn copy_entry(src_root: &Path, dst_root: &Path, rel: &Path, report: &mut SkillCopyReport) {
+    let src_path = src_root.join(rel);
+    let dst_path = dst_root.join(rel);
+    let skip = |report: &mut SkillCopyReport, reason: String| {
+        report
+            .skipped
+            .push(format!("{}: {}", rel.display(), reason))
+    };
+
+    let meta = match src_path.symlink_metadata() {
+        Ok(m) => m,
+        Err(e) => return skip(report, e.to_string()),
+    };
+
+    if meta.file_type().is_symlink() {
+        match copy_symlink(&src_path, &dst_path, rel, dst_root) {
+            Ok(()) => report.copied += 1,
+            Err(reason) => skip(report, reason),
+        }
+    } else if meta.is_dir() {
+        if let Err(e) = fs::create_dir_all(&dst_path) {
+            return skip(report, e.to_string());
+        }
+        let entries = match fs::read_dir(&src_path) {
+            Ok(entries) => entries,
+            Err(e) => return skip(report, e.to_string()),
+        };
+        for entry in entries {
+            match entry {
+                Ok(entry) => copy_entry(src_root, dst_root, &rel.join(entry.file_name()), report),
+                Err(e) => skip(report, e.to_string()),
+            }
+        }
+    } else if meta.is_file() {
+        match copy_file(&src_path, &dst_path, dst_root) {
+            Ok(()) => report.copied += 1,
+            Err(reason) => skip(report, reason),
+        }
+    } else {
+        skip(report, "not a regular file".to_string());
+    }
+}
+
+/// Copy one file's contents and permissions.  An existing destination is
+/// removed first: writing through it would modify every hardlink that shares
+/// it, and a read-only file would refuse the write.
+fn copy_file(src: &Path, dst: &Path, dst_root: &Path) -> Result<(), String> {
+    remove_existing(dst, dst_root)?;
+    fs::copy(src, dst).map_err(|e| e.to_string())?;
+    let permissions = fs::metadata(src).map_err(|e| e.to_string())?.permissions();
+    fs::set_permissions(dst, permissions).map_err(|e| e.to_string())
+}
+
+/// Recreate the symlink at `src` (found at `rel` inside the skill) at `dst`.
+/// Links that leave the skill directory are rejected, since following them
+/// could copy arbitrary files from the machine into a project.
+fn copy_symlink(src: &Path, dst: &Path, rel: &Path, dst_root: &Path) -> Result<(), String> {
+    let target = fs::read_link(src).map_err(|e| e.to_string())?;
+    if !symlink_stays_inside(rel, &target) {
+        return Err(format!(
+            "symlink to '{}' points outside the skill directory",
+            target.display()
+        ));
+    }
+    remove_existing(dst, dst_root)?;
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&target, dst).map_err(|e| e.to_string())
+    }
+    #[cfg(not(unix))]
+    {
+        // Creating symlinks usually needs elevated rights on Windows, so the
+        // linked file is copied in its place.
+        let resolved = src.parent().unwrap_or(src).join(&target);
+        if resolved.is_file() {
+            copy_file(&resolved, dst, dst_root)
+        } else {
+            Err("directory symlinks are not supported on this platform".to_string())
+        }
+    }
+}
+
+/// Clear whatever sits at `path` inside the destination skill `dst_root`,
+/// honouring the `destructive_ops` setting.  In a dry run the old entry stays,
+/// so the new one is skipped rather than written through it.
+fn remove_existing(path: &Path, dst_root: &Path) -> Result<(), String> {
+    let context = RemovalContext::skills(vec![dst_root.to_path_buf()]);
+    match remove_guarded(path, &context)? {
+        RemovalOutcome::WouldRemove { .. } => {
+            Err("dry run: existing entry was not replaced".to_string())
+        }
+        RemovalOutcome::Removed { .. } | RemovalOutcome::Missing => Ok(()),
+    }
+}
+
+/// Whether a symlink located at `link` (relative to the skill root) with the
+/// given `target` resolves inside the skill root.  Purely lexical: absolute
+/// targets are always rejected and `..` may not climb above the root.
+pub(crate) fn symlink_stays_inside(link: &Path, target: &Path) -> bool {
+    let mut depth = link
+        .parent()
+        .map(|p| {
+            p.components()
+                .filter(|c| matches!(c, Component::Normal(_)))
+                .count()
+        })
+        .unwrap_or(0);
+
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn symlinks_escaping_the_skill_are_rejected() {
+        let link = Path::new("scripts/run.sh");
+        assert!(symlink_stays_inside(link, Path::new("helper.sh")));
+        assert!(symlink_stays_inside(
+            link,
+            Path::new("../references/run.md")
+        ));
+        assert!(symlink_stays_inside(link, Path::new("./lib/../helper.sh")));
+        assert!(symlink_stays_inside(Path::new("latest"), Path::new("v2")));
+
+        assert!(!symlink_stays_inside(link, Path::new("../../secrets")));
+        assert!(!symlink_stays_inside(Path::new("latest"), Path::new("..")));
+        assert!(!symlink_stays_inside(link, Path::new("../x/../../y")));
+        let absolute = std::env::temp_dir().join("elsewhere");
+        assert!(!symlink_stays_inside(link, &absolute));
+    }
+
+    #[test]
+    fn copies_nested_companions_and_replaces_existing_files() {
+        let src = tempdir().unwrap();
+        let dst = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), "# Skill").unwrap();
+        fs::create_dir_all(src.path().join("references/api")).unwrap();
+        fs::write(src.path().join("references/api/v1.md"), "v1").unwrap();
+        fs::write(dst.path().join("SKILL.md"), "stale").unwrap();
+
+        let report = copy_skill_dir(src.path(), dst.path()).unwrap();
+        assert!(!report.is_partial(), "{:?}", report.skipped);
+        assert_eq!(report.copied, 2);
+        assert_eq!(
+            fs::read_to_string(dst.path().join("SKILL.md")).unwrap(),
+            "# Skill"
+        );
+        assert_eq!(
+            fs::read_to_string(dst.path().join("references/api/v1.md")).unwrap(),
+            "v1"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn executable_bits_survive_the_copy() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = tempdir().unwrap();
+        let dst = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("scripts")).unwrap();
+        let script = src.path().join("scripts/setup.sh");
+        fs::write(&script, "#!/bin/sh\necho ok\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        copy_skill_dir(src.path(), dst.path()).unwrap();
+        let mode = fs::metadata(dst.path().join("scripts/setup.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn internal_symlinks_are_recreated_and_escaping_ones_reported() {
+        use std::os::unix::fs::symlink;
+
+        let outside = tempdir().unwrap();
+        fs::write(outside.path().join("secret"), "nope").unwrap();
+        let src = tempdir().unwrap();
+        let dst = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("scripts")).unwrap();
+        fs::create_dir_all(src.path().join("references")).unwrap();
+        fs::write(src.path().join("references/guide.md"), "guide").unwrap();
+        symlink(
+            "../references/guide.md",
+            src.path().join("scripts/guide.md"),
+        )
+        .unwrap();
+        symlink(outside.path().join("secret"), src.path().join("secret")).unwrap();
+
+        let report = copy_skill_dir(src.path(), dst.path()).unwrap();
+        let link = dst.path().join("scripts/guide.md");
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(
+            fs::read_link(&link).unwrap(),
+            Path::new("../references/guide.md")
+        );
+        assert_eq!(fs::read_to_string(&link).unwrap(), "guide");
+
+        assert!(dst.path().join("secret").symlink_metadata().is_err());
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].starts_with("secret:"), "{:?}", report);
+    }
+
+    #[test]
+    fn copy_skill_entries_only_copies_named_entries() {
+        let src = tempdir().unwrap();
+        let dst = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("scripts")).unwrap();
+        fs::write(src.path().join("scripts/a.sh"), "a").unwrap();
+        fs::write(src.path().join("LICENSE"), "MIT").unwrap();
+        fs::write(src.path().join("notes.txt"), "private").unwrap();
+
+        let report =
+            copy_skill_entries(src.path(), dst.path(), &["scripts", "LICENSE", "docs"]).unwrap();
+        assert_eq!(report.copied, 2);
+        assert!(dst.path().join("scripts/a.sh").exists());
+        assert!(dst.path().join("LICENSE").exists());
+        assert!(!dst.path().join("notes.txt").exists());
+    }
+}
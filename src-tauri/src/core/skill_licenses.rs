@@ -0,0 +1,182 @@
+use serde::Serialize;
+
+use super::*;
+
+// ── License Report ───────────────────────────────────────────────────────────
+//
+// Purely informational: lists every installed skill with the license recorded
+// for it so users can review what they pulled in from community repos.
+// Nothing is ever blocked on a missing or unexpected license.
+
+/// One installed skill in the license report.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SkillLicenseEntry {
+    pub name: String,
+    /// SKILL.md frontmatter license, or the one recorded at import time.
+    pub license: Option<String>,
+    /// GitHub "owner/repo" the skill was imported from; `None` for skills
+    /// created locally.
+    pub source: Option<String>,
+    /// "github", "bundled", or "local".
+    pub kind: String,
+    /// True when no license is known for a skill Automatic did not ship.
+    pub unknown: bool,
+}
+
+/// Every installed skill with its recorded license and source.
+pub fn license_report() -> Result<Vec<SkillLicenseEntry>, String> {
+    Ok(build_license_report(&list_skills()?))
+}
+
+fn build_license_report(skills: &[SkillEntry]) -> Vec<SkillLicenseEntry> {
+    skills
+        .iter()
+        .map(|skill| {
+            let kind = skill
+                .source
+                .as_ref()
+                .map(|s| s.kind.clone())
+                .unwrap_or_else(|| "local".to_string());
+            SkillLicenseEntry {
+                name: skill.name.clone(),
+                license: skill.license.clone(),
+                source: skill.source.as_ref().map(|s| s.source.clone()),
+                // Bundled skills ship under the app's own license.
+                unknown: skill.license.is_none() && kind != "bundled",
+                kind,
+            }
+        })
+        .collect()
+}
+
+/// Render the report as CSV with a header row.
+pub fn license_report_csv(entries: &[SkillLicenseEntry]) -> String {
+    let mut out = String::from("name,license,source,kind,unknown\n");
+    for e in entries {
+        out.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(&e.name),
+            csv_field(e.license.as_deref().unwrap_or("")),
+            csv_field(e.source.as_deref().unwrap_or("")),
+            csv_field(&e.kind),
+            e.unknown
+        ));
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render the report as a markdown table.
+pub fn license_report_markdown(entries: &[SkillLicenseEntry]) -> String {
+    let mut out = String::from("| Skill | License | Source | Kind |\n|---|---|---|---|\n");
+    for e in entries {
+        let license = match (&e.license, e.unknown) {
+            (Some(l), _) => md_cell(l),
+            (None, true) => "**unknown**".to_string(),
+            (None, false) => "—".to_string(),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            md_cell(&e.name),
+            license,
+            md_cell(e.source.as_deref().unwrap_or("—")),
+            md_cell(&e.kind)
+        ));
+    }
+    out
+}
+
+fn md_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &str, license: Option<&str>, source: Option<(&str, &str)>) -> SkillEntry {
+        SkillEntry {
+            name: name.into(),
+            sources: vec!["agents".into()],
+            source: source.map(|(repo, kind)| SkillSource {
+                source: repo.into(),
+                id: format!("{}/{}", repo, name),
+                kind: kind.into(),
+                pinned: false,
+                license: license.map(String::from),
+                installed_hash: None,
+                imported_at: None,
+            }),
+            has_resources: false,
+            description: None,
+            license: license.map(String::from),
+            plugin_id: None,
+            collection: None,
+            locally_modified: false,
+            scope: "global".into(),
+        }
+    }
+
+    fn report() -> Vec<SkillLicenseEntry> {
+        build_license_report(&[
+            skill("react", Some("MIT"), Some(("vercel-labs/skills", "github"))),
+            skill(
+                "terraform",
+                None,
+                Some(("hashicorp/agent-skills", "github")),
+            ),
+            skill(
+                "automatic",
+                None,
+                Some(("automatic/automatic-app", "bundled")),
+            ),
+            skill("house-style", None, None),
+        ])
+    }
+
+    #[test]
+    fn skills_without_a_license_are_flagged_unless_bundled() {
+        let report = report();
+        let unknown: Vec<&str> = report
+            .iter()
+            .filter(|e| e.unknown)
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(unknown, vec!["terraform", "house-style"]);
+        assert_eq!(report[3].kind, "local");
+        assert_eq!(report[3].source, None);
+        assert_eq!(report[0].source.as_deref(), Some("vercel-labs/skills"));
+    }
+
+    #[test]
+    fn csv_export_has_one_row_per_skill_and_quotes_commas() {
+        let mut entries = report();
+        entries[0].license = Some("MIT, see LICENSE".into());
+        let csv = license_report_csv(&entries);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "name,license,source,kind,unknown");
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[1],
+            "react,\"MIT, see LICENSE\",vercel-labs/skills,github,false"
+        );
+        assert_eq!(lines[4], "house-style,,,local,true");
+    }
+
+    #[test]
+    fn markdown_export_marks_unknown_licenses() {
+        let md = license_report_markdown(&report());
+        assert!(md.contains("| react | MIT | vercel-labs/skills | github |"));
+        assert!(md.contains("| terraform | **unknown** | hashicorp/agent-skills | github |"));
+        assert!(md.contains("| automatic | — | automatic/automatic-app | bundled |"));
+    }
+}
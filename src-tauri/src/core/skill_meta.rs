@@ -0,0 +1,346 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::skills::{get_skill_path, save_skill};
+
+// ── SKILL.md Frontmatter ─────────────────────────────────────────────────────
+//
+// Agents read a skill's `name` and `description` from the YAML frontmatter
+// at the top of SKILL.md, and some refuse or silently skip a skill whose
+// frontmatter is missing or malformed.  The block is parsed as real YAML
+// here, so quoting, block scalars and lists behave as they do in the agents.
+// Validation only ever warns: saving a skill with issues still succeeds.
+
+/// Agents truncate or reject descriptions longer than this.
+pub const MAX_SKILL_DESCRIPTION_LEN: usize = 1024;
+
+/// Characters Windows does not allow in a file name.
+const WINDOWS_ILLEGAL_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Device names Windows reserves regardless of extension.
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// The frontmatter of a SKILL.md.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SkillMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// `allowed-tools`, written either as a YAML list or as one string of
+    /// comma- or space-separated tools.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    /// Every other top-level key, as JSON.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillIssueKind {
+    MissingFrontmatter,
+    InvalidFrontmatter,
+    MissingName,
+    NameMismatch,
+    IllegalName,
+    MissingDescription,
+    DescriptionTooLong,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillValidationIssue {
+    pub kind: SkillIssueKind,
+    pub message: String,
+}
+
+/// Result of [`save_skill_checked`].  The skill is saved either way.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SkillSaveResult {
+    pub warnings: Vec<SkillValidationIssue>,
+}
+
+/// The YAML between a leading `---` line and the next `---` (or `...`)
+/// line, or `None` when the content does not start with frontmatter.
+fn frontmatter_block(content: &str) -> Option<&str> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines = content.split_inclusive('\n');
+    if lines.next()?.trim_end() != "---" {
+        return None;
+    }
+    let start = content.find('\n')? + 1;
+    let mut offset = start;
+    for line in lines {
+        if matches!(line.trim_end(), "---" | "...") {
+            return Some(&content[start..offset]);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Parse the frontmatter of a SKILL.md.  `Ok(None)` when there is none;
+/// `Err` with the YAML error when the block is not a valid YAML mapping.
+pub fn parse_skill_frontmatter(content: &str) -> Result<Option<SkillMeta>, String> {
+    let Some(block) = frontmatter_block(content) else {
+        return Ok(None);
+    };
+    let value: serde_yaml::Value = serde_yaml::from_str(block).map_err(|e| e.to_string())?;
+    let mapping = match value {
+        serde_yaml::Value::Mapping(mapping) => mapping,
+        // An empty block parses as null.
+        serde_yaml::Value::Null => return Ok(Some(SkillMeta::default())),
+        _ => return Err("frontmatter is not a mapping of keys to values".into()),
+    };
+
+    let mut meta = SkillMeta::default();
+    for (key, value) in mapping {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        match key {
+            "name" => meta.name = yaml_scalar(&value),
+            "description" => meta.description = yaml_scalar(&value),
+            "license" => meta.license = yaml_scalar(&value),
+            "allowed-tools" => meta.allowed_tools = yaml_tool_list(&value),
+            _ => {
+                if let Ok(json) = serde_json::to_value(&value) {
+                    meta.extra.insert(key.to_string(), json);
+                }
+            }
+        }
+    }
+    Ok(Some(meta))
+}
+
+/// A scalar as a trimmed string; `None` for empty values and non-scalars.
+fn yaml_scalar(value: &serde_yaml::Value) -> Option<String> {
+    let text = match value {
+        serde_yaml::Value::String(s) => s.trim().to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+fn yaml_tool_list(value: &serde_yaml::Value) -> Vec<String> {
+    match value {
+        serde_yaml::Value::Sequence(items) => items.iter().filter_map(yaml_scalar).collect(),
+        serde_yaml::Value::String(s) if s.contains(',') => s
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+        serde_yaml::Value::String(s) => s.split_whitespace().map(String::from).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Why `name` cannot be a directory name on Windows, if it cannot.
+fn windows_name_problem(name: &str) -> Option<String> {
+    if let Some(c) = name
+        .chars()
+        .find(|c| WINDOWS_ILLEGAL_CHARS.contains(c) || c.is_control())
+    {
+        return Some(format!("contains '{}'", c.escape_default()));
+    }
+    if name.ends_with('.') || name.ends_with(' ') {
+        return Some("ends with a dot or space".into());
+    }
+    let stem = name.split('.').next().unwrap_or(name);
+    if WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|r| r.eq_ignore_ascii_case(stem))
+    {
+        return Some(format!("'{}' is a reserved device name", stem));
+    }
+    None
+}
+
+fn issue(kind: SkillIssueKind, message: String) -> SkillValidationIssue {
+    SkillValidationIssue { kind, message }
+}
+
+/// Check SKILL.md `content`.  With `dir_name`, the directory the skill is
+/// stored in, the frontmatter `name` must match it.
+pub fn validate_skill_content(dir_name: Option<&str>, content: &str) -> Vec<SkillValidationIssue> {
+    use SkillIssueKind::*;
+
+    let mut issues = Vec::new();
+    if let Some(problem) = dir_name.and_then(windows_name_problem) {
+        issues.push(issue(
+            IllegalName,
+            format!(
+                "Skill directory name {} and cannot be used on Windows",
+                problem
+            ),
+        ));
+    }
+
+    let meta = match parse_skill_frontmatter(content) {
+        Ok(Some(meta)) => meta,
+        Ok(None) => {
+            issues.push(issue(
+                MissingFrontmatter,
+                "SKILL.md has no frontmatter; add a '---' block with name and description".into(),
+            ));
+            return issues;
+        }
+        Err(e) => {
+            issues.push(issue(
+                InvalidFrontmatter,
+                format!("SKILL.md frontmatter is not valid YAML: {}", e),
+            ));
+            return issues;
+        }
+    };
+
+    match (&meta.name, dir_name) {
+        (None, _) => issues.push(issue(MissingName, "Frontmatter has no 'name'".into())),
+        (Some(name), Some(dir_name)) if name != dir_name => issues.push(issue(
+            NameMismatch,
+            format!(
+                "Frontmatter name '{}' does not match the skill directory '{}'",
+                name, dir_name
+            ),
+        )),
+        (Some(name), None) => {
+            if let Some(problem) = windows_name_problem(name) {
+                issues.push(issue(
+                    IllegalName,
+                    format!("Skill name {} and cannot be used on Windows", problem),
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    match &meta.description {
+        None => issues.push(issue(
+            MissingDescription,
+            "Frontmatter has no 'description'; agents use it to decide when to load the skill"
+                .into(),
+        )),
+        Some(description) if description.chars().count() > MAX_SKILL_DESCRIPTION_LEN => issues
+            .push(issue(
+                DescriptionTooLong,
+                format!(
+                    "Description is {} characters; keep it under {}",
+                    description.chars().count(),
+                    MAX_SKILL_DESCRIPTION_LEN
+                ),
+            )),
+        Some(_) => {}
+    }
+
+    issues
+}
+
+/// Validate a skill given either its name (the installed SKILL.md is read
+/// and its `name` checked against the directory) or raw SKILL.md content.
+/// Anything that spans several lines or starts with `---` is content.
+pub fn validate_skill(name_or_content: &str) -> Result<Vec<SkillValidationIssue>, String> {
+    if name_or_content.contains('\n') || name_or_content.starts_with("---") {
+        return Ok(validate_skill_content(None, name_or_content));
+    }
+    let name = name_or_content;
+    let path = get_skill_path(name)?.ok_or_else(|| format!("Skill '{}' not found", name))?;
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    Ok(validate_skill_content(Some(name), &content))
+}
+
+/// [`save_skill`], returning the validation issues of what was saved as
+/// warnings.
+pub fn save_skill_checked(name: &str, content: &str) -> Result<SkillSaveResult, String> {
+    save_skill(name, content)?;
+    Ok(SkillSaveResult {
+        warnings: validate_skill_content(Some(name), content),
+    })
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(issues: &[SkillValidationIssue]) -> Vec<SkillIssueKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn parses_yaml_frontmatter() {
+        let content = "---\nname: \"pdf-tools\"\ndescription: >\n  Fill forms: and\n  merge PDFs.\nlicense: MIT\nallowed-tools: Read, Grep\nmetadata:\n  version: 2\n---\n# PDF\n";
+        let meta = parse_skill_frontmatter(content).unwrap().unwrap();
+        assert_eq!(meta.name.as_deref(), Some("pdf-tools"));
+        assert_eq!(
+            meta.description.as_deref(),
+            Some("Fill forms: and merge PDFs.")
+        );
+        assert_eq!(meta.license.as_deref(), Some("MIT"));
+        assert_eq!(meta.allowed_tools, vec!["Read", "Grep"]);
+        assert_eq!(meta.extra["metadata"], serde_json::json!({ "version": 2 }));
+
+        let listed = "---\nname: x\nallowed-tools:\n  - Bash(git add:*)\n  - Read\n---\n";
+        let meta = parse_skill_frontmatter(listed).unwrap().unwrap();
+        assert_eq!(meta.allowed_tools, vec!["Bash(git add:*)", "Read"]);
+    }
+
+    #[test]
+    fn missing_and_invalid_frontmatter() {
+        assert_eq!(parse_skill_frontmatter("# Just markdown\n"), Ok(None));
+        assert_eq!(parse_skill_frontmatter("---\nname: x\n"), Ok(None));
+        assert!(parse_skill_frontmatter("---\nname: [unclosed\n---\n").is_err());
+
+        let issues = validate_skill_content(Some("x"), "# Just markdown\n");
+        assert_eq!(kinds(&issues), vec![SkillIssueKind::MissingFrontmatter]);
+        let issues = validate_skill_content(Some("x"), "---\ndescription: a: b\n---\n");
+        assert_eq!(kinds(&issues), vec![SkillIssueKind::InvalidFrontmatter]);
+    }
+
+    #[test]
+    fn validates_name_and_description() {
+        let ok = "---\nname: tdd\ndescription: Write the test first.\n---\n";
+        assert!(validate_skill_content(Some("tdd"), ok).is_empty());
+
+        let issues = validate_skill_content(Some("testing"), ok);
+        assert_eq!(kinds(&issues), vec![SkillIssueKind::NameMismatch]);
+
+        let long = format!(
+            "---\nname: tdd\ndescription: {}\n---\n",
+            "a".repeat(MAX_SKILL_DESCRIPTION_LEN + 1)
+        );
+        let issues = validate_skill_content(Some("tdd"), &long);
+        assert_eq!(kinds(&issues), vec![SkillIssueKind::DescriptionTooLong]);
+
+        let issues = validate_skill_content(None, "---\nname: \"\"\n---\n");
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                SkillIssueKind::MissingName,
+                SkillIssueKind::MissingDescription
+            ]
+        );
+    }
+
+    #[test]
+    fn windows_illegal_names_are_flagged() {
+        assert!(windows_name_problem("code-review").is_none());
+        assert!(windows_name_problem("what?").is_some());
+        assert!(windows_name_problem("a:b").is_some());
+        assert!(windows_name_problem("trailing.").is_some());
+        assert!(windows_name_problem("con").is_some());
+        assert!(windows_name_problem("lpt1.skill").is_some());
+        assert!(windows_name_problem("console").is_none());
+
+        let issues = validate_skill_content(None, "---\nname: \"a|b\"\ndescription: d\n---\n");
+        assert_eq!(kinds(&issues), vec![SkillIssueKind::IllegalName]);
+    }
+}
@@ -0,0 +1,304 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agent::{self, SkillDelivery};
+
+use super::*;
+
+// ── Skill delivery preview ───────────────────────────────────────────────────
+//
+// Shows what a project sync would place on disk for one skill, per agent,
+// without writing anything.  Delivery decisions come from
+// `agent::skill_delivery`, the same rule the sync engine applies.
+
+/// One file a sync would write for the skill.
+#[derive(Debug, Serialize)]
+pub struct SkillPreviewFile {
+    /// Path relative to the project directory.
+    pub path: String,
+    /// Final file content.  `None` for companion files that are copied as-is
+    /// and are not valid UTF-8 text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// The skill as delivered into one agent skill directory.
+#[derive(Debug, Serialize)]
+pub struct SkillPreviewTarget {
+    /// Skill directory relative to the project (e.g. `.claude/skills/my-skill`).
+    pub path: String,
+    pub delivery: SkillDelivery,
+    /// For symlinks: the hub directory the link points at.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_target: Option<String>,
+    /// Files materialised in this directory.  Empty for symlinks — the agent
+    /// reads the hub files through the link.
+    pub files: Vec<SkillPreviewFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillAgentPreview {
+    pub agent_id: String,
+    pub agent_label: String,
+    /// `false` when the agent has no project skill directory; it never
+    /// receives the skill.
+    pub receives_skill: bool,
+    pub targets: Vec<SkillPreviewTarget>,
+}
+
+/// Per-agent preview returned by [`preview_skill_for_agents`].
+#[derive(Debug, Serialize)]
+pub struct SkillPreview {
+    pub skill_name: String,
+    /// Whether the project currently includes the skill.  The preview shows
+    /// what a sync would produce either way.
+    pub attached: bool,
+    /// The project hub copy (`.agents/skills/<name>/`) every target derives from.
+    pub hub: SkillPreviewTarget,
+    pub agents: Vec<SkillAgentPreview>,
+}
+
+/// Compute, for every agent on the project, the skill artifacts a sync would
+/// produce for `skill_name`.  Read-only.
+pub fn preview_skill_for_agents(
+    skill_name: &str,
+    project_name: &str,
+) -> Result<SkillPreview, String> {
+    let raw = read_project(project_name)?;
+    let project: Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+
+    let custom = project
+        .custom_skills
+        .as_deref()
+        .unwrap_or(&[])
+        .iter()
+        .find(|s| s.name == skill_name);
+    // Custom skills live inline in the project JSON, so sync writes only their
+    // SKILL.md; registry skills are copied as whole directories.
+    let (content, source_dir) = match custom {
+        Some(cs) => (cs.content.clone(), None),
+        None => {
+            let content = read_skill(skill_name)?;
+            if content.is_empty() {
+                return Err(format!("Skill '{}' not found", skill_name));
+            }
+            (content, get_skill_dir(skill_name)?)
+        }
+    };
+
+    let sync_mode = read_settings().unwrap_or_default().sync_mode;
+    Ok(build_skill_preview(
+        &project,
+        skill_name,
+        &content,
+        source_dir.as_deref(),
+        &sync_mode,
+    ))
+}
+
+fn build_skill_preview(
+    project: &Project,
+    skill_name: &str,
+    content: &str,
+    source_dir: Option<&Path>,
+    sync_mode: &str,
+) -> SkillPreview {
+    let dir = PathBuf::from(&project.directory);
+    let hub_dir = dir.join(".agents").join("skills");
+    let rel = |p: &Path| {
+        p.strip_prefix(&dir)
+            .unwrap_or(p)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let hub_skill_dir = hub_dir.join(skill_name);
+
+    let hub = SkillPreviewTarget {
+        path: rel(&hub_skill_dir),
+        delivery: SkillDelivery::HubCopy,
+        link_target: None,
+        files: hub_files(&rel(&hub_skill_dir), content, source_dir),
+    };
+
+    let attached = project.skills.iter().any(|s| s == skill_name)
+        || project
+            .custom_skills
+            .as_deref()
+            .unwrap_or(&[])
+            .iter()
+            .any(|s| s.name == skill_name);
+
+    let agents = project
+        .agents
+        .iter()
+        .filter_map(|id| agent::from_id(id))
+        .map(|agent_instance| {
+            let targets: Vec<SkillPreviewTarget> = agent_instance
+                .skill_dirs(&dir)
+                .iter()
+                .map(|skills_dir| {
+                    let delivery = agent::skill_delivery(skills_dir, &hub_dir, sync_mode);
+                    let path = rel(&skills_dir.join(skill_name));
+                    match delivery {
+                        SkillDelivery::HubCopy => SkillPreviewTarget {
+                            path,
+                            delivery,
+                            link_target: None,
+                            files: hub_files(&hub.path, content, source_dir),
+                        },
+                        SkillDelivery::Symlink => SkillPreviewTarget {
+                            path,
+                            delivery,
+                            link_target: Some(hub.path.clone()),
+                            files: vec![],
+                        },
+                        SkillDelivery::Copy => SkillPreviewTarget {
+                            files: vec![SkillPreviewFile {
+                                path: format!("{}/SKILL.md", path),
+                                content: Some(content.to_string()),
+                            }],
+                            path,
+                            delivery,
+                            link_target: None,
+                        },
+                    }
+                })
+                .collect();
+            SkillAgentPreview {
+                agent_id: agent_instance.id().to_string(),
+                agent_label: agent_instance.label().to_string(),
+                receives_skill: !targets.is_empty(),
+                targets,
+            }
+        })
+        .collect();
+
+    SkillPreview {
+        skill_name: skill_name.to_string(),
+        attached,
+        hub,
+        agents,
+    }
+}
+
+/// Files in the hub copy: the registry directory copied recursively, or just
+/// `SKILL.md` when there is no source directory (mirrors
+/// `copy_skills_to_project`'s fallback).
+fn hub_files(rel_dir: &str, content: &str, source_dir: Option<&Path>) -> Vec<SkillPreviewFile> {
+    let mut files = Vec::new();
+    if let Some(src) = source_dir {
+        collect_files(src, src, &mut files);
+    }
+    if files.is_empty() {
+        files.push(("SKILL.md".to_string(), Some(content.to_string())));
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+        .into_iter()
+        .map(|(path, content)| SkillPreviewFile {
+            path: format!("{}/{}", rel_dir, path),
+            content,
+        })
+        .collect()
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<(String, Option<String>)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, out);
+        } else if let Ok(rel) = path.strip_prefix(root) {
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            out.push((rel, fs::read_to_string(&path).ok()));
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const RESTRICTED: &str = "---\nname: release-notes\ndescription: Draft release notes\nallowed-tools: Bash(git log:*)\nagents: claude\n---\n\n# Release notes\n";
+
+    fn project(agents: &[&str]) -> Project {
+        Project {
+            name: "demo".into(),
+            directory: "/work/demo".into(),
+            agents: agents.iter().map(|a| a.to_string()).collect(),
+            skills: vec!["release-notes".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn preview_covers_every_agent_with_its_delivery() {
+        let p = project(&["claude", "opencode", "warp"]);
+        let preview = build_skill_preview(&p, "release-notes", RESTRICTED, None, "symlink");
+
+        assert!(preview.attached);
+        assert_eq!(preview.hub.path, ".agents/skills/release-notes");
+        assert_eq!(preview.hub.files.len(), 1);
+        assert_eq!(
+            preview.hub.files[0].path,
+            ".agents/skills/release-notes/SKILL.md"
+        );
+        // Frontmatter is delivered verbatim — sync does not rewrite it.
+        assert_eq!(preview.hub.files[0].content.as_deref(), Some(RESTRICTED));
+
+        let claude = &preview.agents[0];
+        assert_eq!(claude.agent_id, "claude");
+        assert_eq!(claude.targets[0].path, ".claude/skills/release-notes");
+        assert_eq!(claude.targets[0].delivery, SkillDelivery::Symlink);
+        assert_eq!(
+            claude.targets[0].link_target.as_deref(),
+            Some(".agents/skills/release-notes")
+        );
+
+        let opencode = &preview.agents[1];
+        assert_eq!(opencode.targets[0].delivery, SkillDelivery::HubCopy);
+        assert_eq!(
+            opencode.targets[0].files[0].content.as_deref(),
+            Some(RESTRICTED)
+        );
+
+        assert_eq!(preview.agents.len(), 3);
+    }
+
+    #[test]
+    fn copy_mode_materialises_skill_md_and_hub_includes_companions() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("SKILL.md"), RESTRICTED).unwrap();
+        fs::create_dir_all(src.path().join("scripts")).unwrap();
+        fs::write(src.path().join("scripts/collect.sh"), "git log\n").unwrap();
+
+        let p = project(&["claude"]);
+        let preview =
+            build_skill_preview(&p, "release-notes", RESTRICTED, Some(src.path()), "copy");
+
+        let hub_paths: Vec<&str> = preview.hub.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            hub_paths,
+            vec![
+                ".agents/skills/release-notes/SKILL.md",
+                ".agents/skills/release-notes/scripts/collect.sh",
+            ]
+        );
+
+        let target = &preview.agents[0].targets[0];
+        assert_eq!(target.delivery, SkillDelivery::Copy);
+        assert_eq!(target.files.len(), 1);
+        assert_eq!(
+            target.files[0].path,
+            ".claude/skills/release-notes/SKILL.md"
+        );
+        assert_eq!(target.files[0].content.as_deref(), Some(RESTRICTED));
+    }
+}
@@ -0,0 +1,293 @@
+use serde::Serialize;
+
+use super::*;
+
+// ── Skill Requirements ───────────────────────────────────────────────────────
+//
+// A SKILL.md may declare what it expects to be available:
+//
+//     requires_mcp: [github]
+//     requires_commands:
+//       - gh
+//
+// Sync, the project checklist and `automatic doctor` cross-check a project's
+// selected skills against its enabled MCP servers and the local PATH.  The
+// result is advisory only: a missing requirement never stops a sync.
+
+/// Dependencies declared in a skill's frontmatter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillRequirements {
+    /// MCP server names (`requires_mcp`).
+    pub mcp_servers: Vec<String>,
+    /// CLI executables looked up on PATH (`requires_commands`).
+    pub commands: Vec<String>,
+}
+
+impl SkillRequirements {
+    pub fn is_empty(&self) -> bool {
+        self.mcp_servers.is_empty() && self.commands.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementKind {
+    McpServer,
+    Command,
+}
+
+/// A requirement of a selected skill that the project does not meet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillRequirementWarning {
+    pub skill: String,
+    pub kind: RequirementKind,
+    /// The missing server or command name.
+    pub missing: String,
+    /// What the user can do about it.
+    pub suggestion: String,
+}
+
+impl SkillRequirementWarning {
+    /// One-line description for logs and reports.
+    pub fn message(&self) -> String {
+        let what = match self.kind {
+            RequirementKind::McpServer => "MCP server",
+            RequirementKind::Command => "command",
+        };
+        format!(
+            "skill '{}' requires {} '{}' ({})",
+            self.skill, what, self.missing, self.suggestion
+        )
+    }
+}
+
+/// Read `requires_mcp` and `requires_commands` from SKILL.md `content`.
+/// Both accept an inline list (`[a, b]`), a comma-separated scalar, or a
+/// block list of `- item` lines.
+pub fn parse_skill_requirements(content: &str) -> SkillRequirements {
+    SkillRequirements {
+        mcp_servers: frontmatter_list(content, "requires_mcp"),
+        commands: frontmatter_list(content, "requires_commands"),
+    }
+}
+
+/// The list value of frontmatter field `field`, empty when absent.
+fn frontmatter_list(content: &str, field: &str) -> Vec<String> {
+    let Some(inner) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return Vec::new();
+    };
+    let Some(end) = inner.find("\n---") else {
+        return Vec::new();
+    };
+
+    let mut lines = inner[..end].lines();
+    while let Some(line) = lines.next() {
+        let Some(rest) = line
+            .strip_prefix(field)
+            .and_then(|r| r.trim_start().strip_prefix(':'))
+        else {
+            continue;
+        };
+        let rest = rest.trim();
+        let items: Vec<&str> = if rest.is_empty() {
+            lines
+                .map(str::trim)
+                .take_while(|l| l.starts_with('-'))
+                .map(|l| l[1..].trim())
+                .collect()
+        } else {
+            rest.trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .collect()
+        };
+        return items
+            .into_iter()
+            .map(|item| item.trim().trim_matches('"').trim_matches('\'').to_string())
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+    Vec::new()
+}
+
+/// Requirements of `skills` (`(name, SKILL.md content)`) that are not met
+/// by `mcp_servers` or by `command_available`.
+pub fn check_skill_requirements(
+    skills: &[(String, String)],
+    mcp_servers: &[String],
+    command_available: &dyn Fn(&str) -> bool,
+) -> Vec<SkillRequirementWarning> {
+    let mut warnings = Vec::new();
+    for (skill, content) in skills {
+        let requirements = parse_skill_requirements(content);
+        for server in requirements.mcp_servers {
+            // The Automatic server is added to every project at sync time.
+            if server == "automatic" || mcp_servers.contains(&server) {
+                continue;
+            }
+            warnings.push(SkillRequirementWarning {
+                skill: skill.clone(),
+                kind: RequirementKind::McpServer,
+                suggestion: format!("add the '{}' MCP server to the project", server),
+                missing: server,
+            });
+        }
+        for command in requirements.commands {
+            if command_available(&command) {
+                continue;
+            }
+            warnings.push(SkillRequirementWarning {
+                skill: skill.clone(),
+                kind: RequirementKind::Command,
+                suggestion: format!("install '{}' and make sure it is on PATH", command),
+                missing: command,
+            });
+        }
+    }
+    warnings
+}
+
+/// `(name, content)` of every skill selected in `project`, registry skills
+/// first, then the project's custom skills.  Unreadable skills are skipped.
+pub fn project_skill_contents(project: &Project) -> Vec<(String, String)> {
+    let mut skills: Vec<(String, String)> = project
+        .skills
+        .iter()
+        .filter_map(|name| {
+            read_skill(name)
+                .ok()
+                .filter(|c| !c.is_empty())
+                .map(|c| (name.clone(), c))
+        })
+        .collect();
+    skills.extend(
+        project
+            .custom_skills
+            .iter()
+            .flatten()
+            .map(|s| (s.name.clone(), s.content.clone())),
+    );
+    skills
+}
+
+/// Unmet requirements of the skills selected in `project`.
+pub fn project_skill_requirement_warnings(project: &Project) -> Vec<SkillRequirementWarning> {
+    check_skill_requirements(
+        &project_skill_contents(project),
+        &project.enabled_mcp_servers(),
+        &which_available,
+    )
+}
+
+/// Short "requirements status" footer for `content` read in the context of
+/// `project`; `None` when the skill declares no requirements.
+pub fn skill_requirements_footer(name: &str, content: &str, project: &Project) -> Option<String> {
+    if parse_skill_requirements(content).is_empty() {
+        return None;
+    }
+    let skills = [(name.to_string(), content.to_string())];
+    let warnings =
+        check_skill_requirements(&skills, &project.enabled_mcp_servers(), &which_available);
+    let mut footer = format!(
+        "\n\n---\nRequirements status for project '{}': ",
+        project.name
+    );
+    if warnings.is_empty() {
+        footer.push_str("all met.");
+    } else {
+        footer.push_str("missing");
+        for warning in &warnings {
+            footer.push_str(&format!("\n- {} — {}", warning.missing, warning.suggestion));
+        }
+    }
+    Some(footer)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &str, frontmatter: &str) -> (String, String) {
+        (
+            name.to_string(),
+            format!("---\nname: {}\n{}---\n\n# {}\n", name, frontmatter, name),
+        )
+    }
+
+    #[test]
+    fn requirements_are_parsed_from_every_list_form() {
+        let (_, inline) = skill("pr", "requires_mcp: [github, \"linear\"]\n");
+        assert_eq!(
+            parse_skill_requirements(&inline).mcp_servers,
+            vec!["github", "linear"]
+        );
+
+        let (_, block) = skill("pr", "requires_commands:\n  - gh\n  - 'jq'\nlicense: MIT\n");
+        let requirements = parse_skill_requirements(&block);
+        assert_eq!(requirements.commands, vec!["gh", "jq"]);
+        assert!(requirements.mcp_servers.is_empty());
+
+        let (_, scalar) = skill("pr", "requires_commands: gh, jq\n");
+        assert_eq!(parse_skill_requirements(&scalar).commands, vec!["gh", "jq"]);
+
+        assert!(parse_skill_requirements("# No frontmatter\nrequires_mcp: [x]\n").is_empty());
+    }
+
+    #[test]
+    fn met_requirements_produce_no_warnings() {
+        let skills = vec![
+            skill(
+                "pr",
+                "requires_mcp: [github, automatic]\nrequires_commands: [gh]\n",
+            ),
+            skill("plain", ""),
+        ];
+        let warnings = check_skill_requirements(&skills, &["github".to_string()], &|c| c == "gh");
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn missing_requirements_name_the_skill_and_the_fix() {
+        let skills = vec![skill(
+            "pr",
+            "requires_mcp: [github]\nrequires_commands: [gh]\n",
+        )];
+        let warnings = check_skill_requirements(&skills, &[], &|_| false);
+        assert_eq!(warnings.len(), 2);
+
+        assert_eq!(warnings[0].skill, "pr");
+        assert_eq!(warnings[0].kind, RequirementKind::McpServer);
+        assert_eq!(warnings[0].missing, "github");
+        assert!(warnings[0]
+            .message()
+            .contains("add the 'github' MCP server"));
+
+        assert_eq!(warnings[1].kind, RequirementKind::Command);
+        assert_eq!(warnings[1].missing, "gh");
+        assert!(warnings[1].suggestion.contains("install 'gh'"));
+    }
+
+    #[test]
+    fn footer_only_for_skills_with_requirements() {
+        let project = Project {
+            name: "web".into(),
+            mcp_servers: vec!["github".into()],
+            ..Default::default()
+        };
+        let (_, plain) = skill("plain", "");
+        assert_eq!(skill_requirements_footer("plain", &plain, &project), None);
+
+        let (_, pr) = skill("pr", "requires_mcp: [github]\n");
+        let footer = skill_requirements_footer("pr", &pr, &project).unwrap();
+        assert!(footer.contains("all met"), "{}", footer);
+
+        let (_, db) = skill("db", "requires_mcp: [postgres]\n");
+        let footer = skill_requirements_footer("db", &db, &project).unwrap();
+        assert!(footer.contains("- postgres — add the 'postgres' MCP server"));
+    }
+}
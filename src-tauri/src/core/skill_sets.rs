@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+// ── Skill Sets ───────────────────────────────────────────────────────────────
+//
+// Named, ordered lists of skills that projects apply as a unit, stored as
+// individual JSON files at:
+//   ~/.automatic/skill_sets/{name}.json
+//
+// A project lists the sets it uses in `Project::skill_sets`.  They are
+// expanded into concrete skills only when the project is synced (see
+// `selected_skill_names`), so editing a set and re-syncing updates every
+// project that uses it.  Unrelated to skill collections, which record the
+// package or repository a skill came from.
+
+/// A named bundle of skills.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SkillSet {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Skill names, in the order they were added.
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+fn skill_set_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+/// All skill sets, sorted by name.  Files that do not parse are skipped.
+pub fn list_skill_sets() -> Result<Vec<SkillSet>, String> {
+    list_skill_sets_in(&get_skill_sets_dir()?)
+}
+
+fn list_skill_sets_in(dir: &Path) -> Result<Vec<SkillSet>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut sets = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if !is_valid_name(stem) {
+            continue;
+        }
+        match read_skill_set_in(dir, stem) {
+            Ok(set) => sets.push(set),
+            Err(e) => tracing::warn!("Skipping skill set '{}': {}", stem, e),
+        }
+    }
+    sets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(sets)
+}
+
+pub fn read_skill_set(name: &str) -> Result<SkillSet, String> {
+    read_skill_set_in(&get_skill_sets_dir()?, name)
+}
+
+fn read_skill_set_in(dir: &Path, name: &str) -> Result<SkillSet, String> {
+    if !is_valid_name(name) {
+        return Err("Invalid skill set name".into());
+    }
+    let path = skill_set_path(dir, name);
+    if !path.exists() {
+        return Err(format!("Skill set '{}' not found", name));
+    }
+    let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut set: SkillSet =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid skill set data: {}", e))?;
+    // The file name is authoritative.
+    set.name = name.to_string();
+    Ok(set)
+}
+
+/// Create or replace the set named `set.name`.  Duplicate skills are dropped,
+/// keeping the first occurrence.
+pub fn save_skill_set(set: &SkillSet) -> Result<SkillSet, String> {
+    save_skill_set_in(&get_skill_sets_dir()?, set)
+}
+
+fn save_skill_set_in(dir: &Path, set: &SkillSet) -> Result<SkillSet, String> {
+    if !is_valid_name(&set.name) {
+        return Err("Invalid skill set name".into());
+    }
+    let mut skills: Vec<String> = Vec::new();
+    for skill in &set.skills {
+        if !is_valid_name(skill) {
+            return Err(format!("Invalid skill name '{}'", skill));
+        }
+        if !skills.contains(skill) {
+            skills.push(skill.clone());
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let created_at = read_skill_set_in(dir, &set.name)
+        .ok()
+        .map(|existing| existing.created_at)
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| now.clone());
+    let saved = SkillSet {
+        name: set.name.clone(),
+        description: set.description.trim().to_string(),
+        skills,
+        created_at,
+        updated_at: now,
+    };
+    let pretty = serde_json::to_string_pretty(&saved).map_err(|e| e.to_string())?;
+    write_atomic(&skill_set_path(dir, &set.name), pretty)?;
+    Ok(saved)
+}
+
+/// Delete a skill set.  Projects that use it are updated by the caller.
+pub fn delete_skill_set(name: &str) -> Result<(), String> {
+    if !is_valid_name(name) {
+        return Err("Invalid skill set name".into());
+    }
+    let path = skill_set_path(&get_skill_sets_dir()?, name);
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// The skills `project` syncs: its own selection followed by the skills of
+/// each of its sets, without duplicates.  Sets that no longer exist are
+/// skipped; project health reports them.
+pub fn selected_skill_names(project: &Project) -> Vec<String> {
+    if project.skill_sets.is_empty() {
+        return project.skills.clone();
+    }
+    match get_skill_sets_dir() {
+        Ok(dir) => selected_skill_names_in(&dir, project),
+        Err(e) => {
+            tracing::warn!("Failed to resolve skill sets: {}", e);
+            project.skills.clone()
+        }
+    }
+}
+
+fn selected_skill_names_in(dir: &Path, project: &Project) -> Vec<String> {
+    let mut selected = project.skills.clone();
+    for set_name in &project.skill_sets {
+        let Ok(set) = read_skill_set_in(dir, set_name) else {
+            continue;
+        };
+        for skill in set.skills {
+            if !selected.contains(&skill) {
+                selected.push(skill);
+            }
+        }
+    }
+    selected
+}
+
+/// Best-effort: drop a deleted skill from every set that lists it.
+pub fn remove_skill_from_skill_sets(skill_name: &str) {
+    update_skill_sets(|skills| {
+        let before = skills.len();
+        skills.retain(|s| s != skill_name);
+        skills.len() != before
+    });
+}
+
+/// Best-effort: follow a skill rename in every set that lists it.
+pub fn rename_skill_in_skill_sets(old_name: &str, new_name: &str) {
+    update_skill_sets(|skills| {
+        let mut changed = false;
+        for skill in skills.iter_mut().filter(|s| *s == old_name) {
+            *skill = new_name.to_string();
+            changed = true;
+        }
+        changed
+    });
+}
+
+/// Apply `edit` to the skills of every set and save the ones it changed.
+fn update_skill_sets(mut edit: impl FnMut(&mut Vec<String>) -> bool) {
+    let sets = match list_skill_sets() {
+        Ok(sets) => sets,
+        Err(e) => {
+            tracing::warn!("Failed to list skill sets: {}", e);
+            return;
+        }
+    };
+    for mut set in sets {
+        if edit(&mut set.skills) {
+            if let Err(e) = save_skill_set(&set) {
+                tracing::warn!("Failed to update skill set '{}': {}", set.name, e);
+            }
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn set(name: &str, skills: &[&str]) -> SkillSet {
+        SkillSet {
+            name: name.into(),
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sets_round_trip_without_duplicate_skills() {
+        let dir = tempdir().unwrap();
+        let saved = save_skill_set_in(dir.path(), &set("frontend", &["react", "css", "react"]))
+            .expect("save");
+        assert_eq!(saved.skills, ["react", "css"]);
+        assert!(!saved.created_at.is_empty());
+
+        let resaved = save_skill_set_in(dir.path(), &set("frontend", &["css"])).expect("resave");
+        assert_eq!(resaved.created_at, saved.created_at);
+        assert_eq!(read_skill_set_in(dir.path(), "frontend").unwrap(), resaved);
+
+        save_skill_set_in(dir.path(), &set("backend", &["rust"])).expect("save");
+        let names: Vec<String> = list_skill_sets_in(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, ["backend", "frontend"]);
+
+        assert!(save_skill_set_in(dir.path(), &set("../x", &[])).is_err());
+        assert!(save_skill_set_in(dir.path(), &set("bad", &["a/b"])).is_err());
+    }
+
+    #[test]
+    fn project_selection_is_the_union_of_skills_and_sets() {
+        let dir = tempdir().unwrap();
+        save_skill_set_in(dir.path(), &set("frontend", &["react", "css", "tests"])).unwrap();
+        save_skill_set_in(dir.path(), &set("quality", &["tests", "lint"])).unwrap();
+        let project = Project {
+            skills: vec!["css".into(), "docs".into()],
+            skill_sets: vec!["frontend".into(), "missing".into(), "quality".into()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            selected_skill_names_in(dir.path(), &project),
+            ["css", "docs", "react", "tests", "lint"]
+        );
+    }
+}
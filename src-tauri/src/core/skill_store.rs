@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::types::SkillsJson;
 use super::*;
@@ -18,18 +19,77 @@ pub struct RemoteSkillResult {
     pub installs: u64,
     /// The GitHub source in "owner/repo" format.
     pub source: String,
+    /// License reported by skills.sh, when it provides one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Short description, when skills.sh provides one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// When skills.sh last saw the skill change, as it reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// True when a skill of this name is already installed locally.
+    #[serde(default)]
+    pub installed: bool,
 }
 
+/// Result order for [`search_remote_skills`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillSearchSort {
+    /// The order skills.sh ranks matches in.
+    #[default]
+    Relevance,
+    /// Most installed first.
+    Installs,
+}
+
+/// Paging and ordering for [`search_remote_skills`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillSearchOptions {
+    /// Results per page, at most [`MAX_SKILL_SEARCH_LIMIT`].
+    pub limit: usize,
+    /// Results to skip.
+    pub offset: usize,
+    pub sort: SkillSearchSort,
+}
+
+impl Default for SkillSearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_SKILL_SEARCH_LIMIT,
+            offset: 0,
+            sort: SkillSearchSort::default(),
+        }
+    }
+}
+
+pub const DEFAULT_SKILL_SEARCH_LIMIT: usize = 20;
+pub const MAX_SKILL_SEARCH_LIMIT: usize = 100;
+
+/// Deepest result skills.sh is asked for; pages past it come back empty.
+const MAX_SKILL_SEARCH_WINDOW: usize = 500;
+
 /// Search skills.sh for skills matching `query`.
-/// Calls `https://skills.sh/api/search?q=<query>&limit=20`.
-pub async fn search_remote_skills(query: &str) -> Result<Vec<RemoteSkillResult>, String> {
+/// Calls `https://skills.sh/api/search?q=<query>&limit=<n>`.
+///
+/// The API has no offset, so enough results to cover the requested page are
+/// fetched and the page is cut out here.  Sorting by installs applies to
+/// that window.
+pub async fn search_remote_skills(
+    query: &str,
+    options: &SkillSearchOptions,
+) -> Result<Vec<RemoteSkillResult>, String> {
     if query.trim().is_empty() {
         return Ok(Vec::new());
     }
 
+    let limit = options.limit.clamp(1, MAX_SKILL_SEARCH_LIMIT);
+    let window = (options.offset + limit).min(MAX_SKILL_SEARCH_WINDOW);
     let url = format!(
-        "https://skills.sh/api/search?q={}&limit=20",
-        urlencoding::encode(query)
+        "https://skills.sh/api/search?q={}&limit={}",
+        urlencoding::encode(query),
+        window
     );
 
     let client = reqwest::Client::builder()
@@ -54,11 +114,18 @@ pub async fn search_remote_skills(query: &str) -> Result<Vec<RemoteSkillResult>,
     }
 
     #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
     struct ApiSkill {
         id: String,
         name: String,
         installs: u64,
         source: String,
+        #[serde(default)]
+        license: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default, alias = "updated_at")]
+        updated_at: Option<String>,
     }
 
     let body: ApiResponse = resp
@@ -66,7 +133,7 @@ pub async fn search_remote_skills(query: &str) -> Result<Vec<RemoteSkillResult>,
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    Ok(body
+    let results = body
         .skills
         .into_iter()
         .map(|s| RemoteSkillResult {
@@ -74,8 +141,42 @@ pub async fn search_remote_skills(query: &str) -> Result<Vec<RemoteSkillResult>,
             name: s.name,
             installs: s.installs,
             source: s.source,
+            license: s.license,
+            description: s.description,
+            updated_at: s.updated_at,
+            installed: false,
         })
-        .collect())
+        .collect();
+    let installed: std::collections::HashSet<String> = super::list_skill_names()
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    Ok(page_search_results(
+        results,
+        &SkillSearchOptions { limit, ..*options },
+        &installed,
+    ))
+}
+
+/// Order `results`, cut out the requested page and flag installed skills.
+fn page_search_results(
+    mut results: Vec<RemoteSkillResult>,
+    options: &SkillSearchOptions,
+    installed: &std::collections::HashSet<String>,
+) -> Vec<RemoteSkillResult> {
+    if options.sort == SkillSearchSort::Installs {
+        // Stable, so equal counts keep their relevance order.
+        results.sort_by(|a, b| b.installs.cmp(&a.installs));
+    }
+    results
+        .into_iter()
+        .skip(options.offset)
+        .take(options.limit)
+        .map(|mut r| {
+            r.installed = installed.contains(&r.name);
+            r
+        })
+        .collect()
 }
 
 /// Extract the value of a named YAML frontmatter field from raw SKILL.md text.
@@ -127,11 +228,26 @@ pub fn extract_frontmatter_license(content: &str) -> Option<String> {
 ///    with no GitHub API calls and no rate-limit exposure. The blobless clone
 ///    downloads only git metadata (~100-200 KB), not file contents.
 pub async fn fetch_remote_skill_content(source: &str, name: &str) -> Result<String, String> {
-    let client = reqwest::Client::builder()
+    let client = skill_fetch_client()?;
+    fetch_skill_content_with(&client, source, name, &mut None).await
+}
+
+fn skill_fetch_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(15))
         .build()
-        .map_err(|e| format!("HTTP client error: {}", e))?;
+        .map_err(|e| format!("HTTP client error: {}", e))
+}
 
+/// [`fetch_remote_skill_content`] with a shared client.  `listing` caches
+/// the repo's file listing, so skills fetched from the same repo one after
+/// another need at most one clone between them.
+async fn fetch_skill_content_with(
+    client: &reqwest::Client,
+    source: &str,
+    name: &str,
+    listing: &mut Option<Result<RepoListing, String>>,
+) -> Result<String, String> {
     // ── Step 1: static candidates fired in parallel ───────────────────────────
     // All candidate URLs (5 layouts × 2 branch names) are fetched
     // concurrently. The first one that returns a matching SKILL.md wins.
@@ -258,6 +374,90 @@ pub async fn fetch_remote_skill_content(source: &str, name: &str) -> Result<Stri
     }
 
     // ── Step 2: blobless shallow clone + local tree walk ─────────────────────
+    let listing = match listing.get_or_insert_with(|| clone_repo_listing(source, name)) {
+        Ok(listing) => &*listing,
+        Err(e) => return Err(e.clone()),
+    };
+    let raw_base = format!(
+        "https://raw.githubusercontent.com/{}/{}",
+        source, listing.branch
+    );
+
+    // Find ALL SKILL.md files in the tree.  The directory name may differ
+    // from the skills.sh name (e.g. dir "react-best-practices" with
+    // frontmatter `name: vercel-react-best-practices`), so we collect every
+    // SKILL.md and rely on the frontmatter check below to identify the
+    // correct one.
+    let mut candidate_paths: Vec<&str> = listing
+        .files
+        .iter()
+        .map(String::as_str)
+        .filter(|p| p.ends_with("/SKILL.md") || *p == "SKILL.md")
+        .collect();
+
+    // Try exact directory-name matches first (fast path), then everything
+    // else.  Within each tier the original tree order is preserved.
+    candidate_paths.sort_by_key(|p| {
+        let parent = std::path::Path::new(p)
+            .parent()
+            .and_then(|d| d.file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or("");
+        if parent == name {
+            0usize
+        } else {
+            1usize
+        }
+    });
+
+    for path in candidate_paths {
+        let url = format!("{}/{}", raw_base, path);
+        let resp = match client
+            .get(&url)
+            .header("User-Agent", "automatic-desktop/1.0")
+            .send()
+            .await
+        {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        if !resp.status().is_success() {
+            continue;
+        }
+        let content = match resp.text().await {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        // The frontmatter `name:` field is authoritative when present.
+        // When absent, only accept the file if the directory name matches
+        // the requested skill name (or it's the repo root SKILL.md for a
+        // single-skill repo).  This prevents false positives in multi-skill
+        // repos where a different skill's SKILL.md lacks frontmatter.
+        let dir_matches = std::path::Path::new(path)
+            .parent()
+            .and_then(|d| d.file_name())
+            .and_then(|n| n.to_str())
+            .map_or(false, |p| p == name);
+        match extract_frontmatter_name(&content) {
+            Some(ref n) if n == name => return Ok(content),
+            None if dir_matches || path == "SKILL.md" => return Ok(content),
+            _ => {}
+        }
+    }
+
+    Err(format!("Could not fetch SKILL.md for '{}'", name))
+}
+
+/// A GitHub repo's checked-out branch and every path in its tree.
+#[derive(Debug, Clone)]
+struct RepoListing {
+    branch: String,
+    files: Vec<String>,
+}
+
+/// List a repo's files from a blobless shallow clone.  `name` is the skill
+/// being looked for, used in errors and the temp dir name.
+fn clone_repo_listing(source: &str, name: &str) -> Result<RepoListing, String> {
     // Clone only the git metadata (no file blobs). This is ~100-200 KB and
     // takes under a second. No GitHub API involved — no rate limit.
     let tmp_dir = std::env::temp_dir().join(format!(
@@ -266,7 +466,7 @@ pub async fn fetch_remote_skill_content(source: &str, name: &str) -> Result<Stri
         name
     ));
     // Clean up any leftover from a previous failed attempt.
-    let _ = std::fs::remove_dir_all(&tmp_dir);
+    let _ = remove_guarded(&tmp_dir, &RemovalContext::temp().forced());
 
     let clone_url = format!("https://github.com/{}.git", source);
     let clone_result = std::process::Command::new("git")
@@ -288,7 +488,7 @@ pub async fn fetch_remote_skill_content(source: &str, name: &str) -> Result<Stri
     };
 
     if !clone_ok {
-        let _ = std::fs::remove_dir_all(&tmp_dir);
+        let _ = remove_guarded(&tmp_dir, &RemovalContext::temp().forced());
         return Err(format!(
             "Could not fetch SKILL.md for '{}': git clone failed (is git installed?)",
             name
@@ -318,7 +518,7 @@ pub async fn fetch_remote_skill_content(source: &str, name: &str) -> Result<Stri
         ])
         .output();
 
-    let _ = std::fs::remove_dir_all(&tmp_dir);
+    let _ = remove_guarded(&tmp_dir, &RemovalContext::temp().forced());
 
     let ls_output = match ls_result {
         Ok(out) if out.status.success() => out.stdout,
@@ -335,70 +535,152 @@ pub async fn fetch_remote_skill_content(source: &str, name: &str) -> Result<Stri
         _ => "main".to_string(),
     };
 
-    let file_list = String::from_utf8_lossy(&ls_output);
-    let raw_base = format!("https://raw.githubusercontent.com/{}/{}", source, branch);
+    Ok(RepoListing {
+        branch,
+        files: String::from_utf8_lossy(&ls_output)
+            .lines()
+            .map(String::from)
+            .collect(),
+    })
+}
 
-    // Find ALL SKILL.md files in the tree.  The directory name may differ
-    // from the skills.sh name (e.g. dir "react-best-practices" with
-    // frontmatter `name: vercel-react-best-practices`), so we collect every
-    // SKILL.md and rely on the frontmatter check below to identify the
-    // correct one.
-    let mut candidate_paths: Vec<&str> = file_list
-        .lines()
-        .filter(|p| p.ends_with("/SKILL.md") || *p == "SKILL.md")
-        .collect();
+// ── Batch Import ─────────────────────────────────────────────────────────────
+//
+// Installs a stack of skills in one go.  SKILL.md contents are fetched
+// concurrently over one HTTP client, a few repos at a time; skills from the
+// same repo are fetched one after another so they share a single clone when
+// the fallback is needed.  Saving and recording sources then happens in
+// request order, since `skills.json` is rewritten on every record.
+
+/// Repos fetched from at the same time by [`import_remote_skills`].
+const MAX_CONCURRENT_SKILL_REPOS: usize = 4;
+
+/// One skill to import with [`import_remote_skills`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSkillRef {
+    pub name: String,
+    /// GitHub owner/repo.
+    pub source: String,
+    /// Full skills.sh id; defaults to `<source>/<name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Replace the skill even when it was edited locally.
+    #[serde(default)]
+    pub force: bool,
+}
 
-    // Try exact directory-name matches first (fast path), then everything
-    // else.  Within each tier the original tree order is preserved.
-    candidate_paths.sort_by_key(|p| {
-        let parent = std::path::Path::new(p)
-            .parent()
-            .and_then(|d| d.file_name())
-            .and_then(|n| n.to_str())
-            .unwrap_or("");
-        if parent == name {
-            0usize
-        } else {
-            1usize
+/// Outcome of importing one skill with [`import_remote_skills`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportResult {
+    pub name: String,
+    pub source: String,
+    pub id: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Import several skills from skills.sh: fetch, save and record the source
+/// of each, like a single import.  A skill that fails is reported without
+/// stopping the rest; results follow the order of `requests`.  Projects are
+/// not re-synced here, so callers can do it once for the whole batch.
+pub async fn import_remote_skills(requests: Vec<RemoteSkillRef>) -> Vec<ImportResult> {
+    let client = match skill_fetch_client() {
+        Ok(client) => client,
+        Err(e) => {
+            return requests
+                .into_iter()
+                .map(|request| import_result(request, Err(e.clone())))
+                .collect()
         }
-    });
+    };
 
-    for path in candidate_paths {
-        let url = format!("{}/{}", raw_base, path);
-        let resp = match client
-            .get(&url)
-            .header("User-Agent", "automatic-desktop/1.0")
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-        if !resp.status().is_success() {
-            continue;
+    let mut fetched = Vec::new();
+    let mut tasks = tokio::task::JoinSet::new();
+    for (source, group) in group_by_source(requests) {
+        if tasks.len() >= MAX_CONCURRENT_SKILL_REPOS {
+            if let Some(Ok(done)) = tasks.join_next().await {
+                fetched.extend(done);
+            }
         }
-        let content = match resp.text().await {
-            Ok(t) => t,
-            Err(_) => continue,
+        let client = client.clone();
+        tasks.spawn(async move {
+            let mut listing = None;
+            let mut done = Vec::new();
+            for (index, request) in group {
+                let content =
+                    fetch_skill_content_with(&client, &source, &request.name, &mut listing).await;
+                done.push((index, request, content));
+            }
+            done
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(done) = joined {
+            fetched.extend(done);
+        }
+    }
+    fetched.sort_by_key(|(index, _, _)| *index);
+
+    let mut results = Vec::new();
+    for (_, request, content) in fetched {
+        let outcome = match content {
+            Ok(content) => install_fetched_skill(&request, &content).await,
+            Err(e) => Err(e),
         };
-        // The frontmatter `name:` field is authoritative when present.
-        // When absent, only accept the file if the directory name matches
-        // the requested skill name (or it's the repo root SKILL.md for a
-        // single-skill repo).  This prevents false positives in multi-skill
-        // repos where a different skill's SKILL.md lacks frontmatter.
-        let dir_matches = std::path::Path::new(path)
-            .parent()
-            .and_then(|d| d.file_name())
-            .and_then(|n| n.to_str())
-            .map_or(false, |p| p == name);
-        match extract_frontmatter_name(&content) {
-            Some(ref n) if n == name => return Ok(content),
-            None if dir_matches || path == "SKILL.md" => return Ok(content),
-            _ => {}
+        results.push(import_result(request, outcome));
+    }
+    results
+}
+
+/// Requests grouped by repo, each with its position in `requests`.
+fn group_by_source(requests: Vec<RemoteSkillRef>) -> Vec<(String, Vec<(usize, RemoteSkillRef)>)> {
+    let mut groups: Vec<(String, Vec<(usize, RemoteSkillRef)>)> = Vec::new();
+    for (index, request) in requests.into_iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|(source, _)| *source == request.source)
+        {
+            Some((_, group)) => group.push((index, request)),
+            None => groups.push((request.source.clone(), vec![(index, request)])),
         }
     }
+    groups
+}
 
-    Err(format!("Could not fetch SKILL.md for '{}'", name))
+async fn install_fetched_skill(request: &RemoteSkillRef, content: &str) -> Result<(), String> {
+    if !request.force && skill_local_modifications(&request.name) {
+        return Err(format!(
+            "Skill '{}' has local changes that would be overwritten",
+            request.name
+        ));
+    }
+    super::save_imported_skill(&request.name, content)?;
+    record_skill_source(
+        &request.name,
+        &request.source,
+        &skill_ref_id(request),
+        "github",
+    )?;
+    capture_remote_license(&request.name, &request.source).await;
+    Ok(())
+}
+
+fn skill_ref_id(request: &RemoteSkillRef) -> String {
+    request
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("{}/{}", request.source, request.name))
+}
+
+fn import_result(request: RemoteSkillRef, outcome: Result<(), String>) -> ImportResult {
+    ImportResult {
+        id: skill_ref_id(&request),
+        ok: outcome.is_ok(),
+        error: outcome.err(),
+        name: request.name,
+        source: request.source,
+    }
 }
 
 // ── Skills Registry (~/.automatic/skills.json) ───────────────────────────────────
@@ -418,49 +700,235 @@ fn get_skills_registry_path() -> Result<PathBuf, String> {
 
 /// Read the full registry.  Returns an empty map if the file doesn't exist.
 pub fn read_skill_sources() -> Result<std::collections::HashMap<String, SkillSource>, String> {
-    let path = get_skills_registry_path()?;
+    read_skill_sources_at(&get_skills_registry_path()?)
+}
+
+fn read_skill_sources_at(
+    path: &Path,
+) -> Result<std::collections::HashMap<String, SkillSource>, String> {
     if !path.exists() {
         return Ok(std::collections::HashMap::new());
     }
-    let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
     serde_json::from_str(&raw).map_err(|e| format!("Invalid skills.json: {}", e))
 }
 
-/// Write the full registry atomically.
-fn write_skill_sources(
-    registry: &std::collections::HashMap<String, SkillSource>,
-) -> Result<(), String> {
-    let path = get_skills_registry_path()?;
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
-    }
-    let json = serde_json::to_string_pretty(registry).map_err(|e| e.to_string())?;
-    fs::write(&path, json).map_err(|e| e.to_string())
+/// Read-modify-write the registry under its file lock, replacing the file
+/// atomically.  Nothing is written when `update` returns an error.
+fn update_skill_sources<T>(
+    update: impl FnOnce(&mut std::collections::HashMap<String, SkillSource>) -> Result<T, String>,
+) -> Result<T, String> {
+    update_skill_sources_at(&get_skills_registry_path()?, update)
+}
+
+fn update_skill_sources_at<T>(
+    path: &Path,
+    update: impl FnOnce(&mut std::collections::HashMap<String, SkillSource>) -> Result<T, String>,
+) -> Result<T, String> {
+    with_file_lock(path, || {
+        let mut registry = read_skill_sources_at(path)?;
+        let result = update(&mut registry)?;
+        write_atomic(path, to_stable_json(&registry)?)?;
+        Ok(result)
+    })
 }
 
 /// Record that a skill was imported from a remote source, or is bundled with
 /// the app.  `kind` is "github" for registry-imported skills, "bundled" for
-/// skills shipped with Automatic.
+/// skills shipped with Automatic.  Call it after the skill is saved: the
+/// installed SKILL.md's `license:` frontmatter field and its content hash
+/// are recorded with it.
+///
+/// Re-recording an existing skill (e.g. when applying an update) preserves
+/// its `pinned` flag, and its license when the SKILL.md declares none.
 pub fn record_skill_source(name: &str, source: &str, id: &str, kind: &str) -> Result<(), String> {
-    let mut registry = read_skill_sources()?;
-    registry.insert(
-        name.to_string(),
-        SkillSource {
-            source: source.to_string(),
-            id: id.to_string(),
-            kind: kind.to_string(),
-        },
-    );
-    write_skill_sources(&registry)
+    let content = super::read_skill(name).ok().filter(|c| !c.is_empty());
+    record_skill_source_at(
+        &get_skills_registry_path()?,
+        name,
+        source,
+        id,
+        kind,
+        content.as_deref(),
+    )
+}
+
+fn record_skill_source_at(
+    path: &Path,
+    name: &str,
+    source: &str,
+    id: &str,
+    kind: &str,
+    content: Option<&str>,
+) -> Result<(), String> {
+    update_skill_sources_at(path, |registry| {
+        let existing = registry.get(name);
+        let pinned = existing.map(|s| s.pinned).unwrap_or(false);
+        let license = content
+            .and_then(extract_frontmatter_license)
+            .or_else(|| existing.and_then(|s| s.license.clone()));
+        registry.insert(
+            name.to_string(),
+            SkillSource {
+                source: source.to_string(),
+                id: id.to_string(),
+                kind: kind.to_string(),
+                pinned,
+                license,
+                installed_hash: content.map(skill_content_hash),
+                imported_at: Some(chrono::Utc::now().to_rfc3339()),
+            },
+        );
+        Ok(())
+    })
+}
+
+/// Hash recorded in [`SkillSource::installed_hash`].
+fn skill_content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Whether `content` differs from what was installed from `source`.  False
+/// when no hash was recorded.
+fn differs_from_installed(source: &SkillSource, content: &str) -> bool {
+    source
+        .installed_hash
+        .as_deref()
+        .is_some_and(|hash| hash != skill_content_hash(content))
+}
+
+/// Whether the skill's SKILL.md was edited since it was imported or last
+/// updated from upstream.  False for skills without a recorded source or
+/// hash, and for skills that no longer exist.
+pub fn skill_local_modifications(name: &str) -> bool {
+    let Some(source) = read_skill_sources().ok().and_then(|mut r| r.remove(name)) else {
+        return false;
+    };
+    match super::read_skill(name) {
+        Ok(content) if !content.is_empty() => differs_from_installed(&source, &content),
+        _ => false,
+    }
+}
+
+/// Like [`skill_local_modifications`], for a `list_skills` entry whose
+/// content was already read.
+pub(crate) fn skill_content_modified(source: Option<&SkillSource>, content: &str) -> bool {
+    source.is_some_and(|s| differs_from_installed(s, content))
+}
+
+/// Set the recorded license of a skill that already has a registry entry.
+pub fn record_skill_license(name: &str, license: &str) -> Result<(), String> {
+    update_skill_sources(|registry| {
+        let entry = registry
+            .get_mut(name)
+            .ok_or_else(|| format!("Skill '{}' has no recorded remote source", name))?;
+        entry.license = Some(license.to_string());
+        Ok(())
+    })
+}
+
+/// Repo-root files checked for a license, in order of preference.
+const LICENSE_FILES: &[&str] = &["LICENSE", "LICENSE.md", "LICENSE.txt", "COPYING"];
+
+/// The first non-empty line of a LICENSE file (e.g. "MIT License"), which is
+/// close enough to an SPDX identifier for an informational report.
+pub fn license_from_file_text(text: &str) -> Option<String> {
+    text.lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.to_string())
+}
+
+/// Fetch the license of a GitHub repo ("owner/repo") from its LICENSE file
+/// via raw.githubusercontent.com.  Returns `None` when no file is found.
+pub async fn fetch_remote_repo_license(source: &str) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .ok()?;
+
+    // All candidates are fetched concurrently; the most preferred hit wins.
+    let mut tasks = tokio::task::JoinSet::new();
+    for (rank, (branch, file)) in ["main", "master"]
+        .iter()
+        .flat_map(|branch| LICENSE_FILES.iter().map(move |file| (branch, file)))
+        .enumerate()
+    {
+        let client2 = client.clone();
+        let url = format!(
+            "https://raw.githubusercontent.com/{}/{}/{}",
+            source, branch, file
+        );
+        tasks.spawn(async move {
+            let resp = client2
+                .get(&url)
+                .header("User-Agent", "automatic-desktop/1.0")
+                .send()
+                .await
+                .ok()?;
+            if !resp.status().is_success() {
+                return None;
+            }
+            let text = resp.text().await.ok()?;
+            Some((rank, license_from_file_text(&text)?))
+        });
+    }
+
+    let mut found: Vec<(usize, String)> = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(Some(hit)) = result {
+            found.push(hit);
+        }
+    }
+    found
+        .into_iter()
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, l)| l)
+}
+
+/// After importing a skill from `source`, fall back to the repo's LICENSE
+/// file when its SKILL.md declared no license.  Best-effort and purely
+/// informational: failures are ignored.
+pub async fn capture_remote_license(name: &str, source: &str) {
+    let recorded = read_skill_sources()
+        .ok()
+        .and_then(|registry| registry.get(name).map(|s| s.license.is_some()));
+    if recorded != Some(false) {
+        return;
+    }
+    if let Some(license) = fetch_remote_repo_license(source).await {
+        let _ = record_skill_license(name, &license);
+    }
+}
+
+/// Pin or unpin an imported skill.  Pinned skills are skipped by the
+/// background update check.
+pub fn set_skill_pinned(name: &str, pinned: bool) -> Result<(), String> {
+    update_skill_sources(|registry| {
+        let entry = registry
+            .get_mut(name)
+            .ok_or_else(|| format!("Skill '{}' has no recorded remote source", name))?;
+        entry.pinned = pinned;
+        Ok(())
+    })
 }
 
 /// Remove the remote origin record for a skill (called on delete).
 pub fn remove_skill_source(name: &str) -> Result<(), String> {
-    let mut registry = read_skill_sources()?;
-    registry.remove(name);
-    write_skill_sources(&registry)
+    update_skill_sources(|registry| {
+        registry.remove(name);
+        Ok(())
+    })
+}
+
+/// Move a renamed skill's remote origin record to its new name.
+pub fn rename_skill_source(old_name: &str, new_name: &str) -> Result<(), String> {
+    update_skill_sources(|registry| {
+        if let Some(entry) = registry.remove(old_name) {
+            registry.insert(new_name.to_string(), entry);
+        }
+        Ok(())
+    })
 }
 
 // ── Repository Import ───────────────────────────────────────────────────────────
@@ -548,10 +1016,11 @@ pub async fn import_skill_from_repository(
                 let actual_name = extract_frontmatter_name(&content)
                     .unwrap_or_else(|| name.clone());
 
-                super::save_skill(&actual_name, &content)?;
+                super::save_imported_skill(&actual_name, &content)?;
 
                 let id = format!("{}/{}", source, actual_name);
                 record_skill_source(&actual_name, &source, &id, "github")?;
+                capture_remote_license(&actual_name, &source).await;
                 let _ = super::set_skill_collection(&actual_name, &source);
 
                 return Ok(vec![ImportedSkillFromRepo {
@@ -610,13 +1079,14 @@ pub async fn import_skill_from_repository(
                     let actual_name = extract_frontmatter_name(&content)
                         .unwrap_or_else(|| name.clone());
 
-                    if let Err(e) = super::save_skill(&actual_name, &content) {
+                    if let Err(e) = super::save_imported_skill(&actual_name, &content) {
                         eprintln!("[automatic] Failed to save skill '{}': {}", actual_name, e);
                         continue;
                     }
 
                     let id = format!("{}/{}", source, actual_name);
                     let _ = record_skill_source(&actual_name, &source, &id, "github");
+                    capture_remote_license(&actual_name, &source).await;
                     let _ = super::set_skill_collection(&actual_name, &source);
 
                     imported.push(ImportedSkillFromRepo {
@@ -652,3 +1122,162 @@ pub struct ImportedSkillFromRepo {
     pub source: String,
     pub id: String,
 }
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frontmatter_license_is_extracted() {
+        let content = "---\nname: terraform\nlicense: \"Apache-2.0\"\n---\n# Terraform\n";
+        assert_eq!(
+            extract_frontmatter_license(content).as_deref(),
+            Some("Apache-2.0")
+        );
+        assert_eq!(extract_frontmatter_license("# No frontmatter\n"), None);
+    }
+
+    #[test]
+    fn license_file_yields_its_first_non_empty_line() {
+        let mit = "\n   MIT License\n\nCopyright (c) 2025 Someone\n";
+        assert_eq!(license_from_file_text(mit).as_deref(), Some("MIT License"));
+        assert_eq!(
+            license_from_file_text("# Apache License\nVersion 2.0\n").as_deref(),
+            Some("Apache License")
+        );
+        assert_eq!(license_from_file_text("\n  \n"), None);
+    }
+
+    #[test]
+    fn registry_entries_without_a_license_still_parse() {
+        let registry: std::collections::HashMap<String, SkillSource> = serde_json::from_str(
+            r#"{"react": {"source": "vercel-labs/skills", "id": "vercel-labs/skills/react"}}"#,
+        )
+        .unwrap();
+        assert_eq!(registry["react"].license, None);
+
+        let mut source = registry["react"].clone();
+        source.license = Some("MIT".into());
+        let json = serde_json::to_value(&source).unwrap();
+        assert_eq!(json["license"], "MIT");
+        assert!(json.get("installed_hash").is_none());
+    }
+
+    #[test]
+    fn search_results_are_sorted_paged_and_flagged() {
+        let result = |name: &str, installs: u64| RemoteSkillResult {
+            id: format!("owner/repo/{}", name),
+            name: name.into(),
+            installs,
+            source: "owner/repo".into(),
+            license: None,
+            description: None,
+            updated_at: None,
+            installed: false,
+        };
+        let results = vec![
+            result("a", 5),
+            result("b", 50),
+            result("c", 5),
+            result("d", 9),
+        ];
+        let installed = ["c".to_string()].into();
+        let names = |page: Vec<RemoteSkillResult>| -> Vec<(String, bool)> {
+            page.into_iter().map(|r| (r.name, r.installed)).collect()
+        };
+
+        let by_installs = SkillSearchOptions {
+            limit: 2,
+            offset: 1,
+            sort: SkillSearchSort::Installs,
+        };
+        assert_eq!(
+            names(page_search_results(
+                results.clone(),
+                &by_installs,
+                &installed
+            )),
+            vec![("d".to_string(), false), ("a".to_string(), false)]
+        );
+
+        let relevance = SkillSearchOptions {
+            offset: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(page_search_results(results, &relevance, &installed)),
+            vec![("c".to_string(), true), ("d".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn batch_requests_are_grouped_by_repo_in_order() {
+        let requests: Vec<RemoteSkillRef> = serde_json::from_str(
+            r#"[
+                {"name": "react", "source": "vercel-labs/skills"},
+                {"name": "terraform", "source": "hashicorp/agent-skills", "id": "hashicorp/agent-skills/tf"},
+                {"name": "next", "source": "vercel-labs/skills", "force": true}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(skill_ref_id(&requests[0]), "vercel-labs/skills/react");
+        assert_eq!(skill_ref_id(&requests[1]), "hashicorp/agent-skills/tf");
+
+        let groups: Vec<(String, Vec<usize>)> = group_by_source(requests)
+            .into_iter()
+            .map(|(source, group)| (source, group.into_iter().map(|(i, _)| i).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("vercel-labs/skills".to_string(), vec![0, 2]),
+                ("hashicorp/agent-skills".to_string(), vec![1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn local_edits_are_detected_against_the_installed_hash() {
+        let content = "---\nname: react\n---\n# React\n";
+        let mut source: SkillSource = serde_json::from_str(
+            r#"{"source": "vercel-labs/skills", "id": "vercel-labs/skills/react"}"#,
+        )
+        .unwrap();
+        assert!(!differs_from_installed(&source, content));
+
+        source.installed_hash = Some(skill_content_hash(content));
+        assert!(!differs_from_installed(&source, content));
+        assert!(differs_from_installed(&source, "# React, edited\n"));
+        assert!(skill_content_modified(Some(&source), "# React, edited\n"));
+        assert!(!skill_content_modified(None, "# React, edited\n"));
+    }
+
+    #[test]
+    fn concurrent_records_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skills.json");
+
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for i in 0..10 {
+                        let name = format!("skill-{}-{}", t, i);
+                        let id = format!("owner/repo/{}", name);
+                        record_skill_source_at(&path, &name, "owner/repo", &id, "github", None)
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let registry = read_skill_sources_at(&path).unwrap();
+        assert_eq!(registry.len(), 80);
+        assert!(registry.contains_key("skill-7-9"));
+    }
+}
@@ -0,0 +1,444 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::paths::get_automatic_dir;
+
+// ── Skill Update Checks (~/.automatic/skill_updates.json) ────────────────────
+//
+// Imported skills (kind "github" in skills.json) are periodically compared
+// against their upstream SKILL.md.  The check runs from the startup
+// background thread on a weekly-by-default schedule, is rate-limited per
+// request, and backs off exponentially after network failures.
+
+/// Default number of days between scheduled update checks.
+pub const DEFAULT_SKILL_UPDATE_INTERVAL_DAYS: u32 = 7;
+
+/// First retry delay after a failed check; doubled for each further failure
+/// and capped at the configured interval.
+const BASE_BACKOFF_MINUTES: i64 = 30;
+
+/// Pause between consecutive upstream fetches so a large skill library never
+/// produces a burst of requests.
+const REQUEST_SPACING: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// A skill whose upstream SKILL.md differs from the installed copy.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AvailableSkillUpdate {
+    pub name: String,
+    /// GitHub owner/repo the skill was imported from.
+    pub source: String,
+    /// Full skills.sh id, used by the UI to open the update flow.
+    pub id: String,
+}
+
+/// Persisted scheduling state and the results of the last successful check.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SkillUpdateState {
+    /// RFC 3339 timestamp of the last check that completed successfully.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_checked_at: Option<String>,
+    /// RFC 3339 timestamp before which the scheduler will not run again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_check_at: Option<String>,
+    /// Number of checks in a row that failed because upstream was unreachable.
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    /// Hash of each skill's local SKILL.md as last seen in sync with (or
+    /// installed from) upstream.  A local hash that differs from this value
+    /// means the user edited the skill, so it is not offered for update.
+    #[serde(default)]
+    pub baseline_hashes: HashMap<String, String>,
+    /// Updates found by the last successful check.
+    #[serde(default)]
+    pub available: Vec<AvailableSkillUpdate>,
+}
+
+/// Result of a single update check run.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct SkillUpdateReport {
+    pub checked_at: String,
+    pub available: Vec<AvailableSkillUpdate>,
+    /// Skills skipped because they were edited locally.
+    pub locally_modified: Vec<String>,
+    /// Skills skipped because they are pinned.
+    pub pinned: Vec<String>,
+    /// Skills whose upstream content could not be fetched.
+    pub failed: Vec<String>,
+    /// Human-readable summary for notifications, e.g. "3 skills have updates".
+    pub summary: String,
+    /// Number of upstream fetches attempted during the run.
+    #[serde(skip)]
+    attempted: usize,
+}
+
+// ── Scheduling decisions ─────────────────────────────────────────────────────
+
+/// Whether a scheduled check should run at `now`.  Always `false` when
+/// `interval_days` is `0` (scheduled checks disabled).
+///
+/// A shortened interval takes effect immediately: the check is due once
+/// either the stored `next_check_at` or `last_checked_at + interval` (plus
+/// the maximum jitter) passes.
+pub fn is_check_due(state: &SkillUpdateState, interval_days: u32, now: DateTime<Utc>) -> bool {
+    if interval_days == 0 {
+        return false;
+    }
+    let next = parse_time(state.next_check_at.as_deref());
+    if state.consecutive_failures > 0 {
+        // Backing off: only the retry time counts.
+        return next.map_or(true, |n| now >= n);
+    }
+    let by_interval = parse_time(state.last_checked_at.as_deref())
+        .map(|t| t + Duration::days(interval_days as i64) + max_jitter(interval_days));
+    match (next, by_interval) {
+        (None, None) => true,
+        (Some(t), None) | (None, Some(t)) => now >= t,
+        (Some(n), Some(i)) => now >= n.min(i),
+    }
+}
+
+/// Delay before retrying after `failures` consecutive failed checks.
+pub fn backoff_delay(failures: u32, interval_days: u32) -> Duration {
+    let exp = failures.saturating_sub(1).min(16);
+    let delay = Duration::minutes(BASE_BACKOFF_MINUTES * (1i64 << exp));
+    let cap = Duration::days(interval_days.max(1) as i64);
+    delay.min(cap)
+}
+
+/// Record a successful check and schedule the next one `interval + jitter`
+/// from `now`.  The jitter spreads checks from many installs over time.
+fn record_success(
+    state: &mut SkillUpdateState,
+    now: DateTime<Utc>,
+    interval_days: u32,
+    jitter: Duration,
+) {
+    state.last_checked_at = Some(now.to_rfc3339());
+    state.consecutive_failures = 0;
+    let interval = Duration::days(interval_days.max(1) as i64);
+    state.next_check_at = Some((now + interval + jitter).to_rfc3339());
+}
+
+/// Record a failed check and schedule a retry using exponential backoff.
+fn record_failure(state: &mut SkillUpdateState, now: DateTime<Utc>, interval_days: u32) {
+    state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+    let delay = backoff_delay(state.consecutive_failures, interval_days);
+    state.next_check_at = Some((now + delay).to_rfc3339());
+}
+
+/// Upper bound for scheduling jitter: 10% of the interval.
+fn max_jitter(interval_days: u32) -> Duration {
+    Duration::minutes((interval_days.max(1) as i64 * 24 * 60) / 10)
+}
+
+/// Random jitter between zero and [`max_jitter`].
+fn random_jitter(interval_days: u32) -> Duration {
+    let max_minutes = max_jitter(interval_days).num_minutes();
+    Duration::minutes(rand::random_range(0..=max_minutes))
+}
+
+fn parse_time(value: Option<&str>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+// ── Per-skill comparison ─────────────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+enum SkillComparison {
+    UpToDate,
+    UpdateAvailable,
+    LocallyModified,
+}
+
+/// Compare a skill's local and upstream hashes against the stored baseline.
+/// Returns the outcome and the baseline to store going forward.
+///
+/// With no baseline yet, the local copy is assumed to be unmodified.  When
+/// local and upstream match, the baseline is reset so that applying an
+/// update clears any earlier "modified" state.
+fn compare_skill(local: &str, upstream: &str, baseline: Option<&str>) -> (SkillComparison, String) {
+    if local == upstream {
+        return (SkillComparison::UpToDate, local.to_string());
+    }
+    match baseline {
+        Some(b) if b != local => (SkillComparison::LocallyModified, b.to_string()),
+        _ => (SkillComparison::UpdateAvailable, local.to_string()),
+    }
+}
+
+fn summarise(count: usize) -> String {
+    match count {
+        0 => "All skills are up to date".to_string(),
+        1 => "1 skill has an update".to_string(),
+        n => format!("{} skills have updates", n),
+    }
+}
+
+// ── Persistence ──────────────────────────────────────────────────────────────
+
+fn get_skill_update_state_path() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("skill_updates.json"))
+}
+
+/// Read the persisted update-check state.  Returns defaults when the file is
+/// missing or unreadable so a corrupt file never blocks the scheduler.
+pub fn read_skill_update_state() -> Result<SkillUpdateState, String> {
+    let path = get_skill_update_state_path()?;
+    if !path.exists() {
+        return Ok(SkillUpdateState::default());
+    }
+    let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    Ok(serde_json::from_str(&raw).unwrap_or_default())
+}
+
+fn write_skill_update_state(state: &SkillUpdateState) -> Result<(), String> {
+    let path = get_skill_update_state_path()?;
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+    }
+    let raw = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(&path, raw).map_err(|e| e.to_string())
+}
+
+// ── Check runs ───────────────────────────────────────────────────────────────
+
+/// Run the update check immediately, regardless of schedule, and persist the
+/// outcome.  A run in which every upstream fetch failed counts as a network
+/// failure and triggers backoff.
+pub async fn check_skill_updates_now() -> Result<SkillUpdateReport, String> {
+    let interval_days = super::read_settings()
+        .map(|s| s.skill_update_interval_days)
+        .unwrap_or(DEFAULT_SKILL_UPDATE_INTERVAL_DAYS);
+    let mut state = read_skill_update_state()?;
+    let now = Utc::now();
+
+    let report = run_check(&mut state, now).await;
+
+    if report.attempted > 0 && report.failed.len() == report.attempted {
+        record_failure(&mut state, now, interval_days);
+        write_skill_update_state(&state)?;
+        return Err(format!(
+            "Could not reach upstream for {} skill(s); will retry later",
+            report.failed.len()
+        ));
+    }
+
+    record_success(&mut state, now, interval_days, random_jitter(interval_days));
+    state.available = report.available.clone();
+    write_skill_update_state(&state)?;
+    Ok(report)
+}
+
+/// Run the update check only if the schedule says it is due.  Returns
+/// `Ok(None)` when scheduled checks are disabled or not yet due.
+pub async fn run_scheduled_skill_update_check() -> Result<Option<SkillUpdateReport>, String> {
+    let interval_days = super::read_settings()
+        .map(|s| s.skill_update_interval_days)
+        .unwrap_or(DEFAULT_SKILL_UPDATE_INTERVAL_DAYS);
+    let state = read_skill_update_state()?;
+    if !is_check_due(&state, interval_days, Utc::now()) {
+        return Ok(None);
+    }
+    check_skill_updates_now().await.map(Some)
+}
+
+async fn run_check(state: &mut SkillUpdateState, now: DateTime<Utc>) -> SkillUpdateReport {
+    let mut report = SkillUpdateReport {
+        checked_at: now.to_rfc3339(),
+        ..Default::default()
+    };
+
+    let mut sources: Vec<(String, super::SkillSource)> = super::read_skill_sources()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, s)| s.kind == "github")
+        .collect();
+    sources.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // Forget baselines for skills that are no longer imported.
+    state
+        .baseline_hashes
+        .retain(|name, _| sources.iter().any(|(n, _)| n == name));
+
+    let mut first = true;
+    for (name, source) in sources {
+        if source.pinned {
+            report.pinned.push(name);
+            continue;
+        }
+        let local = match super::read_skill(&name) {
+            Ok(content) if !content.is_empty() => content,
+            _ => continue,
+        };
+        // Edited since it was installed: no need to ask upstream.
+        if super::skill_store::skill_content_modified(Some(&source), &local) {
+            report.locally_modified.push(name);
+            continue;
+        }
+
+        if !first {
+            tokio::time::sleep(REQUEST_SPACING).await;
+        }
+        first = false;
+        report.attempted += 1;
+
+        // The skills.sh name is the last segment of the id; it can differ
+        // from the local (frontmatter) name.
+        let remote_name = source.id.rsplit('/').next().unwrap_or(&name).to_string();
+        let upstream = match super::fetch_remote_skill_content(&source.source, &remote_name).await {
+            Ok(content) => content,
+            Err(_) => {
+                report.failed.push(name);
+                continue;
+            }
+        };
+
+        let local_hash = super::compute_content_hash(&local);
+        let upstream_hash = super::compute_content_hash(&upstream);
+        let baseline = state.baseline_hashes.get(&name).map(|s| s.as_str());
+        let (outcome, new_baseline) = compare_skill(&local_hash, &upstream_hash, baseline);
+        state.baseline_hashes.insert(name.clone(), new_baseline);
+
+        match outcome {
+            SkillComparison::UpToDate => {}
+            SkillComparison::UpdateAvailable => report.available.push(AvailableSkillUpdate {
+                name,
+                source: source.source,
+                id: source.id,
+            }),
+            SkillComparison::LocallyModified => report.locally_modified.push(name),
+        }
+    }
+
+    report.summary = summarise(report.available.len());
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    // ── Scheduling ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn first_run_is_due() {
+        assert!(is_check_due(&SkillUpdateState::default(), 7, at(1, 0)));
+    }
+
+    #[test]
+    fn disabled_interval_is_never_due() {
+        assert!(!is_check_due(&SkillUpdateState::default(), 0, at(1, 0)));
+    }
+
+    #[test]
+    fn success_schedules_next_run_after_interval_plus_jitter() {
+        let mut state = SkillUpdateState::default();
+        record_success(&mut state, at(1, 0), 7, Duration::hours(3));
+
+        assert!(!is_check_due(&state, 7, at(7, 23)));
+        assert!(!is_check_due(&state, 7, at(8, 2)));
+        assert!(is_check_due(&state, 7, at(8, 3)));
+        assert_eq!(state.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn restart_does_not_recheck_early() {
+        let mut state = SkillUpdateState::default();
+        record_success(&mut state, at(1, 0), 7, Duration::zero());
+        let raw = serde_json::to_string(&state).unwrap();
+        let reloaded: SkillUpdateState = serde_json::from_str(&raw).unwrap();
+        assert!(!is_check_due(&reloaded, 7, at(2, 0)));
+    }
+
+    #[test]
+    fn shortened_interval_takes_effect() {
+        let mut state = SkillUpdateState::default();
+        record_success(&mut state, at(1, 0), 7, Duration::zero());
+        assert!(!is_check_due(&state, 7, at(2, 3)));
+        assert!(is_check_due(&state, 1, at(2, 3)));
+    }
+
+    #[test]
+    fn failures_back_off_exponentially() {
+        let mut state = SkillUpdateState::default();
+        record_success(&mut state, at(1, 0), 7, Duration::zero());
+
+        record_failure(&mut state, at(8, 0), 7);
+        assert_eq!(state.consecutive_failures, 1);
+        // Retry after 30 minutes, even though the interval has elapsed.
+        assert!(!is_check_due(&state, 7, at(8, 0) + Duration::minutes(29)));
+        assert!(is_check_due(&state, 7, at(8, 0) + Duration::minutes(30)));
+
+        record_failure(&mut state, at(8, 1), 7);
+        assert!(!is_check_due(&state, 7, at(8, 1) + Duration::minutes(59)));
+        assert!(is_check_due(&state, 7, at(8, 2)));
+    }
+
+    #[test]
+    fn backoff_is_capped_at_interval() {
+        assert_eq!(backoff_delay(1, 7), Duration::minutes(30));
+        assert_eq!(backoff_delay(3, 7), Duration::minutes(120));
+        assert_eq!(backoff_delay(20, 7), Duration::days(7));
+        assert_eq!(backoff_delay(20, 0), Duration::days(1));
+    }
+
+    #[test]
+    fn success_after_failure_resets_backoff() {
+        let mut state = SkillUpdateState::default();
+        record_failure(&mut state, at(1, 0), 7);
+        record_failure(&mut state, at(1, 1), 7);
+        record_success(&mut state, at(1, 3), 7, Duration::zero());
+        assert_eq!(state.consecutive_failures, 0);
+        assert!(!is_check_due(&state, 7, at(7, 0)));
+    }
+
+    #[test]
+    fn jitter_stays_within_ten_percent() {
+        for _ in 0..50 {
+            let j = random_jitter(7);
+            assert!(j >= Duration::zero());
+            assert!(j <= Duration::minutes(7 * 24 * 6));
+        }
+    }
+
+    // ── Comparison ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn compare_detects_update_without_baseline() {
+        let (outcome, baseline) = compare_skill("a", "b", None);
+        assert_eq!(outcome, SkillComparison::UpdateAvailable);
+        assert_eq!(baseline, "a");
+    }
+
+    #[test]
+    fn compare_skips_locally_modified() {
+        let (outcome, baseline) = compare_skill("edited", "b", Some("a"));
+        assert_eq!(outcome, SkillComparison::LocallyModified);
+        assert_eq!(baseline, "a");
+    }
+
+    #[test]
+    fn compare_resets_baseline_when_in_sync() {
+        let (outcome, baseline) = compare_skill("b", "b", Some("a"));
+        assert_eq!(outcome, SkillComparison::UpToDate);
+        assert_eq!(baseline, "b");
+    }
+
+    #[test]
+    fn summary_pluralises() {
+        assert_eq!(summarise(1), "1 skill has an update");
+        assert_eq!(summarise(3), "3 skills have updates");
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::types::SkillsJson;
 use super::*;
@@ -56,6 +56,56 @@ pub fn get_all_skill_sources() -> Vec<SkillSourceDir> {
     sources
 }
 
+/// Source id of the skill library (see [`get_skill_library_dir`]).
+pub const SKILL_LIBRARY_SOURCE: &str = "library";
+
+/// The global skill sources followed by the skill library: every directory
+/// an installed skill can live in.  The library comes last, so a skill that
+/// is also installed globally resolves to its global copy.
+pub fn get_skill_lookup_sources() -> Vec<SkillSourceDir> {
+    let mut sources = get_all_skill_sources();
+    if let Ok(library_dir) = get_skill_library_dir() {
+        sources.push(SkillSourceDir {
+            id: SKILL_LIBRARY_SOURCE.to_string(),
+            path: library_dir.display().to_string(),
+        });
+    }
+    sources
+}
+
+/// The scope of a skill found in `sources`: `"library"` when the library is
+/// the only source holding it, `"global"` otherwise.
+fn scope_of(sources: &[String]) -> &'static str {
+    if !sources.is_empty() && sources.iter().all(|s| s == SKILL_LIBRARY_SOURCE) {
+        "library"
+    } else {
+        "global"
+    }
+}
+
+/// The path a skills directory resolves to once symlinks are followed (e.g.
+/// a `~/.claude/skills` that links to `~/.agents/skills`).  Paths that do not
+/// exist are returned unchanged.
+fn resolve_dir(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Group skill sources by the physical directory they resolve to, keeping
+/// priority order.  Sources that alias one another (through a symlinked
+/// root) collapse into one entry listing all of their ids, so each
+/// directory is scanned, synced and deleted from exactly once.
+fn distinct_skill_dirs(sources: &[SkillSourceDir]) -> Vec<(PathBuf, Vec<String>)> {
+    let mut dirs: Vec<(PathBuf, Vec<String>)> = Vec::new();
+    for source in sources {
+        let resolved = resolve_dir(Path::new(&source.path));
+        match dirs.iter_mut().find(|(dir, _)| *dir == resolved) {
+            Some((_, ids)) => ids.push(source.id.clone()),
+            None => dirs.push((resolved, vec![source.id.clone()])),
+        }
+    }
+    dirs
+}
+
 // ── Skills ───────────────────────────────────────────────────────────────────
 
 /// Return true if a skill directory contains anything besides SKILL.md.
@@ -134,26 +184,12 @@ fn scan_skills_dir(dir: &PathBuf) -> Result<std::collections::HashSet<String>, S
     Ok(names)
 }
 
-/// List skills from all global skill directories,
+/// List skills from all global skill directories and the skill library,
 /// returning entries that indicate which sources each skill exists in,
 /// with remote origin info joined from ~/.automatic/skills.json.
 pub fn list_skills() -> Result<Vec<SkillEntry>, String> {
-    let sources = get_all_skill_sources();
-
-    // Map: skill name -> set of source IDs where it exists
-    let mut skill_sources: HashMap<String, Vec<String>> = HashMap::new();
-
-    // Scan each source directory
-    for source in &sources {
-        let source_path = PathBuf::from(&source.path);
-        let names = scan_skills_dir(&source_path)?;
-        for name in names {
-            skill_sources
-                .entry(name)
-                .or_insert_with(Vec::new)
-                .push(source.id.clone());
-        }
-    }
+    let sources = get_skill_lookup_sources();
+    let skill_sources = scan_skill_sources(&sources)?;
 
     // Best-effort registry load — don't fail list_skills if the file is missing/corrupt
     let registry = read_skill_sources().unwrap_or_default();
@@ -175,24 +211,38 @@ pub fn list_skills() -> Result<Vec<SkillEntry>, String> {
 
             let has_resources = skill_has_resources(&canonical_dir);
 
-            // Extract license from SKILL.md frontmatter (best-effort, no error on failure)
-            let license = fs::read_to_string(canonical_dir.join("SKILL.md"))
-                .ok()
-                .and_then(|c| super::skill_store::extract_frontmatter_license(&c));
+            // Extract license from SKILL.md frontmatter (best-effort, no error
+            // on failure), falling back to the one recorded at import time.
+            let content = fs::read_to_string(canonical_dir.join("SKILL.md")).ok();
+            let license = content
+                .as_deref()
+                .and_then(super::skill_store::extract_frontmatter_license)
+                .or_else(|| registry.get(&name).and_then(|s| s.license.clone()));
+            let description = content
+                .as_deref()
+                .and_then(|c| super::parse_skill_frontmatter(c).ok().flatten())
+                .and_then(|meta| meta.description);
+            let locally_modified = content.as_deref().is_some_and(|c| {
+                super::skill_store::skill_content_modified(registry.get(&name), c)
+            });
 
             let mut sources_list = skill_sources.get(&name).cloned().unwrap_or_default();
             sources_list.sort();
 
             let plugin_id = super::app_plugins::plugin_id_for_skill(&name);
             let collection = collections.get(&name).cloned();
+            let scope = scope_of(&sources_list).to_string();
 
             SkillEntry {
                 sources: sources_list,
                 source: registry.get(&name).cloned(),
                 has_resources,
+                description,
                 license,
                 plugin_id,
                 collection,
+                locally_modified,
+                scope,
                 name,
             }
         })
@@ -201,41 +251,39 @@ pub fn list_skills() -> Result<Vec<SkillEntry>, String> {
     Ok(entries)
 }
 
+/// Map each skill name to the ids of the sources that contain it.  A
+/// directory reached through several sources (symlinked roots) is scanned
+/// once and credited to all of them.
+fn scan_skill_sources(sources: &[SkillSourceDir]) -> Result<HashMap<String, Vec<String>>, String> {
+    let mut skill_sources: HashMap<String, Vec<String>> = HashMap::new();
+    for (dir, ids) in distinct_skill_dirs(sources) {
+        for name in scan_skills_dir(&dir)? {
+            skill_sources
+                .entry(name)
+                .or_insert_with(Vec::new)
+                .extend(ids.iter().cloned());
+        }
+    }
+    Ok(skill_sources)
+}
+
 /// Convenience: list just the skill names (union of both directories).
 /// Used by sync and autodetect where only names are needed.
 pub fn list_skill_names() -> Result<Vec<String>, String> {
     Ok(list_skills()?.into_iter().map(|e| e.name).collect())
 }
 
-/// Read a skill's raw SKILL.md content without any companion file formatting.
-/// Use this for sync and drift detection where the on-disk file must match
-/// exactly — companion resource sections are not written to project skill files.
-pub fn read_skill_raw(name: &str) -> Result<String, String> {
+/// Read a skill's SKILL.md content exactly as it is on disk.  Checks
+/// `~/.agents/skills/` first (the canonical location), then falls back to
+/// `~/.claude/skills/`.  Returns an empty string if the skill does not exist.
+/// Companion files are listed separately by [`list_skill_resources`].
+pub fn read_skill(name: &str) -> Result<String, String> {
     match get_skill_path(name)? {
         Some(path) => fs::read_to_string(&path).map_err(|e| e.to_string()),
         None => Ok("".to_string()),
     }
 }
 
-/// Read a skill's SKILL.md content.  Checks `~/.agents/skills/` first
-/// (the canonical location), then falls back to `~/.claude/skills/`.
-pub fn read_skill(name: &str) -> Result<String, String> {
-    if let Some(path) = get_skill_path(name)? {
-        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-
-        // Find the parent directory to check for companion files
-        if let Some(skill_dir) = path.parent() {
-            let companions = discover_companion_files(skill_dir);
-            if !companions.is_empty() {
-                return Ok(format_skill_with_companions(&content, &companions));
-            }
-        }
-
-        return Ok(content);
-    }
-    Ok("".to_string())
-}
-
 /// A single companion resource entry returned to the frontend.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResourceFile {
@@ -268,16 +316,18 @@ pub fn list_skill_resources(name: &str) -> Result<SkillResources, String> {
         return Err("Invalid skill name".into());
     }
 
-    let skill_dir = match get_skill_dir(name)? {
-        Some(d) => d,
-        None => return Ok(SkillResources::default()),
-    };
+    match get_skill_dir(name)? {
+        Some(skill_dir) => Ok(resources_in(&skill_dir)),
+        None => Ok(SkillResources::default()),
+    }
+}
 
+fn resources_in(skill_dir: &Path) -> SkillResources {
     let mut result = SkillResources::default();
 
-    let entries = match fs::read_dir(&skill_dir) {
+    let entries = match fs::read_dir(skill_dir) {
         Ok(e) => e,
-        Err(_) => return Ok(result),
+        Err(_) => return result,
     };
 
     // Collect and sort entries for deterministic output
@@ -333,102 +383,119 @@ pub fn list_skill_resources(name: &str) -> Result<SkillResources, String> {
         });
     }
 
-    Ok(result)
+    result
 }
 
-// ── Legacy internal helpers (used by read_skill for in-content rendering) ─────
-
-/// Companion file entry — internal only.
-#[derive(Debug)]
-struct CompanionFile {
-    relative_path: String,
-    is_dir: bool,
+// ── Companion file editing ───────────────────────────────────────────────────
+//
+// `relative_path` comes from the UI or an agent, so it is resolved strictly
+// inside the skill directory: no absolute paths, no `..`, and no symlink in
+// the skill that leads outside it.  SKILL.md itself is edited through
+// `save_skill` and cannot be written or deleted here.
+
+/// Read the companion file `relative_path` (e.g. `scripts/init.py`) of skill
+/// `name`.
+pub fn read_skill_resource(name: &str, relative_path: &str) -> Result<String, String> {
+    read_resource_in(&existing_skill_dir(name)?, relative_path)
 }
 
-/// Discover companion files inside known subdirectories only.
-fn discover_companion_files(skill_dir: &std::path::Path) -> Vec<CompanionFile> {
-    let mut companions = Vec::new();
+/// Create or overwrite the companion file `relative_path` of skill `name`,
+/// creating intermediate directories.
+pub fn save_skill_resource(name: &str, relative_path: &str, content: &str) -> Result<(), String> {
+    save_resource_in(&existing_skill_dir(name)?, relative_path, content)
+}
 
-    let known_dirs = [
-        "scripts",
-        "references",
-        "docs",
-        "assets",
-        "examples",
-        "templates",
-    ];
+/// Delete the companion file `relative_path` of skill `name`, along with
+/// any directories it leaves empty.
+pub fn delete_skill_resource(name: &str, relative_path: &str) -> Result<(), String> {
+    delete_resource_in(&existing_skill_dir(name)?, relative_path)
+}
 
-    for dir_name in &known_dirs {
-        let dir_path = skill_dir.join(dir_name);
-        if dir_path.is_dir() {
-            companions.push(CompanionFile {
-                relative_path: dir_name.to_string(),
-                is_dir: true,
-            });
+fn existing_skill_dir(name: &str) -> Result<PathBuf, String> {
+    get_skill_dir(name)?.ok_or_else(|| format!("Skill '{}' not found", name))
+}
 
-            if let Ok(entries) = std::fs::read_dir(&dir_path) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    if let Ok(file_type) = entry.file_type() {
-                        if file_type.is_file() {
-                            if let Some(file_name) = entry.file_name().to_str() {
-                                companions.push(CompanionFile {
-                                    relative_path: format!("{}/{}", dir_name, file_name),
-                                    is_dir: false,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-        }
+fn read_resource_in(skill_dir: &Path, relative_path: &str) -> Result<String, String> {
+    let path = resource_path(skill_dir, relative_path)?;
+    if !path.is_file() {
+        return Err(format!("'{}' not found", relative_path));
     }
-
-    companions
+    fs::read_to_string(&path).map_err(|e| format!("Cannot read '{}': {}", relative_path, e))
 }
 
-/// Format skill content with companion files listed at the end
-fn format_skill_with_companions(skill_content: &str, companions: &[CompanionFile]) -> String {
-    let mut output = String::from(skill_content);
+fn save_resource_in(skill_dir: &Path, relative_path: &str, content: &str) -> Result<(), String> {
+    let path = writable_resource_path(skill_dir, relative_path)?;
+    if path.is_dir() {
+        return Err(format!("'{}' is a directory", relative_path));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, content).map_err(|e| format!("Failed to write '{}': {}", relative_path, e))
+}
 
-    // Add companion files section
-    output.push_str("\n\n---\n\n");
-    output.push_str("## Companion Resources\n\n");
-    output.push_str("This skill includes additional resources:\n\n");
+fn delete_resource_in(skill_dir: &Path, relative_path: &str) -> Result<(), String> {
+    let path = writable_resource_path(skill_dir, relative_path)?;
+    if !path.is_file() {
+        return Err(format!("'{}' not found", relative_path));
+    }
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete '{}': {}", relative_path, e))?;
 
-    // Group by directory
-    let mut current_dir: Option<String> = None;
-    for companion in companions {
-        if companion.is_dir {
-            current_dir = Some(companion.relative_path.clone());
-            output.push_str(&format!("\n### {}\n", companion.relative_path));
-        } else {
-            // Extract directory and filename
-            if let Some(slash_pos) = companion.relative_path.rfind('/') {
-                let dir = &companion.relative_path[..slash_pos];
-                let file = &companion.relative_path[slash_pos + 1..];
-
-                if current_dir.as_deref() == Some(dir) {
-                    output.push_str(&format!("- `{}`\n", file));
-                } else {
-                    output.push_str(&format!("- `{}`\n", companion.relative_path));
-                }
-            } else {
-                output.push_str(&format!("- `{}`\n", companion.relative_path));
-            }
+    let mut parent = path.parent();
+    while let Some(dir) = parent.filter(|d| *d != skill_dir) {
+        if fs::remove_dir(dir).is_err() {
+            break; // not empty
         }
+        parent = dir.parent();
     }
+    Ok(())
+}
 
-    output
+/// [`resource_path`], refusing SKILL.md.
+fn writable_resource_path(skill_dir: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let path = resource_path(skill_dir, relative_path)?;
+    if path.parent() == Some(skill_dir) && path.file_name().is_some_and(|n| n == "SKILL.md") {
+        return Err("SKILL.md is edited with the skill itself".into());
+    }
+    Ok(path)
+}
+
+/// `relative_path` joined onto `skill_dir`, or an error when it would
+/// resolve outside it.
+fn resource_path(skill_dir: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(relative_path);
+    let plain = relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if relative_path.trim().is_empty() || !plain {
+        return Err(format!("Invalid resource path '{}'", relative_path));
+    }
+    let path = skill_dir.join(relative);
+    let dangling = path.is_symlink() && !path.exists();
+    // Resolves symlinks along the existing part of the path.
+    let root = canonical_key(skill_dir);
+    let resolved = canonical_key(&path);
+    let inside = resolved
+        .strip_prefix(&root)
+        .is_some_and(|rest| rest.starts_with(['/', '\\']));
+    if !inside || dangling {
+        return Err(format!(
+            "Resource path '{}' leaves the skill directory",
+            relative_path
+        ));
+    }
+    Ok(path)
 }
 
 /// Get the absolute path to a skill's directory. Searches all global sources
-/// in priority order (agents first, then claude, then other agent sources).
+/// in priority order (agents first, then claude, then other agent sources),
+/// then the skill library.
 pub fn get_skill_dir(name: &str) -> Result<Option<PathBuf>, String> {
     if !is_valid_name(name) {
         return Err("Invalid skill name".into());
     }
 
-    for source in get_all_skill_sources() {
+    for source in get_skill_lookup_sources() {
         let skill_dir = PathBuf::from(&source.path).join(name);
         if skill_dir.join("SKILL.md").exists() {
             return Ok(Some(skill_dir));
@@ -459,14 +526,15 @@ fn resolve_entrypoint(skill_dir: &PathBuf, name: &str) -> String {
 }
 
 /// Get the absolute path to a skill's entrypoint file. Searches all global sources
-/// in priority order (agents first, then claude, then other agent sources).
+/// in priority order (agents first, then claude, then other agent sources),
+/// then the skill library.
 /// Respects the `entrypoint` field in `skill.json` if present; defaults to `SKILL.md`.
 pub fn get_skill_path(name: &str) -> Result<Option<PathBuf>, String> {
     if !is_valid_name(name) {
         return Err("Invalid skill name".into());
     }
 
-    for source in get_all_skill_sources() {
+    for source in get_skill_lookup_sources() {
         let skill_dir = PathBuf::from(&source.path).join(name);
         let entrypoint = resolve_entrypoint(&skill_dir, name);
         let entrypoint_path = skill_dir.join(&entrypoint);
@@ -491,12 +559,56 @@ pub fn skill_exists(name: &str) -> bool {
     agents_dir.join(name).join("SKILL.md").exists()
 }
 
-/// Save a skill to `~/.agents/skills/` (the agentskills.io standard location).
+/// Save a skill to `~/.agents/skills/` (the agentskills.io standard location),
+/// or to the skill library when that is where the skill lives.
 pub fn save_skill(name: &str, content: &str) -> Result<(), String> {
+    save_skill_in(&skill_write_root(name, false)?, name, content)
+}
+
+/// Save a skill that is being imported.  Like [`save_skill`], except that a
+/// new skill goes to the skill library when the `skill_install_scope`
+/// setting is `"library"`.
+pub fn save_imported_skill(name: &str, content: &str) -> Result<(), String> {
+    save_skill_in(&skill_write_root(name, true)?, name, content)
+}
+
+/// The root directory `name` is written to: the skill library for a library
+/// skill, or for a new one being imported while the install scope is
+/// `"library"`; `~/.agents/skills/` otherwise.
+fn skill_write_root(name: &str, importing: bool) -> Result<PathBuf, String> {
+    if !is_valid_name(name) {
+        return Err("Invalid skill name".into());
+    }
+    let to_library = match skill_scope_in(&get_skill_lookup_sources(), name)? {
+        Some(scope) => scope == "library",
+        None => importing && install_to_library(),
+    };
+    if to_library {
+        get_skill_library_dir()
+    } else {
+        // Write through the resolved path so a symlinked ~/.agents/skills is
+        // written once, at its real location.
+        Ok(resolve_dir(&get_agents_skills_dir()?))
+    }
+}
+
+/// True when the `skill_install_scope` setting sends imports to the library.
+fn install_to_library() -> bool {
+    read_settings()
+        .map(|s| s.skill_install_scope == "library")
+        .unwrap_or(false)
+}
+
+/// The scope of `name` among `sources`, or `None` when no source has it.
+fn skill_scope_in(sources: &[SkillSourceDir], name: &str) -> Result<Option<&'static str>, String> {
+    let found = scan_skill_sources(sources)?.remove(name);
+    Ok(found.map(|ids| scope_of(&ids)))
+}
+
+fn save_skill_in(agents_dir: &Path, name: &str, content: &str) -> Result<(), String> {
     if !is_valid_name(name) {
         return Err("Invalid skill name".into());
     }
-    let agents_dir = get_agents_skills_dir()?;
     let skill_dir = agents_dir.join(name);
 
     if !skill_dir.exists() {
@@ -507,21 +619,16 @@ pub fn save_skill(name: &str, content: &str) -> Result<(), String> {
     fs::write(skill_path, content).map_err(|e| e.to_string())
 }
 
-/// Delete a skill from all global skill source directories and remove its registry entry.
+/// Delete a skill from all global skill source directories and the skill
+/// library, and remove its registry entry.
 pub fn delete_skill(name: &str) -> Result<(), String> {
     if !is_valid_name(name) {
         return Err("Invalid skill name".into());
     }
 
-    // Remove from all known skill source directories (agents, claude, codex, cline, etc.)
-    for source in get_all_skill_sources() {
-        let skill_dir = PathBuf::from(&source.path).join(name);
-        if skill_dir.exists() {
-            fs::remove_dir_all(&skill_dir).map_err(|e| {
-                format!("Failed to delete skill from {}: {}", source.path, e)
-            })?;
-        }
-    }
+    // Remove from all known skill source directories (agents, claude, codex,
+    // cline, etc.) and the library
+    remove_skill_dirs(&get_skill_lookup_sources(), name)?;
 
     // Best-effort: remove from registry and collection (ignore errors)
     let _ = remove_skill_source(name);
@@ -530,59 +637,318 @@ pub fn delete_skill(name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Remove `name` from every source directory.  Each physical directory is
+/// visited once; a skill directory that is itself a symlink has only the
+/// link removed, never the directory it points at through another root.
+fn remove_skill_dirs(sources: &[SkillSourceDir], name: &str) -> Result<(), String> {
+    let dirs = distinct_skill_dirs(sources);
+    let context = RemovalContext::skills(dirs.iter().map(|(dir, _)| dir.clone()).collect());
+    for (dir, _) in &dirs {
+        remove_guarded(&dir.join(name), &context)
+            .map_err(|e| format!("Failed to delete skill from {}: {}", dir.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Rename a skill in every global skill source directory and the skill
+/// library, and carry its
+/// registry and collection entries over.  Project references are updated by
+/// the caller.
+pub fn rename_skill(old_name: &str, new_name: &str) -> Result<(), String> {
+    if !is_valid_name(old_name) || !is_valid_name(new_name) {
+        return Err("Invalid skill name".into());
+    }
+    if old_name == new_name {
+        return Ok(());
+    }
+
+    rename_skill_dirs(&get_skill_lookup_sources(), old_name, new_name)?;
+
+    // Best-effort, as in delete_skill
+    let _ = rename_skill_source(old_name, new_name);
+    let _ = rename_skill_collection(old_name, new_name);
+
+    Ok(())
+}
+
+/// Move `old_name` to `new_name` in every source directory.  All roots are
+/// checked before anything moves, so a name clash leaves the skill intact.
+/// A case-only rename (`Foo` → `foo`) on a case-insensitive filesystem names
+/// the same entry twice and is not a clash, as in `rename_project_in`.
+fn rename_skill_dirs(
+    sources: &[SkillSourceDir],
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), String> {
+    let dirs = distinct_skill_dirs(sources);
+
+    let mut moves: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (dir, _) in &dirs {
+        let from = dir.join(old_name);
+        if from.symlink_metadata().is_err() {
+            continue;
+        }
+        let to = dir.join(new_name);
+        if to.symlink_metadata().is_ok() && !same_path(&from, &to) {
+            return Err(format!(
+                "A skill named '{}' already exists in {}",
+                new_name,
+                dir.display()
+            ));
+        }
+        moves.push((from, to));
+    }
+    if moves.is_empty() {
+        return Err(format!("Skill '{}' not found in any location", old_name));
+    }
+
+    for (from, to) in &moves {
+        // A skill directory linked to the same skill in another root would
+        // dangle once that moves; point it at the new name instead.
+        let link_target = fs::read_link(from)
+            .ok()
+            .filter(|target| target.file_name().is_some_and(|n| n == old_name));
+        match link_target {
+            Some(target) => {
+                fs::remove_file(from).map_err(|e| e.to_string())?;
+                let new_target = target.with_file_name(new_name);
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(&new_target, to).map_err(|e| e.to_string())?;
+                #[cfg(windows)]
+                std::os::windows::fs::symlink_dir(&new_target, to).map_err(|e| e.to_string())?;
+            }
+            None => {
+                fs::rename(from, to)
+                    .map_err(|e| format!("Failed to rename skill in {}: {}", from.display(), e))?;
+                rename_frontmatter_name(&to.join("SKILL.md"), old_name, new_name);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Update the frontmatter `name:` of a renamed skill when it still carries
+/// the old name, so validation does not flag the mismatch.
+fn rename_frontmatter_name(skill_md: &Path, old_name: &str, new_name: &str) {
+    let Ok(content) = fs::read_to_string(skill_md) else {
+        return;
+    };
+    let Some(body) = content.strip_prefix("---\n") else {
+        return;
+    };
+    let Some(end) = body.find("\n---") else {
+        return;
+    };
+    let mut replaced = false;
+    let frontmatter: Vec<String> = body[..end]
+        .lines()
+        .map(|line| {
+            let value = line
+                .strip_prefix("name:")
+                .map(|v| v.trim().trim_matches(['"', '\'']));
+            if !replaced && value == Some(old_name) {
+                replaced = true;
+                format!("name: {}", new_name)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if replaced {
+        let updated = format!("---\n{}{}", frontmatter.join("\n"), &body[end..]);
+        if let Err(e) = fs::write(skill_md, updated) {
+            eprintln!(
+                "[automatic] Failed to update name in {}: {}",
+                skill_md.display(),
+                e
+            );
+        }
+    }
+}
+
 /// Sync a single skill into the primary ~/.agents/skills/ directory.
 /// Copies from the first source that has the skill.
-/// If it already exists in ~/.agents/skills/, this is a no-op.
+/// If it already exists in ~/.agents/skills/, or lives only in the skill
+/// library, this is a no-op.
 pub fn sync_skill(name: &str) -> Result<(), String> {
     if !is_valid_name(name) {
         return Err("Invalid skill name".into());
     }
+    sync_skill_in(&get_skill_lookup_sources(), name)
+}
 
-    let agents_dir = get_agents_skills_dir()?;
-    let agents_path = agents_dir.join(name).join("SKILL.md");
+fn sync_skill_in(sources: &[SkillSourceDir], name: &str) -> Result<(), String> {
+    let dirs = distinct_skill_dirs(sources);
+    let Some((agents_dir, _)) = dirs
+        .iter()
+        .find(|(_, ids)| ids.iter().any(|i| i == "agents"))
+    else {
+        return Err("No ~/.agents/skills/ source configured".into());
+    };
+    let agents_skill_dir = agents_dir.join(name);
+    let agents_path = agents_skill_dir.join("SKILL.md");
 
-    // If already in agents directory, nothing to do
+    // If already in agents directory (directly or through a symlink),
+    // nothing to do
     if agents_path.exists() {
         return Ok(());
     }
 
-    // Find the first source that has this skill
-    for source in get_all_skill_sources() {
-        if source.id == "agents" {
-            continue; // Skip primary, we already checked
+    // Find the first other directory that has this skill.  Sources aliasing
+    // the agents directory were collapsed into it above.
+    let mut in_library = false;
+    for (dir, ids) in &dirs {
+        if dir == agents_dir {
+            continue;
         }
-        let source_path = PathBuf::from(&source.path).join(name).join("SKILL.md");
+        // Library skills are kept out of the global directories on purpose.
+        if ids.iter().any(|i| i == SKILL_LIBRARY_SOURCE) {
+            in_library |= dir.join(name).join("SKILL.md").exists();
+            continue;
+        }
+        let source_path = dir.join(name).join("SKILL.md");
         if source_path.exists() {
-            // Copy to agents directory
+            // A dangling symlink left where the skill used to be would make
+            // the copy fail; replace it with a real directory.
+            if agents_skill_dir
+                .symlink_metadata()
+                .is_ok_and(|m| m.file_type().is_symlink())
+            {
+                fs::remove_file(&agents_skill_dir).map_err(|e| e.to_string())?;
+            }
             let content = fs::read_to_string(&source_path).map_err(|e| e.to_string())?;
-            let target_dir = agents_dir.join(name);
-            fs::create_dir_all(&target_dir).map_err(|e| e.to_string())?;
+            fs::create_dir_all(&agents_skill_dir).map_err(|e| e.to_string())?;
             fs::write(&agents_path, content).map_err(|e| e.to_string())?;
             return Ok(());
         }
     }
 
+    if in_library {
+        return Ok(());
+    }
     Err(format!("Skill '{}' not found in any location", name))
 }
 
 /// Sync all skills across all global directories.
-/// Copies any skill missing from ~/.agents/skills/ into that location.
+/// Copies any skill missing from ~/.agents/skills/ into that location,
+/// except those that live only in the skill library.
 /// Returns the list of skill names that were synced.
 pub fn sync_all_skills() -> Result<Vec<String>, String> {
-    let entries = list_skills()?;
+    sync_all_skills_in(&get_skill_lookup_sources())
+}
+
+fn sync_all_skills_in(sources: &[SkillSourceDir]) -> Result<Vec<String>, String> {
+    let mut names: Vec<(String, Vec<String>)> = scan_skill_sources(sources)?.into_iter().collect();
+    names.sort();
     let mut synced = Vec::new();
 
-    for entry in entries {
-        // Sync any skill that doesn't exist in the primary agents directory
-        if !entry.sources.contains(&"agents".to_string()) {
-            sync_skill(&entry.name)?;
-            synced.push(entry.name);
+    for (name, found_in) in names {
+        // Sync any global skill that doesn't exist in the primary agents
+        // directory
+        if !found_in.iter().any(|id| id == "agents") && scope_of(&found_in) == "global" {
+            sync_skill_in(sources, &name)?;
+            synced.push(name);
         }
     }
 
     Ok(synced)
 }
 
+// ── Skill Library ────────────────────────────────────────────────────────────
+
+/// Move a library skill into `~/.agents/skills/`, where every agent can see
+/// it.
+pub fn promote_skill_to_global(name: &str) -> Result<(), String> {
+    if !is_valid_name(name) {
+        return Err("Invalid skill name".into());
+    }
+    promote_skill_in(&get_skill_lookup_sources(), name)
+}
+
+fn promote_skill_in(sources: &[SkillSourceDir], name: &str) -> Result<(), String> {
+    match skill_scope_in(sources, name)? {
+        None => return Err(format!("Skill '{}' not found in any location", name)),
+        Some("global") => return Err(format!("Skill '{}' is already global", name)),
+        Some(_) => {}
+    }
+    let (library, global): (Vec<SkillSourceDir>, Vec<SkillSourceDir>) = sources
+        .iter()
+        .cloned()
+        .partition(|s| s.id == SKILL_LIBRARY_SOURCE);
+    let agents_dir = global
+        .iter()
+        .find(|s| s.id == "agents")
+        .map(|s| resolve_dir(Path::new(&s.path)))
+        .ok_or("No ~/.agents/skills/ source configured")?;
+    let library_dir = PathBuf::from(&library[0].path);
+
+    move_skill(
+        &library_dir.join(name),
+        &agents_dir.join(name),
+        &library,
+        name,
+    )
+}
+
+/// Move a global skill into the skill library and remove it from every
+/// global skill directory, so agents only see it in projects that use it.
+pub fn demote_skill_to_library(name: &str) -> Result<(), String> {
+    if !is_valid_name(name) {
+        return Err("Invalid skill name".into());
+    }
+    demote_skill_in(&get_skill_lookup_sources(), name)
+}
+
+fn demote_skill_in(sources: &[SkillSourceDir], name: &str) -> Result<(), String> {
+    match skill_scope_in(sources, name)? {
+        None => return Err(format!("Skill '{}' not found in any location", name)),
+        Some("library") => return Err(format!("Skill '{}' is already in the library", name)),
+        Some(_) => {}
+    }
+    let (library, global): (Vec<SkillSourceDir>, Vec<SkillSourceDir>) = sources
+        .iter()
+        .cloned()
+        .partition(|s| s.id == SKILL_LIBRARY_SOURCE);
+    let library_dir = library
+        .first()
+        .map(|s| PathBuf::from(&s.path))
+        .ok_or("No skill library configured")?;
+    // The copy agents have been using, found the way get_skill_dir finds it.
+    let global_dir = global
+        .iter()
+        .map(|s| PathBuf::from(&s.path).join(name))
+        .find(|dir| dir.join("SKILL.md").exists())
+        .ok_or_else(|| format!("Skill '{}' not found in any location", name))?;
+
+    // A library copy shadowed by the global one is out of date.
+    remove_skill_dirs(&library, name)?;
+    move_skill(&global_dir, &library_dir.join(name), &global, name)
+}
+
+/// Copy the skill directory `from` to `to`, then remove `name` from every
+/// one of `sources`.  A copy that leaves anything out is undone and nothing
+/// is removed.
+fn move_skill(
+    from: &Path,
+    to: &Path,
+    sources: &[SkillSourceDir],
+    name: &str,
+) -> Result<(), String> {
+    if to.symlink_metadata().is_ok() {
+        return Err(format!("'{}' already exists", to.display()));
+    }
+    let report = copy_skill_dir(from, to)?;
+    if report.is_partial() {
+        let _ = remove_guarded(to, &RemovalContext::external(to).forced());
+        return Err(format!(
+            "Failed to move skill '{}': {}",
+            name,
+            report.skipped.join(", ")
+        ));
+    }
+    remove_skill_dirs(sources, name)
+}
+
 // ── Skill Import ─────────────────────────────────────────────────────────────────
 
 /// Result of importing a skill from a local path.
@@ -599,6 +965,9 @@ pub struct ImportedSkill {
 /// 2. A directory with skill.json at root → imports all skills defined in the manifest
 /// 3. A directory with SKILL.md files up to 3 levels deep → imports each found skill
 ///
+/// New skills go to the skill library when the `skill_install_scope` setting
+/// is `"library"`.
+///
 /// Returns the list of imported skill names.
 pub fn import_skill_from_local_path(path: &str) -> Result<Vec<ImportedSkill>, String> {
     let source_path = PathBuf::from(path);
@@ -607,7 +976,6 @@ pub fn import_skill_from_local_path(path: &str) -> Result<Vec<ImportedSkill>, St
         return Err(format!("Path does not exist: {}", path));
     }
 
-    let agents_dir = get_agents_skills_dir()?;
     let mut imported = Vec::new();
 
     // ── Case 1: Direct SKILL.md file ─────────────────────────────────────────
@@ -637,7 +1005,7 @@ pub fn import_skill_from_local_path(path: &str) -> Result<Vec<ImportedSkill>, St
         let content =
             fs::read_to_string(&source_path).map_err(|e| format!("Failed to read file: {}", e))?;
 
-        let skill_dir = agents_dir.join(skill_name);
+        let skill_dir = skill_write_root(skill_name, true)?.join(skill_name);
         fs::create_dir_all(&skill_dir)
             .map_err(|e| format!("Failed to create skill directory: {}", e))?;
 
@@ -688,7 +1056,7 @@ pub fn import_skill_from_local_path(path: &str) -> Result<Vec<ImportedSkill>, St
             let content = fs::read_to_string(&entry_path)
                 .map_err(|e| format!("Failed to read {}: {}", entrypoint, e))?;
 
-            let skill_dir = agents_dir.join(&skill_entry.name);
+            let skill_dir = skill_write_root(&skill_entry.name, true)?.join(&skill_entry.name);
             fs::create_dir_all(&skill_dir)
                 .map_err(|e| format!("Failed to create skill directory: {}", e))?;
 
@@ -762,7 +1130,7 @@ pub fn import_skill_from_local_path(path: &str) -> Result<Vec<ImportedSkill>, St
         let content = fs::read_to_string(&skill_file)
             .map_err(|e| format!("Failed to read {}: {}", skill_file.display(), e))?;
 
-        let skill_dir = agents_dir.join(skill_name);
+        let skill_dir = skill_write_root(skill_name, true)?.join(skill_name);
         fs::create_dir_all(&skill_dir)
             .map_err(|e| format!("Failed to create skill directory: {}", e))?;
 
@@ -783,49 +1151,31 @@ pub fn import_skill_from_local_path(path: &str) -> Result<Vec<ImportedSkill>, St
     Ok(imported)
 }
 
-/// Copy companion files (scripts/, references/, etc.) from a source skill directory
-/// to the destination skill directory.
+/// Copy companion files (scripts/, references/, etc.) and root-level files
+/// (LICENSE, README, etc.) from a source skill directory to the destination
+/// skill directory.  Entries that cannot be copied are logged and skipped.
 fn copy_companion_files(source: &std::path::Path, dest: &std::path::Path) -> Result<(), String> {
-    let companion_dirs = [
+    let companions = [
         "scripts",
         "references",
         "docs",
         "assets",
         "examples",
         "templates",
+        "LICENSE",
+        "LICENSE.md",
+        "LICENSE.txt",
+        "README.md",
     ];
 
-    for dir_name in &companion_dirs {
-        let source_dir = source.join(dir_name);
-        if source_dir.is_dir() {
-            let dest_dir = dest.join(dir_name);
-            fs::create_dir_all(&dest_dir)
-                .map_err(|e| format!("Failed to create directory {}: {}", dir_name, e))?;
-
-            if let Ok(entries) = fs::read_dir(&source_dir) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    let src_file = entry.path();
-                    let dest_file = dest_dir.join(entry.file_name());
-                    if src_file.is_file() {
-                        fs::copy(&src_file, &dest_file)
-                            .map_err(|e| format!("Failed to copy file: {}", e))?;
-                    }
-                }
-            }
-        }
+    let report = copy_skill_entries(source, dest, &companions)?;
+    for entry in &report.skipped {
+        eprintln!(
+            "[automatic] Skipped companion file in '{}': {}",
+            source.display(),
+            entry
+        );
     }
-
-    // Also copy any root-level files (LICENSE, README, etc.)
-    let root_files = ["LICENSE", "LICENSE.md", "LICENSE.txt", "README.md"];
-    for file_name in &root_files {
-        let src_file = source.join(file_name);
-        if src_file.is_file() {
-            let dest_file = dest.join(file_name);
-            fs::copy(&src_file, &dest_file)
-                .map_err(|e| format!("Failed to copy {}: {}", file_name, e))?;
-        }
-    }
-
     Ok(())
 }
 
@@ -914,13 +1264,7 @@ pub fn read_skill_collections() -> Result<HashMap<String, String>, String> {
 /// Write the full collections registry atomically.
 fn write_skill_collections(registry: &HashMap<String, String>) -> Result<(), String> {
     let path = get_skill_collections_path()?;
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
-    }
-    let json = serde_json::to_string_pretty(registry).map_err(|e| e.to_string())?;
-    fs::write(&path, json).map_err(|e| e.to_string())
+    write_atomic(&path, to_stable_json(registry)?)
 }
 
 /// Assign a skill to a collection.
@@ -937,6 +1281,18 @@ pub fn remove_skill_collection(skill_name: &str) -> Result<(), String> {
     write_skill_collections(&registry)
 }
 
+/// Move a renamed skill's collection assignment to its new name.
+pub fn rename_skill_collection(old_name: &str, new_name: &str) -> Result<(), String> {
+    let mut registry = read_skill_collections()?;
+    match registry.remove(old_name) {
+        Some(collection) => {
+            registry.insert(new_name.to_string(), collection);
+            write_skill_collections(&registry)
+        }
+        None => Ok(()),
+    }
+}
+
 /// Assign multiple skills to the same collection in a single write.
 pub fn set_skills_collection(skill_names: &[String], collection: &str) -> Result<(), String> {
     let mut registry = read_skill_collections()?;
@@ -1294,6 +1650,208 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ── Symlinked skill roots ─────────────────────────────────────────────────
+
+    /// A fixture home whose `.claude/skills` is a symlink to `.agents/skills`.
+    #[cfg(unix)]
+    fn symlinked_home(tmp: &TempDir) -> Vec<SkillSourceDir> {
+        let agents = tmp.path().join(".agents/skills");
+        fs::create_dir_all(&agents).expect("create agents");
+        fs::create_dir_all(tmp.path().join(".claude")).expect("create .claude");
+        std::os::unix::fs::symlink(&agents, tmp.path().join(".claude/skills"))
+            .expect("symlink claude -> agents");
+        home_sources(tmp)
+    }
+
+    fn home_sources(tmp: &TempDir) -> Vec<SkillSourceDir> {
+        ["agents", "claude"]
+            .iter()
+            .map(|id| SkillSourceDir {
+                id: id.to_string(),
+                path: tmp
+                    .path()
+                    .join(format!(".{}/skills", id))
+                    .display()
+                    .to_string(),
+            })
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_roots_collapse_into_one_directory() {
+        let tmp = tmp();
+        let sources = symlinked_home(&tmp);
+        make_skill(&tmp.path().join(".agents/skills"), "shared", "# Shared");
+
+        let dirs = distinct_skill_dirs(&sources);
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(dirs[0].1, vec!["agents", "claude"]);
+
+        // Listed once, present in both sources.
+        let found = scan_skill_sources(&sources).expect("scan");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found["shared"], vec!["agents", "claude"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_is_a_no_op_for_symlinked_roots() {
+        let tmp = tmp();
+        let sources = symlinked_home(&tmp);
+        make_skill(&tmp.path().join(".agents/skills"), "shared", "# Shared");
+
+        sync_skill_in(&sources, "shared").expect("sync");
+        assert!(sync_all_skills_in(&sources).expect("sync all").is_empty());
+        let content =
+            fs::read_to_string(tmp.path().join(".claude/skills/shared/SKILL.md")).unwrap();
+        assert_eq!(content, "# Shared");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn delete_through_symlinked_roots_succeeds() {
+        let tmp = tmp();
+        let sources = symlinked_home(&tmp);
+        make_skill(&tmp.path().join(".agents/skills"), "bye", "# Bye");
+
+        remove_skill_dirs(&sources, "bye").expect("delete");
+        assert!(!tmp.path().join(".agents/skills/bye").exists());
+        assert!(scan_skill_sources(&sources).expect("scan").is_empty());
+        // The symlinked root itself is left alone.
+        assert!(tmp.path().join(".claude/skills").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_skill_directory_is_listed_in_both_and_unlinked_on_delete() {
+        let tmp = tmp();
+        let sources = home_sources(&tmp);
+        let agents = tmp.path().join(".agents/skills");
+        let claude = tmp.path().join(".claude/skills");
+        make_skill(&agents, "linked", "# Linked");
+        fs::create_dir_all(&claude).expect("create claude");
+        std::os::unix::fs::symlink(agents.join("linked"), claude.join("linked"))
+            .expect("symlink skill");
+
+        assert_eq!(distinct_skill_dirs(&sources).len(), 2);
+        let found = scan_skill_sources(&sources).expect("scan");
+        assert_eq!(found["linked"], vec!["agents", "claude"]);
+        assert!(sync_all_skills_in(&sources).expect("sync all").is_empty());
+
+        remove_skill_dirs(&sources, "linked").expect("delete");
+        assert!(agents.join("linked").symlink_metadata().is_err());
+        // No dangling link is left behind in the claude root.
+        assert!(claude.join("linked").symlink_metadata().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rename_moves_skill_and_retargets_linked_copies() {
+        let tmp = tmp();
+        let sources = home_sources(&tmp);
+        let agents = tmp.path().join(".agents/skills");
+        let claude = tmp.path().join(".claude/skills");
+        make_skill(&agents, "old", "---\nname: old\ndescription: D\n---\n# Old");
+        fs::create_dir_all(&claude).expect("create claude");
+        std::os::unix::fs::symlink(agents.join("old"), claude.join("old")).expect("symlink");
+
+        rename_skill_dirs(&sources, "old", "new").expect("rename");
+        assert!(agents.join("old").symlink_metadata().is_err());
+        assert_eq!(
+            fs::read_to_string(claude.join("new/SKILL.md")).unwrap(),
+            "---\nname: new\ndescription: D\n---\n# Old"
+        );
+        assert_eq!(
+            fs::read_link(claude.join("new")).unwrap(),
+            agents.join("new")
+        );
+        assert!(rename_skill_dirs(&sources, "old", "new").is_err());
+    }
+
+    #[test]
+    fn rename_refuses_an_existing_target_without_moving_anything() {
+        let tmp = tmp();
+        let sources = home_sources(&tmp);
+        let agents = tmp.path().join(".agents/skills");
+        let claude = tmp.path().join(".claude/skills");
+        make_skill(&agents, "old", "# Old");
+        make_skill(&claude, "old", "# Old");
+        make_skill(&claude, "new", "# Taken");
+
+        let err = rename_skill_dirs(&sources, "old", "new").unwrap_err();
+        assert!(err.contains("already exists"));
+        assert!(agents.join("old/SKILL.md").exists());
+        assert!(!agents.join("new").exists());
+    }
+
+    #[test]
+    fn sync_copies_claude_only_skill_into_agents_root() {
+        let tmp = tmp();
+        let sources = home_sources(&tmp);
+        make_skill(&tmp.path().join(".claude/skills"), "claude-only", "# C");
+
+        assert_eq!(
+            sync_all_skills_in(&sources).expect("sync all"),
+            vec!["claude-only"]
+        );
+        assert!(tmp
+            .path()
+            .join(".agents/skills/claude-only/SKILL.md")
+            .exists());
+        assert!(sync_all_skills_in(&sources).expect("sync all").is_empty());
+    }
+
+    fn library_sources(tmp: &TempDir) -> Vec<SkillSourceDir> {
+        let mut sources = home_sources(tmp);
+        sources.push(SkillSourceDir {
+            id: SKILL_LIBRARY_SOURCE.to_string(),
+            path: tmp.path().join("skill_library").display().to_string(),
+        });
+        sources
+    }
+
+    #[test]
+    fn library_skills_are_not_synced_into_global_dirs() {
+        let tmp = tmp();
+        let sources = library_sources(&tmp);
+        make_skill(&tmp.path().join("skill_library"), "kept", "# Kept");
+
+        assert_eq!(skill_scope_in(&sources, "kept").unwrap(), Some("library"));
+        sync_skill_in(&sources, "kept").expect("sync");
+        assert!(sync_all_skills_in(&sources).expect("sync all").is_empty());
+        assert!(!tmp.path().join(".agents/skills/kept").exists());
+    }
+
+    #[test]
+    fn skills_move_between_library_and_global_dirs() {
+        let tmp = tmp();
+        let sources = library_sources(&tmp);
+        let agents = tmp.path().join(".agents/skills");
+        let claude = tmp.path().join(".claude/skills");
+        make_skill(&agents, "move", "# Move");
+        make_skill(&claude, "move", "# Move");
+        fs::create_dir_all(agents.join("move/scripts")).unwrap();
+        fs::write(agents.join("move/scripts/run.sh"), "echo").unwrap();
+
+        demote_skill_in(&sources, "move").expect("demote");
+        assert!(!agents.join("move").exists());
+        assert!(!claude.join("move").exists());
+        let library = tmp.path().join("skill_library/move");
+        assert!(library.join("scripts/run.sh").exists());
+        assert_eq!(skill_scope_in(&sources, "move").unwrap(), Some("library"));
+        assert!(demote_skill_in(&sources, "move").is_err());
+
+        promote_skill_in(&sources, "move").expect("promote");
+        assert!(!library.exists());
+        assert_eq!(
+            fs::read_to_string(agents.join("move/SKILL.md")).unwrap(),
+            "# Move"
+        );
+        assert_eq!(skill_scope_in(&sources, "move").unwrap(), Some("global"));
+        assert!(promote_skill_in(&sources, "move").is_err());
+    }
+
     // ── list_skill_resources ──────────────────────────────────────────────────
 
     #[test]
@@ -1307,35 +1865,117 @@ mod tests {
         fs::create_dir_all(&scripts).expect("create scripts");
         fs::write(scripts.join("run.sh"), "#!/bin/bash").expect("write script");
 
-        // Test the resource discovery logic directly using the types.
-        let root_files: Vec<String> = {
-            let mut files = Vec::new();
-            if let Ok(entries) = fs::read_dir(&skill_dir) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    let p = entry.path();
-                    let name = p.file_name().unwrap().to_str().unwrap().to_string();
-                    if p.is_file() && name != "SKILL.md" {
-                        files.push(name);
-                    }
-                }
-            }
-            files.sort();
-            files
-        };
+        fs::create_dir_all(skill_dir.join("docs")).expect("create empty docs");
+
+        let resources = resources_in(&skill_dir);
+
+        let root_files: Vec<&str> = resources
+            .root_files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
         assert_eq!(root_files, vec!["notes.txt"]);
+        let dirs: Vec<(&str, Vec<&str>)> = resources
+            .dirs
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.files.iter().map(|f| f.path.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(dirs, vec![("docs", vec![]), ("scripts", vec!["run.sh"])]);
+    }
 
-        let script_files: Vec<String> = {
-            let mut files = Vec::new();
-            if let Ok(entries) = fs::read_dir(&scripts) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    if let Some(name) = entry.path().file_name().and_then(|n| n.to_str()) {
-                        files.push(name.to_string());
-                    }
-                }
-            }
-            files
-        };
-        assert!(script_files.contains(&"run.sh".to_string()));
+    // ── save_skill ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn saved_skill_is_listed_and_synced_into_projects() {
+        let tmp = tmp();
+        let agents_dir = tmp.path().join("skills");
+        let project_dir = tmp.path().join("project");
+        fs::create_dir_all(&project_dir).unwrap();
+        let content = "---\nname: refined\ndescription: Learned in a session\n---\nBody\n";
+
+        assert!(save_skill_in(&agents_dir, "../escape", content).is_err());
+        save_skill_in(&agents_dir, "refined", content).expect("save");
+
+        let sources = vec![SkillSourceDir {
+            id: "agents".into(),
+            path: agents_dir.display().to_string(),
+        }];
+        let listed = scan_skill_sources(&sources).unwrap();
+        assert_eq!(listed.get("refined"), Some(&vec!["agents".to_string()]));
+
+        let saved = fs::read_to_string(agents_dir.join("refined").join("SKILL.md")).unwrap();
+        let claude = crate::agent::from_id("claude").unwrap();
+        claude
+            .sync_skills(
+                &project_dir,
+                &[("refined".to_string(), saved)],
+                &["refined".to_string()],
+                &[],
+            )
+            .expect("sync");
+        assert_eq!(
+            fs::read_to_string(project_dir.join(".claude/skills/refined/SKILL.md")).unwrap(),
+            content
+        );
+    }
+
+    // ── companion file editing ────────────────────────────────────────────────
+
+    #[test]
+    fn resources_can_be_written_read_and_deleted() {
+        let tmp = tmp();
+        let skill_dir = tmp.path().join("my-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Skill").unwrap();
+
+        save_resource_in(&skill_dir, "scripts/init.py", "print('hi')\n").expect("save");
+        assert_eq!(
+            read_resource_in(&skill_dir, "scripts/init.py").unwrap(),
+            "print('hi')\n"
+        );
+        save_resource_in(&skill_dir, "scripts/init.py", "print('bye')\n").expect("overwrite");
+        assert_eq!(
+            fs::read_to_string(skill_dir.join("scripts/init.py")).unwrap(),
+            "print('bye')\n"
+        );
+
+        delete_resource_in(&skill_dir, "scripts/init.py").expect("delete");
+        assert!(!skill_dir.join("scripts").exists(), "empty dir removed");
+        assert!(skill_dir.join("SKILL.md").exists());
+        assert!(read_resource_in(&skill_dir, "scripts/init.py").is_err());
+    }
+
+    #[test]
+    fn resource_paths_cannot_leave_the_skill() {
+        let tmp = tmp();
+        let skill_dir = tmp.path().join("my-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(tmp.path().join("secret.txt"), "secret").unwrap();
+
+        for bad in [
+            "",
+            "../secret.txt",
+            "scripts/../../secret.txt",
+            "./x",
+            "/etc/passwd",
+        ] {
+            assert!(resource_path(&skill_dir, bad).is_err(), "{:?}", bad);
+        }
+        assert!(save_resource_in(&skill_dir, "SKILL.md", "x").is_err());
+        assert!(delete_resource_in(&skill_dir, "SKILL.md").is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(tmp.path(), skill_dir.join("escape")).unwrap();
+            assert!(read_resource_in(&skill_dir, "escape/secret.txt").is_err());
+            assert!(save_resource_in(&skill_dir, "escape/new.txt", "x").is_err());
+            assert!(!tmp.path().join("new.txt").exists());
+        }
     }
 
     // ── is_valid_name (path safety) ───────────────────────────────────────────
@@ -1354,32 +1994,4 @@ mod tests {
         assert!(is_valid_name("skill_with_underscore"));
         assert!(is_valid_name("SkillWithCaps"));
     }
-
-    // ── format_skill_with_companions ─────────────────────────────────────────
-
-    #[test]
-    fn format_skill_with_companions_appends_section() {
-        let companions = vec![
-            CompanionFile {
-                relative_path: "scripts".to_string(),
-                is_dir: true,
-            },
-            CompanionFile {
-                relative_path: "scripts/run.sh".to_string(),
-                is_dir: false,
-            },
-        ];
-        let result = format_skill_with_companions("# My Skill", &companions);
-        assert!(result.contains("## Companion Resources"));
-        assert!(result.contains("### scripts"));
-        assert!(result.contains("run.sh"));
-    }
-
-    #[test]
-    fn format_skill_with_no_companions_returns_original() {
-        let result = format_skill_with_companions("# My Skill", &[]);
-        // Empty companions list → still appends the section header.
-        // What matters is that the original content is preserved.
-        assert!(result.starts_with("# My Skill"));
-    }
 }
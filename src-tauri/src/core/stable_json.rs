@@ -0,0 +1,131 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::Path;
+
+use super::strip_config_metadata;
+
+// ── Deterministic JSON output ────────────────────────────────────────────────
+//
+// Every JSON file Automatic generates (agent MCP configs, project.json,
+// registry entries) goes through `to_stable_json` so the same data always
+// produces the same bytes: keys sorted at every depth, two-space indentation
+// and a trailing newline.  `HashMap` fields are the main source of churn —
+// their iteration order differs between runs — so values are converted to a
+// `serde_json::Value` and re-ordered before printing.
+
+/// Serialise `value` as pretty JSON with recursively sorted keys and a
+/// trailing newline.
+pub fn to_stable_json<T: Serialize + ?Sized>(value: &T) -> Result<String, String> {
+    let value = serde_json::to_value(value).map_err(|e| format!("JSON error: {}", e))?;
+    let mut out = serde_json::to_string_pretty(&sort_json_keys(value))
+        .map_err(|e| format!("JSON error: {}", e))?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// Return `value` with the keys of every nested object in sorted order.
+/// Array order is significant and left unchanged.
+pub fn sort_json_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut sorted = Map::new();
+            for (key, val) in entries {
+                sorted.insert(key, sort_json_keys(val));
+            }
+            Value::Object(sorted)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_json_keys).collect()),
+        other => other,
+    }
+}
+
+/// Compare generated and on-disk file content for drift detection.
+///
+/// `.json` files are parsed and compared as values, so key order and
+/// whitespace differences (e.g. an agent re-saving its config) are not drift.
+/// Everything else — and JSON that fails to parse — is compared byte-for-byte.
+/// The `"$automatic"` provenance key is ignored on both sides.
+pub fn generated_content_matches(path: &Path, expected: &str, actual: &str) -> bool {
+    if expected == actual {
+        return true;
+    }
+    if path.extension().and_then(|e| e.to_str()) != Some("json") {
+        return false;
+    }
+    match (
+        serde_json::from_str::<Value>(expected),
+        serde_json::from_str::<Value>(actual),
+    ) {
+        (Ok(mut a), Ok(mut b)) => {
+            strip_config_metadata(&mut a);
+            strip_config_metadata(&mut b);
+            a == b
+        }
+        _ => false,
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn stable_json_is_identical_for_any_hashmap_order() {
+        let mut rules: HashMap<String, Vec<String>> = HashMap::new();
+        for key in ["zeta", "alpha", "GEMINI.md", "_project", "mid"] {
+            rules.insert(key.to_string(), vec![key.to_string()]);
+        }
+        let mut reinserted: HashMap<String, Vec<String>> = HashMap::new();
+        for (k, v) in rules.iter().collect::<Vec<_>>().into_iter().rev() {
+            reinserted.insert(k.clone(), v.clone());
+        }
+
+        let a = to_stable_json(&rules).unwrap();
+        let b = to_stable_json(&reinserted).unwrap();
+        assert_eq!(a, b);
+        assert!(a.ends_with("}\n"));
+        assert!(a.find("\"GEMINI.md\"").unwrap() < a.find("\"zeta\"").unwrap());
+        assert!(a.contains("\n  \"_project\": ["));
+    }
+
+    #[test]
+    fn json_content_ignores_key_order_and_whitespace() {
+        let path = Path::new(".mcp.json");
+        let expected =
+            to_stable_json(&json!({"mcpServers": {"a": {"command": "x", "args": []}}})).unwrap();
+        let reordered = r#"{"mcpServers":{"a":{"args":[],"command":"x"}}}"#;
+        assert!(generated_content_matches(path, &expected, reordered));
+
+        let changed = r#"{"mcpServers":{"a":{"args":["y"],"command":"x"}}}"#;
+        assert!(!generated_content_matches(path, &expected, changed));
+    }
+
+    #[test]
+    fn non_json_content_is_compared_byte_for_byte() {
+        let path = Path::new("config.toml");
+        assert!(generated_content_matches(path, "a = 1\n", "a = 1\n"));
+        assert!(!generated_content_matches(path, "a = 1\n", "a = 1"));
+        // Invalid JSON under a .json name also falls back to bytes.
+        assert!(!generated_content_matches(Path::new("x.json"), "{", "{ "));
+    }
+
+    #[test]
+    fn provenance_annotations_are_not_drift() {
+        let path = Path::new(".cursor/mcp.json");
+        let expected = to_stable_json(&json!({"mcpServers": {"a": {"command": "x"}}})).unwrap();
+        let mut metadata = crate::core::config_metadata("web");
+        metadata["app_version"] = json!("0.1.0");
+        let annotated = json!({"$automatic": metadata, "mcpServers": {"a": {"command": "x"}}});
+        assert!(generated_content_matches(
+            path,
+            &expected,
+            &annotated.to_string()
+        ));
+    }
+}
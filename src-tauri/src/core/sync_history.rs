@@ -0,0 +1,273 @@
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::fsutil::{with_file_lock, write_atomic};
+use super::paths::{get_automatic_dir, is_valid_name};
+use super::projects::project_storage_key;
+use super::types::Project;
+
+// ── Sync History (~/.automatic/history/<project>.jsonl) ──────────────────────
+//
+// An append-only record of what Automatic wrote into each project: every
+// sync, agent removal and rules re-injection, with what triggered it and the
+// paths written or removed.  One JSON object per line, oldest first.  A file
+// that grows past `MAX_HISTORY_BYTES` is cut back to its newest entries.
+//
+// The trigger is not passed down through the sync engine.  Callers that
+// are not acting on a direct UI request open a `SyncHistoryScope` for the
+// duration of the call, and entries recorded on that thread pick it up.
+
+/// History files past this size are cut back to their newest entries
+/// totalling at most half of it.
+const MAX_HISTORY_BYTES: u64 = 512 * 1024;
+
+/// Entries returned by [`read_sync_history`] when no limit is given.
+pub const DEFAULT_SYNC_HISTORY_LIMIT: usize = 50;
+
+/// What caused Automatic to write into a project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncTrigger {
+    /// An explicit action in the app.
+    #[default]
+    Ui,
+    /// A tool call to the Automatic MCP server.
+    Mcp,
+    /// A skill, rule or MCP server the project uses was edited, renamed or
+    /// deleted in the registry.
+    RegistryChange,
+}
+
+/// The kind of write recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAction {
+    /// A full sync of the project's agent configs.
+    Sync,
+    /// An agent's files were removed from the project.
+    RemoveAgent,
+    /// Rules sections were rewritten in place, outside a full sync.
+    RulesInjection,
+}
+
+/// One line of a project's sync history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncHistoryEntry {
+    pub id: String,
+    /// RFC 3339 time of the write.
+    pub timestamp: String,
+    pub trigger: SyncTrigger,
+    pub action: SyncAction,
+    /// The agent removed, for [`SyncAction::RemoveAgent`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(default)]
+    pub written: Vec<String>,
+    #[serde(default)]
+    pub removed: Vec<String>,
+}
+
+thread_local! {
+    static CURRENT_TRIGGER: Cell<SyncTrigger> = const { Cell::new(SyncTrigger::Ui) };
+    static LAST_ENTRY_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Sets the trigger recorded for writes on this thread until dropped.
+pub struct SyncHistoryScope {
+    previous: SyncTrigger,
+}
+
+impl SyncHistoryScope {
+    /// Id of the last entry recorded on this thread since the scope opened.
+    pub fn last_entry_id(&self) -> Option<String> {
+        LAST_ENTRY_ID.with(|id| id.borrow().clone())
+    }
+}
+
+impl Drop for SyncHistoryScope {
+    fn drop(&mut self) {
+        CURRENT_TRIGGER.with(|t| t.set(self.previous));
+    }
+}
+
+/// Record writes on this thread as caused by `trigger` until the returned
+/// scope is dropped.  Scopes nest.
+pub fn sync_history_scope(trigger: SyncTrigger) -> SyncHistoryScope {
+    LAST_ENTRY_ID.with(|id| *id.borrow_mut() = None);
+    SyncHistoryScope {
+        previous: CURRENT_TRIGGER.with(|t| t.replace(trigger)),
+    }
+}
+
+fn history_dir() -> Result<PathBuf, String> {
+    Ok(get_automatic_dir()?.join("history"))
+}
+
+/// Append an entry for `project` and return its id.  Nothing is recorded
+/// when nothing was written or removed.
+pub fn record_sync_history(
+    project: &Project,
+    action: SyncAction,
+    agent: Option<&str>,
+    written: &[String],
+    removed: &[String],
+) -> Result<Option<String>, String> {
+    if written.is_empty() && removed.is_empty() {
+        return Ok(None);
+    }
+    let entry = SyncHistoryEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        trigger: CURRENT_TRIGGER.with(Cell::get),
+        action,
+        agent: agent.map(str::to_string),
+        written: written.to_vec(),
+        removed: removed.to_vec(),
+    };
+    let path = history_path(&history_dir()?, &project_storage_key(project))?;
+    append_entry(&path, &entry, MAX_HISTORY_BYTES)?;
+    LAST_ENTRY_ID.with(|id| *id.borrow_mut() = Some(entry.id.clone()));
+    Ok(Some(entry.id))
+}
+
+/// [`record_sync_history`], logging failures instead of returning them:
+/// a history that cannot be written never fails the write it describes.
+pub fn log_sync_history(
+    project: &Project,
+    action: SyncAction,
+    agent: Option<&str>,
+    written: &[String],
+    removed: &[String],
+) {
+    if let Err(e) = record_sync_history(project, action, agent, written, removed) {
+        tracing::error!(
+            "Failed to record sync history for '{}': {}",
+            project.name,
+            e
+        );
+    }
+}
+
+/// The newest `limit` history entries of `project`, newest first.  Lines
+/// that cannot be parsed are skipped.
+pub fn read_sync_history(project: &str, limit: usize) -> Result<Vec<SyncHistoryEntry>, String> {
+    read_entries(&history_path(&history_dir()?, project)?, limit)
+}
+
+fn history_path(dir: &Path, project: &str) -> Result<PathBuf, String> {
+    if !is_valid_name(project) {
+        return Err("Invalid project name".into());
+    }
+    Ok(dir.join(format!("{}.jsonl", project)))
+}
+
+fn append_entry(path: &Path, entry: &SyncHistoryEntry, max_bytes: u64) -> Result<(), String> {
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    with_file_lock(path, || {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+        writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+        let len = file.metadata().map_err(|e| e.to_string())?.len();
+        drop(file);
+        if len > max_bytes {
+            truncate_history(path, (max_bytes / 2) as usize)?;
+        }
+        Ok(())
+    })
+}
+
+/// Keep only the newest lines of `path` that fit within `keep_bytes`.
+fn truncate_history(path: &Path, keep_bytes: usize) -> Result<(), String> {
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut kept: Vec<&str> = Vec::new();
+    let mut size = 0;
+    for line in raw.lines().rev() {
+        size += line.len() + 1;
+        if size > keep_bytes {
+            break;
+        }
+        kept.push(line);
+    }
+    kept.reverse();
+    let mut contents = kept.join("\n");
+    if !contents.is_empty() {
+        contents.push('\n');
+    }
+    write_atomic(path, contents)
+}
+
+fn read_entries(path: &Path, limit: usize) -> Result<Vec<SyncHistoryEntry>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    Ok(raw
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit)
+        .collect())
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> SyncHistoryEntry {
+        SyncHistoryEntry {
+            id: id.to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            trigger: SyncTrigger::RegistryChange,
+            action: SyncAction::Sync,
+            agent: None,
+            written: vec!["/work/app/.mcp.json".to_string()],
+            removed: vec![],
+        }
+    }
+
+    #[test]
+    fn entries_are_read_newest_first_and_truncated_past_the_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = history_path(dir.path(), "web").unwrap();
+        let line_len = serde_json::to_string(&entry("e00")).unwrap().len() as u64 + 1;
+
+        for i in 0..10 {
+            append_entry(&path, &entry(&format!("e{:02}", i)), line_len * 6).unwrap();
+        }
+
+        // The seventh append crossed the cap and kept the newest three lines.
+        let ids: Vec<String> = read_entries(&path, 100)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, ["e09", "e08", "e07", "e06", "e05", "e04"]);
+        assert_eq!(read_entries(&path, 2).unwrap()[1].id, "e08");
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains(r#""trigger":"registry-change""#));
+        assert!(history_path(dir.path(), "../web").is_err());
+    }
+
+    #[test]
+    fn scopes_set_the_trigger_and_restore_it() {
+        assert_eq!(CURRENT_TRIGGER.with(Cell::get), SyncTrigger::Ui);
+        {
+            let _outer = sync_history_scope(SyncTrigger::Mcp);
+            {
+                let _inner = sync_history_scope(SyncTrigger::RegistryChange);
+                assert_eq!(CURRENT_TRIGGER.with(Cell::get), SyncTrigger::RegistryChange);
+            }
+            assert_eq!(CURRENT_TRIGGER.with(Cell::get), SyncTrigger::Mcp);
+        }
+        assert_eq!(CURRENT_TRIGGER.with(Cell::get), SyncTrigger::Ui);
+    }
+}
@@ -0,0 +1,564 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::agent;
+
+use super::*;
+
+// ── Applying Project Templates ───────────────────────────────────────────────
+//
+// Merges a saved project template into an existing project and reports what
+// actually changed, so the user can decide whether to sync straight away or
+// review first.  Applying never syncs agent configs itself.
+//
+// In `replace` mode the project's skills and MCP servers become exactly the
+// template's and template files overwrite existing ones.  Agents are always
+// merged: dropping one needs the cleanup `remove_agent_from_project` does.
+
+/// How [`apply_project_template`] treats what the project already has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateApplyMode {
+    /// Add the template's items; keep everything else.
+    #[default]
+    Merge,
+    /// Drop skills and MCP servers the template does not name and overwrite
+    /// existing project files with the template's.
+    Replace,
+}
+
+/// What applying a template to a project changed.
+#[derive(Debug, Default, Serialize)]
+pub struct TemplateApplyReport {
+    pub template: String,
+    pub project: String,
+    pub added_agents: Vec<String>,
+    pub existing_agents: Vec<String>,
+    pub added_skills: Vec<String>,
+    pub existing_skills: Vec<String>,
+    pub added_servers: Vec<String>,
+    pub existing_servers: Vec<String>,
+    /// Skills and MCP servers dropped in replace mode.
+    pub removed_skills: Vec<String>,
+    pub removed_servers: Vec<String>,
+    /// Rules newly attached to the project's instruction files.
+    pub attached_rules: Vec<String>,
+    /// Files written to the project directory.
+    pub written_files: Vec<String>,
+    /// Template project files left alone because they already existed.
+    pub skipped_files: Vec<String>,
+    /// Skills the template names that were not installed and have been
+    /// installed from the app bundle.
+    pub installed_skills: Vec<String>,
+    /// Skills the template names that are neither installed nor bundled with
+    /// the app.
+    pub unresolved_skills: Vec<String>,
+    /// MCP servers the template names that have no local config.
+    pub unresolved_servers: Vec<String>,
+    /// Rules the template names that are neither installed nor bundled.
+    pub unresolved_rules: Vec<String>,
+    pub warnings: Vec<String>,
+    /// Files written by the sync that followed, when one was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synced_files: Option<Vec<String>>,
+}
+
+impl TemplateApplyReport {
+    /// One-line summary used for the project activity log.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        for (count, label) in [
+            (self.added_agents.len(), "agent"),
+            (self.added_skills.len(), "skill"),
+            (self.added_servers.len(), "MCP server"),
+            (self.attached_rules.len(), "rule"),
+            (self.written_files.len(), "file"),
+        ] {
+            if count > 0 {
+                parts.push(format!(
+                    "{} {}{}",
+                    count,
+                    label,
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+        }
+        let unresolved = self.unresolved_skills.len()
+            + self.unresolved_servers.len()
+            + self.unresolved_rules.len();
+        let mut out = if parts.is_empty() {
+            format!("{}: nothing new", self.template)
+        } else {
+            format!("{}: added {}", self.template, parts.join(", "))
+        };
+        let removed = self.removed_skills.len() + self.removed_servers.len();
+        if removed > 0 {
+            out.push_str(&format!(", removed {}", removed));
+        }
+        if unresolved > 0 {
+            out.push_str(&format!(" ({} unresolved)", unresolved));
+        }
+        out
+    }
+}
+
+/// Skills, MCP servers and rules available locally.
+#[derive(Debug, Default)]
+pub(crate) struct TemplateInventory {
+    /// Installed skills.
+    pub skills: HashSet<String>,
+    /// Skills shipped with the app, installed or not.
+    pub bundled_skills: HashSet<String>,
+    pub servers: HashSet<String>,
+    pub rules: HashSet<String>,
+}
+
+impl TemplateInventory {
+    fn load() -> Self {
+        let mut rules: HashSet<String> = list_rules()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        rules.extend(bundled_rule_names().into_iter().map(String::from));
+        Self {
+            skills: list_skill_names().unwrap_or_default().into_iter().collect(),
+            bundled_skills: bundled_skill_names()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            servers: list_mcp_server_configs()
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            rules,
+        }
+    }
+}
+
+/// Apply the saved project template `template_name` to `project`: merge its
+/// agents, skills, MCP servers and rules, install the bundled skills it
+/// needs, persist the project, then write the unified instruction and the
+/// template project files (only missing ones, unless `mode` is replace).
+/// Agent configs are not synced.
+pub fn apply_project_template(
+    template_name: &str,
+    project: &mut Project,
+    mode: TemplateApplyMode,
+) -> Result<TemplateApplyReport, String> {
+    project.ensure_unlocked()?;
+    let raw = read_project_template(template_name)?;
+    let template: ProjectTemplate =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid template: {}", e))?;
+    let subject = format!("Template '{}'", template_name);
+    match version_compat(&subject, template.min_app_version.as_deref()) {
+        VersionCompat::UpdateRequired { message, .. } => return Err(message),
+        VersionCompat::Newer { message, .. } => eprintln!("[automatic] {}", message),
+        VersionCompat::Compatible => {}
+    }
+
+    let mut report = merge_template(project, &template, &TemplateInventory::load(), mode);
+    if !report.installed_skills.is_empty() {
+        if let Err(e) = install_skills_from_bundle(&report.installed_skills) {
+            report
+                .warnings
+                .push(format!("Failed to install bundled skills: {}", e));
+            report
+                .unresolved_skills
+                .append(&mut report.installed_skills);
+        }
+    }
+    if !report.added_servers.is_empty() {
+        report.warnings.extend(
+            project_mcp_budget_warnings(project)
+                .iter()
+                .map(McpBudgetWarning::message),
+        );
+    }
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    persist_project(project)?;
+
+    write_template_files(project, &template, mode, &mut report);
+    if !report.written_files.is_empty() {
+        let name = project.name.clone();
+        record_instruction_hashes(&name, project);
+    }
+    Ok(report)
+}
+
+/// Merge `template` into `project` in memory, classifying each item as newly
+/// added, already present or (in replace mode) removed.  No files are
+/// written and nothing is installed.
+pub(crate) fn merge_template(
+    project: &mut Project,
+    template: &ProjectTemplate,
+    inventory: &TemplateInventory,
+    mode: TemplateApplyMode,
+) -> TemplateApplyReport {
+    let mut report = TemplateApplyReport {
+        template: template.name.clone(),
+        project: project.name.clone(),
+        ..Default::default()
+    };
+
+    if project.description.is_empty() {
+        project.description = template.description.clone();
+    }
+
+    if mode == TemplateApplyMode::Replace {
+        for (items, wanted, removed) in [
+            (
+                &mut project.skills,
+                &template.skills,
+                &mut report.removed_skills,
+            ),
+            (
+                &mut project.mcp_servers,
+                &template.mcp_servers,
+                &mut report.removed_servers,
+            ),
+        ] {
+            removed.extend(items.iter().filter(|i| !wanted.contains(i)).cloned());
+            items.retain(|i| wanted.contains(i));
+        }
+    }
+
+    for agent_id in &template.agents {
+        if project.agents.contains(agent_id) {
+            report.existing_agents.push(agent_id.clone());
+        } else if agent::from_id(agent_id).is_none() {
+            report
+                .warnings
+                .push(format!("Unknown agent '{}' was skipped", agent_id));
+        } else {
+            project.agents.push(agent_id.clone());
+            report.added_agents.push(agent_id.clone());
+        }
+    }
+
+    for skill in &template.skills {
+        if project.skills.contains(skill) {
+            report.existing_skills.push(skill.clone());
+        } else {
+            project.skills.push(skill.clone());
+            report.added_skills.push(skill.clone());
+        }
+        if inventory.skills.contains(skill) || project.local_skills.contains(skill) {
+            continue;
+        }
+        if inventory.bundled_skills.contains(skill) {
+            report.installed_skills.push(skill.clone());
+        } else {
+            report.unresolved_skills.push(skill.clone());
+        }
+    }
+
+    for server in &template.mcp_servers {
+        if project.mcp_servers.contains(server) {
+            report.existing_servers.push(server.clone());
+        } else {
+            project.mcp_servers.push(server.clone());
+            report.added_servers.push(server.clone());
+        }
+        if !inventory.servers.contains(server) {
+            report.unresolved_servers.push(server.clone());
+        }
+    }
+
+    for (target, source) in [
+        (&mut project.providers, &template.providers),
+        (&mut project.user_agents, &template.user_agents),
+        (&mut project.user_commands, &template.user_commands),
+    ] {
+        for item in source {
+            if !target.contains(item) {
+                target.push(item.clone());
+            }
+        }
+    }
+
+    let has_unified =
+        !template.unified_instruction.trim().is_empty() || !template.unified_rules.is_empty();
+    if has_unified {
+        project.instruction_mode = "unified".to_string();
+    }
+    if !template.unified_rules.is_empty() {
+        // `_project` is the key the Rules tab and sync engine read.
+        let rules = project
+            .file_rules
+            .entry("_project".to_string())
+            .or_default();
+        for rule in &template.unified_rules {
+            if !rules.contains(rule) {
+                rules.push(rule.clone());
+                report.attached_rules.push(rule.clone());
+            }
+            if !inventory.rules.contains(rule) {
+                report.unresolved_rules.push(rule.clone());
+            }
+        }
+    }
+
+    report
+}
+
+/// Write the template's unified instruction and project files.  In merge
+/// mode project files that already exist are skipped, never overwritten.
+fn write_template_files(
+    project: &Project,
+    template: &ProjectTemplate,
+    mode: TemplateApplyMode,
+    report: &mut TemplateApplyReport,
+) {
+    if project.directory.is_empty() {
+        if !template.project_files.is_empty() || !template.unified_instruction.is_empty() {
+            report
+                .warnings
+                .push("Project has no directory; template files were not written".into());
+        }
+        return;
+    }
+    let dir = PathBuf::from(&project.directory);
+
+    // Rules-only templates still write the unified files so the rules
+    // section is rendered.
+    let has_unified =
+        !template.unified_instruction.trim().is_empty() || !template.unified_rules.is_empty();
+    if has_unified {
+        match save_project_file_for_project(project, "_unified", &template.unified_instruction) {
+            Ok(()) => {
+                for agent_instance in project.agents.iter().filter_map(|id| agent::from_id(id)) {
+                    let filename = agent_instance.project_file_name().to_string();
+                    if !report.written_files.contains(&filename) {
+                        report.written_files.push(filename);
+                    }
+                }
+            }
+            Err(e) => report
+                .warnings
+                .push(format!("Failed to write unified instructions: {}", e)),
+        }
+    }
+
+    for file in &template.project_files {
+        if file.filename.is_empty() || file.content.is_empty() {
+            continue;
+        }
+        let exists = dir.join(&file.filename).exists();
+        if (exists && mode == TemplateApplyMode::Merge)
+            || report.written_files.contains(&file.filename)
+        {
+            report.skipped_files.push(file.filename.clone());
+            continue;
+        }
+        match save_project_file_for_project(project, &file.filename, &file.content) {
+            Ok(()) => report.written_files.push(file.filename.clone()),
+            Err(e) => report
+                .warnings
+                .push(format!("Failed to write '{}': {}", file.filename, e)),
+        }
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn existing_project() -> Project {
+        Project {
+            name: "web".into(),
+            description: "Existing app".into(),
+            agents: vec!["claude".into()],
+            skills: vec!["testing".into()],
+            local_skills: vec!["house-style".into()],
+            mcp_servers: vec!["github".into()],
+            file_rules: [(
+                "_project".to_string(),
+                vec!["automatic-general".to_string()],
+            )]
+            .into_iter()
+            .collect(),
+            instruction_mode: "per-agent".into(),
+            ..Default::default()
+        }
+    }
+
+    fn template() -> ProjectTemplate {
+        ProjectTemplate {
+            name: "react-app".into(),
+            description: "React starter".into(),
+            agents: vec!["claude".into(), "codex".into(), "not-an-agent".into()],
+            skills: vec![
+                "testing".into(),
+                "react".into(),
+                "house-style".into(),
+                "ghost".into(),
+            ],
+            mcp_servers: vec!["github".into(), "figma".into()],
+            unified_rules: vec![
+                "automatic-general".into(),
+                "frontend".into(),
+                "missing".into(),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn inventory() -> TemplateInventory {
+        TemplateInventory {
+            skills: ["testing"].iter().map(|s| s.to_string()).collect(),
+            bundled_skills: ["react"].iter().map(|s| s.to_string()).collect(),
+            servers: ["github"].iter().map(|s| s.to_string()).collect(),
+            rules: ["automatic-general", "frontend"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn partial_overlap_is_classified_per_item() {
+        let mut project = existing_project();
+        let report = merge_template(
+            &mut project,
+            &template(),
+            &inventory(),
+            TemplateApplyMode::Merge,
+        );
+
+        assert_eq!(report.added_agents, vec!["codex"]);
+        assert_eq!(report.existing_agents, vec!["claude"]);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("not-an-agent"));
+
+        assert_eq!(report.added_skills, vec!["react", "house-style", "ghost"]);
+        assert_eq!(report.existing_skills, vec!["testing"]);
+        assert_eq!(report.added_servers, vec!["figma"]);
+        assert_eq!(report.existing_servers, vec!["github"]);
+        assert_eq!(report.attached_rules, vec!["frontend", "missing"]);
+
+        // Local project skills count as present; bundled ones get installed.
+        assert_eq!(report.installed_skills, vec!["react"]);
+        assert_eq!(report.unresolved_skills, vec!["ghost"]);
+        assert_eq!(report.unresolved_servers, vec!["figma"]);
+        assert_eq!(report.unresolved_rules, vec!["missing"]);
+
+        assert_eq!(project.agents, vec!["claude", "codex"]);
+        assert_eq!(project.description, "Existing app");
+        assert_eq!(project.instruction_mode, "unified");
+        assert_eq!(
+            project.file_rules["_project"],
+            vec!["automatic-general", "frontend", "missing"]
+        );
+    }
+
+    #[test]
+    fn reapplying_reports_everything_as_existing() {
+        let mut project = existing_project();
+        merge_template(
+            &mut project,
+            &template(),
+            &inventory(),
+            TemplateApplyMode::Merge,
+        );
+        let report = merge_template(
+            &mut project,
+            &template(),
+            &inventory(),
+            TemplateApplyMode::Merge,
+        );
+
+        assert!(report.added_agents.is_empty());
+        assert!(report.added_skills.is_empty());
+        assert!(report.added_servers.is_empty());
+        assert!(report.attached_rules.is_empty());
+        assert_eq!(report.existing_agents, vec!["claude", "codex"]);
+        assert_eq!(report.summary(), "react-app: nothing new (3 unresolved)");
+    }
+
+    #[test]
+    fn existing_project_files_are_skipped() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("AGENTS.md"), "# Ours\n").unwrap();
+        let project = Project {
+            name: "web".into(),
+            directory: dir.path().display().to_string(),
+            agents: vec!["codex".into()],
+            instruction_mode: "per-agent".into(),
+            unregistered: true,
+            ..Default::default()
+        };
+        let template = ProjectTemplate {
+            name: "react-app".into(),
+            project_files: vec![
+                TemplateProjectFile {
+                    filename: "AGENTS.md".into(),
+                    content: "# Template\n".into(),
+                },
+                TemplateProjectFile {
+                    filename: "CLAUDE.md".into(),
+                    content: "# Template\n".into(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut report = TemplateApplyReport::default();
+        write_template_files(&project, &template, TemplateApplyMode::Merge, &mut report);
+
+        assert_eq!(report.skipped_files, vec!["AGENTS.md"]);
+        assert_eq!(report.written_files, vec!["CLAUDE.md"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("AGENTS.md")).unwrap(),
+            "# Ours\n"
+        );
+        assert!(fs::read_to_string(dir.path().join("CLAUDE.md"))
+            .unwrap()
+            .contains("# Template"));
+
+        let mut report = TemplateApplyReport::default();
+        write_template_files(&project, &template, TemplateApplyMode::Replace, &mut report);
+
+        assert!(report.skipped_files.is_empty());
+        assert_eq!(report.written_files, vec!["AGENTS.md", "CLAUDE.md"]);
+        assert!(fs::read_to_string(dir.path().join("AGENTS.md"))
+            .unwrap()
+            .contains("# Template"));
+    }
+
+    #[test]
+    fn replace_drops_skills_and_servers_but_keeps_agents() {
+        let mut project = existing_project();
+        project.skills.push("legacy".into());
+        project.mcp_servers.push("jira".into());
+        let mut template = template();
+        template.agents = vec!["codex".into()];
+
+        let report = merge_template(
+            &mut project,
+            &template,
+            &inventory(),
+            TemplateApplyMode::Replace,
+        );
+
+        assert_eq!(report.removed_skills, vec!["legacy"]);
+        assert_eq!(report.removed_servers, vec!["jira"]);
+        assert_eq!(report.existing_skills, vec!["testing"]);
+        assert_eq!(
+            project.skills,
+            vec!["testing", "react", "house-style", "ghost"]
+        );
+        assert_eq!(project.mcp_servers, vec!["github", "figma"]);
+        assert_eq!(project.agents, vec!["claude", "codex"]);
+        assert!(
+            report.summary().contains(", removed 2 ("),
+            "{}",
+            report.summary()
+        );
+    }
+}
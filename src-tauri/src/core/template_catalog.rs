@@ -0,0 +1,350 @@
+use serde::Serialize;
+
+use crate::agent;
+
+use super::*;
+
+// ── Project Template Catalog ─────────────────────────────────────────────────
+//
+// One view over the user's saved project templates and the bundled
+// marketplace templates, used by the MCP server so agents can discover and
+// read templates when helping to set up a repo.  A saved template shadows a
+// bundled one with the same name.
+
+/// Where a catalog template comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateOrigin {
+    /// Saved in `~/.automatic/project_templates/`.
+    Local,
+    /// A marketplace template shipped with the app.
+    Bundled,
+}
+
+/// Summary of one template in the catalog.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateListing {
+    pub name: String,
+    pub source: TemplateOrigin,
+    pub description: String,
+    pub agents: Vec<String>,
+    pub skills: Vec<String>,
+    /// Marketplace category; saved templates have none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+/// List saved and bundled templates, saved ones first.  Unreadable entries
+/// are skipped.
+pub fn list_template_catalog() -> Result<Vec<TemplateListing>, String> {
+    let local: Vec<ProjectTemplate> = list_project_templates()?
+        .iter()
+        .filter_map(|name| read_project_template(name).ok())
+        .filter_map(|raw| serde_json::from_str(&raw).ok())
+        .collect();
+    Ok(merge_template_listings(local, bundled_templates()))
+}
+
+/// Full JSON of the template called `name`: the saved template when there is
+/// one, otherwise the bundled template.  Unknown names produce an error
+/// listing the available ones.
+pub fn read_catalog_template(name: &str) -> Result<(TemplateOrigin, String), String> {
+    let local = list_project_templates()?;
+    let bundled: Vec<String> = bundled_templates().into_iter().map(|t| t.name).collect();
+    match resolve_template_origin(name, &local, &bundled)? {
+        TemplateOrigin::Local => Ok((TemplateOrigin::Local, read_project_template(name)?)),
+        TemplateOrigin::Bundled => Ok((
+            TemplateOrigin::Bundled,
+            read_bundled_project_template(name)?,
+        )),
+    }
+}
+
+fn bundled_templates() -> Vec<BundledProjectTemplate> {
+    list_bundled_project_templates()
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Vec<serde_json::Value>>(&raw).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| serde_json::from_value(v).ok())
+        .collect()
+}
+
+fn merge_template_listings(
+    local: Vec<ProjectTemplate>,
+    bundled: Vec<BundledProjectTemplate>,
+) -> Vec<TemplateListing> {
+    let mut listings: Vec<TemplateListing> = local
+        .into_iter()
+        .map(|t| TemplateListing {
+            name: t.name,
+            source: TemplateOrigin::Local,
+            description: t.description,
+            agents: t.agents,
+            skills: t.skills,
+            category: None,
+        })
+        .collect();
+    for t in bundled {
+        if listings.iter().any(|l| l.name == t.name) {
+            continue;
+        }
+        listings.push(TemplateListing {
+            name: t.name,
+            source: TemplateOrigin::Bundled,
+            description: t.description,
+            agents: t.agents,
+            skills: t.skills,
+            category: Some(t.category),
+        });
+    }
+    listings
+}
+
+fn resolve_template_origin(
+    name: &str,
+    local: &[String],
+    bundled: &[String],
+) -> Result<TemplateOrigin, String> {
+    if local.iter().any(|n| n == name) {
+        return Ok(TemplateOrigin::Local);
+    }
+    if bundled.iter().any(|n| n == name) {
+        return Ok(TemplateOrigin::Bundled);
+    }
+
+    let mut available: Vec<&str> = local.iter().chain(bundled).map(String::as_str).collect();
+    available.sort_unstable();
+    available.dedup();
+    let list = if available.is_empty() {
+        "no templates available".to_string()
+    } else {
+        available.join(", ")
+    };
+    Err(format!(
+        "Unknown project template '{}'. Available templates are: {}. \
+         Call automatic_list_project_templates to confirm the correct name before retrying.",
+        name, list
+    ))
+}
+
+// ── Templates from projects ──────────────────────────────────────────────────
+
+/// A project saved as a template, with what could not be carried over.
+#[derive(Debug, Serialize)]
+pub struct SavedProjectTemplate {
+    #[serde(flatten)]
+    pub template: ProjectTemplate,
+    /// The project's local skills.  They live in the project directory, so
+    /// the template cannot share them.
+    pub skipped_local_skills: Vec<String>,
+}
+
+/// Snapshot the shareable parts of `project` as a template called `name`.
+/// Instruction files are captured with their user-authored content only;
+/// Automatic's managed sections are regenerated when the template is applied.
+/// Local skills are left out.
+pub fn project_template_from_project(project: &Project, name: &str) -> ProjectTemplate {
+    let mut template = ProjectTemplate {
+        name: name.to_string(),
+        description: project.description.clone(),
+        skills: project
+            .skills
+            .iter()
+            .filter(|s| !project.local_skills.contains(s))
+            .cloned()
+            .collect(),
+        mcp_servers: project.mcp_servers.clone(),
+        providers: project.providers.clone(),
+        agents: project.agents.clone(),
+        user_agents: project.user_agents.clone(),
+        user_commands: project.user_commands.clone(),
+        ..Default::default()
+    };
+
+    let mut filenames: Vec<&str> = Vec::new();
+    for agent_id in &project.agents {
+        if let Some(a) = agent::from_id(agent_id) {
+            if a.capabilities().instructions && !filenames.contains(&a.project_file_name()) {
+                filenames.push(a.project_file_name());
+            }
+        }
+    }
+    let contents: Vec<(String, String)> = filenames
+        .into_iter()
+        .filter_map(|f| {
+            let content = read_project_file(&project.directory, f).ok()?;
+            (!content.trim().is_empty()).then(|| (f.to_string(), content))
+        })
+        .collect();
+
+    if project.instruction_mode == "unified" {
+        template.unified_instruction = contents
+            .into_iter()
+            .next()
+            .map(|(_, content)| content)
+            .unwrap_or_default();
+        template.unified_rules = file_rules_for(project, "_unified");
+    } else {
+        template.project_files = contents
+            .into_iter()
+            .map(|(filename, content)| TemplateProjectFile { filename, content })
+            .collect();
+    }
+    template
+}
+
+/// Save `project` as the template `name`.  Refuses to replace an existing
+/// saved template unless `overwrite` is set.
+pub fn save_project_as_template(
+    project: &Project,
+    name: &str,
+    overwrite: bool,
+) -> Result<SavedProjectTemplate, String> {
+    if !is_valid_name(name) {
+        return Err(format!(
+            "Invalid template name '{}'. Use letters, numbers, '-' and '_' only.",
+            name
+        ));
+    }
+    if !overwrite && list_project_templates()?.iter().any(|n| n == name) {
+        return Err(format!(
+            "Project template '{}' already exists. Choose another name or set overwrite.",
+            name
+        ));
+    }
+    let template = project_template_from_project(project, name);
+    let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
+    save_project_template(name, &json)?;
+    Ok(SavedProjectTemplate {
+        template,
+        skipped_local_skills: project.local_skills.clone(),
+    })
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn bundled(name: &str) -> BundledProjectTemplate {
+        BundledProjectTemplate {
+            name: name.into(),
+            display_name: name.into(),
+            description: format!("bundled {}", name),
+            category: "web".into(),
+            tags: vec![],
+            skills: vec![],
+            mcp_servers: vec![],
+            providers: vec![],
+            agents: vec!["claude".into()],
+            project_files: vec![],
+            unified_instruction: String::new(),
+            unified_rules: vec![],
+            user_commands: vec![],
+            icon: None,
+            _author: None,
+            license: None,
+            skill_sources: Default::default(),
+            skill_urls: Default::default(),
+            min_app_version: None,
+        }
+    }
+
+    fn local(name: &str) -> ProjectTemplate {
+        ProjectTemplate {
+            name: name.into(),
+            description: format!("local {}", name),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn local_template_shadows_bundled_one_with_the_same_name() {
+        let listings = merge_template_listings(
+            vec![local("rust-cli-app")],
+            vec![bundled("rust-cli-app"), bundled("graphql-api")],
+        );
+        let names: Vec<(&str, TemplateOrigin)> = listings
+            .iter()
+            .map(|l| (l.name.as_str(), l.source))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("rust-cli-app", TemplateOrigin::Local),
+                ("graphql-api", TemplateOrigin::Bundled),
+            ]
+        );
+        assert_eq!(listings[0].description, "local rust-cli-app");
+        assert_eq!(listings[0].category, None);
+        assert_eq!(listings[1].category.as_deref(), Some("web"));
+    }
+
+    #[test]
+    fn name_resolution_prefers_local_then_bundled() {
+        let local = vec!["rust-cli-app".to_string(), "mine".to_string()];
+        let bundled = vec!["rust-cli-app".to_string(), "graphql-api".to_string()];
+        assert_eq!(
+            resolve_template_origin("rust-cli-app", &local, &bundled),
+            Ok(TemplateOrigin::Local)
+        );
+        assert_eq!(
+            resolve_template_origin("graphql-api", &local, &bundled),
+            Ok(TemplateOrigin::Bundled)
+        );
+
+        let err = resolve_template_origin("nope", &local, &bundled).unwrap_err();
+        assert!(
+            err.contains("Available templates are: graphql-api, mine, rust-cli-app."),
+            "{}",
+            err
+        );
+        let err = resolve_template_origin("nope", &[], &[]).unwrap_err();
+        assert!(err.contains("no templates available"), "{}", err);
+    }
+
+    #[test]
+    fn template_from_project_captures_instruction_files() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "# House rules\n").unwrap();
+        let mut project = Project {
+            name: "web".into(),
+            description: "Storefront".into(),
+            directory: dir.path().display().to_string(),
+            agents: vec!["claude".into()],
+            skills: vec!["react".into(), "house-style".into()],
+            local_skills: vec!["house-style".into()],
+            mcp_servers: vec!["github".into()],
+            instruction_mode: "per-agent".into(),
+            ..Default::default()
+        };
+
+        let template = project_template_from_project(&project, "storefront");
+        assert_eq!(template.name, "storefront");
+        assert_eq!(template.description, "Storefront");
+        assert_eq!(template.skills, vec!["react"]);
+        assert_eq!(template.mcp_servers, vec!["github"]);
+        assert_eq!(template.project_files.len(), 1);
+        assert_eq!(template.project_files[0].filename, "CLAUDE.md");
+        assert!(template.unified_instruction.is_empty());
+
+        project.instruction_mode = "unified".into();
+        project
+            .file_rules
+            .insert("_unified".into(), vec!["automatic-general".into()]);
+        let template = project_template_from_project(&project, "storefront");
+        assert!(template.project_files.is_empty());
+        assert!(template.unified_instruction.contains("House rules"));
+        assert_eq!(template.unified_rules, vec!["automatic-general"]);
+
+        // Project-level rules (what applying a template writes) win.
+        project
+            .file_rules
+            .insert("_project".into(), vec!["frontend".into()]);
+        let template = project_template_from_project(&project, "storefront");
+        assert_eq!(template.unified_rules, vec!["frontend"]);
+    }
+}
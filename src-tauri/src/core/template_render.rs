@@ -0,0 +1,184 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::templates::read_template;
+use super::types::Project;
+
+// ── Rendering Instruction Templates ──────────────────────────────────────────
+//
+// Instruction templates may contain `{{key}}` placeholders, filled in from a
+// context map when the template is inserted into a project file.  Spaces
+// inside the braces are ignored (`{{ key }}` works too).  Rendering is a
+// single pass: a value that itself contains `{{…}}` is inserted verbatim.
+//
+// Placeholders with no value in the context are left in the output exactly
+// as written and reported in `errors`, so a lenient caller can ignore them
+// and a strict one can refuse the result.
+//
+// Escaping: a backslash directly before `{{` produces a literal `{{` and is
+// itself dropped, so `\{{project_name}}` renders as `{{project_name}}`.
+// Closing braces need no escaping.
+
+/// Keys [`project_template_context`] always provides.
+pub const TEMPLATE_BUILTIN_KEYS: [&str; 5] =
+    ["project_name", "directory", "skills", "agents", "date"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenderedTemplate {
+    pub content: String,
+    /// Placeholders that could not be filled, in order of first appearance.
+    /// Empty when every placeholder had a value.
+    pub errors: Vec<String>,
+}
+
+/// Render the saved instruction template `name` with `context`.
+pub fn render_template(
+    name: &str,
+    context: &HashMap<String, String>,
+) -> Result<RenderedTemplate, String> {
+    Ok(render_template_content(&read_template(name)?, context))
+}
+
+/// Substitute the `{{key}}` placeholders of `template` from `context`.
+pub fn render_template_content(
+    template: &str,
+    context: &HashMap<String, String>,
+) -> RenderedTemplate {
+    let mut content = String::with_capacity(template.len());
+    let mut errors: Vec<String> = Vec::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find("{{") {
+        if rest[..open].ends_with('\\') {
+            content.push_str(&rest[..open - 1]);
+            content.push_str("{{");
+            rest = &rest[open + 2..];
+            continue;
+        }
+        content.push_str(&rest[..open]);
+
+        let inner = &rest[open + 2..];
+        let Some(close) = inner.find("}}") else {
+            push_error(&mut errors, "Unterminated placeholder '{{'".to_string());
+            content.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let key = inner[..close].trim();
+        match context.get(key) {
+            Some(value) => content.push_str(value),
+            None => {
+                push_error(
+                    &mut errors,
+                    format!("Unknown placeholder '{{{{{}}}}}'", key),
+                );
+                content.push_str(&rest[open..open + 2 + close + 2]);
+            }
+        }
+        rest = &inner[close + 2..];
+    }
+    content.push_str(rest);
+
+    RenderedTemplate { content, errors }
+}
+
+fn push_error(errors: &mut Vec<String>, error: String) {
+    if !errors.contains(&error) {
+        errors.push(error);
+    }
+}
+
+/// The built-in template context for `project`: its name, directory,
+/// agents and skills (comma-separated) and today's date (`YYYY-MM-DD`).
+/// `extra` keys are added on top and win over the built-in ones.
+pub fn project_template_context(
+    project: &Project,
+    extra: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut context = HashMap::from([
+        ("project_name".to_string(), project.name.clone()),
+        ("directory".to_string(), project.directory.clone()),
+        ("skills".to_string(), project.skills.join(", ")),
+        ("agents".to_string(), project.agents.join(", ")),
+        (
+            "date".to_string(),
+            chrono::Local::now().format("%Y-%m-%d").to_string(),
+        ),
+    ]);
+    context.extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+    context
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_known_keys_and_keeps_unknown_ones() {
+        let rendered = render_template_content(
+            "# {{project_name}}\nOwner: {{ owner }}\n{{missing}} and {{missing}}",
+            &context(&[("project_name", "web"), ("owner", "platform")]),
+        );
+        assert_eq!(
+            rendered.content,
+            "# web\nOwner: platform\n{{missing}} and {{missing}}"
+        );
+        assert_eq!(rendered.errors, vec!["Unknown placeholder '{{missing}}'"]);
+    }
+
+    #[test]
+    fn escaped_braces_render_literally() {
+        let rendered = render_template_content(
+            "Use \\{{project_name}} in templates; this is {{project_name}}.",
+            &context(&[("project_name", "web")]),
+        );
+        assert_eq!(
+            rendered.content,
+            "Use {{project_name}} in templates; this is web."
+        );
+        assert!(rendered.errors.is_empty());
+    }
+
+    #[test]
+    fn values_are_not_rendered_again() {
+        let rendered = render_template_content("{{a}}", &context(&[("a", "{{b}}"), ("b", "nope")]));
+        assert_eq!(rendered.content, "{{b}}");
+        assert!(rendered.errors.is_empty());
+    }
+
+    #[test]
+    fn unterminated_placeholder_is_kept_and_reported() {
+        let rendered = render_template_content("{{a}} then {{b", &context(&[("a", "x")]));
+        assert_eq!(rendered.content, "x then {{b");
+        assert_eq!(rendered.errors, vec!["Unterminated placeholder '{{'"]);
+    }
+
+    #[test]
+    fn project_context_has_builtins_and_extra_keys_win() {
+        let project = Project {
+            name: "web".into(),
+            directory: "/src/web".into(),
+            agents: vec!["claude".into(), "codex".into()],
+            skills: vec!["tdd".into()],
+            ..Default::default()
+        };
+        let ctx =
+            project_template_context(&project, &context(&[("team", "core"), ("skills", "none")]));
+        for key in TEMPLATE_BUILTIN_KEYS {
+            assert!(ctx.contains_key(key), "missing {}", key);
+        }
+        assert_eq!(ctx["agents"], "claude, codex");
+        assert_eq!(ctx["skills"], "none");
+        assert_eq!(ctx["team"], "core");
+        assert_eq!(ctx["date"].len(), 10);
+    }
+}
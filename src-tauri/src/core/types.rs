@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ── Data Structures ──────────────────────────────────────────────────────────
 
@@ -127,10 +127,28 @@ pub struct SkillSource {
     /// For bundled skills this is "automatic/automatic-app/<name>".
     pub id: String,
     /// "github" for registry-imported skills; "bundled" for skills shipped
-    /// with the Automatic app.  Defaults to "github" when absent so existing
-    /// registry entries are not broken.
+    /// with the Automatic app; "bundle" for skills installed from an offline
+    /// skill bundle; "url" for skills downloaded from a template's
+    /// `skill_urls`.  Defaults to "github" when absent so existing registry
+    /// entries are not broken.
     #[serde(default = "default_skill_source_kind")]
     pub kind: String,
+    /// When `true` the skill is held at its installed version and skipped by
+    /// the background update check.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// License recorded at import time: the SKILL.md `license:` frontmatter
+    /// field, or the first line of the source repo's LICENSE file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// SHA-256 of the SKILL.md content as installed, so local edits can be
+    /// told apart from upstream changes.  Absent for entries recorded before
+    /// it was tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_hash: Option<String>,
+    /// RFC 3339 time the skill was imported or last updated from upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_at: Option<String>,
 }
 
 fn default_skill_source_kind() -> String {
@@ -189,16 +207,50 @@ pub struct AgentOptions {
     /// Set to `false` to revert to the legacy inline-injection behaviour.
     #[serde(default = "default_true")]
     pub claude_rules_in_dot_claude: bool,
+
+    /// **Gemini CLI only.**  When `true`, MCP servers are written as a Gemini
+    /// CLI extension at `.gemini/extensions/automatic/` (manifest plus a
+    /// bundled context file) instead of being merged into
+    /// `.gemini/settings.json`.  Defaults to `false` for compatibility.
+    #[serde(default)]
+    pub gemini_use_extension: bool,
+
+    /// **Claude Code only.**  Scope each MCP server is installed at, keyed by
+    /// server name.  `local` servers are written to `~/.claude.json` under
+    /// the project path instead of the shared, checked-in `.mcp.json`.
+    /// Servers not listed use project scope.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub claude_mcp_scopes: HashMap<String, ClaudeMcpScope>,
+
+    /// **All agents.**  When `true`, the `automatic` MCP server entry written
+    /// for this agent runs `mcp-serve --read-only`, so the agent can read
+    /// configuration but not change it.  Defaults to `false` (full access).
+    #[serde(default)]
+    pub automatic_mcp_read_only: bool,
 }
 
 impl Default for AgentOptions {
     fn default() -> Self {
         Self {
             claude_rules_in_dot_claude: true,
+            gemini_use_extension: false,
+            claude_mcp_scopes: HashMap::new(),
+            automatic_mcp_read_only: false,
         }
     }
 }
 
+/// Where Claude Code reads an MCP server from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaudeMcpScope {
+    /// `.mcp.json` in the project directory, shared with everyone.
+    #[default]
+    Project,
+    /// `~/.claude.json`, private to this user and project.
+    Local,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -217,7 +269,11 @@ pub struct SkillEntry {
     /// True if the skill directory contains any files or subdirectories besides SKILL.md
     #[serde(default)]
     pub has_resources: bool,
-    /// License from the SKILL.md frontmatter `license:` field, if present.
+    /// Description from the SKILL.md frontmatter, when it parses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// License from the SKILL.md frontmatter `license:` field, falling back
+    /// to the license recorded in ~/.automatic/skills.json at import time.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
     /// When set, this skill is owned by a plugin and cannot be deleted.
@@ -227,6 +283,18 @@ pub struct SkillEntry {
     /// skill.json package name. Stored in ~/.automatic/skill-collections.json.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub collection: Option<String>,
+    /// True when the SKILL.md differs from the content recorded at import
+    /// (see [`SkillSource::installed_hash`]).
+    #[serde(default)]
+    pub locally_modified: bool,
+    /// `"global"` for skills in an agent-visible directory, `"library"` for
+    /// skills that only live in the skill library.
+    #[serde(default = "default_skill_scope")]
+    pub scope: String,
+}
+
+fn default_skill_scope() -> String {
+    "global".to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -238,6 +306,10 @@ pub struct Project {
     pub directory: String,
     #[serde(default)]
     pub skills: Vec<String>,
+    /// Skill sets applied to this project.  Their skills are added to
+    /// `skills` when the project is synced (see `selected_skill_names`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skill_sets: Vec<String>,
     /// Skills that exist only in the project directory, not in the global
     /// registry.  Discovered during autodetection but never auto-imported.
     #[serde(default)]
@@ -250,6 +322,11 @@ pub struct Project {
     pub providers: Vec<String>,
     #[serde(default)]
     pub agents: Vec<String>,
+    /// Agents the user removed and told Automatic never to manage again.
+    /// Autodetection skips them even when their files are present, and an
+    /// agent cannot be both here and in `agents`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_agents: Vec<String>,
     /// Tool names assigned to this project. Tool definitions live in
     /// `~/.automatic/tools/`. Populated by autodetection or manual addition.
     #[serde(default)]
@@ -270,8 +347,8 @@ pub struct Project {
     /// (e.g. "CLAUDE.md") to an ordered list of rule names whose content is
     /// appended below the user-authored content when the file is written.
     /// In unified mode the key `"_unified"` is used for all files.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub file_rules: HashMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_rules: BTreeMap<String, Vec<String>>,
     /// `"unified"` — one set of instructions written to all agent files.
     /// `"per-agent"` (default) — each agent file is edited independently.
     #[serde(default = "default_instruction_mode")]
@@ -290,6 +367,11 @@ pub struct Project {
     /// way as global rules, but are scoped to this project only.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub custom_rules: Vec<CustomRule>,
+    /// Rule text found in an instruction file's managed rules section that
+    /// matches no rule in the local registry, e.g. when adopting a directory
+    /// another machine synced.  Kept until the user imports or dismisses it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rule_candidates: Vec<RuleCandidate>,
     /// Workspace agent names selected for this project. These are written
     /// to the agent's sub-agent directory (e.g. `.claude/agents/`) on sync.
     /// Agent machine names reference files in `~/.automatic/agents/`.
@@ -315,6 +397,58 @@ pub struct Project {
     /// alongside global and local skills.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_skills: Option<Vec<CustomSkill>>,
+    /// Write-protects the project: sync, agent removal and repair operations
+    /// refuse to touch its files, and reactive syncs triggered by registry
+    /// edits skip it.  Read-only operations (drift checks, previews) still work.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+    /// Optional explanation shown with the lock (e.g. "release freeze").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_reason: Option<String>,
+    /// Agents each MCP server was discovered from, keyed by server name.
+    /// Recorded when autodetection or adding an agent brings a server into
+    /// the project.  Servers the user added by hand have no entry; they are
+    /// never removed along with an agent.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub mcp_server_origins: HashMap<String, Vec<String>>,
+    /// Project-specific changes to registry MCP server configs, keyed by
+    /// server name.  Each value is a JSON merge patch applied over the
+    /// registry config at sync time, so e.g. one project can point a server
+    /// at a different database without forking it.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub mcp_server_overrides: HashMap<String, serde_json::Value>,
+    /// Hash of the `SKILL.md` content Automatic last wrote for each global
+    /// skill.  Lets sync tell a stale copy it wrote itself (safe to update)
+    /// from one that was edited inside the project.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub skill_hashes: HashMap<String, String>,
+    /// Global skills whose project copy differs from the registry version.
+    /// Sync leaves these copies untouched until the user resolves them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skill_conflicts: Vec<SkillShadow>,
+    /// Global skills whose project copy is kept as-is.  Sync never
+    /// overwrites them with the registry version.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preserved_skills: Vec<String>,
+    /// Set when the project was loaded straight from its directory by
+    /// [`load_project_from_dir`](crate::core::load_project_from_dir) and has
+    /// no `~/.automatic/projects/` registry entry.  Never serialised; it makes
+    /// [`persist_project`](crate::core::persist_project) write only
+    /// `.automatic/project.json`.
+    #[serde(skip)]
+    pub unregistered: bool,
+    /// Whether the full config lives in `<directory>/.automatic/project.json`
+    /// (`None` or `Some(true)`, the default) or only in the home registry.
+    /// When `false` nothing is written under the project's `.automatic/`:
+    /// snapshots and notes move to `~/.automatic/projects-data/<name>/`.
+    /// Read through [`Project::stores_config_in_repo`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_config_in_repo: Option<bool>,
+    /// Oldest Automatic version that understands every setting in this
+    /// project.  Stamped on save; older builds treat the project as
+    /// read-only rather than dropping settings they do not know.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_app_version: Option<String>,
 
     // ── Resolved metadata (project portability) ─────────────────────────────
     //
@@ -357,11 +491,93 @@ pub struct Project {
 }
 
 impl Project {
+    /// Returns an error describing the lock when the project is locked.
+    /// Every operation that writes agent configs or instruction files calls
+    /// this first.
+    pub fn ensure_unlocked(&self) -> Result<(), String> {
+        super::check_project_writable(self)?;
+        if !self.locked {
+            return Ok(());
+        }
+        match self
+            .locked_reason
+            .as_deref()
+            .filter(|r| !r.trim().is_empty())
+        {
+            Some(reason) => Err(format!(
+                "Project '{}' is locked ({}). Unlock it before making changes.",
+                self.name,
+                reason.trim()
+            )),
+            None => Err(format!(
+                "Project '{}' is locked. Unlock it before making changes.",
+                self.name
+            )),
+        }
+    }
+
+    /// Reject a config that both selects and ignores the same agent.
+    pub fn validate_disabled_agents(&self) -> Result<(), String> {
+        match self
+            .agents
+            .iter()
+            .find(|a| self.disabled_agents.contains(a))
+        {
+            Some(agent) => Err(format!(
+                "Agent '{}' is ignored for project '{}'. Un-ignore it before adding it.",
+                agent, self.name
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Put the project in its canonical on-disk form so that saving an
+    /// unchanged project rewrites identical bytes.  Lists that are really
+    /// sets (skills, servers, agents, ...) are sorted and de-duplicated;
+    /// lists whose order the user chose — rules per file, custom items —
+    /// are left alone.  Map key order is handled by `to_stable_json`.
+    pub fn normalize(&mut self) {
+        for list in [
+            &mut self.skills,
+            &mut self.local_skills,
+            &mut self.mcp_servers,
+            &mut self.disabled_mcp_servers,
+            &mut self.providers,
+            &mut self.agents,
+            &mut self.disabled_agents,
+            &mut self.tools,
+            &mut self.user_agents,
+            &mut self.user_commands,
+            &mut self.preserved_skills,
+        ] {
+            list.sort();
+            list.dedup();
+        }
+        for agents in self.mcp_server_origins.values_mut() {
+            agents.sort();
+            agents.dedup();
+        }
+        if self.store_config_in_repo == Some(true) {
+            self.store_config_in_repo = None;
+        }
+    }
+
+    /// Whether the config is written into the project directory; see
+    /// [`Project::store_config_in_repo`].
+    pub fn stores_config_in_repo(&self) -> bool {
+        self.store_config_in_repo != Some(false)
+    }
+
+    /// Whether autodetection may add `agent_id` to this project.
+    pub fn is_agent_disabled(&self, agent_id: &str) -> bool {
+        self.disabled_agents.iter().any(|a| a == agent_id)
+    }
+
     pub fn enabled_mcp_servers(&self) -> Vec<String> {
         self.mcp_servers
             .iter()
             .filter(|server| {
-                server.as_str() != "automatic"
+                server.as_str() != super::AUTOMATIC_SERVER_NAME
                     && !self
                         .disabled_mcp_servers
                         .iter()
@@ -370,6 +586,49 @@ impl Project {
             .cloned()
             .collect()
     }
+
+    /// Record the agents an MCP server was discovered from.  Only servers
+    /// the project does not list yet get an origin; existing selections keep
+    /// whatever provenance (or lack of it) they already have.
+    pub fn add_discovered_mcp_server(&mut self, server: &str, agents: &[String]) {
+        if self.mcp_servers.iter().any(|s| s == server) {
+            return;
+        }
+        self.mcp_servers.push(server.to_string());
+        if !agents.is_empty() {
+            self.mcp_server_origins
+                .insert(server.to_string(), agents.to_vec());
+        }
+    }
+
+    /// MCP servers whose only recorded origin is `agent_id` — the servers
+    /// that came into the project solely because that agent was configured
+    /// with them.
+    pub fn mcp_servers_unique_to(&self, agent_id: &str) -> Vec<String> {
+        self.mcp_servers
+            .iter()
+            .filter(|server| {
+                self.mcp_server_origins
+                    .get(server.as_str())
+                    .is_some_and(|origins| origins.len() == 1 && origins[0] == agent_id)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// A selected global skill whose copy in the project directory has diverged
+/// from the registry (e.g. a project keeps an older fork with local tweaks).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SkillShadow {
+    pub name: String,
+    /// Skill directory holding the project copy, relative to the project
+    /// (e.g. `.agents/skills/terraform-skill`).
+    pub path: String,
+    /// Hash of the project copy's `SKILL.md`.
+    pub project_hash: String,
+    /// Hash of the registry's `SKILL.md`.
+    pub registry_hash: String,
 }
 
 /// An inline rule stored directly inside a project configuration.
@@ -383,6 +642,22 @@ pub struct CustomRule {
     pub content: String,
 }
 
+/// Unrecognised rule text recovered from instruction files by autodetection.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RuleCandidate {
+    /// Suggested machine name, derived from the first heading.
+    pub machine_name: String,
+    /// Suggested display name (the first heading's text).
+    pub name: String,
+    pub content: String,
+    /// Instruction files the text was found in.
+    pub files: Vec<String>,
+    /// Set when the user chose not to import it, so autodetection does not
+    /// offer the same text again.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dismissed: bool,
+}
+
 /// A named group that relates two or more projects to each other.
 ///
 /// When a project is synced, Automatic looks up all groups that contain it
@@ -567,6 +842,10 @@ mod tests {
                 source: "owner/repo".into(),
                 id: "owner/repo/my-skill".into(),
                 kind: "github".into(),
+                pinned: false,
+                license: None,
+                installed_hash: None,
+                imported_at: None,
             },
         );
         project
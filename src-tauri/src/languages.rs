@@ -180,13 +180,13 @@ pub fn load_bundled() -> Vec<LanguageModule> {
 }
 
 /// Load bundled modules and any user-supplied override modules from
-/// `~/.automatic/languages/*.mod`.  User modules with the same `id` as a
+/// `languages/*.mod` in the data directory.  User modules with the same `id` as a
 /// bundled module replace the bundled version; new ids are appended.
 pub fn load_all() -> Vec<LanguageModule> {
     let mut modules = load_bundled();
 
     // Attempt to load user overrides — failure is silent (directory may not exist)
-    if let Some(user_dir) = dirs::home_dir().map(|h| h.join(".automatic").join("languages")) {
+    if let Ok(user_dir) = crate::core::get_automatic_dir().map(|d| d.join("languages")) {
         if user_dir.is_dir() {
             if let Ok(entries) = std::fs::read_dir(&user_dir) {
                 for entry in entries.flatten() {
@@ -22,6 +22,7 @@
 
 pub mod activity;
 pub mod agent;
+pub mod cli;
 pub mod context;
 pub mod core;
 pub mod features;
@@ -42,15 +43,20 @@ mod commands;
 pub fn run() {
     use commands::*;
 
+    core::init_logging();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
-        .setup(|_app| {
+        .setup(|app| {
             // Ensure plugin marketplace exists on disk; register with Claude
             // Code if the CLI is available.  Runs on a background thread so
             // it never blocks the UI.
-            std::thread::spawn(|| {
+            let startup_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                use tauri::Emitter;
+
                 // Version-gated skill reinstall: if the stored version differs
                 // from the current binary version, overwrite all bundled skills
                 // so on-disk copies always match what shipped in this release.
@@ -69,79 +75,150 @@ pub fn run() {
                 // bundled-skills version gate so the files are overwritten
                 // whenever the app ships a new release.
                 if let Err(e) = core::init_marketplace_files(force_reinstall) {
-                    eprintln!("[automatic] marketplace init error: {}", e);
+                    tracing::error!("marketplace init error: {}", e);
                 }
 
                 if let Err(e) = core::install_default_skills_inner(force_reinstall) {
-                    eprintln!("[automatic] skill install error: {}", e);
+                    tracing::error!("skill install error: {}", e);
                 } else if force_reinstall {
                     // Persist the current version so we don't reinstall next launch.
                     match core::read_settings() {
                         Ok(mut settings) => {
                             settings.bundled_skills_version = Some(APP_VERSION.to_string());
                             if let Err(e) = core::write_settings(&settings) {
-                                eprintln!(
-                                    "[automatic] failed to persist bundled_skills_version: {}",
-                                    e
-                                );
+                                tracing::error!("failed to persist bundled_skills_version: {}", e);
                             }
                         }
-                        Err(e) => eprintln!(
-                            "[automatic] failed to read settings after skill install: {}",
-                            e
-                        ),
+                        Err(e) => {
+                            tracing::error!("failed to read settings after skill install: {}", e)
+                        }
                     }
                 }
 
                 if let Err(e) = core::install_default_templates() {
-                    eprintln!("[automatic] template install error: {}", e);
+                    tracing::error!("template install error: {}", e);
                 }
-                if let Err(e) = core::install_default_rules() {
-                    eprintln!("[automatic] rule install error: {}", e);
+                match core::install_default_rules() {
+                    Ok(updates) if !updates.is_empty() => {
+                        // Bundled rules the user edited keep their text; let
+                        // the UI offer the new default instead.
+                        tracing::info!("rule defaults updated: {:?}", updates);
+                        if let Err(e) =
+                            startup_handle.emit("rule-default-updates-available", &updates)
+                        {
+                            tracing::error!("failed to emit rule update event: {}", e);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("rule install error: {}", e),
                 }
                 if let Err(e) = core::install_default_user_agents() {
-                    eprintln!("[automatic] user agent install error: {}", e);
+                    tracing::error!("user agent install error: {}", e);
                 }
                 match core::install_plugin_marketplace() {
-                    Ok(msg) => eprintln!("[automatic] plugin startup: {}", msg),
-                    Err(e) => eprintln!("[automatic] plugin startup error: {}", e),
+                    Ok(msg) => tracing::info!("plugin startup: {}", msg),
+                    Err(e) => tracing::error!("plugin startup error: {}", e),
+                }
+                // One-time cleanup of artifacts left by pre-rename installs.
+                match sync::migrate_legacy_nexus_once() {
+                    Ok(Some(report)) if !report.is_empty() => {
+                        tracing::info!("legacy nexus migration: {:?}", report)
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("legacy nexus migration error: {}", e),
+                }
+                if let Err(e) = memory::prune_stale_session_memories() {
+                    tracing::error!("session memory prune error: {}", e);
+                }
+                if let Err(e) = sync::prune_removal_backups() {
+                    tracing::error!("removal backup prune error: {}", e);
+                }
+                match core::install_path_shim() {
+                    Ok(Some(shim)) => {
+                        tracing::info!("PATH shim updated: {}", shim.display())
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::error!("PATH shim error: {}", e),
                 }
                 match core::ensure_automatic_in_global_mcp() {
                     Ok(projects_to_sync) => {
                         // Re-sync any project whose automatic entry was added or whose
-                        // binary path changed (dev→release or after an app update).
+                        // command changed (dev→release, after an app update, or a new
+                        // MCP command mode).
                         // This keeps MCP config files and skill directories in sync
                         // without requiring the user to press "Sync now".
                         for project_name in projects_to_sync {
                             match core::read_project(&project_name) {
                                 Ok(raw) => match serde_json::from_str::<core::Project>(&raw) {
                                     Ok(mut project) => {
-                                        if let Err(e) =
-                                            sync::sync_project_without_autodetect(&mut project)
-                                        {
-                                            eprintln!(
-                                                "[automatic] startup re-sync failed for '{}': {}",
-                                                project_name, e
-                                            );
+                                        match sync::reactive_sync_project(&mut project) {
+                                            sync::ReactiveSync::SkippedLocked => {
+                                                commands::record_locked_skip(
+                                                    &project_name,
+                                                    &project,
+                                                )
+                                            }
+                                            sync::ReactiveSync::Failed(e) => tracing::error!(
+                                                "startup re-sync failed for '{}': {}",
+                                                project_name,
+                                                e
+                                            ),
+                                            _ => {}
                                         }
                                     }
-                                    Err(e) => eprintln!(
-                                        "[automatic] failed to parse project '{}' for re-sync: {}",
-                                        project_name, e
+                                    Err(e) => tracing::error!(
+                                        "failed to parse project '{}' for re-sync: {}",
+                                        project_name,
+                                        e
                                     ),
                                 },
-                                Err(e) => eprintln!(
-                                    "[automatic] failed to read project '{}' for re-sync: {}",
-                                    project_name, e
+                                Err(e) => tracing::error!(
+                                    "failed to read project '{}' for re-sync: {}",
+                                    project_name,
+                                    e
                                 ),
                             }
                         }
                     }
-                    Err(e) => eprintln!("[automatic] global MCP install error: {}", e),
+                    Err(e) => tracing::error!("global MCP install error: {}", e),
                 }
                 // Reconcile tool/skill/rule registries with current plugin states.
                 core::reconcile_plugin_resources_on_startup();
             });
+
+            // Watch opted-in projects for hand edits to their agent configs.
+            let drift_handle = app.handle().clone();
+            sync::start_drift_watch(move |event| {
+                use tauri::Emitter;
+                if let Err(e) = drift_handle.emit("project-drift-detected", &event) {
+                    tracing::error!("failed to emit drift event: {}", e);
+                }
+            });
+
+            // Periodic skill update check.  The schedule (last run, next due
+            // time, backoff) is persisted, so this only wakes hourly to ask
+            // whether a check is due.  The first look is delayed so it never
+            // competes with the startup work above.
+            let handle = app.handle().clone();
+            std::thread::spawn(move || {
+                use tauri::Emitter;
+                std::thread::sleep(std::time::Duration::from_secs(
+                    120 + rand::random_range(0..180),
+                ));
+                loop {
+                    match tauri::async_runtime::block_on(core::run_scheduled_skill_update_check()) {
+                        Ok(Some(report)) if !report.available.is_empty() => {
+                            tracing::info!("skill updates: {}", report.summary);
+                            if let Err(e) = handle.emit("skill-updates-available", &report) {
+                                tracing::error!("failed to emit skill update event: {}", e);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::error!("skill update check failed: {}", e),
+                    }
+                    std::thread::sleep(std::time::Duration::from_secs(60 * 60));
+                }
+            });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -157,43 +234,77 @@ pub fn run() {
             reset_settings,
             reinstall_defaults,
             erase_app_data,
+            get_data_dir,
+            migrate_data_dir,
+            export_backup,
+            restore_backup,
             dismiss_welcome,
             clear_opencode_cache,
             clean_opencode_snapshots,
+            migrate_legacy_nexus,
             save_api_key,
             get_api_key,
             has_api_key,
             has_ai_key,
             delete_api_key,
+            list_api_key_providers,
             list_agents,
             list_agents_with_projects,
+            get_agent_mcp_snippet,
+            get_agent_mcp_export,
             detect_installed_agents,
             detect_agent_global_configs,
             import_agent_global_configs,
             import_agent_global_skills,
+            detect_cursor_global_config,
+            migrate_cursor_global_to_project,
+            import_cursor_global_rules,
             get_skills,
             list_skill_directories,
             read_skill,
             save_skill,
+            validate_skill,
             delete_skill,
+            rename_skill,
             sync_skill,
             sync_all_skills,
+            promote_skill_to_global,
+            demote_skill_to_library,
+            preview_skill_for_agents,
             reinstall_default_skills,
-get_skill_resources,
-import_skill_from_local_path,
-import_skill_from_repository,
-import_skill_from_package,
-get_skill_collections,
-set_skill_collection,
-remove_skill_collection,
-get_templates,
+            get_skill_resources,
+            read_skill_resource,
+            save_skill_resource,
+            delete_skill_resource,
+            import_skill_from_local_path,
+            import_skill_from_repository,
+            import_skill_from_package,
+            import_skill_bundle,
+            export_skill_bundle,
+            get_skill_collections,
+            set_skill_collection,
+            remove_skill_collection,
+            list_skill_sets,
+            read_skill_set,
+            save_skill_set,
+            delete_skill_set,
+            get_templates,
             read_template,
             save_template,
             delete_template,
+            render_template_for_project,
             get_rules,
             read_rule,
             save_rule,
             delete_rule,
+            rename_rule,
+            preview_rules_section,
+            get_project_file_rules,
+            set_project_file_rules,
+            reset_rule_to_default,
+            diff_rule_against_default,
+            import_rule_candidate,
+            dismiss_rule_candidate,
             get_projects_referencing_rule,
             sync_rule_to_project,
             get_project_templates,
@@ -201,9 +312,13 @@ get_templates,
             save_project_template,
             delete_project_template,
             rename_project_template,
+            apply_project_template,
+            save_project_as_template,
             list_bundled_project_templates,
+            refresh_remote_project_templates,
             read_bundled_project_template,
             import_bundled_project_template,
+            import_bundled_project_template_with_skills,
             search_bundled_project_templates,
             check_template_dependencies,
             get_project_file_info,
@@ -222,17 +337,29 @@ get_templates,
             read_mcp_server_config,
             save_mcp_server_config,
             delete_mcp_server_config,
+            test_mcp_server,
+            parse_mcp_snippet,
+            import_mcp_snippet,
             search_mcp_marketplace,
             search_collections,
             get_projects,
             read_project,
+            get_projects_detailed,
+            lock_project,
+            unlock_project,
             preview_rebuild_project,
             autodetect_project_dependencies,
+            cancel_autodetect,
             rebuild_project,
             save_project,
             rename_project,
+            duplicate_project,
             delete_project,
             sync_project,
+            sync_all_projects,
+            scan_for_projects,
+            cancel_project_scan,
+            adopt_discovered_projects,
             list_groups,
             read_group,
             save_group,
@@ -240,8 +367,26 @@ get_templates,
             groups_for_project,
             get_agent_cleanup_preview,
             remove_agent_from_project,
+            get_unsync_project_preview,
+            unsync_project,
+            list_removal_backups,
+            restore_agent_removal,
             check_project_drift,
+            check_project_drift_detailed,
+            resolve_project_drift,
+            enable_drift_watch,
+            disable_drift_watch,
+            get_project_mcp_override,
+            set_project_mcp_override,
+            check_project_health,
+            get_project_checklist,
+            list_sync_generations,
+            restore_sync_generation,
+            get_project_version_compat,
+            unignore_agent,
+            bulk_update_projects,
             adopt_stale_skill,
+            resolve_skill_shadow,
             remove_stale_skill,
             get_project_context,
             get_project_docs,
@@ -256,14 +401,23 @@ get_templates,
             save_local_skill,
             install_plugin_marketplace,
             get_sessions,
+            repair_sessions_store,
+            get_recent_logs,
+            get_log_file_path,
             list_app_plugins,
             set_app_plugin_enabled,
             is_app_plugin_enabled,
             get_plugin_locked_resources,
             search_remote_skills,
             fetch_remote_skill_content,
+            check_skill_updates_now,
+            get_skill_update_status,
+            set_skill_pinned,
             import_remote_skill,
+            import_remote_skills,
             get_skill_sources,
+            get_skill_license_report,
+            export_skill_license_report,
             get_project_memories,
             store_memory,
             get_memory,
@@ -271,6 +425,8 @@ get_templates,
             search_memories,
             delete_memory,
             clear_memories,
+            export_memories,
+            import_memories,
             get_claude_memory,
             check_installed_editors,
             open_in_editor,
@@ -278,8 +434,11 @@ get_templates,
             get_project_activity,
             get_project_activity_paged,
             get_project_activity_count,
+            get_sync_history,
             get_all_activity,
             track_event,
+            check_for_updates,
+            install_update,
             restart_app,
             open_directory_dialog,
             subscribe_newsletter,
@@ -1,38 +1,109 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use automatic_lib::cli::{self, Cli, Command};
+use clap::Parser;
+
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
+    // Arguments that are not CLI subcommands (e.g. those added by OS
+    // launchers) still open the desktop app, as they always have.
+    let cli_invocation = std::env::args()
+        .nth(1)
+        .is_some_and(|arg| cli::is_cli_invocation(&arg));
+    if !cli_invocation {
+        automatic_lib::run();
+        return;
+    }
 
-    if args.len() > 1 && args[1] == "mcp-serve" {
-        // Ensure marketplace catalogue files exist on disk before serving.
-        // Uses force=false so an existing (app-written) file is never overwritten;
-        // this only seeds the files when they are absent (e.g. first run without
-        // the GUI, or the user deleted them).
-        if let Err(e) = automatic_lib::core::init_marketplace_files(false) {
-            eprintln!("[automatic] marketplace init error: {}", e);
-        }
+    match Cli::parse().command {
+        Some(Command::McpServe {
+            project_dir,
+            read_only,
+            allow_credentials,
+        }) => {
+            if let Some(dir) = project_dir {
+                std::env::set_var(automatic_lib::mcp::PROJECT_DIR_ENV, dir);
+            }
+            // stdout carries the protocol; diagnostics go to stderr and the
+            // shared log file through the app's subscriber.
+            automatic_lib::core::init_logging();
+
+            // Ensure marketplace catalogue files exist on disk before serving.
+            // Uses force=false so an existing (app-written) file is never overwritten;
+            // this only seeds the files when they are absent (e.g. first run without
+            // the GUI, or the user deleted them).
+            if let Err(e) = automatic_lib::core::init_marketplace_files(false) {
+                tracing::error!("marketplace init error: {}", e);
+            }
+            if let Err(e) = automatic_lib::memory::prune_stale_session_memories() {
+                tracing::error!("session memory prune error: {}", e);
+            }
 
-        // Run as MCP server on stdio
-        let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
-        rt.block_on(async {
-            if let Err(e) = automatic_lib::mcp::run_mcp_server().await {
-                eprintln!("MCP server error: {}", e);
+            // Run as MCP server on stdio
+            let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+            rt.block_on(async {
+                let access = automatic_lib::mcp::McpAccess {
+                    read_only,
+                    allow_credentials,
+                }
+                .with_env();
+                if let Err(e) = automatic_lib::mcp::run_mcp_server(access).await {
+                    tracing::error!("MCP server error: {}", e);
+                    std::process::exit(1);
+                }
+            });
+        }
+        Some(Command::McpProxy { server_name }) => {
+            // Run as a transparent MCP proxy: stdio ↔ remote HTTP with keychain auth
+            let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+            rt.block_on(async {
+                if let Err(e) = automatic_lib::proxy::run_proxy(&server_name).await {
+                    eprintln!("MCP proxy error: {}", e);
+                    std::process::exit(1);
+                }
+            });
+        }
+        Some(Command::Completions { shell }) => cli::print_completions(shell),
+        Some(Command::SessionEnd { session_id }) => {
+            let deregistered = automatic_lib::core::remove_session(&session_id);
+            match automatic_lib::memory::end_session(&session_id) {
+                Ok(removed) => println!("Removed {} session memories", removed),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+            if let Err(e) = deregistered {
+                eprintln!("{}", e);
                 std::process::exit(1);
             }
-        });
-    } else if args.len() > 2 && args[1] == "mcp-proxy" {
-        // Run as a transparent MCP proxy: stdio ↔ remote HTTP with keychain auth
-        let server_name = args[2].clone();
-        let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
-        rt.block_on(async {
-            if let Err(e) = automatic_lib::proxy::run_proxy(&server_name).await {
-                eprintln!("MCP proxy error: {}", e);
+        }
+        Some(Command::Doctor {
+            project_dir,
+            json,
+            schema,
+        }) => {
+            if schema {
+                println!("{}", cli::doctor_report_schema());
+                return;
+            }
+            let report = cli::run_doctor(project_dir.as_deref());
+            if json {
+                match serde_json::to_string_pretty(&report) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                cli::print_doctor_report(&report);
+            }
+            if report.failures > 0 {
                 std::process::exit(1);
             }
-        });
-    } else {
-        // Default: launch Tauri desktop app
-        automatic_lib::run();
+        }
+        // Only reachable for `help`, which clap handles itself.
+        None => automatic_lib::run(),
     }
 }
@@ -16,10 +16,19 @@ pub struct GetCredentialParams {
     pub provider: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ListSkillResourcesParams {
+    /// The skill name (directory name under ~/.agents/skills/ or ~/.claude/skills/)
+    pub name: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct ReadSkillParams {
     /// The skill name (directory name under ~/.agents/skills/ or ~/.claude/skills/)
     pub name: String,
+    /// Optional: project to check the skill's declared requirements against
+    #[serde(default)]
+    pub project: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -28,10 +37,52 @@ pub struct ReadProjectParams {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetProjectFilesParams {
+    /// The project name
+    pub project: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ReadProjectFileParams {
+    /// The project name
+    pub project: String,
+    /// Instruction file path relative to the project directory (e.g. "CLAUDE.md", "AGENTS.md")
+    pub filename: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SaveProjectFileParams {
+    /// The project name
+    pub project: String,
+    /// Instruction file path relative to the project directory (e.g. "CLAUDE.md", "AGENTS.md")
+    pub filename: String,
+    /// The user-authored content; Automatic re-appends the configured rules
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ReadRuleParams {
+    /// The rule's machine name (e.g. "automatic-service"), as listed by automatic_list_rules
+    pub machine_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ReadProjectRulesParams {
+    /// The project name
+    pub project: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct SearchSkillsParams {
     /// Search query (skill name, topic, or keyword)
     pub query: String,
+    /// Results per page (default 20, at most 100)
+    pub limit: Option<usize>,
+    /// Number of results to skip, for paging (default 0)
+    pub offset: Option<usize>,
+    /// "relevance" (default) or "installs"
+    pub sort: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -40,6 +91,44 @@ pub struct SyncProjectParams {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ApplyTemplateParams {
+    /// Name of the saved project template to apply
+    pub template: String,
+    /// The project to apply it to
+    pub project: String,
+    /// "merge" (default) adds the template's items; "replace" also drops
+    /// skills and MCP servers the template does not name and overwrites
+    /// existing project files
+    pub mode: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ReadProjectTemplateParams {
+    /// Template name as returned by automatic_list_project_templates
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SaveSkillParams {
+    /// The skill name (lowercase letters, numbers and hyphens)
+    pub name: String,
+    /// The full SKILL.md content, frontmatter included
+    pub content: String,
+    /// Refuse to replace a skill that already exists (default false)
+    pub create_only: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SaveProjectTemplateParams {
+    /// The project to snapshot, as registered in Automatic
+    pub project: String,
+    /// Name for the new template (letters, numbers, '-' and '_')
+    pub name: String,
+    /// Replace an existing saved template with the same name (default false)
+    pub overwrite: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct StoreMemoryParams {
     /// The project name
@@ -50,6 +139,18 @@ pub struct StoreMemoryParams {
     pub value: String,
     /// Optional: identifier for the agent/tool storing this memory
     pub source: Option<String>,
+    /// Optional: store the entry only for this session; it is deleted when
+    /// the session ends
+    pub session_id: Option<String>,
+    /// Optional: store the entry only for the current session (as reported
+    /// by the Automatic plugin) without naming it
+    pub ephemeral: Option<bool>,
+    /// Optional: delete the entry this many seconds after storing it.
+    /// Omit for a permanent entry
+    pub ttl_seconds: Option<u64>,
+    /// Optional: namespace to store the entry in, keeping it apart from other
+    /// agents' keys (defaults to "default")
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -58,6 +159,12 @@ pub struct GetMemoryParams {
     pub project: String,
     /// The memory key to retrieve
     pub key: String,
+    /// Optional: also read this session's entries (defaults to the current session)
+    pub session_id: Option<String>,
+    /// Optional: also read the entries of every session
+    pub include_ephemeral: Option<bool>,
+    /// Optional: namespace to read from (defaults to "default")
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -66,6 +173,14 @@ pub struct ListMemoriesParams {
     pub project: String,
     /// Optional: filter keys by this substring (case-insensitive)
     pub pattern: Option<String>,
+    /// Optional: also list this session's entries (defaults to the current session)
+    pub session_id: Option<String>,
+    /// Optional: also list the entries of every session
+    pub include_ephemeral: Option<bool>,
+    /// Optional: only list this namespace (defaults to every namespace)
+    pub namespace: Option<String>,
+    /// Optional: also list the global ("_global") memories, labelled by scope
+    pub include_global: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -74,6 +189,12 @@ pub struct SearchMemoriesParams {
     pub project: String,
     /// Search query to match against keys and values
     pub query: String,
+    /// Optional: also search this session's entries (defaults to the current session)
+    pub session_id: Option<String>,
+    /// Optional: also search the entries of every session
+    pub include_ephemeral: Option<bool>,
+    /// Optional: only search this namespace (defaults to every namespace)
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -82,6 +203,8 @@ pub struct DeleteMemoryParams {
     pub project: String,
     /// The memory key to delete
     pub key: String,
+    /// Optional: namespace the key was stored in
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -92,6 +215,31 @@ pub struct ClearMemoriesParams {
     pub pattern: Option<String>,
     /// Must be set to true to confirm deletion
     pub confirm: bool,
+    /// Optional: only delete memories in this namespace, even without a pattern
+    pub namespace: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct PruneMemoriesParams {
+    /// The project name
+    pub project: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ExportMemoriesParams {
+    /// The project name
+    pub project: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ImportMemoriesParams {
+    /// The project name
+    pub project: String,
+    /// A document produced by automatic_export_memories
+    pub data: String,
+    /// Optional: what to do with keys the project already has: "merge"
+    /// (default, keep them), "overwrite" or "fail_on_conflict"
+    pub strategy: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -106,6 +254,51 @@ pub struct GetProjectContextParams {
     pub project: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetProjectChecklistParams {
+    /// The project name as registered in Automatic
+    pub project: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct CheckProjectHealthParams {
+    /// The project name as registered in Automatic
+    pub project: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct BulkUpdateProjectsParams {
+    /// One of: add_skill, remove_skill, add_mcp_server, remove_mcp_server,
+    /// attach_rule, detach_rule
+    pub op: String,
+    /// Name of the skill, MCP server or rule
+    pub item: String,
+    /// Project names to update.  Omit (together with `group`) to update
+    /// every registered project.
+    #[serde(default)]
+    pub projects: Option<Vec<String>>,
+    /// Update every project in this project group instead
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ImportMcpSnippetParams {
+    /// Text containing the server's JSON config, e.g. a README section.
+    /// Prose and markdown code fences around the JSON are fine.
+    pub snippet: String,
+    /// Only import these servers (names as returned by a preview).  Omit to
+    /// import every server in the snippet.
+    #[serde(default)]
+    pub servers: Option<Vec<String>>,
+    /// Return the servers found without saving anything (default false)
+    #[serde(default)]
+    pub preview: Option<bool>,
+    /// Replace registry servers with the same name (default false)
+    #[serde(default)]
+    pub overwrite: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct GetRelatedProjectsParams {
     /// The project name as registered in Automatic
@@ -224,27 +417,196 @@ pub struct AddFeatureUpdateParams {
 
 // ── Helpers ──────────────────────────────────────────────────────────────────
 
-/// Verify that `project` is a registered project name.
-/// Returns `Ok(())` on success, or an `Err` with a helpful message listing
-/// the valid project names so the agent can self-correct immediately.
-fn validate_project(project: &str) -> Result<(), String> {
+/// Environment variable naming a project directory the server may use even
+/// when it has no `~/.automatic/projects/` registry entry (e.g. CI checkouts).
+/// Also set by `automatic mcp-serve --project-dir <dir>`.
+pub const PROJECT_DIR_ENV: &str = "AUTOMATIC_PROJECT_DIR";
+
+/// Environment variable carrying the agent session id.  The Automatic
+/// plugin's SessionStart hook exports it so memory tools can default to the
+/// current session.
+pub const SESSION_ID_ENV: &str = "AUTOMATIC_SESSION_ID";
+
+fn current_session_id() -> Option<String> {
+    std::env::var(SESSION_ID_ENV)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// How a tool's `project` argument was resolved.
+enum ProjectRef {
+    /// A name registered in Automatic.
+    Registered,
+    /// The unregistered project loaded from [`PROJECT_DIR_ENV`].
+    AdHoc(Box<crate::core::Project>),
+}
+
+/// Verify that `project` is a registered project name, or names the ad-hoc
+/// project in [`PROJECT_DIR_ENV`].
+/// Returns an `Err` with a helpful message listing the valid project names
+/// so the agent can self-correct immediately.
+fn validate_project(project: &str) -> Result<ProjectRef, String> {
     let known = crate::core::list_projects().unwrap_or_default();
+    let adhoc_dir = std::env::var(PROJECT_DIR_ENV)
+        .ok()
+        .filter(|d| !d.trim().is_empty());
+    lookup_project(project, &known, adhoc_dir.as_deref())
+}
+
+/// Path-injectable core of [`validate_project`].  Registered names always
+/// win; the ad-hoc directory is only consulted for names not in `known`, and
+/// matches either the project's name or the directory path itself.
+fn lookup_project(
+    project: &str,
+    known: &[String],
+    adhoc_dir: Option<&str>,
+) -> Result<ProjectRef, String> {
     if known.iter().any(|p| p == project) {
+        return Ok(ProjectRef::Registered);
+    }
+
+    let mut valid: Vec<String> = known.to_vec();
+    if let Some(dir) = adhoc_dir {
+        match crate::core::load_project_from_dir(dir) {
+            Ok(adhoc) if adhoc.name == project || names_directory(project, dir) => {
+                return Ok(ProjectRef::AdHoc(Box::new(adhoc)));
+            }
+            Ok(adhoc) => valid.push(adhoc.name),
+            Err(e) => tracing::warn!("{} ignored: {}", PROJECT_DIR_ENV, e),
+        }
+    }
+
+    let list = if valid.is_empty() {
+        "no projects registered yet".to_string()
+    } else {
+        valid.join(", ")
+    };
+    Err(format!(
+        "Unknown project '{}'. Valid project names are: {}. \
+         Call automatic_list_projects to confirm the correct name before retrying.",
+        project, list
+    ))
+}
+
+/// Whether a `project` argument is a path to `dir` rather than a name.
+fn names_directory(project: &str, dir: &str) -> bool {
+    let looks_like_path = project.contains(['/', '\\']) || project.starts_with('~');
+    looks_like_path && crate::core::same_path(project, dir)
+}
+
+/// Load the full project config for a tool's `project` argument.
+fn load_project(project: &str) -> Result<crate::core::Project, String> {
+    match validate_project(project)? {
+        ProjectRef::Registered => {
+            let raw = crate::core::read_project(project)
+                .map_err(|e| format!("Failed to read project '{}': {}", project, e))?;
+            serde_json::from_str(&raw).map_err(|e| format!("Failed to parse project data: {}", e))
+        }
+        ProjectRef::AdHoc(p) => Ok(*p),
+    }
+}
+
+/// Accept `filename` only when it is one of the instruction files `project`'s agents
+/// read, so the file tools cannot touch anything else in the directory.
+fn check_instruction_file(project: &crate::core::Project, filename: &str) -> Result<(), String> {
+    let files = crate::core::instruction_file_names(project);
+    if files.iter().any(|f| f == filename) {
         Ok(())
     } else {
-        let list = if known.is_empty() {
-            "no projects registered yet".to_string()
-        } else {
-            known.join(", ")
-        };
         Err(format!(
-            "Unknown project '{}'. Valid project names are: {}. \
-             Call automatic_list_projects to confirm the correct name before retrying.",
-            project, list
+            "'{}' is not an instruction file of project '{}'. Instruction files: {}",
+            filename,
+            project.name,
+            files.join(", ")
         ))
     }
 }
 
+/// Memory store key for a tool's `project` argument — the name for
+/// registered projects, a directory hash for the ad-hoc project.  The
+/// reserved global scope is accepted as-is from any session.
+fn memory_key(project: &str) -> Result<String, String> {
+    if project == crate::memory::GLOBAL_SCOPE {
+        return Ok(project.to_string());
+    }
+    match validate_project(project)? {
+        ProjectRef::Registered => Ok(project.to_string()),
+        ProjectRef::AdHoc(p) => Ok(crate::core::project_storage_key(&p)),
+    }
+}
+
+// ── Access Modes ────────────────────────────────────────────────────────────
+
+/// Environment equivalent of `automatic mcp-serve --read-only`.
+pub const READ_ONLY_ENV: &str = "AUTOMATIC_MCP_READ_ONLY";
+
+/// Tools a read-only server registers.  Everything else, including tools
+/// added later, is left out of the router, so it is neither listed nor
+/// callable.
+const READ_ONLY_TOOLS: &[&str] = &[
+    "automatic_list_skills",
+    "automatic_read_skill",
+    "automatic_list_skill_resources",
+    "automatic_search_skills",
+    "automatic_skill_updates",
+    "automatic_list_mcp_servers",
+    "automatic_list_projects",
+    "automatic_read_project",
+    "automatic_get_project_files",
+    "automatic_read_project_file",
+    "automatic_read_project_rules",
+    "automatic_list_rules",
+    "automatic_read_rule",
+    "automatic_get_related_projects",
+    "automatic_get_project_context",
+    "automatic_get_project_checklist",
+    "automatic_check_project_health",
+    "automatic_list_project_templates",
+    "automatic_read_project_template",
+    "automatic_list_sessions",
+    "automatic_get_memory",
+    "automatic_list_memories",
+    "automatic_search_memories",
+    "automatic_export_memories",
+    "automatic_read_claude_memory",
+    "automatic_list_features",
+    "automatic_get_feature",
+];
+
+/// Tools that hand out stored secrets.  A read-only server only registers
+/// them with `--allow-credentials`.
+const CREDENTIAL_TOOLS: &[&str] = &["automatic_get_credential"];
+
+/// Which tools `mcp-serve` exposes.  The default is full access.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct McpAccess {
+    pub read_only: bool,
+    pub allow_credentials: bool,
+}
+
+impl McpAccess {
+    /// Apply [`READ_ONLY_ENV`] on top of the command-line flags.  The
+    /// variable can only restrict access, never widen it.
+    pub fn with_env(self) -> Self {
+        let env_read_only = std::env::var(READ_ONLY_ENV).is_ok_and(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        });
+        Self {
+            read_only: self.read_only || env_read_only,
+            ..self
+        }
+    }
+
+    fn allows(&self, tool: &str) -> bool {
+        !self.read_only
+            || READ_ONLY_TOOLS.contains(&tool)
+            || (self.allow_credentials && CREDENTIAL_TOOLS.contains(&tool))
+    }
+}
+
 // ── MCP Server Handler ──────────────────────────────────────────────────────
 
 #[derive(Clone)]
@@ -255,9 +617,22 @@ pub struct AutomaticMcpServer {
 #[tool_router]
 impl AutomaticMcpServer {
     pub fn new() -> Self {
-        Self {
-            tool_router: Self::tool_router(),
+        Self::with_access(McpAccess::default())
+    }
+
+    /// Build a server exposing only the tools `access` allows.
+    pub fn with_access(access: McpAccess) -> Self {
+        let mut tool_router = Self::tool_router();
+        let blocked: Vec<String> = tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .filter(|name| !access.allows(name))
+            .collect();
+        for name in blocked {
+            tool_router.remove_route(&name);
         }
+        Self { tool_router }
     }
 
     // ── Read-only tools ──────────────────────────────────────────────────
@@ -299,14 +674,24 @@ impl AutomaticMcpServer {
 
     #[tool(
         name = "automatic_read_skill",
-        description = "Read the content of a specific skill from the Automatic skill registry"
+        description = "Read the SKILL.md of a specific skill from the Automatic skill registry, exactly as stored. Pass `project` to append whether the MCP servers and commands the skill requires are available to that project. Use automatic_list_skill_resources for the skill's companion files."
     )]
     async fn read_skill(
         &self,
         params: Parameters<ReadSkillParams>,
     ) -> Result<CallToolResult, McpError> {
         match crate::core::read_skill(&params.0.name) {
-            Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+            Ok(mut content) => {
+                let project = params.0.project.as_deref().map(load_project);
+                if let Some(Ok(project)) = project {
+                    if let Some(footer) =
+                        crate::core::skill_requirements_footer(&params.0.name, &content, &project)
+                    {
+                        content.push_str(&footer);
+                    }
+                }
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to read skill '{}': {}",
                 params.0.name, e
@@ -314,6 +699,78 @@ impl AutomaticMcpServer {
         }
     }
 
+    #[tool(
+        name = "automatic_list_skill_resources",
+        description = "List the companion files bundled with a skill: each top-level directory (scripts/, docs/, templates/, ...) with its files, plus loose files next to SKILL.md. Paths are relative to the skill directory."
+    )]
+    async fn list_skill_resources(
+        &self,
+        params: Parameters<ListSkillResourcesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match crate::core::list_skill_resources(&params.0.name) {
+            Ok(resources) => {
+                let json =
+                    serde_json::to_string_pretty(&resources).unwrap_or_else(|_| "{}".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to list resources of skill '{}': {}",
+                params.0.name, e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_save_skill",
+        description = "Create or update a skill's SKILL.md in the Automatic skill registry, then re-sync every project that uses the skill. Use this to persist improvements to a skill made during a session; read the current content with automatic_read_skill first. Set create_only=true when adding a new skill so an existing one is never replaced. Unavailable when the user restricted Automatic's MCP access to read-only."
+    )]
+    async fn save_skill(
+        &self,
+        params: Parameters<SaveSkillParams>,
+    ) -> Result<CallToolResult, McpError> {
+        if !crate::core::mcp_writes_allowed() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Automatic's MCP access is set to read-only; ask the user to edit the skill from the app instead.",
+            )]));
+        }
+        let SaveSkillParams {
+            name,
+            content,
+            create_only,
+        } = params.0;
+        if !crate::core::is_valid_name(&name) {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Invalid skill name '{}'",
+                name
+            ))]));
+        }
+        let exists = matches!(crate::core::get_skill_dir(&name), Ok(Some(_)));
+        if exists && create_only.unwrap_or(false) {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Skill '{}' already exists; read it with automatic_read_skill and save without create_only to update it",
+                name
+            ))]));
+        }
+
+        match crate::core::save_skill_checked(&name, &content) {
+            Ok(result) => {
+                crate::sync::sync_projects_referencing_skill(&name);
+                let mut text = format!(
+                    "Saved skill '{}' and re-synced the projects that use it",
+                    name
+                );
+                for warning in &result.warnings {
+                    text.push_str(&format!("\nWarning: {}", warning.message));
+                }
+                Ok(CallToolResult::success(vec![Content::text(text)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to save skill: {}",
+                e
+            ))])),
+        }
+    }
+
     #[tool(
         name = "automatic_list_mcp_servers",
         description = "List all MCP server configurations registered in the Automatic server registry"
@@ -342,6 +799,58 @@ impl AutomaticMcpServer {
         }
     }
 
+    #[tool(
+        name = "automatic_import_mcp_snippet",
+        description = "Register MCP servers in Automatic from a config snippet, such as the JSON block in a server's README. Accepts Claude, Cursor, VS Code, OpenCode and Zed formats or a bare server object. Use preview=true first to see the servers found and any placeholder values (e.g. YOUR_API_KEY) the user must fill in; env placeholders are saved empty for the user to set in the app. Unavailable when the user restricted Automatic's MCP access to read-only."
+    )]
+    async fn import_mcp_snippet(
+        &self,
+        params: Parameters<ImportMcpSnippetParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if params.preview.unwrap_or(false) {
+            return match crate::core::parse_mcp_snippet(&params.snippet) {
+                Ok(candidates) => Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&candidates).unwrap_or_else(|_| "[]".to_string()),
+                )])),
+                Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+            };
+        }
+        if !crate::core::mcp_writes_allowed() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Automatic's MCP access is set to read-only; ask the user to add the server from the app instead.",
+            )]));
+        }
+
+        let names = match params.servers.filter(|names| !names.is_empty()) {
+            Some(names) => names,
+            None => match crate::core::parse_mcp_snippet(&params.snippet) {
+                Ok(candidates) => candidates.into_iter().map(|c| c.name).collect(),
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            },
+        };
+        let overwrite = params.overwrite.unwrap_or(false);
+        let overrides: Vec<crate::core::McpSnippetOverride> = names
+            .into_iter()
+            .map(|name| crate::core::McpSnippetOverride {
+                name,
+                overwrite,
+                ..Default::default()
+            })
+            .collect();
+
+        match crate::core::import_mcp_snippet(&params.snippet, &overrides) {
+            Ok(saved) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Registered MCP servers: {}. Add them to a project to use them.",
+                saved.join(", ")
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to import MCP servers: {}",
+                e
+            ))])),
+        }
+    }
+
     // ── Project tools ────────────────────────────────────────────────────
 
     #[tool(
@@ -350,7 +859,18 @@ impl AutomaticMcpServer {
     )]
     async fn list_projects(&self) -> Result<CallToolResult, McpError> {
         match crate::core::list_projects() {
-            Ok(projects) => {
+            Ok(mut projects) => {
+                // Include the unregistered project from AUTOMATIC_PROJECT_DIR.
+                if let Some(dir) = std::env::var(PROJECT_DIR_ENV)
+                    .ok()
+                    .filter(|d| !d.trim().is_empty())
+                {
+                    if let Ok(adhoc) = crate::core::load_project_from_dir(&dir) {
+                        if !projects.contains(&adhoc.name) {
+                            projects.push(adhoc.name);
+                        }
+                    }
+                }
                 let json =
                     serde_json::to_string_pretty(&projects).unwrap_or_else(|_| "[]".to_string());
                 Ok(CallToolResult::success(vec![Content::text(json)]))
@@ -370,15 +890,102 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<ReadProjectParams>,
     ) -> Result<CallToolResult, McpError> {
-        match crate::core::read_project(&params.0.name) {
+        match load_project(&params.0.name) {
+            Ok(project) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&project).unwrap_or_else(|_| "{}".to_string()),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_get_project_files",
+        description = "List the instruction files a project manages (e.g. CLAUDE.md, AGENTS.md), with the agents that read each one and whether it exists. In unified instruction mode the agents' files are folded into one \"_unified\" entry whose target_files lists the files a save writes to. Read-only."
+    )]
+    async fn get_project_files(
+        &self,
+        params: Parameters<GetProjectFilesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let files = crate::core::project_file_info(&project);
+        let json = serde_json::to_string_pretty(&files).unwrap_or_else(|_| "[]".to_string());
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        name = "automatic_read_project_file",
+        description = "Read the user-authored content of one of a project's agent instruction files (e.g. CLAUDE.md, AGENTS.md). Sections Automatic manages — skills and rules — are stripped, so the result is what automatic_save_project_file expects back."
+    )]
+    async fn read_project_file(
+        &self,
+        params: Parameters<ReadProjectFileParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        if let Err(e) = check_instruction_file(&project, &params.0.filename) {
+            return Ok(CallToolResult::error(vec![Content::text(e)]));
+        }
+        match crate::core::read_project_file(&project.directory, &params.0.filename) {
             Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to read project '{}': {}",
-                params.0.name, e
+                "Failed to read '{}': {}",
+                params.0.filename, e
             ))])),
         }
     }
 
+    #[tool(
+        name = "automatic_save_project_file",
+        description = "Replace the user-authored content of one of a project's agent instruction files (e.g. CLAUDE.md, AGENTS.md). Automatic re-appends the project's configured rules, and in unified instruction mode writes the same content to every agent's file. Read the current content with automatic_read_project_file first. Unavailable when the user restricted Automatic's MCP access to read-only."
+    )]
+    async fn save_project_file(
+        &self,
+        params: Parameters<SaveProjectFileParams>,
+    ) -> Result<CallToolResult, McpError> {
+        if !crate::core::mcp_writes_allowed() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Automatic's MCP access is set to read-only; ask the user to edit the file from the app instead.",
+            )]));
+        }
+        let mut project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let filename = &params.0.filename;
+        if let Err(e) = check_instruction_file(&project, filename) {
+            return Ok(CallToolResult::error(vec![Content::text(e)]));
+        }
+        if let Err(e) =
+            crate::core::save_project_file_for_project(&project, filename, &params.0.content)
+        {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to save '{}': {}",
+                filename, e
+            ))]));
+        }
+
+        // Record updated hashes so drift detection reflects what we just wrote.
+        let name = project.name.clone();
+        crate::core::record_instruction_hashes(&name, &mut project);
+
+        let unified = project.instruction_mode == "unified"
+            && !crate::agent::is_copilot_scoped_file(filename);
+        let message = if unified {
+            format!(
+                "Saved the unified instructions of project '{}' to every agent's file",
+                name
+            )
+        } else {
+            format!("Saved '{}' for project '{}'", filename, name)
+        };
+        Ok(CallToolResult::success(vec![Content::text(message)]))
+    }
+
     #[tool(
         name = "automatic_get_related_projects",
         description = "Return all projects related to the given project via Project Groups, \
@@ -390,16 +997,10 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<GetRelatedProjectsParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
-
         // Load the requesting project to get its directory for relative-path computation.
-        let this_dir = match crate::core::read_project(&params.0.project) {
-            Ok(raw) => serde_json::from_str::<crate::core::Project>(&raw)
-                .map(|p| p.directory)
-                .unwrap_or_default(),
-            Err(_) => String::new(),
+        let this_dir = match load_project(&params.0.project) {
+            Ok(p) => p.directory,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
         };
 
         // Find every group this project belongs to.
@@ -479,28 +1080,9 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<GetProjectContextParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
-
-        let project_json = match crate::core::read_project(&params.0.project) {
-            Ok(j) => j,
-            Err(e) => {
-                return Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to read project '{}': {}",
-                    params.0.project, e
-                ))]));
-            }
-        };
-
-        let project: crate::core::Project = match serde_json::from_str(&project_json) {
+        let project = match load_project(&params.0.project) {
             Ok(p) => p,
-            Err(e) => {
-                return Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to parse project data: {}",
-                    e
-                ))]));
-            }
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
         };
 
         match crate::context::get_project_context(&project.directory) {
@@ -542,6 +1124,108 @@ impl AutomaticMcpServer {
         }
     }
 
+    #[tool(
+        name = "automatic_get_project_checklist",
+        description = "Report how complete a project's agent setup is: instruction files, rules, sync and \
+                       drift, MCP server configuration, the Claude Code plugin, .gitignore coverage of \
+                       configs holding credentials, and pending conflicts. Each item has a status \
+                       (ok, warning, action_needed), a message and the command that fixes it; the \
+                       score is 0-100. Read-only."
+    )]
+    async fn get_project_checklist(
+        &self,
+        params: Parameters<GetProjectChecklistParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let checklist = crate::core::project_checklist(&project);
+        let json = serde_json::to_string_pretty(&checklist).unwrap_or_else(|_| "{}".to_string());
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        name = "automatic_check_project_health",
+        description = "Check that everything a project selects actually exists: its directory, each skill \
+                       in the skill registry, each MCP server's registry config, each rule attached to \
+                       its instruction files, each agent's instruction file, and the skills copied to \
+                       .agents/skills/. Use it to find out why an expected skill or server is missing. \
+                       Issues carry a severity (error, warning), a code and the subject they concern. \
+                       Read-only."
+    )]
+    async fn check_project_health(
+        &self,
+        params: Parameters<CheckProjectHealthParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match crate::sync::check_project_health(&project) {
+            Ok(report) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string()),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to check project health: {}",
+                e
+            ))])),
+        }
+    }
+
+    // ── Rule tools ───────────────────────────────────────────────────────
+
+    #[tool(
+        name = "automatic_list_rules",
+        description = "List the rules in Automatic's registry: machine name (id), display name, and whether the rule is bundled or owned by a plugin. Rules are injected into project instruction files."
+    )]
+    async fn list_rules(&self) -> Result<CallToolResult, McpError> {
+        match crate::core::list_rules() {
+            Ok(rules) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&rules).unwrap_or_else(|_| "[]".to_string()),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to list rules: {}",
+                e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_read_rule",
+        description = "Read the markdown content of a rule by machine name."
+    )]
+    async fn read_rule(
+        &self,
+        params: Parameters<ReadRuleParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match crate::core::read_rule_content(&params.0.machine_name) {
+            Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to read rule: {}",
+                e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_read_project_rules",
+        description = "Return the rules that apply to each of a project's instruction files: rule machine names, inline custom rules, and their content concatenated in injection order. Use this to reload a project's guardrails mid-session without re-reading the whole instruction file."
+    )]
+    async fn read_project_rules(
+        &self,
+        params: Parameters<ReadProjectRulesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let files = crate::core::project_rules_by_file(&project);
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&files).unwrap_or_else(|_| "[]".to_string()),
+        )]))
+    }
+
     // ── Sessions tool ────────────────────────────────────────────────────
 
     #[tool(
@@ -562,13 +1246,32 @@ impl AutomaticMcpServer {
 
     #[tool(
         name = "automatic_search_skills",
-        description = "Search the skills.sh registry for community skills matching a query. Returns skill names, install counts, and source repos."
+        description = "Search the skills.sh registry for community skills matching a query. Returns skill names, install counts, source repos, descriptions when available, and whether each skill is already installed. Supports paging with limit/offset and sorting by relevance or installs."
     )]
     async fn search_skills(
         &self,
         params: Parameters<SearchSkillsParams>,
     ) -> Result<CallToolResult, McpError> {
-        match crate::core::search_remote_skills(&params.0.query).await {
+        let params = params.0;
+        let defaults = crate::core::SkillSearchOptions::default();
+        let sort = match params.sort.as_deref() {
+            None => Default::default(),
+            Some(sort) => match serde_json::from_value(serde_json::Value::String(sort.into())) {
+                Ok(sort) => sort,
+                Err(_) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format!(
+                        "Unknown sort '{}'; use relevance or installs",
+                        sort
+                    ))]))
+                }
+            },
+        };
+        let options = crate::core::SkillSearchOptions {
+            limit: params.limit.unwrap_or(defaults.limit),
+            offset: params.offset.unwrap_or(defaults.offset),
+            sort,
+        };
+        match crate::core::search_remote_skills(&params.query, &options).await {
             Ok(results) => {
                 let json =
                     serde_json::to_string_pretty(&results).unwrap_or_else(|_| "[]".to_string());
@@ -581,6 +1284,27 @@ impl AutomaticMcpServer {
         }
     }
 
+    #[tool(
+        name = "automatic_skill_updates",
+        description = "Report imported skills that have upstream updates available, as found by Automatic's last background update check. Read-only: does not contact GitHub or apply updates."
+    )]
+    async fn skill_updates(&self) -> Result<CallToolResult, McpError> {
+        match crate::core::read_skill_update_state() {
+            Ok(state) => {
+                let json = serde_json::to_string_pretty(&serde_json::json!({
+                    "last_checked_at": state.last_checked_at,
+                    "available": state.available,
+                }))
+                .unwrap_or_else(|_| "{}".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to read skill update status: {}",
+                e
+            ))])),
+        }
+    }
+
     // ── Config sync tool ─────────────────────────────────────────────────
 
     #[tool(
@@ -591,32 +1315,43 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<SyncProjectParams>,
     ) -> Result<CallToolResult, McpError> {
-        let raw = match crate::core::read_project(&params.0.name) {
-            Ok(r) => r,
-            Err(e) => {
-                return Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to read project '{}': {}",
-                    params.0.name, e
-                ))]));
-            }
-        };
-
-        let project: crate::core::Project = match serde_json::from_str(&raw) {
+        let project = match load_project(&params.0.name) {
             Ok(p) => p,
-            Err(e) => {
-                return Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Invalid project data: {}",
-                    e
-                ))]));
-            }
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
         };
+        if let Err(e) = project.ensure_unlocked() {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Refusing to sync: {}",
+                e
+            ))]));
+        }
 
-        match crate::sync::sync_project(&project) {
-            Ok(files) => {
+        let history = crate::core::sync_history_scope(crate::core::SyncTrigger::Mcp);
+        match crate::sync::sync_project_with_report(&project) {
+            Ok(report) => {
+                let files = report.files;
+                let mut warnings: Vec<String> =
+                    crate::core::project_skill_requirement_warnings(&project)
+                        .iter()
+                        .map(|w| w.message())
+                        .collect();
+                warnings.extend(
+                    crate::core::project_mcp_budget_warnings(&project)
+                        .iter()
+                        .map(|w| w.message()),
+                );
+                warnings.extend(report.secret_warnings.iter().map(|w| w.message()));
+                warnings.extend(
+                    crate::sync::project_rule_warnings(&project)
+                        .iter()
+                        .map(|w| w.message()),
+                );
                 let response = serde_json::json!({
                     "synced_files": files,
                     "agents": project.agents,
                     "directory": project.directory,
+                    "warnings": warnings,
+                    "history_entry_id": history.last_entry_id(),
                 });
                 Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&response)
@@ -630,24 +1365,215 @@ impl AutomaticMcpServer {
         }
     }
 
+    #[tool(
+        name = "automatic_sync_all_projects",
+        description = "Sync every registered project to its directory, a few projects at a time, without re-running dependency autodetection. Projects with no directory, a missing directory or a lock are skipped with a reason; a project that fails does not stop the rest. Returns one result per project with its status (synced, skipped or failed), the files written and the reason or error."
+    )]
+    async fn sync_all_projects(&self) -> Result<CallToolResult, McpError> {
+        match tokio::task::spawn_blocking(crate::sync::sync_all_projects).await {
+            Ok(results) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&results).unwrap_or_else(|_| "[]".to_string()),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Sync failed: {}",
+                e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_apply_template",
+        description = "Apply a saved project template to a project: merges its agents, skills, MCP servers and rules, installs bundled skills it needs, and writes template files that do not exist yet. mode=replace also drops skills and MCP servers the template does not name and overwrites existing template files. Does not sync agent configs. Returns a report of what was added, removed or already present, and any skills/servers/rules the template names that are not installed."
+    )]
+    async fn apply_template(
+        &self,
+        params: Parameters<ApplyTemplateParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let mode: crate::core::TemplateApplyMode = match params.0.mode.as_deref() {
+            None => Default::default(),
+            Some(mode) => match serde_json::from_value(serde_json::Value::String(mode.into())) {
+                Ok(mode) => mode,
+                Err(_) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format!(
+                        "Unknown mode '{}'; use merge or replace",
+                        mode
+                    ))]))
+                }
+            },
+        };
+        let mut project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+
+        match crate::core::apply_project_template(&params.0.template, &mut project, mode) {
+            Ok(report) => {
+                crate::activity::log(
+                    &crate::core::project_storage_key(&project),
+                    crate::activity::ActivityEvent::TemplateApplied,
+                    "Template applied",
+                    &report.summary(),
+                );
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&report).unwrap_or_else(|_| report.summary()),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to apply template: {}",
+                e
+            ))])),
+        }
+    }
+
+    // ── Project template tools ───────────────────────────────────────────
+
+    #[tool(
+        name = "automatic_list_project_templates",
+        description = "List the project templates available in Automatic: the user's saved templates and the bundled marketplace templates, each tagged with its source (local or bundled). A saved template shadows a bundled one with the same name. Returns name, description, agents, skills and category for each."
+    )]
+    async fn list_project_templates(&self) -> Result<CallToolResult, McpError> {
+        match crate::core::list_template_catalog() {
+            Ok(listings) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&listings).unwrap_or_else(|_| "[]".to_string()),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to list project templates: {}",
+                e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_read_project_template",
+        description = "Read the full JSON of a project template by name, together with its source (local or bundled). Saved (local) templates take precedence over bundled marketplace templates with the same name."
+    )]
+    async fn read_project_template(
+        &self,
+        params: Parameters<ReadProjectTemplateParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match crate::core::read_catalog_template(&params.0.name) {
+            Ok((source, json)) => {
+                let template: serde_json::Value =
+                    serde_json::from_str(&json).unwrap_or(serde_json::Value::String(json));
+                let out = serde_json::json!({ "source": source, "template": template });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&out).unwrap_or_default(),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_save_project_template",
+        description = "Save a project's agents, skills, MCP servers and instruction files as a new project template. Only call this when the user explicitly asks to create a template. Refuses to replace an existing template unless overwrite is true, and is unavailable when the user restricted Automatic's MCP access to read-only."
+    )]
+    async fn save_project_template(
+        &self,
+        params: Parameters<SaveProjectTemplateParams>,
+    ) -> Result<CallToolResult, McpError> {
+        if !crate::core::mcp_writes_allowed() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Automatic's MCP access is set to read-only; ask the user to save the template from the app instead.",
+            )]));
+        }
+        let project = match load_project(&params.0.project) {
+            Ok(p) => p,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+
+        match crate::core::save_project_as_template(
+            &project,
+            &params.0.name,
+            params.0.overwrite.unwrap_or(false),
+        ) {
+            Ok(template) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&template)
+                    .unwrap_or_else(|_| format!("Saved template '{}'", params.0.name)),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to save template: {}",
+                e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_bulk_update_projects",
+        description = "Add or remove a skill, MCP server or rule across many projects at once (op: add_skill, remove_skill, add_mcp_server, remove_mcp_server, attach_rule, detach_rule). Targets the listed projects, a project group, or every project when neither is given. Returns what happened to each project; changed projects are synced in the background. Only call this when the user explicitly asks; unavailable when the user restricted Automatic's MCP access to read-only."
+    )]
+    async fn bulk_update_projects(
+        &self,
+        params: Parameters<BulkUpdateProjectsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        if !crate::core::mcp_writes_allowed() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Automatic's MCP access is set to read-only; ask the user to make this change from the app instead.",
+            )]));
+        }
+        let params = params.0;
+        let op: crate::core::BulkOp =
+            match serde_json::from_value(serde_json::Value::String(params.op.clone())) {
+                Ok(op) => op,
+                Err(_) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format!(
+                        "Unknown op '{}'",
+                        params.op
+                    ))]))
+                }
+            };
+        let targets = match (params.projects, params.group) {
+            (Some(projects), _) => crate::core::BulkTargets::Projects(projects),
+            (None, Some(group)) => crate::core::BulkTargets::Group(group),
+            (None, None) => crate::core::BulkTargets::All,
+        };
+
+        match crate::core::bulk_update_projects(op, &params.item, &targets) {
+            Ok(results) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&results).unwrap_or_else(|_| "[]".to_string()),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Bulk update failed: {}",
+                e
+            ))])),
+        }
+    }
+
     // ── Memory tools ─────────────────────────────────────────────────────
 
     #[tool(
         name = "automatic_store_memory",
-        description = "Stores a memory entry (key-value pair) for a project. AI agents can use this to persist learned information, preferences, or context over time."
+        description = "Stores a memory entry (key-value pair) for a project. AI agents can use this to persist learned information, preferences, or context over time. Set ephemeral (or session_id) for scratch state that should only live for the current session, or ttl_seconds for context that should expire after a while. Use project \"_global\" for facts that are not project-specific, such as the user's commit style."
     )]
     async fn store_memory(
         &self,
         params: Parameters<StoreMemoryParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let session_id = match (&params.0.session_id, params.0.ephemeral) {
+            (Some(id), _) => Some(id.clone()),
+            (None, Some(true)) => match current_session_id() {
+                Some(id) => Some(id),
+                None => {
+                    return Ok(CallToolResult::error(vec![Content::text(format!(
+                        "No current session: {} is not set. Pass session_id explicitly.",
+                        SESSION_ID_ENV
+                    ))]))
+                }
+            },
+            (None, _) => None,
+        };
         match crate::memory::store_memory(
-            &params.0.project,
+            &key,
             &params.0.key,
             &params.0.value,
             params.0.source.as_deref(),
+            session_id.as_deref(),
+            params.0.ttl_seconds,
+            params.0.namespace.as_deref(),
         ) {
             Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
@@ -665,10 +1591,16 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<GetMemoryParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
-        match crate::memory::get_memory(&params.0.project, &params.0.key) {
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let session_id = params.0.session_id.clone().or_else(current_session_id);
+        let scope = crate::memory::SessionScope {
+            session_id: session_id.as_deref(),
+            include_ephemeral: params.0.include_ephemeral.unwrap_or(false),
+        };
+        match crate::memory::get_memory(&key, &params.0.key, scope, params.0.namespace.as_deref()) {
             Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get memory: {}",
@@ -679,16 +1611,28 @@ impl AutomaticMcpServer {
 
     #[tool(
         name = "automatic_list_memories",
-        description = "Lists all stored memories for a project, optionally filtered by a key pattern."
+        description = "Lists all stored memories for a project, optionally filtered by a key pattern or namespace. Each entry shows its namespace. Set include_global to also list the cross-project \"_global\" memories, labelled by scope."
     )]
     async fn list_memories(
         &self,
         params: Parameters<ListMemoriesParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
-        match crate::memory::list_memories(&params.0.project, params.0.pattern.as_deref()) {
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let session_id = params.0.session_id.clone().or_else(current_session_id);
+        let scope = crate::memory::SessionScope {
+            session_id: session_id.as_deref(),
+            include_ephemeral: params.0.include_ephemeral.unwrap_or(false),
+        };
+        match crate::memory::list_memories(
+            &key,
+            params.0.pattern.as_deref(),
+            scope,
+            params.0.namespace.as_deref(),
+            params.0.include_global.unwrap_or(false),
+        ) {
             Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to list memories: {}",
@@ -705,10 +1649,21 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<SearchMemoriesParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
-        match crate::memory::search_memories(&params.0.project, &params.0.query) {
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let session_id = params.0.session_id.clone().or_else(current_session_id);
+        let scope = crate::memory::SessionScope {
+            session_id: session_id.as_deref(),
+            include_ephemeral: params.0.include_ephemeral.unwrap_or(false),
+        };
+        match crate::memory::search_memories(
+            &key,
+            &params.0.query,
+            scope,
+            params.0.namespace.as_deref(),
+        ) {
             Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to search memories: {}",
@@ -725,10 +1680,11 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<DeleteMemoryParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
-        match crate::memory::delete_memory(&params.0.project, &params.0.key) {
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match crate::memory::delete_memory(&key, &params.0.key, params.0.namespace.as_deref()) {
             Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to delete memory: {}",
@@ -739,19 +1695,21 @@ impl AutomaticMcpServer {
 
     #[tool(
         name = "automatic_clear_memories",
-        description = "Clears all memories for a project, optionally filtered by pattern. Use with caution!"
+        description = "Clears all memories for a project, optionally filtered by pattern and/or namespace. Use with caution!"
     )]
     async fn clear_memories(
         &self,
         params: Parameters<ClearMemoriesParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
         match crate::memory::clear_memories(
-            &params.0.project,
+            &key,
             params.0.pattern.as_deref(),
             params.0.confirm,
+            params.0.namespace.as_deref(),
         ) {
             Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
@@ -761,6 +1719,91 @@ impl AutomaticMcpServer {
         }
     }
 
+    #[tool(
+        name = "automatic_prune_memories",
+        description = "Deletes a project's expired memories (entries stored with ttl_seconds whose time is up). Expired entries are already hidden from reads and pruned lazily; call this to clean up explicitly."
+    )]
+    async fn prune_memories(
+        &self,
+        params: Parameters<PruneMemoriesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match crate::memory::prune_expired(&key) {
+            Ok(removed) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Pruned {} expired memor{} for project '{}'",
+                removed,
+                if removed == 1 { "y" } else { "ies" },
+                key
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to prune memories: {}",
+                e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_export_memories",
+        description = "Exports a project's long-term memories as a versioned JSON document (keys, values, sources and timestamps) for backup or for importing into another project or machine with automatic_import_memories."
+    )]
+    async fn export_memories(
+        &self,
+        params: Parameters<ExportMemoriesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match crate::memory::export_memories(&key) {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to export memories: {}",
+                e
+            ))])),
+        }
+    }
+
+    #[tool(
+        name = "automatic_import_memories",
+        description = "Imports a document from automatic_export_memories into a project. strategy decides what happens to existing keys: merge (default, keep them), overwrite, or fail_on_conflict (import nothing if any existing value differs). A malformed document is rejected without changes."
+    )]
+    async fn import_memories(
+        &self,
+        params: Parameters<ImportMemoriesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let key = match memory_key(&params.0.project) {
+            Ok(k) => k,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let strategy = params.0.strategy.as_deref().unwrap_or("merge");
+        let strategy: crate::memory::MemoryImportStrategy =
+            match serde_json::from_value(serde_json::Value::String(strategy.into())) {
+                Ok(strategy) => strategy,
+                Err(_) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format!(
+                        "Unknown strategy '{}'; use merge, overwrite or fail_on_conflict",
+                        strategy
+                    ))]))
+                }
+            };
+        match crate::memory::import_memories(&key, &params.0.data, strategy) {
+            Ok(report) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Imported memories into '{}': {} new, {} overwritten, {} skipped",
+                params.0.project,
+                report.imported.len(),
+                report.overwritten.len(),
+                report.skipped.len()
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Failed to import memories: {}",
+                e
+            ))])),
+        }
+    }
+
     // ── Claude auto-memory integration ────────────────────────────────────
 
     #[tool(
@@ -774,29 +1817,10 @@ impl AutomaticMcpServer {
         &self,
         params: Parameters<ReadClaudeMemoryParams>,
     ) -> Result<CallToolResult, McpError> {
-        if let Err(e) = validate_project(&params.0.project) {
-            return Ok(CallToolResult::error(vec![Content::text(e)]));
-        }
-
         // Look up the project's directory
-        let project_json = match crate::core::read_project(&params.0.project) {
-            Ok(j) => j,
-            Err(e) => {
-                return Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to read project '{}': {}",
-                    params.0.project, e
-                ))]));
-            }
-        };
-
-        let project: crate::core::Project = match serde_json::from_str(&project_json) {
+        let project = match load_project(&params.0.project) {
             Ok(p) => p,
-            Err(e) => {
-                return Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to parse project data: {}",
-                    e
-                ))]));
-            }
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
         };
 
         match crate::memory::read_claude_memory(&project.directory) {
@@ -1132,10 +2156,150 @@ impl ServerHandler for AutomaticMcpServer {
 
 // ── Entry Point ──────────────────────────────────────────────────────────────
 
-pub async fn run_mcp_server() -> Result<(), Box<dyn std::error::Error>> {
-    let server = AutomaticMcpServer::new();
+pub async fn run_mcp_server(access: McpAccess) -> Result<(), Box<dyn std::error::Error>> {
+    let server = AutomaticMcpServer::with_access(access);
     let service = server.serve(stdio()).await?;
     service.waiting().await?;
 
     Ok(())
 }
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn adhoc_checkout(name: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".automatic")).unwrap();
+        let project = crate::core::Project {
+            name: name.into(),
+            ..Default::default()
+        };
+        fs::write(
+            dir.path().join(".automatic").join("project.json"),
+            serde_json::to_string(&project).unwrap(),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn registered_name_takes_precedence() {
+        let dir = adhoc_checkout("web");
+        let known = vec!["web".to_string()];
+        let found = lookup_project("web", &known, dir.path().to_str());
+        assert!(matches!(found, Ok(ProjectRef::Registered)));
+    }
+
+    #[test]
+    fn unregistered_project_dir_resolves_by_name() {
+        let dir = adhoc_checkout("ci-checkout-demo");
+        let found = lookup_project("ci-checkout-demo", &[], dir.path().to_str());
+        match found {
+            Ok(ProjectRef::AdHoc(p)) => {
+                assert_eq!(p.name, "ci-checkout-demo");
+                assert_eq!(
+                    std::path::PathBuf::from(&p.directory),
+                    fs::canonicalize(dir.path()).unwrap()
+                );
+            }
+            _ => panic!("expected the ad-hoc project to resolve"),
+        }
+    }
+
+    #[test]
+    fn unregistered_project_dir_resolves_by_path_spelling() {
+        let dir = adhoc_checkout("ci-checkout-demo");
+        let spelled = format!("{}/", dir.path().join("src").join("..").display());
+        let found = lookup_project(&spelled, &[], dir.path().to_str());
+        assert!(matches!(found, Ok(ProjectRef::AdHoc(_))));
+    }
+
+    #[test]
+    fn unknown_name_lists_ad_hoc_project() {
+        let dir = adhoc_checkout("ci-checkout-demo");
+        let err = match lookup_project("other", &["alpha".into()], dir.path().to_str()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error"),
+        };
+        assert!(err.contains("alpha, ci-checkout-demo"), "{}", err);
+    }
+
+    #[test]
+    fn global_memory_scope_needs_no_project() {
+        assert_eq!(
+            memory_key(crate::memory::GLOBAL_SCOPE).unwrap(),
+            crate::memory::GLOBAL_SCOPE
+        );
+    }
+
+    fn tool_names(server: &AutomaticMcpServer) -> Vec<String> {
+        server
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|t| t.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn read_only_mode_advertises_only_read_tools() {
+        let full = tool_names(&AutomaticMcpServer::new());
+        let read_only = tool_names(&AutomaticMcpServer::with_access(McpAccess {
+            read_only: true,
+            allow_credentials: false,
+        }));
+
+        assert!(full.contains(&"automatic_sync_project".to_string()));
+        assert!(full.contains(&"automatic_get_credential".to_string()));
+        assert!(read_only.len() < full.len());
+        assert!(read_only
+            .iter()
+            .all(|t| READ_ONLY_TOOLS.contains(&t.as_str())));
+        // Every allow-listed name must still exist, or the list has rotted.
+        for tool in READ_ONLY_TOOLS.iter().chain(CREDENTIAL_TOOLS) {
+            assert!(full.contains(&tool.to_string()), "unknown tool {}", tool);
+        }
+
+        let with_credentials = tool_names(&AutomaticMcpServer::with_access(McpAccess {
+            read_only: true,
+            allow_credentials: true,
+        }));
+        assert!(with_credentials.contains(&"automatic_get_credential".to_string()));
+        assert!(!with_credentials.contains(&"automatic_store_memory".to_string()));
+    }
+
+    #[tokio::test]
+    async fn read_only_server_rejects_calls_to_unregistered_tools() {
+        let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+        let server = AutomaticMcpServer::with_access(McpAccess {
+            read_only: true,
+            allow_credentials: false,
+        });
+        tokio::spawn(async move {
+            if let Ok(running) = server.serve(tokio::io::split(server_io)).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let client = ().serve(tokio::io::split(client_io)).await.unwrap();
+
+        let listed = client.list_all_tools().await.unwrap();
+        assert!(!listed.iter().any(|t| t.name == "automatic_delete_memory"));
+
+        let call = client
+            .call_tool(CallToolRequestParams {
+                meta: None,
+                name: "automatic_delete_memory".into(),
+                arguments: serde_json::json!({"project": "web", "key": "k"})
+                    .as_object()
+                    .cloned(),
+                task: None,
+            })
+            .await;
+        assert!(call.is_err(), "{:?}", call);
+        client.cancel().await.unwrap();
+    }
+}
@@ -1,9 +1,10 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-#[cfg(test)]
-use std::path::Path;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
 
 /// A single memory entry with metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,11 +19,47 @@ pub struct MemoryEntry {
     /// Populated by the frontend/MCP caller for future team/cloud sync.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_by: Option<String>,
+    /// RFC 3339 timestamp after which the entry is treated as deleted.
+    /// Entries without one are permanent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// Namespace the entry belongs to.  Files written before namespaces
+    /// existed load with every entry in [`DEFAULT_NAMESPACE`].
+    #[serde(
+        default = "default_namespace",
+        skip_serializing_if = "is_default_namespace"
+    )]
+    pub namespace: String,
+}
+
+impl MemoryEntry {
+    /// True once `expires_at` has passed.  An expiry that does not parse is
+    /// ignored rather than hiding the entry.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .as_deref()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .is_some_and(|ts| ts <= now)
+    }
+
+    /// The key the entry was stored under, given its key in the database.
+    fn key_in<'a>(&self, stored_key: &'a str) -> &'a str {
+        if is_default_namespace(&self.namespace) {
+            return stored_key;
+        }
+        stored_key
+            .strip_prefix(self.namespace.as_str())
+            .and_then(|rest| rest.strip_prefix(NAMESPACE_SEPARATOR))
+            .unwrap_or(stored_key)
+    }
 }
 
 /// Memory database type: a simple key-value store.
 pub type MemoryDb = HashMap<String, MemoryEntry>;
 
+/// Session-scoped memories for one project, keyed by session id.
+pub type EphemeralDb = HashMap<String, MemoryDb>;
+
 pub fn get_memory_dir() -> Result<PathBuf, String> {
     Ok(crate::core::get_automatic_dir()?.join("memory"))
 }
@@ -38,41 +75,517 @@ fn get_project_memory_path(project_name: &str) -> Result<PathBuf, String> {
     Ok(dir.join(format!("{}.json", project_name)))
 }
 
-pub fn read_memory_db(project_name: &str) -> Result<MemoryDb, String> {
+/// Sidecar holding the project's session-scoped entries, kept apart from the
+/// long-term store so they can be dropped wholesale when a session ends.
+fn get_ephemeral_memory_path(project_name: &str) -> Result<PathBuf, String> {
     let path = get_project_memory_path(project_name)?;
+    Ok(path.with_extension(EPHEMERAL_EXTENSION))
+}
+
+const EPHEMERAL_EXTENSION: &str = "ephemeral.json";
+
+pub fn read_memory_db(project_name: &str) -> Result<MemoryDb, String> {
+    read_db_file(&get_project_memory_path(project_name)?)
+}
+
+pub fn write_memory_db(project_name: &str, db: &MemoryDb) -> Result<(), String> {
+    update_db_file(&get_project_memory_path(project_name)?, |current| {
+        *current = db.clone();
+        Ok(())
+    })
+}
+
+fn read_db_file<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
     if !path.exists() {
-        return Ok(HashMap::new());
+        return Ok(T::default());
     }
-    let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let db: MemoryDb = serde_json::from_str(&raw).unwrap_or_default();
-    Ok(db)
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    Ok(serde_json::from_str(&raw).unwrap_or_default())
 }
 
-pub fn write_memory_db(project_name: &str, db: &MemoryDb) -> Result<(), String> {
-    let path = get_project_memory_path(project_name)?;
+/// Replace the file atomically so lock-free readers never see a
+/// half-written database.
+fn write_db_file<T: Serialize>(path: &Path, db: &T) -> Result<(), String> {
     let raw = serde_json::to_string_pretty(db).map_err(|e| e.to_string())?;
-    fs::write(&path, raw).map_err(|e| e.to_string())
+    crate::core::write_atomic(path, raw)
+}
+
+// ── Locking ──────────────────────────────────────────────────────────────────
+//
+// Agents in parallel sessions each run their own MCP server process, and the
+// app itself serves memory calls from several threads.  Every
+// read-modify-write therefore runs under `core::with_file_lock` (an
+// in-process mutex per file plus an advisory OS lock on a
+// `<project>.json.lock` sidecar).  The database is read only once both are
+// held, so concurrent writers merge their changes instead of overwriting
+// each other's.
+
+/// Read-modify-write the database at `path` under both locks.  Nothing is
+/// written when `update` returns an error.
+fn update_db_file<D, T>(
+    path: &Path,
+    update: impl FnOnce(&mut D) -> Result<T, String>,
+) -> Result<T, String>
+where
+    D: Serialize + DeserializeOwned + Default,
+{
+    crate::core::with_file_lock(path, || {
+        let mut db = read_db_file(path)?;
+        let result = update(&mut db)?;
+        write_db_file(path, &db)?;
+        Ok(result)
+    })
+}
+
+fn insert_entry(
+    db: &mut MemoryDb,
+    namespace: &str,
+    key: &str,
+    value: &str,
+    source: Option<&str>,
+    expires_at: Option<&str>,
+) -> Result<(), String> {
+    let stored_key = stored_key(namespace, key);
+    if let Some(existing) = db.get(&stored_key) {
+        if existing.namespace != namespace {
+            return Err(format!(
+                "Memory key '{}' in namespace '{}' clashes with key '{}' in namespace '{}'",
+                key,
+                namespace,
+                existing.key_in(&stored_key),
+                existing.namespace
+            ));
+        }
+    }
+    db.insert(
+        stored_key,
+        MemoryEntry {
+            value: value.to_string(),
+            timestamp: current_timestamp(),
+            source: source.map(|s| s.to_string()),
+            created_by: None,
+            expires_at: expires_at.map(|s| s.to_string()),
+            namespace: namespace.to_string(),
+        },
+    );
+    Ok(())
+}
+
+/// Remove `key`.  Without a namespace `key` is taken as stored, which is how
+/// the UI addresses entries.
+fn remove_entry(db: &mut MemoryDb, namespace: Option<&str>, key: &str) -> Result<(), String> {
+    let stored = match namespace {
+        Some(ns) => stored_key(ns, key),
+        None => key.to_string(),
+    };
+    let in_namespace = db
+        .get(&stored)
+        .is_some_and(|entry| namespace.is_none_or(|ns| entry.namespace == ns));
+    if !in_namespace {
+        return Err(format!("Memory key '{}' not found", key));
+    }
+    db.remove(&stored);
+    Ok(())
+}
+
+/// Remove every entry, or those whose key contains `pattern`
+/// (case-insensitive), limited to `namespace` when one is given.  Returns
+/// the number removed.
+fn clear_entries(db: &mut MemoryDb, pattern: Option<&str>, namespace: Option<&str>) -> usize {
+    let pat_lower = pattern.map(|p| p.to_lowercase());
+    let before = db.len();
+    db.retain(|k, entry| {
+        let in_namespace = namespace.is_none_or(|ns| entry.namespace == ns);
+        let matches = pat_lower
+            .as_deref()
+            .is_none_or(|pat| entry.key_in(k).to_lowercase().contains(pat));
+        !(in_namespace && matches)
+    });
+    before - db.len()
+}
+
+// ── Namespaces ───────────────────────────────────────────────────────────────
+//
+// Agents sharing a project (Claude Code and Cursor, say) keep their keys
+// apart by storing them in their own namespace.  Entries in the default
+// namespace are stored under their plain key, so databases written before
+// namespaces existed read unchanged; any other namespace is stored under
+// `<namespace>:<key>` and records its namespace in the entry.
+
+/// Namespace used when a caller does not name one.
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+const NAMESPACE_SEPARATOR: &str = ":";
+
+fn default_namespace() -> String {
+    DEFAULT_NAMESPACE.to_string()
+}
+
+fn is_default_namespace(namespace: &str) -> bool {
+    namespace == DEFAULT_NAMESPACE
+}
+
+fn stored_key(namespace: &str, key: &str) -> String {
+    if is_default_namespace(namespace) {
+        key.to_string()
+    } else {
+        format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, key)
+    }
+}
+
+/// Namespaces are short identifiers: letters, digits, `-`, `_` and `.`.
+fn validate_namespace(namespace: &str) -> Result<(), String> {
+    let valid = !namespace.is_empty()
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid memory namespace '{}': use letters, digits, '-', '_' or '.'",
+            namespace
+        ))
+    }
+}
+
+/// `namespace`, validated, or [`DEFAULT_NAMESPACE`] when absent.
+fn namespace_or_default(namespace: Option<&str>) -> Result<&str, String> {
+    let namespace = namespace.unwrap_or(DEFAULT_NAMESPACE);
+    validate_namespace(namespace)?;
+    Ok(namespace)
+}
+
+/// Validate an optional namespace filter.
+fn namespace_filter(namespace: Option<&str>) -> Result<Option<&str>, String> {
+    namespace.map(validate_namespace).transpose()?;
+    Ok(namespace)
+}
+
+// ── Session-scoped memories ──────────────────────────────────────────────────
+//
+// Scratch state an agent only needs for the current session ("files already
+// reviewed in this pass") is stored per session id in the project's
+// `<project>.ephemeral.json` sidecar rather than the long-term store.  Reads
+// only see it when they name the same session or ask for ephemeral entries
+// explicitly.  A session's entries are purged when the session ends
+// (`automatic session-end`, run by the plugin's SessionEnd hook) or, if that
+// never happens, by the stale-session prune.
+
+/// How long an inactive session's entries survive after its last write.
+const STALE_SESSION_HOURS: i64 = 24;
+
+/// Which session-scoped entries a read can see besides the long-term ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionScope<'a> {
+    /// Entries stored under this session id.
+    pub session_id: Option<&'a str>,
+    /// Entries of every session.
+    pub include_ephemeral: bool,
+}
+
+impl SessionScope<'_> {
+    fn sees(&self, session: &str) -> bool {
+        self.include_ephemeral || self.session_id == Some(session)
+    }
+
+    fn sees_any(&self) -> bool {
+        self.include_ephemeral || self.session_id.is_some()
+    }
+}
+
+/// One entry as seen through a [`SessionScope`].
+struct VisibleEntry<'a> {
+    key: &'a str,
+    entry: &'a MemoryEntry,
+    /// The owning session, for ephemeral entries.
+    session: Option<&'a str>,
+    /// Whether the entry comes from [`GLOBAL_SCOPE`] rather than the project.
+    global: bool,
+}
+
+/// Long-term entries plus the ephemeral ones `scope` can see, sorted by key
+/// and namespace with the long-term entry first.
+fn visible_entries<'a>(
+    db: &'a MemoryDb,
+    ephemeral: &'a EphemeralDb,
+    scope: SessionScope,
+) -> Vec<VisibleEntry<'a>> {
+    let now = Utc::now();
+    let mut visible: Vec<VisibleEntry> = db
+        .iter()
+        .filter(|(_, entry)| !entry.is_expired(now))
+        .map(|(key, entry)| VisibleEntry {
+            key: entry.key_in(key),
+            entry,
+            session: None,
+            global: false,
+        })
+        .collect();
+    for (session, entries) in ephemeral {
+        if scope.sees(session) {
+            visible.extend(
+                entries
+                    .iter()
+                    .filter(|(_, entry)| !entry.is_expired(now))
+                    .map(|(key, entry)| VisibleEntry {
+                        key: entry.key_in(key),
+                        entry,
+                        session: Some(session),
+                        global: false,
+                    }),
+            );
+        }
+    }
+    sort_visible(&mut visible);
+    visible
+}
+
+/// Sort by key and namespace, with project entries before global ones and
+/// long-term entries before session ones.
+fn sort_visible(visible: &mut [VisibleEntry]) {
+    visible.sort_by(|a, b| {
+        (a.key, &a.entry.namespace, a.global, a.session).cmp(&(
+            b.key,
+            &b.entry.namespace,
+            b.global,
+            b.session,
+        ))
+    });
+}
+
+/// Add the unexpired entries of the [`GLOBAL_SCOPE`] store to `visible`.
+fn with_global<'a>(
+    mut visible: Vec<VisibleEntry<'a>>,
+    global: &'a MemoryDb,
+) -> Vec<VisibleEntry<'a>> {
+    let now = Utc::now();
+    visible.extend(
+        global
+            .iter()
+            .filter(|(_, entry)| !entry.is_expired(now))
+            .map(|(key, entry)| VisibleEntry {
+                key: entry.key_in(key),
+                entry,
+                session: None,
+                global: true,
+            }),
+    );
+    sort_visible(&mut visible);
+    visible
+}
+
+/// Keep the entries in `namespace`, or all of them without one.
+fn in_namespace<'a>(
+    visible: Vec<VisibleEntry<'a>>,
+    namespace: Option<&str>,
+) -> Vec<VisibleEntry<'a>> {
+    match namespace {
+        Some(ns) => visible
+            .into_iter()
+            .filter(|v| v.entry.namespace == ns)
+            .collect(),
+        None => visible,
+    }
+}
+
+fn insert_session_entry(
+    db: &mut EphemeralDb,
+    session_id: &str,
+    namespace: &str,
+    key: &str,
+    value: &str,
+    source: Option<&str>,
+    expires_at: Option<&str>,
+) -> Result<(), String> {
+    insert_entry(
+        db.entry(session_id.to_string()).or_default(),
+        namespace,
+        key,
+        value,
+        source,
+        expires_at,
+    )
+}
+
+/// Drop the sessions `purge` selects from every ephemeral store in `dir`.
+/// Returns the number of entries removed.
+fn purge_sessions_in(
+    dir: &Path,
+    mut purge: impl FnMut(&str, &MemoryDb) -> bool,
+) -> Result<usize, String> {
+    let Ok(read) = fs::read_dir(dir) else {
+        return Ok(0);
+    };
+    let suffix = format!(".{}", EPHEMERAL_EXTENSION);
+    let mut removed = 0;
+    for entry in read.flatten() {
+        let path = entry.path();
+        let is_ephemeral = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.ends_with(&suffix));
+        if !is_ephemeral {
+            continue;
+        }
+        removed += update_db_file(&path, |db: &mut EphemeralDb| {
+            let mut dropped = 0;
+            db.retain(|session, entries| {
+                let purged = purge(session, entries);
+                if purged {
+                    dropped += entries.len();
+                }
+                !purged
+            });
+            Ok(dropped)
+        })?;
+    }
+    Ok(removed)
+}
+
+/// True when no entry in `entries` was written at or after `cutoff`.
+fn is_stale(entries: &MemoryDb, cutoff: DateTime<Utc>) -> bool {
+    entries
+        .values()
+        .filter_map(|e| DateTime::parse_from_rfc3339(&e.timestamp).ok())
+        .all(|ts| ts < cutoff)
+}
+
+/// Remove every entry stored under `session_id`, across all projects.
+/// Returns the number of entries removed.
+pub fn end_session(session_id: &str) -> Result<usize, String> {
+    purge_sessions_in(&get_memory_dir()?, |session, _| session == session_id)
+}
+
+/// Remove the entries of sessions that are no longer registered as active
+/// and have not written anything for [`STALE_SESSION_HOURS`].  Covers
+/// sessions whose SessionEnd hook never ran.
+pub fn prune_stale_session_memories() -> Result<usize, String> {
+    let active: HashSet<String> =
+        serde_json::from_str::<HashMap<String, serde_json::Value>>(&crate::core::list_sessions()?)
+            .map(|sessions| sessions.into_keys().collect())
+            .unwrap_or_default();
+    let cutoff = Utc::now() - Duration::hours(STALE_SESSION_HOURS);
+    purge_sessions_in(&get_memory_dir()?, |session, entries| {
+        !active.contains(session) && is_stale(entries, cutoff)
+    })
+}
+
+// ── Global scope ─────────────────────────────────────────────────────────────
+//
+// Facts about the user rather than a project (preferred commit style, GitHub
+// handle) are stored under the reserved [`GLOBAL_SCOPE`] name, in
+// `memory/_global.json`, and work with every memory call in place of a
+// project.  Project listings include them on request, labelled by scope.
+
+/// Reserved scope for memories shared by every project and session.
+pub const GLOBAL_SCOPE: &str = "_global";
+
+// ── Expiry ───────────────────────────────────────────────────────────────────
+//
+// Entries stored with a TTL carry an `expires_at` timestamp.  Reads hide
+// expired entries straight away and prune them from disk lazily, so the
+// store does not fill up with transient context.  Entries without an expiry
+// are permanent.
+
+/// The `expires_at` timestamp for an entry stored now with `ttl_seconds`.
+fn expiry_for_ttl(ttl_seconds: Option<u64>) -> Result<Option<String>, String> {
+    let Some(ttl) = ttl_seconds else {
+        return Ok(None);
+    };
+    i64::try_from(ttl)
+        .ok()
+        .filter(|secs| *secs > 0)
+        .and_then(Duration::try_seconds)
+        .and_then(|ttl| Utc::now().checked_add_signed(ttl))
+        .map(|at| Some(at.to_rfc3339()))
+        .ok_or_else(|| format!("Invalid ttl_seconds {}", ttl))
+}
+
+/// Remove expired entries.  Returns the number removed.
+fn remove_expired(db: &mut MemoryDb, now: DateTime<Utc>) -> usize {
+    let before = db.len();
+    db.retain(|_, entry| !entry.is_expired(now));
+    before - db.len()
+}
+
+/// Remove expired session entries, and sessions left empty by that.
+fn remove_expired_sessions(db: &mut EphemeralDb, now: DateTime<Utc>) -> usize {
+    let removed = db
+        .values_mut()
+        .map(|entries| remove_expired(entries, now))
+        .sum();
+    db.retain(|_, entries| !entries.is_empty());
+    removed
+}
+
+/// Apply `prune` to the database at `path`, only taking the locks and
+/// rewriting the file when an unlocked read shows there is something to
+/// remove.
+fn prune_file<D>(path: &Path, prune: impl Fn(&mut D) -> usize) -> Result<usize, String>
+where
+    D: Serialize + DeserializeOwned + Default,
+{
+    let mut current: D = read_db_file(path)?;
+    if prune(&mut current) == 0 {
+        return Ok(0);
+    }
+    update_db_file(path, |db| Ok(prune(db)))
+}
+
+/// Remove the project's expired entries from both the long-term and the
+/// session store.  Returns the number of entries removed.
+pub fn prune_expired(project_name: &str) -> Result<usize, String> {
+    let removed = prune_expired_in(&get_project_memory_path(project_name)?, Utc::now())?;
+    if removed > 0 {
+        crate::activity::log(
+            project_name,
+            crate::activity::ActivityEvent::MemoryCleared,
+            "Expired memories pruned",
+            &format!("{} entries", removed),
+        );
+    }
+    Ok(removed)
+}
+
+fn prune_expired_in(path: &Path, now: DateTime<Utc>) -> Result<usize, String> {
+    Ok(
+        prune_file(path, |db: &mut MemoryDb| remove_expired(db, now))?
+            + prune_file(
+                &path.with_extension(EPHEMERAL_EXTENSION),
+                |db: &mut EphemeralDb| remove_expired_sessions(db, now),
+            )?,
+    )
+}
+
+/// [`prune_expired`] on the read path: a failed prune must not fail the
+/// read, whose results are filtered anyway.
+fn prune_expired_lazily(project_name: &str) {
+    if let Err(e) = prune_expired(project_name) {
+        eprintln!(
+            "[automatic] Failed to prune expired memories for '{}': {}",
+            project_name, e
+        );
+    }
 }
 
 // ── Path-injectable helpers used by tests ────────────────────────────────────
 
+#[cfg(test)]
+fn db_path_at(base: &Path, project_name: &str) -> PathBuf {
+    base.join(format!("{}.json", project_name))
+}
+
 #[cfg(test)]
 /// Read the memory DB from an explicit base directory (used in tests).
 fn read_db_at(base: &Path, project_name: &str) -> Result<MemoryDb, String> {
-    let path = base.join(format!("{}.json", project_name));
-    if !path.exists() {
-        return Ok(HashMap::new());
-    }
-    let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    Ok(serde_json::from_str(&raw).unwrap_or_default())
+    read_db_file(&db_path_at(base, project_name))
 }
 
 #[cfg(test)]
 /// Write the memory DB to an explicit base directory (used in tests).
 fn write_db_at(base: &Path, project_name: &str, db: &MemoryDb) -> Result<(), String> {
-    let path = base.join(format!("{}.json", project_name));
-    let raw = serde_json::to_string_pretty(db).map_err(|e| e.to_string())?;
-    fs::write(&path, raw).map_err(|e| e.to_string())
+    write_db_file(&db_path_at(base, project_name), db)
 }
 
 #[cfg(test)]
@@ -84,31 +597,42 @@ fn store_at(
     value: &str,
     source: Option<&str>,
 ) -> Result<String, String> {
-    let mut db = read_db_at(base, project_name)?;
-    db.insert(
-        key.to_string(),
-        MemoryEntry {
-            value: value.to_string(),
-            timestamp: current_timestamp(),
-            source: source.map(|s| s.to_string()),
-            created_by: None,
-        },
-    );
-    write_db_at(base, project_name, &db)?;
+    update_db_file(&db_path_at(base, project_name), |db| {
+        insert_entry(db, DEFAULT_NAMESPACE, key, value, source, None)
+    })?;
     Ok(format!(
         "Memory stored: key='{}' for project '{}'",
         key, project_name
     ))
 }
 
+#[cfg(test)]
+/// Store a session-scoped entry into an explicit base directory (used in tests).
+fn store_session_at(base: &Path, project_name: &str, session_id: &str, key: &str, value: &str) {
+    let path = db_path_at(base, project_name).with_extension(EPHEMERAL_EXTENSION);
+    update_db_file(&path, |db| {
+        insert_session_entry(db, session_id, DEFAULT_NAMESPACE, key, value, None, None)
+    })
+    .unwrap();
+}
+
+#[cfg(test)]
+/// Read the long-term and session-scoped entries from an explicit base
+/// directory (used in tests).
+fn read_scoped_at(base: &Path, project_name: &str) -> (MemoryDb, EphemeralDb) {
+    let path = db_path_at(base, project_name);
+    (
+        read_db_file(&path).unwrap(),
+        read_db_file(&path.with_extension(EPHEMERAL_EXTENSION)).unwrap(),
+    )
+}
+
 #[cfg(test)]
 /// Delete a memory entry from an explicit base directory (used in tests).
 fn delete_at(base: &Path, project_name: &str, key: &str) -> Result<String, String> {
-    let mut db = read_db_at(base, project_name)?;
-    if db.remove(key).is_none() {
-        return Err(format!("Memory key '{}' not found", key));
-    }
-    write_db_at(base, project_name, &db)?;
+    update_db_file(&db_path_at(base, project_name), |db| {
+        remove_entry(db, None, key)
+    })?;
     Ok(format!(
         "Memory deleted: key='{}' for project '{}'",
         key, project_name
@@ -126,26 +650,22 @@ fn clear_at(
     if !confirm {
         return Err("Deletion not confirmed.".to_string());
     }
-    let mut db = read_db_at(base, project_name)?;
-    let deleted_count = if let Some(pat) = pattern {
-        let pat_lower = pat.to_lowercase();
-        let keys: Vec<String> = db
-            .keys()
-            .filter(|k| k.to_lowercase().contains(&pat_lower))
-            .cloned()
-            .collect();
-        let n = keys.len();
-        for k in keys {
-            db.remove(&k);
-        }
-        n
-    } else {
-        let n = db.len();
-        db.clear();
-        n
-    };
-    write_db_at(base, project_name, &db)?;
-    Ok(deleted_count)
+    update_db_file(&db_path_at(base, project_name), |db| {
+        Ok(clear_entries(db, pattern, None))
+    })
+}
+
+#[cfg(test)]
+/// Import a memory export into an explicit base directory (used in tests).
+fn import_at(
+    base: &Path,
+    project_name: &str,
+    data: &str,
+    strategy: MemoryImportStrategy,
+) -> Result<MemoryImportReport, String> {
+    update_db_file(&db_path_at(base, project_name), |db| {
+        import_document(db, data, strategy)
+    })
 }
 
 /// Generates an ISO 8601 timestamp for the current time.
@@ -158,7 +678,11 @@ pub fn current_timestamp() -> String {
 // ============================================================================
 
 pub fn get_all_memories(project_name: &str) -> Result<MemoryDb, String> {
-    read_memory_db(project_name)
+    prune_expired_lazily(project_name);
+    let now = Utc::now();
+    let mut db = read_memory_db(project_name)?;
+    db.retain(|_, entry| !entry.is_expired(now));
+    Ok(db)
 }
 
 // ============================================================================
@@ -170,20 +694,43 @@ pub fn store_memory(
     key: &str,
     value: &str,
     source: Option<&str>,
+    session_id: Option<&str>,
+    ttl_seconds: Option<u64>,
+    namespace: Option<&str>,
 ) -> Result<String, String> {
-    let mut db = read_memory_db(project_name)?;
+    let namespace = namespace_or_default(namespace)?;
+    let expires_at = expiry_for_ttl(ttl_seconds)?;
+    let expiry_note = expires_at
+        .as_deref()
+        .map(|at| format!(" (expires {})", at))
+        .unwrap_or_default();
+    let key_note = if is_default_namespace(namespace) {
+        format!("key='{}'", key)
+    } else {
+        format!("key='{}' in namespace '{}'", key, namespace)
+    };
 
-    db.insert(
-        key.to_string(),
-        MemoryEntry {
-            value: value.to_string(),
-            timestamp: current_timestamp(),
-            source: source.map(|s| s.to_string()),
-            created_by: None,
-        },
-    );
+    if let Some(session) = session_id {
+        update_db_file(&get_ephemeral_memory_path(project_name)?, |db| {
+            insert_session_entry(
+                db,
+                session,
+                namespace,
+                key,
+                value,
+                source,
+                expires_at.as_deref(),
+            )
+        })?;
+        return Ok(format!(
+            "Memory stored: {} for session '{}' of project '{}' (removed when the session ends){}",
+            key_note, session, project_name, expiry_note
+        ));
+    }
 
-    write_memory_db(project_name, &db)?;
+    update_db_file(&get_project_memory_path(project_name)?, |db| {
+        insert_entry(db, namespace, key, value, source, expires_at.as_deref())
+    })?;
 
     crate::activity::log(
         project_name,
@@ -193,126 +740,235 @@ pub fn store_memory(
     );
 
     Ok(format!(
-        "Memory stored: key='{}' for project '{}'",
-        key, project_name
+        "Memory stored: {} for project '{}'{}",
+        key_note, project_name, expiry_note
     ))
 }
 
-pub fn get_memory(project_name: &str, key: &str) -> Result<String, String> {
+/// Read the long-term store, plus the session store when `scope` can see
+/// any of it, pruning expired entries first.
+fn read_scoped(project_name: &str, scope: SessionScope) -> Result<(MemoryDb, EphemeralDb), String> {
+    prune_expired_lazily(project_name);
     let db = read_memory_db(project_name)?;
+    let ephemeral = if scope.sees_any() {
+        read_db_file(&get_ephemeral_memory_path(project_name)?)?
+    } else {
+        EphemeralDb::new()
+    };
+    Ok((db, ephemeral))
+}
 
-    if let Some(entry) = db.get(key) {
+/// Read `key` from `namespace`, or from [`DEFAULT_NAMESPACE`] without one.
+pub fn get_memory(
+    project_name: &str,
+    key: &str,
+    scope: SessionScope,
+    namespace: Option<&str>,
+) -> Result<String, String> {
+    let namespace = namespace_or_default(namespace)?;
+    let (db, ephemeral) = read_scoped(project_name, scope)?;
+    let visible = in_namespace(visible_entries(&db, &ephemeral, scope), Some(namespace));
+    render_memory(key, &visible, scope)
+}
+
+/// Render `key`, preferring the caller's own session entry over the
+/// long-term one.
+fn render_memory(
+    key: &str,
+    visible: &[VisibleEntry],
+    scope: SessionScope,
+) -> Result<String, String> {
+    let matches: Vec<&VisibleEntry> = visible.iter().filter(|v| v.key == key).collect();
+    let found = matches
+        .iter()
+        .find(|v| v.session.is_some() && v.session == scope.session_id)
+        .or_else(|| matches.first());
+
+    if let Some(v) = found {
+        let entry = v.entry;
         let mut output = format!("# Memory: {}\n\n", key);
         output.push_str(&format!("**Value:** {}\n", entry.value));
         output.push_str(&format!("**Timestamp:** {}\n", entry.timestamp));
+        if let Some(expires_at) = &entry.expires_at {
+            output.push_str(&format!("**Expires:** {}\n", expires_at));
+        }
         if let Some(src) = &entry.source {
             output.push_str(&format!("**Source:** {}\n", src));
         }
+        if !is_default_namespace(&entry.namespace) {
+            output.push_str(&format!("**Namespace:** {}\n", entry.namespace));
+        }
+        if let Some(session) = v.session {
+            output.push_str(&format!("**Session:** {}\n", session));
+        }
         Ok(output)
     } else {
         Err(format!("Memory key '{}' not found", key))
     }
 }
 
-pub fn list_memories(project_name: &str, pattern: Option<&str>) -> Result<String, String> {
-    let db = read_memory_db(project_name)?;
-
-    if db.is_empty() {
-        return Ok(format!("No memories stored for project '{}'", project_name));
+/// List the memories in `namespace`, or in every namespace without one.
+/// With `include_global` the [`GLOBAL_SCOPE`] entries are listed too.
+pub fn list_memories(
+    project_name: &str,
+    pattern: Option<&str>,
+    scope: SessionScope,
+    namespace: Option<&str>,
+    include_global: bool,
+) -> Result<String, String> {
+    let namespace = namespace_filter(namespace)?;
+    let (db, ephemeral) = read_scoped(project_name, scope)?;
+    let global = if include_global && project_name != GLOBAL_SCOPE {
+        prune_expired_lazily(GLOBAL_SCOPE);
+        read_memory_db(GLOBAL_SCOPE)?
+    } else {
+        MemoryDb::new()
+    };
+    let visible = with_global(visible_entries(&db, &ephemeral, scope), &global);
+    let visible = in_namespace(visible, namespace);
+    if let (Some(ns), true) = (namespace, visible.is_empty()) {
+        return Ok(format!(
+            "No memories in namespace '{}' for project '{}'",
+            ns, project_name
+        ));
     }
+    Ok(render_memory_list(project_name, pattern, &visible))
+}
 
-    let mut keys: Vec<&String> = db.keys().collect();
-    keys.sort();
+fn render_memory_list(
+    project_name: &str,
+    pattern: Option<&str>,
+    visible: &[VisibleEntry],
+) -> String {
+    if visible.is_empty() {
+        return format!("No memories stored for project '{}'", project_name);
+    }
 
-    let filtered_keys: Vec<&String> = if let Some(pat) = pattern {
+    let filtered: Vec<&VisibleEntry> = if let Some(pat) = pattern {
         let pat_lower = pat.to_lowercase();
-        keys.into_iter()
-            .filter(|k| k.to_lowercase().contains(&pat_lower))
+        visible
+            .iter()
+            .filter(|v| v.key.to_lowercase().contains(&pat_lower))
             .collect()
     } else {
-        keys
+        visible.iter().collect()
     };
 
-    if filtered_keys.is_empty() {
-        return Ok(format!(
+    if filtered.is_empty() {
+        return format!(
             "No memories matching pattern '{}' for project '{}'",
             pattern.unwrap_or(""),
             project_name
-        ));
+        );
     }
 
     let mut output = format!("# Memories for '{}'\n\n", project_name);
     if let Some(pat) = pattern {
         output.push_str(&format!("Filtered by: {}\n\n", pat));
     }
-
-    for key in filtered_keys {
-        if let Some(entry) = db.get(key) {
-            output.push_str(&format!("- **{}**\n", key));
-            output.push_str(&format!("  Timestamp: {}\n", entry.timestamp));
-            if let Some(src) = &entry.source {
-                output.push_str(&format!("  Source: {}\n", src));
-            }
-            let preview = if entry.value.len() > 100 {
-                format!("{}...", &entry.value[..100])
-            } else {
-                entry.value.clone()
-            };
-            output.push_str(&format!("  Preview: {}\n", preview));
+    let labelled = filtered.iter().any(|v| v.global);
+
+    for v in filtered {
+        let entry = v.entry;
+        output.push_str(&format!("- **{}**\n", v.key));
+        if labelled {
+            let scope = if v.global { "global" } else { "project" };
+            output.push_str(&format!("  Scope: {}\n", scope));
         }
+        output.push_str(&format!("  Namespace: {}\n", entry.namespace));
+        output.push_str(&format!("  Timestamp: {}\n", entry.timestamp));
+        if let Some(expires_at) = &entry.expires_at {
+            output.push_str(&format!("  Expires: {}\n", expires_at));
+        }
+        if let Some(src) = &entry.source {
+            output.push_str(&format!("  Source: {}\n", src));
+        }
+        if let Some(session) = v.session {
+            output.push_str(&format!("  Session: {}\n", session));
+        }
+        let preview = if entry.value.len() > 100 {
+            format!("{}...", &entry.value[..100])
+        } else {
+            entry.value.clone()
+        };
+        output.push_str(&format!("  Preview: {}\n", preview));
     }
 
-    Ok(output)
+    output
 }
 
-pub fn search_memories(project_name: &str, query: &str) -> Result<String, String> {
-    let db = read_memory_db(project_name)?;
+/// Search the memories in `namespace`, or in every namespace without one.
+pub fn search_memories(
+    project_name: &str,
+    query: &str,
+    scope: SessionScope,
+    namespace: Option<&str>,
+) -> Result<String, String> {
+    let namespace = namespace_filter(namespace)?;
+    let (db, ephemeral) = read_scoped(project_name, scope)?;
+    let visible = in_namespace(visible_entries(&db, &ephemeral, scope), namespace);
+    if let (Some(ns), true) = (namespace, visible.is_empty()) {
+        return Ok(format!(
+            "No memories in namespace '{}' for project '{}'",
+            ns, project_name
+        ));
+    }
+    Ok(render_search(project_name, query, &visible))
+}
 
-    if db.is_empty() {
-        return Ok(format!("No memories stored for project '{}'", project_name));
+fn render_search(project_name: &str, query: &str, visible: &[VisibleEntry]) -> String {
+    if visible.is_empty() {
+        return format!("No memories stored for project '{}'", project_name);
     }
 
     let query_lower = query.to_lowercase();
-    let mut matches: Vec<(&String, &MemoryEntry)> = db
+    let matches: Vec<&VisibleEntry> = visible
         .iter()
-        .filter(|(k, v)| {
-            k.to_lowercase().contains(&query_lower) || v.value.to_lowercase().contains(&query_lower)
+        .filter(|v| {
+            v.key.to_lowercase().contains(&query_lower)
+                || v.entry.value.to_lowercase().contains(&query_lower)
         })
         .collect();
 
     if matches.is_empty() {
-        return Ok(format!(
+        return format!(
             "No memories matching query '{}' for project '{}'",
             query, project_name
-        ));
+        );
     }
 
-    matches.sort_by_key(|(k, _)| *k);
-
     let mut output = format!("# Search results for '{}' in '{}'\n\n", query, project_name);
     output.push_str(&format!("Found {} match(es)\n\n", matches.len()));
 
-    for (key, entry) in matches {
-        output.push_str(&format!("## {}\n", key));
+    for v in matches {
+        let entry = v.entry;
+        output.push_str(&format!("## {}\n", v.key));
+        output.push_str(&format!("**Namespace:** {}\n", entry.namespace));
         output.push_str(&format!("**Value:** {}\n", entry.value));
         output.push_str(&format!("**Timestamp:** {}\n", entry.timestamp));
         if let Some(src) = &entry.source {
             output.push_str(&format!("**Source:** {}\n", src));
         }
+        if let Some(session) = v.session {
+            output.push_str(&format!("**Session:** {}\n", session));
+        }
         output.push('\n');
     }
 
-    Ok(output)
+    output
 }
 
-pub fn delete_memory(project_name: &str, key: &str) -> Result<String, String> {
-    let mut db = read_memory_db(project_name)?;
-
-    if db.remove(key).is_none() {
-        return Err(format!("Memory key '{}' not found", key));
-    }
-
-    write_memory_db(project_name, &db)?;
+/// Delete `key` from `namespace`.  Without a namespace `key` is the key as
+/// stored, as returned by [`get_all_memories`].
+pub fn delete_memory(
+    project_name: &str,
+    key: &str,
+    namespace: Option<&str>,
+) -> Result<String, String> {
+    let namespace = namespace_filter(namespace)?;
+    update_db_file(&get_project_memory_path(project_name)?, |db| {
+        remove_entry(db, namespace, key)
+    })?;
 
     crate::activity::log(
         project_name,
@@ -327,41 +983,33 @@ pub fn delete_memory(project_name: &str, key: &str) -> Result<String, String> {
     ))
 }
 
+/// Clear the memories matching `pattern`, or all of them, in `namespace`
+/// when one is given and in every namespace otherwise.
 pub fn clear_memories(
     project_name: &str,
     pattern: Option<&str>,
     confirm: bool,
+    namespace: Option<&str>,
 ) -> Result<String, String> {
     if !confirm {
         return Err("Deletion not confirmed. Set 'confirm' to true to proceed.".to_string());
     }
+    let namespace = namespace_filter(namespace)?;
 
-    let mut db = read_memory_db(project_name)?;
-    let deleted_count;
-
-    if let Some(pat) = pattern {
-        let pat_lower = pat.to_lowercase();
-        let keys_to_delete: Vec<String> = db
-            .keys()
-            .filter(|k| k.to_lowercase().contains(&pat_lower))
-            .cloned()
-            .collect();
-
-        deleted_count = keys_to_delete.len();
-        for key in keys_to_delete {
-            db.remove(&key);
-        }
-    } else {
-        deleted_count = db.len();
-        db.clear();
-    }
-
-    write_memory_db(project_name, &db)?;
+    let deleted_count = update_db_file(&get_project_memory_path(project_name)?, |db| {
+        Ok(clear_entries(db, pattern, namespace))
+    })?;
 
+    let namespace_note = namespace
+        .map(|ns| format!(" in namespace '{}'", ns))
+        .unwrap_or_default();
     let detail = if let Some(pat) = pattern {
-        format!("{} entries matching '{}'", deleted_count, pat)
+        format!(
+            "{} entries matching '{}'{}",
+            deleted_count, pat, namespace_note
+        )
     } else {
-        format!("{} entries", deleted_count)
+        format!("{} entries{}", deleted_count, namespace_note)
     };
 
     crate::activity::log(
@@ -373,22 +1021,217 @@ pub fn clear_memories(
 
     if let Some(pat) = pattern {
         Ok(format!(
-            "Cleared {} memor{} matching pattern '{}' for project '{}'",
+            "Cleared {} memor{} matching pattern '{}'{} for project '{}'",
             deleted_count,
             if deleted_count == 1 { "y" } else { "ies" },
             pat,
+            namespace_note,
             project_name
         ))
     } else {
         Ok(format!(
-            "Cleared all {} memor{} for project '{}'",
+            "Cleared all {} memor{}{} for project '{}'",
             deleted_count,
             if deleted_count == 1 { "y" } else { "ies" },
+            namespace_note,
             project_name
         ))
     }
 }
 
+// ============================================================================
+// Export / Import
+// ============================================================================
+
+/// Version of the document written by [`export_memories`].
+pub const MEMORY_EXPORT_FORMAT: u32 = 1;
+
+/// A project's long-term memories as a portable document.  Session-scoped
+/// entries are not exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryExport {
+    pub format: u32,
+    /// The project the memories were exported from.
+    pub project: String,
+    pub exported_at: String,
+    /// Entries in key order.
+    pub entries: Vec<ExportedMemory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedMemory {
+    pub key: String,
+    #[serde(flatten)]
+    pub entry: MemoryEntry,
+}
+
+/// What [`import_memories`] does with keys the project already holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryImportStrategy {
+    /// Keep the existing entry.
+    Merge,
+    /// Replace the existing entry.
+    Overwrite,
+    /// Import nothing if any existing entry has a different value.
+    FailOnConflict,
+}
+
+/// Outcome of [`import_memories`].
+#[derive(Debug, Default, Serialize)]
+pub struct MemoryImportReport {
+    /// Keys that were new to the project.
+    pub imported: Vec<String>,
+    /// Existing keys replaced by the imported entry.
+    pub overwritten: Vec<String>,
+    /// Existing keys left as they were.
+    pub skipped: Vec<String>,
+}
+
+/// Serialise the project's long-term memories as a [`MemoryExport`].
+pub fn export_memories(project_name: &str) -> Result<String, String> {
+    export_document(project_name, &read_memory_db(project_name)?)
+}
+
+/// Import a [`MemoryExport`] document into the project.  The document is
+/// validated in full first; a malformed one, or a conflict under
+/// [`MemoryImportStrategy::FailOnConflict`], changes nothing.
+pub fn import_memories(
+    project_name: &str,
+    data: &str,
+    strategy: MemoryImportStrategy,
+) -> Result<MemoryImportReport, String> {
+    let report = update_db_file(&get_project_memory_path(project_name)?, |db| {
+        import_document(db, data, strategy)
+    })?;
+
+    crate::activity::log(
+        project_name,
+        crate::activity::ActivityEvent::MemoryStored,
+        "Memories imported",
+        &format!(
+            "{} new, {} overwritten, {} skipped",
+            report.imported.len(),
+            report.overwritten.len(),
+            report.skipped.len()
+        ),
+    );
+
+    Ok(report)
+}
+
+fn export_document(project_name: &str, db: &MemoryDb) -> Result<String, String> {
+    let now = Utc::now();
+    let mut entries: Vec<ExportedMemory> = db
+        .iter()
+        .filter(|(_, entry)| !entry.is_expired(now))
+        .map(|(key, entry)| ExportedMemory {
+            key: key.clone(),
+            entry: entry.clone(),
+        })
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    let export = MemoryExport {
+        format: MEMORY_EXPORT_FORMAT,
+        project: project_name.to_string(),
+        exported_at: current_timestamp(),
+        entries,
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+/// Parse and validate `data`, then apply it to `db`.  `db` is only changed
+/// once every entry has been checked.
+fn import_document(
+    db: &mut MemoryDb,
+    data: &str,
+    strategy: MemoryImportStrategy,
+) -> Result<MemoryImportReport, String> {
+    let export: MemoryExport =
+        serde_json::from_str(data).map_err(|e| format!("Invalid memory export: {}", e))?;
+    if export.format != MEMORY_EXPORT_FORMAT {
+        return Err(format!(
+            "Unsupported memory export format {} (expected {})",
+            export.format, MEMORY_EXPORT_FORMAT
+        ));
+    }
+    let mut seen = HashSet::new();
+    for item in &export.entries {
+        if item.key.trim().is_empty() {
+            return Err("Invalid memory export: an entry has an empty key".into());
+        }
+        if !seen.insert(item.key.as_str()) {
+            return Err(format!(
+                "Invalid memory export: key '{}' appears more than once",
+                item.key
+            ));
+        }
+        if DateTime::parse_from_rfc3339(&item.entry.timestamp).is_err() {
+            return Err(format!(
+                "Invalid memory export: entry '{}' has an invalid timestamp '{}'",
+                item.key, item.entry.timestamp
+            ));
+        }
+        validate_namespace(&item.entry.namespace)
+            .map_err(|e| format!("Invalid memory export: entry '{}': {}", item.key, e))?;
+        if stored_key(&item.entry.namespace, item.entry.key_in(&item.key)) != item.key {
+            return Err(format!(
+                "Invalid memory export: key '{}' does not belong to namespace '{}'",
+                item.key, item.entry.namespace
+            ));
+        }
+        if let Some(expires_at) = &item.entry.expires_at {
+            if DateTime::parse_from_rfc3339(expires_at).is_err() {
+                return Err(format!(
+                    "Invalid memory export: entry '{}' has an invalid expiry '{}'",
+                    item.key, expires_at
+                ));
+            }
+        }
+    }
+
+    if strategy == MemoryImportStrategy::FailOnConflict {
+        let conflicts: Vec<&str> = export
+            .entries
+            .iter()
+            .filter(|item| {
+                db.get(&item.key)
+                    .is_some_and(|existing| existing.value != item.entry.value)
+            })
+            .map(|item| item.key.as_str())
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(format!(
+                "Import cancelled: {} existing key(s) differ: {}",
+                conflicts.len(),
+                conflicts.join(", ")
+            ));
+        }
+    }
+
+    let mut report = MemoryImportReport::default();
+    for item in export.entries {
+        let replace = match db.get(&item.key) {
+            None => {
+                report.imported.push(item.key.clone());
+                true
+            }
+            Some(_) if strategy == MemoryImportStrategy::Overwrite => {
+                report.overwritten.push(item.key.clone());
+                true
+            }
+            Some(_) => {
+                report.skipped.push(item.key.clone());
+                false
+            }
+        };
+        if replace {
+            db.insert(item.key, item.entry);
+        }
+    }
+    Ok(report)
+}
+
 // ============================================================================
 // Claude Auto-Memory Integration
 // ============================================================================
@@ -673,6 +1516,8 @@ mod tests {
                 timestamp: current_timestamp(),
                 source: Some("claude-code".to_string()),
                 created_by: None,
+                expires_at: None,
+                namespace: DEFAULT_NAMESPACE.into(),
             },
         );
         write_db_at(dir.path(), "proj", &db).unwrap();
@@ -685,6 +1530,110 @@ mod tests {
         );
     }
 
+    // ── concurrency ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn concurrent_stores_of_distinct_keys_are_all_kept() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().to_path_buf();
+        let handles: Vec<_> = (0..16)
+            .map(|t| {
+                let base = base.clone();
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        let key = format!("thread-{}/key-{}", t, i);
+                        store_at(&base, "proj", &key, "v", None).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let db = read_db_at(&base, "proj").unwrap();
+        assert_eq!(db.len(), 16 * 25);
+    }
+
+    #[test]
+    fn writer_waits_for_other_process_lock_and_merges_its_changes() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().to_path_buf();
+        store_at(&base, "proj", "existing", "v", None).unwrap();
+
+        // Another process holds the lock on its own file handle...
+        let path = db_path_at(&base, "proj");
+        let other = crate::core::lock_file(&path).unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let writer = {
+            let base = base.clone();
+            std::thread::spawn(move || {
+                store_at(&base, "proj", "mine", "v", None).unwrap();
+                tx.send(()).unwrap();
+            })
+        };
+        let blocked = rx.recv_timeout(std::time::Duration::from_millis(200));
+        assert!(
+            blocked.is_err(),
+            "store must wait for the other lock holder"
+        );
+
+        // ...writes its own entry, then releases the lock.
+        let mut db: MemoryDb = read_db_file(&path).unwrap();
+        insert_entry(
+            &mut db,
+            DEFAULT_NAMESPACE,
+            "theirs",
+            "v",
+            Some("other-session"),
+            None,
+        )
+        .unwrap();
+        write_db_file(&path, &db).unwrap();
+        drop(other);
+
+        writer.join().unwrap();
+        let db = read_db_at(&base, "proj").unwrap();
+        let mut keys: Vec<&str> = db.keys().map(|k| k.as_str()).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["existing", "mine", "theirs"]);
+    }
+
+    #[test]
+    fn delete_and_clear_merge_with_concurrent_stores() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().to_path_buf();
+        for i in 0..8 {
+            store_at(&base, "proj", &format!("old/{}", i), "v", None).unwrap();
+            store_at(&base, "proj", &format!("stale/{}", i), "v", None).unwrap();
+        }
+
+        let clearer = {
+            let base = base.clone();
+            std::thread::spawn(move || clear_at(&base, "proj", Some("stale/"), true).unwrap())
+        };
+        let writers: Vec<_> = (0..8)
+            .map(|t| {
+                let base = base.clone();
+                std::thread::spawn(move || {
+                    store_at(&base, "proj", &format!("new/{}", t), "v", None).unwrap();
+                    delete_at(&base, "proj", &format!("old/{}", t)).unwrap();
+                })
+            })
+            .collect();
+        assert_eq!(clearer.join().unwrap(), 8);
+        for h in writers {
+            h.join().unwrap();
+        }
+
+        let db = read_db_at(&base, "proj").unwrap();
+        let mut keys: Vec<&str> = db.keys().map(|k| k.as_str()).collect();
+        keys.sort();
+        let expected: Vec<String> = (0..8).map(|t| format!("new/{}", t)).collect();
+        assert_eq!(keys, expected);
+    }
+
     // ── project isolation ────────────────────────────────────────────────────
 
     #[test]
@@ -699,4 +1648,463 @@ mod tests {
         assert_eq!(a["shared-key"].value, "alpha");
         assert_eq!(b["shared-key"].value, "beta");
     }
+
+    #[test]
+    fn ad_hoc_directories_with_same_name_are_isolated() {
+        let base = tempdir().unwrap();
+        let checkouts = tempdir().unwrap();
+        let keys: Vec<String> = ["checkout-a", "checkout-b"]
+            .iter()
+            .map(|d| {
+                let dir = checkouts.path().join(d);
+                fs::create_dir_all(&dir).unwrap();
+                crate::core::project_storage_key(&crate::core::Project {
+                    name: "shared-name".into(),
+                    directory: dir.display().to_string(),
+                    unregistered: true,
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        assert_ne!(keys[0], keys[1]);
+        assert!(keys.iter().all(|k| k.starts_with("adhoc-")));
+
+        store_at(base.path(), &keys[0], "ci/cache", "warm", None).unwrap();
+        assert!(read_db_at(base.path(), &keys[1]).unwrap().is_empty());
+        assert_eq!(
+            read_db_at(base.path(), &keys[0]).unwrap()["ci/cache"].value,
+            "warm"
+        );
+    }
+
+    // ── session scope ────────────────────────────────────────────────────────
+
+    fn scope(session_id: Option<&str>, include_ephemeral: bool) -> SessionScope<'_> {
+        SessionScope {
+            session_id,
+            include_ephemeral,
+        }
+    }
+
+    #[test]
+    fn sessions_only_see_their_own_ephemeral_entries() {
+        let base = tempdir().unwrap();
+        store_at(base.path(), "proj", "plan", "ship it", None).unwrap();
+        store_session_at(base.path(), "proj", "s1", "reviewed", "a.rs");
+        store_session_at(base.path(), "proj", "s2", "reviewed", "b.rs");
+
+        let (db, ephemeral) = read_scoped_at(base.path(), "proj");
+        assert_eq!(
+            db.len(),
+            1,
+            "ephemeral entries stay out of the long-term store"
+        );
+
+        let s1 = scope(Some("s1"), false);
+        let visible = visible_entries(&db, &ephemeral, s1);
+        let keys: Vec<(&str, Option<&str>)> = visible.iter().map(|v| (v.key, v.session)).collect();
+        assert_eq!(keys, vec![("plan", None), ("reviewed", Some("s1"))]);
+        let got = render_memory("reviewed", &visible, s1).unwrap();
+        assert!(got.contains("a.rs") && !got.contains("b.rs"), "{}", got);
+
+        let s2 = scope(Some("s2"), false);
+        let got = render_memory("reviewed", &visible_entries(&db, &ephemeral, s2), s2).unwrap();
+        assert!(got.contains("b.rs") && !got.contains("a.rs"), "{}", got);
+
+        let none = SessionScope::default();
+        let visible = visible_entries(&db, &ephemeral, none);
+        assert!(render_memory("reviewed", &visible, none).is_err());
+        assert!(!render_search("proj", "rs", &visible).contains("reviewed"));
+
+        let all = scope(None, true);
+        let listing = render_memory_list("proj", None, &visible_entries(&db, &ephemeral, all));
+        assert!(listing.contains("Session: s1") && listing.contains("Session: s2"));
+    }
+
+    #[test]
+    fn own_session_entry_wins_over_long_term_entry() {
+        let base = tempdir().unwrap();
+        store_at(base.path(), "proj", "focus", "long-term", None).unwrap();
+        store_session_at(base.path(), "proj", "s1", "focus", "scratch");
+        let (db, ephemeral) = read_scoped_at(base.path(), "proj");
+
+        let s1 = scope(Some("s1"), false);
+        let got = render_memory("focus", &visible_entries(&db, &ephemeral, s1), s1).unwrap();
+        assert!(got.contains("scratch") && got.contains("**Session:** s1"));
+
+        let all = scope(None, true);
+        let got = render_memory("focus", &visible_entries(&db, &ephemeral, all), all).unwrap();
+        assert!(got.contains("long-term"));
+    }
+
+    #[test]
+    fn ending_a_session_purges_only_its_entries() {
+        let base = tempdir().unwrap();
+        store_at(base.path(), "proj", "plan", "keep", None).unwrap();
+        store_session_at(base.path(), "proj", "s1", "a", "1");
+        store_session_at(base.path(), "proj", "s1", "b", "2");
+        store_session_at(base.path(), "other", "s1", "c", "3");
+        store_session_at(base.path(), "proj", "s2", "a", "4");
+
+        let removed = purge_sessions_in(base.path(), |session, _| session == "s1").unwrap();
+        assert_eq!(removed, 3);
+
+        let (db, ephemeral) = read_scoped_at(base.path(), "proj");
+        assert_eq!(db["plan"].value, "keep");
+        assert!(!ephemeral.contains_key("s1"));
+        assert_eq!(ephemeral["s2"]["a"].value, "4");
+        assert!(read_scoped_at(base.path(), "other").1.is_empty());
+
+        assert_eq!(
+            purge_sessions_in(base.path(), |session, _| session == "s1").unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn sessions_are_stale_once_every_entry_is_older_than_the_cutoff() {
+        let cutoff = Utc::now() - Duration::hours(STALE_SESSION_HOURS);
+        let entry_at = |ts: DateTime<Utc>| MemoryEntry {
+            value: "v".into(),
+            timestamp: ts.to_rfc3339(),
+            source: None,
+            created_by: None,
+            expires_at: None,
+            namespace: DEFAULT_NAMESPACE.into(),
+        };
+        let mut entries = MemoryDb::new();
+        entries.insert("old".into(), entry_at(cutoff - Duration::hours(1)));
+        assert!(is_stale(&entries, cutoff));
+        entries.insert("new".into(), entry_at(Utc::now()));
+        assert!(!is_stale(&entries, cutoff));
+    }
+
+    // ── namespaces ───────────────────────────────────────────────────────────
+
+    fn store_in(base: &Path, namespace: &str, key: &str, value: &str) -> Result<(), String> {
+        update_db_file(&db_path_at(base, "proj"), |db| {
+            insert_entry(db, namespace, key, value, None, None)
+        })
+    }
+
+    #[test]
+    fn namespaces_keep_agents_keys_apart() {
+        let base = tempdir().unwrap();
+        store_in(base.path(), DEFAULT_NAMESPACE, "style", "shared").unwrap();
+        store_in(base.path(), "claude", "style", "tabs").unwrap();
+        store_in(base.path(), "cursor", "style", "spaces").unwrap();
+        store_in(base.path(), "cursor", "todo", "lint").unwrap();
+
+        let db = read_db_at(base.path(), "proj").unwrap();
+        assert_eq!(db.len(), 4, "same key in different namespaces coexists");
+        let ephemeral = EphemeralDb::new();
+        let all = || visible_entries(&db, &ephemeral, SessionScope::default());
+
+        let listing = render_memory_list("proj", None, &all());
+        for ns in ["default", "claude", "cursor"] {
+            assert!(
+                listing.contains(&format!("Namespace: {}", ns)),
+                "{}",
+                listing
+            );
+        }
+        let cursor = in_namespace(all(), Some("cursor"));
+        let keys: Vec<&str> = cursor.iter().map(|v| v.key).collect();
+        assert_eq!(keys, vec!["style", "todo"]);
+        let got = render_memory("style", &cursor, SessionScope::default()).unwrap();
+        assert!(got.contains("spaces") && got.contains("**Namespace:** cursor"));
+        let got = render_memory(
+            "style",
+            &in_namespace(all(), Some(DEFAULT_NAMESPACE)),
+            SessionScope::default(),
+        )
+        .unwrap();
+        assert!(got.contains("shared") && !got.contains("Namespace"));
+
+        let mut db = db;
+        assert_eq!(clear_entries(&mut db, None, Some("cursor")), 2);
+        assert_eq!(clear_entries(&mut db, Some("STY"), Some("claude")), 1);
+        assert_eq!(db.len(), 1);
+        assert_eq!(db["style"].value, "shared");
+        assert!(remove_entry(&mut db, Some("claude"), "style").is_err());
+    }
+
+    #[test]
+    fn entries_without_a_namespace_load_into_the_default_one() {
+        let base = tempdir().unwrap();
+        fs::write(
+            db_path_at(base.path(), "proj"),
+            r#"{"plan":{"value":"ship","timestamp":"2025-01-01T00:00:00Z","source":null}}"#,
+        )
+        .unwrap();
+
+        let db = read_db_at(base.path(), "proj").unwrap();
+        assert_eq!(db["plan"].namespace, DEFAULT_NAMESPACE);
+        let raw = serde_json::to_string(&db).unwrap();
+        assert!(!raw.contains("namespace"), "{}", raw);
+
+        store_in(base.path(), "cursor", "plan", "review").unwrap();
+        let raw = fs::read_to_string(db_path_at(base.path(), "proj")).unwrap();
+        assert!(raw.contains(r#""cursor:plan""#) && raw.contains(r#""namespace": "cursor""#));
+    }
+
+    #[test]
+    fn clashing_stored_keys_and_bad_namespaces_are_rejected() {
+        let base = tempdir().unwrap();
+        store_in(base.path(), "cursor", "plan", "review").unwrap();
+        let err = store_in(base.path(), DEFAULT_NAMESPACE, "cursor:plan", "x").unwrap_err();
+        assert!(err.contains("clashes"), "{}", err);
+        assert_eq!(
+            read_db_at(base.path(), "proj").unwrap()["cursor:plan"].value,
+            "review"
+        );
+
+        for bad in ["", "a:b", "a/b", "with space"] {
+            assert!(validate_namespace(bad).is_err(), "{:?}", bad);
+        }
+        assert_eq!(namespace_or_default(None).unwrap(), DEFAULT_NAMESPACE);
+        assert_eq!(
+            namespace_or_default(Some("claude.code")).unwrap(),
+            "claude.code"
+        );
+    }
+
+    // ── global scope ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn global_entries_are_listed_with_a_project_on_request() {
+        let base = tempdir().unwrap();
+        store_at(base.path(), "proj", "style", "tabs", None).unwrap();
+        store_at(base.path(), GLOBAL_SCOPE, "style", "conventional", None).unwrap();
+        store_at(base.path(), GLOBAL_SCOPE, "handle", "octocat", None).unwrap();
+        let db = read_db_at(base.path(), "proj").unwrap();
+        let global = read_db_at(base.path(), GLOBAL_SCOPE).unwrap();
+        let ephemeral = EphemeralDb::new();
+        let visible = || visible_entries(&db, &ephemeral, SessionScope::default());
+
+        let listing = render_memory_list("proj", None, &visible());
+        assert!(!listing.contains("octocat") && !listing.contains("Scope:"));
+
+        let combined = with_global(visible(), &global);
+        let keys: Vec<(&str, bool)> = combined.iter().map(|v| (v.key, v.global)).collect();
+        assert_eq!(
+            keys,
+            vec![("handle", true), ("style", false), ("style", true)]
+        );
+        let listing = render_memory_list("proj", None, &combined);
+        assert!(listing.contains("Scope: project") && listing.contains("Scope: global"));
+    }
+
+    // ── expiry ───────────────────────────────────────────────────────────────
+
+    fn expiring(value: &str, expires_at: Option<DateTime<Utc>>) -> MemoryEntry {
+        MemoryEntry {
+            value: value.into(),
+            timestamp: current_timestamp(),
+            source: None,
+            created_by: None,
+            expires_at: expires_at.map(|at| at.to_rfc3339()),
+            namespace: DEFAULT_NAMESPACE.into(),
+        }
+    }
+
+    #[test]
+    fn expired_entries_are_hidden_and_pruned() {
+        let base = tempdir().unwrap();
+        let now = Utc::now();
+        let mut db = MemoryDb::new();
+        db.insert("permanent".into(), expiring("kept", None));
+        db.insert(
+            "later".into(),
+            expiring("kept", Some(now + Duration::hours(1))),
+        );
+        db.insert(
+            "gone".into(),
+            expiring("old", Some(now - Duration::seconds(1))),
+        );
+        write_db_at(base.path(), "proj", &db).unwrap();
+        let path = db_path_at(base.path(), "proj");
+        let mut ephemeral = EphemeralDb::new();
+        ephemeral.entry("s1".into()).or_default().insert(
+            "scratch".into(),
+            expiring("old", Some(now - Duration::seconds(1))),
+        );
+        write_db_file(&path.with_extension(EPHEMERAL_EXTENSION), &ephemeral).unwrap();
+
+        let (db, ephemeral) = read_scoped_at(base.path(), "proj");
+        let all = SessionScope {
+            session_id: None,
+            include_ephemeral: true,
+        };
+        let visible = visible_entries(&db, &ephemeral, all);
+        let keys: Vec<&str> = visible.iter().map(|v| v.key).collect();
+        assert_eq!(keys, vec!["later", "permanent"]);
+        assert!(render_memory("gone", &visible, all).is_err());
+        assert!(render_memory("later", &visible, all)
+            .unwrap()
+            .contains("**Expires:**"));
+
+        assert_eq!(prune_expired_in(&path, now).unwrap(), 2);
+        let (db, ephemeral) = read_scoped_at(base.path(), "proj");
+        assert_eq!(db.len(), 2);
+        assert!(!db.contains_key("gone"));
+        assert!(ephemeral.is_empty(), "emptied sessions are dropped");
+        assert_eq!(prune_expired_in(&path, now).unwrap(), 0);
+    }
+
+    #[test]
+    fn ttl_must_be_a_positive_number_of_seconds() {
+        assert_eq!(expiry_for_ttl(None).unwrap(), None);
+        let at = expiry_for_ttl(Some(60)).unwrap().unwrap();
+        let at = DateTime::parse_from_rfc3339(&at).unwrap();
+        assert!(at > Utc::now() + Duration::seconds(50));
+        assert!(expiry_for_ttl(Some(0)).is_err());
+        assert!(expiry_for_ttl(Some(u64::MAX)).is_err());
+    }
+
+    // ── export / import ──────────────────────────────────────────────────────
+
+    #[test]
+    fn export_import_roundtrip_keeps_timestamps_and_sources() {
+        let base = tempdir().unwrap();
+        let mut db = MemoryDb::new();
+        db.insert(
+            "conventions".into(),
+            MemoryEntry {
+                value: "use snake_case".into(),
+                timestamp: "2025-03-01T10:00:00+00:00".into(),
+                source: Some("claude".into()),
+                created_by: Some("user_1".into()),
+                expires_at: None,
+                namespace: DEFAULT_NAMESPACE.into(),
+            },
+        );
+        db.insert(
+            "deploy".into(),
+            MemoryEntry {
+                value: "via CI".into(),
+                timestamp: "2025-04-02T12:30:00+00:00".into(),
+                source: None,
+                created_by: None,
+                expires_at: None,
+                namespace: DEFAULT_NAMESPACE.into(),
+            },
+        );
+        let data = export_document("proj", &db).unwrap();
+        let export: MemoryExport = serde_json::from_str(&data).unwrap();
+        assert_eq!(export.format, MEMORY_EXPORT_FORMAT);
+        assert_eq!(export.entries[0].key, "conventions");
+
+        let report = import_at(base.path(), "copy", &data, MemoryImportStrategy::Merge).unwrap();
+        assert_eq!(report.imported, vec!["conventions", "deploy"]);
+
+        let imported = read_db_at(base.path(), "copy").unwrap();
+        assert_eq!(
+            imported["conventions"].timestamp,
+            "2025-03-01T10:00:00+00:00"
+        );
+        assert_eq!(imported["conventions"].source.as_deref(), Some("claude"));
+        assert_eq!(
+            imported["conventions"].created_by.as_deref(),
+            Some("user_1")
+        );
+        assert_eq!(imported["deploy"].timestamp, "2025-04-02T12:30:00+00:00");
+        assert!(imported["deploy"].source.is_none());
+    }
+
+    #[test]
+    fn import_strategies_handle_existing_keys() {
+        let mut source = MemoryDb::new();
+        insert_entry(
+            &mut source,
+            DEFAULT_NAMESPACE,
+            "shared",
+            "theirs",
+            Some("import"),
+            None,
+        )
+        .unwrap();
+        insert_entry(&mut source, DEFAULT_NAMESPACE, "same", "equal", None, None).unwrap();
+        insert_entry(&mut source, DEFAULT_NAMESPACE, "new", "fresh", None, None).unwrap();
+        let data = export_document("other", &source).unwrap();
+        let seed = || {
+            let dir = tempdir().unwrap();
+            store_at(dir.path(), "proj", "shared", "ours", None).unwrap();
+            store_at(dir.path(), "proj", "same", "equal", None).unwrap();
+            dir
+        };
+
+        let dir = seed();
+        let report = import_at(dir.path(), "proj", &data, MemoryImportStrategy::Merge).unwrap();
+        assert_eq!(report.imported, vec!["new"]);
+        assert_eq!(report.skipped, vec!["same", "shared"]);
+        assert_eq!(
+            read_db_at(dir.path(), "proj").unwrap()["shared"].value,
+            "ours"
+        );
+
+        let dir = seed();
+        let report = import_at(dir.path(), "proj", &data, MemoryImportStrategy::Overwrite).unwrap();
+        assert_eq!(report.overwritten, vec!["same", "shared"]);
+        let db = read_db_at(dir.path(), "proj").unwrap();
+        assert_eq!(db["shared"].value, "theirs");
+        assert_eq!(db["shared"].source.as_deref(), Some("import"));
+
+        let dir = seed();
+        let err = import_at(
+            dir.path(),
+            "proj",
+            &data,
+            MemoryImportStrategy::FailOnConflict,
+        )
+        .unwrap_err();
+        assert!(err.contains("shared") && !err.contains("same"), "{}", err);
+        let db = read_db_at(dir.path(), "proj").unwrap();
+        assert_eq!(db.len(), 2);
+        assert_eq!(db["shared"].value, "ours");
+    }
+
+    #[test]
+    fn malformed_documents_are_rejected_without_changes() {
+        let base = tempdir().unwrap();
+        store_at(base.path(), "proj", "keep", "v", None).unwrap();
+        let entry = |key: &str, ts: &str| {
+            format!(
+                r#"{{"key":"{}","value":"x","timestamp":"{}","source":null}}"#,
+                key, ts
+            )
+        };
+        let doc = |format: u32, entries: &[String]| {
+            format!(
+                r#"{{"format":{},"project":"p","exported_at":"2025-01-01T00:00:00Z","entries":[{}]}}"#,
+                format,
+                entries.join(",")
+            )
+        };
+        let ok = entry("a", "2025-01-01T00:00:00Z");
+        let cases = [
+            ("not json".to_string(), "Invalid memory export"),
+            (r#"{"format":1}"#.to_string(), "Invalid memory export"),
+            (
+                doc(2, std::slice::from_ref(&ok)),
+                "Unsupported memory export format 2",
+            ),
+            (doc(1, &[ok.clone(), ok.clone()]), "appears more than once"),
+            (
+                doc(1, &[ok.clone(), entry("b", "yesterday")]),
+                "invalid timestamp",
+            ),
+            (doc(1, &[entry(" ", "2025-01-01T00:00:00Z")]), "empty key"),
+        ];
+        for (data, expected) in cases {
+            let err =
+                import_at(base.path(), "proj", &data, MemoryImportStrategy::Overwrite).unwrap_err();
+            assert!(err.contains(expected), "{}: {}", expected, err);
+        }
+
+        let db = read_db_at(base.path(), "proj").unwrap();
+        assert_eq!(db.len(), 1);
+        assert!(db.contains_key("keep"));
+    }
 }
@@ -1,11 +1,125 @@
-use std::collections::HashSet;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use crate::agent;
-use crate::core::Project;
+use crate::core::{self, Project};
 
 use super::helpers::add_unique;
+use super::mcp_dedup::{merge_discovered_servers, McpServerConflict, MergedMcpServers};
+use super::rule_recovery::recover_file_rules;
+use super::skill_shadow::detect_skill_shadows;
+
+// ── Limits, progress and cancellation ─────────────────────────────────────────
+//
+// Agent config files, agent skill directories and MCP configs all live at
+// fixed paths relative to the project root, so they are checked directly.
+// Only the generic `skills/` directory is walked, breadth-first, and that
+// walk stops at `max_depth` levels or after reading `max_dirs` directories,
+// whichever comes first.  Hidden directories and symlinks are not entered.
+
+/// Bounds for the generic `skills/` walk, from the `autodetect_max_depth`
+/// and `autodetect_max_dirs` settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutodetectLimits {
+    /// Levels below `skills/` searched for `SKILL.md`; 1 is `skills/<name>/`.
+    pub max_depth: usize,
+    /// Directories read before the walk gives up.
+    pub max_dirs: usize,
+}
+
+impl Default for AutodetectLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: core::DEFAULT_AUTODETECT_MAX_DEPTH,
+            max_dirs: core::DEFAULT_AUTODETECT_MAX_DIRS,
+        }
+    }
+}
+
+impl AutodetectLimits {
+    pub fn from_settings() -> Self {
+        core::read_settings()
+            .map(|s| Self {
+                max_depth: s.autodetect_max_depth.max(1),
+                max_dirs: s.autodetect_max_dirs.max(1),
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Cancels a running autodetection between steps.  Clones share the flag.
+#[derive(Debug, Clone, Default)]
+pub struct AutodetectCancel(Arc<AtomicBool>);
+
+impl AutodetectCancel {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err("Autodetection cancelled".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Payload of the `autodetect-progress` event: findings so far, sent after
+/// each step.
+#[derive(Debug, Clone, Serialize)]
+pub struct AutodetectProgress {
+    pub project: String,
+    /// Step just finished: `agents`, `skills`, `mcp_servers` or `tools`.
+    pub step: String,
+    pub agents: Vec<String>,
+    pub skills: Vec<String>,
+    pub local_skills: Vec<String>,
+    /// Directories read by the `skills/` walk.
+    pub dirs_scanned: usize,
+    /// `true` when the walk stopped at its directory budget.
+    pub truncated: bool,
+}
+
+fn running_autodetections() -> &'static Mutex<HashMap<String, AutodetectCancel>> {
+    static RUNNING: OnceLock<Mutex<HashMap<String, AutodetectCancel>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Register an autodetection of `project` so [`cancel_autodetect`] can stop
+/// it.  Call [`finish_autodetect`] when it returns.
+pub fn begin_autodetect(project: &str) -> AutodetectCancel {
+    let cancel = AutodetectCancel::default();
+    if let Ok(mut running) = running_autodetections().lock() {
+        running.insert(project.to_string(), cancel.clone());
+    }
+    cancel
+}
+
+pub fn finish_autodetect(project: &str) {
+    if let Ok(mut running) = running_autodetections().lock() {
+        running.remove(project);
+    }
+}
+
+/// Cancel the running autodetection of `project`.  Returns `false` when
+/// none is running.
+pub fn cancel_autodetect(project: &str) -> bool {
+    running_autodetections()
+        .lock()
+        .ok()
+        .and_then(|running| running.get(project).cloned())
+        .map(|cancel| cancel.cancel())
+        .is_some()
+}
 
 /// Discover dependencies already present in a project's directory and persist
 /// any new findings into the project + global registries.
@@ -18,30 +132,84 @@ pub fn autodetect_project_dependencies(project: &Project) -> Result<Project, Str
     Ok(updated)
 }
 
+/// Like [`autodetect_project_dependencies`], but also returns the MCP servers
+/// whose discovered configs genuinely differ between agents.  Conflicting
+/// servers are still listed in the project's `mcp_servers`; the user picks
+/// which variant to keep.
+pub fn autodetect_project_dependencies_with_conflicts(
+    project: &Project,
+) -> Result<(Project, Vec<McpServerConflict>), String> {
+    let (updated, discovered) = autodetect_inner(project)?;
+    Ok((updated, discovered.conflicts))
+}
+
+/// Like [`autodetect_project_dependencies_with_conflicts`], reporting
+/// findings after each step and stopping with an error once `cancel` is set.
+pub fn autodetect_project_dependencies_with_progress(
+    project: &Project,
+    limits: AutodetectLimits,
+    cancel: &AutodetectCancel,
+    on_progress: impl FnMut(AutodetectProgress),
+) -> Result<(Project, Vec<McpServerConflict>), String> {
+    let (updated, discovered) = autodetect_with(project, limits, cancel, on_progress)?;
+    Ok((updated, discovered.conflicts))
+}
+
 /// Inner autodetection that returns both the enriched project and the
-/// discovered MCP server configs (name -> pretty-printed JSON string) so that
+/// discovered MCP server configs, merged across agents, so that
 /// `sync_project` can persist them without a second filesystem scan.
-pub(super) fn autodetect_inner(
+pub(super) fn autodetect_inner(project: &Project) -> Result<(Project, MergedMcpServers), String> {
+    autodetect_with(
+        project,
+        AutodetectLimits::from_settings(),
+        &AutodetectCancel::default(),
+        |_| {},
+    )
+}
+
+fn autodetect_with(
     project: &Project,
-) -> Result<(Project, Vec<(String, String)>), String> {
+    limits: AutodetectLimits,
+    cancel: &AutodetectCancel,
+    mut on_progress: impl FnMut(AutodetectProgress),
+) -> Result<(Project, MergedMcpServers), String> {
     if project.directory.is_empty() {
-        return Ok((project.clone(), vec![]));
+        return Ok((project.clone(), MergedMcpServers::default()));
     }
 
     let dir = PathBuf::from(&project.directory);
     if !dir.exists() {
-        return Ok((project.clone(), vec![]));
+        return Ok((project.clone(), MergedMcpServers::default()));
     }
 
     let mut updated_project = project.clone();
-    let mut discovered_servers: Vec<(String, String)> = Vec::new();
+    let mut found_servers = Vec::new();
+    let mut walk = SkillWalk::default();
+    let mut report = |step: &str, project: &Project, walk: &SkillWalk| {
+        on_progress(AutodetectProgress {
+            project: project.name.clone(),
+            step: step.to_string(),
+            agents: project.agents.clone(),
+            skills: project.skills.clone(),
+            local_skills: project.local_skills.clone(),
+            dirs_scanned: walk.dirs_scanned,
+            truncated: walk.truncated,
+        })
+    };
 
-    // Detect which agents are present by asking each agent to check
+    // Detect which agents are present by asking each agent to check.
+    // Agents the user told us to ignore are never re-added.
     for a in agent::all() {
-        if a.detect_in(&dir) {
+        if !project.is_agent_disabled(a.id()) && a.detect_in(&dir) {
             add_unique(&mut updated_project.agents, a.id());
         }
     }
+    report("agents", &updated_project, &walk);
+    cancel.check()?;
+
+    // A project adopted from a directory synced elsewhere has no rules yet;
+    // rebuild them from the rules sections already in its instruction files.
+    recover_file_rules(&mut updated_project, &dir);
 
     // Discover skills from all known skill directories
     // (includes agent-specific dirs + the generic `skills/` dir)
@@ -50,53 +218,73 @@ pub(super) fn autodetect_inner(
         .into_iter()
         .collect();
 
-    let mut skill_dirs: Vec<PathBuf> = Vec::new();
+    let mut found_skills: Vec<String> = Vec::new();
     for a in agent::all() {
-        skill_dirs.extend(a.skill_dirs(&dir));
+        for skill_base_dir in a.skill_dirs(&dir) {
+            found_skills.extend(skills_in(&skill_base_dir));
+        }
     }
-    skill_dirs.push(dir.join("skills")); // generic fallback
+    cancel.check()?;
+    // Generic fallback: the only directory that is walked.
+    walk_generic_skills(&dir.join("skills"), limits, cancel, &mut walk)?;
+    found_skills.extend(walk.found.iter().cloned());
 
-    for skill_base_dir in &skill_dirs {
-        if !skill_base_dir.exists() {
-            continue;
-        }
-        if let Ok(entries) = fs::read_dir(skill_base_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                        let skill_file = path.join("SKILL.md");
-                        if skill_file.exists() && crate::core::is_valid_name(name) {
-                            if global_skill_names.contains(name) {
-                                // Skill exists in the global registry — track
-                                // it as a normal (global) project skill.
-                                add_unique(&mut updated_project.skills, name);
-                            } else if !updated_project.skills.contains(&name.to_string()) {
-                                // Skill only exists locally in this project —
-                                // track it separately without importing.
-                                add_unique(&mut updated_project.local_skills, name);
-                            }
-                        }
-                    }
-                }
-            }
+    for name in &found_skills {
+        if global_skill_names.contains(name) {
+            // Skill exists in the global registry — track it as a normal
+            // (global) project skill.
+            add_unique(&mut updated_project.skills, name);
+        } else if !updated_project.skills.contains(name) {
+            // Skill only exists locally in this project — track it
+            // separately without importing.
+            add_unique(&mut updated_project.local_skills, name);
         }
     }
 
+    // Selected global skills whose project copy was edited are recorded as
+    // conflicts so sync leaves them alone until the user resolves them.
+    updated_project.skill_conflicts = detect_skill_shadows(&updated_project);
+    report("skills", &updated_project, &walk);
+    cancel.check()?;
+
     // Discover MCP servers by asking each agent to scan its config files.
     // Configs are collected here and returned to the caller — we do not write
-    // to the global MCP registry from this read-only function.
+    // to the global MCP registry from this read-only function.  Variants of
+    // the same server found in several agents' configs are merged, and
+    // genuinely different ones reported as conflicts.  Ignored agents'
+    // configs belong to someone else and are not imported.
     for a in agent::all() {
-        let servers = a.discover_mcp_servers(&dir);
-        for (name, config) in servers {
-            if let Ok(config_str) = serde_json::to_string_pretty(&config) {
-                if !updated_project.mcp_servers.contains(&name) {
-                    updated_project.mcp_servers.push(name.clone());
-                }
-                discovered_servers.push((name, config_str));
+        if project.is_agent_disabled(a.id()) {
+            continue;
+        }
+        for (name, config) in a.discover_mcp_servers(&dir) {
+            found_servers.push((a.id().to_string(), name, config));
+        }
+    }
+    let discovered_servers = merge_discovered_servers(found_servers);
+    for (name, agents) in &discovered_servers.origins {
+        updated_project.add_discovered_mcp_server(name, agents);
+    }
+
+    // Servers the user added to Claude Code at local scope stay there: sync
+    // must not move a personal server into the shared `.mcp.json`.  Entries
+    // Automatic wrote itself follow the project's scope setting instead.
+    if updated_project.agents.iter().any(|a| a == "claude") {
+        let managed = agent::managed_claude_local_servers(&dir);
+        for name in agent::discover_claude_local_servers(&dir).keys() {
+            if !managed.contains(name) {
+                updated_project
+                    .agent_options
+                    .entry("claude".to_string())
+                    .or_default()
+                    .claude_mcp_scopes
+                    .entry(name.clone())
+                    .or_insert(crate::core::ClaudeMcpScope::Local);
             }
         }
     }
+    report("mcp_servers", &updated_project, &walk);
+    cancel.check()?;
 
     // ── Detect tools declared by enabled plugins ─────────────────────────────
     //
@@ -129,5 +317,215 @@ pub(super) fn autodetect_inner(
         }
     }
 
+    report("tools", &updated_project, &walk);
+
     Ok((updated_project, discovered_servers))
 }
+
+/// Skill names directly under `base`: subdirectories holding a `SKILL.md`.
+fn skills_in(base: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.join("SKILL.md").exists())
+        .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+        .filter(|name| core::is_valid_name(name))
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct SkillWalk {
+    found: Vec<String>,
+    dirs_scanned: usize,
+    truncated: bool,
+}
+
+/// Breadth-first search of `root` for skill directories, within `limits`.
+/// A directory holding a `SKILL.md` is a skill and is not searched further.
+fn walk_generic_skills(
+    root: &Path,
+    limits: AutodetectLimits,
+    cancel: &AutodetectCancel,
+    walk: &mut SkillWalk,
+) -> Result<(), String> {
+    let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::from([(root.to_path_buf(), 0)]);
+    while let Some((path, depth)) = queue.pop_front() {
+        if walk.dirs_scanned >= limits.max_dirs {
+            walk.truncated = true;
+            tracing::warn!(
+                "autodetect: stopped searching {} after {} directories",
+                root.display(),
+                walk.dirs_scanned
+            );
+            break;
+        }
+        cancel.check()?;
+        let Ok(entries) = fs::read_dir(&path) else {
+            continue;
+        };
+        walk.dirs_scanned += 1;
+        for entry in entries.flatten() {
+            let child = entry.path();
+            if !child.is_dir() {
+                continue;
+            }
+            let Some(name) = child.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if child.join("SKILL.md").exists() {
+                if core::is_valid_name(name) {
+                    walk.found.push(name.to_string());
+                }
+                continue;
+            }
+            // Symlinked skills are detected above; symlinked directories are
+            // not followed, so a link back up the tree cannot loop.
+            let is_link = entry.file_type().map(|t| t.is_symlink()).unwrap_or(true);
+            if depth + 1 < limits.max_depth && !name.starts_with('.') && !is_link {
+                queue.push_back((child, depth + 1));
+            }
+        }
+    }
+    Ok(())
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_skill(dir: &Path, name: &str) {
+        let skill = dir.join(name);
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), format!("# {}\n", name)).unwrap();
+    }
+
+    #[test]
+    fn skill_walk_respects_the_directory_budget() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("skills");
+        // A synthetic monorepo: 40 packages, each 12 directories deep, with a
+        // skill at the bottom of every one.
+        for package in 0..40 {
+            let mut deep = root.join(format!("pkg-{}", package));
+            for level in 0..12 {
+                deep = deep.join(format!("level-{}", level));
+            }
+            write_skill(&deep, &format!("deep-{}", package));
+        }
+        write_skill(&root, "shallow");
+
+        let limits = AutodetectLimits {
+            max_depth: 20,
+            max_dirs: 50,
+        };
+        let mut walk = SkillWalk::default();
+        walk_generic_skills(&root, limits, &AutodetectCancel::default(), &mut walk).unwrap();
+        assert_eq!(walk.dirs_scanned, 50);
+        assert!(walk.truncated);
+        assert_eq!(walk.found, vec!["shallow"]);
+
+        let limits = AutodetectLimits {
+            max_depth: 3,
+            max_dirs: 10_000,
+        };
+        let mut walk = SkillWalk::default();
+        walk_generic_skills(&root, limits, &AutodetectCancel::default(), &mut walk).unwrap();
+        // The root plus two levels of every package.
+        assert_eq!(walk.dirs_scanned, 1 + 40 * 2);
+        assert!(!walk.truncated);
+        assert_eq!(walk.found, vec!["shallow"]);
+    }
+
+    #[test]
+    fn default_limits_match_the_flat_skills_layout() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("skills");
+        write_skill(&root, "deploy");
+        write_skill(&root, "review");
+        write_skill(&root.join("group"), "nested");
+        fs::create_dir_all(root.join("notes")).unwrap();
+
+        let mut walk = SkillWalk::default();
+        walk_generic_skills(
+            &root,
+            AutodetectLimits::default(),
+            &AutodetectCancel::default(),
+            &mut walk,
+        )
+        .unwrap();
+        walk.found.sort();
+        assert_eq!(walk.found, vec!["deploy", "review"]);
+        assert_eq!(walk.dirs_scanned, 1);
+    }
+
+    #[test]
+    fn autodetect_reports_progress_and_stops_when_cancelled() {
+        let dir = tempdir().unwrap();
+        write_skill(&dir.path().join(".claude").join("skills"), "only-here");
+        let project = Project {
+            name: "mono".into(),
+            directory: dir.path().display().to_string(),
+            ..Default::default()
+        };
+
+        let mut steps = Vec::new();
+        let (updated, _) = autodetect_with(
+            &project,
+            AutodetectLimits::default(),
+            &AutodetectCancel::default(),
+            |p| steps.push((p.step, p.agents, p.local_skills)),
+        )
+        .unwrap();
+        assert!(updated.agents.contains(&"claude".to_string()));
+        let names: Vec<&str> = steps.iter().map(|(s, _, _)| s.as_str()).collect();
+        assert_eq!(names, vec!["agents", "skills", "mcp_servers", "tools"]);
+        assert!(steps[0].1.contains(&"claude".to_string()));
+        assert_eq!(steps[1].2, vec!["only-here"]);
+
+        let cancel = AutodetectCancel::default();
+        let mut seen = Vec::new();
+        let err = autodetect_with(&project, AutodetectLimits::default(), &cancel, |p| {
+            seen.push(p.step);
+            cancel.cancel();
+        })
+        .unwrap_err();
+        assert!(err.contains("cancelled"), "{}", err);
+        assert_eq!(seen, vec!["agents"]);
+    }
+
+    #[test]
+    fn autodetect_adds_aider_from_its_config_file() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".aider.conf.yml"), "read: CONVENTIONS.md\n").unwrap();
+        let project = Project {
+            name: "aider".into(),
+            directory: dir.path().display().to_string(),
+            ..Default::default()
+        };
+
+        let (updated, _) = autodetect_with(
+            &project,
+            AutodetectLimits::default(),
+            &AutodetectCancel::default(),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(updated.agents, vec!["aider"]);
+    }
+
+    #[test]
+    fn cancel_reaches_a_registered_autodetection() {
+        assert!(!cancel_autodetect("not-running"));
+        let cancel = begin_autodetect("web");
+        assert!(cancel_autodetect("web"));
+        assert!(cancel.is_cancelled());
+        finish_autodetect("web");
+        assert!(!cancel_autodetect("web"));
+    }
+}
@@ -1,31 +1,79 @@
 use std::fs;
 use std::path::PathBuf;
 
-use crate::agent;
+use crate::agent::{self, Agent};
 use crate::core::AgentOptions;
 use crate::core::Project;
 
+use serde::{Deserialize, Serialize};
+
 use super::engine::sync_project_without_autodetect;
-use super::helpers::cleanup_custom_agents;
+use super::helpers::{add_unique, clean_project_file_rules_section, cleanup_custom_agents};
+use super::trash::backup_agent_removal;
+
+/// What [`remove_agent_from_project`] does with MCP servers whose only
+/// recorded origin is the removed agent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UniqueMcpServers {
+    /// Keep them in the project.  Their origin is cleared, so they are
+    /// treated as user-added from now on.
+    #[default]
+    Keep,
+    /// Remove them from the project along with the agent.
+    Remove,
+}
+
+/// Read-only preview returned by [`get_agent_cleanup_preview`].
+#[derive(Debug, Serialize)]
+pub struct AgentCleanupPreview {
+    /// Files and directories that would be deleted or modified.
+    pub files: Vec<String>,
+    /// MCP servers discovered only from this agent.  The caller chooses
+    /// whether they stay with the project via [`UniqueMcpServers`].
+    pub unique_mcp_servers: Vec<String>,
+}
+
+/// Result of [`remove_agent_from_project`].
+#[derive(Debug, Serialize)]
+pub struct AgentRemoval {
+    /// Paths that were removed or modified.
+    pub removed_files: Vec<String>,
+    /// Servers unique to the agent that stay in the project, now user-owned.
+    pub kept_mcp_servers: Vec<String>,
+    /// Servers unique to the agent that were removed from the project.
+    pub removed_mcp_servers: Vec<String>,
+    /// Token for [`restore_agent_removal`](super::restore_agent_removal),
+    /// or `None` when no files were touched.
+    pub restore_token: Option<String>,
+}
 
 /// Remove an agent from a project and clean up all files it wrote.
 ///
 /// Steps:
-/// 1. Compute the remaining agent list (project minus the removed agent).
+/// 1. Copy every path the removal will touch into the trash (see
+///    [`super::trash`]) so it can be undone, then compute the remaining
+///    agent list (project minus the removed agent).
 /// 2. Call [`agent::cleanup_agent_from_project`] to delete / strip the
 ///    agent's config file and agent-specific skill directories.
 /// 3. For the `claude` agent, also strip the managed rules block from
 ///    `CLAUDE.md`, remove any Automatic-managed `.claude/rules/*.md` files,
 ///    and attempt to remove the now-empty `.claude/` directory.
-/// 4. Update `project.agents` and persist the new project config.
-/// 5. If other agents remain, re-sync them so their own configs are still
+/// 4. Keep or remove the MCP servers only this agent brought into the
+///    project, as chosen by `unique_servers`, and drop the agent from the
+///    origins of every other server.
+/// 5. Update `project.agents` and persist the new project config.  With
+///    `disable_detection`, the agent is also added to
+///    `project.disabled_agents` so autodetection never re-adds it.
+/// 6. If other agents remain, re-sync them so their own configs are still
 ///    accurate (e.g. no longer lists servers written for the removed agent).
-///
-/// Returns the list of paths that were removed or modified.
 pub fn remove_agent_from_project(
     project: &mut Project,
     agent_id: &str,
-) -> Result<Vec<String>, String> {
+    unique_servers: UniqueMcpServers,
+    disable_detection: bool,
+) -> Result<AgentRemoval, String> {
+    project.ensure_unlocked()?;
     if project.directory.is_empty() {
         return Err("Project has no directory configured".into());
     }
@@ -35,6 +83,12 @@ pub fn remove_agent_from_project(
         return Err(format!("Directory '{}' does not exist", project.directory));
     }
 
+    // Back up everything the cleanup below deletes or modifies.  Refuse to
+    // remove the agent if the backup cannot be made.
+    let restore_token =
+        backup_agent_removal(project, agent_id, &agent_cleanup_files(project, agent_id))
+            .map_err(|e| format!("Failed to back up agent files before removal: {}", e))?;
+
     // Compute the remaining agents before mutating the project
     let remaining: Vec<String> = project
         .agents
@@ -43,16 +97,77 @@ pub fn remove_agent_from_project(
         .cloned()
         .collect();
 
+    let removed = cleanup_agent_files(project, agent_id, &dir, &remaining);
+
+    // Servers discovered only from this agent either stay (origin cleared,
+    // now user-owned) or leave with it.  Multi-origin servers stay and just
+    // lose this agent from their origins.
+    let unique = project.mcp_servers_unique_to(agent_id);
+    for origins in project.mcp_server_origins.values_mut() {
+        origins.retain(|id| id != agent_id);
+    }
+    project
+        .mcp_server_origins
+        .retain(|_, origins| !origins.is_empty());
+    let (kept_mcp_servers, removed_mcp_servers) = match unique_servers {
+        UniqueMcpServers::Keep => (unique, vec![]),
+        UniqueMcpServers::Remove => {
+            project.mcp_servers.retain(|s| !unique.contains(s));
+            project.disabled_mcp_servers.retain(|s| !unique.contains(s));
+            (vec![], unique)
+        }
+    };
+
+    // Update and persist the project
+    project.agents = remaining;
+    if disable_detection {
+        add_unique(&mut project.disabled_agents, agent_id);
+    }
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    crate::core::persist_project(project)?;
+    crate::core::log_sync_history(
+        project,
+        crate::core::SyncAction::RemoveAgent,
+        Some(agent_id),
+        &[],
+        &removed,
+    );
+
+    // Re-sync remaining agents so their configs are up to date
+    if !project.agents.is_empty() {
+        let _ = sync_project_without_autodetect(project);
+    }
+
+    Ok(AgentRemoval {
+        removed_files: removed,
+        kept_mcp_servers,
+        removed_mcp_servers,
+        restore_token,
+    })
+}
+
+/// Delete or strip every file `agent_id` wrote into `dir`: its MCP config,
+/// commands, rule files, skill directories and custom agents, plus the
+/// Claude and Copilot instruction-file extras.  The `.agents/skills/` hub
+/// stays when one of `remaining` still uses it.
+///
+/// Returns the paths that were removed or modified.
+pub(super) fn cleanup_agent_files(
+    project: &Project,
+    agent_id: &str,
+    dir: &PathBuf,
+    remaining: &[String],
+) -> Vec<String> {
     // Clean up the agent's resources
     let mut removed = if let Some(agent_instance) = agent::from_id(agent_id) {
-        agent::cleanup_agent_from_project(agent_instance, &dir, &remaining)
+        agent::cleanup_agent_from_project(agent_instance, dir, remaining)
     } else {
         vec![]
     };
 
     // Clean up custom agents directory for this agent
     if let Some(agent_instance) = agent::from_id(agent_id) {
-        if let Some(agents_dir) = agent_instance.agents_dir(&dir) {
+        if let Some(agents_dir) = agent_instance.agents_dir(dir) {
             removed.extend(cleanup_custom_agents(
                 &agents_dir,
                 agent_instance.agents_file_ext(),
@@ -69,22 +184,16 @@ pub fn remove_agent_from_project(
             .get("claude")
             .cloned()
             .unwrap_or_default();
-        removed.extend(cleanup_claude_project_files(&dir, &opts));
+        removed.extend(cleanup_claude_project_files(dir, &opts));
     }
 
-    // Update and persist the project
-    project.agents = remaining;
-    project.updated_at = chrono::Utc::now().to_rfc3339();
-    let project_str =
-        serde_json::to_string_pretty(&project).map_err(|e| format!("Serialise error: {}", e))?;
-    crate::core::save_project(&project.name, &project_str)?;
-
-    // Re-sync remaining agents so their configs are up to date
-    if !project.agents.is_empty() {
-        let _ = sync_project_without_autodetect(project);
+    // Copilot-specific cleanup: strip managed rules from path-scoped
+    // instruction files.  The files themselves are team-authored and kept.
+    if agent_id == "copilot" {
+        removed.extend(cleanup_copilot_instruction_files(dir));
     }
 
-    Ok(removed)
+    removed
 }
 
 /// Return the list of file/directory paths that *would* be removed if
@@ -92,14 +201,24 @@ pub fn remove_agent_from_project(
 ///
 /// This is a read-only operation used to populate the confirmation dialog
 /// shown before the user commits to the removal.
-pub fn get_agent_cleanup_preview(project: &Project, agent_id: &str) -> Result<Vec<String>, String> {
+pub fn get_agent_cleanup_preview(
+    project: &Project,
+    agent_id: &str,
+) -> Result<AgentCleanupPreview, String> {
+    Ok(AgentCleanupPreview {
+        files: agent_cleanup_files(project, agent_id),
+        unique_mcp_servers: project.mcp_servers_unique_to(agent_id),
+    })
+}
+
+fn agent_cleanup_files(project: &Project, agent_id: &str) -> Vec<String> {
     if project.directory.is_empty() {
-        return Ok(vec![]);
+        return vec![];
     }
 
     let dir = PathBuf::from(&project.directory);
     if !dir.exists() {
-        return Ok(vec![]);
+        return vec![];
     }
 
     let remaining: Vec<String> = project
@@ -108,16 +227,25 @@ pub fn get_agent_cleanup_preview(project: &Project, agent_id: &str) -> Result<Ve
         .filter(|id| id.as_str() != agent_id)
         .cloned()
         .collect();
+    agent_cleanup_preview(project, agent_id, &dir, &remaining)
+}
 
+/// The paths [`cleanup_agent_files`] would remove or modify.
+pub(super) fn agent_cleanup_preview(
+    project: &Project,
+    agent_id: &str,
+    dir: &PathBuf,
+    remaining: &[String],
+) -> Vec<String> {
     let mut preview = if let Some(agent_instance) = agent::from_id(agent_id) {
-        agent::cleanup_agent_preview(agent_instance, &dir, &remaining)
+        agent::cleanup_agent_preview(agent_instance, dir, remaining)
     } else {
         vec![]
     };
 
     // Include custom agents directory in the preview
     if let Some(agent_instance) = agent::from_id(agent_id) {
-        if let Some(agents_dir) = agent_instance.agents_dir(&dir) {
+        if let Some(agents_dir) = agent_instance.agents_dir(dir) {
             if agents_dir.exists() {
                 if let Ok(entries) = fs::read_dir(&agents_dir) {
                     for entry in entries.flatten() {
@@ -138,10 +266,14 @@ pub fn get_agent_cleanup_preview(project: &Project, agent_id: &str) -> Result<Ve
             .get("claude")
             .cloned()
             .unwrap_or_default();
-        preview.extend(claude_cleanup_preview(&dir, &opts));
+        preview.extend(claude_cleanup_preview(dir, &opts));
     }
 
-    Ok(preview)
+    if agent_id == "copilot" {
+        preview.extend(copilot_cleanup_preview(dir));
+    }
+
+    preview
 }
 
 // ── Claude-specific cleanup helpers ─────────────────────────────────────────
@@ -182,7 +314,7 @@ fn cleanup_claude_project_files(dir: &PathBuf, opts: &AgentOptions) -> Vec<Strin
         // managed files and writes nothing new.
         match crate::core::sync_rules_to_dot_claude_rules(&dir.display().to_string(), &[]) {
             Ok(removed) => touched.extend(removed),
-            Err(e) => eprintln!("Failed to clean .claude/rules/ on agent removal: {}", e),
+            Err(e) => tracing::error!("Failed to clean .claude/rules/ on agent removal: {}", e),
         }
 
         // Remove the .claude/rules/ directory itself if now empty.
@@ -234,3 +366,147 @@ fn claude_cleanup_preview(dir: &PathBuf, _opts: &AgentOptions) -> Vec<String> {
 
     preview
 }
+
+// ── Copilot-specific cleanup helpers ────────────────────────────────────────
+
+/// Strip the managed rules block from every team-authored
+/// `.github/instructions/*.instructions.md` and `.github/prompts/*.prompt.md`
+/// file.  Never deletes a file, even when nothing but frontmatter remains.
+/// Prompt files Automatic wrote for commands are removed by the command
+/// cleanup instead.
+///
+/// Returns the paths of files modified.
+fn cleanup_copilot_instruction_files(dir: &PathBuf) -> Vec<String> {
+    let mut touched: Vec<String> = Vec::new();
+    for filename in agent::GitHubCopilot.extra_instruction_files(dir) {
+        if let Ok(Some(path)) = clean_project_file_rules_section(dir, &filename) {
+            touched.push(path);
+        }
+    }
+    touched
+}
+
+/// Return the instruction files that [`cleanup_copilot_instruction_files`]
+/// would modify — those containing a managed rules block.
+fn copilot_cleanup_preview(dir: &PathBuf) -> Vec<String> {
+    agent::GitHubCopilot
+        .extra_instruction_files(dir)
+        .into_iter()
+        .map(|f| dir.join(f))
+        .filter(|p| {
+            fs::read_to_string(p)
+                .map(|c| c.contains("<!-- automatic:rules:start -->"))
+                .unwrap_or(false)
+        })
+        .map(|p| p.display().to_string())
+        .collect()
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// `github` came only from Codex, `linear` from both agents, and `notes`
+    /// was added by hand.
+    fn project(dir: &std::path::Path) -> Project {
+        let origins = [
+            ("github", vec!["codex"]),
+            ("linear", vec!["claude", "codex"]),
+        ];
+        Project {
+            name: "origins".into(),
+            directory: dir.display().to_string(),
+            agents: vec!["claude".into(), "codex".into()],
+            mcp_servers: vec!["github".into(), "linear".into(), "notes".into()],
+            disabled_mcp_servers: vec!["github".into()],
+            mcp_server_origins: origins
+                .into_iter()
+                .map(|(s, a)| (s.to_string(), a.into_iter().map(String::from).collect()))
+                .collect(),
+            unregistered: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn preview_lists_servers_unique_to_the_agent() {
+        let dir = tempdir().unwrap();
+        let p = project(dir.path());
+        let preview = get_agent_cleanup_preview(&p, "codex").unwrap();
+        assert_eq!(preview.unique_mcp_servers, vec!["github".to_string()]);
+        assert!(get_agent_cleanup_preview(&p, "claude")
+            .unwrap()
+            .unique_mcp_servers
+            .is_empty());
+    }
+
+    #[test]
+    fn keep_clears_origin_so_server_becomes_user_owned() {
+        let dir = tempdir().unwrap();
+        let mut p = project(dir.path());
+        let removal =
+            remove_agent_from_project(&mut p, "codex", UniqueMcpServers::Keep, false).unwrap();
+
+        assert_eq!(removal.kept_mcp_servers, vec!["github".to_string()]);
+        assert!(removal.removed_mcp_servers.is_empty());
+        assert_eq!(p.mcp_servers, vec!["github", "linear", "notes"]);
+        assert_eq!(p.disabled_mcp_servers, vec!["github"]);
+        assert!(!p.mcp_server_origins.contains_key("github"));
+        assert!(p.mcp_servers_unique_to("codex").is_empty());
+    }
+
+    #[test]
+    fn remove_drops_unique_servers_and_leaves_multi_origin_ones() {
+        let dir = tempdir().unwrap();
+        let mut p = project(dir.path());
+        let removal =
+            remove_agent_from_project(&mut p, "codex", UniqueMcpServers::Remove, false).unwrap();
+
+        assert!(removal.kept_mcp_servers.is_empty());
+        assert_eq!(removal.removed_mcp_servers, vec!["github".to_string()]);
+        assert_eq!(p.agents, vec!["claude"]);
+        assert_eq!(p.mcp_servers, vec!["linear", "notes"]);
+        assert!(p.disabled_mcp_servers.is_empty());
+        // `linear` stays; Claude is now its only origin.
+        assert_eq!(p.mcp_server_origins["linear"], vec!["claude"]);
+        assert_eq!(p.mcp_servers_unique_to("claude"), vec!["linear"]);
+    }
+
+    #[test]
+    fn disabled_agent_is_not_re_added_by_a_full_sync() {
+        let dir = tempdir().unwrap();
+        let github = dir.path().join(".github");
+        fs::create_dir_all(&github).unwrap();
+        let team_file = "# Owned by the platform team\n";
+        fs::write(github.join("copilot-instructions.md"), team_file).unwrap();
+
+        let mut p = Project {
+            name: "ignored".into(),
+            directory: dir.path().display().to_string(),
+            agents: vec!["claude".into(), "copilot".into()],
+            unregistered: true,
+            ..Default::default()
+        };
+        remove_agent_from_project(&mut p, "copilot", UniqueMcpServers::Keep, true).unwrap();
+        assert_eq!(p.agents, vec!["claude"]);
+        assert_eq!(p.disabled_agents, vec!["copilot"]);
+
+        crate::sync::sync_project(&p).unwrap();
+        let raw = fs::read_to_string(dir.path().join(".automatic").join("project.json")).unwrap();
+        let saved: Project = serde_json::from_str(&raw).unwrap();
+        assert_eq!(saved.agents, vec!["claude"]);
+        assert_eq!(saved.disabled_agents, vec!["copilot"]);
+        assert_eq!(
+            fs::read_to_string(github.join("copilot-instructions.md")).unwrap(),
+            team_file
+        );
+
+        // Selecting an ignored agent again is rejected until it is un-ignored.
+        let mut both = saved.clone();
+        both.agents.push("copilot".into());
+        assert!(both.validate_disabled_agents().is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::agent;
+use crate::core;
+
+use super::autodetect::AutodetectCancel;
+
+// ── Project discovery ────────────────────────────────────────────────────────
+//
+// Finds directories that already hold agent configs, so a new user can adopt
+// the repos they have set up for Claude Code, Cursor, … in one go.  The walk
+// is breadth-first from each root and never enters hidden directories,
+// symlinks, dependency or build output directories, or a directory already
+// found to be a project.  It stops at `max_depth` levels, after
+// `SCAN_MAX_DIRS` directories or `SCAN_TIME_BUDGET`, whichever comes first,
+// and then reports what it found so far as `truncated`.
+
+/// Levels below each root searched when the caller gives no depth.
+pub const DEFAULT_SCAN_MAX_DEPTH: usize = 3;
+const SCAN_MAX_DIRS: usize = 20_000;
+const SCAN_TIME_BUDGET: Duration = Duration::from_secs(10);
+
+/// Directories that hold dependencies or build output, never projects.
+const SKIPPED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    "__pycache__",
+];
+
+/// A directory that looks like a project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiscoveredProject {
+    pub directory: String,
+    /// Suggested project name: the one in `.automatic/project.json`, else
+    /// the directory's basename.
+    pub name: String,
+    /// Ids of the agents whose files were found.
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// Whether the directory has an `.automatic/project.json`.
+    #[serde(default)]
+    pub has_project_config: bool,
+    /// The registered project that already uses this directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registered_as: Option<String>,
+}
+
+/// Result of [`scan_for_projects`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectScan {
+    /// Found projects, ordered by directory.
+    pub projects: Vec<DiscoveredProject>,
+    pub dirs_scanned: usize,
+    /// `true` when the scan stopped at its directory or time budget, or was
+    /// cancelled, before the whole tree was searched.
+    pub truncated: bool,
+}
+
+struct ScanBudget {
+    max_dirs: usize,
+    deadline: Instant,
+}
+
+fn running_scan() -> &'static Mutex<Option<AutodetectCancel>> {
+    static RUNNING: OnceLock<Mutex<Option<AutodetectCancel>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(None))
+}
+
+/// Cancel the running [`scan_for_projects`].  Returns `false` when none is
+/// running.
+pub fn cancel_project_scan() -> bool {
+    running_scan()
+        .lock()
+        .ok()
+        .and_then(|running| running.clone())
+        .map(|cancel| cancel.cancel())
+        .is_some()
+}
+
+/// Search `root_dirs` for project directories, `max_depth` levels deep.
+/// Roots that do not exist are skipped.
+pub fn scan_for_projects(root_dirs: Vec<String>, max_depth: usize) -> ProjectScan {
+    let cancel = AutodetectCancel::default();
+    if let Ok(mut running) = running_scan().lock() {
+        *running = Some(cancel.clone());
+    }
+    let registered = core::registered_project_directories().unwrap_or_else(|e| {
+        tracing::warn!("Failed to list registered projects: {}", e);
+        HashMap::new()
+    });
+    let budget = ScanBudget {
+        max_dirs: SCAN_MAX_DIRS,
+        deadline: Instant::now() + SCAN_TIME_BUDGET,
+    };
+    let scan = scan_with(&root_dirs, max_depth, &budget, &cancel, &registered);
+    if let Ok(mut running) = running_scan().lock() {
+        *running = None;
+    }
+    scan
+}
+
+fn scan_with(
+    root_dirs: &[String],
+    max_depth: usize,
+    budget: &ScanBudget,
+    cancel: &AutodetectCancel,
+    registered: &HashMap<String, String>,
+) -> ProjectScan {
+    let home = dirs::home_dir().map(core::canonical_key);
+    let mut scan = ProjectScan::default();
+    let mut seen = HashSet::new();
+    let mut queue: VecDeque<(PathBuf, usize)> = root_dirs
+        .iter()
+        .filter_map(|root| fs::canonicalize(root).ok())
+        .filter(|root| root.is_dir())
+        .map(|root| (root, 0))
+        .collect();
+
+    while let Some((dir, depth)) = queue.pop_front() {
+        if scan.dirs_scanned >= budget.max_dirs
+            || Instant::now() >= budget.deadline
+            || cancel.is_cancelled()
+        {
+            scan.truncated = true;
+            break;
+        }
+        scan.dirs_scanned += 1;
+
+        let key = core::canonical_key(&dir);
+        // Agents keep their user-level config in the home directory, which
+        // would otherwise make it look like a project.
+        if home.as_deref() != Some(key.as_str()) {
+            if let Some(mut found) = inspect_dir(&dir) {
+                if seen.insert(key.clone()) {
+                    found.registered_as = registered.get(&key).cloned();
+                    scan.projects.push(found);
+                }
+                continue;
+            }
+        }
+        if depth >= max_depth {
+            continue;
+        }
+
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut children: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|e| {
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref())
+            })
+            .map(|e| e.path())
+            .collect();
+        children.sort();
+        queue.extend(children.into_iter().map(|child| (child, depth + 1)));
+    }
+
+    scan.projects.sort_by(|a, b| a.directory.cmp(&b.directory));
+    scan
+}
+
+/// `dir` as a discovered project, when any agent's files or an Automatic
+/// project config are present.
+fn inspect_dir(dir: &Path) -> Option<DiscoveredProject> {
+    let agents: Vec<String> = agent::all()
+        .into_iter()
+        .filter(|a| a.detect_in(dir))
+        .map(|a| a.id().to_string())
+        .collect();
+    let config_path = dir.join(".automatic").join("project.json");
+    let has_project_config = config_path.is_file();
+    if agents.is_empty() && !has_project_config {
+        return None;
+    }
+
+    let configured_name = fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(str::to_string))
+        .filter(|name| core::is_valid_name(name));
+    let name = configured_name.unwrap_or_else(|| {
+        dir.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "project".to_string())
+    });
+
+    Some(DiscoveredProject {
+        directory: dir.display().to_string(),
+        name,
+        agents,
+        has_project_config,
+        registered_as: None,
+    })
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget() -> ScanBudget {
+        ScanBudget {
+            max_dirs: 1000,
+            deadline: Instant::now() + Duration::from_secs(30),
+        }
+    }
+
+    #[test]
+    fn finds_projects_without_entering_them_or_skipped_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        let root_path = fs::canonicalize(root.path()).unwrap();
+        let mkdir = |rel: &str| fs::create_dir_all(root_path.join(rel)).unwrap();
+        mkdir("api/.cursor/rules");
+        mkdir("api/packages/inner/.cursor/rules");
+        mkdir("web/.automatic");
+        fs::write(
+            root_path.join("web/.automatic/project.json"),
+            r#"{"name": "website"}"#,
+        )
+        .unwrap();
+        mkdir("node_modules/dep/.cursor/rules");
+        mkdir("deep/a/b/c/.cursor/rules");
+        mkdir("plain/src");
+
+        let registered: HashMap<String, String> = [(
+            core::canonical_key(root_path.join("api")),
+            "api".to_string(),
+        )]
+        .into();
+        let scan = scan_with(
+            &[root_path.display().to_string()],
+            2,
+            &budget(),
+            &AutodetectCancel::default(),
+            &registered,
+        );
+
+        let found: Vec<(&str, &str, bool)> = scan
+            .projects
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.agents.first().map_or("", String::as_str),
+                    p.has_project_config,
+                )
+            })
+            .collect();
+        assert_eq!(found, [("api", "cursor", false), ("website", "", true)]);
+        assert_eq!(scan.projects[0].registered_as.as_deref(), Some("api"));
+        assert!(!scan.truncated);
+
+        let tight = ScanBudget {
+            max_dirs: 2,
+            ..budget()
+        };
+        let partial = scan_with(
+            &[root_path.display().to_string()],
+            2,
+            &tight,
+            &AutodetectCancel::default(),
+            &HashMap::new(),
+        );
+        assert!(partial.truncated);
+        assert_eq!(partial.dirs_scanned, 2);
+    }
+}
@@ -1,16 +1,20 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
 
 use crate::agent;
-use crate::core::Project;
+use crate::core::{generated_content_matches, Project, SkillShadow, CONFIG_METADATA_KEY};
 
 use super::helpers::{
-    build_selected_servers, extract_agent_machine_name, load_mcp_server_configs,
-    load_skill_contents,
+    build_selected_servers, extract_agent_machine_name, gemini_extension_rule_names,
+    load_mcp_server_configs, load_rule_contents, load_skill_contents, resolve_file_rules,
+    servers_for_agent,
 };
+use super::line_diff::{size_summary, unified_diff};
+use super::skill_shadow::detect_skill_shadows;
 
 // ── Drift types ───────────────────────────────────────────────────────────────
 
@@ -19,7 +23,9 @@ use super::helpers::{
 pub struct DriftedFile {
     /// Relative path from the project directory (e.g. `.mcp.json`).
     pub path: String,
-    /// Short description of why it's drifted: "missing", "modified", "stale", "unreadable".
+    /// Short description of why it's drifted: "missing", "modified", "stale",
+    /// "unreadable", or "conflicted" (a skill copy edited in the project
+    /// while the registry version also changed).
     pub reason: String,
     /// The content Automatic would generate. Present only for "modified" files.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -27,6 +33,15 @@ pub struct DriftedFile {
     /// The content currently on disk. Present only for "modified" files.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub actual: Option<String>,
+    /// Server-level differences for "modified" JSON MCP configs, e.g.
+    /// `server 'postgres' env differs`.  A detailed check adds a size
+    /// comparison for modified files too large to diff.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    /// Unified diff from `expected` to `actual`.  Present only for
+    /// "modified" and "conflicted" files in a detailed check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
 }
 
 /// Per-agent drift report returned by [`check_project_drift`].
@@ -67,8 +82,39 @@ pub struct DriftReport {
 /// Check whether the on-disk agent configs match what Automatic would generate.
 /// Returns a [`DriftReport`] describing which agents and files have drifted,
 /// and any instruction files that have external content Automatic was not aware of.
-/// This is a read-only operation — nothing is written.
-pub fn check_project_drift(project: &Project) -> Result<DriftReport, String> {
+/// This is a read-only operation — nothing is written to disk; the result is
+/// remembered for [`cached_drifted_agents`].
+///
+/// With `detailed`, modified files also carry a unified diff (see
+/// [`DriftedFile::diff`]).
+pub fn check_project_drift(project: &Project, detailed: bool) -> Result<DriftReport, String> {
+    let mut report = compute_project_drift(project)?;
+    if detailed {
+        for file in report.agents.iter_mut().flat_map(|a| a.files.iter_mut()) {
+            add_file_diff(file);
+        }
+    }
+    let drifted_agents = report.agents.iter().map(|a| a.agent_id.clone()).collect();
+    if let Ok(mut cache) = drift_cache().lock() {
+        cache.insert(project.name.clone(), drifted_agents);
+    }
+    Ok(report)
+}
+
+/// Agents found drifted by the most recent drift check of each project in
+/// this process.  Summaries read it so they never trigger a fresh check.
+fn drift_cache() -> &'static Mutex<HashMap<String, Vec<String>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The agents that had drifted when `project` was last checked, or `None`
+/// if it has not been checked since the app started.
+pub fn cached_drifted_agents(project: &str) -> Option<Vec<String>> {
+    drift_cache().lock().ok()?.get(project).cloned()
+}
+
+pub(super) fn compute_project_drift(project: &Project) -> Result<DriftReport, String> {
     if project.directory.is_empty() || project.agents.is_empty() {
         return Ok(DriftReport {
             drifted: false,
@@ -90,16 +136,25 @@ pub fn check_project_drift(project: &Project) -> Result<DriftReport, String> {
     // (strips internal `_` fields, substitutes OAuth proxy configs).
     let mcp_config = load_mcp_server_configs()?;
     let enabled_mcp_servers = project.enabled_mcp_servers();
-    let selected_servers = build_selected_servers(&project.name, &enabled_mcp_servers, &mcp_config);
-
-    let mut skill_contents = load_skill_contents(&project.skills);
+    let selected_servers = build_selected_servers(project, &enabled_mcp_servers, &mcp_config);
+
+    let selected_skills = crate::core::selected_skill_names(project);
+    let mut skill_contents = load_skill_contents(&selected_skills);
+    // Project copies the user chose to keep are user-managed, like local skills.
+    skill_contents.retain(|(name, _)| !project.preserved_skills.contains(name));
+    let user_managed_skills: Vec<String> = project
+        .local_skills
+        .iter()
+        .chain(project.preserved_skills.iter())
+        .cloned()
+        .collect();
+    let skill_conflicts = detect_skill_shadows(project);
     let custom_skills = project.custom_skills.as_deref().unwrap_or(&[]);
     for cs in custom_skills {
         skill_contents.push((cs.name.clone(), cs.content.clone()));
     }
     let custom_skill_names: Vec<String> = custom_skills.iter().map(|s| s.name.clone()).collect();
-    let all_selected_skill_names: Vec<String> = project
-        .skills
+    let all_selected_skill_names: Vec<String> = selected_skills
         .iter()
         .chain(custom_skill_names.iter())
         .cloned()
@@ -111,15 +166,52 @@ pub fn check_project_drift(project: &Project) -> Result<DriftReport, String> {
         if let Some(agent_instance) = agent::from_id(agent_id) {
             let mut files: Vec<DriftedFile> = Vec::new();
 
-            collect_mcp_drift(agent_instance, &dir, &selected_servers, &mut files);
+            let agent_servers = servers_for_agent(project, agent_id, &selected_servers);
+            let use_gemini_extension = agent_id == "gemini"
+                && project
+                    .agent_options
+                    .get(agent_id)
+                    .is_some_and(|o| o.gemini_use_extension);
+            if use_gemini_extension {
+                collect_gemini_extension_drift(
+                    &dir,
+                    &agent_servers,
+                    &gemini_extension_rule_names(project),
+                    &mut files,
+                );
+            } else if agent_id == "claude" {
+                let scopes = project
+                    .agent_options
+                    .get(agent_id)
+                    .map(|o| o.claude_mcp_scopes.clone())
+                    .unwrap_or_default();
+                let (project_servers, _) =
+                    agent::split_claude_servers_by_scope(&agent_servers, &scopes);
+                collect_mcp_drift(agent_instance, &dir, &project_servers, &mut files);
+                let (expected, actual) =
+                    agent::claude_local_scope_state(&dir, &agent_servers, &scopes);
+                collect_claude_local_drift(&expected, &actual, &mut files);
+            } else {
+                collect_mcp_drift(agent_instance, &dir, &agent_servers, &mut files);
+            }
+            if agent_id == "opencode" {
+                let allowed: Vec<String> = all_selected_skill_names
+                    .iter()
+                    .chain(user_managed_skills.iter())
+                    .cloned()
+                    .collect();
+                let (expected, actual) = agent::opencode_skill_permission_state(&dir, &allowed);
+                collect_opencode_skill_permission_drift(&expected, actual.as_ref(), &mut files);
+            }
             collect_skills_drift(
                 agent_instance,
                 &dir,
                 &skill_contents,
                 &all_selected_skill_names,
-                &project.local_skills,
+                &user_managed_skills,
                 &mut files,
             );
+            mark_conflicted_skills(&mut files, &skill_conflicts);
             collect_agents_drift(
                 agent_instance,
                 &dir,
@@ -127,6 +219,11 @@ pub fn check_project_drift(project: &Project) -> Result<DriftReport, String> {
                 &project.user_agents,
                 &mut files,
             );
+            let native_rules = load_rule_contents(&resolve_file_rules(
+                project,
+                agent_instance.project_file_name(),
+            ));
+            collect_rules_drift(agent_instance, &dir, &native_rules, &mut files);
 
             if !files.is_empty() {
                 agent_drifts.push(AgentDrift {
@@ -148,6 +245,31 @@ pub fn check_project_drift(project: &Project) -> Result<DriftReport, String> {
     })
 }
 
+/// Fill in `file.diff`, or a size comparison in `details` when the file is
+/// binary or too large to diff.
+fn add_file_diff(file: &mut DriftedFile) {
+    let (Some(expected), Some(actual)) = (&file.expected, &file.actual) else {
+        return;
+    };
+    match unified_diff(expected, actual) {
+        Some(diff) => file.diff = Some(diff),
+        None => file.details.push(size_summary(expected, actual)),
+    }
+}
+
+/// Relabel "modified" skill files that belong to a conflicted skill, so the
+/// UI offers conflict resolution instead of a plain overwrite.
+fn mark_conflicted_skills(files: &mut [DriftedFile], conflicts: &[SkillShadow]) {
+    for file in files.iter_mut().filter(|f| f.reason == "modified") {
+        let conflicted = conflicts
+            .iter()
+            .any(|c| file.path.ends_with(&format!("/{}/SKILL.md", c.name)));
+        if conflicted {
+            file.reason = "conflicted".into();
+        }
+    }
+}
+
 /// Public wrapper for use by the `commands` layer.
 pub fn collect_instruction_conflicts_pub(
     project: &Project,
@@ -299,8 +421,7 @@ fn collect_instruction_file_conflicts(
         // every write.  If no snapshot exists Automatic has never written the
         // file, so we leave it empty — the UI will show a plain preview.
         let automatic_content =
-            crate::core::read_instruction_snapshot(project.directory.as_str(), filename)
-                .unwrap_or_default();
+            crate::core::read_instruction_snapshot(project, filename).unwrap_or_default();
 
         conflicts.push(InstructionFileConflict {
             filename: filename.clone(),
@@ -327,6 +448,24 @@ fn collect_mcp_drift(
         Err(_) => return,
     };
 
+    // Agents that merge into a shared settings file (Zed, Gemini CLI,
+    // Copilot) keep whatever else the file holds, so start from the copy on
+    // disk: an in-sync file then comes back unchanged.
+    for existing in agent_instance.cleanup_mcp_preview(dir) {
+        let existing = PathBuf::from(existing);
+        let Ok(relative) = existing.strip_prefix(dir) else {
+            continue;
+        };
+        if !existing.is_file() {
+            continue;
+        }
+        let seeded = tmp.path().join(relative);
+        if let Some(parent) = seeded.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::copy(&existing, &seeded);
+    }
+
     if agent_instance
         .write_mcp_config(tmp.path(), servers)
         .is_err()
@@ -334,19 +473,10 @@ fn collect_mcp_drift(
         return;
     }
 
-    let tmp_entries: Vec<_> = match fs::read_dir(tmp.path()) {
-        Ok(e) => e.flatten().collect(),
-        Err(_) => return,
-    };
-
-    for entry in &tmp_entries {
-        let tmp_path = entry.path();
-        if !tmp_path.is_file() {
-            continue;
-        }
-        let filename = match tmp_path.file_name().and_then(|n| n.to_str()) {
-            Some(f) => f.to_string(),
-            None => continue,
+    for tmp_path in files_under(tmp.path()) {
+        let filename = match tmp_path.strip_prefix(tmp.path()) {
+            Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+            Err(_) => continue,
         };
         let disk_path = dir.join(&filename);
 
@@ -356,6 +486,8 @@ fn collect_mcp_drift(
                 reason: "missing".into(),
                 expected: None,
                 actual: None,
+                details: Vec::new(),
+                diff: None,
             });
             continue;
         }
@@ -372,17 +504,232 @@ fn collect_mcp_drift(
                     reason: "unreadable".into(),
                     expected: None,
                     actual: None,
+                    details: Vec::new(),
+                    diff: None,
                 });
                 continue;
             }
         };
-        if expected != actual {
+        if !generated_content_matches(&disk_path, &expected, &actual) {
+            let details = if filename.ends_with(".json") {
+                mcp_config_details(&expected, &actual)
+            } else {
+                Vec::new()
+            };
             out.push(DriftedFile {
                 path: filename,
                 reason: "modified".into(),
                 expected: Some(expected),
                 actual: Some(actual),
+                details,
+                diff: None,
+            });
+        }
+    }
+}
+
+/// Every file below `root`, at any depth, sorted.
+fn files_under(root: &std::path::Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Keys agents nest their MCP server maps under.
+const MCP_SERVER_KEYS: &[&str] = &["mcpServers", "servers", "context_servers", "mcp"];
+
+/// Describe, server by server, how the MCP config on disk differs from the
+/// one Automatic would write.  Returns nothing when either side is not a
+/// JSON object, so the caller falls back to the plain "modified" reason.
+fn mcp_config_details(expected: &str, actual: &str) -> Vec<String> {
+    let (Ok(Value::Object(expected)), Ok(Value::Object(actual))) = (
+        serde_json::from_str::<Value>(expected),
+        serde_json::from_str::<Value>(actual),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut details = Vec::new();
+    let empty = Map::new();
+    for key in MCP_SERVER_KEYS {
+        let want = expected.get(*key).and_then(Value::as_object);
+        let have = actual.get(*key).and_then(Value::as_object);
+        if want.is_none() && have.is_none() {
+            continue;
+        }
+        details.extend(server_map_details(
+            want.unwrap_or(&empty),
+            have.unwrap_or(&empty),
+        ));
+    }
+    let is_server_or_metadata =
+        |key: &str| MCP_SERVER_KEYS.contains(&key) || key == CONFIG_METADATA_KEY;
+    for (key, value) in &expected {
+        if !is_server_or_metadata(key) && actual.get(key) != Some(value) {
+            details.push(format!("'{}' differs", key));
+        }
+    }
+    for key in actual.keys() {
+        if !is_server_or_metadata(key) && !expected.contains_key(key) {
+            details.push(format!("'{}' added on disk", key));
+        }
+    }
+    details
+}
+
+/// Per-server differences between two server maps, in name order.
+fn server_map_details(expected: &Map<String, Value>, actual: &Map<String, Value>) -> Vec<String> {
+    let names: BTreeSet<&String> = expected.keys().chain(actual.keys()).collect();
+    let mut details = Vec::new();
+    for name in names {
+        match (expected.get(name), actual.get(name)) {
+            (Some(_), None) => details.push(format!("server '{}' missing", name)),
+            (None, Some(_)) => details.push(format!("server '{}' added on disk", name)),
+            (Some(Value::Object(want)), Some(Value::Object(have))) if want != have => {
+                let fields: BTreeSet<&String> = want.keys().chain(have.keys()).collect();
+                for field in fields {
+                    if want.get(field) != have.get(field) {
+                        details.push(format!("server '{}' {} differs", name, field));
+                    }
+                }
+            }
+            (Some(want), Some(have)) if want != have => {
+                details.push(format!("server '{}' differs", name));
+            }
+            _ => {}
+        }
+    }
+    details
+}
+
+/// Compare the local-scope servers Claude Code should have in
+/// `~/.claude.json` with what it holds, adding one entry to `out` on mismatch.
+fn collect_claude_local_drift(
+    expected: &Map<String, Value>,
+    actual: &Map<String, Value>,
+    out: &mut Vec<DriftedFile>,
+) {
+    if expected == actual {
+        return;
+    }
+    let path = "~/.claude.json".to_string();
+    let drifted = if actual.is_empty() {
+        DriftedFile {
+            path,
+            reason: "missing".into(),
+            expected: None,
+            actual: None,
+            details: Vec::new(),
+            diff: None,
+        }
+    } else if expected.is_empty() {
+        DriftedFile {
+            path,
+            reason: "stale".into(),
+            expected: None,
+            actual: None,
+            details: Vec::new(),
+            diff: None,
+        }
+    } else {
+        let pretty = |servers: &Map<String, Value>| {
+            serde_json::to_string_pretty(&Value::Object(servers.clone())).ok()
+        };
+        DriftedFile {
+            path,
+            reason: "modified".into(),
+            expected: pretty(expected),
+            actual: pretty(actual),
+            details: server_map_details(expected, actual),
+            diff: None,
+        }
+    };
+    out.push(drifted);
+}
+
+/// Compare the `permission.skill` value OpenCode should have in
+/// `opencode.json` with what it holds.  The entry joins one already
+/// reported for the file by the MCP check.
+fn collect_opencode_skill_permission_drift(
+    expected: &Value,
+    actual: Option<&Value>,
+    out: &mut Vec<DriftedFile>,
+) {
+    if actual == Some(expected) {
+        return;
+    }
+    let detail = match actual {
+        None => "'permission.skill' missing".to_string(),
+        Some(_) => "'permission.skill' differs".to_string(),
+    };
+    if let Some(existing) = out.iter_mut().find(|f| f.path == "opencode.json") {
+        existing.details.push(detail);
+        return;
+    }
+    let pretty = |value: &Value| serde_json::to_string_pretty(value).ok();
+    out.push(DriftedFile {
+        path: "opencode.json".to_string(),
+        reason: "modified".into(),
+        expected: pretty(expected),
+        actual: actual.and_then(pretty),
+        details: vec![detail],
+        diff: None,
+    });
+}
+
+/// Collect drift for the Gemini CLI `automatic` extension files into `out`.
+fn collect_gemini_extension_drift(
+    dir: &PathBuf,
+    servers: &Map<String, Value>,
+    rule_names: &[String],
+    out: &mut Vec<DriftedFile>,
+) {
+    let expected_files = match agent::gemini_extension_files(servers, rule_names) {
+        Ok(f) => f,
+        Err(_) => return,
+    };
+    for (rel, expected) in expected_files {
+        let disk_path = dir.join(&rel);
+        if !disk_path.exists() {
+            out.push(DriftedFile {
+                path: rel,
+                reason: "missing".into(),
+                expected: None,
+                actual: None,
+                details: Vec::new(),
+                diff: None,
             });
+            continue;
+        }
+        match fs::read_to_string(&disk_path) {
+            Ok(actual) if generated_content_matches(&disk_path, &expected, &actual) => {}
+            Ok(actual) => out.push(DriftedFile {
+                path: rel,
+                reason: "modified".into(),
+                expected: Some(expected),
+                actual: Some(actual),
+                details: Vec::new(),
+                diff: None,
+            }),
+            Err(_) => out.push(DriftedFile {
+                path: rel,
+                reason: "unreadable".into(),
+                expected: None,
+                actual: None,
+                details: Vec::new(),
+                diff: None,
+            }),
         }
     }
 }
@@ -447,6 +794,8 @@ fn collect_skills_drift(
                             reason: "missing".into(),
                             expected: None,
                             actual: None,
+                            details: Vec::new(),
+                            diff: None,
                         });
                         continue;
                     }
@@ -463,16 +812,20 @@ fn collect_skills_drift(
                                 reason: "unreadable".into(),
                                 expected: None,
                                 actual: None,
+                                details: Vec::new(),
+                                diff: None,
                             });
                             continue;
                         }
                     };
-                    if expected != actual {
+                    if !generated_content_matches(&disk_file, &expected, &actual) {
                         out.push(DriftedFile {
                             path: rel_path,
                             reason: "modified".into(),
                             expected: Some(expected),
                             actual: Some(actual),
+                            details: Vec::new(),
+                            diff: None,
                         });
                     }
                 }
@@ -505,6 +858,8 @@ fn collect_skills_drift(
                                 reason: "stale".into(),
                                 expected: None,
                                 actual,
+                                details: Vec::new(),
+                                diff: None,
                             });
                         }
                     }
@@ -570,6 +925,8 @@ fn collect_agents_drift(
                 reason: "missing".into(),
                 expected: Some(converted_content),
                 actual: None,
+                details: Vec::new(),
+                diff: None,
             });
         } else if let Ok(disk_content) = fs::read_to_string(&agent_path) {
             if disk_content != converted_content {
@@ -579,6 +936,8 @@ fn collect_agents_drift(
                     reason: "modified".into(),
                     expected: Some(converted_content),
                     actual: Some(disk_content),
+                    details: Vec::new(),
+                    diff: None,
                 });
             }
         }
@@ -601,6 +960,8 @@ fn collect_agents_drift(
                         reason: "missing".into(),
                         expected: Some(converted_content),
                         actual: None,
+                        details: Vec::new(),
+                        diff: None,
                     });
                 } else if let Ok(disk_content) = fs::read_to_string(&agent_path) {
                     if disk_content != converted_content {
@@ -610,6 +971,8 @@ fn collect_agents_drift(
                             reason: "modified".into(),
                             expected: Some(converted_content),
                             actual: Some(disk_content),
+                            details: Vec::new(),
+                            diff: None,
                         });
                     }
                 }
@@ -634,6 +997,8 @@ fn collect_agents_drift(
                                 reason: "stale".into(),
                                 expected: None,
                                 actual,
+                                details: Vec::new(),
+                                diff: None,
                             });
                         }
                     }
@@ -643,6 +1008,48 @@ fn collect_agents_drift(
     }
 }
 
+/// Collect drift in the agent's native rule files (see
+/// [`agent::Agent::rules_dir`]) into `out`: assigned rules whose file is
+/// missing or differs, and managed files for rules no longer assigned.
+fn collect_rules_drift(
+    agent_instance: &dyn agent::Agent,
+    dir: &PathBuf,
+    rules: &[(String, String)],
+    out: &mut Vec<DriftedFile>,
+) {
+    let expected = agent::expected_rule_files(agent_instance, dir, rules);
+    let relative = |path: &PathBuf| path.strip_prefix(dir).unwrap_or(path).display().to_string();
+
+    for (path, content) in &expected {
+        let (reason, actual) = match fs::read_to_string(path) {
+            Ok(actual) if actual == *content => continue,
+            Ok(actual) => ("modified", Some(actual)),
+            Err(_) => ("missing", None),
+        };
+        out.push(DriftedFile {
+            path: relative(path),
+            reason: reason.into(),
+            expected: Some(content.clone()),
+            actual,
+            details: Vec::new(),
+            diff: None,
+        });
+    }
+    for path in agent::managed_rule_files(agent_instance, dir) {
+        if expected.iter().any(|(p, _)| *p == path) {
+            continue;
+        }
+        out.push(DriftedFile {
+            path: relative(&path),
+            reason: "stale".into(),
+            expected: None,
+            actual: fs::read_to_string(&path).ok(),
+            details: Vec::new(),
+            diff: None,
+        });
+    }
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -699,6 +1106,222 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merged_zed_settings_drift_only_when_servers_differ() {
+        let project_dir = tempdir().unwrap();
+        let dir = project_dir.path().to_path_buf();
+        let settings = dir.join(".zed/settings.json");
+        fs::create_dir_all(settings.parent().unwrap()).unwrap();
+        fs::write(&settings, r#"{"theme": "One Dark", "ui_font_size": 16}"#).unwrap();
+
+        let mut servers = Map::new();
+        servers.insert(
+            "github".to_string(),
+            serde_json::json!({"type": "stdio", "command": "npx", "args": ["gh"]}),
+        );
+        agent::Zed.write_mcp_config(&dir, &servers).unwrap();
+
+        let mut files = Vec::new();
+        collect_mcp_drift(&agent::Zed, &dir, &servers, &mut files);
+        assert!(
+            files.is_empty(),
+            "{:?}",
+            files.iter().map(|f| &f.path).collect::<Vec<_>>()
+        );
+
+        servers.insert(
+            "linear".to_string(),
+            serde_json::json!({"type": "http", "url": "https://mcp.linear.app/sse"}),
+        );
+        collect_mcp_drift(&agent::Zed, &dir, &servers, &mut files);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, ".zed/settings.json");
+        assert_eq!(files[0].reason, "modified");
+    }
+
+    #[test]
+    fn native_rule_files_drift_after_edits_and_unassignment() {
+        let project_dir = tempdir().unwrap();
+        let dir = project_dir.path().to_path_buf();
+        let rules = vec![
+            ("style".to_string(), "Use tabs.".to_string()),
+            ("testing".to_string(), "Write tests first.".to_string()),
+        ];
+        agent::Cursor.write_rules(&dir, &rules).unwrap();
+
+        let mut files = Vec::new();
+        collect_rules_drift(&agent::Cursor, &dir, &rules, &mut files);
+        assert!(files.is_empty());
+
+        fs::write(dir.join(".cursor/rules/automatic-style.mdc"), "edited").unwrap();
+        fs::write(dir.join(".cursor/rules/team.mdc"), "Our own rule.").unwrap();
+        collect_rules_drift(&agent::Cursor, &dir, &rules[..1], &mut files);
+        let found: Vec<(&str, &str)> = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.reason.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (".cursor/rules/automatic-style.mdc", "modified"),
+                (".cursor/rules/automatic-testing.mdc", "stale"),
+            ]
+        );
+    }
+
+    fn snapshot_files(dir: &std::path::Path, out: &mut Vec<(String, Vec<u8>)>) {
+        let mut entries: Vec<_> = fs::read_dir(dir).unwrap().flatten().collect();
+        entries.sort_by_key(|e| e.path());
+        for entry in entries {
+            let path = entry.path();
+            if path.is_dir() {
+                snapshot_files(&path, out);
+            } else {
+                out.push((path.display().to_string(), fs::read(&path).unwrap()));
+            }
+        }
+    }
+
+    /// Two consecutive syncs of the same project must leave every generated
+    /// file byte-identical, including `project.json` (whose `file_rules` and
+    /// `agent_options` are hash maps).
+    #[test]
+    fn consecutive_syncs_write_identical_files() {
+        let project_dir = tempdir().unwrap();
+        let mut project = Project {
+            name: "stable".into(),
+            directory: project_dir.path().display().to_string(),
+            agents: ["claude", "cursor", "gemini", "opencode", "codex"]
+                .map(String::from)
+                .to_vec(),
+            instruction_mode: "per-agent".into(),
+            unregistered: true,
+            ..Default::default()
+        };
+        for key in ["CLAUDE.md", "AGENTS.md", "GEMINI.md", "zz.md", "aa.md"] {
+            project.file_rules.insert(key.into(), vec![]);
+        }
+        for agent in ["gemini", "claude", "cursor"] {
+            project
+                .agent_options
+                .insert(agent.into(), crate::core::AgentOptions::default());
+        }
+
+        crate::sync::sync_project_without_autodetect(&mut project).unwrap();
+        let mut first = Vec::new();
+        snapshot_files(project_dir.path(), &mut first);
+
+        crate::sync::sync_project_without_autodetect(&mut project).unwrap();
+        let mut second = Vec::new();
+        snapshot_files(project_dir.path(), &mut second);
+
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+
+    /// A read-only agent gets `mcp-serve --read-only`; other agents keep full
+    /// access, and drift detection expects the same per-agent configs.
+    #[test]
+    fn read_only_agent_option_adds_flag_to_automatic_server() {
+        let project_dir = tempdir().unwrap();
+        let mut project = Project {
+            name: "restricted".into(),
+            directory: project_dir.path().display().to_string(),
+            agents: vec!["claude".into(), "cursor".into()],
+            unregistered: true,
+            ..Default::default()
+        };
+        project.agent_options.insert(
+            "cursor".into(),
+            crate::core::AgentOptions {
+                automatic_mcp_read_only: true,
+                ..Default::default()
+            },
+        );
+        crate::sync::sync_project_without_autodetect(&mut project).unwrap();
+
+        let args = |path: &str| -> Vec<String> {
+            let raw = fs::read_to_string(project_dir.path().join(path)).unwrap();
+            let config: serde_json::Value = serde_json::from_str(&raw).unwrap();
+            serde_json::from_value(config["mcpServers"]["automatic"]["args"].clone()).unwrap()
+        };
+        assert_eq!(args(".mcp.json"), vec!["mcp-serve"]);
+        assert_eq!(args(".cursor/mcp.json"), vec!["mcp-serve", "--read-only"]);
+
+        let report = compute_project_drift(&project).unwrap();
+        let mcp_drift: Vec<&DriftedFile> = report
+            .agents
+            .iter()
+            .flat_map(|a| &a.files)
+            .filter(|f| f.path.ends_with("mcp.json"))
+            .collect();
+        assert!(mcp_drift.is_empty(), "unexpected drift: {:?}", mcp_drift);
+    }
+
+    /// An on-disk MCP config with the same content in a different key order
+    /// and layout (e.g. re-saved by the agent) is not drift.
+    #[test]
+    fn reordered_mcp_config_is_not_drift() {
+        let project_dir = tempdir().unwrap();
+        let mut servers = Map::new();
+        servers.insert(
+            "github".to_string(),
+            serde_json::json!({"command": "npx", "args": ["-y", "server-github"], "env": {"B": "2", "A": "1"}}),
+        );
+        ClaudeCode
+            .write_mcp_config(project_dir.path(), &servers)
+            .unwrap();
+
+        fs::write(
+            project_dir.path().join(".mcp.json"),
+            r#"{"mcpServers":{"github":{"env":{"A":"1","B":"2"},"args":["-y","server-github"],"command":"npx"}}}"#,
+        )
+        .unwrap();
+
+        let mut files: Vec<DriftedFile> = Vec::new();
+        collect_mcp_drift(
+            &ClaudeCode,
+            &project_dir.path().to_path_buf(),
+            &servers,
+            &mut files,
+        );
+        assert!(files.is_empty(), "unexpected drift: {:?}", files);
+    }
+
+    /// Gemini extension mode: the extension files written by
+    /// `write_gemini_extension` match, and a hand edit is reported.
+    #[test]
+    fn gemini_extension_drift_tracks_extension_files() {
+        let project_dir = tempdir().unwrap();
+        let dir = project_dir.path().to_path_buf();
+        let mut servers = Map::new();
+        servers.insert(
+            "automatic".to_string(),
+            serde_json::json!({"command": "/usr/local/bin/automatic", "args": ["mcp-serve"]}),
+        );
+        let rules = vec!["automatic-service".to_string()];
+
+        let mut files: Vec<DriftedFile> = Vec::new();
+        collect_gemini_extension_drift(&dir, &servers, &rules, &mut files);
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|f| f.reason == "missing"));
+
+        agent::write_gemini_extension(&dir, &servers, &rules).unwrap();
+        files.clear();
+        collect_gemini_extension_drift(&dir, &servers, &rules, &mut files);
+        assert!(files.is_empty(), "unexpected drift: {:?}", files);
+
+        let manifest = agent::gemini_extension_dir(&dir).join("gemini-extension.json");
+        fs::write(&manifest, "{}").unwrap();
+        collect_gemini_extension_drift(&dir, &servers, &rules, &mut files);
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].path,
+            ".gemini/extensions/automatic/gemini-extension.json"
+        );
+        assert_eq!(files[0].reason, "modified");
+    }
+
     /// After `write_mcp_config` writes the MCP config, `collect_mcp_drift` must
     /// report no drift for the same servers map.
     #[test]
@@ -803,7 +1426,7 @@ mod tests {
             .map(|e| e.path())
             .collect();
 
-        let _ = check_project_drift(&project);
+        let _ = check_project_drift(&project, false);
 
         let after: Vec<_> = fs::read_dir(project_dir.path())
             .unwrap()
@@ -899,4 +1522,236 @@ mod tests {
             "Expected a 'missing' drift entry"
         );
     }
+
+    #[test]
+    fn edited_copy_of_a_conflicted_skill_is_reported_as_conflicted() {
+        let project_dir = tempdir().unwrap();
+        let skill_dir = project_dir.path().join(".claude/skills/terraform-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Pinned v1\n").unwrap();
+
+        let skill_contents = vec![("terraform-skill".to_string(), "# v2\n".to_string())];
+        let selected_names = vec!["terraform-skill".to_string()];
+        let mut files: Vec<DriftedFile> = Vec::new();
+        collect_skills_drift(
+            &ClaudeCode,
+            &project_dir.path().to_path_buf(),
+            &skill_contents,
+            &selected_names,
+            &[],
+            &mut files,
+        );
+        assert_eq!(files[0].reason, "modified");
+
+        mark_conflicted_skills(
+            &mut files,
+            &[SkillShadow {
+                name: "terraform-skill".into(),
+                path: ".claude/skills/terraform-skill".into(),
+                project_hash: String::new(),
+                registry_hash: String::new(),
+            }],
+        );
+        assert_eq!(files[0].reason, "conflicted");
+        assert_eq!(files[0].actual.as_deref(), Some("# Pinned v1\n"));
+    }
+
+    #[test]
+    fn detailed_check_diffs_a_hand_edited_skill() {
+        let project_dir = tempdir().unwrap();
+        let skill_contents = vec![(
+            "review".to_string(),
+            "# Review\n\nCheck tests.\nCheck docs.\n".to_string(),
+        )];
+        let selected_names = vec!["review".to_string()];
+        ClaudeCode
+            .sync_skills(project_dir.path(), &skill_contents, &selected_names, &[])
+            .unwrap();
+        fs::write(
+            project_dir.path().join(".claude/skills/review/SKILL.md"),
+            "# Review\n\nCheck tests twice.\nCheck docs.\n",
+        )
+        .unwrap();
+
+        let mut files: Vec<DriftedFile> = Vec::new();
+        collect_skills_drift(
+            &ClaudeCode,
+            &project_dir.path().to_path_buf(),
+            &skill_contents,
+            &selected_names,
+            &[],
+            &mut files,
+        );
+        assert_eq!(files.len(), 1);
+        assert!(files[0].diff.is_none(), "diffs are opt-in");
+
+        add_file_diff(&mut files[0]);
+        let diff = files[0].diff.as_deref().unwrap();
+        assert!(
+            diff.contains("-Check tests.\n+Check tests twice.\n"),
+            "{}",
+            diff
+        );
+
+        // Binary content falls back to a size comparison.
+        let mut binary = DriftedFile {
+            path: "blob".into(),
+            reason: "modified".into(),
+            expected: Some("a\0".into()),
+            actual: Some("ab\0".into()),
+            details: Vec::new(),
+            diff: None,
+        };
+        add_file_diff(&mut binary);
+        assert!(binary.diff.is_none());
+        assert_eq!(
+            binary.details,
+            vec!["expected 2 bytes, found 3 bytes on disk"]
+        );
+    }
+
+    #[test]
+    fn mcp_details_report_added_and_missing_servers() {
+        let expected =
+            r#"{"mcpServers":{"automatic":{"command":"automatic"},"github":{"command":"npx"}}}"#;
+        let actual = r#"{"mcpServers":{"github":{"command":"npx"},"linear":{"command":"npx"}}}"#;
+        assert_eq!(
+            mcp_config_details(expected, actual),
+            vec![
+                "server 'automatic' missing".to_string(),
+                "server 'linear' added on disk".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn mcp_details_report_each_differing_field() {
+        let expected =
+            r#"{"servers":{"postgres":{"command":"pg","args":["-v"],"env":{"PGHOST":"db"}}}}"#;
+        let actual = r#"{"servers":{"postgres":{"command":"pg","args":["-q"],"env":{"PGHOST":"localhost"},"cwd":"/tmp"}}}"#;
+        assert_eq!(
+            mcp_config_details(expected, actual),
+            vec![
+                "server 'postgres' args differs".to_string(),
+                "server 'postgres' cwd differs".to_string(),
+                "server 'postgres' env differs".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn mcp_details_report_top_level_keys_outside_the_server_map() {
+        let expected = r#"{"mcpServers":{},"inputs":[]}"#;
+        let actual = r#"{"mcpServers":{},"inputs":[{"id":"token"}],"theme":"dark"}"#;
+        assert_eq!(
+            mcp_config_details(expected, actual),
+            vec![
+                "'inputs' differs".to_string(),
+                "'theme' added on disk".to_string()
+            ]
+        );
+        // Not JSON objects: no details, the plain reason stands.
+        assert!(mcp_config_details("[mcp]\n", "{}").is_empty());
+    }
+
+    #[test]
+    fn modified_json_mcp_config_carries_server_details() {
+        let project_dir = tempdir().unwrap();
+        let mut servers = Map::new();
+        servers.insert(
+            "github".to_string(),
+            serde_json::json!({"command": "npx", "env": {"TOKEN": "a"}}),
+        );
+        fs::write(
+            project_dir.path().join(".mcp.json"),
+            r#"{"mcpServers":{"github":{"command":"npx","env":{"TOKEN":"b"}}}}"#,
+        )
+        .unwrap();
+
+        let mut files: Vec<DriftedFile> = Vec::new();
+        collect_mcp_drift(
+            &ClaudeCode,
+            &project_dir.path().to_path_buf(),
+            &servers,
+            &mut files,
+        );
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].reason, "modified");
+        assert_eq!(files[0].details, vec!["server 'github' env differs"]);
+        let json = serde_json::to_value(&files[0]).unwrap();
+        assert_eq!(json["details"][0], "server 'github' env differs");
+    }
+
+    #[test]
+    fn command_variants_apply_per_agent_and_drift_stays_clean() {
+        let project_dir = tempdir().unwrap();
+        let dir = project_dir.path().to_path_buf();
+        let project = Project {
+            name: "web".to_string(),
+            directory: dir.display().to_string(),
+            agents: vec!["claude".to_string(), "cursor".to_string()],
+            ..Default::default()
+        };
+        let mut selected = Map::new();
+        selected.insert(
+            "github".to_string(),
+            serde_json::json!({
+                "command": "npx",
+                "args": ["-y", "server-github"],
+                "variants": {"cursor": {"command": "/opt/bin/server-github", "args": []}}
+            }),
+        );
+
+        for agent_id in &project.agents {
+            let agent_instance = agent::from_id(agent_id).unwrap();
+            let servers = servers_for_agent(&project, agent_id, &selected);
+            agent_instance.write_mcp_config(&dir, &servers).unwrap();
+        }
+
+        let claude = fs::read_to_string(dir.join(".mcp.json")).unwrap();
+        assert!(claude.contains("\"npx\""), "{}", claude);
+        let cursor = fs::read_to_string(dir.join(".cursor/mcp.json")).unwrap();
+        assert!(cursor.contains("/opt/bin/server-github"), "{}", cursor);
+        assert!(!cursor.contains("npx"), "{}", cursor);
+        assert!(!claude.contains("variants") && !cursor.contains("variants"));
+
+        for agent_id in &project.agents {
+            let agent_instance = agent::from_id(agent_id).unwrap();
+            let servers = servers_for_agent(&project, agent_id, &selected);
+            let mut files: Vec<DriftedFile> = Vec::new();
+            collect_mcp_drift(agent_instance, &dir, &servers, &mut files);
+            assert!(files.is_empty(), "{}: {:?}", agent_id, files);
+        }
+    }
+
+    #[test]
+    fn provenance_metadata_is_not_drift() {
+        let project_dir = tempdir().unwrap();
+        let dir = project_dir.path().to_path_buf();
+        let mut servers = Map::new();
+        servers.insert("db".to_string(), serde_json::json!({"command": "db-mcp"}));
+        let cursor = agent::from_id("cursor").unwrap();
+        let path = PathBuf::from(cursor.write_mcp_config(&dir, &servers).unwrap());
+
+        // Written by an older build: only the version fields differ.
+        let mut older = crate::core::config_metadata("web");
+        older["app_version"] = serde_json::json!("0.9.0");
+        older["annotation_version"] = serde_json::json!(0);
+        crate::core::annotate_json_config(&path, "web", None).unwrap();
+        let mut on_disk: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        on_disk[CONFIG_METADATA_KEY] = older;
+        fs::write(&path, on_disk.to_string()).unwrap();
+
+        let mut files: Vec<DriftedFile> = Vec::new();
+        collect_mcp_drift(cursor, &dir, &servers, &mut files);
+        assert!(files.is_empty(), "{:?}", files);
+
+        on_disk["mcpServers"]["db"]["command"] = serde_json::json!("other");
+        fs::write(&path, on_disk.to_string()).unwrap();
+        collect_mcp_drift(cursor, &dir, &servers, &mut files);
+        assert_eq!(files.len(), 1);
+        let details = &files[0].details;
+        assert!(!details.is_empty());
+        assert!(details.iter().all(|d| !d.contains(CONFIG_METADATA_KEY)));
+    }
 }
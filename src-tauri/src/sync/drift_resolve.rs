@@ -0,0 +1,474 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agent;
+use crate::core::{compute_content_hash, Project};
+
+use super::drift::{compute_project_drift, DriftReport};
+use super::mcp_dedup::{mcp_configs_equivalent, save_mcp_server_if_changed};
+
+/// Which side of a drifted file [`resolve_drift`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftResolution {
+    /// Adopt the file on disk: its MCP servers or skill content are written
+    /// back into the registry and the project.
+    KeepDisk,
+    /// Rewrite the file with what Automatic would generate.
+    KeepRegistry,
+}
+
+/// What [`resolve_drift`] changed, in each direction.
+#[derive(Debug, Default, Serialize)]
+pub struct DriftResolutionReport {
+    /// MCP servers saved to the registry or added to the project from an
+    /// agent's config on disk.
+    pub adopted_servers: Vec<String>,
+    /// MCP servers dropped from the project because the agent's config on
+    /// disk no longer lists them.
+    pub removed_servers: Vec<String>,
+    /// Global skills overwritten with the project copy.
+    pub adopted_skills: Vec<String>,
+    /// Files rewritten with the registry version.
+    pub rewritten_files: Vec<String>,
+}
+
+/// Settle drifted files one by one instead of re-syncing over all of them.
+/// `resolutions` maps each drifted path, as reported by
+/// [`check_project_drift`](super::check_project_drift), to the side to keep.
+/// Every path is checked before anything is written.  The project is
+/// persisted but not re-synced, so files left out stay as they are.
+pub fn resolve_drift(
+    project: &mut Project,
+    resolutions: &BTreeMap<String, DriftResolution>,
+) -> Result<DriftResolutionReport, String> {
+    project.ensure_unlocked()?;
+    let drift = compute_project_drift(project)?;
+    let _watch_pause = super::watch::pause_drift_watch(project);
+    let report = apply_resolutions(
+        project,
+        resolutions,
+        &drift,
+        &crate::core::save_skill,
+        &save_mcp_server_if_changed,
+    )?;
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    crate::core::persist_project(project)?;
+    Ok(report)
+}
+
+/// A drifted file both sides of which are known.
+struct Resolvable<'a> {
+    agent_id: &'a str,
+    expected: &'a str,
+    actual: &'a str,
+}
+
+/// What settling one drifted file takes, worked out before anything is
+/// written.
+enum Step<'a> {
+    Rewrite(&'a str),
+    AdoptSkill(&'a str, &'a str),
+    AdoptServers {
+        agent_id: &'a str,
+        expected: Map<String, Value>,
+        actual: Map<String, Value>,
+    },
+}
+
+/// Apply `resolutions` to the project state and files.  `save_skill`
+/// writes a global skill's `SKILL.md`; `save_server` writes a registry MCP
+/// server and returns whether it changed.
+fn apply_resolutions(
+    project: &mut Project,
+    resolutions: &BTreeMap<String, DriftResolution>,
+    drift: &DriftReport,
+    save_skill: &dyn Fn(&str, &str) -> Result<(), String>,
+    save_server: &dyn Fn(&str, &Value) -> Result<bool, String>,
+) -> Result<DriftResolutionReport, String> {
+    let mut steps = Vec::new();
+    for (path, resolution) in resolutions {
+        let target = find_resolvable(drift, path)?;
+        let step = match (resolution, skill_name_of(path)) {
+            (DriftResolution::KeepRegistry, _) => Step::Rewrite(target.expected),
+            (DriftResolution::KeepDisk, Some(skill)) => {
+                if !project.skills.iter().any(|s| s == skill) {
+                    return Err(format!(
+                        "'{}' is not a global skill of this project and cannot be adopted",
+                        skill
+                    ));
+                }
+                Step::AdoptSkill(skill, target.actual)
+            }
+            (DriftResolution::KeepDisk, None) => mcp_step(path, &target)?,
+        };
+        steps.push((path, step));
+    }
+
+    let dir = PathBuf::from(&project.directory);
+    let mut report = DriftResolutionReport::default();
+    for (path, step) in steps {
+        match step {
+            Step::Rewrite(expected) => {
+                let disk_path = dir.join(path);
+                fs::write(&disk_path, expected)
+                    .map_err(|e| format!("Failed to write '{}': {}", disk_path.display(), e))?;
+                if let Some(skill) = skill_name_of(path) {
+                    settle_skill(project, skill, expected);
+                }
+                report.rewritten_files.push(path.clone());
+            }
+            Step::AdoptSkill(skill, actual) => {
+                save_skill(skill, actual)?;
+                settle_skill(project, skill, actual);
+                if !report.adopted_skills.iter().any(|s| s == skill) {
+                    report.adopted_skills.push(skill.to_string());
+                }
+            }
+            Step::AdoptServers {
+                agent_id,
+                expected,
+                actual,
+            } => adopt_servers(
+                project,
+                agent_id,
+                &expected,
+                &actual,
+                save_server,
+                &mut report,
+            )?,
+        }
+    }
+    Ok(report)
+}
+
+/// The drifted entry for `path`, provided it has both an expected and an
+/// on-disk version.
+fn find_resolvable<'a>(drift: &'a DriftReport, path: &str) -> Result<Resolvable<'a>, String> {
+    if path.starts_with("~/") {
+        return Err(format!(
+            "'{}' is outside the project; sync the project to update it",
+            path
+        ));
+    }
+    let (agent_id, file) = drift
+        .agents
+        .iter()
+        .flat_map(|a| a.files.iter().map(move |f| (a.agent_id.as_str(), f)))
+        .find(|(_, f)| f.path == path)
+        .ok_or_else(|| format!("'{}' has not drifted", path))?;
+    match (&file.expected, &file.actual) {
+        (Some(expected), Some(actual)) if file.reason != "stale" => Ok(Resolvable {
+            agent_id,
+            expected,
+            actual,
+        }),
+        _ => Err(format!(
+            "'{}' is {}; only modified files can be resolved",
+            path, file.reason
+        )),
+    }
+}
+
+/// The skill a drifted `<skill dir>/<name>/SKILL.md` path belongs to.
+fn skill_name_of(path: &str) -> Option<&str> {
+    let rest = path.strip_suffix("/SKILL.md")?;
+    rest.rsplit('/').next().filter(|name| !name.is_empty())
+}
+
+/// Record `content` as what the project holds for `skill`, clearing any
+/// conflict or preserved copy.
+fn settle_skill(project: &mut Project, skill: &str, content: &str) {
+    project
+        .skill_hashes
+        .insert(skill.to_string(), compute_content_hash(content));
+    project.skill_conflicts.retain(|c| c.name != skill);
+    project.preserved_skills.retain(|s| s != skill);
+}
+
+/// Parse both sides of a drifted MCP config with its agent's discovery.
+fn mcp_step<'a>(path: &str, target: &Resolvable<'a>) -> Result<Step<'a>, String> {
+    let agent_instance = agent::from_id(target.agent_id)
+        .ok_or_else(|| format!("Unknown agent '{}'", target.agent_id))?;
+    let expected = servers_in(agent_instance, path, target.expected)?;
+    let actual = servers_in(agent_instance, path, target.actual)?;
+    if expected.is_empty() && actual.is_empty() {
+        return Err(format!(
+            "'{}' holds no MCP servers; only MCP configs and skills can be kept from disk",
+            path
+        ));
+    }
+    Ok(Step::AdoptServers {
+        agent_id: target.agent_id,
+        expected,
+        actual,
+    })
+}
+
+/// Adopt the MCP servers of an agent config on disk: changed and new
+/// servers go into the registry and the project, servers missing from the
+/// file leave the project.
+fn adopt_servers(
+    project: &mut Project,
+    agent_id: &str,
+    expected: &Map<String, Value>,
+    actual: &Map<String, Value>,
+    save_server: &dyn Fn(&str, &Value) -> Result<bool, String>,
+    report: &mut DriftResolutionReport,
+) -> Result<(), String> {
+    for (name, config) in actual {
+        let unchanged = expected
+            .get(name)
+            .is_some_and(|want| mcp_configs_equivalent(want, config));
+        if unchanged {
+            continue;
+        }
+        let saved = save_server(name, config)?;
+        let added = !project.mcp_servers.contains(name);
+        project.add_discovered_mcp_server(name, &[agent_id.to_string()]);
+        if (saved || added) && !report.adopted_servers.contains(name) {
+            report.adopted_servers.push(name.clone());
+        }
+    }
+    for name in expected.keys().filter(|name| !actual.contains_key(*name)) {
+        if project.mcp_servers.contains(name) {
+            project.mcp_servers.retain(|s| s != name);
+            project.mcp_server_origins.remove(name);
+            project.mcp_server_overrides.remove(name);
+            report.removed_servers.push(name.clone());
+        }
+    }
+    Ok(())
+}
+
+/// The MCP servers `agent_instance` finds in `content` when it is stored
+/// at `path`, read from a scratch directory so no other config file of the
+/// agent is mixed in.
+fn servers_in(
+    agent_instance: &dyn agent::Agent,
+    path: &str,
+    content: &str,
+) -> Result<Map<String, Value>, String> {
+    let tmp = tempfile::tempdir().map_err(|e| e.to_string())?;
+    write_scratch(tmp.path(), path, content)?;
+    Ok(agent_instance.discover_mcp_servers(tmp.path()))
+}
+
+fn write_scratch(dir: &Path, path: &str, content: &str) -> Result<(), String> {
+    let file = dir.join(path);
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&file, content).map_err(|e| e.to_string())
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{AgentDrift, DriftedFile};
+    use serde_json::json;
+    use std::cell::RefCell;
+    use tempfile::tempdir;
+
+    fn modified(path: &str, expected: &str, actual: &str) -> DriftedFile {
+        DriftedFile {
+            path: path.into(),
+            reason: "modified".into(),
+            expected: Some(expected.into()),
+            actual: Some(actual.into()),
+            details: Vec::new(),
+            diff: None,
+        }
+    }
+
+    fn report(agent_id: &str, files: Vec<DriftedFile>) -> DriftReport {
+        DriftReport {
+            drifted: true,
+            agents: vec![AgentDrift {
+                agent_id: agent_id.into(),
+                agent_label: agent_id.into(),
+                files,
+            }],
+            instruction_conflicts: vec![],
+        }
+    }
+
+    fn project(dir: &Path) -> Project {
+        Project {
+            name: "app".into(),
+            directory: dir.display().to_string(),
+            agents: vec!["cursor".into()],
+            skills: vec!["deploy".into()],
+            mcp_servers: vec!["github".into(), "postgres".into()],
+            ..Default::default()
+        }
+    }
+
+    fn mcp_json(servers: Value) -> String {
+        serde_json::to_string_pretty(&json!({ "mcpServers": servers })).unwrap()
+    }
+
+    #[test]
+    fn keep_disk_adopts_servers_and_skills() {
+        let dir = tempdir().unwrap();
+        let mut project = project(dir.path());
+        let expected = mcp_json(json!({
+            "github": {"command": "gh-mcp"},
+            "postgres": {"command": "pg-mcp"},
+        }));
+        let actual = mcp_json(json!({
+            "github": {"command": "gh-mcp", "env": {"GH_HOST": "ghe.example.com"}},
+            "sentry": {"command": "sentry-mcp"},
+        }));
+        let drift = report(
+            "cursor",
+            vec![
+                modified(".cursor/mcp.json", &expected, &actual),
+                modified(
+                    ".cursor/skills/deploy/SKILL.md",
+                    "# Deploy\n",
+                    "# Deploy v2\n",
+                ),
+            ],
+        );
+        let resolutions = BTreeMap::from([
+            (".cursor/mcp.json".to_string(), DriftResolution::KeepDisk),
+            (
+                ".cursor/skills/deploy/SKILL.md".to_string(),
+                DriftResolution::KeepDisk,
+            ),
+        ]);
+
+        let skills = RefCell::new(Vec::new());
+        let servers = RefCell::new(Vec::new());
+        let result = apply_resolutions(
+            &mut project,
+            &resolutions,
+            &drift,
+            &|name, content| {
+                skills
+                    .borrow_mut()
+                    .push((name.to_string(), content.to_string()));
+                Ok(())
+            },
+            &|name, config| {
+                servers
+                    .borrow_mut()
+                    .push((name.to_string(), config.clone()));
+                Ok(true)
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result.adopted_servers, vec!["github", "sentry"]);
+        assert_eq!(result.removed_servers, vec!["postgres"]);
+        assert_eq!(result.adopted_skills, vec!["deploy"]);
+        assert!(result.rewritten_files.is_empty());
+        assert_eq!(project.mcp_servers, vec!["github", "sentry"]);
+        assert_eq!(servers.borrow()[0].1["env"]["GH_HOST"], "ghe.example.com");
+        assert_eq!(
+            *skills.borrow(),
+            vec![("deploy".to_string(), "# Deploy v2\n".to_string())]
+        );
+        assert_eq!(
+            project.skill_hashes["deploy"],
+            compute_content_hash("# Deploy v2\n")
+        );
+    }
+
+    #[test]
+    fn keep_registry_rewrites_only_that_file() {
+        let dir = tempdir().unwrap();
+        let skill_dir = dir.path().join(".cursor/skills/deploy");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Deploy v2\n").unwrap();
+        fs::write(dir.path().join("other.md"), "hand edit\n").unwrap();
+        let mut project = project(dir.path());
+        let drift = report(
+            "cursor",
+            vec![
+                modified(
+                    ".cursor/skills/deploy/SKILL.md",
+                    "# Deploy\n",
+                    "# Deploy v2\n",
+                ),
+                modified("other.md", "generated\n", "hand edit\n"),
+            ],
+        );
+        let resolutions = BTreeMap::from([(
+            ".cursor/skills/deploy/SKILL.md".to_string(),
+            DriftResolution::KeepRegistry,
+        )]);
+
+        let result = apply_resolutions(
+            &mut project,
+            &resolutions,
+            &drift,
+            &|_, _| panic!("registry must not change"),
+            &|_, _| panic!("registry must not change"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result.rewritten_files,
+            vec![".cursor/skills/deploy/SKILL.md"]
+        );
+        assert_eq!(
+            fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+            "# Deploy\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("other.md")).unwrap(),
+            "hand edit\n"
+        );
+    }
+
+    #[test]
+    fn unresolvable_paths_fail_before_anything_is_written() {
+        let dir = tempdir().unwrap();
+        let project = project(dir.path());
+        let mut missing = modified(".cursor/mcp.json", "", "");
+        missing.reason = "missing".into();
+        missing.expected = None;
+        missing.actual = None;
+        let drift = report(
+            "cursor",
+            vec![
+                modified("notes.md", "generated\n", "hand edit\n"),
+                missing,
+                modified(".cursor/skills/local/SKILL.md", "a\n", "b\n"),
+            ],
+        );
+        let resolve = |path: &str, resolution| {
+            let resolutions = BTreeMap::from([
+                ("notes.md".to_string(), DriftResolution::KeepRegistry),
+                (path.to_string(), resolution),
+            ]);
+            apply_resolutions(
+                &mut project.clone(),
+                &resolutions,
+                &drift,
+                &|_, _| Ok(()),
+                &|_, _| Ok(true),
+            )
+        };
+
+        let err = resolve(".cursor/mcp.json", DriftResolution::KeepRegistry).unwrap_err();
+        assert!(err.contains("is missing"), "{}", err);
+        let err = resolve("AGENTS.md", DriftResolution::KeepDisk).unwrap_err();
+        assert!(err.contains("has not drifted"), "{}", err);
+        let err = resolve(".cursor/skills/local/SKILL.md", DriftResolution::KeepDisk).unwrap_err();
+        assert!(err.contains("not a global skill"), "{}", err);
+        assert!(!dir.path().join("notes.md").exists());
+
+        let err = resolve("notes.md", DriftResolution::KeepDisk).unwrap_err();
+        assert!(err.contains("holds no MCP servers"), "{}", err);
+        let err = resolve("~/.claude.json", DriftResolution::KeepDisk).unwrap_err();
+        assert!(err.contains("outside the project"), "{}", err);
+    }
+}
@@ -1,3 +1,5 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
 use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
@@ -7,19 +9,57 @@ use crate::core::{self, Project};
 
 use super::autodetect::autodetect_inner;
 use super::helpers::{
-    build_selected_servers, clean_project_file, clean_project_file_rules_section,
-    extract_agent_machine_name, load_mcp_server_configs, load_skill_contents, sync_custom_agents,
-    sync_user_agents,
+    build_selected_servers, build_selected_servers_with_warnings, clean_project_file,
+    clean_project_file_rules_section, extract_agent_machine_name, gemini_extension_rule_names,
+    load_mcp_server_configs, load_mcp_server_configs_with_errors, load_rule_contents,
+    load_skill_contents, project_rule_warnings, resolve_file_rules, servers_for_agent,
+    sync_custom_agents, sync_user_agents,
 };
+use super::mcp_dedup::{merge_discovered_servers, save_mcp_server_if_changed, MergedMcpServers};
+use super::skill_shadow::{detect_skill_shadows, held_back_skills};
+
+/// The project's MCP servers as `agent_id` would be given them, in the
+/// paste-in format of an agent Automatic cannot configure directly (see
+/// [`agent::Agent::mcp_config_snippet`]).  `None` when the agent has no such
+/// format.
+pub fn agent_mcp_snippet(project: &Project, agent_id: &str) -> Result<Option<String>, String> {
+    let (agent_instance, agent_servers) = agent_mcp_servers(project, agent_id)?;
+    Ok(agent_instance.mcp_config_snippet(&agent_servers))
+}
+
+/// [`agent_mcp_snippet`] with instructions for where to paste it (see
+/// [`agent::Agent::export_mcp_setup`]).
+pub fn agent_mcp_export(
+    project: &Project,
+    agent_id: &str,
+) -> Result<Option<agent::AgentMcpExport>, String> {
+    let (agent_instance, agent_servers) = agent_mcp_servers(project, agent_id)?;
+    Ok(agent_instance.export_mcp_setup(&agent_servers))
+}
+
+/// The servers sync would give `agent_id`, Automatic's own included.
+fn agent_mcp_servers(
+    project: &Project,
+    agent_id: &str,
+) -> Result<(&'static dyn agent::Agent, Map<String, Value>), String> {
+    let agent_instance =
+        agent::from_id(agent_id).ok_or_else(|| format!("Unknown agent: {}", agent_id))?;
+    let mcp_config = load_mcp_server_configs()?;
+    let selected_servers =
+        build_selected_servers(project, &project.enabled_mcp_servers(), &mcp_config);
+    let agent_servers = servers_for_agent(project, agent_id, &selected_servers).into_owned();
+    Ok((agent_instance, agent_servers))
+}
 
 /// Discover MCP server configurations from specific agents' existing on-disk
 /// config files.  Used when new agents are added to an existing project so
 /// that any servers they already have configured are preserved rather than
 /// silently discarded when Automatic writes its own config.
 ///
-/// Returns `(server_name, pretty-printed JSON string)` pairs.  The caller is
-/// responsible for persisting them to the global registry and for merging the
-/// names into `project.mcp_servers` before calling
+/// Variants of the same server found in several agents are merged; those
+/// that genuinely differ are returned as conflicts instead.  The caller is
+/// responsible for persisting the merged servers to the global registry and
+/// for merging the names into `project.mcp_servers` before calling
 /// [`sync_project_without_autodetect`].
 ///
 /// The `automatic` server entries are filtered out automatically by
@@ -27,23 +67,37 @@ use super::helpers::{
 pub fn discover_new_agent_mcp_configs(
     dir: &std::path::Path,
     agent_ids: &[String],
-) -> Vec<(String, String)> {
+) -> MergedMcpServers {
     let mut discovered = Vec::new();
     for agent_id in agent_ids {
         if let Some(a) = agent::from_id(agent_id) {
             for (name, config) in a.discover_mcp_servers(dir) {
-                if let Ok(config_str) = serde_json::to_string_pretty(&config) {
-                    discovered.push((name, config_str));
-                }
+                discovered.push((a.id().to_string(), name, config));
             }
         }
     }
-    discovered
+    merge_discovered_servers(discovered)
+}
+
+/// What a sync wrote, and what it had to leave out.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Files written by the sync.
+    pub files: Vec<String>,
+    /// Servers left out of every agent config because their keychain
+    /// placeholders could not be resolved.
+    pub secret_warnings: Vec<core::McpSecretWarning>,
 }
 
 /// Sync a project's configuration to its directory for all selected agent tools.
 /// Returns a list of files that were written.
 pub fn sync_project(project: &Project) -> Result<Vec<String>, String> {
+    sync_project_with_report(project).map(|report| report.files)
+}
+
+/// [`sync_project`], also reporting the servers the sync left out.
+pub fn sync_project_with_report(project: &Project) -> Result<SyncReport, String> {
+    project.ensure_unlocked()?;
     if project.directory.is_empty() {
         return Err("Project has no directory configured".into());
     }
@@ -56,18 +110,33 @@ pub fn sync_project(project: &Project) -> Result<Vec<String>, String> {
     let (mut updated_project, discovered_servers) = autodetect_inner(project)?;
 
     // Persist newly discovered MCP server configs into the global registry.
-    // This only happens during an explicit sync, not during a read-only load.
-    for (name, config_str) in discovered_servers {
-        let _ = crate::core::save_mcp_server_config(&name, &config_str);
+    // This only happens during an explicit sync, not during a read-only load,
+    // and only for configs that actually changed.  Conflicting variants are
+    // left for the user to resolve.  Servers the project overrides are
+    // skipped: their files on disk hold the merged, project-specific config.
+    for (name, config) in discovered_servers.servers {
+        if project.mcp_server_overrides.contains_key(&name) {
+            continue;
+        }
+        let _ = save_mcp_server_if_changed(&name, &config);
     }
 
-    sync_project_without_autodetect(&mut updated_project)
+    sync_project_without_autodetect_with_report(&mut updated_project)
 }
 
 /// Sync a project's configuration to its directory without re-running
 /// dependency autodetection. Useful when reacting to registry changes
 /// (e.g. deleting a skill/server) to avoid re-importing stale local files.
 pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<String>, String> {
+    sync_project_without_autodetect_with_report(project).map(|report| report.files)
+}
+
+/// [`sync_project_without_autodetect`], also reporting the servers the sync
+/// left out.
+pub fn sync_project_without_autodetect_with_report(
+    project: &mut Project,
+) -> Result<SyncReport, String> {
+    project.ensure_unlocked()?;
     if project.directory.is_empty() {
         return Err("Project has no directory configured".into());
     }
@@ -76,24 +145,36 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
     if !dir.exists() {
         return Err(format!("Directory '{}' does not exist", project.directory));
     }
+    let _watch_pause = super::watch::pause_drift_watch(project);
 
     // Ensure the project config is written to the project directory
-    if let Ok(proj_str) = serde_json::to_string_pretty(project) {
-        let _ = crate::core::save_project(&project.name, &proj_str);
-    }
+    // (unregistered projects are never given a registry entry).
+    let _ = crate::core::persist_project(project);
 
     // Read MCP server configs from the Automatic registry and build the
     // selected server map (includes stripping internal fields and OAuth proxy
     // substitution).  Uses the shared helper so drift detection produces
     // identical output.
-    let mcp_config = load_mcp_server_configs()?;
+    let (mcp_config, mcp_config_errors) = load_mcp_server_configs_with_errors()?;
     let enabled_mcp_servers = project.enabled_mcp_servers();
-    let selected_servers = build_selected_servers(&project.name, &enabled_mcp_servers, &mcp_config);
+    let (selected_servers, secret_warnings) =
+        build_selected_servers_with_warnings(project, &enabled_mcp_servers, &mcp_config);
 
     // Read all skill contents from the global skill registry, then append
     // project-scoped custom skills (which live inline in the project JSON
-    // rather than in ~/.automatic/skills/).
-    let mut skill_contents = load_skill_contents(&project.skills);
+    // rather than in ~/.automatic/skills/).  Skill sets are expanded here,
+    // so a set edited since the last sync applies now.
+    let selected_skills = core::selected_skill_names(project);
+    let mut skill_contents = load_skill_contents(&selected_skills);
+
+    // Project copies that differ from the registry and were not written by
+    // Automatic are held back until the user resolves them, as are skills
+    // the user chose to keep as project copies.
+    project.skill_conflicts = detect_skill_shadows(project);
+    let held_back = held_back_skills(project);
+    skill_contents.retain(|(name, _)| !held_back.contains(name));
+    let global_skill_contents = skill_contents.clone();
+
     let custom_skills = project.custom_skills.as_deref().unwrap_or(&[]);
     for cs in custom_skills {
         skill_contents.push((cs.name.clone(), cs.content.clone()));
@@ -117,20 +198,37 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
     // global registry (~/.agents/skills/) so companion files are included.
     // Custom skills (project-scoped) are included alongside global skills.
     let project_skills_dir = dir.join(".agents").join("skills");
-    let all_selected_skill_names: Vec<String> = project
-        .skills
+    let all_selected_skill_names: Vec<String> = selected_skills
         .iter()
         .chain(custom_skill_names.iter())
         .cloned()
         .collect();
+    let preserve_names: Vec<String> = project
+        .local_skills
+        .iter()
+        .chain(held_back.iter())
+        .cloned()
+        .collect();
     agent::copy_skills_to_project(
         &project_skills_dir,
         &skill_contents,
         &all_selected_skill_names,
-        &project.local_skills,
+        &preserve_names,
         &mut written_files,
     )?;
 
+    // Remember what was written so a copy that merely went stale when the
+    // registry changed is not mistaken for a user edit.
+    project
+        .skill_hashes
+        .retain(|name, _| selected_skills.contains(name));
+    for (name, content) in &global_skill_contents {
+        project
+            .skill_hashes
+            .insert(name.clone(), core::compute_content_hash(content));
+    }
+    let _ = crate::core::persist_project(project);
+
     // Look up all groups this project belongs to once, before the per-agent loop.
     let project_groups = crate::core::groups_for_project(&project.name);
 
@@ -151,16 +249,80 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
                         &project_skills_dir,
                         &skill_contents,
                         &all_selected_skill_names,
-                        &project.local_skills,
+                        &preserve_names,
                         &mut written_files,
                     )?;
                 }
 
-                let path = agent_instance.write_mcp_config(&dir, &selected_servers)?;
-                // write_mcp_config returns "" for agents (like Warp) that
-                // cannot have their MCP config managed by Automatic.
-                if !path.is_empty() {
-                    written_files.push(path);
+                let agent_servers = servers_for_agent(project, agent_id, &selected_servers);
+                // Read the provenance key before the write replaces it, so an
+                // app upgrade alone does not rewrite the file.
+                let annotated_config = agent_instance.annotated_config_path(&dir);
+                let previous_metadata = annotated_config
+                    .as_deref()
+                    .and_then(crate::core::read_config_metadata);
+                let use_gemini_extension = agent_id == "gemini"
+                    && project
+                        .agent_options
+                        .get(agent_id)
+                        .is_some_and(|o| o.gemini_use_extension);
+                if use_gemini_extension {
+                    // Gemini extension mode: the managed payload lives in
+                    // `.gemini/extensions/automatic/` instead of settings.json.
+                    written_files.extend(agent::write_gemini_extension(
+                        &dir,
+                        &agent_servers,
+                        &gemini_extension_rule_names(project),
+                    )?);
+                } else if agent_id == "claude" {
+                    // Claude Code: local-scope servers go to ~/.claude.json.
+                    let scopes = project
+                        .agent_options
+                        .get(agent_id)
+                        .map(|o| o.claude_mcp_scopes.clone())
+                        .unwrap_or_default();
+                    written_files.extend(agent::write_claude_scoped_mcp_config(
+                        &dir,
+                        &agent_servers,
+                        &scopes,
+                    )?);
+                } else {
+                    let path = agent_instance.write_mcp_config(&dir, &agent_servers)?;
+                    // write_mcp_config returns "" for agents (like Warp) that
+                    // cannot have their MCP config managed by Automatic.
+                    if !path.is_empty() {
+                        written_files.push(path);
+                    }
+                    // Switching back from extension mode: drop the extension so
+                    // Gemini does not load the same servers twice.
+                    if agent_id == "gemini" {
+                        written_files.extend(agent::remove_gemini_extension(&dir));
+                    }
+                    // OpenCode also loads the global skills; allow only the
+                    // ones in the project hub.
+                    if agent_id == "opencode" {
+                        let allowed: Vec<String> = all_selected_skill_names
+                            .iter()
+                            .chain(preserve_names.iter())
+                            .cloned()
+                            .collect();
+                        match agent::write_opencode_skill_permissions(&dir, &allowed) {
+                            Ok(Some(path)) if !written_files.contains(&path) => {
+                                written_files.push(path)
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                tracing::error!("Failed to set OpenCode skill permissions: {}", e)
+                            }
+                        }
+                    }
+                }
+                if let Some(path) = annotated_config {
+                    crate::core::annotate_json_config(
+                        &path,
+                        &project.name,
+                        previous_metadata.as_ref(),
+                    )?;
                 }
 
                 // Sync custom agents to this provider's agents directory
@@ -225,26 +387,9 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
                         }
                     }
 
-                    // Resolve the rules assigned to this project file.
-                    // Priority order:
-                    //   1. "_project" — project-level rules set from the Rules tab (applies to all files)
-                    //   2. "_unified" — legacy unified-mode key
-                    //   3. Per-file key (e.g. "CLAUDE.md") — legacy per-agent mode
-                    // Mandatory rules (e.g. automatic-service) are always included.
-                    let user_rules: Vec<String> = project
-                        .file_rules
-                        .get("_project")
-                        .filter(|v| !v.is_empty())
-                        .or_else(|| {
-                            if project.instruction_mode == "unified" {
-                                project.file_rules.get("_unified")
-                            } else {
-                                project.file_rules.get(pf)
-                            }
-                        })
-                        .cloned()
-                        .unwrap_or_default();
-                    let rules = crate::core::ensure_mandatory_rules(&user_rules);
+                    // Resolve the rules assigned to this project file
+                    // (mandatory rules are always included).
+                    let rules = resolve_file_rules(project, pf);
 
                     // Resolve per-agent options for this agent (use defaults if absent).
                     let opts = project
@@ -274,7 +419,7 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
                         ) {
                             Ok(touched) => written_files.extend(touched),
                             Err(e) => {
-                                eprintln!("Failed to sync rules to .claude/rules/: {}", e)
+                                tracing::error!("Failed to sync rules to .claude/rules/: {}", e)
                             }
                         }
                         // Custom rules are still injected inline even in dot-claude mode.
@@ -315,9 +460,53 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
                         }
                     }
                 }
+
+                // Extra instruction files (e.g. Copilot's `.github/instructions/`)
+                // only receive rules assigned to their exact path.  Files without
+                // an entry are left untouched — they are team-authored.
+                for extra in agent_instance.extra_instruction_files(&dir) {
+                    let Some(rules) = project.file_rules.get(&extra) else {
+                        continue;
+                    };
+                    if let Ok(true) = crate::core::inject_rules_into_project_file_with_custom(
+                        &project.directory,
+                        &extra,
+                        rules,
+                        &[],
+                    ) {
+                        let rule_path = dir.join(&extra).display().to_string();
+                        if !written_files.contains(&rule_path) {
+                            written_files.push(rule_path);
+                        }
+                    }
+                }
+
+                // Agents with their own rules location (e.g. Cursor's
+                // `.cursor/rules/`) also get each rule of their instruction
+                // file as a separate, Automatic-managed file.
+                let native_rules = load_rule_contents(&resolve_file_rules(
+                    project,
+                    agent_instance.project_file_name(),
+                ));
+                match agent_instance.write_rules(&dir, &native_rules) {
+                    Ok(touched) => {
+                        for path in touched {
+                            if !written_files.contains(&path) {
+                                written_files.push(path);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to sync rules for {}: {}",
+                            agent_instance.label(),
+                            e
+                        )
+                    }
+                }
             }
             None => {
-                eprintln!("Unknown agent '{}', skipping", agent_id);
+                tracing::warn!("Unknown agent '{}', skipping", agent_id);
             }
         }
     }
@@ -372,8 +561,8 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
             // An instruction file was modified outside Automatic.
             // Do NOT overwrite — leave the files as-is so drift detection
             // can surface the conflict and the user can choose what to keep.
-            eprintln!(
-                "[automatic] Unified replication skipped: instruction file(s) were modified externally. \
+            tracing::warn!(
+                "Unified replication skipped: instruction file(s) were modified externally. \
                  Drift detection will surface the conflict."
             );
         } else {
@@ -449,14 +638,50 @@ pub fn sync_project_without_autodetect(project: &mut Project) -> Result<Vec<Stri
 
             if let Ok(user_content) = crate::core::read_project_file(&project.directory, &filename)
             {
-                let _ = crate::core::save_instruction_snapshot(
-                    &project.directory,
-                    &filename,
-                    &user_content,
-                );
+                let _ = crate::core::save_instruction_snapshot(project, &filename, &user_content);
             }
         }
     }
 
-    Ok(written_files)
+    // ── Step 5: Advisory skill requirement and server budget checks ─────
+    //
+    // Skills may name MCP servers or CLI tools they rely on, some agents
+    // degrade past a number of servers, and attached rules may be missing or
+    // empty.  All are reported, never enforced, as are registry server
+    // configs that could not be loaded.
+    for warning in core::check_skill_requirements(
+        &skill_contents,
+        &enabled_mcp_servers,
+        &core::which_available,
+    ) {
+        tracing::warn!("Sync warning: {}", warning.message());
+    }
+    let agents: Vec<&dyn agent::Agent> = project
+        .agents
+        .iter()
+        .filter_map(|id| agent::from_id(id))
+        .collect();
+    for warning in core::check_mcp_budget(&agents, &enabled_mcp_servers, &skill_contents) {
+        tracing::warn!("Sync warning: {}", warning.message());
+    }
+    for warning in &secret_warnings {
+        tracing::warn!("Sync warning: {}", warning.message());
+    }
+    for warning in project_rule_warnings(project) {
+        tracing::warn!("Sync warning: {}", warning.message());
+    }
+    for error in &mcp_config_errors {
+        tracing::warn!("Sync warning: {}", error);
+    }
+
+    // ── Step 6: Record a sync generation for time-travel restore ────────
+    if let Err(e) = super::generations::record_generation(project, &written_files) {
+        tracing::error!("Failed to record sync generation: {}", e);
+    }
+    core::log_sync_history(project, core::SyncAction::Sync, None, &written_files, &[]);
+
+    Ok(SyncReport {
+        files: written_files,
+        secret_warnings,
+    })
 }
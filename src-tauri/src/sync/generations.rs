@@ -0,0 +1,766 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::agent;
+use crate::core::{self, Project, RemovalContext};
+
+use super::skill_shadow::held_back_skills;
+
+// ── Sync Generations ─────────────────────────────────────────────────────────
+//
+// Every sync records the managed state it leaves in the project directory as
+// a generation, so a whole project can be rolled back at once rather than
+// file by file.  Generations live in the project's data dir (see
+// `core::project_data_dir`):
+//
+//   backups/<generation>/manifest.json   paths, hashes and blob locations
+//   backups/blobs/<sha256>               content, shared between generations
+//   backups/restored.json                set while a restore is in effect
+//
+// Only what Automatic manages is recorded: configs the project's agents own
+// outright, the skill hub and agent skill directories, files the sync
+// reported writing, and the marker sections of instruction files.  User
+// content outside the markers is neither recorded nor restored.
+//
+// A restore is left in place until the next explicit sync; reactive syncs
+// skip the project meanwhile so they do not silently undo it.
+
+/// Version of the manifest layout written by [`record_generation`].
+pub const GENERATION_FORMAT: u32 = 1;
+
+const BACKUPS_DIR: &str = "backups";
+const BLOBS_DIR: &str = "blobs";
+const MANIFEST_FILE: &str = "manifest.json";
+const RESTORED_FILE: &str = "restored.json";
+
+/// How much of a path Automatic manages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedKind {
+    /// A file Automatic writes in full.
+    File,
+    /// An instruction file: only its groups and rules sections.
+    Sections,
+    /// A symlink, e.g. an agent skill directory linked to the skill hub.
+    Symlink,
+}
+
+/// One managed path in a generation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagedEntry {
+    /// Relative to the project directory, `/`-separated.
+    pub path: String,
+    pub kind: ManagedKind,
+    /// SHA-256 of the recorded content, or of the link target.
+    pub hash: String,
+    /// Blob holding the content, relative to `backups/`.  `None` for links.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup: Option<String>,
+    /// Link target for `Symlink` entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub bytes: u64,
+}
+
+/// The `manifest.json` of one generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationManifest {
+    pub format: u32,
+    /// Sortable id, also the directory name, e.g. `20261015T093012345Z`.
+    pub generation: String,
+    /// RFC 3339.
+    pub created_at: String,
+    pub agents: Vec<String>,
+    pub entries: Vec<ManagedEntry>,
+}
+
+/// A generation as listed for the user.
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationSummary {
+    pub generation: String,
+    pub created_at: String,
+    pub agents: Vec<String>,
+    pub files: usize,
+    pub bytes: u64,
+    /// True for the generation the project is currently restored from.
+    pub restored: bool,
+}
+
+/// Result of [`restore_generation`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GenerationRestoreReport {
+    pub generation: String,
+    /// Paths created or rewritten, including instruction files whose
+    /// sections were swapped.
+    pub restored: Vec<String>,
+    /// Managed paths the generation did not have, now removed (or, for
+    /// instruction files, stripped of their sections).
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RestoredMarker {
+    generation: String,
+    restored_at: String,
+}
+
+// ── Recording ────────────────────────────────────────────────────────────────
+
+/// Record the managed state of `project` after a sync that wrote `written`
+/// (absolute paths), keeping as many generations as the
+/// `sync_generations_retained` setting allows.  Also ends any restore in
+/// effect, since an explicit sync supersedes it.  Returns the new
+/// generation, or `None` when nothing changed since the last one.
+pub fn record_generation(project: &Project, written: &[String]) -> Result<Option<String>, String> {
+    let retained = core::read_settings()
+        .unwrap_or_default()
+        .sync_generations_retained;
+    record_generation_with(project, written, retained)
+}
+
+fn record_generation_with(
+    project: &Project,
+    written: &[String],
+    retained: usize,
+) -> Result<Option<String>, String> {
+    let Some(backups) = backups_dir(project) else {
+        return Ok(None);
+    };
+    let _ = fs::remove_file(backups.join(RESTORED_FILE));
+    if retained == 0 {
+        return Ok(None);
+    }
+
+    let dir = PathBuf::from(&project.directory);
+    let mut entries = Vec::new();
+    for (rel, kind) in managed_paths(project, &dir, written) {
+        let path = dir.join(&rel);
+        let entry = match kind {
+            ManagedKind::Symlink => {
+                let Ok(target) = fs::read_link(&path) else {
+                    continue;
+                };
+                let target = target.display().to_string();
+                ManagedEntry {
+                    path: rel,
+                    kind,
+                    hash: sha256_hex(target.as_bytes()),
+                    backup: None,
+                    target: Some(target),
+                    bytes: 0,
+                }
+            }
+            ManagedKind::Sections => {
+                let Ok(content) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let sections = core::extract_managed_sections(&content);
+                if sections.is_empty() {
+                    continue;
+                }
+                blob_entry(&backups, rel, kind, sections.as_bytes())?
+            }
+            ManagedKind::File => {
+                let Ok(bytes) = fs::read(&path) else {
+                    continue;
+                };
+                blob_entry(&backups, rel, kind, &bytes)?
+            }
+        };
+        entries.push(entry);
+    }
+
+    let manifests = read_manifests(&backups);
+    if manifests.last().is_some_and(|m| m.entries == entries) {
+        return Ok(None);
+    }
+
+    let generation = new_generation_id(&backups);
+    let manifest = GenerationManifest {
+        format: GENERATION_FORMAT,
+        generation: generation.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        agents: project.agents.clone(),
+        entries,
+    };
+    let gen_dir = backups.join(&generation);
+    fs::create_dir_all(&gen_dir).map_err(|e| e.to_string())?;
+    let raw = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    fs::write(gen_dir.join(MANIFEST_FILE), raw).map_err(|e| e.to_string())?;
+
+    prune_generations(project, &backups, retained)?;
+    Ok(Some(generation))
+}
+
+/// Store `bytes` as a blob and describe it.
+fn blob_entry(
+    backups: &Path,
+    path: String,
+    kind: ManagedKind,
+    bytes: &[u8],
+) -> Result<ManagedEntry, String> {
+    let hash = sha256_hex(bytes);
+    let blobs = backups.join(BLOBS_DIR);
+    let blob = blobs.join(&hash);
+    if !blob.exists() {
+        fs::create_dir_all(&blobs).map_err(|e| e.to_string())?;
+        // Copies of MCP configs can hold credentials: never commit them.
+        let ignore = backups.join(".gitignore");
+        if !ignore.exists() {
+            fs::write(&ignore, "*\n").map_err(|e| e.to_string())?;
+        }
+        fs::write(&blob, bytes).map_err(|e| e.to_string())?;
+    }
+    Ok(ManagedEntry {
+        path,
+        kind,
+        backup: Some(format!("{}/{}", BLOBS_DIR, hash)),
+        hash,
+        target: None,
+        bytes: bytes.len() as u64,
+    })
+}
+
+/// Drop all but the newest `retained` generations, then any blob no
+/// remaining generation refers to.
+fn prune_generations(project: &Project, backups: &Path, retained: usize) -> Result<(), String> {
+    let manifests = read_manifests(backups);
+    let excess = manifests.len().saturating_sub(retained);
+    let context = data_removal_context(project);
+    for manifest in &manifests[..excess] {
+        core::remove_guarded(&backups.join(&manifest.generation), &context)?;
+    }
+
+    let referenced: HashSet<&str> = manifests[excess..]
+        .iter()
+        .flat_map(|m| m.entries.iter())
+        .filter_map(|e| e.backup.as_deref())
+        .collect();
+    if let Ok(blobs) = fs::read_dir(backups.join(BLOBS_DIR)) {
+        for blob in blobs.flatten() {
+            let name = format!("{}/{}", BLOBS_DIR, blob.file_name().to_string_lossy());
+            if !referenced.contains(name.as_str()) {
+                let _ = fs::remove_file(blob.path());
+            }
+        }
+    }
+    Ok(())
+}
+
+// ── Listing ──────────────────────────────────────────────────────────────────
+
+/// The recorded generations of `project`, newest first.
+pub fn list_generations(project: &Project) -> Result<Vec<GenerationSummary>, String> {
+    let Some(backups) = backups_dir(project) else {
+        return Ok(Vec::new());
+    };
+    let restored = restored_generation(project);
+    Ok(read_manifests(&backups)
+        .into_iter()
+        .rev()
+        .map(|m| GenerationSummary {
+            restored: restored.as_deref() == Some(m.generation.as_str()),
+            files: m.entries.len(),
+            bytes: m.entries.iter().map(|e| e.bytes).sum(),
+            generation: m.generation,
+            created_at: m.created_at,
+            agents: m.agents,
+        })
+        .collect())
+}
+
+/// The generation `project` was restored from, while that restore is in
+/// effect (until the next explicit sync).
+pub fn restored_generation(project: &Project) -> Option<String> {
+    let raw = fs::read_to_string(backups_dir(project)?.join(RESTORED_FILE)).ok()?;
+    serde_json::from_str::<RestoredMarker>(&raw)
+        .ok()
+        .map(|m| m.generation)
+}
+
+// ── Restoring ────────────────────────────────────────────────────────────────
+
+/// Put every managed path of `project` back the way `generation` recorded
+/// it: recorded files and links are recreated, managed paths it did not
+/// have are removed, and instruction files get that generation's sections
+/// around their current user content.  Unmanaged files are never touched.
+///
+/// The project is then marked as restored, which holds off reactive syncs
+/// until the user syncs explicitly.
+pub fn restore_generation(
+    project: &Project,
+    generation: &str,
+) -> Result<GenerationRestoreReport, String> {
+    project.ensure_unlocked()?;
+    let backups =
+        backups_dir(project).ok_or_else(|| "Project has no directory configured".to_string())?;
+    let manifests = read_manifests(&backups);
+    let target = manifests
+        .iter()
+        .find(|m| m.generation == generation)
+        .ok_or_else(|| format!("Sync generation '{}' not found", generation))?;
+    if let Some(bad) = target.entries.iter().find(|e| !is_safe_relative(&e.path)) {
+        return Err(format!(
+            "Sync generation '{}' has an invalid path '{}'",
+            generation, bad.path
+        ));
+    }
+
+    let dir = PathBuf::from(&project.directory);
+    let context = RemovalContext::project(&dir);
+    let mut report = GenerationRestoreReport {
+        generation: generation.to_string(),
+        ..Default::default()
+    };
+
+    // Everything managed now or by a later generation.
+    let mut current = managed_paths(project, &dir, &[]);
+    for later in manifests
+        .iter()
+        .filter(|m| m.generation > target.generation)
+    {
+        for entry in later.entries.iter().filter(|e| is_safe_relative(&e.path)) {
+            current.entry(entry.path.clone()).or_insert(entry.kind);
+        }
+    }
+    let wanted: BTreeMap<&str, &ManagedEntry> = target
+        .entries
+        .iter()
+        .map(|e| (e.path.as_str(), e))
+        .collect();
+
+    // Removals first, so a linked skill directory is gone before files are
+    // restored beneath its path.
+    for (rel, kind) in &current {
+        if wanted.contains_key(rel.as_str()) {
+            continue;
+        }
+        let path = dir.join(rel);
+        if *kind == ManagedKind::Sections {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let stripped = core::replace_managed_sections(&content, "");
+            if stripped == content {
+                continue;
+            }
+            if stripped.trim().is_empty() {
+                core::remove_guarded(&path, &context)?;
+            } else {
+                fs::write(&path, stripped).map_err(|e| e.to_string())?;
+            }
+            report.removed.push(rel.clone());
+        } else if core::remove_guarded(&path, &context)?.is_removal() {
+            remove_empty_parents(&dir, &path);
+            report.removed.push(rel.clone());
+        }
+    }
+
+    for entry in &target.entries {
+        let path = dir.join(&entry.path);
+        let changed = match entry.kind {
+            ManagedKind::File => {
+                let bytes = read_blob(&backups, entry)?;
+                let is_link = path
+                    .symlink_metadata()
+                    .is_ok_and(|m| m.file_type().is_symlink());
+                if !is_link && fs::read(&path).ok().as_deref() == Some(bytes.as_slice()) {
+                    false
+                } else {
+                    prepare_path(&dir, &path, &context)?;
+                    fs::write(&path, bytes).map_err(|e| e.to_string())?;
+                    true
+                }
+            }
+            ManagedKind::Sections => {
+                let sections = String::from_utf8(read_blob(&backups, entry)?)
+                    .map_err(|e| format!("Invalid backup of {}: {}", entry.path, e))?;
+                let content = fs::read_to_string(&path).unwrap_or_default();
+                let updated = core::replace_managed_sections(&content, &sections);
+                if updated == content {
+                    false
+                } else {
+                    prepare_path(&dir, &path, &context)?;
+                    fs::write(&path, updated).map_err(|e| e.to_string())?;
+                    true
+                }
+            }
+            ManagedKind::Symlink => {
+                let target = PathBuf::from(entry.target.as_deref().unwrap_or_default());
+                if fs::read_link(&path).ok().as_ref() == Some(&target) {
+                    false
+                } else {
+                    prepare_path(&dir, &path, &context)?;
+                    core::remove_guarded(&path, &context)?;
+                    make_symlink(&target, &path)?;
+                    true
+                }
+            }
+        };
+        if changed {
+            report.restored.push(entry.path.clone());
+        } else {
+            report.unchanged += 1;
+        }
+    }
+
+    let marker = RestoredMarker {
+        generation: generation.to_string(),
+        restored_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let raw = serde_json::to_string_pretty(&marker).map_err(|e| e.to_string())?;
+    fs::write(backups.join(RESTORED_FILE), raw).map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+/// Content of `entry`'s blob, checked against its hash.
+fn read_blob(backups: &Path, entry: &ManagedEntry) -> Result<Vec<u8>, String> {
+    let backup = entry
+        .backup
+        .as_deref()
+        .filter(|b| is_safe_relative(b))
+        .ok_or_else(|| format!("No backup recorded for {}", entry.path))?;
+    let bytes = fs::read(backups.join(backup))
+        .map_err(|e| format!("Backup of {} is missing: {}", entry.path, e))?;
+    if sha256_hex(&bytes) != entry.hash {
+        return Err(format!("Backup of {} is corrupt", entry.path));
+    }
+    Ok(bytes)
+}
+
+/// Make `path` writable as a plain file: no symlink at it or on the way
+/// (writing through one would change the link target), no directory in its
+/// place, and its parent exists.
+pub(super) fn prepare_path(
+    dir: &Path,
+    path: &Path,
+    context: &RemovalContext,
+) -> Result<(), String> {
+    let Ok(rel) = path.strip_prefix(dir) else {
+        return Ok(());
+    };
+    let mut ancestor = dir.to_path_buf();
+    for component in rel.parent().into_iter().flat_map(Path::components) {
+        ancestor.push(component);
+        if ancestor
+            .symlink_metadata()
+            .is_ok_and(|m| m.file_type().is_symlink())
+        {
+            core::remove_guarded(&ancestor, context)?;
+        }
+    }
+    if path
+        .symlink_metadata()
+        .is_ok_and(|m| m.is_dir() || m.file_type().is_symlink())
+    {
+        core::remove_guarded(path, context)?;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Remove directories left empty by a removal, up to (not including) `dir`.
+fn remove_empty_parents(dir: &Path, path: &Path) {
+    let mut parent = path.parent();
+    while let Some(p) = parent {
+        if p == dir || !p.starts_with(dir) || fs::remove_dir(p).is_err() {
+            break;
+        }
+        parent = p.parent();
+    }
+}
+
+pub(super) fn make_symlink(target: &Path, link: &Path) -> Result<(), String> {
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(target, link);
+    #[cfg(windows)]
+    let result = std::os::windows::fs::symlink_dir(target, link);
+    result.map_err(|e| format!("Failed to link {}: {}", link.display(), e))
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────────────
+
+fn backups_dir(project: &Project) -> Option<PathBuf> {
+    core::project_data_dir(project).map(|d| d.join(BACKUPS_DIR))
+}
+
+fn data_removal_context(project: &Project) -> RemovalContext {
+    if project.stores_config_in_repo() {
+        RemovalContext::project(Path::new(&project.directory))
+    } else {
+        RemovalContext::app_data()
+    }
+}
+
+/// Every generation manifest in `backups`, oldest first.  Unreadable ones
+/// are skipped.
+fn read_manifests(backups: &Path) -> Vec<GenerationManifest> {
+    let Ok(entries) = fs::read_dir(backups) else {
+        return Vec::new();
+    };
+    let mut manifests: Vec<GenerationManifest> = entries
+        .flatten()
+        .filter_map(|e| fs::read_to_string(e.path().join(MANIFEST_FILE)).ok())
+        .filter_map(|raw| serde_json::from_str::<GenerationManifest>(&raw).ok())
+        .filter(|m| is_generation_id(&m.generation))
+        .collect();
+    manifests.sort_by(|a, b| a.generation.cmp(&b.generation));
+    manifests
+}
+
+pub(super) fn new_generation_id(backups: &Path) -> String {
+    let base = chrono::Utc::now().format("%Y%m%dT%H%M%S%3fZ").to_string();
+    let mut id = base.clone();
+    let mut n = 1;
+    while backups.join(&id).exists() {
+        n += 1;
+        id = format!("{}-{}", base, n);
+    }
+    id
+}
+
+pub(super) fn is_generation_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// A relative path that stays inside its base directory.
+pub(super) fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Paths in `dir` that Automatic manages for `project`, `/`-separated and
+/// sorted.  `written` adds the files a sync reported writing.
+fn managed_paths(
+    project: &Project,
+    dir: &Path,
+    written: &[String],
+) -> BTreeMap<String, ManagedKind> {
+    let mut paths = BTreeMap::new();
+    let agents: Vec<&dyn agent::Agent> = project
+        .agents
+        .iter()
+        .filter_map(|id| agent::from_id(id))
+        .collect();
+
+    for a in agents.iter().filter(|a| a.capabilities().instructions) {
+        let path = dir.join(a.project_file_name());
+        if path.is_file() {
+            if let Some(rel) = relative_path(dir, &path) {
+                paths.insert(rel, ManagedKind::Sections);
+            }
+        }
+    }
+    for a in &agents {
+        for path in a.owned_config_paths(dir) {
+            add_tree(dir, &path, &mut paths);
+        }
+    }
+
+    let held_back = held_back_skills(project);
+    let selected_skills = core::selected_skill_names(project);
+    let skill_names: Vec<&String> = selected_skills
+        .iter()
+        .chain(project.custom_skills.iter().flatten().map(|s| &s.name))
+        .filter(|name| !held_back.contains(*name))
+        .collect();
+    let mut skill_dirs = vec![dir.join(".agents").join("skills")];
+    for a in &agents {
+        for skill_dir in a.skill_dirs(dir) {
+            if !skill_dirs.contains(&skill_dir) {
+                skill_dirs.push(skill_dir);
+            }
+        }
+    }
+    for skill_dir in &skill_dirs {
+        for name in &skill_names {
+            add_tree(dir, &skill_dir.join(name), &mut paths);
+        }
+    }
+
+    // Files only: a reported directory may also hold user files.
+    for path in written.iter().map(Path::new) {
+        let is_file_or_link = path
+            .symlink_metadata()
+            .is_ok_and(|m| m.is_file() || m.file_type().is_symlink());
+        if is_file_or_link {
+            add_tree(dir, path, &mut paths);
+        }
+    }
+    paths
+}
+
+/// Add `path` (a file, a symlink, or every file below a directory).
+fn add_tree(dir: &Path, path: &Path, paths: &mut BTreeMap<String, ManagedKind>) {
+    let Ok(meta) = path.symlink_metadata() else {
+        return;
+    };
+    let Some(rel) = relative_path(dir, path) else {
+        return;
+    };
+    if rel == ".automatic" || rel.starts_with(".automatic/") {
+        return;
+    }
+    if meta.file_type().is_symlink() {
+        paths.entry(rel).or_insert(ManagedKind::Symlink);
+    } else if meta.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                add_tree(dir, &entry.path(), paths);
+            }
+        }
+    } else if meta.is_file() {
+        paths.entry(rel).or_insert(ManagedKind::File);
+    }
+}
+
+/// `path` relative to `dir`, `/`-separated; `None` outside `dir`.
+pub(super) fn relative_path(dir: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(dir).ok()?;
+    let parts: Vec<String> = rel
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const RULES_V1: &str =
+        "<!-- automatic:rules:start -->\nPrefer early returns.\n<!-- automatic:rules:end -->";
+    const RULES_V2: &str =
+        "<!-- automatic:rules:start -->\nPrefer guard clauses.\n<!-- automatic:rules:end -->";
+
+    fn project(dir: &Path, agents: &[&str]) -> Project {
+        Project {
+            name: "web".into(),
+            directory: dir.display().to_string(),
+            agents: agents.iter().map(|a| a.to_string()).collect(),
+            unregistered: true,
+            ..Default::default()
+        }
+    }
+
+    fn write(dir: &Path, rel: &str, content: &str) {
+        let path = dir.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn read(dir: &Path, rel: &str) -> String {
+        fs::read_to_string(dir.join(rel)).unwrap()
+    }
+
+    #[test]
+    fn restore_after_an_agent_was_added_removes_its_files() {
+        let tmp = tempdir().unwrap();
+        let dir = tmp.path();
+        let mut p = project(dir, &["claude"]);
+        write(dir, ".mcp.json", r#"{"mcpServers":{"db":{}}}"#);
+        write(dir, "CLAUDE.md", &format!("# Team\n\n{}\n", RULES_V1));
+        let first = record_generation_with(&p, &[], 10).unwrap().unwrap();
+
+        p.agents.push("cursor".into());
+        write(dir, ".mcp.json", r#"{"mcpServers":{"db":{},"github":{}}}"#);
+        write(dir, ".cursor/mcp.json", r#"{"mcpServers":{"github":{}}}"#);
+        write(dir, ".claude/rules/style.md", "managed rule");
+        write(dir, ".cursor/notes.md", "not ours");
+        let written = vec![dir.join(".claude/rules/style.md").display().to_string()];
+        let second = record_generation_with(&p, &written, 10).unwrap().unwrap();
+        assert!(second > first);
+
+        let report = restore_generation(&p, &first).unwrap();
+        assert_eq!(read(dir, ".mcp.json"), r#"{"mcpServers":{"db":{}}}"#);
+        assert!(!dir.join(".cursor/mcp.json").exists());
+        assert!(!dir.join(".claude/rules").exists());
+        assert_eq!(read(dir, ".cursor/notes.md"), "not ours");
+        assert!(report.removed.contains(&".cursor/mcp.json".to_string()));
+        assert!(report.restored.contains(&".mcp.json".to_string()));
+
+        assert_eq!(restored_generation(&p).as_deref(), Some(first.as_str()));
+        let listed = list_generations(&p).unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].generation, second);
+        assert!(listed[1].restored && !listed[0].restored);
+        assert_eq!(listed[1].agents, vec!["claude"]);
+
+        // The next explicit sync ends the restore.
+        record_generation_with(&p, &[], 10).unwrap();
+        assert_eq!(restored_generation(&p), None);
+    }
+
+    #[test]
+    fn user_instruction_content_is_never_touched() {
+        let tmp = tempdir().unwrap();
+        let dir = tmp.path();
+        let p = project(dir, &["claude"]);
+        write(dir, "CLAUDE.md", &format!("# Team\n\n{}\n", RULES_V1));
+        let with_v1 = record_generation_with(&p, &[], 10).unwrap().unwrap();
+
+        // Nothing recorded for a file without sections.
+        write(dir, "CLAUDE.md", "# Team\n");
+        let without_rules = record_generation_with(&p, &[], 10).unwrap().unwrap();
+
+        write(
+            dir,
+            "CLAUDE.md",
+            &format!("# Team, edited\n\n{}\n", RULES_V2),
+        );
+        restore_generation(&p, &with_v1).unwrap();
+        assert_eq!(
+            read(dir, "CLAUDE.md"),
+            format!("# Team, edited\n\n{}\n", RULES_V1)
+        );
+
+        restore_generation(&p, &without_rules).unwrap();
+        assert_eq!(read(dir, "CLAUDE.md"), "# Team, edited\n");
+    }
+
+    #[test]
+    fn unchanged_state_is_not_recorded_and_retention_is_enforced() {
+        let tmp = tempdir().unwrap();
+        let dir = tmp.path();
+        let p = project(dir, &["claude"]);
+        write(dir, ".mcp.json", "v1");
+        assert!(record_generation_with(&p, &[], 2).unwrap().is_some());
+        assert!(record_generation_with(&p, &[], 2).unwrap().is_none());
+
+        write(dir, ".mcp.json", "v2");
+        record_generation_with(&p, &[], 2).unwrap();
+        write(dir, ".mcp.json", "v3");
+        record_generation_with(&p, &[], 2).unwrap();
+
+        let listed = list_generations(&p).unwrap();
+        assert_eq!(listed.len(), 2);
+        let backups = backups_dir(&p).unwrap();
+        let blobs = fs::read_dir(backups.join(BLOBS_DIR)).unwrap().count();
+        assert_eq!(blobs, 2, "the pruned generation's blob is collected");
+        assert_eq!(
+            fs::read_to_string(backups.join(".gitignore")).unwrap(),
+            "*\n"
+        );
+
+        assert!(record_generation_with(&p, &[], 0).unwrap().is_none());
+        assert!(restore_generation(&p, "missing").is_err());
+    }
+}
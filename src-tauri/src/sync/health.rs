@@ -0,0 +1,368 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::agent;
+use crate::core::Project;
+
+use super::skill_shadow::held_back_skills;
+
+// ── Project health ────────────────────────────────────────────────────────────
+//
+// Answers "is everything this project selects actually there?": the project
+// directory, the registry entries behind its skills, MCP servers and rules,
+// its agents' instruction files, and the skills in the project hub
+// (`.agents/skills/`).  Errors are selections sync cannot honour; warnings
+// are things the next sync or a small edit would fix.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HealthIssue {
+    pub severity: HealthSeverity,
+    /// Machine-readable kind, e.g. `"skill_missing"`.
+    pub code: &'static str,
+    /// The skill, server, rule, agent or file the issue is about.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectHealthReport {
+    pub project: String,
+    /// True when there are no errors; warnings are allowed.
+    pub healthy: bool,
+    pub issues: Vec<HealthIssue>,
+}
+
+/// What the global registry holds, gathered up front.
+#[derive(Debug, Default)]
+pub(crate) struct RegistryContents {
+    pub skills: HashSet<String>,
+    pub mcp_servers: HashSet<String>,
+    pub rules: HashSet<String>,
+    /// Skill set name → the set's skills.
+    pub skill_sets: HashMap<String, Vec<String>>,
+}
+
+impl RegistryContents {
+    fn load() -> Result<Self, String> {
+        Ok(Self {
+            skills: crate::core::list_skill_names()?.into_iter().collect(),
+            mcp_servers: crate::core::list_mcp_server_configs()?
+                .into_iter()
+                .collect(),
+            rules: crate::core::list_rules()?
+                .into_iter()
+                .map(|r| r.id)
+                .collect(),
+            skill_sets: crate::core::list_skill_sets()?
+                .into_iter()
+                .map(|set| (set.name, set.skills))
+                .collect(),
+        })
+    }
+
+    /// `project.skills` followed by the skills of its sets that exist, as
+    /// `core::selected_skill_names` expands them at sync time.
+    fn selected_skills(&self, project: &Project) -> Vec<String> {
+        let mut selected = project.skills.clone();
+        for skill in project
+            .skill_sets
+            .iter()
+            .filter_map(|set| self.skill_sets.get(set))
+            .flatten()
+        {
+            if !selected.contains(skill) {
+                selected.push(skill.clone());
+            }
+        }
+        selected
+    }
+}
+
+/// Check that everything `project` selects exists.  Read-only.
+pub fn check_project_health(project: &Project) -> Result<ProjectHealthReport, String> {
+    Ok(project_health(project, &RegistryContents::load()?))
+}
+
+pub(crate) fn project_health(
+    project: &Project,
+    registry: &RegistryContents,
+) -> ProjectHealthReport {
+    let mut issues = Vec::new();
+    let mut issue = |severity, code, subject: &str, message: String| {
+        issues.push(HealthIssue {
+            severity,
+            code,
+            subject: (!subject.is_empty()).then(|| subject.to_string()),
+            message,
+        })
+    };
+    use HealthSeverity::*;
+
+    for set in &project.skill_sets {
+        if !registry.skill_sets.contains_key(set) {
+            issue(
+                Error,
+                "skill_set_missing",
+                set,
+                format!("Skill set '{}' no longer exists", set),
+            );
+        }
+    }
+    for skill in &registry.selected_skills(project) {
+        if !registry.skills.contains(skill) {
+            issue(
+                Error,
+                "skill_missing",
+                skill,
+                format!("Skill '{}' is no longer in the skill registry", skill),
+            );
+        }
+    }
+    for server in &project.mcp_servers {
+        if !crate::core::is_managed_name(server) && !registry.mcp_servers.contains(server) {
+            issue(
+                Error,
+                "mcp_server_missing",
+                server,
+                format!("MCP server '{}' has no registry config", server),
+            );
+        }
+    }
+    let mut reported_rules = HashSet::new();
+    for rule in project.file_rules.values().flatten() {
+        if !registry.rules.contains(rule) && reported_rules.insert(rule) {
+            issue(
+                Warning,
+                "rule_missing",
+                rule,
+                format!(
+                    "Rule '{}' no longer exists and is left out of instruction files",
+                    rule
+                ),
+            );
+        }
+    }
+    for agent_id in &project.agents {
+        if agent::from_id(agent_id).is_none() {
+            issue(
+                Warning,
+                "agent_unknown",
+                agent_id,
+                format!("Agent '{}' is not supported by this version", agent_id),
+            );
+        }
+    }
+
+    let dir = Path::new(&project.directory);
+    if project.directory.is_empty() {
+        issue(
+            Error,
+            "directory_not_set",
+            "",
+            "The project has no directory configured".to_string(),
+        );
+    } else if !dir.is_dir() {
+        issue(
+            Error,
+            "directory_missing",
+            &project.directory,
+            format!("Directory '{}' does not exist", project.directory),
+        );
+    } else {
+        let mut seen = HashSet::new();
+        for agent_instance in project.agents.iter().filter_map(|id| agent::from_id(id)) {
+            let filename = agent_instance.project_file_name();
+            if agent_instance.capabilities().instructions
+                && seen.insert(filename)
+                && !dir.join(filename).is_file()
+            {
+                issue(
+                    Warning,
+                    "instruction_file_missing",
+                    filename,
+                    format!(
+                        "{} has no {} yet; sync creates it",
+                        agent_instance.label(),
+                        filename
+                    ),
+                );
+            }
+        }
+
+        for (code, name, message) in hub_mismatches(project, registry, dir) {
+            issue(Warning, code, &name, message);
+        }
+    }
+
+    ProjectHealthReport {
+        project: project.name.clone(),
+        healthy: !issues.iter().any(|i| i.severity == Error),
+        issues,
+    }
+}
+
+/// Differences between the selected skills and the copies in the project
+/// hub, as `(code, skill, message)`.
+fn hub_mismatches(
+    project: &Project,
+    registry: &RegistryContents,
+    dir: &Path,
+) -> Vec<(&'static str, String, String)> {
+    let hub = dir.join(".agents").join("skills");
+    let custom: Vec<String> = project
+        .custom_skills
+        .iter()
+        .flatten()
+        .map(|s| s.name.clone())
+        .collect();
+    let selected = registry.selected_skills(project);
+    let expected: Vec<&String> = selected
+        .iter()
+        .filter(|s| registry.skills.contains(*s))
+        .chain(custom.iter())
+        .collect();
+    let allowed: HashSet<String> = selected
+        .iter()
+        .chain(custom.iter())
+        .chain(project.local_skills.iter())
+        .cloned()
+        .chain(held_back_skills(project))
+        .collect();
+
+    let mut mismatches = Vec::new();
+    for skill in expected {
+        if !hub.join(skill).join("SKILL.md").is_file() {
+            mismatches.push((
+                "hub_skill_missing",
+                skill.clone(),
+                format!(
+                    "Skill '{}' is not in .agents/skills/; sync copies it",
+                    skill
+                ),
+            ));
+        }
+    }
+    let mut present: Vec<String> = fs::read_dir(&hub)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    present.sort();
+    for name in present.into_iter().filter(|n| !allowed.contains(n)) {
+        mismatches.push((
+            "hub_skill_unselected",
+            name.clone(),
+            format!(
+                "'.agents/skills/{}' is not a selected, local or custom skill",
+                name
+            ),
+        ));
+    }
+    mismatches
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn codes(report: &ProjectHealthReport) -> Vec<(&'static str, Option<&str>)> {
+        report
+            .issues
+            .iter()
+            .map(|i| (i.code, i.subject.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn missing_registry_entries_and_hub_differences_are_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hub = tmp.path().join(".agents/skills");
+        for skill in ["review", "scratch", "local-notes"] {
+            fs::create_dir_all(hub.join(skill)).unwrap();
+            fs::write(hub.join(skill).join("SKILL.md"), "---\n---\n").unwrap();
+        }
+        fs::write(tmp.path().join("CLAUDE.md"), "# Project\n").unwrap();
+
+        let project = Project {
+            name: "web".into(),
+            directory: tmp.path().to_string_lossy().to_string(),
+            skills: vec!["review".into(), "deploy".into(), "vanished".into()],
+            skill_sets: vec!["quality".into(), "gone".into()],
+            local_skills: vec!["local-notes".into()],
+            mcp_servers: vec!["automatic".into(), "github".into(), "linear".into()],
+            agents: vec!["claude".into(), "codex".into()],
+            file_rules: BTreeMap::from([
+                (
+                    "CLAUDE.md".to_string(),
+                    vec!["style".to_string(), "old".to_string()],
+                ),
+                ("AGENTS.md".to_string(), vec!["old".to_string()]),
+            ]),
+            ..Default::default()
+        };
+        let registry = RegistryContents {
+            skills: ["review", "deploy"].map(String::from).into(),
+            mcp_servers: ["github"].map(String::from).into(),
+            rules: ["style"].map(String::from).into(),
+            skill_sets: HashMap::from([(
+                "quality".to_string(),
+                vec!["review".to_string(), "lint".to_string()],
+            )]),
+        };
+
+        let report = project_health(&project, &registry);
+        assert!(!report.healthy);
+        assert_eq!(
+            codes(&report),
+            vec![
+                ("skill_set_missing", Some("gone")),
+                ("skill_missing", Some("vanished")),
+                ("skill_missing", Some("lint")),
+                ("mcp_server_missing", Some("linear")),
+                ("rule_missing", Some("old")),
+                ("instruction_file_missing", Some("AGENTS.md")),
+                ("hub_skill_missing", Some("deploy")),
+                ("hub_skill_unselected", Some("scratch")),
+            ]
+        );
+        assert_eq!(report.issues[0].severity, HealthSeverity::Error);
+        assert_eq!(report.issues[4].severity, HealthSeverity::Warning);
+    }
+
+    #[test]
+    fn a_project_without_a_directory_is_unhealthy() {
+        let mut project = Project {
+            name: "web".into(),
+            ..Default::default()
+        };
+        let registry = RegistryContents::default();
+        let report = project_health(&project, &registry);
+        assert_eq!(codes(&report), vec![("directory_not_set", None)]);
+        assert!(!report.healthy);
+
+        project.directory = "/nonexistent/automatic-health".into();
+        let report = project_health(&project, &registry);
+        assert_eq!(report.issues[0].code, "directory_missing");
+
+        let tmp = tempfile::tempdir().unwrap();
+        project.directory = tmp.path().to_string_lossy().to_string();
+        let report = project_health(&project, &registry);
+        assert!(report.healthy && report.issues.is_empty());
+    }
+}
@@ -1,36 +1,47 @@
 use serde_json::{json, Map, Value};
+use std::borrow::Cow;
 use std::fs;
 use std::path::PathBuf;
 
 /// Load MCP server configs from the Automatic registry (~/.automatic/mcp_servers/).
 pub(crate) fn load_mcp_server_configs() -> Result<Map<String, Value>, String> {
+    Ok(load_mcp_server_configs_with_errors()?.0)
+}
+
+/// [`load_mcp_server_configs`], also returning a message for each registry
+/// file left out because it does not hold a usable server config.  Configs
+/// are normalised through [`crate::core::McpServerConfig`], so legacy shapes
+/// saved before validation existed are written out in canonical form.
+pub(crate) fn load_mcp_server_configs_with_errors(
+) -> Result<(Map<String, Value>, Vec<String>), String> {
     let names = crate::core::list_mcp_server_configs()?;
     let mut servers = Map::new();
+    let mut errors = Vec::new();
 
     for name in names {
-        match crate::core::read_mcp_server_config(&name) {
-            Ok(raw) => {
-                if let Ok(config) = serde_json::from_str::<Value>(&raw) {
-                    servers.insert(name, config);
-                }
+        let parsed = crate::core::read_mcp_server_config(&name).and_then(|raw| {
+            let value = serde_json::from_str::<Value>(&raw).map_err(|e| e.to_string())?;
+            crate::core::McpServerConfig::from_value(value)
+        });
+        match parsed {
+            Ok(config) => {
+                servers.insert(name, config.to_value());
             }
-            Err(_) => continue,
+            Err(e) => errors.push(format!("MCP server '{}' was skipped: {}", name, e)),
         }
     }
 
-    Ok(servers)
+    Ok((servers, errors))
 }
 
 /// Read all skill contents from the global registry for the given names.
 ///
-/// Uses the raw SKILL.md content (without companion resource formatting) so
-/// that the content written to project skill files during sync matches exactly
-/// what drift detection compares against.  Companion files are handled
-/// separately by `copy_skills_to_project` which copies the full directory.
+/// Only SKILL.md is read; companion files are handled separately by
+/// `copy_skills_to_project`, which copies the full directory.
 pub(crate) fn load_skill_contents(skill_names: &[String]) -> Vec<(String, String)> {
     let mut contents = Vec::new();
     for name in skill_names {
-        match crate::core::read_skill_raw(name) {
+        match crate::core::read_skill(name) {
             Ok(content) if !content.is_empty() => {
                 contents.push((name.clone(), content));
             }
@@ -40,12 +51,45 @@ pub(crate) fn load_skill_contents(skill_names: &[String]) -> Vec<(String, String
     contents
 }
 
-/// Find the Automatic binary path.
-pub(crate) fn find_automatic_binary() -> String {
-    std::env::current_exe()
-        .ok()
-        .and_then(|p| p.to_str().map(|s| s.to_string()))
-        .unwrap_or_else(|| "automatic".to_string())
+/// Read the content of each named global rule, skipping rules that are
+/// missing or empty.  Returns `(name, content)` pairs for
+/// [`crate::agent::Agent::write_rules`].
+pub(crate) fn load_rule_contents(rule_names: &[String]) -> Vec<(String, String)> {
+    rule_names
+        .iter()
+        .filter_map(|name| match crate::core::read_rule_content(name) {
+            Ok(content) if !content.trim().is_empty() => Some((name.clone(), content)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Resolve the global rules assigned to one project instruction file.  See
+/// [`crate::core::file_rules_for`] for the priority order; in unified mode
+/// the legacy `"_unified"` key stands in for the filename.
+///
+/// Mandatory rules (e.g. automatic-service) are always included.
+pub(crate) fn resolve_file_rules(project: &crate::core::Project, filename: &str) -> Vec<String> {
+    let key = if project.instruction_mode == "unified" {
+        "_unified"
+    } else {
+        filename
+    };
+    crate::core::ensure_mandatory_rules(&crate::core::file_rules_for(project, key))
+}
+
+/// Rule names listed in the Gemini extension context file: the global rules
+/// for `GEMINI.md` followed by the project's custom rules.
+pub(crate) fn gemini_extension_rule_names(project: &crate::core::Project) -> Vec<String> {
+    let mut names = resolve_file_rules(project, "GEMINI.md");
+    names.extend(
+        project
+            .custom_rules
+            .iter()
+            .filter(|r| !r.content.trim().is_empty())
+            .map(|r| r.name.clone()),
+    );
+    names
 }
 
 /// Strip any legacy `<!-- automatic:skills:start -->…<!-- automatic:skills:end -->`
@@ -115,31 +159,63 @@ pub(crate) fn add_unique(items: &mut Vec<String>, value: &str) -> bool {
 }
 
 /// Build the selected MCP server map for a project, applying all
-/// transformations that the sync engine uses (stripping internal `_`-prefixed
-/// fields, substituting OAuth proxy configs for HTTP servers with stored
-/// tokens).
+/// transformations that the sync engine uses (merging the project's
+/// per-server overrides, stripping internal `_`-prefixed fields, substituting
+/// OAuth proxy configs for HTTP servers with stored tokens, resolving
+/// `${keychain:...}` placeholders).
 ///
 /// Both `engine.rs` and `drift.rs` must use this function to ensure the
 /// expected config matches what is actually written to disk.
 pub(crate) fn build_selected_servers(
-    project_name: &str,
+    project: &crate::core::Project,
     server_names: &[String],
     mcp_config: &Map<String, Value>,
 ) -> Map<String, Value> {
+    build_selected_servers_with_warnings(project, server_names, mcp_config).0
+}
+
+/// Rules attached to the project's instruction files that sync leaves out
+/// because they are missing from the registry or empty.
+pub fn project_rule_warnings(project: &crate::core::Project) -> Vec<crate::core::RuleWarning> {
+    let mut files: Vec<&str> = Vec::new();
+    for agent_id in &project.agents {
+        if let Some(agent) = crate::agent::from_id(agent_id) {
+            let file = agent.project_file_name();
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for file in files {
+        let Ok(preview) = crate::core::build_rules_section(&resolve_file_rules(project, file))
+        else {
+            continue;
+        };
+        warnings.extend(preview.skipped().map(|entry| crate::core::RuleWarning {
+            file: file.to_string(),
+            rule: entry.name.clone(),
+            status: entry.status,
+        }));
+    }
+    warnings
+}
+
+/// [`build_selected_servers`], also returning a warning for each server left
+/// out because of an unresolvable `${keychain:...}` placeholder.
+pub(crate) fn build_selected_servers_with_warnings(
+    project: &crate::core::Project,
+    server_names: &[String],
+    mcp_config: &Map<String, Value>,
+) -> (Map<String, Value>, Vec<crate::core::McpSecretWarning>) {
+    let mut warnings = Vec::new();
     let mut selected_servers = Map::new();
-    let automatic_binary = find_automatic_binary();
+    let automatic_command = crate::core::automatic_command();
 
     // Always include the Automatic MCP server
-    selected_servers.insert(
-        "automatic".to_string(),
-        json!({
-            "command": automatic_binary,
-            "args": ["mcp-serve"],
-            "env": {
-                "AUTOMATIC_PROJECT": project_name
-            }
-        }),
-    );
+    let (name, entry) = crate::core::managed_entry(Some(&project.name));
+    selected_servers.insert(name.to_string(), entry);
 
     // Add project-selected MCP servers from the Automatic registry.
     // Strip Automatic-internal fields (prefixed with `_`) before writing to agent files.
@@ -149,11 +225,15 @@ pub(crate) fn build_selected_servers(
     // token out of every project file — the proxy loads it from the keychain
     // at runtime.
     for server_name in server_names {
-        if server_name == "automatic" {
+        if crate::core::is_managed_name(server_name) {
             continue;
         }
         if let Some(server_config) = mcp_config.get(server_name) {
-            let cleaned = strip_internal_fields(server_config.clone());
+            let mut server_config = server_config.clone();
+            if let Some(patch) = project.mcp_server_overrides.get(server_name) {
+                merge_mcp_override(&mut server_config, patch);
+            }
+            let cleaned = strip_internal_fields(server_config);
 
             // Check if this is an HTTP server with a stored OAuth token.
             let is_http = cleaned
@@ -168,21 +248,92 @@ pub(crate) fn build_selected_servers(
                 selected_servers.insert(
                     server_name.clone(),
                     json!({
-                        "command": automatic_binary,
+                        "command": automatic_command,
                         "args": ["mcp-proxy", server_name],
                     }),
                 );
             } else {
+                // Keychain placeholders are resolved at write time.  A server
+                // whose secrets are missing is skipped rather than written
+                // with the literal placeholder.
+                let mut server = cleaned;
+                let missing = crate::core::resolve_keychain_placeholders(&mut server);
+                if !missing.is_empty() {
+                    warnings.push(crate::core::McpSecretWarning {
+                        server: server_name.clone(),
+                        missing,
+                    });
+                    continue;
+                }
                 // For stdio servers, replace empty env values with ${KEY} so
                 // the agent expands them from the shell environment at runtime.
-                let mut server = cleaned;
                 apply_env_inheritance(&mut server);
                 selected_servers.insert(server_name.clone(), server);
             }
         }
     }
 
-    selected_servers
+    (selected_servers, warnings)
+}
+
+/// The server map written for one agent: `selected`, with each server's
+/// command variant for that agent applied (and `variants` removed), and
+/// `--read-only` appended to the `automatic` entry's args when the project
+/// restricts that agent (`AgentOptions::automatic_mcp_read_only`).
+///
+/// Like [`build_selected_servers`], shared by `engine.rs` and `drift.rs`.
+pub(crate) fn servers_for_agent<'a>(
+    project: &crate::core::Project,
+    agent_id: &str,
+    selected: &'a Map<String, Value>,
+) -> Cow<'a, Map<String, Value>> {
+    let read_only = project
+        .agent_options
+        .get(agent_id)
+        .is_some_and(|o| o.automatic_mcp_read_only);
+    let has_variants = selected.values().any(|s| s.get("variants").is_some());
+    if !read_only && !has_variants {
+        return Cow::Borrowed(selected);
+    }
+    let mut servers = selected.clone();
+    for server in servers.values_mut() {
+        crate::core::apply_mcp_command_variant(server, agent_id);
+    }
+    if read_only {
+        if let Some(args) = servers
+            .get_mut(crate::core::AUTOMATIC_SERVER_NAME)
+            .and_then(|entry| entry.get_mut("args"))
+            .and_then(Value::as_array_mut)
+        {
+            if !args.iter().any(|a| a == "--read-only") {
+                args.push(json!("--read-only"));
+            }
+        }
+    }
+    Cow::Owned(servers)
+}
+
+/// Deep-merge a project's override for one server (see
+/// `Project::mcp_server_overrides`) into the registry config, JSON Merge
+/// Patch style: objects merge key by key, `null` removes a key, and any
+/// other value replaces what was there.
+pub(crate) fn merge_mcp_override(config: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *config = patch.clone();
+        return;
+    };
+    if !config.is_object() {
+        *config = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = config {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_mcp_override(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
 }
 
 /// Remove fields whose names start with `_` from a JSON object.
@@ -276,11 +427,12 @@ pub(crate) fn cleanup_custom_agents(agents_dir: &std::path::Path, ext: &str) ->
     let mut removed = Vec::new();
 
     if agents_dir.exists() {
+        let context = crate::core::RemovalContext::project(agents_dir);
         if let Ok(entries) = fs::read_dir(agents_dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
                 if path.extension().is_some_and(|e| e == ext) {
-                    if fs::remove_file(&path).is_ok() {
+                    if crate::core::remove_guarded(&path, &context).is_ok() {
                         removed.push(path.display().to_string());
                     }
                 }
@@ -360,3 +512,38 @@ pub(crate) fn sync_user_agents(
 
     Ok(written)
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_merge_into_the_registry_config() {
+        let mut config = json!({
+            "command": "npx",
+            "args": ["-y", "@modelcontextprotocol/server-postgres"],
+            "env": { "DATABASE_URL": "postgres://localhost/dev", "PGSSLMODE": "disable" },
+        });
+        merge_mcp_override(
+            &mut config,
+            &json!({
+                "env": { "DATABASE_URL": "postgres://db/web", "PGSSLMODE": null },
+                "args": ["-y", "pg-mcp"],
+            }),
+        );
+        assert_eq!(
+            config,
+            json!({
+                "command": "npx",
+                "args": ["-y", "pg-mcp"],
+                "env": { "DATABASE_URL": "postgres://db/web" },
+            })
+        );
+
+        let mut config = json!({ "command": "srv" });
+        merge_mcp_override(&mut config, &json!({ "env": { "TOKEN": "" } }));
+        assert_eq!(config, json!({ "command": "srv", "env": { "TOKEN": "" } }));
+    }
+}
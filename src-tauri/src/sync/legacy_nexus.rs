@@ -0,0 +1,320 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::agent;
+use crate::core::{Project, AUTOMATIC_SERVER_NAME, LEGACY_SERVER_NAME};
+
+// ── Legacy "nexus" Migration ─────────────────────────────────────────────────
+//
+// Automatic was called Nexus before the rename.  Old installs left `nexus`
+// MCP entries in project agent configs, `<!-- nexus:… -->` marker comments in
+// instruction files, and a `nexus` plugin registered with the claude CLI.
+// The migration rewrites those to their current equivalents.  It only touches
+// what it finds, so running it again (or on a machine that never had the
+// legacy install) changes nothing.
+
+/// Project agent configs that keep MCP servers under `mcpServers` with
+/// Claude-style `command`/`args`/`env` entries.
+const LEGACY_MCP_CONFIGS: &[&str] = &[
+    ".mcp.json",
+    ".cursor/mcp.json",
+    ".gemini/settings.json",
+    ".kiro/settings/mcp.json",
+    ".cline/mcp.json",
+    ".kilocode/mcp.json",
+    ".junie/mcp.json",
+    ".junie/mcp/mcp.json",
+];
+
+/// Managed sections whose markers were once prefixed `nexus:`.
+const MARKER_SECTIONS: &[&str] = &["skills", "rules", "groups"];
+
+/// What [`migrate_legacy_nexus`] changed.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+pub struct LegacyMigrationReport {
+    /// Agent config files whose `nexus` MCP entry was replaced.
+    pub mcp_configs: Vec<String>,
+    /// Instruction files whose legacy markers were upgraded or removed.
+    pub instruction_files: Vec<String>,
+    /// Claude CLI plugin/marketplace entries that were unregistered.
+    pub plugin: Option<String>,
+    /// Files that could not be migrated, with the reason.
+    pub errors: Vec<String>,
+}
+
+impl LegacyMigrationReport {
+    pub fn is_empty(&self) -> bool {
+        self.mcp_configs.is_empty() && self.instruction_files.is_empty() && self.plugin.is_none()
+    }
+}
+
+/// Migrate every registered project plus the claude CLI plugin registration.
+pub fn migrate_legacy_nexus() -> Result<LegacyMigrationReport, String> {
+    let mut report = LegacyMigrationReport::default();
+    for name in crate::core::list_projects()? {
+        let Ok(raw) = crate::core::read_project(&name) else {
+            continue;
+        };
+        let Ok(project) = serde_json::from_str::<Project>(&raw) else {
+            continue;
+        };
+        migrate_project(&project, &mut report);
+    }
+
+    match crate::core::remove_legacy_nexus_plugin() {
+        Ok(removed) => report.plugin = removed,
+        Err(e) => report.errors.push(format!("claude plugin: {}", e)),
+    }
+    Ok(report)
+}
+
+/// Run [`migrate_legacy_nexus`] once, recording the stamp in settings so
+/// later launches skip it.  Called from app startup.
+pub fn migrate_legacy_nexus_once() -> Result<Option<LegacyMigrationReport>, String> {
+    let mut settings = crate::core::read_settings()?;
+    if settings.legacy_nexus_migrated {
+        return Ok(None);
+    }
+    let report = migrate_legacy_nexus()?;
+    settings.legacy_nexus_migrated = true;
+    crate::core::write_settings(&settings)?;
+    Ok(Some(report))
+}
+
+/// Migrate one project's directory.  Projects without a directory (or whose
+/// directory is gone) are skipped.
+fn migrate_project(project: &Project, report: &mut LegacyMigrationReport) {
+    if project.directory.is_empty() {
+        return;
+    }
+    let dir = Path::new(&project.directory);
+    if !dir.is_dir() {
+        return;
+    }
+
+    let (_, entry) = crate::core::managed_entry(Some(&project.name));
+    for rel in LEGACY_MCP_CONFIGS {
+        let path = dir.join(rel);
+        match migrate_mcp_config(&path, &entry) {
+            Ok(true) => report.mcp_configs.push(path.display().to_string()),
+            Ok(false) => {}
+            Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    let mut filenames: Vec<&str> = agent::all()
+        .into_iter()
+        .map(|a| a.project_file_name())
+        .collect();
+    filenames.sort_unstable();
+    filenames.dedup();
+    for filename in filenames {
+        let path = dir.join(filename);
+        match migrate_instruction_file(&path) {
+            Ok(true) => report.instruction_files.push(path.display().to_string()),
+            Ok(false) => {}
+            Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+}
+
+/// Replace a `nexus` server in the MCP config at `path` with `entry` under
+/// the `automatic` name (an existing `automatic` entry is kept).  Returns
+/// whether the file changed.
+fn migrate_mcp_config(path: &Path, entry: &Value) -> Result<bool, String> {
+    if !path.is_file() {
+        return Ok(false);
+    }
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let Ok(mut root) = serde_json::from_str::<Value>(&raw) else {
+        // Not ours to repair; leave malformed files alone.
+        return Ok(false);
+    };
+    let Some(servers) = root.get_mut("mcpServers").and_then(|s| s.as_object_mut()) else {
+        return Ok(false);
+    };
+    if servers.remove(LEGACY_SERVER_NAME).is_none() {
+        return Ok(false);
+    }
+    if !servers.contains_key(AUTOMATIC_SERVER_NAME) {
+        servers.insert(AUTOMATIC_SERVER_NAME.to_string(), entry.clone());
+    }
+
+    let out = serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?;
+    fs::write(path, format!("{}\n", out)).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn migrate_instruction_file(path: &Path) -> Result<bool, String> {
+    if !path.is_file() {
+        return Ok(false);
+    }
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    match upgrade_legacy_markers(&raw) {
+        Some(upgraded) => {
+            fs::write(path, upgraded).map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Rewrite `<!-- nexus:<section>:start|end -->` markers to their
+/// `automatic:` equivalents and drop bare `<!-- nexus:<section> -->` lines,
+/// which have no current counterpart.  Returns `None` when `content` has no
+/// legacy markers.
+pub(crate) fn upgrade_legacy_markers(content: &str) -> Option<String> {
+    if !content.contains("<!-- nexus:") {
+        return None;
+    }
+
+    let mut out = content.to_string();
+    for section in MARKER_SECTIONS {
+        for edge in ["start", "end"] {
+            out = out.replace(
+                &format!("<!-- nexus:{}:{} -->", section, edge),
+                &format!("<!-- automatic:{}:{} -->", section, edge),
+            );
+        }
+        let bare = format!("<!-- nexus:{} -->", section);
+        out = out
+            .split_inclusive('\n')
+            .filter(|line| line.trim() != bare)
+            .collect();
+        out = out.replace(&bare, "");
+    }
+
+    (out != content).then_some(out)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    const LEGACY_CLAUDE_MD: &str = "# Project\n\n\
+        <!-- nexus:skills -->\n\
+        <!-- nexus:skills:start -->\n## Skills\n- react\n<!-- nexus:skills:end -->\n\n\
+        <!-- nexus:rules:start -->\nBe nice.\n<!-- nexus:rules:end -->\n";
+
+    fn fixture(dir: &Path) -> Project {
+        fs::write(
+            dir.join(".mcp.json"),
+            serde_json::to_string_pretty(&json!({
+                "mcpServers": {
+                    "nexus": {"command": "/old/path/to/nexus", "args": ["mcp-serve"]},
+                    "github": {"command": "npx", "args": ["-y", "server-github"]}
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join(".cursor")).unwrap();
+        fs::write(
+            dir.join(".cursor/mcp.json"),
+            r#"{"mcpServers": {"github": {"command": "npx"}}}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("CLAUDE.md"), LEGACY_CLAUDE_MD).unwrap();
+        fs::write(dir.join("AGENTS.md"), "# Agents\n").unwrap();
+
+        Project {
+            name: "legacy".into(),
+            directory: dir.display().to_string(),
+            agents: vec!["claude".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn legacy_markers_are_upgraded_or_removed() {
+        let upgraded = upgrade_legacy_markers(LEGACY_CLAUDE_MD).unwrap();
+        assert!(!upgraded.contains("nexus"));
+        assert!(upgraded.contains(
+            "<!-- automatic:skills:start -->\n## Skills\n- react\n<!-- automatic:skills:end -->"
+        ));
+        assert!(upgraded.contains("<!-- automatic:rules:start -->"));
+        assert!(upgraded.starts_with("# Project\n\n<!-- automatic:skills:start -->"));
+
+        assert_eq!(upgrade_legacy_markers("# Current\n"), None);
+        assert_eq!(upgrade_legacy_markers(&upgraded), None);
+    }
+
+    #[test]
+    fn nexus_entry_is_replaced_and_other_servers_kept() {
+        let dir = tempdir().unwrap();
+        let project = fixture(dir.path());
+        let mut report = LegacyMigrationReport::default();
+        migrate_project(&project, &mut report);
+
+        assert_eq!(report.mcp_configs.len(), 1);
+        assert!(report.mcp_configs[0].ends_with(".mcp.json"));
+        assert_eq!(report.instruction_files.len(), 1);
+        assert!(report.instruction_files[0].ends_with("CLAUDE.md"));
+        assert!(report.errors.is_empty());
+
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join(".mcp.json")).unwrap())
+                .unwrap();
+        let servers = config["mcpServers"].as_object().unwrap();
+        assert!(!servers.contains_key("nexus"));
+        assert_eq!(servers["automatic"]["env"]["AUTOMATIC_PROJECT"], "legacy");
+        assert_eq!(servers["github"]["command"], "npx");
+    }
+
+    #[test]
+    fn existing_automatic_entry_is_kept() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".mcp.json");
+        fs::write(
+            &path,
+            r#"{"mcpServers": {"nexus": {"command": "old"}, "automatic": {"command": "new"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            migrate_mcp_config(&path, &json!({"command": "x"})),
+            Ok(true)
+        );
+        let config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            config["mcpServers"],
+            json!({"automatic": {"command": "new"}})
+        );
+    }
+
+    #[test]
+    fn second_run_changes_nothing() {
+        let dir = tempdir().unwrap();
+        let project = fixture(dir.path());
+        migrate_project(&project, &mut LegacyMigrationReport::default());
+        let claude_md = fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap();
+
+        let mut report = LegacyMigrationReport::default();
+        migrate_project(&project, &mut report);
+        assert!(report.is_empty(), "{:?}", report);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(),
+            claude_md
+        );
+    }
+
+    #[test]
+    fn projects_without_legacy_artifacts_are_untouched() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("CLAUDE.md"), "# Fresh\n").unwrap();
+        let project = Project {
+            name: "fresh".into(),
+            directory: dir.path().display().to_string(),
+            ..Default::default()
+        };
+        let mut report = LegacyMigrationReport::default();
+        migrate_project(&project, &mut report);
+        assert!(report.is_empty());
+        assert!(report.errors.is_empty());
+        assert!(!dir.path().join(".mcp.json").exists());
+    }
+}
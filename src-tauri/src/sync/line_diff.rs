@@ -0,0 +1,220 @@
+// ── Line Diffs ───────────────────────────────────────────────────────────────
+//
+// Unified diffs for the detailed drift report, so the user can see which
+// line of a generated file was hand-edited before re-syncing over it.  Only
+// small text files are diffed: the LCS table grows with the product of the
+// changed line counts, so anything larger falls back to a size comparison.
+
+/// Lines of unchanged context around each change.
+const CONTEXT: usize = 3;
+
+/// Upper bound on the LCS table (changed lines × changed lines).
+const MAX_TABLE_CELLS: usize = 1_000_000;
+
+/// Diffs longer than this are cut off.
+const MAX_DIFF_BYTES: usize = 64 * 1024;
+
+/// Unified diff turning `expected` into `actual`, or `None` when either side
+/// looks binary or the change is too large to diff.
+pub(crate) fn unified_diff(expected: &str, actual: &str) -> Option<String> {
+    if expected.contains('\0') || actual.contains('\0') {
+        return None;
+    }
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    let ops = edit_script(&old, &new)?;
+
+    let mut out = String::from("--- expected\n+++ actual\n");
+    for hunk in hunks(&ops) {
+        if out.len() > MAX_DIFF_BYTES {
+            out.push_str("... (diff truncated)\n");
+            break;
+        }
+        out.push_str(&hunk);
+    }
+    Some(out)
+}
+
+/// One-line size comparison for files [`unified_diff`] cannot show.
+pub(crate) fn size_summary(expected: &str, actual: &str) -> String {
+    format!(
+        "expected {} bytes, found {} bytes on disk",
+        expected.len(),
+        actual.len()
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Same,
+    Removed,
+    Added,
+}
+
+/// The line-by-line edit script from `old` to `new`.  Common leading and
+/// trailing lines are matched directly; the rest through an LCS table.
+fn edit_script<'a>(old: &[&'a str], new: &[&'a str]) -> Option<Vec<(Op, &'a str)>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+    if a.len().saturating_mul(b.len()) > MAX_TABLE_CELLS {
+        return None;
+    }
+
+    // lcs[i][j]: length of the longest common subsequence of a[i..] and b[j..].
+    let width = b.len() + 1;
+    let mut lcs = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * width + j] = if a[i] == b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|l| (Op::Same, *l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((Op::Same, a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len()
+            && (j == b.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+        {
+            ops.push((Op::Removed, a[i]));
+            i += 1;
+        } else {
+            ops.push((Op::Added, b[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|l| (Op::Same, *l)));
+    Some(ops)
+}
+
+/// Render `ops` as `@@` hunks with [`CONTEXT`] lines around each change.
+fn hunks(ops: &[(Op, &str)]) -> Vec<String> {
+    let changes: Vec<usize> = (0..ops.len()).filter(|&i| ops[i].0 != Op::Same).collect();
+    let mut out = Vec::new();
+    let mut k = 0;
+    while k < changes.len() {
+        // Extend the hunk while the next change is within reach of its context.
+        let first = changes[k];
+        let mut last = first;
+        while k + 1 < changes.len() && changes[k + 1] - last <= 2 * CONTEXT {
+            k += 1;
+            last = changes[k];
+        }
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + CONTEXT + 1).min(ops.len());
+
+        // Old-side lines are everything but additions, new-side lines
+        // everything but removals.
+        let lines = |ops: &[(Op, &str)], skip: Op| ops.iter().filter(|(op, _)| *op != skip).count();
+        let mut hunk = format!(
+            "@@ -{} +{} @@\n",
+            range(
+                lines(&ops[..start], Op::Added),
+                lines(&ops[start..end], Op::Added)
+            ),
+            range(
+                lines(&ops[..start], Op::Removed),
+                lines(&ops[start..end], Op::Removed)
+            )
+        );
+        for (op, line) in &ops[start..end] {
+            let marker = match op {
+                Op::Same => ' ',
+                Op::Removed => '-',
+                Op::Added => '+',
+            };
+            hunk.push(marker);
+            hunk.push_str(line);
+            hunk.push('\n');
+        }
+        out.push(hunk);
+        k += 1;
+    }
+    out
+}
+
+/// A hunk range in unified diff notation: 1-based start, then the length
+/// when it is not 1.  An empty range points at the line before it.
+fn range(before: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", before),
+        1 => format!("{}", before + 1),
+        _ => format!("{},{}", before + 1, len),
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_line_is_shown_with_context() {
+        let expected = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let actual = "a\nb\nc\nd\nE\nf\ng\nh\n";
+        assert_eq!(
+            unified_diff(expected, actual).unwrap(),
+            "--- expected\n+++ actual\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let expected: String = (1..=20).map(|i| format!("{}\n", i)).collect();
+        let actual: String = (1..=20)
+            .filter(|&i| i != 19)
+            .map(|i| match i {
+                2 => "two\n".to_string(),
+                i => format!("{}\n", i),
+            })
+            .collect();
+        let diff = unified_diff(&expected, &actual).unwrap();
+
+        assert_eq!(diff.matches("@@ -").count(), 2, "{}", diff);
+        assert!(
+            diff.contains("@@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n"),
+            "{}",
+            diff
+        );
+        assert!(
+            diff.contains("@@ -16,5 +16,4 @@\n 16\n 17\n 18\n-19\n 20\n"),
+            "{}",
+            diff
+        );
+    }
+
+    #[test]
+    fn additions_to_an_empty_file() {
+        assert_eq!(
+            unified_diff("", "x\n").unwrap(),
+            "--- expected\n+++ actual\n@@ -0,0 +1 @@\n+x\n"
+        );
+    }
+
+    #[test]
+    fn binary_and_huge_files_are_not_diffed() {
+        assert!(unified_diff("a\0b", "a").is_none());
+        let old: String = (0..1_500).map(|i| format!("old {}\n", i)).collect();
+        let new: String = (0..1_500).map(|i| format!("new {}\n", i)).collect();
+        assert!(unified_diff(&old, &new).is_none());
+        assert_eq!(
+            size_summary("abc", "abcdef"),
+            "expected 3 bytes, found 6 bytes on disk"
+        );
+    }
+}
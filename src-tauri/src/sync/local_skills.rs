@@ -35,6 +35,7 @@ pub fn save_local_skill(
     skill_name: &str,
     content: &str,
 ) -> Result<Vec<String>, String> {
+    project.ensure_unlocked()?;
     if project.directory.is_empty() {
         return Err("Project has no directory configured".into());
     }
@@ -100,6 +101,7 @@ pub fn save_local_skill(
 /// Copy a local skill into the global registry and promote it to a normal
 /// (global) project skill.  Returns the updated project.
 pub fn import_local_skill(project: &Project, skill_name: &str) -> Result<Project, String> {
+    project.ensure_unlocked()?;
     let content = read_local_skill(project, skill_name)?;
     crate::core::save_skill(skill_name, &content)?;
 
@@ -117,6 +119,7 @@ pub fn import_local_skill(project: &Project, skill_name: &str) -> Result<Project
 /// Copy every local skill to all agent skill directories so that each agent
 /// in the project has a copy.  Returns the list of files written.
 pub fn sync_local_skills_across_agents(project: &Project) -> Result<Vec<String>, String> {
+    project.ensure_unlocked()?;
     if project.directory.is_empty() {
         return Err("Project has no directory configured".into());
     }
@@ -0,0 +1,229 @@
+use crate::activity::{self, ActivityEvent};
+use crate::core::{self, Project};
+
+use super::engine::sync_project_without_autodetect;
+
+/// Outcome of [`reactive_sync_project`].
+#[derive(Debug)]
+pub enum ReactiveSync {
+    /// The project was re-synced; holds the files written.
+    Synced(Vec<String>),
+    /// The project is locked and was left untouched.
+    SkippedLocked,
+    /// The project was restored from this sync generation and is left alone
+    /// until the user syncs it explicitly.
+    SkippedRestored(String),
+    /// The project has no directory or no agents, so there is nothing to sync.
+    NotConfigured,
+    /// The sync itself failed.
+    Failed(String),
+}
+
+/// Re-sync a project in response to a registry change (a rule, skill or MCP
+/// server it references was edited) rather than an explicit user request.
+///
+/// Unlike [`sync_project_without_autodetect`], a locked project is not an
+/// error here: it is skipped so one frozen project never blocks updates to
+/// the others.  Callers record the skip for the user.  A project restored
+/// from a sync generation is skipped too, so the restore is not undone.
+pub fn reactive_sync_project(project: &mut Project) -> ReactiveSync {
+    if project.directory.is_empty() || project.agents.is_empty() {
+        return ReactiveSync::NotConfigured;
+    }
+    if project.locked {
+        return ReactiveSync::SkippedLocked;
+    }
+    if let Some(generation) = super::generations::restored_generation(project) {
+        return ReactiveSync::SkippedRestored(generation);
+    }
+    let _history = core::sync_history_scope(core::SyncTrigger::RegistryChange);
+    match sync_project_without_autodetect(project) {
+        Ok(files) => ReactiveSync::Synced(files),
+        Err(e) => ReactiveSync::Failed(e),
+    }
+}
+
+// ── Registry-change fan-out ──────────────────────────────────────────────────
+//
+// Used by the Tauri commands and the MCP server when a registry item is saved
+// or deleted and the projects referencing it need updating.
+
+/// Run `f` on every registered project that can be read and parsed.  Writes
+/// made by `f` are recorded in sync history as registry changes.
+pub(crate) fn with_each_project_mut<F>(mut f: F)
+where
+    F: FnMut(&str, &mut Project),
+{
+    let _history = core::sync_history_scope(core::SyncTrigger::RegistryChange);
+    let project_names = match core::list_projects() {
+        Ok(names) => names,
+        Err(e) => {
+            tracing::error!("Failed to list projects for config updates: {}", e);
+            return;
+        }
+    };
+
+    for project_name in project_names {
+        let raw = match core::read_project(&project_name) {
+            Ok(raw) => raw,
+            Err(e) => {
+                tracing::error!("Failed to read project '{}': {}", project_name, e);
+                continue;
+            }
+        };
+
+        let mut project: Project = match serde_json::from_str(&raw) {
+            Ok(project) => project,
+            Err(e) => {
+                tracing::error!("Failed to parse project '{}': {}", project_name, e);
+                continue;
+            }
+        };
+
+        f(&project_name, &mut project);
+    }
+}
+
+/// [`reactive_sync_project`], logging the outcomes callers have no use for.
+pub(crate) fn sync_project_if_configured(project_name: &str, project: &mut Project) {
+    match reactive_sync_project(project) {
+        ReactiveSync::SkippedLocked => record_locked_skip(project_name, project),
+        ReactiveSync::Failed(e) => tracing::error!(
+            "Failed to sync project '{}' after registry update: {}",
+            project_name,
+            e
+        ),
+        ReactiveSync::Synced(_)
+        | ReactiveSync::NotConfigured
+        | ReactiveSync::SkippedRestored(_) => {}
+    }
+}
+
+/// Note in the activity log that a registry-triggered update left a locked
+/// project untouched.  The triggering operation itself still succeeds.
+pub(crate) fn record_locked_skip(project_name: &str, project: &Project) {
+    activity::log(
+        project_name,
+        ActivityEvent::SyncSkippedLocked,
+        &format!("Skipped locked project {}", project_name),
+        project.locked_reason.as_deref().unwrap_or(""),
+    );
+}
+
+/// Re-sync every project that uses skill `skill_name`, after it was edited
+/// from the app or over MCP.
+pub(crate) fn sync_projects_referencing_skill(skill_name: &str) {
+    sync_projects_referencing_skills(&[skill_name.to_string()]);
+}
+
+/// Re-sync, once each, every project that uses any of `skill_names`,
+/// directly or through a skill set.
+pub(crate) fn sync_projects_referencing_skills(skill_names: &[String]) {
+    with_each_project_mut(|project_name, project| {
+        if core::selected_skill_names(project)
+            .iter()
+            .any(|skill| skill_names.contains(skill))
+        {
+            sync_project_if_configured(project_name, project);
+        }
+    });
+}
+
+/// Re-sync every project that applies the skill set `set_name`.
+pub(crate) fn sync_projects_referencing_skill_set(set_name: &str) {
+    with_each_project_mut(|project_name, project| {
+        if project.skill_sets.iter().any(|s| s == set_name) {
+            sync_project_if_configured(project_name, project);
+        }
+    });
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CustomRule;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    const AUTHORED: &str = "# Team instructions\n";
+
+    fn checkout(dir: &Path, locked: bool) -> Project {
+        fs::write(dir.join("AGENTS.md"), AUTHORED).unwrap();
+        Project {
+            name: "shared".into(),
+            directory: dir.display().to_string(),
+            agents: vec!["codex".into()],
+            instruction_mode: "per-agent".into(),
+            locked,
+            locked_reason: locked.then(|| "release freeze".into()),
+            unregistered: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rule_edit_skips_locked_project_and_updates_unlocked_one() {
+        let locked_dir = tempdir().unwrap();
+        let unlocked_dir = tempdir().unwrap();
+        let mut projects = [
+            checkout(locked_dir.path(), true),
+            checkout(unlocked_dir.path(), false),
+        ];
+
+        // The shared rule changed: both projects now carry the new content.
+        let outcomes: Vec<ReactiveSync> = projects
+            .iter_mut()
+            .map(|p| {
+                p.custom_rules = vec![CustomRule {
+                    name: "style".into(),
+                    content: "Prefer early returns.".into(),
+                }];
+                reactive_sync_project(p)
+            })
+            .collect();
+
+        assert!(matches!(outcomes[0], ReactiveSync::SkippedLocked));
+        assert_eq!(
+            fs::read_to_string(locked_dir.path().join("AGENTS.md")).unwrap(),
+            AUTHORED
+        );
+        assert!(!locked_dir.path().join(".automatic").exists());
+        assert!(!locked_dir.path().join(".codex").exists());
+
+        assert!(matches!(outcomes[1], ReactiveSync::Synced(_)));
+        let updated = fs::read_to_string(unlocked_dir.path().join("AGENTS.md")).unwrap();
+        assert!(updated.starts_with(AUTHORED.trim_end()));
+        assert!(updated.contains("Prefer early returns."));
+    }
+
+    #[test]
+    fn explicit_operations_refuse_locked_project() {
+        let dir = tempdir().unwrap();
+        let mut project = checkout(dir.path(), true);
+
+        let err = sync_project_without_autodetect(&mut project).unwrap_err();
+        assert!(err.contains("locked (release freeze)"), "{}", err);
+        assert!(super::super::sync_project(&project).is_err());
+        assert!(super::super::remove_agent_from_project(
+            &mut project,
+            "codex",
+            super::super::UniqueMcpServers::Keep,
+            false
+        )
+        .is_err());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("AGENTS.md")).unwrap(),
+            AUTHORED
+        );
+    }
+
+    #[test]
+    fn read_only_drift_check_still_works_when_locked() {
+        let dir = tempdir().unwrap();
+        let project = checkout(dir.path(), true);
+        assert!(super::super::check_project_drift(&project, false).is_ok());
+    }
+}
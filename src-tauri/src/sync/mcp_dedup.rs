@@ -0,0 +1,429 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// One agent's version of a discovered MCP server config.
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerVariant {
+    pub agent_id: String,
+    pub config: Value,
+}
+
+/// A server name discovered with configs that still differ after
+/// canonicalization.  Neither variant is written to the registry; the user
+/// picks one (saving it via `save_mcp_server_config`).
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerConflict {
+    pub name: String,
+    pub variants: Vec<McpServerVariant>,
+}
+
+/// Result of merging MCP servers discovered across several agents.
+#[derive(Debug, Default)]
+pub struct MergedMcpServers {
+    /// One config per server name whose variants were all equivalent, in
+    /// first-seen order.
+    pub servers: Vec<(String, Value)>,
+    pub conflicts: Vec<McpServerConflict>,
+    /// Agents each server name was discovered from (conflicting names
+    /// included), in first-seen order.  Recorded on the project as
+    /// `mcp_server_origins` when the server is first added.
+    pub origins: Vec<(String, Vec<String>)>,
+}
+
+/// Merge `(agent_id, server_name, config)` triples discovered across agents.
+///
+/// Equivalent configs for the same name collapse to the first one seen; names
+/// with genuinely different configs become [`McpServerConflict`] entries
+/// listing each agent's variant.
+pub fn merge_discovered_servers(found: Vec<(String, String, Value)>) -> MergedMcpServers {
+    let mut grouped: Vec<(String, Vec<McpServerVariant>)> = Vec::new();
+    for (agent_id, name, config) in found {
+        let variant = McpServerVariant { agent_id, config };
+        match grouped.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, variants)) => variants.push(variant),
+            None => grouped.push((name, vec![variant])),
+        }
+    }
+
+    let mut merged = MergedMcpServers::default();
+    for (name, variants) in grouped {
+        let mut agents: Vec<String> = Vec::new();
+        for v in &variants {
+            if !agents.contains(&v.agent_id) {
+                agents.push(v.agent_id.clone());
+            }
+        }
+        merged.origins.push((name.clone(), agents));
+        let first = &variants[0].config;
+        if variants
+            .iter()
+            .all(|v| mcp_configs_equivalent(first, &v.config))
+        {
+            merged.servers.push((name, first.clone()));
+        } else {
+            merged.conflicts.push(McpServerConflict { name, variants });
+        }
+    }
+    merged
+}
+
+/// Whether two MCP server configs describe the same server once cosmetic
+/// differences are removed (see [`canonicalize_mcp_config`]).
+pub fn mcp_configs_equivalent(a: &Value, b: &Value) -> bool {
+    canonicalize_mcp_config(a) == canonicalize_mcp_config(b)
+}
+
+/// Reduce an MCP server config to a canonical form for comparison only —
+/// the result is never written anywhere.
+///
+/// - object keys are sorted and `_`-prefixed internal fields dropped
+/// - fields agents strip on write (`type: "stdio"`, `enabled: true`,
+///   `timeout`) and empty `args` / `env` / `headers` are dropped
+/// - the command is reduced to its basename without `.cmd` / `.exe`
+///   (`/usr/local/bin/npx` → `npx`)
+/// - `-y` / `--yes` are dropped from `npx` args and version suffixes are
+///   stripped from package args (`pkg@latest`, `@scope/pkg@1.2.3`)
+/// - env values of `${KEY}` are treated as the empty "inherit" value
+/// - a trailing `/` is dropped from `url`
+pub fn canonicalize_mcp_config(config: &Value) -> Value {
+    let Some(obj) = config.as_object() else {
+        return config.clone();
+    };
+
+    let command = obj
+        .get("command")
+        .and_then(|c| c.as_str())
+        .map(canonical_command);
+
+    let mut out = Map::new();
+    let mut keys: Vec<&String> = obj.keys().collect();
+    keys.sort();
+    for key in keys {
+        let value = &obj[key];
+        if key.starts_with('_') || key == "timeout" {
+            continue;
+        }
+        match key.as_str() {
+            "type" if value.as_str() == Some("stdio") => {}
+            "enabled" if value.as_bool() == Some(true) => {}
+            "command" => {
+                if let Some(c) = &command {
+                    out.insert(key.clone(), Value::String(c.clone()));
+                }
+            }
+            "args" => {
+                let args: Vec<Value> = value
+                    .as_array()
+                    .map(|a| a.as_slice())
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|a| {
+                        command.as_deref() != Some("npx")
+                            || !matches!(a.as_str(), Some("-y") | Some("--yes"))
+                    })
+                    .map(|a| match a.as_str() {
+                        Some(s) => Value::String(strip_version_suffix(s).to_string()),
+                        None => a.clone(),
+                    })
+                    .collect();
+                if !args.is_empty() {
+                    out.insert(key.clone(), Value::Array(args));
+                }
+            }
+            "env" => {
+                if let Some(env) = value.as_object() {
+                    if !env.is_empty() {
+                        out.insert(key.clone(), canonical_env(env));
+                    }
+                }
+            }
+            "url" => {
+                let url = value.as_str().unwrap_or_default().trim_end_matches('/');
+                out.insert(key.clone(), Value::String(url.to_string()));
+            }
+            _ => {
+                let v = sort_keys(value);
+                let empty = v.as_object().is_some_and(|m| m.is_empty())
+                    || v.as_array().is_some_and(|a| a.is_empty());
+                if !empty {
+                    out.insert(key.clone(), v);
+                }
+            }
+        }
+    }
+    Value::Object(out)
+}
+
+fn canonical_command(command: &str) -> String {
+    let base = command.rsplit(['/', '\\']).next().unwrap_or(command);
+    let lower = base.to_ascii_lowercase();
+    for ext in [".cmd", ".exe", ".bat"] {
+        if lower.ends_with(ext) {
+            return base[..base.len() - ext.len()].to_string();
+        }
+    }
+    base.to_string()
+}
+
+fn canonical_env(env: &Map<String, Value>) -> Value {
+    let mut keys: Vec<&String> = env.keys().collect();
+    keys.sort();
+    let mut out = Map::new();
+    for key in keys {
+        let value = &env[key];
+        let inherited = value.as_str() == Some(&format!("${{{}}}", key));
+        let v = if inherited {
+            Value::String(String::new())
+        } else {
+            value.clone()
+        };
+        out.insert(key.clone(), v);
+    }
+    Value::Object(out)
+}
+
+/// Strip a trailing `@<version>` from a package specifier.  A leading `@`
+/// (npm scope) is never treated as a version separator.
+fn strip_version_suffix(arg: &str) -> &str {
+    let Some(at) = arg.rfind('@') else {
+        return arg;
+    };
+    if at == 0 {
+        return arg;
+    }
+    let version = &arg[at + 1..];
+    let looks_like_version = version == "latest"
+        || version == "next"
+        || version
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit() || c == '^' || c == '~');
+    if looks_like_version && !version.contains('/') {
+        &arg[..at]
+    } else {
+        arg
+    }
+}
+
+fn sort_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let mut out = Map::new();
+            for key in keys {
+                out.insert(key.clone(), sort_keys(&map[key]));
+            }
+            Value::Object(out)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(sort_keys).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Save a discovered server config to the registry only when it differs from
+/// the existing entry.  Returns `true` if the registry was written.
+///
+/// Local OAuth proxy entries (`automatic mcp-proxy <name>`) that Automatic
+/// itself wrote are never saved over an existing registry entry — the
+/// registry keeps the remote URL form.
+pub(crate) fn save_mcp_server_if_changed(name: &str, config: &Value) -> Result<bool, String> {
+    let existing = crate::core::read_mcp_server_config(name)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok());
+
+    if let Some(existing) = &existing {
+        let is_proxy = config
+            .get("args")
+            .and_then(|a| a.as_array())
+            .and_then(|a| a.first())
+            .and_then(|a| a.as_str())
+            == Some("mcp-proxy");
+        if is_proxy || mcp_configs_equivalent(existing, config) {
+            return Ok(false);
+        }
+    }
+
+    let config_str = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialise config for '{}': {}", name, e))?;
+    crate::core::save_mcp_server_config(name, &config_str)?;
+    Ok(true)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn equivalent_config_pairs() {
+        let cases = [
+            (
+                "version suffix",
+                json!({"command": "npx", "args": ["-y", "pkg"]}),
+                json!({"command": "npx", "args": ["-y", "pkg@latest"]}),
+            ),
+            (
+                "scoped package version",
+                json!({"command": "npx", "args": ["@scope/pkg"]}),
+                json!({"command": "npx", "args": ["@scope/pkg@1.2.3"]}),
+            ),
+            (
+                "npx yes flag",
+                json!({"command": "npx", "args": ["pkg"]}),
+                json!({"command": "npx", "args": ["--yes", "pkg"]}),
+            ),
+            (
+                "absolute command path",
+                json!({"command": "/usr/local/bin/npx", "args": ["pkg"]}),
+                json!({"command": "npx", "args": ["pkg"]}),
+            ),
+            (
+                "windows shim",
+                json!({"command": "C:\\Program Files\\nodejs\\npx.cmd", "args": ["pkg"]}),
+                json!({"command": "npx", "args": ["pkg"]}),
+            ),
+            (
+                "env order",
+                json!({"command": "uvx", "env": {"A": "1", "B": "2"}}),
+                json!({"command": "uvx", "env": {"B": "2", "A": "1"}}),
+            ),
+            (
+                "inherited env placeholder",
+                json!({"command": "uvx", "env": {"TOKEN": ""}}),
+                json!({"command": "uvx", "env": {"TOKEN": "${TOKEN}"}}),
+            ),
+            (
+                "stripped stdio fields",
+                json!({"type": "stdio", "command": "uvx", "enabled": true, "timeout": 30}),
+                json!({"command": "uvx"}),
+            ),
+            (
+                "empty args and internal fields",
+                json!({"command": "uvx", "args": [], "_author": "me"}),
+                json!({"command": "uvx"}),
+            ),
+            (
+                "url trailing slash",
+                json!({"type": "http", "url": "https://api.example.com/mcp/"}),
+                json!({"type": "http", "url": "https://api.example.com/mcp"}),
+            ),
+            (
+                "nested header order",
+                json!({"url": "https://x", "headers": {"A": "1", "B": "2"}}),
+                json!({"headers": {"B": "2", "A": "1"}, "url": "https://x"}),
+            ),
+        ];
+        for (label, a, b) in cases {
+            assert!(
+                mcp_configs_equivalent(&a, &b),
+                "expected equivalent: {}",
+                label
+            );
+        }
+    }
+
+    #[test]
+    fn non_equivalent_config_pairs() {
+        let cases = [
+            (
+                "different package",
+                json!({"command": "npx", "args": ["pkg-a"]}),
+                json!({"command": "npx", "args": ["pkg-b"]}),
+            ),
+            (
+                "different command",
+                json!({"command": "npx", "args": ["pkg"]}),
+                json!({"command": "bunx", "args": ["pkg"]}),
+            ),
+            (
+                "yes flag kept for non-npx",
+                json!({"command": "uvx", "args": ["-y", "pkg"]}),
+                json!({"command": "uvx", "args": ["pkg"]}),
+            ),
+            (
+                "different env value",
+                json!({"command": "uvx", "env": {"TOKEN": "a"}}),
+                json!({"command": "uvx", "env": {"TOKEN": "b"}}),
+            ),
+            (
+                "extra env key",
+                json!({"command": "uvx", "env": {"A": "1"}}),
+                json!({"command": "uvx", "env": {"A": "1", "B": "2"}}),
+            ),
+            (
+                "different url",
+                json!({"type": "http", "url": "https://a.example.com/mcp"}),
+                json!({"type": "http", "url": "https://b.example.com/mcp"}),
+            ),
+            (
+                "disabled server",
+                json!({"command": "uvx", "enabled": false}),
+                json!({"command": "uvx"}),
+            ),
+            (
+                "email-like arg is not a version",
+                json!({"command": "tool", "args": ["user@example.com"]}),
+                json!({"command": "tool", "args": ["user"]}),
+            ),
+        ];
+        for (label, a, b) in cases {
+            assert!(
+                !mcp_configs_equivalent(&a, &b),
+                "expected different: {}",
+                label
+            );
+        }
+    }
+
+    #[test]
+    fn merge_collapses_equivalent_variants() {
+        let merged = merge_discovered_servers(vec![
+            (
+                "claude".into(),
+                "github".into(),
+                json!({"command": "npx", "args": ["-y", "gh"]}),
+            ),
+            (
+                "cursor".into(),
+                "github".into(),
+                json!({"command": "/opt/bin/npx", "args": ["gh@latest"]}),
+            ),
+        ]);
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(merged.servers.len(), 1);
+        assert_eq!(merged.servers[0].1["command"], "npx");
+        assert_eq!(merged.origins[0].0, "github");
+        assert_eq!(merged.origins[0].1, vec!["claude", "cursor"]);
+    }
+
+    #[test]
+    fn merge_reports_conflicting_variants() {
+        let merged = merge_discovered_servers(vec![
+            (
+                "claude".into(),
+                "db".into(),
+                json!({"command": "db-mcp", "env": {"DB": "a"}}),
+            ),
+            (
+                "cursor".into(),
+                "db".into(),
+                json!({"command": "db-mcp", "env": {"DB": "b"}}),
+            ),
+            ("cursor".into(), "other".into(), json!({"command": "other"})),
+        ]);
+        assert_eq!(merged.servers.len(), 1);
+        assert_eq!(merged.servers[0].0, "other");
+        assert_eq!(merged.conflicts.len(), 1);
+        let conflict = &merged.conflicts[0];
+        assert_eq!(conflict.name, "db");
+        let agents: Vec<&str> = conflict
+            .variants
+            .iter()
+            .map(|v| v.agent_id.as_str())
+            .collect();
+        assert_eq!(agents, vec!["claude", "cursor"]);
+    }
+}
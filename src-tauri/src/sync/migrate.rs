@@ -0,0 +1,289 @@
+use serde_json::Value;
+use std::path::Path;
+
+use crate::agent;
+use crate::core::Project;
+
+use super::engine::sync_project_without_autodetect;
+use super::helpers::add_unique;
+use super::mcp_dedup::{mcp_configs_equivalent, McpServerConflict, McpServerVariant};
+
+/// Outcome of [`migrate_cursor_global_to_project`].
+#[derive(Debug, Default, serde::Serialize)]
+pub struct CursorMigrationResult {
+    /// Servers copied into the registry and assigned to the project.
+    pub migrated: Vec<String>,
+    /// Requested servers that were not present in the global Cursor config.
+    pub missing: Vec<String>,
+    /// Requested servers whose name is already taken in the registry by a
+    /// different config.  Neither config is changed and the server stays in
+    /// the global Cursor config; the user picks one (saving it via
+    /// `save_mcp_server_config`) and migrates again.
+    pub conflicts: Vec<McpServerConflict>,
+    /// Backup of `~/.cursor/mcp.json` written before servers were removed
+    /// from it.  `None` when the global file was left untouched.
+    pub backup_path: Option<String>,
+    /// Why the project could not be re-synced after the migration.  The
+    /// servers are still assigned to the project.
+    pub sync_error: Option<String>,
+}
+
+/// Move selected servers from Cursor's user-level `~/.cursor/mcp.json` into
+/// Automatic's management for a single project.
+///
+/// Steps:
+/// 1. Save each requested server into the Automatic MCP server registry.
+///    A server whose name the registry already holds with an equivalent
+///    config is assigned as is; one with a different config is reported as
+///    a conflict and left alone.
+/// 2. Assign the servers to `project.mcp_servers` and persist the project.
+/// 3. When `remove_from_global` is set, strip the servers from the global
+///    Cursor config (after writing a timestamped backup) so that the project's
+///    own `.cursor/mcp.json` becomes the single source for them.
+/// 4. Re-sync the project if it has a directory and agents configured.
+pub fn migrate_cursor_global_to_project(
+    project: &mut Project,
+    server_names: &[String],
+    remove_from_global: bool,
+) -> Result<CursorMigrationResult, String> {
+    project.ensure_unlocked()?;
+    let global_path =
+        agent::global_mcp_config_path().ok_or("Could not determine home directory")?;
+
+    let mut result = migrate_from_global_config(
+        &global_path,
+        project,
+        server_names,
+        remove_from_global,
+        |name| {
+            crate::core::read_mcp_server_config(name)
+                .ok()
+                .and_then(|raw| serde_json::from_str(&raw).ok())
+        },
+        |name, config| {
+            let config_str = serde_json::to_string_pretty(config)
+                .map_err(|e| format!("Failed to serialise config for '{}': {}", name, e))?;
+            crate::core::save_mcp_server_config(name, &config_str)
+        },
+    )?;
+
+    if !result.migrated.is_empty() {
+        project.updated_at = chrono::Utc::now().to_rfc3339();
+        let project_str = serde_json::to_string_pretty(&project)
+            .map_err(|e| format!("Serialise error: {}", e))?;
+        crate::core::save_project(&project.name, &project_str)?;
+
+        if !project.directory.is_empty() && !project.agents.is_empty() {
+            if let Err(e) = sync_project_without_autodetect(project) {
+                result.sync_error = Some(e);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Path-injectable core of [`migrate_cursor_global_to_project`].  Does not
+/// persist the project — `existing_server` looks up a registry entry and
+/// `save_server` is called for each server new to the registry, so tests can
+/// observe registry reads and writes without touching the real registry.
+fn migrate_from_global_config(
+    global_path: &Path,
+    project: &mut Project,
+    server_names: &[String],
+    remove_from_global: bool,
+    existing_server: impl Fn(&str) -> Option<Value>,
+    mut save_server: impl FnMut(&str, &Value) -> Result<(), String>,
+) -> Result<CursorMigrationResult, String> {
+    let global = agent::discover_cursor_global_config(global_path);
+    let mut result = CursorMigrationResult::default();
+
+    for name in server_names {
+        let Some(config) = global.get(name) else {
+            result.missing.push(name.clone());
+            continue;
+        };
+        match existing_server(name) {
+            Some(current) if !mcp_configs_equivalent(&current, config) => {
+                result.conflicts.push(McpServerConflict {
+                    name: name.clone(),
+                    variants: vec![
+                        McpServerVariant {
+                            agent_id: "automatic".into(),
+                            config: current,
+                        },
+                        McpServerVariant {
+                            agent_id: "cursor".into(),
+                            config: config.clone(),
+                        },
+                    ],
+                });
+                continue;
+            }
+            Some(_) => {}
+            None => save_server(name, config)?,
+        }
+        add_unique(&mut project.mcp_servers, name);
+        project.disabled_mcp_servers.retain(|s| s != name);
+        result.migrated.push(name.clone());
+    }
+
+    if remove_from_global && !result.migrated.is_empty() {
+        result.backup_path =
+            agent::remove_servers_from_cursor_config(global_path, &result.migrated)?
+                .map(|p| p.display().to_string());
+    }
+
+    Ok(result)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write_global(dir: &Path) -> std::path::PathBuf {
+        let path = dir.join("mcp.json");
+        let config = json!({
+            "mcpServers": {
+                "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]},
+                "linear": {"url": "https://mcp.linear.app/sse"}
+            }
+        });
+        fs::write(&path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_migrate_copies_servers_and_keeps_global() {
+        let dir = tempdir().unwrap();
+        let path = write_global(dir.path());
+        let mut project = Project {
+            name: "demo".into(),
+            disabled_mcp_servers: vec!["github".into()],
+            ..Default::default()
+        };
+        let mut saved = Vec::new();
+
+        let result = migrate_from_global_config(
+            &path,
+            &mut project,
+            &["github".into(), "absent".into()],
+            false,
+            |_| None,
+            |name, _| {
+                saved.push(name.to_string());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result.migrated, vec!["github"]);
+        assert_eq!(result.missing, vec!["absent"]);
+        assert!(result.backup_path.is_none());
+        assert_eq!(saved, vec!["github"]);
+        assert_eq!(project.mcp_servers, vec!["github"]);
+        assert!(project.disabled_mcp_servers.is_empty());
+
+        let global = agent::discover_cursor_global_config(&path);
+        assert!(global.contains_key("github"));
+    }
+
+    #[test]
+    fn test_migrate_removes_from_global_with_backup() {
+        let dir = tempdir().unwrap();
+        let path = write_global(dir.path());
+        let mut project = Project {
+            name: "demo".into(),
+            mcp_servers: vec!["linear".into()],
+            ..Default::default()
+        };
+
+        let result = migrate_from_global_config(
+            &path,
+            &mut project,
+            &["linear".into()],
+            true,
+            |_| None,
+            |_, _| Ok(()),
+        )
+        .unwrap();
+
+        assert_eq!(project.mcp_servers, vec!["linear"]);
+        let backup = result.backup_path.expect("backup expected");
+        assert!(Path::new(&backup).exists());
+
+        let global = agent::discover_cursor_global_config(&path);
+        assert!(!global.contains_key("linear"));
+        assert!(global.contains_key("github"));
+    }
+
+    #[test]
+    fn test_migrate_reports_conflict_with_different_registry_entry() {
+        let dir = tempdir().unwrap();
+        let path = write_global(dir.path());
+        let mut project = Project {
+            name: "demo".into(),
+            ..Default::default()
+        };
+        let registry = json!({"command": "npx", "args": ["-y", "github-fork"]});
+        let mut saved = Vec::new();
+
+        let result = migrate_from_global_config(
+            &path,
+            &mut project,
+            &["github".into()],
+            true,
+            |name| (name == "github").then(|| registry.clone()),
+            |name, _| {
+                saved.push(name.to_string());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert!(result.migrated.is_empty());
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].name, "github");
+        assert_eq!(result.conflicts[0].variants[0].config, registry);
+        assert!(saved.is_empty());
+        assert!(project.mcp_servers.is_empty());
+        assert!(result.backup_path.is_none());
+
+        let global = agent::discover_cursor_global_config(&path);
+        assert!(global.contains_key("github"));
+    }
+
+    #[test]
+    fn test_migrate_reuses_equivalent_registry_entry() {
+        let dir = tempdir().unwrap();
+        let path = write_global(dir.path());
+        let mut project = Project {
+            name: "demo".into(),
+            ..Default::default()
+        };
+        let mut saved = Vec::new();
+
+        let result = migrate_from_global_config(
+            &path,
+            &mut project,
+            &["linear".into()],
+            false,
+            |_| Some(json!({"url": "https://mcp.linear.app/sse"})),
+            |name, _| {
+                saved.push(name.to_string());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result.migrated, vec!["linear"]);
+        assert!(result.conflicts.is_empty());
+        assert!(saved.is_empty());
+        assert_eq!(project.mcp_servers, vec!["linear"]);
+    }
+}
@@ -1,20 +1,77 @@
 mod autodetect;
 mod cleanup;
+mod discover;
 pub mod drift;
+mod drift_resolve;
 mod engine;
+mod generations;
+mod health;
 mod helpers;
+mod legacy_nexus;
+mod line_diff;
 mod local_skills;
+mod lock;
+mod mcp_dedup;
+mod migrate;
 mod rebuild;
+mod rule_recovery;
+mod skill_shadow;
+mod sync_all;
+mod trash;
+mod unsync;
+mod watch;
 
 // Re-export the public API so callers can use `sync::function_name` as before.
-pub use autodetect::autodetect_project_dependencies;
-pub use cleanup::{get_agent_cleanup_preview, remove_agent_from_project};
+pub use autodetect::{
+    autodetect_project_dependencies, autodetect_project_dependencies_with_conflicts,
+    autodetect_project_dependencies_with_progress, begin_autodetect, cancel_autodetect,
+    finish_autodetect, AutodetectCancel, AutodetectLimits, AutodetectProgress,
+};
+pub use cleanup::{
+    get_agent_cleanup_preview, remove_agent_from_project, AgentCleanupPreview, AgentRemoval,
+    UniqueMcpServers,
+};
+pub use discover::{
+    cancel_project_scan, scan_for_projects, DiscoveredProject, ProjectScan, DEFAULT_SCAN_MAX_DEPTH,
+};
 pub use drift::{
-    check_project_drift, collect_instruction_conflicts_pub, AgentDrift, DriftReport, DriftedFile,
-    InstructionFileConflict,
+    cached_drifted_agents, check_project_drift, collect_instruction_conflicts_pub, AgentDrift,
+    DriftReport, DriftedFile, InstructionFileConflict,
+};
+pub use drift_resolve::{resolve_drift, DriftResolution, DriftResolutionReport};
+pub use engine::{
+    agent_mcp_export, agent_mcp_snippet, discover_new_agent_mcp_configs, sync_project,
+    sync_project_with_report, sync_project_without_autodetect,
+    sync_project_without_autodetect_with_report, SyncReport,
 };
-pub use engine::{discover_new_agent_mcp_configs, sync_project, sync_project_without_autodetect};
+pub use generations::{
+    list_generations, record_generation, restore_generation, restored_generation,
+    GenerationManifest, GenerationRestoreReport, GenerationSummary, ManagedEntry, ManagedKind,
+    GENERATION_FORMAT,
+};
+pub use health::{check_project_health, HealthIssue, HealthSeverity, ProjectHealthReport};
+pub use helpers::project_rule_warnings;
+pub use legacy_nexus::{migrate_legacy_nexus, migrate_legacy_nexus_once, LegacyMigrationReport};
 pub use local_skills::{
     import_local_skill, read_local_skill, save_local_skill, sync_local_skills_across_agents,
 };
+pub use lock::{reactive_sync_project, ReactiveSync};
+pub(crate) use lock::{
+    record_locked_skip, sync_project_if_configured, sync_projects_referencing_skill,
+    sync_projects_referencing_skill_set, sync_projects_referencing_skills, with_each_project_mut,
+};
+pub(crate) use mcp_dedup::save_mcp_server_if_changed;
+pub use mcp_dedup::{
+    canonicalize_mcp_config, mcp_configs_equivalent, merge_discovered_servers, McpServerConflict,
+    McpServerVariant, MergedMcpServers,
+};
+pub use migrate::{migrate_cursor_global_to_project, CursorMigrationResult};
 pub use rebuild::{rebuild_instruction_snapshots, rebuild_project_state};
+pub use skill_shadow::{detect_skill_shadows, resolve_skill_shadow, SkillShadowResolution};
+pub use sync_all::{sync_all_projects, ProjectSyncResult, ProjectSyncStatus};
+pub use trash::{
+    list_removal_backups, prune_removal_backups, restore_agent_removal, AgentRemovalRestore,
+    RemovalBackupManifest, RemovalBackupSummary, TrashEntry, TRASH_RETENTION_DAYS,
+};
+pub use unsync::{unsync_project, unsync_project_preview, UNSYNCED_LOCK_REASON};
+pub use watch::{disable_drift_watch, enable_drift_watch, start_drift_watch, DriftWatchEvent};
@@ -6,6 +6,7 @@ use crate::agent;
 use crate::core::{self, CustomAgent, CustomCommand, Project, UserAgent};
 
 use super::autodetect::autodetect_inner;
+use super::mcp_dedup::save_mcp_server_if_changed;
 
 struct GlobalUserAgent {
     id: String,
@@ -29,14 +30,18 @@ pub fn rebuild_project_state(project: &Project) -> Result<Project, String> {
 
     let (mut rebuilt, discovered_servers) = autodetect_inner(&seed)?;
 
-    if project.mcp_servers.iter().any(|name| name == "automatic")
-        && !rebuilt.mcp_servers.iter().any(|name| name == "automatic")
+    let automatic = crate::core::AUTOMATIC_SERVER_NAME;
+    if project.mcp_servers.iter().any(|name| name == automatic)
+        && !rebuilt.mcp_servers.iter().any(|name| name == automatic)
     {
-        rebuilt.mcp_servers.push("automatic".to_string());
+        rebuilt.mcp_servers.push(automatic.to_string());
     }
 
-    for (name, config_str) in discovered_servers {
-        let _ = core::save_mcp_server_config(&name, &config_str);
+    for (name, config) in discovered_servers.servers {
+        if project.mcp_server_overrides.contains_key(&name) {
+            continue;
+        }
+        let _ = save_mcp_server_if_changed(&name, &config);
     }
 
     rebuilt.disabled_mcp_servers = project
@@ -400,7 +405,7 @@ pub fn rebuild_instruction_snapshots(project: &Project) {
         }
 
         if let Ok(user_content) = core::read_project_file(&project.directory, &filename) {
-            let _ = core::save_instruction_snapshot(&project.directory, &filename, &user_content);
+            let _ = core::save_instruction_snapshot(project, &filename, &user_content);
         }
     }
 }
@@ -0,0 +1,325 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::agent;
+use crate::core::{self, Project, RuleCandidate};
+
+// ── Rule recovery ────────────────────────────────────────────────────────────
+//
+// A directory synced by Automatic on another machine carries its rules only
+// as text inside each instruction file's managed rules section.  When the
+// local project has no rules configured, autodetection splits that text back
+// into rules: blocks matching a registry rule (after whitespace
+// normalisation) rebuild `file_rules`, and anything else becomes a
+// `RuleCandidate` the user can import rather than losing it on the next sync.
+
+/// Rebuild `file_rules` and `rule_candidates` from the rules sections of the
+/// project's instruction files.  Does nothing when the project already has
+/// rules configured.
+pub(super) fn recover_file_rules(project: &mut Project, dir: &Path) {
+    if project.file_rules.values().any(|rules| !rules.is_empty()) {
+        return;
+    }
+    let registry: Vec<(String, String)> = core::list_rules()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|rule| {
+            core::read_rule_content(&rule.id)
+                .ok()
+                .map(|content| (rule.id, content))
+        })
+        .collect();
+    recover_file_rules_with(project, dir, &registry);
+}
+
+fn recover_file_rules_with(project: &mut Project, dir: &Path, registry: &[(String, String)]) {
+    // Registry rules are recorded; the project's own custom rules are
+    // recognised but already stored inline.
+    let mut known: Vec<(Option<String>, String)> = registry
+        .iter()
+        .map(|(id, content)| (Some(id.clone()), normalize_rule_text(content)))
+        .collect();
+    known.extend(
+        project
+            .custom_rules
+            .iter()
+            .map(|rule| (None, normalize_rule_text(&rule.content))),
+    );
+    known.retain(|(_, content)| !content.is_empty());
+
+    let mut filenames: Vec<&'static str> = project
+        .agents
+        .iter()
+        .filter_map(|id| agent::from_id(id))
+        .map(|a| a.project_file_name())
+        .collect();
+    filenames.sort_unstable();
+    filenames.dedup();
+
+    let mut per_file: Vec<(String, Vec<String>)> = Vec::new();
+    for filename in filenames {
+        let Ok(content) = fs::read_to_string(dir.join(filename)) else {
+            continue;
+        };
+        let Some(body) = core::rules_section_body(&content) else {
+            continue;
+        };
+        let (matched, unknown) = split_rules_section(body, &known);
+        let attached: Vec<String> = matched
+            .into_iter()
+            .filter(|id| !core::is_mandatory_rule(id))
+            .collect();
+        per_file.push((filename.to_string(), attached));
+        for chunk in unknown {
+            add_candidate(project, registry, &chunk, filename);
+        }
+    }
+
+    let first = per_file.first().map(|(_, rules)| rules.clone());
+    match first {
+        Some(rules) if per_file.iter().all(|(_, r)| *r == rules) => {
+            if !rules.is_empty() {
+                project.file_rules.insert("_project".to_string(), rules);
+            }
+        }
+        _ => {
+            for (filename, rules) in per_file {
+                if !rules.is_empty() {
+                    project.file_rules.insert(filename, rules);
+                }
+            }
+        }
+    }
+}
+
+/// Line endings and trailing whitespace vary between checkouts; compare
+/// rule text without them.
+fn normalize_rule_text(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Split a rules section body into the known rules it contains, in order,
+/// and the chunks of text between them that match nothing.
+fn split_rules_section(
+    body: &str,
+    known: &[(Option<String>, String)],
+) -> (Vec<String>, Vec<String>) {
+    let body = normalize_rule_text(body);
+    let starts_block = |text: &str, content: &str| {
+        text.starts_with(content)
+            && (text.len() == content.len() || text[content.len()..].starts_with('\n'))
+    };
+
+    let mut matched = Vec::new();
+    let mut unknown = Vec::new();
+    let mut rest = body.as_str();
+    while !rest.is_empty() {
+        // Longest match first, so a rule whose text extends another wins.
+        let hit = known
+            .iter()
+            .filter(|(_, content)| starts_block(rest, content))
+            .max_by_key(|(_, content)| content.len());
+        if let Some((id, content)) = hit {
+            if let Some(id) = id {
+                if !matched.contains(id) {
+                    matched.push(id.clone());
+                }
+            }
+            rest = rest[content.len()..].trim_start();
+            continue;
+        }
+
+        let end = rest
+            .match_indices('\n')
+            .map(|(i, _)| i + 1)
+            .find(|&i| known.iter().any(|(_, c)| starts_block(&rest[i..], c)))
+            .unwrap_or(rest.len());
+        unknown.extend(split_at_headings(rest[..end].trim()));
+        rest = rest[end..].trim_start();
+    }
+    (matched, unknown)
+}
+
+/// Separate unrecognised text at top-level headings, one candidate each.
+fn split_at_headings(text: &str) -> Vec<String> {
+    let mut chunks: Vec<Vec<&str>> = Vec::new();
+    for line in text.lines() {
+        if line.starts_with("# ") || chunks.is_empty() {
+            chunks.push(Vec::new());
+        }
+        if let Some(chunk) = chunks.last_mut() {
+            chunk.push(line);
+        }
+    }
+    chunks
+        .into_iter()
+        .map(|lines| lines.join("\n").trim().to_string())
+        .filter(|chunk| !chunk.is_empty())
+        .collect()
+}
+
+fn add_candidate(project: &mut Project, registry: &[(String, String)], content: &str, file: &str) {
+    if let Some(existing) = project
+        .rule_candidates
+        .iter_mut()
+        .find(|c| normalize_rule_text(&c.content) == content)
+    {
+        if !existing.files.iter().any(|f| f == file) {
+            existing.files.push(file.to_string());
+        }
+        return;
+    }
+
+    let name = content
+        .lines()
+        .find_map(|line| line.strip_prefix('#'))
+        .map(|heading| heading.trim_start_matches('#').trim().to_string())
+        .filter(|heading| !heading.is_empty())
+        .unwrap_or_else(|| "Imported rule".to_string());
+    let taken: HashSet<&str> = registry
+        .iter()
+        .map(|(id, _)| id.as_str())
+        .chain(
+            project
+                .rule_candidates
+                .iter()
+                .map(|c| c.machine_name.as_str()),
+        )
+        .collect();
+    let base = machine_name_for(&name);
+    let machine_name = (1..)
+        .map(|n| {
+            if n == 1 {
+                base.clone()
+            } else {
+                format!("{}-{}", base, n)
+            }
+        })
+        .find(|candidate| !taken.contains(candidate.as_str()))
+        .unwrap_or(base);
+
+    project.rule_candidates.push(RuleCandidate {
+        machine_name,
+        name,
+        content: content.to_string(),
+        files: vec![file.to_string()],
+        dismissed: false,
+    });
+}
+
+/// Lowercase, hyphen-separated slug accepted by `is_valid_machine_name`.
+fn machine_name_for(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-').to_string();
+    let slug = if slug.starts_with(|c: char| c.is_ascii_lowercase()) {
+        slug
+    } else {
+        format!("rule-{}", slug).trim_end_matches('-').to_string()
+    };
+    if core::is_valid_machine_name(&slug) {
+        slug
+    } else {
+        "imported-rule".to_string()
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const STYLE: &str = "# Code Style\n\nPrefer early returns.\n";
+
+    fn registry() -> Vec<(String, String)> {
+        vec![
+            ("team-style".to_string(), STYLE.to_string()),
+            (
+                "automatic-service".to_string(),
+                "# Automatic\n\nUse the tools.".to_string(),
+            ),
+        ]
+    }
+
+    fn project(dir: &Path) -> Project {
+        Project {
+            name: "adopted".into(),
+            directory: dir.display().to_string(),
+            agents: vec!["claude".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn marker_section_rebuilds_known_rules_and_keeps_unknown_text() {
+        let dir = tempdir().unwrap();
+        // Written on another machine: CRLF line endings and trailing spaces.
+        let section = "<!-- automatic:rules:start -->\r\n# Automatic\r\n\r\nUse the tools.\r\n\r\n\
+            # Code Style  \r\n\r\nPrefer early returns.\r\n\r\n\
+            # Release Checklist\r\n\r\n- Bump the version.\r\n\
+            <!-- automatic:rules:end -->\r\n";
+        fs::write(
+            dir.path().join("CLAUDE.md"),
+            format!("# Project\r\n\r\n{}", section),
+        )
+        .unwrap();
+
+        let mut p = project(dir.path());
+        recover_file_rules_with(&mut p, dir.path(), &registry());
+
+        assert_eq!(p.file_rules["_project"], vec!["team-style"]);
+        assert_eq!(p.rule_candidates.len(), 1);
+        let candidate = &p.rule_candidates[0];
+        assert_eq!(candidate.machine_name, "release-checklist");
+        assert_eq!(candidate.name, "Release Checklist");
+        assert_eq!(
+            candidate.content,
+            "# Release Checklist\n\n- Bump the version."
+        );
+        assert_eq!(candidate.files, vec!["CLAUDE.md"]);
+    }
+
+    #[test]
+    fn configured_rules_are_left_alone() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("CLAUDE.md"),
+            "<!-- automatic:rules:start -->\n# Unknown\n<!-- automatic:rules:end -->\n",
+        )
+        .unwrap();
+        let mut p = project(dir.path());
+        p.file_rules
+            .insert("_project".into(), vec!["team-style".into()]);
+
+        recover_file_rules(&mut p, dir.path());
+        assert_eq!(p.file_rules["_project"], vec!["team-style"]);
+        assert!(p.rule_candidates.is_empty());
+    }
+
+    #[test]
+    fn candidate_names_avoid_registry_collisions() {
+        let mut p = Project::default();
+        add_candidate(&mut p, &registry(), "# Team Style!\nText", "AGENTS.md");
+        add_candidate(&mut p, &registry(), "# Team Style!\nText", "CLAUDE.md");
+        add_candidate(&mut p, &registry(), "No heading", "AGENTS.md");
+        assert_eq!(p.rule_candidates.len(), 2);
+        assert_eq!(p.rule_candidates[0].machine_name, "team-style-2");
+        assert_eq!(p.rule_candidates[0].files, vec!["AGENTS.md", "CLAUDE.md"]);
+        assert_eq!(p.rule_candidates[1].machine_name, "imported-rule");
+    }
+}
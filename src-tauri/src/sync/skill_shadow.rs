@@ -0,0 +1,355 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agent;
+use crate::core::{compute_content_hash, Project, SkillShadow};
+
+use super::engine::sync_project_without_autodetect;
+use super::helpers::load_skill_contents;
+
+/// How [`resolve_skill_shadow`] settles a conflicting project copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillShadowResolution {
+    /// Keep the project copy.  The skill is marked preserved and sync never
+    /// overwrites it.
+    KeepProjectCopy,
+    /// Push the project copy's `SKILL.md` into the global registry.
+    AdoptIntoRegistry,
+    /// Overwrite the project copy with the registry version.
+    UseGlobal,
+}
+
+/// Find selected global skills whose project copy differs from the registry.
+/// Read-only.
+pub fn detect_skill_shadows(project: &Project) -> Vec<SkillShadow> {
+    find_shadows(
+        project,
+        &load_skill_contents(&crate::core::selected_skill_names(project)),
+    )
+}
+
+/// Skills sync must leave untouched: unresolved conflicts and preserved
+/// project copies.
+pub(crate) fn held_back_skills(project: &Project) -> Vec<String> {
+    project
+        .skill_conflicts
+        .iter()
+        .map(|c| c.name.clone())
+        .chain(project.preserved_skills.iter().cloned())
+        .collect()
+}
+
+/// Settle a conflict reported in `project.skill_conflicts`, persist the
+/// project and re-sync it.  Returns the paths written or removed.
+pub fn resolve_skill_shadow(
+    project: &mut Project,
+    skill: &str,
+    resolution: SkillShadowResolution,
+) -> Result<Vec<String>, String> {
+    project.ensure_unlocked()?;
+    let registry = load_skill_contents(&[skill.to_string()]);
+    let mut touched = apply_resolution(project, skill, resolution, &registry, &|name, content| {
+        crate::core::save_skill(name, content)
+    })?;
+    touched.extend(sync_project_without_autodetect(project)?);
+    Ok(touched)
+}
+
+/// Directories sync writes skills into: the project hub first, then every
+/// agent's own skill directory.
+fn skill_base_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut bases = vec![dir.join(".agents").join("skills")];
+    for a in agent::all() {
+        for base in a.skill_dirs(dir) {
+            if !bases.contains(&base) {
+                bases.push(base);
+            }
+        }
+    }
+    bases
+}
+
+/// Every real (non-symlink) copy of `name` in the project whose `SKILL.md`
+/// is neither the registry content nor the content Automatic last wrote.
+fn shadow_copies(project: &Project, name: &str, registry_content: &str) -> Vec<(PathBuf, String)> {
+    let dir = PathBuf::from(&project.directory);
+    let written_hash = project.skill_hashes.get(name);
+    let mut copies = Vec::new();
+    for base in skill_base_dirs(&dir) {
+        let skill_dir = base.join(name);
+        let Ok(meta) = skill_dir.symlink_metadata() else {
+            continue;
+        };
+        // Symlinks point at the hub copy, which is checked itself.
+        if meta.file_type().is_symlink() || !meta.is_dir() {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(skill_dir.join("SKILL.md")) else {
+            continue;
+        };
+        if content == registry_content
+            || written_hash.is_some_and(|h| *h == compute_content_hash(&content))
+        {
+            continue;
+        }
+        copies.push((skill_dir, content));
+    }
+    copies
+}
+
+fn find_shadows(project: &Project, registry: &[(String, String)]) -> Vec<SkillShadow> {
+    if project.directory.is_empty() {
+        return vec![];
+    }
+    let dir = PathBuf::from(&project.directory);
+    registry
+        .iter()
+        .filter(|(name, _)| !project.preserved_skills.contains(name))
+        .filter_map(|(name, registry_content)| {
+            let (path, content) = shadow_copies(project, name, registry_content)
+                .into_iter()
+                .next()?;
+            Some(SkillShadow {
+                name: name.clone(),
+                path: path
+                    .strip_prefix(&dir)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                project_hash: compute_content_hash(&content),
+                registry_hash: compute_content_hash(registry_content),
+            })
+        })
+        .collect()
+}
+
+/// Apply `resolution` to the project state and files, without syncing.
+/// `save_to_registry` writes a skill's `SKILL.md` into the global registry.
+fn apply_resolution(
+    project: &mut Project,
+    skill: &str,
+    resolution: SkillShadowResolution,
+    registry: &[(String, String)],
+    save_to_registry: &dyn Fn(&str, &str) -> Result<(), String>,
+) -> Result<Vec<String>, String> {
+    let registry_content = registry
+        .iter()
+        .find(|(name, _)| name == skill)
+        .map(|(_, content)| content.as_str())
+        .ok_or_else(|| format!("Skill '{}' not found in the registry", skill))?;
+    let copies = shadow_copies(project, skill, registry_content);
+    let Some((_, project_content)) = copies.first().cloned() else {
+        return Err(format!("Skill '{}' has no conflicting project copy", skill));
+    };
+
+    let mut touched = Vec::new();
+    match resolution {
+        SkillShadowResolution::KeepProjectCopy => {
+            if !project.preserved_skills.iter().any(|s| s == skill) {
+                project.preserved_skills.push(skill.to_string());
+            }
+        }
+        SkillShadowResolution::AdoptIntoRegistry => {
+            save_to_registry(skill, &project_content)?;
+            project.preserved_skills.retain(|s| s != skill);
+            project
+                .skill_hashes
+                .insert(skill.to_string(), compute_content_hash(&project_content));
+        }
+        SkillShadowResolution::UseGlobal => {
+            for (skill_dir, _) in &copies {
+                fs::write(skill_dir.join("SKILL.md"), registry_content)
+                    .map_err(|e| format!("Failed to overwrite '{}': {}", skill_dir.display(), e))?;
+                touched.push(skill_dir.display().to_string());
+            }
+            project.preserved_skills.retain(|s| s != skill);
+            project
+                .skill_hashes
+                .insert(skill.to_string(), compute_content_hash(registry_content));
+        }
+    }
+
+    project.skill_conflicts.retain(|c| c.name != skill);
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    crate::core::persist_project(project)?;
+    Ok(touched)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use tempfile::tempdir;
+
+    const GLOBAL: &str = "# Terraform\n\nUse modules v2.\n";
+    const FORK: &str = "# Terraform\n\nUse modules v1 (pinned for this repo).\n";
+
+    fn registry() -> Vec<(String, String)> {
+        vec![("terraform-skill".to_string(), GLOBAL.to_string())]
+    }
+
+    /// A project whose hub holds an edited fork of `terraform-skill`.
+    fn forked(dir: &Path) -> Project {
+        let skill_dir = dir.join(".agents/skills/terraform-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), FORK).unwrap();
+        let mut project = Project {
+            name: "infra".into(),
+            directory: dir.display().to_string(),
+            agents: vec!["codex".into()],
+            skills: vec!["terraform-skill".into()],
+            unregistered: true,
+            ..Default::default()
+        };
+        project.skill_conflicts = find_shadows(&project, &registry());
+        project
+    }
+
+    fn hub_content(dir: &Path) -> String {
+        fs::read_to_string(dir.join(".agents/skills/terraform-skill/SKILL.md")).unwrap()
+    }
+
+    fn no_registry_writes(_: &str, _: &str) -> Result<(), String> {
+        panic!("registry must not be written")
+    }
+
+    #[test]
+    fn edited_project_copy_is_a_conflict_with_both_hashes() {
+        let dir = tempdir().unwrap();
+        let project = forked(dir.path());
+
+        assert_eq!(
+            project.skill_conflicts,
+            vec![SkillShadow {
+                name: "terraform-skill".into(),
+                path: ".agents/skills/terraform-skill".into(),
+                project_hash: compute_content_hash(FORK),
+                registry_hash: compute_content_hash(GLOBAL),
+            }]
+        );
+        assert_eq!(held_back_skills(&project), vec!["terraform-skill"]);
+    }
+
+    #[test]
+    fn stale_copy_written_by_automatic_is_not_a_conflict() {
+        let dir = tempdir().unwrap();
+        let mut project = forked(dir.path());
+        // FORK is what an earlier sync wrote; the registry has moved on since.
+        project
+            .skill_hashes
+            .insert("terraform-skill".into(), compute_content_hash(FORK));
+        assert!(find_shadows(&project, &registry()).is_empty());
+
+        // Symlinked agent directories are never reported separately.
+        project.skill_hashes.clear();
+        let claude_skills = dir.path().join(".claude/skills");
+        fs::create_dir_all(&claude_skills).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(
+            dir.path().join(".agents/skills/terraform-skill"),
+            claude_skills.join("terraform-skill"),
+        )
+        .unwrap();
+        assert_eq!(find_shadows(&project, &registry()).len(), 1);
+    }
+
+    #[test]
+    fn keep_project_copy_marks_skill_preserved() {
+        let dir = tempdir().unwrap();
+        let mut project = forked(dir.path());
+        let touched = apply_resolution(
+            &mut project,
+            "terraform-skill",
+            SkillShadowResolution::KeepProjectCopy,
+            &registry(),
+            &no_registry_writes,
+        )
+        .unwrap();
+
+        assert!(touched.is_empty());
+        assert!(project.skill_conflicts.is_empty());
+        assert_eq!(project.preserved_skills, vec!["terraform-skill"]);
+        assert_eq!(held_back_skills(&project), vec!["terraform-skill"]);
+        // Preserved skills are no longer reported as conflicts.
+        assert!(find_shadows(&project, &registry()).is_empty());
+        assert_eq!(hub_content(dir.path()), FORK);
+    }
+
+    #[test]
+    fn adopt_into_registry_pushes_project_content() {
+        let dir = tempdir().unwrap();
+        let mut project = forked(dir.path());
+        let saved = RefCell::new(Vec::new());
+        apply_resolution(
+            &mut project,
+            "terraform-skill",
+            SkillShadowResolution::AdoptIntoRegistry,
+            &registry(),
+            &|name, content| {
+                saved
+                    .borrow_mut()
+                    .push((name.to_string(), content.to_string()));
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            saved.into_inner(),
+            vec![("terraform-skill".to_string(), FORK.to_string())]
+        );
+        assert!(project.skill_conflicts.is_empty());
+        assert!(held_back_skills(&project).is_empty());
+        assert_eq!(hub_content(dir.path()), FORK);
+        // With the registry now holding FORK there is nothing to report.
+        let adopted = vec![("terraform-skill".to_string(), FORK.to_string())];
+        assert!(find_shadows(&project, &adopted).is_empty());
+    }
+
+    #[test]
+    fn use_global_overwrites_project_copy() {
+        let dir = tempdir().unwrap();
+        let mut project = forked(dir.path());
+        let touched = apply_resolution(
+            &mut project,
+            "terraform-skill",
+            SkillShadowResolution::UseGlobal,
+            &registry(),
+            &no_registry_writes,
+        )
+        .unwrap();
+
+        assert_eq!(touched.len(), 1);
+        assert_eq!(hub_content(dir.path()), GLOBAL);
+        assert!(project.skill_conflicts.is_empty());
+        assert!(held_back_skills(&project).is_empty());
+        assert_eq!(
+            project.skill_hashes["terraform-skill"],
+            compute_content_hash(GLOBAL)
+        );
+    }
+
+    #[test]
+    fn resolving_without_a_conflict_is_an_error() {
+        let dir = tempdir().unwrap();
+        let mut project = forked(dir.path());
+        fs::write(
+            dir.path().join(".agents/skills/terraform-skill/SKILL.md"),
+            GLOBAL,
+        )
+        .unwrap();
+        let err = apply_resolution(
+            &mut project,
+            "terraform-skill",
+            SkillShadowResolution::UseGlobal,
+            &registry(),
+            &no_registry_writes,
+        )
+        .unwrap_err();
+        assert!(err.contains("no conflicting project copy"), "{}", err);
+    }
+}
@@ -0,0 +1,226 @@
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{mpsc, Mutex};
+
+use crate::activity::{self, ActivityEvent};
+use crate::core::{self, Project};
+
+use super::engine::sync_project_without_autodetect_with_report;
+
+// ── Sync all projects ────────────────────────────────────────────────────────
+//
+// The explicit "sync everything now" operation.  Each registered project is
+// synced on its own, so one project that cannot be read or synced is reported
+// without stopping the rest.  Projects are synced a few at a time: some
+// project directories live on slow network mounts, and running them all at
+// once would only make every sync wait on the slowest disk.
+
+/// Upper bound on the number of projects synced at the same time.
+const MAX_PARALLEL_SYNCS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSyncStatus {
+    Synced,
+    /// The project has no usable directory or is locked; nothing was written.
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectSyncResult {
+    pub project: String,
+    pub status: ProjectSyncStatus,
+    /// Files written by the sync.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Servers the sync left out because their keychain placeholders could
+    /// not be resolved.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub secret_warnings: Vec<core::McpSecretWarning>,
+    /// Why the project was skipped, or the error it failed with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ProjectSyncResult {
+    fn new(project: &str, status: ProjectSyncStatus, reason: Option<String>) -> Self {
+        Self {
+            project: project.to_string(),
+            status,
+            files: Vec::new(),
+            secret_warnings: Vec::new(),
+            reason,
+        }
+    }
+}
+
+/// Sync every registered project without re-running autodetection, and
+/// report what happened to each, in registry order.
+pub fn sync_all_projects() -> Vec<ProjectSyncResult> {
+    let names = match core::list_projects() {
+        Ok(names) => names,
+        Err(e) => {
+            tracing::error!("Failed to list projects to sync: {}", e);
+            return Vec::new();
+        }
+    };
+    let projects: Vec<(String, Result<Project, String>)> = names
+        .into_iter()
+        .map(|name| {
+            let project = core::read_project(&name).and_then(|raw| {
+                serde_json::from_str::<Project>(&raw)
+                    .map_err(|e| format!("Invalid project data: {}", e))
+            });
+            (name, project)
+        })
+        .collect();
+
+    let parallelism = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_PARALLEL_SYNCS);
+    let results = run_bounded(projects, parallelism, |(name, project)| {
+        sync_one(&name, project)
+    });
+
+    // Logged afterwards, one at a time, rather than from the sync workers.
+    for result in &results {
+        if result.status == ProjectSyncStatus::Synced && !result.files.is_empty() {
+            let detail = format!(
+                "{} file{}",
+                result.files.len(),
+                if result.files.len() == 1 { "" } else { "s" }
+            );
+            activity::log(
+                &result.project,
+                ActivityEvent::ProjectSynced,
+                "Synced agent configs",
+                &detail,
+            );
+        }
+    }
+    results
+}
+
+fn sync_one(name: &str, project: Result<Project, String>) -> ProjectSyncResult {
+    use ProjectSyncStatus::*;
+
+    let mut project = match project {
+        Ok(project) => project,
+        Err(e) => return ProjectSyncResult::new(name, Failed, Some(e)),
+    };
+    if project.directory.is_empty() {
+        return ProjectSyncResult::new(name, Skipped, Some("No directory configured".into()));
+    }
+    if !Path::new(&project.directory).is_dir() {
+        return ProjectSyncResult::new(
+            name,
+            Skipped,
+            Some(format!("Directory '{}' does not exist", project.directory)),
+        );
+    }
+    if project.locked {
+        let reason = match project.locked_reason.as_deref() {
+            Some(why) if !why.is_empty() => format!("Locked ({})", why),
+            _ => "Locked".to_string(),
+        };
+        return ProjectSyncResult::new(name, Skipped, Some(reason));
+    }
+
+    match sync_project_without_autodetect_with_report(&mut project) {
+        Ok(report) => ProjectSyncResult {
+            files: report.files,
+            secret_warnings: report.secret_warnings,
+            ..ProjectSyncResult::new(name, Synced, None)
+        },
+        Err(e) => ProjectSyncResult::new(name, Failed, Some(e)),
+    }
+}
+
+/// Run `f` over `items` on at most `parallelism` threads, returning the
+/// results in the order of `items`.
+fn run_bounded<T, R, F>(items: Vec<T>, parallelism: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let total = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::scope(|scope| {
+        for _ in 0..parallelism.clamp(1, total.max(1)) {
+            let tx = tx.clone();
+            let (queue, f) = (&queue, &f);
+            scope.spawn(move || loop {
+                let next = queue.lock().ok().and_then(|mut items| items.next());
+                let Some((index, item)) = next else { break };
+                if tx.send((index, f(item))).is_err() {
+                    break;
+                }
+            });
+        }
+    });
+    drop(tx);
+
+    let mut results: Vec<(usize, R)> = rx.into_iter().collect();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+
+    #[test]
+    fn bounded_runner_keeps_order_and_limits_concurrency() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let results = run_bounded((0..12).collect(), 3, |n: u64| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(5 * (12 - n)));
+            running.fetch_sub(1, Ordering::SeqCst);
+            n * 2
+        });
+        assert_eq!(results, (0..12).map(|n| n * 2).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert!(run_bounded(Vec::<u64>::new(), 3, |n| n).is_empty());
+    }
+
+    #[test]
+    fn unusable_projects_are_skipped_or_failed_without_syncing() {
+        let dir = tempdir().unwrap();
+        let project = |directory: String, locked: bool| Project {
+            name: "web".into(),
+            directory,
+            agents: vec!["codex".into()],
+            locked,
+            locked_reason: locked.then(|| "release freeze".into()),
+            ..Default::default()
+        };
+
+        let result = sync_one("a", Ok(project(String::new(), false)));
+        assert_eq!(result.status, ProjectSyncStatus::Skipped);
+        assert_eq!(result.reason.as_deref(), Some("No directory configured"));
+
+        let missing = dir.path().join("gone").display().to_string();
+        let result = sync_one("b", Ok(project(missing, false)));
+        assert_eq!(result.status, ProjectSyncStatus::Skipped);
+        assert!(result.reason.unwrap().contains("does not exist"));
+
+        let locked = project(dir.path().display().to_string(), true);
+        let result = sync_one("c", Ok(locked));
+        assert_eq!(result.status, ProjectSyncStatus::Skipped);
+        assert_eq!(result.reason.as_deref(), Some("Locked (release freeze)"));
+        assert!(!dir.path().join("AGENTS.md").exists());
+
+        let result = sync_one("d", Err("Invalid project data: eof".into()));
+        assert_eq!(result.status, ProjectSyncStatus::Failed);
+    }
+}
@@ -0,0 +1,483 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::{self, Project, RemovalContext};
+
+use super::generations::{
+    is_generation_id, is_safe_relative, make_symlink, new_generation_id, prepare_path,
+    relative_path, ManagedKind,
+};
+use super::helpers::add_unique;
+
+// ── Agent Removal Trash ──────────────────────────────────────────────────────
+//
+// Before `remove_agent_from_project` deletes or strips anything, every path
+// it is about to touch is copied into
+//
+//   ~/.automatic/trash/<project>/<token>/manifest.json
+//   ~/.automatic/trash/<project>/<token>/files/<relative path>
+//
+// so the removal can be undone.  The token is a sortable timestamp.  Symlinks
+// (e.g. agent skill directories linked to the hub) are recorded by target
+// and never followed.  The manifest also keeps the project as it was, so a
+// restore puts the agent and the MCP servers that left with it back.
+// Entries older than `TRASH_RETENTION_DAYS` are pruned on startup.
+
+const TRASH_DIR: &str = "trash";
+const FILES_DIR: &str = "files";
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// How long removal backups are kept.
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// One file or link saved before an agent removal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// Relative to the project directory, `/`-separated.
+    pub path: String,
+    /// `File` or `Symlink`.
+    pub kind: ManagedKind,
+    /// Link target for `Symlink` entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// The `manifest.json` of one removal backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovalBackupManifest {
+    pub token: String,
+    /// RFC 3339.
+    pub created_at: String,
+    pub agent: String,
+    pub entries: Vec<TrashEntry>,
+    /// The project as it was before the removal.
+    pub project: Project,
+}
+
+/// A removal backup as listed for the user.
+#[derive(Debug, Clone, Serialize)]
+pub struct RemovalBackupSummary {
+    pub token: String,
+    pub created_at: String,
+    pub agent: String,
+    pub files: usize,
+}
+
+/// Result of [`restore_agent_removal`].
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentRemovalRestore {
+    pub token: String,
+    pub agent: String,
+    /// Paths written back, relative to the project directory.
+    pub restored: Vec<String>,
+}
+
+fn trash_root() -> Result<PathBuf, String> {
+    Ok(core::get_automatic_dir()?.join(TRASH_DIR))
+}
+
+fn project_trash(root: &Path, project: &Project) -> Result<PathBuf, String> {
+    if !core::is_valid_name(&project.name) {
+        return Err(format!("Invalid project name '{}'", project.name));
+    }
+    Ok(root.join(&project.name))
+}
+
+// ── Backing up ───────────────────────────────────────────────────────────────
+
+/// Copy `paths` (absolute paths inside the project directory, as listed by
+/// the cleanup preview) into the trash before `agent_id` is removed.
+/// Returns the restore token, or `None` when there was nothing to save.
+pub(crate) fn backup_agent_removal(
+    project: &Project,
+    agent_id: &str,
+    paths: &[String],
+) -> Result<Option<String>, String> {
+    backup_agent_removal_at(&trash_root()?, project, agent_id, paths)
+}
+
+fn backup_agent_removal_at(
+    root: &Path,
+    project: &Project,
+    agent_id: &str,
+    paths: &[String],
+) -> Result<Option<String>, String> {
+    let dir = PathBuf::from(&project.directory);
+    let mut found = BTreeMap::new();
+    for path in paths {
+        collect_entries(&dir, Path::new(path), &mut found);
+    }
+    if found.is_empty() {
+        return Ok(None);
+    }
+
+    let project_dir = project_trash(root, project)?;
+    fs::create_dir_all(&project_dir).map_err(|e| e.to_string())?;
+    let token = new_generation_id(&project_dir);
+    let token_dir = project_dir.join(&token);
+    let files = token_dir.join(FILES_DIR);
+
+    let mut entries = Vec::new();
+    for (rel, target) in found {
+        let entry = match target {
+            Some(target) => TrashEntry {
+                path: rel,
+                kind: ManagedKind::Symlink,
+                target: Some(target),
+            },
+            None => {
+                let dest = files.join(&rel);
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                fs::copy(dir.join(&rel), &dest)
+                    .map_err(|e| format!("Failed to back up {}: {}", rel, e))?;
+                TrashEntry {
+                    path: rel,
+                    kind: ManagedKind::File,
+                    target: None,
+                }
+            }
+        };
+        entries.push(entry);
+    }
+
+    let manifest = RemovalBackupManifest {
+        token: token.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        agent: agent_id.to_string(),
+        entries,
+        project: project.clone(),
+    };
+    let raw = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    fs::write(token_dir.join(MANIFEST_FILE), raw).map_err(|e| e.to_string())?;
+    Ok(Some(token))
+}
+
+/// Add the file or link at `path`, or every one below a directory, keyed by
+/// relative path with the link target for symlinks.  Symlinks are never
+/// followed.
+fn collect_entries(dir: &Path, path: &Path, found: &mut BTreeMap<String, Option<String>>) {
+    let Ok(meta) = path.symlink_metadata() else {
+        return;
+    };
+    let Some(rel) = relative_path(dir, path) else {
+        return;
+    };
+    if meta.file_type().is_symlink() {
+        if let Ok(target) = fs::read_link(path) {
+            found.insert(rel, Some(target.display().to_string()));
+        }
+    } else if meta.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                collect_entries(dir, &entry.path(), found);
+            }
+        }
+    } else if meta.is_file() {
+        found.insert(rel, None);
+    }
+}
+
+// ── Listing ──────────────────────────────────────────────────────────────────
+
+/// The removal backups of `project`, newest first.
+pub fn list_removal_backups(project: &Project) -> Result<Vec<RemovalBackupSummary>, String> {
+    let project_dir = project_trash(&trash_root()?, project)?;
+    Ok(read_manifests(&project_dir)
+        .into_iter()
+        .rev()
+        .map(|m| RemovalBackupSummary {
+            files: m.entries.len(),
+            token: m.token,
+            created_at: m.created_at,
+            agent: m.agent,
+        })
+        .collect())
+}
+
+/// Every manifest under `project_dir`, oldest first.  Unreadable ones are
+/// skipped.
+fn read_manifests(project_dir: &Path) -> Vec<RemovalBackupManifest> {
+    let Ok(entries) = fs::read_dir(project_dir) else {
+        return Vec::new();
+    };
+    let mut manifests: Vec<RemovalBackupManifest> = entries
+        .flatten()
+        .filter_map(|e| fs::read_to_string(e.path().join(MANIFEST_FILE)).ok())
+        .filter_map(|raw| serde_json::from_str::<RemovalBackupManifest>(&raw).ok())
+        .filter(|m| is_generation_id(&m.token))
+        .collect();
+    manifests.sort_by(|a, b| a.token.cmp(&b.token));
+    manifests
+}
+
+// ── Restoring ────────────────────────────────────────────────────────────────
+
+/// Undo the agent removal recorded under `token`: the saved files and links
+/// are written back over whatever is there now, the agent rejoins
+/// `project.agents` (and leaves `disabled_agents`), and MCP servers that left
+/// with it are re-added.  The project is persisted and the backup deleted.
+pub fn restore_agent_removal(
+    project: &mut Project,
+    token: &str,
+) -> Result<AgentRemovalRestore, String> {
+    restore_agent_removal_at(
+        &trash_root()?,
+        &RemovalContext::app_data().forced(),
+        project,
+        token,
+    )
+}
+
+fn restore_agent_removal_at(
+    root: &Path,
+    trash_context: &RemovalContext,
+    project: &mut Project,
+    token: &str,
+) -> Result<AgentRemovalRestore, String> {
+    project.ensure_unlocked()?;
+    if project.directory.is_empty() {
+        return Err("Project has no directory configured".into());
+    }
+    let dir = PathBuf::from(&project.directory);
+    if !dir.exists() {
+        return Err(format!("Directory '{}' does not exist", project.directory));
+    }
+    if !is_generation_id(token) {
+        return Err(format!("Invalid restore token '{}'", token));
+    }
+    let token_dir = project_trash(root, project)?.join(token);
+    let raw = fs::read_to_string(token_dir.join(MANIFEST_FILE))
+        .map_err(|_| format!("Removal backup '{}' not found", token))?;
+    let manifest: RemovalBackupManifest = serde_json::from_str(&raw)
+        .map_err(|e| format!("Invalid removal backup '{}': {}", token, e))?;
+    if let Some(bad) = manifest.entries.iter().find(|e| !is_safe_relative(&e.path)) {
+        return Err(format!(
+            "Removal backup '{}' has an invalid path '{}'",
+            token, bad.path
+        ));
+    }
+
+    let context = RemovalContext::project(&dir);
+    let mut restored = Vec::new();
+    for entry in &manifest.entries {
+        let path = dir.join(&entry.path);
+        prepare_path(&dir, &path, &context)?;
+        match entry.kind {
+            ManagedKind::Symlink => {
+                let target = PathBuf::from(entry.target.as_deref().unwrap_or_default());
+                core::remove_guarded(&path, &context)?;
+                make_symlink(&target, &path)?;
+            }
+            _ => {
+                fs::copy(token_dir.join(FILES_DIR).join(&entry.path), &path)
+                    .map_err(|e| format!("Failed to restore {}: {}", entry.path, e))?;
+            }
+        }
+        restored.push(entry.path.clone());
+    }
+
+    rejoin_agent(project, &manifest.project, &manifest.agent);
+    core::persist_project(project)?;
+    core::remove_guarded(&token_dir, trash_context)?;
+
+    Ok(AgentRemovalRestore {
+        token: manifest.token,
+        agent: manifest.agent,
+        restored,
+    })
+}
+
+/// Put `agent_id` and the MCP servers it took with it back into `project`,
+/// using `before`, the project as it was before the removal.  Changes made
+/// since the removal are kept.
+fn rejoin_agent(project: &mut Project, before: &Project, agent_id: &str) {
+    if !project.agents.iter().any(|a| a == agent_id) {
+        let position = before
+            .agents
+            .iter()
+            .position(|a| a == agent_id)
+            .unwrap_or(project.agents.len())
+            .min(project.agents.len());
+        project.agents.insert(position, agent_id.to_string());
+    }
+    project.disabled_agents.retain(|a| a != agent_id);
+
+    for server in &before.mcp_servers {
+        if add_unique(&mut project.mcp_servers, server)
+            && before.disabled_mcp_servers.contains(server)
+        {
+            add_unique(&mut project.disabled_mcp_servers, server);
+        }
+    }
+    for (server, origins) in &before.mcp_server_origins {
+        if origins.iter().any(|a| a == agent_id) {
+            let current = project
+                .mcp_server_origins
+                .entry(server.clone())
+                .or_default();
+            add_unique(current, agent_id);
+        }
+    }
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+}
+
+// ── Retention ────────────────────────────────────────────────────────────────
+
+/// Delete removal backups older than [`TRASH_RETENTION_DAYS`].  Run on
+/// startup.  Returns how many were deleted.
+pub fn prune_removal_backups() -> Result<usize, String> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(TRASH_RETENTION_DAYS);
+    prune_removal_backups_at(&trash_root()?, cutoff, &RemovalContext::app_data().forced())
+}
+
+fn prune_removal_backups_at(
+    root: &Path,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    context: &RemovalContext,
+) -> Result<usize, String> {
+    let Ok(projects) = fs::read_dir(root) else {
+        return Ok(0);
+    };
+    let mut pruned = 0;
+    for project_dir in projects.flatten().map(|e| e.path()) {
+        let Ok(backups) = fs::read_dir(&project_dir) else {
+            continue;
+        };
+        for backup in backups.flatten().map(|e| e.path()) {
+            if created_at(&backup).is_some_and(|created| created < cutoff) {
+                core::remove_guarded(&backup, context)?;
+                pruned += 1;
+            }
+        }
+        // Drop the project's folder once its last backup is gone.
+        let _ = fs::remove_dir(&project_dir);
+    }
+    Ok(pruned)
+}
+
+/// When the backup in `backup_dir` was made: the manifest's timestamp, or
+/// the directory's modification time when the manifest is unreadable.
+fn created_at(backup_dir: &Path) -> Option<chrono::DateTime<chrono::Utc>> {
+    let from_manifest = fs::read_to_string(backup_dir.join(MANIFEST_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<RemovalBackupManifest>(&raw).ok())
+        .and_then(|m| chrono::DateTime::parse_from_rfc3339(&m.created_at).ok())
+        .map(|t| t.with_timezone(&chrono::Utc));
+    from_manifest.or_else(|| {
+        let modified = backup_dir.metadata().ok()?.modified().ok()?;
+        Some(modified.into())
+    })
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn project(dir: &Path) -> Project {
+        Project {
+            name: "web".into(),
+            directory: dir.display().to_string(),
+            agents: vec!["claude".into(), "codex".into()],
+            mcp_servers: vec!["github".into(), "notes".into()],
+            disabled_mcp_servers: vec!["github".into()],
+            mcp_server_origins: [("github".to_string(), vec!["codex".to_string()])].into(),
+            unregistered: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn removal_backup_restores_files_links_and_project_entries() {
+        let dir = tempdir().unwrap();
+        let root = tempdir().unwrap();
+        let codex = dir.path().join(".codex");
+        fs::create_dir_all(codex.join("skills")).unwrap();
+        fs::write(codex.join("config.toml"), "[mcp_servers.github]\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("../../.agents/skills/review", codex.join("skills/review"))
+            .unwrap();
+
+        let before = project(dir.path());
+        let token = backup_agent_removal_at(
+            root.path(),
+            &before,
+            "codex",
+            &[codex.display().to_string()],
+        )
+        .unwrap()
+        .expect("files were backed up");
+
+        // The removal: files gone, agent and its unique server dropped.
+        fs::remove_dir_all(&codex).unwrap();
+        let mut after = Project {
+            agents: vec!["claude".into()],
+            mcp_servers: vec!["notes".into()],
+            disabled_mcp_servers: vec![],
+            mcp_server_origins: Default::default(),
+            disabled_agents: vec!["codex".into()],
+            ..before.clone()
+        };
+
+        let trash = RemovalContext::temp().forced();
+        let report = restore_agent_removal_at(root.path(), &trash, &mut after, &token).unwrap();
+        assert_eq!(report.agent, "codex");
+        assert_eq!(
+            fs::read_to_string(codex.join("config.toml")).unwrap(),
+            "[mcp_servers.github]\n"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(codex.join("skills/review")).unwrap(),
+            PathBuf::from("../../.agents/skills/review")
+        );
+        assert_eq!(after.agents, vec!["claude", "codex"]);
+        assert!(after.disabled_agents.is_empty());
+        assert_eq!(after.mcp_servers, vec!["notes", "github"]);
+        assert_eq!(after.disabled_mcp_servers, vec!["github"]);
+        assert_eq!(after.mcp_server_origins["github"], vec!["codex"]);
+
+        // A token restores once.
+        assert!(restore_agent_removal_at(root.path(), &trash, &mut after, &token).is_err());
+        assert!(restore_agent_removal_at(root.path(), &trash, &mut after, "../x").is_err());
+    }
+
+    #[test]
+    fn nothing_to_remove_means_no_backup() {
+        let dir = tempdir().unwrap();
+        let root = tempdir().unwrap();
+        let missing = dir.path().join(".codex").display().to_string();
+        let token = backup_agent_removal_at(root.path(), &project(dir.path()), "codex", &[missing]);
+        assert_eq!(token.unwrap(), None);
+        assert!(!root.path().join("web").exists());
+    }
+
+    #[test]
+    fn old_backups_are_pruned() {
+        let dir = tempdir().unwrap();
+        let root = tempdir().unwrap();
+        fs::write(dir.path().join("opencode.json"), "{}").unwrap();
+        let file = dir.path().join("opencode.json").display().to_string();
+        let p = project(dir.path());
+        backup_agent_removal_at(root.path(), &p, "opencode", &[file]).unwrap();
+
+        let context = RemovalContext::temp().forced();
+        let long_ago = chrono::Utc::now() - chrono::Duration::days(TRASH_RETENTION_DAYS);
+        assert_eq!(
+            prune_removal_backups_at(root.path(), long_ago, &context).unwrap(),
+            0
+        );
+        let soon = chrono::Utc::now() + chrono::Duration::seconds(1);
+        assert_eq!(
+            prune_removal_backups_at(root.path(), soon, &context).unwrap(),
+            1
+        );
+        assert!(!root.path().join("web").exists());
+    }
+}
@@ -0,0 +1,192 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::{self, Project, RemovalContext, RemovalOutcome};
+
+use super::cleanup::{agent_cleanup_preview, cleanup_agent_files};
+use super::helpers::add_unique;
+
+// ── Unsync a project ─────────────────────────────────────────────────────────
+//
+// Takes everything Automatic wrote out of a project directory — agent MCP
+// entries, skills, rule files, managed instruction sections and the
+// `.automatic/` folder — while the project stays in the local registry.
+// The full config moves into `~/.automatic/projects/<name>.json` (as with
+// `store_config_in_repo: false`), and the project is locked so registry
+// changes do not sync the files straight back.  Unlocking and syncing
+// restores them.
+
+/// Lock reason recorded on a project that was unsynced.
+pub const UNSYNCED_LOCK_REASON: &str = "Unsynced: Automatic files removed from the repository";
+
+/// Remove every Automatic-managed file from `project`'s directory, keep the
+/// project in the registry, and return the paths removed or modified.
+pub fn unsync_project(project: &mut Project) -> Result<Vec<String>, String> {
+    project.ensure_unlocked()?;
+    let dir = unsync_dir(project)?;
+
+    let mut removed = Vec::new();
+    for agent_id in &project.agents {
+        for path in cleanup_agent_files(project, agent_id, &dir, &[]) {
+            add_unique(&mut removed, &path);
+        }
+    }
+    for path in strip_instruction_files(project, &dir) {
+        add_unique(&mut removed, &path);
+    }
+
+    // Move the config into the home registry before `.automatic/` goes;
+    // this also carries snapshots and sync generations to
+    // `~/.automatic/projects-data/<name>/`.
+    project.store_config_in_repo = Some(false);
+    project.locked = true;
+    project.locked_reason = Some(UNSYNCED_LOCK_REASON.to_string());
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    core::persist_project(project)?;
+
+    let automatic_dir = dir.join(".automatic");
+    if automatic_dir.exists() {
+        let outcome = core::remove_guarded(&automatic_dir, &RemovalContext::project(&dir))?;
+        if let RemovalOutcome::Removed { .. } = outcome {
+            add_unique(&mut removed, &automatic_dir.display().to_string());
+        }
+    }
+
+    Ok(removed)
+}
+
+/// The paths [`unsync_project`] would remove or modify.  Read-only.
+pub fn unsync_project_preview(project: &Project) -> Result<Vec<String>, String> {
+    let dir = unsync_dir(project)?;
+
+    let mut preview = Vec::new();
+    for agent_id in &project.agents {
+        for path in agent_cleanup_preview(project, agent_id, &dir, &[]) {
+            add_unique(&mut preview, &path);
+        }
+    }
+    for filename in core::instruction_file_names(project) {
+        let path = dir.join(&filename);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if strip_managed_sections(&content) != content {
+            add_unique(&mut preview, &path.display().to_string());
+        }
+    }
+    let automatic_dir = dir.join(".automatic");
+    if automatic_dir.exists() {
+        add_unique(&mut preview, &automatic_dir.display().to_string());
+    }
+    Ok(preview)
+}
+
+fn unsync_dir(project: &Project) -> Result<PathBuf, String> {
+    if project.unregistered {
+        return Err(format!(
+            "Project '{}' is not registered, so there is nowhere to keep its config",
+            project.name
+        ));
+    }
+    if project.directory.is_empty() {
+        return Err("Project has no directory configured".into());
+    }
+    let dir = PathBuf::from(&project.directory);
+    if !dir.exists() {
+        return Err(format!("Directory '{}' does not exist", project.directory));
+    }
+    Ok(dir)
+}
+
+/// Strip the managed skills and rules sections from every instruction file
+/// the project's agents read.  User content stays; a file left with nothing
+/// but whitespace was written entirely by Automatic and is deleted.
+fn strip_instruction_files(project: &Project, dir: &Path) -> Vec<String> {
+    let mut touched = Vec::new();
+    for filename in core::instruction_file_names(project) {
+        let path = dir.join(&filename);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let stripped = strip_managed_sections(&content);
+        if stripped == content {
+            continue;
+        }
+        let result = if stripped.trim().is_empty() {
+            core::remove_guarded(&path, &RemovalContext::project(dir)).map(|_| ())
+        } else {
+            fs::write(&path, stripped).map_err(|e| e.to_string())
+        };
+        match result {
+            Ok(()) => touched.push(path.display().to_string()),
+            Err(e) => tracing::error!(
+                "Failed to strip managed sections from {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+    touched
+}
+
+fn strip_managed_sections(content: &str) -> String {
+    core::strip_rules_section_pub(&core::strip_managed_section_pub(content))
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn instruction_files_keep_user_content() {
+        let dir = tempdir().unwrap();
+        let project = Project {
+            name: "web".into(),
+            directory: dir.path().display().to_string(),
+            agents: vec!["claude".into(), "codex".into()],
+            ..Default::default()
+        };
+        fs::write(
+            dir.path().join("AGENTS.md"),
+            "# Team notes\n\n<!-- automatic:rules:start -->\nrule\n<!-- automatic:rules:end -->\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("CLAUDE.md"),
+            "<!-- automatic:skills:start -->\nskills\n<!-- automatic:skills:end -->\n",
+        )
+        .unwrap();
+
+        let touched = strip_instruction_files(&project, dir.path());
+        assert_eq!(touched.len(), 2);
+        let agents_md = fs::read_to_string(dir.path().join("AGENTS.md")).unwrap();
+        assert!(agents_md.starts_with("# Team notes"));
+        assert!(!agents_md.contains("automatic:rules"));
+        assert!(!dir.path().join("CLAUDE.md").exists());
+
+        // Nothing managed is left, so a second pass touches nothing.
+        assert!(strip_instruction_files(&project, dir.path()).is_empty());
+    }
+
+    #[test]
+    fn unregistered_or_locked_projects_are_refused() {
+        let dir = tempdir().unwrap();
+        let mut project = Project {
+            name: "web".into(),
+            directory: dir.path().display().to_string(),
+            agents: vec!["codex".into()],
+            unregistered: true,
+            ..Default::default()
+        };
+        assert!(unsync_project_preview(&project).is_err());
+        assert!(unsync_project(&mut project).is_err());
+
+        project.unregistered = false;
+        project.locked = true;
+        assert!(unsync_project(&mut project).is_err());
+        assert!(unsync_project_preview(&project).is_ok());
+    }
+}
@@ -0,0 +1,431 @@
+// ── Drift Watch ──────────────────────────────────────────────────────────────
+//
+// Watches the agent config files of projects the user opted in to
+// (`Settings::drift_watch_projects`) and reports hand edits without waiting
+// for an explicit drift check.  Changes are debounced per project, and a
+// drift check confirms them before anything is reported, so Automatic's own
+// writes (including a sync run by another process) stay quiet.  Syncs in
+// this process also pause the project's watch while they write.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::Map;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::agent;
+use crate::core::{self, Project};
+
+use super::drift::check_project_drift;
+
+/// How long a project's files must stay quiet before a change is reported.
+const DEBOUNCE: Duration = Duration::from_millis(750);
+
+/// Events arriving this soon after a sync finished are the sync's own
+/// writes being delivered late.
+const SETTLE: Duration = Duration::from_millis(500);
+
+/// Payload of the `project-drift-detected` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DriftWatchEvent {
+    pub project: String,
+    /// Changed paths, relative to the project directory.
+    pub paths: Vec<String>,
+}
+
+enum Message {
+    Changed(String, String),
+    Pause(String),
+    Resume(String),
+}
+
+#[derive(Default)]
+struct WatchState {
+    sender: Option<Sender<Message>>,
+    /// One watcher per watched project; dropping it stops the watch.
+    projects: HashMap<String, RecommendedWatcher>,
+}
+
+fn state() -> &'static Mutex<WatchState> {
+    static STATE: OnceLock<Mutex<WatchState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(WatchState::default()))
+}
+
+/// Start the debounce thread and watch every project listed in the
+/// settings.  `emit` is called from that thread for each confirmed change.
+pub fn start_drift_watch<F>(emit: F)
+where
+    F: Fn(DriftWatchEvent) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    match state().lock() {
+        Ok(mut state) if state.sender.is_none() => state.sender = Some(tx),
+        _ => return,
+    }
+    std::thread::spawn(move || {
+        let mut debouncer = Debouncer::default();
+        loop {
+            let received = match debouncer.next_deadline() {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            let now = Instant::now();
+            match received {
+                Ok(Message::Changed(project, path)) => debouncer.changed(&project, path, now),
+                Ok(Message::Pause(project)) => debouncer.pause(&project),
+                Ok(Message::Resume(project)) => debouncer.resume(&project, now),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            for (project, paths) in debouncer.take_due(Instant::now()) {
+                if has_drifted(&project) {
+                    emit(DriftWatchEvent { project, paths });
+                }
+            }
+        }
+    });
+
+    let names = core::read_settings()
+        .map(|s| s.drift_watch_projects)
+        .unwrap_or_default();
+    for name in names {
+        if let Err(e) = load_and_watch(&name) {
+            tracing::warn!("drift watch for '{}' not started: {}", name, e);
+        }
+    }
+}
+
+/// Watch `name` for drift and remember the choice in the settings.
+pub fn enable_drift_watch(name: &str) -> Result<(), String> {
+    load_and_watch(name)?;
+    let mut settings = core::read_settings()?;
+    if !settings.drift_watch_projects.iter().any(|p| p == name) {
+        settings.drift_watch_projects.push(name.to_string());
+        core::write_settings(&settings)?;
+    }
+    Ok(())
+}
+
+/// Stop watching `name` and remove it from the settings.
+pub fn disable_drift_watch(name: &str) -> Result<(), String> {
+    if let Ok(mut state) = state().lock() {
+        state.projects.remove(name);
+    }
+    let mut settings = core::read_settings()?;
+    if settings.drift_watch_projects.iter().any(|p| p == name) {
+        settings.drift_watch_projects.retain(|p| p != name);
+        core::write_settings(&settings)?;
+    }
+    Ok(())
+}
+
+/// Suspends a project's drift watch until dropped.  Returned by
+/// [`pause_drift_watch`].
+pub(crate) struct DriftWatchPause {
+    /// The project and its config paths, when it is being watched.
+    watched: Option<(Project, Vec<PathBuf>)>,
+}
+
+/// Ignore changes to `project` until the returned guard is dropped, so a
+/// sync does not report its own writes.  Dropping the guard re-arms the
+/// watch, picking up config directories the sync created.
+pub(crate) fn pause_drift_watch(project: &Project) -> DriftWatchPause {
+    let paused = state()
+        .lock()
+        .ok()
+        .is_some_and(|state| match &state.sender {
+            Some(tx) if state.projects.contains_key(&project.name) => {
+                tx.send(Message::Pause(project.name.clone())).is_ok()
+            }
+            _ => false,
+        });
+    DriftWatchPause {
+        watched: paused.then(|| (project.clone(), watch_targets(project))),
+    }
+}
+
+impl Drop for DriftWatchPause {
+    fn drop(&mut self) {
+        let Some((project, targets)) = self.watched.take() else {
+            return;
+        };
+        // Re-arm, unless the watch was disabled while the sync ran.
+        let still_watched = state()
+            .lock()
+            .is_ok_and(|state| state.projects.contains_key(&project.name));
+        if still_watched {
+            if let Err(e) = watch(&project, targets) {
+                tracing::warn!("drift watch for '{}' not re-armed: {}", project.name, e);
+            }
+        }
+        if let Ok(state) = state().lock() {
+            if let Some(tx) = &state.sender {
+                let _ = tx.send(Message::Resume(project.name));
+            }
+        }
+    }
+}
+
+fn load_and_watch(name: &str) -> Result<(), String> {
+    let raw = core::read_project(name)?;
+    let project: Project =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid project data: {}", e))?;
+    let targets = watch_targets(&project);
+    watch(&project, targets)
+}
+
+/// (Re)start watching `project`, replacing any previous watch.  The project
+/// directory itself is watched non-recursively; each top-level directory
+/// holding config paths (`.claude`, `.cursor`, ...) recursively.
+fn watch(project: &Project, targets: Vec<PathBuf>) -> Result<(), String> {
+    if project.directory.is_empty() {
+        return Err("Project has no directory configured".into());
+    }
+    let dir = PathBuf::from(&project.directory);
+    if !dir.is_dir() {
+        return Err(format!("Directory '{}' does not exist", project.directory));
+    }
+    let mut state = state().lock().map_err(|e| e.to_string())?;
+    let Some(tx) = state.sender.clone() else {
+        return Err("The drift watcher is not running".into());
+    };
+
+    let name = project.name.clone();
+    let roots = [
+        dir.clone(),
+        dir.canonicalize().unwrap_or_else(|_| dir.clone()),
+    ];
+    let filter = targets.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if event.kind.is_access() {
+            return;
+        }
+        for path in &event.paths {
+            let Some(rel) = roots.iter().find_map(|root| path.strip_prefix(root).ok()) else {
+                continue;
+            };
+            if is_watched(rel, &filter) {
+                let rel = rel.to_string_lossy().replace('\\', "/");
+                let _ = tx.send(Message::Changed(name.clone(), rel));
+            }
+        }
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch '{}': {}", dir.display(), e))?;
+    let mut top_dirs: Vec<PathBuf> = targets
+        .iter()
+        .filter(|t| t.components().count() > 1)
+        .filter_map(|t| t.components().next())
+        .map(|c| dir.join(c))
+        .collect();
+    top_dirs.sort();
+    top_dirs.dedup();
+    for top in top_dirs.iter().filter(|d| d.is_dir()) {
+        if let Err(e) = watcher.watch(top, RecursiveMode::Recursive) {
+            tracing::error!("failed to watch '{}': {}", top.display(), e);
+        }
+    }
+
+    state.projects.insert(project.name.clone(), watcher);
+    Ok(())
+}
+
+/// The config paths sync writes for `project`'s agents: instruction files,
+/// MCP configs, skill and agent directories, relative to the project
+/// directory.
+fn watch_targets(project: &Project) -> Vec<PathBuf> {
+    let dir = PathBuf::from(&project.directory);
+    let mut targets = vec![PathBuf::from(".agents").join("skills")];
+    let mut add = |target: PathBuf| {
+        if !target.as_os_str().is_empty() && !targets.contains(&target) {
+            targets.push(target);
+        }
+    };
+    for agent_instance in project.agents.iter().filter_map(|id| agent::from_id(id)) {
+        if agent_instance.capabilities().instructions {
+            add(PathBuf::from(agent_instance.project_file_name()));
+        }
+        let config_dirs = agent_instance
+            .skill_dirs(&dir)
+            .into_iter()
+            .chain(agent_instance.agents_dir(&dir));
+        for config_dir in config_dirs {
+            if let Ok(rel) = config_dir.strip_prefix(&dir) {
+                add(rel.to_path_buf());
+            }
+        }
+        // Like the drift check, find the MCP config by writing an empty one
+        // into a scratch directory.
+        if let Ok(tmp) = tempfile::tempdir() {
+            if let Ok(written) = agent_instance.write_mcp_config(tmp.path(), &Map::new()) {
+                if let Ok(rel) = Path::new(&written).strip_prefix(tmp.path()) {
+                    add(rel.to_path_buf());
+                }
+            }
+        }
+    }
+    targets
+}
+
+/// Whether `rel` is, or lies below, one of `targets`.
+fn is_watched(rel: &Path, targets: &[PathBuf]) -> bool {
+    targets.iter().any(|t| rel.starts_with(t))
+}
+
+fn has_drifted(name: &str) -> bool {
+    core::read_project(name)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Project>(&raw).ok())
+        .and_then(|project| check_project_drift(&project, false).ok())
+        .is_some_and(|report| report.drifted)
+}
+
+/// Collects changed paths per project until the project has been quiet for
+/// [`DEBOUNCE`].  Paused projects drop their changes.
+#[derive(Default)]
+struct Debouncer {
+    pending: HashMap<String, (BTreeSet<String>, Instant)>,
+    paused: HashMap<String, usize>,
+    quiet_until: HashMap<String, Instant>,
+}
+
+impl Debouncer {
+    fn changed(&mut self, project: &str, path: String, now: Instant) {
+        if self.paused.get(project).is_some_and(|n| *n > 0)
+            || self.quiet_until.get(project).is_some_and(|t| now < *t)
+        {
+            return;
+        }
+        let entry = self
+            .pending
+            .entry(project.to_string())
+            .or_insert_with(|| (BTreeSet::new(), now));
+        entry.0.insert(path);
+        entry.1 = now;
+    }
+
+    fn pause(&mut self, project: &str) {
+        *self.paused.entry(project.to_string()).or_default() += 1;
+        self.pending.remove(project);
+    }
+
+    fn resume(&mut self, project: &str, now: Instant) {
+        if let Some(n) = self.paused.get_mut(project) {
+            *n = n.saturating_sub(1);
+            if *n == 0 {
+                self.paused.remove(project);
+            }
+        }
+        self.quiet_until.insert(project.to_string(), now + SETTLE);
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.pending
+            .values()
+            .map(|(_, last)| *last + DEBOUNCE)
+            .min()
+    }
+
+    /// Remove and return the projects that have been quiet long enough.
+    fn take_due(&mut self, now: Instant) -> Vec<(String, Vec<String>)> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, (_, last))| now >= *last + DEBOUNCE)
+            .map(|(project, _)| project.clone())
+            .collect();
+        let mut out: Vec<(String, Vec<String>)> = due
+            .into_iter()
+            .filter_map(|project| {
+                let (paths, _) = self.pending.remove(&project)?;
+                Some((project, paths.into_iter().collect()))
+            })
+            .collect();
+        out.sort();
+        out
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_are_reported_once_the_project_is_quiet() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::default();
+        debouncer.changed("app", ".mcp.json".into(), start);
+        debouncer.changed("app", ".mcp.json".into(), start + DEBOUNCE / 2);
+        debouncer.changed("app", "CLAUDE.md".into(), start + DEBOUNCE / 2);
+
+        assert!(debouncer.take_due(start + DEBOUNCE).is_empty());
+        assert_eq!(
+            debouncer.next_deadline(),
+            Some(start + DEBOUNCE / 2 + DEBOUNCE)
+        );
+        assert_eq!(
+            debouncer.take_due(start + 2 * DEBOUNCE),
+            vec![(
+                "app".to_string(),
+                vec![".mcp.json".to_string(), "CLAUDE.md".to_string()]
+            )]
+        );
+        assert_eq!(debouncer.next_deadline(), None);
+    }
+
+    #[test]
+    fn paused_projects_ignore_their_own_writes() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::default();
+        debouncer.changed("app", "AGENTS.md".into(), start);
+        debouncer.pause("app");
+        debouncer.changed("app", ".mcp.json".into(), start);
+        debouncer.changed("other", ".mcp.json".into(), start);
+        debouncer.resume("app", start);
+        // Late delivery of the sync's writes.
+        debouncer.changed("app", ".mcp.json".into(), start + SETTLE / 2);
+
+        let due = debouncer.take_due(start + 2 * DEBOUNCE);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, "other");
+
+        debouncer.changed("app", ".mcp.json".into(), start + SETTLE);
+        assert_eq!(debouncer.take_due(start + SETTLE + DEBOUNCE).len(), 1);
+    }
+
+    #[test]
+    fn only_agent_config_paths_are_watched() {
+        let project = Project {
+            name: "app".into(),
+            directory: "/tmp/app".into(),
+            agents: vec!["claude".into()],
+            ..Default::default()
+        };
+        let targets = watch_targets(&project);
+
+        for path in [
+            ".mcp.json",
+            "CLAUDE.md",
+            ".claude/skills/deploy/SKILL.md",
+            ".claude/agents/reviewer.md",
+        ] {
+            assert!(is_watched(Path::new(path), &targets), "{}", path);
+        }
+        for path in ["README.md", "src/main.rs", ".claude/settings.local.json"] {
+            assert!(!is_watched(Path::new(path), &targets), "{}", path);
+        }
+    }
+}
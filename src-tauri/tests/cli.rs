@@ -0,0 +1,79 @@
+//! Runs the built `automatic` binary against a throwaway home directory.
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn automatic(home: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("automatic").unwrap();
+    // Pin the data root too, so neither the developer's environment nor a
+    // data directory recorded outside HOME leaks into a test.
+    cmd.env("HOME", home.path())
+        .env("USERPROFILE", home.path())
+        .env("AUTOMATIC_DATA_DIR", home.path().join(".automatic"));
+    cmd
+}
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn version_prints_package_version() {
+    let home = TempDir::new().unwrap();
+    let output = automatic(&home).arg("--version").output().unwrap();
+    assert!(output.status.success());
+    assert!(stdout(&output).contains(env!("CARGO_PKG_VERSION")));
+}
+
+#[test]
+fn completions_are_generated_for_each_shell() {
+    let home = TempDir::new().unwrap();
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        let output = automatic(&home)
+            .args(["completions", shell])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", shell);
+        let script = stdout(&output);
+        assert!(script.contains("mcp-serve"), "{}", shell);
+        assert!(script.contains("doctor"), "{}", shell);
+    }
+}
+
+#[test]
+fn doctor_json_matches_its_schema() {
+    let home = TempDir::new().unwrap();
+    let output = automatic(&home)
+        .args(["doctor", "--json"])
+        .output()
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    let checks = report["checks"].as_array().unwrap();
+    let failures = checks.iter().filter(|c| c["status"] == "failed").count();
+    assert_eq!(report["failures"], failures);
+    assert_eq!(output.status.success(), failures == 0);
+    for check in checks {
+        assert!(check["category"].is_string());
+        assert!(check["subject"].is_string());
+        assert!(check["details"].is_array());
+    }
+
+    let output = automatic(&home)
+        .args(["doctor", "--schema"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert!(schema["properties"]["checks"].is_object());
+    assert!(schema["properties"]["failures"].is_object());
+}
+
+#[test]
+fn unknown_subcommand_arguments_are_rejected() {
+    let home = TempDir::new().unwrap();
+    let output = automatic(&home)
+        .args(["doctor", "--bogus"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
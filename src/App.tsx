@@ -31,6 +31,7 @@ import { TaskLogProvider, useTaskLog } from "./contexts/TaskLogContext";
 import TaskLog from "./components/TaskLog";
 import { UpdateProvider } from "./contexts/UpdateContext";
 import UpdateToast from "./components/UpdateToast";
+import DriftWatchToast from "./components/DriftWatchToast";
 import { ClipboardList, Code, Server, ChevronDown, FolderOpen, LayoutTemplate, Bot, Layers, Store, Settings as SettingsIcon, ScrollText, Sparkles, PackageOpen, Puzzle, FlaskConical, Lightbulb, List, Wrench, Hash, MessagesSquare, Terminal } from "lucide-react";
 import { flag } from "./lib/flags";
 import graphLogo from "../logos/graph_5.svg";
@@ -498,6 +499,9 @@ function App() {
         {/* Update toast — shown when a background update is ready to apply */}
         <UpdateToast />
 
+        {/* Drift toast — a watched project's config files were edited by hand */}
+        <DriftWatchToast onReview={(name) => navigateToProject(name)} />
+
         {/* Content Area */}
         <div className="flex-1 overflow-hidden flex flex-col">
           {activeTab === "getting-started" && (
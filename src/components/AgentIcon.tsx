@@ -7,7 +7,7 @@ import { Bot } from "lucide-react";
  *   - "claude" -> "claude-code.svg"
  *   - "kiro"   -> "kiro-cli.svg"
  *
- * Agents without an SVG (junie, warp) fall back to the generic Bot icon.
+ * Agents without an SVG (aider, junie, warp) fall back to the generic Bot icon.
  */
 const AGENT_LOGO_MAP: Record<string, string> = {
   claude: "claude-code",
@@ -20,6 +20,8 @@ export interface AgentInfo {
 /** Per-agent configuration options (mirrors the Rust AgentOptions struct). */
 export interface AgentOptions {
   claude_rules_in_dot_claude: boolean;
+  gemini_use_extension: boolean;
+  automatic_mcp_read_only: boolean;
 }
 
 /** Describes one toggleable option for an agent. */
@@ -43,11 +45,31 @@ const AGENT_OPTION_DEFS: Record<string, OptionDef[]> = {
         "them into CLAUDE.md. Claude Code loads these files automatically every session.",
     },
   ],
+  gemini: [
+    {
+      key: "gemini_use_extension",
+      label: "Write as a Gemini CLI extension",
+      description:
+        "Write MCP servers and a rules context file to .gemini/extensions/automatic/ instead of " +
+        "merging them into .gemini/settings.json.",
+    },
+  ],
 };
 
+/** Options offered for every agent that reads MCP servers. */
+const MCP_OPTION_DEFS: OptionDef[] = [
+  {
+    key: "automatic_mcp_read_only",
+    label: "Read-only Automatic access",
+    description:
+      "Start the Automatic MCP server with --read-only for this agent. It can list and read " +
+      "skills, projects and memories, but the tools that change them are not offered.",
+  },
+];
+
 /** Default values for AgentOptions — must match Rust defaults. */
 function defaultOptions(): AgentOptions {
-  return { claude_rules_in_dot_claude: true };
+  return { claude_rules_in_dot_claude: true, gemini_use_extension: false, automatic_mcp_read_only: false };
 }
 
 interface AgentSelectorProps {
@@ -158,7 +180,10 @@ export function AgentSelector({
       <div className="space-y-2">
         {sortedAgentIds.map(({ id, idx }) => {
           const info = availableAgents.find((a) => a.id === id);
-          const optionDefs = AGENT_OPTION_DEFS[id] ?? [];
+          const optionDefs = [
+            ...(AGENT_OPTION_DEFS[id] ?? []),
+            ...(info?.capabilities?.mcp_servers === false ? [] : MCP_OPTION_DEFS),
+          ];
           const hasOptions = optionDefs.length > 0 && !!onOptionChange;
           const isExpanded = expandedSettings.has(id);
           const opts: AgentOptions = {
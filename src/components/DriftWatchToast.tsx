@@ -0,0 +1,61 @@
+/**
+ * DriftWatchToast — banner shown when a watched project's agent config files
+ * were edited outside Automatic (`project-drift-detected` event).
+ *
+ * Renders next to UpdateToast in App.tsx.  Only the latest event is shown;
+ * it stays until the user reviews the project or dismisses it.
+ */
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { AlertCircle, X } from "lucide-react";
+
+interface DriftWatchEvent {
+  project: string;
+  paths: string[];
+}
+
+interface DriftWatchToastProps {
+  onReview: (project: string) => void;
+}
+
+export default function DriftWatchToast({ onReview }: DriftWatchToastProps) {
+  const [event, setEvent] = useState<DriftWatchEvent | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<DriftWatchEvent>("project-drift-detected", (e) => setEvent(e.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!event) return null;
+
+  const shown = event.paths.slice(0, 3).join(", ");
+  const more = event.paths.length > 3 ? ` and ${event.paths.length - 3} more` : "";
+
+  return (
+    <div className="flex items-center gap-3 px-4 py-2.5 bg-warning/10 border-b border-warning/30 text-[13px]">
+      <AlertCircle size={13} className="text-warning flex-shrink-0" />
+      <span className="text-text-base flex-1 truncate">
+        <span className="font-medium">{event.project}</span>: {shown}
+        {more} edited outside Automatic.
+      </span>
+      <button
+        onClick={() => {
+          onReview(event.project);
+          setEvent(null);
+        }}
+        className="flex-shrink-0 px-3 py-1 rounded text-[12px] font-medium bg-warning/20 text-warning hover:bg-warning/30 transition-colors"
+      >
+        Review
+      </button>
+      <button
+        onClick={() => setEvent(null)}
+        className="flex-shrink-0 p-1 text-text-muted hover:text-text-base rounded transition-colors"
+        title="Dismiss"
+      >
+        <X size={13} />
+      </button>
+    </div>
+  );
+}
@@ -0,0 +1,226 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { X, Download, Loader2, ClipboardPaste, AlertTriangle } from "lucide-react";
+
+interface McpSnippetCandidate {
+  name: string;
+  config: Record<string, unknown>;
+  required_env: string[];
+  placeholders: string[];
+  exists: boolean;
+}
+
+interface CandidateChoice {
+  selected: boolean;
+  saveAs: string;
+  env: Record<string, string>;
+  overwrite: boolean;
+}
+
+interface McpSnippetImportDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+  onImport: (serverNames: string[]) => void;
+}
+
+const inputClass =
+  "w-full px-3 py-2 rounded-md bg-bg-sidebar border border-border-strong/40 hover:border-border-strong focus:border-brand outline-none text-[13px] text-text-base placeholder-text-muted/40";
+
+export default function McpSnippetImportDialog({ isOpen, onClose, onImport }: McpSnippetImportDialogProps) {
+  const [raw, setRaw] = useState("");
+  const [candidates, setCandidates] = useState<McpSnippetCandidate[]>([]);
+  const [choices, setChoices] = useState<Record<string, CandidateChoice>>({});
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleParse = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      const found = await invoke<McpSnippetCandidate[]>("parse_mcp_snippet", { raw });
+      setCandidates(found);
+      setChoices(
+        Object.fromEntries(
+          found.map((c) => [
+            c.name,
+            {
+              selected: !c.exists,
+              saveAs: c.name,
+              env: Object.fromEntries(c.required_env.map((key) => [key, ""])),
+              overwrite: false,
+            },
+          ]),
+        ),
+      );
+    } catch (e: unknown) {
+      setCandidates([]);
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const updateChoice = (name: string, patch: Partial<CandidateChoice>) => {
+    setChoices((prev) => ({ ...prev, [name]: { ...prev[name]!, ...patch } }));
+  };
+
+  const handleImport = async () => {
+    const overrides = candidates
+      .filter((c) => choices[c.name]?.selected)
+      .map((c) => {
+        const choice = choices[c.name]!;
+        return {
+          name: c.name,
+          save_as: choice.saveAs.trim() || null,
+          // Unfilled values stay empty and are inherited from the shell.
+          env: Object.fromEntries(Object.entries(choice.env).filter(([, v]) => v.trim() !== "")),
+          overwrite: choice.overwrite,
+        };
+      });
+    if (overrides.length === 0) return;
+
+    setBusy(true);
+    setError(null);
+    try {
+      const saved = await invoke<string[]>("import_mcp_snippet", { raw, overrides });
+      onImport(saved);
+      handleClose();
+    } catch (e: unknown) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleClose = () => {
+    setRaw("");
+    setCandidates([]);
+    setChoices({});
+    setError(null);
+    onClose();
+  };
+
+  if (!isOpen) return null;
+
+  const selectedCount = candidates.filter((c) => choices[c.name]?.selected).length;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      <div className="absolute inset-0 bg-black/50" onClick={handleClose} />
+      <div className="relative bg-bg-input border border-border-strong rounded-xl shadow-2xl w-full max-w-lg mx-4 max-h-[85vh] flex flex-col">
+        {/* Header */}
+        <div className="flex items-center justify-between px-5 py-4 border-b border-border-strong/40">
+          <h2 className="text-[15px] font-semibold text-text-base">Paste MCP Config</h2>
+          <button
+            onClick={handleClose}
+            className="p-1 text-text-muted hover:text-text-base hover:bg-bg-sidebar rounded transition-colors"
+          >
+            <X size={16} />
+          </button>
+        </div>
+
+        {/* Content */}
+        <div className="px-5 py-4 space-y-4 overflow-y-auto custom-scrollbar">
+          <p className="text-[13px] text-text-muted leading-relaxed">
+            Paste the config snippet from a server's README. Surrounding text and code fences are fine, and
+            Claude, Cursor, VS Code, OpenCode and Zed formats are all recognised.
+          </p>
+          <textarea
+            value={raw}
+            onChange={(e) => {
+              setRaw(e.target.value);
+              setCandidates([]);
+            }}
+            rows={8}
+            placeholder={'{\n  "mcpServers": {\n    "github": { "command": "npx", "args": ["-y", "..."] }\n  }\n}'}
+            className={`${inputClass} font-mono text-[12px] resize-y`}
+            spellCheck={false}
+          />
+
+          {candidates.length === 0 ? (
+            <button
+              onClick={handleParse}
+              disabled={busy || !raw.trim()}
+              className="w-full flex items-center justify-center gap-2 px-4 py-3 bg-brand hover:bg-brand-hover text-white rounded-lg text-[13px] font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              {busy ? <Loader2 size={16} className="animate-spin" /> : <ClipboardPaste size={16} />}
+              Find Servers
+            </button>
+          ) : (
+            <>
+              <ul className="space-y-3">
+                {candidates.map((c) => {
+                  const choice = choices[c.name]!;
+                  return (
+                    <li key={c.name} className="p-3 rounded-lg border border-border-strong/40 bg-bg-sidebar/50 space-y-2">
+                      <label className="flex items-center gap-2 text-[13px] text-text-base">
+                        <input
+                          type="checkbox"
+                          checked={choice.selected}
+                          onChange={(e) => updateChoice(c.name, { selected: e.target.checked })}
+                        />
+                        <input
+                          type="text"
+                          value={choice.saveAs}
+                          onChange={(e) => updateChoice(c.name, { saveAs: e.target.value })}
+                          className={`${inputClass} font-mono`}
+                          spellCheck={false}
+                        />
+                      </label>
+                      {c.exists && (
+                        <label className="flex items-center gap-2 text-[12px] text-amber-400">
+                          <input
+                            type="checkbox"
+                            checked={choice.overwrite}
+                            onChange={(e) => updateChoice(c.name, { overwrite: e.target.checked })}
+                          />
+                          A server with this name exists — replace it
+                        </label>
+                      )}
+                      {c.required_env.map((key) => (
+                        <div key={key}>
+                          <label className="block text-[11px] font-semibold text-text-muted tracking-wider uppercase mb-1">
+                            {key}
+                          </label>
+                          <input
+                            type="password"
+                            value={choice.env[key] ?? ""}
+                            onChange={(e) => updateChoice(c.name, { env: { ...choice.env, [key]: e.target.value } })}
+                            placeholder="Leave empty to use the shell environment"
+                            className={`${inputClass} font-mono`}
+                            spellCheck={false}
+                          />
+                        </div>
+                      ))}
+                      {c.placeholders.length > 0 && (
+                        <p className="flex items-start gap-1.5 text-[12px] text-amber-400">
+                          <AlertTriangle size={12} className="mt-0.5 flex-shrink-0" />
+                          Still has placeholder values in {c.placeholders.join(", ")} — edit the server after importing.
+                        </p>
+                      )}
+                    </li>
+                  );
+                })}
+              </ul>
+
+              <button
+                onClick={handleImport}
+                disabled={busy || selectedCount === 0}
+                className="w-full flex items-center justify-center gap-2 px-4 py-3 bg-brand hover:bg-brand-hover text-white rounded-lg text-[13px] font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+              >
+                {busy ? <Loader2 size={16} className="animate-spin" /> : <Download size={16} />}
+                Import {selectedCount} {selectedCount === 1 ? "Server" : "Servers"}
+              </button>
+            </>
+          )}
+
+          {error && (
+            <div className="p-3 bg-red-500/10 border border-red-500/20 rounded-lg">
+              <p className="text-[13px] text-red-400">{error}</p>
+            </div>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
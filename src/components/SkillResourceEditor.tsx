@@ -0,0 +1,132 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { X, Save, Trash2, Loader2 } from "lucide-react";
+
+interface SkillResourceEditorProps {
+  /** Skill the file belongs to. */
+  skill: string;
+  /** Path relative to the skill directory, e.g. `scripts/init.py`; null when closed. */
+  path: string | null;
+  onClose: () => void;
+  /** Called after the file was saved or deleted. */
+  onChanged: () => void;
+}
+
+export default function SkillResourceEditor({ skill, path, onClose, onChanged }: SkillResourceEditorProps) {
+  const [content, setContent] = useState("");
+  const [original, setOriginal] = useState("");
+  const [loading, setLoading] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!path) return;
+    setLoading(true);
+    setError(null);
+    invoke<string>("read_skill_resource", { name: skill, path })
+      .then((text) => {
+        setContent(text);
+        setOriginal(text);
+      })
+      .catch((e: unknown) => setError(String(e)))
+      .finally(() => setLoading(false));
+  }, [skill, path]);
+
+  if (!path) return null;
+
+  const dirty = content !== original;
+
+  const handleClose = async () => {
+    if (dirty && !(await ask("Discard unsaved changes?", { title: "Unsaved Changes", kind: "warning" }))) return;
+    onClose();
+  };
+
+  const handleSave = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      await invoke("save_skill_resource", { name: skill, path, content });
+      setOriginal(content);
+      onChanged();
+    } catch (e: unknown) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleDelete = async () => {
+    const confirmed = await ask(`Delete ${path} from ${skill}?`, { title: "Delete File", kind: "warning" });
+    if (!confirmed) return;
+    setBusy(true);
+    setError(null);
+    try {
+      await invoke("delete_skill_resource", { name: skill, path });
+      onChanged();
+      onClose();
+    } catch (e: unknown) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      <div className="absolute inset-0 bg-black/50" onClick={handleClose} />
+      <div className="relative bg-bg-input border border-border-strong rounded-xl shadow-2xl w-full max-w-3xl mx-4 max-h-[85vh] flex flex-col">
+        {/* Header */}
+        <div className="flex items-center justify-between px-5 py-4 border-b border-border-strong/40">
+          <h2 className="text-[15px] font-semibold text-text-base font-mono truncate">
+            {skill}/{path}
+          </h2>
+          <button
+            onClick={handleClose}
+            className="p-1 text-text-muted hover:text-text-base hover:bg-bg-sidebar rounded transition-colors"
+          >
+            <X size={16} />
+          </button>
+        </div>
+
+        {/* Content */}
+        <div className="px-5 py-4 space-y-3 flex-1 min-h-0 flex flex-col">
+          {loading ? (
+            <div className="flex items-center justify-center py-10 text-text-muted">
+              <Loader2 size={16} className="animate-spin" />
+            </div>
+          ) : (
+            <textarea
+              value={content}
+              onChange={(e) => setContent(e.target.value)}
+              rows={20}
+              className="w-full flex-1 px-3 py-2 rounded-md bg-bg-sidebar border border-border-strong/40 hover:border-border-strong focus:border-brand outline-none font-mono text-[12px] text-text-base resize-none custom-scrollbar"
+              spellCheck={false}
+            />
+          )}
+          {error && <p className="text-[12px] text-red-400">{error}</p>}
+        </div>
+
+        {/* Footer */}
+        <div className="flex items-center justify-between px-5 py-3 border-t border-border-strong/40">
+          <button
+            onClick={handleDelete}
+            disabled={busy || loading}
+            className="flex items-center gap-1.5 px-3 py-1.5 text-[12px] text-red-400 hover:bg-red-500/10 rounded-md transition-colors disabled:opacity-50"
+          >
+            <Trash2 size={13} />
+            Delete
+          </button>
+          <button
+            onClick={handleSave}
+            disabled={busy || loading || !dirty}
+            className="flex items-center gap-1.5 px-4 py-1.5 bg-brand hover:bg-brand-hover text-white rounded-md text-[12px] font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            {busy ? <Loader2 size={13} className="animate-spin" /> : <Save size={13} />}
+            Save
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
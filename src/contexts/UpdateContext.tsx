@@ -2,13 +2,16 @@
  * UpdateContext — background auto-update orchestration.
  *
  * Responsibilities:
- *  - Check for updates once per hour while the window is visible.
+ *  - Check for updates once per hour while the window is visible, on the
+ *    channel (stable / beta) chosen in Settings.
  *  - Download the update silently in the background as soon as one is found.
  *  - Expose `status` so the rest of the UI can react (toast, Settings page).
  *  - When a new update arrives while one is already downloaded, discard the
  *    stale pending object and replace it with the new one.
  *  - Expose `checkAndDownload()` so the Settings page can trigger a manual check.
  *  - Expose `restartApp()` to apply the installed update.
+ *  - Track download progress from the backend's `update-download-progress`
+ *    events.
  *
  * States:
  *   "idle"        — no check in progress, no update pending
@@ -28,7 +31,7 @@ import {
   ReactNode,
 } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { check, type Update } from "@tauri-apps/plugin-updater";
+import { listen } from "@tauri-apps/api/event";
 import { trackUpdateChecked, trackUpdateInstalled } from "../lib/analytics";
 
 export type UpdateStatus =
@@ -42,11 +45,32 @@ export type UpdateStatus =
 export interface UpdateInfo {
   version: string;
   notes?: string;
+  publishedAt?: string;
+  /** Installer size in bytes, when the server reports it. */
+  downloadSize?: number;
+}
+
+export interface UpdateProgress {
+  downloaded: number;
+  total?: number;
+}
+
+/** Result of the `check_for_updates` command. */
+interface UpdateCheck {
+  version: string;
+  current_version: string;
+  channel: string;
+  notes?: string;
+  published_at?: string;
+  download_size?: number;
+  downgrade: boolean;
 }
 
 interface UpdateContextValue {
   status: UpdateStatus;
   updateInfo: UpdateInfo | null;
+  /** Bytes downloaded so far while `status` is "downloading". */
+  progress: UpdateProgress | null;
   errorMessage: string;
   /** Manually trigger a check + background download. */
   checkAndDownload: () => Promise<void>;
@@ -71,11 +95,8 @@ interface UpdateProviderProps {
 export function UpdateProvider({ children }: UpdateProviderProps) {
   const [status, setStatus] = useState<UpdateStatus>("idle");
   const [updateInfo, setUpdateInfo] = useState<UpdateInfo | null>(null);
+  const [progress, setProgress] = useState<UpdateProgress | null>(null);
   const [errorMessage, setErrorMessage] = useState("");
-
-  // Held in a ref so the interval callback always sees the latest value without
-  // triggering an effect re-run.
-  const pendingUpdateRef = useRef<Update | null>(null);
   // Guard against concurrent calls (e.g. manual + automatic firing together).
   const checkInProgressRef = useRef(false);
 
@@ -87,7 +108,7 @@ export function UpdateProvider({ children }: UpdateProviderProps) {
     setErrorMessage("");
 
     try {
-      const update = await check();
+      const update = await invoke<UpdateCheck | null>("check_for_updates");
 
       if (!update) {
         setStatus("up-to-date");
@@ -95,17 +116,20 @@ export function UpdateProvider({ children }: UpdateProviderProps) {
         return;
       }
 
-      // A new update is available — discard any previously held pending update
-      // and start a fresh download.
-      pendingUpdateRef.current = null;
-      setUpdateInfo({ version: update.version, notes: update.body ?? undefined });
+      // A new update is available — the backend replaces any previously
+      // pending one, so the download always fetches the latest release.
+      setUpdateInfo({
+        version: update.version,
+        notes: update.notes ?? undefined,
+        publishedAt: update.published_at ?? undefined,
+        downloadSize: update.download_size ?? undefined,
+      });
       trackUpdateChecked("available");
 
+      setProgress(null);
       setStatus("downloading");
-      await update.downloadAndInstall();
+      await invoke("install_update");
 
-      // Store the installed update object so Settings can reference it if needed.
-      pendingUpdateRef.current = update;
       setStatus("ready");
       trackUpdateInstalled(update.version);
     } catch (e) {
@@ -121,6 +145,17 @@ export function UpdateProvider({ children }: UpdateProviderProps) {
     invoke("restart_app");
   }, []);
 
+  // ── Download progress ─────────────────────────────────────────────────────
+
+  useEffect(() => {
+    const unlisten = listen<UpdateProgress>("update-download-progress", (event) => {
+      setProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // ── Hourly check while the window is visible ──────────────────────────────
 
   useEffect(() => {
@@ -170,7 +205,7 @@ export function UpdateProvider({ children }: UpdateProviderProps) {
 
   return (
     <UpdateContext.Provider
-      value={{ status, updateInfo, errorMessage, checkAndDownload, restartApp }}
+      value={{ status, updateInfo, progress, errorMessage, checkAndDownload, restartApp }}
     >
       {children}
     </UpdateContext.Provider>
@@ -95,6 +95,7 @@ const AI_USAGE_OPTIONS = [
 ];
 
 const AGENT_OPTIONS = [
+  { id: "aider", label: "Aider" },
   { id: "antigravity", label: "Antigravity" },
   { id: "claude", label: "Claude Code" },
   { id: "cline", label: "Cline" },
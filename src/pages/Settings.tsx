@@ -1,6 +1,7 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { ask } from "@tauri-apps/plugin-dialog";
+import { listen } from "@tauri-apps/api/event";
+import { ask, open, save } from "@tauri-apps/plugin-dialog";
 import { THEMES, applyTheme, Theme } from "../lib/theme";
 
 import { getVersion } from "@tauri-apps/api/app";
@@ -13,12 +14,58 @@ import { Bot, AppWindow, Palette, Puzzle, Shield, FileText, LifeBuoy, X, Refresh
 
 type SettingsPage = "sync" | "agents" | "appearance" | "app" | "plugins" | "support";
 
+interface BackupProgress {
+  phase: "export" | "restore";
+  category: string;
+  done: number;
+  total: number;
+}
+
+interface ExcludedSecret {
+  kind: "api_key" | "mcp_oauth" | "mcp_env";
+  name: string;
+}
+
+interface RestoreReport {
+  dry_run: boolean;
+  changes: { category: string; path: string; action: "create" | "replace" | "keep" | "remove" }[];
+  excluded_secrets: ExcludedSecret[];
+}
+
+interface DataDirMigration {
+  from: string;
+  to: string;
+  entries: string[];
+  files: number;
+  dry_run: boolean;
+  warnings?: string[];
+}
+
 interface AppSettings {
   sync_mode: string;
   analytics_enabled: boolean;
   default_agents: string[];
+  /** "stable" or "beta" — which releases the updater offers. */
+  update_channel: string;
+  /** Levels below a project's `skills/` directory searched by autodetection. */
+  autodetect_max_depth: number;
+  /** Directories autodetection reads in `skills/` before giving up. */
+  autodetect_max_dirs: number;
+  /** Sync generations kept per project for restore; 0 stops recording. */
+  sync_generations_retained: number;
+  /** Projects watched for hand edits; toggled from the project page. */
+  drift_watch_projects: string[];
+  /** Remote template index merged into the marketplace; empty turns it off. */
+  template_index_url: string;
+  /** "global" or "library" — where imported skills are installed. */
+  skill_install_scope: string;
 }
 
+const DEFAULT_AUTODETECT_MAX_DEPTH = 1;
+const DEFAULT_AUTODETECT_MAX_DIRS = 2000;
+const DEFAULT_SYNC_GENERATIONS_RETAINED = 20;
+const DEFAULT_TEMPLATE_INDEX_URL = "https://automatic.sh/marketplace/templates.json";
+
 const PAGES: { id: SettingsPage; label: string; icon: React.ReactNode; description: string }[] = [
   {
     id: "sync",
@@ -68,6 +115,13 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
     sync_mode: "symlink",
     analytics_enabled: true,
     default_agents: [],
+    update_channel: "stable",
+    autodetect_max_depth: DEFAULT_AUTODETECT_MAX_DEPTH,
+    autodetect_max_dirs: DEFAULT_AUTODETECT_MAX_DIRS,
+    sync_generations_retained: DEFAULT_SYNC_GENERATIONS_RETAINED,
+    drift_watch_projects: [],
+    template_index_url: DEFAULT_TEMPLATE_INDEX_URL,
+    skill_install_scope: "global",
   });
   const [loading, setLoading] = useState(true);
   const [availableAgents, setAvailableAgents] = useState<AgentInfo[]>([]);
@@ -83,6 +137,7 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
   const [newsletterEmail, setNewsletterEmail] = useState("");
   const [unsubscribeStatus, setUnsubscribeStatus] = useState<"idle" | "running" | "done" | "error">("idle");
   const [unsubscribeError, setUnsubscribeError] = useState("");
+  const [dataDir, setDataDir] = useState("");
 
   const [currentTheme, setCurrentTheme] = useState<Theme>(() => {
     let saved = localStorage.getItem("automatic.theme") as string;
@@ -112,7 +167,7 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
 
   // Update state — sourced from the shared UpdateContext
   const [appVersion, setAppVersion] = useState<string>("");
-  const { status: updateStatus, updateInfo, errorMessage: updateError, checkAndDownload, restartApp } = useUpdate();
+  const { status: updateStatus, updateInfo, progress: updateProgress, errorMessage: updateError, checkAndDownload, restartApp } = useUpdate();
   const { log, update } = useTaskLog();
 
   useEffect(() => {
@@ -125,6 +180,13 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
           sync_mode: raw.sync_mode ?? raw.skill_sync_mode ?? "symlink",
           analytics_enabled: raw.analytics_enabled ?? true,
           default_agents: raw.default_agents ?? [],
+          update_channel: raw.update_channel ?? "stable",
+          autodetect_max_depth: raw.autodetect_max_depth ?? DEFAULT_AUTODETECT_MAX_DEPTH,
+          autodetect_max_dirs: raw.autodetect_max_dirs ?? DEFAULT_AUTODETECT_MAX_DIRS,
+          sync_generations_retained: raw.sync_generations_retained ?? DEFAULT_SYNC_GENERATIONS_RETAINED,
+          drift_watch_projects: raw.drift_watch_projects ?? [],
+          template_index_url: raw.template_index_url ?? DEFAULT_TEMPLATE_INDEX_URL,
+          skill_install_scope: raw.skill_install_scope ?? "global",
         });
         setAvailableAgents(agents);
         setNewsletterEmail(raw.onboarding?.email ?? "");
@@ -143,6 +205,9 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
       }
     }
     loadSettings();
+    invoke<string>("get_data_dir")
+      .then(setDataDir)
+      .catch(() => {});
     getVersion()
       .then(setAppVersion)
       .catch(() => {});
@@ -163,6 +228,46 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
     await persistSettings(updated);
   }
 
+  async function updateUpdateChannel(channel: string) {
+    const updated = { ...settings, update_channel: channel };
+    setSettings(updated);
+    trackSettingChanged("update_channel", channel);
+    await persistSettings(updated);
+  }
+
+  async function updateAutodetectLimit(
+    key: "autodetect_max_depth" | "autodetect_max_dirs",
+    value: number,
+  ) {
+    if (!Number.isFinite(value) || value < 1) return;
+    const updated = { ...settings, [key]: Math.floor(value) };
+    setSettings(updated);
+    trackSettingChanged(key, Math.floor(value));
+    await persistSettings(updated);
+  }
+
+  async function updateSyncGenerationsRetained(value: number) {
+    if (!Number.isFinite(value) || value < 0) return;
+    const updated = { ...settings, sync_generations_retained: Math.floor(value) };
+    setSettings(updated);
+    trackSettingChanged("sync_generations_retained", Math.floor(value));
+    await persistSettings(updated);
+  }
+
+  async function updateSkillInstallScope(scope: string) {
+    const updated = { ...settings, skill_install_scope: scope };
+    setSettings(updated);
+    trackSettingChanged("skill_install_scope", scope);
+    await persistSettings(updated);
+  }
+
+  async function updateTemplateIndexUrl(value: string) {
+    const updated = { ...settings, template_index_url: value.trim() };
+    setSettings(updated);
+    trackSettingChanged("template_index_url", updated.template_index_url !== "");
+    await persistSettings(updated);
+  }
+
   async function updateAnalyticsEnabled(enabled: boolean) {
     const updated = { ...settings, analytics_enabled: enabled };
     setSettings(updated);
@@ -218,6 +323,13 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
         sync_mode: "symlink",
         analytics_enabled: true,
         default_agents: [],
+        update_channel: "stable",
+        autodetect_max_depth: DEFAULT_AUTODETECT_MAX_DEPTH,
+        autodetect_max_dirs: DEFAULT_AUTODETECT_MAX_DIRS,
+        sync_generations_retained: DEFAULT_SYNC_GENERATIONS_RETAINED,
+        drift_watch_projects: [],
+        template_index_url: DEFAULT_TEMPLATE_INDEX_URL,
+        skill_install_scope: "global",
       };
       setSettings(defaults);
       setAnalyticsEnabled(true);
@@ -248,6 +360,105 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
     }
   }
 
+  function describeSecrets(secrets: ExcludedSecret[]): string {
+    if (secrets.length === 0) return "";
+    const names = secrets.map((s) =>
+      s.kind === "api_key" ? `${s.name} API key` : s.kind === "mcp_oauth" ? `${s.name} OAuth sign-in` : `${s.name} environment values`
+    );
+    return ` Not included (re-enter after restoring): ${names.join(", ")}.`;
+  }
+
+  async function withBackupProgress<T>(entryId: string, label: string, run: () => Promise<T>): Promise<T> {
+    const unlisten = await listen<BackupProgress>("backup-progress", (event) => {
+      const p = event.payload;
+      update(entryId, `${label} ${p.category.replace(/_/g, " ")} (${p.done}/${p.total})…`, "running");
+    });
+    try {
+      return await run();
+    } finally {
+      unlisten();
+    }
+  }
+
+  async function exportBackup() {
+    const dest = await save({
+      defaultPath: `automatic-${new Date().toISOString().slice(0, 10)}.automatic-backup`,
+      filters: [{ name: "Automatic backup", extensions: ["automatic-backup"] }],
+    });
+    if (!dest) return;
+    const entryId = log("Exporting backup…", "running");
+    try {
+      const manifest = await withBackupProgress(entryId, "Exporting", () =>
+        invoke<{ categories: { files: string[] }[]; excluded_secrets: ExcludedSecret[] }>("export_backup", { dest })
+      );
+      const files = manifest.categories.reduce((n, c) => n + c.files.length, 0);
+      update(entryId, `Backup exported (${files} files).${describeSecrets(manifest.excluded_secrets)}`, "success");
+    } catch (e) {
+      update(entryId, `Backup export failed: ${e}`, "error");
+    }
+  }
+
+  async function restoreBackup() {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: "Automatic backup", extensions: ["automatic-backup"] }],
+    });
+    if (!path || Array.isArray(path)) return;
+    const entryId = log("Checking backup…", "running");
+    try {
+      // Merge: add what is missing, keep everything that already exists.
+      const strategy = { default: "merge", dry_run: true };
+      const preview = await invoke<RestoreReport>("restore_backup", { path, strategy });
+      const adds = preview.changes.filter((c) => c.action === "create").length;
+      const kept = preview.changes.filter((c) => c.action === "keep").length;
+      update(entryId, `Backup checked: ${adds} items to restore, ${kept} existing items kept.`, "success");
+      if (adds === 0) return;
+      const confirmed = await ask(
+        `Restore ${adds} missing items from this backup? ${kept} items that already exist will be left unchanged.`,
+        { title: "Restore Backup", kind: "warning" }
+      );
+      if (!confirmed) return;
+
+      const restoreId = log("Restoring backup…", "running");
+      try {
+        const report = await withBackupProgress(restoreId, "Restoring", () =>
+          invoke<RestoreReport>("restore_backup", { path, strategy: { ...strategy, dry_run: false } })
+        );
+        update(restoreId, `Backup restored (${report.changes.filter((c) => c.action === "create").length} items).${describeSecrets(report.excluded_secrets)}`, "success");
+      } catch (e) {
+        update(restoreId, `Backup restore failed: ${e}`, "error");
+      }
+    } catch (e) {
+      update(entryId, `Could not read backup: ${e}`, "error");
+    }
+  }
+
+  async function moveDataDir() {
+    const dest = await open({ directory: true, multiple: false, defaultPath: dataDir || undefined });
+    if (!dest || Array.isArray(dest)) return;
+    try {
+      const preview = await invoke<DataDirMigration>("migrate_data_dir", { newPath: dest, dryRun: true });
+      const confirmed = await ask(
+        `Move ${preview.files} files from ${preview.from} to ${preview.to}?\n\n${preview.entries.join(", ")}\n\nAgent sessions that are already running keep using the old location until they restart.`,
+        { title: "Move Data Directory", kind: "warning" }
+      );
+      if (!confirmed) return;
+    } catch (e) {
+      log(`Cannot move data directory: ${e}`, "error");
+      return;
+    }
+
+    const entryId = log("Moving data directory…", "running");
+    try {
+      const report = await invoke<DataDirMigration>("migrate_data_dir", { newPath: dest, dryRun: false });
+      setDataDir(report.to);
+      const warnings = report.warnings?.length ? ` ${report.warnings.join(" ")}` : "";
+      update(entryId, `Data directory moved to ${report.to}.${warnings}`, "success");
+    } catch (e) {
+      update(entryId, `Failed to move data directory: ${e}`, "error");
+    }
+  }
+
   async function handleUnsubscribe() {
     if (!newsletterEmail) return;
     setUnsubscribeStatus("running");
@@ -280,6 +491,13 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
         sync_mode: "symlink",
         analytics_enabled: true,
         default_agents: [],
+        update_channel: "stable",
+        autodetect_max_depth: DEFAULT_AUTODETECT_MAX_DEPTH,
+        autodetect_max_dirs: DEFAULT_AUTODETECT_MAX_DIRS,
+        sync_generations_retained: DEFAULT_SYNC_GENERATIONS_RETAINED,
+        drift_watch_projects: [],
+        template_index_url: DEFAULT_TEMPLATE_INDEX_URL,
+        skill_install_scope: "global",
       };
       setSettings(defaults);
       setAnalyticsEnabled(true);
@@ -388,6 +606,111 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
                 </div>
               </div>
 
+              {/* Autodetection limits */}
+              <div className="mb-8">
+                <h3 className="text-sm font-medium mb-2 text-text-base">Autodetection</h3>
+                <p className="text-[13px] text-text-muted mb-4 leading-relaxed">
+                  Agent configs and skill directories are checked at their known
+                  locations. Only a project's <code>skills/</code> directory is
+                  searched, within these limits. Raise them if your skills are
+                  grouped into subfolders.
+                </p>
+                <div className="flex gap-4">
+                  <label className="flex flex-col gap-1 text-[12px] text-text-muted">
+                    Search depth
+                    <input
+                      type="number"
+                      min={1}
+                      max={16}
+                      value={settings.autodetect_max_depth}
+                      onChange={(e) => updateAutodetectLimit("autodetect_max_depth", Number(e.target.value))}
+                      className="w-28 px-3 py-1.5 rounded-md border border-border-strong/40 bg-bg-input-dark text-[13px] text-text-base"
+                    />
+                  </label>
+                  <label className="flex flex-col gap-1 text-[12px] text-text-muted">
+                    Directory budget
+                    <input
+                      type="number"
+                      min={1}
+                      step={100}
+                      value={settings.autodetect_max_dirs}
+                      onChange={(e) => updateAutodetectLimit("autodetect_max_dirs", Number(e.target.value))}
+                      className="w-28 px-3 py-1.5 rounded-md border border-border-strong/40 bg-bg-input-dark text-[13px] text-text-base"
+                    />
+                  </label>
+                </div>
+              </div>
+
+              {/* Sync generations */}
+              <div className="mb-8">
+                <h3 className="text-sm font-medium mb-2 text-text-base">Sync History</h3>
+                <p className="text-[13px] text-text-muted mb-4 leading-relaxed">
+                  Each sync records the files Automatic manages in a project so the
+                  whole project can be restored to an earlier sync. Set to 0 to stop
+                  recording.
+                </p>
+                <label className="flex flex-col gap-1 text-[12px] text-text-muted">
+                  Syncs kept per project
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.sync_generations_retained}
+                    onChange={(e) => updateSyncGenerationsRetained(Number(e.target.value))}
+                    className="w-28 px-3 py-1.5 rounded-md border border-border-strong/40 bg-bg-input-dark text-[13px] text-text-base"
+                  />
+                </label>
+              </div>
+
+              {/* Remote templates */}
+              <div className="mb-8">
+                <h3 className="text-sm font-medium mb-2 text-text-base">Remote Templates</h3>
+                <p className="text-[13px] text-text-muted mb-4 leading-relaxed">
+                  Templates published after this release are listed in the Template
+                  Marketplace from this index. Leave empty to show bundled and local
+                  templates only.
+                </p>
+                <label className="flex flex-col gap-1 text-[12px] text-text-muted">
+                  Template index URL
+                  <input
+                    type="url"
+                    value={settings.template_index_url}
+                    placeholder={DEFAULT_TEMPLATE_INDEX_URL}
+                    onChange={(e) => setSettings({ ...settings, template_index_url: e.target.value })}
+                    onBlur={(e) => updateTemplateIndexUrl(e.target.value)}
+                    className="w-full px-3 py-1.5 rounded-md border border-border-strong/40 bg-bg-input-dark text-[13px] text-text-base"
+                  />
+                </label>
+              </div>
+
+              {/* Skill install scope */}
+              <div className="mb-8">
+                <h3 className="text-sm font-medium mb-2 text-text-base">Imported Skills</h3>
+                <p className="text-[13px] text-text-muted mb-4 leading-relaxed">
+                  Choose where skills imported from skills.sh, a repository or a local
+                  path are installed. Library skills stay out of every agent's global
+                  skill folder and are only copied into the projects that select them.
+                </p>
+                <div className="flex gap-2">
+                  {[
+                    { id: "global", label: "Global", hint: "Installed in ~/.agents/skills, visible to every agent." },
+                    { id: "library", label: "Library", hint: "Kept in Automatic's skill library, used per project." },
+                  ].map((scope) => (
+                    <button
+                      key={scope.id}
+                      onClick={() => updateSkillInstallScope(scope.id)}
+                      className={`flex-1 flex flex-col items-start gap-1 p-3 rounded-lg border text-left transition-all ${
+                        settings.skill_install_scope === scope.id
+                          ? "border-brand bg-brand/10"
+                          : "border-border-strong/40 bg-bg-input-dark hover:border-border-strong hover:bg-surface-hover"
+                      }`}
+                    >
+                      <div className="text-[13px] font-medium text-text-base">{scope.label}</div>
+                      <div className="text-[12px] text-text-muted">{scope.hint}</div>
+                    </button>
+                  ))}
+                </div>
+              </div>
+
               {/* Default Skills */}
               <div className="mb-8">
                 <h3 className="text-sm font-medium mb-2 text-text-base">Default Skills</h3>
@@ -743,10 +1066,34 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
                   )}
                 </div>
 
+                {/* Channel */}
+                <div className="flex gap-2 mb-4">
+                  {[
+                    { id: "stable", label: "Stable", hint: "Tested releases only." },
+                    { id: "beta", label: "Beta", hint: "Pre-releases with the newest features." },
+                  ].map((channel) => (
+                    <button
+                      key={channel.id}
+                      onClick={() => updateUpdateChannel(channel.id)}
+                      className={`flex-1 flex flex-col items-start gap-1 p-3 rounded-lg border text-left transition-all ${
+                        settings.update_channel === channel.id
+                          ? "border-brand bg-brand/10"
+                          : "border-border-strong/40 bg-bg-input-dark hover:border-border-strong hover:bg-surface-hover"
+                      }`}
+                    >
+                      <div className="text-[13px] font-medium text-text-base">{channel.label}</div>
+                      <div className="text-[12px] text-text-muted">{channel.hint}</div>
+                    </button>
+                  ))}
+                </div>
+
                 {/* Downloading in background */}
                 {updateStatus === "downloading" && updateInfo && (
                   <div className="mb-4 p-4 rounded-lg border border-brand/40 bg-brand/5 text-[13px] text-text-muted">
-                    Downloading {updateInfo.version}…
+                    Downloading {updateInfo.version}
+                    {updateProgress?.total
+                      ? ` — ${Math.round((updateProgress.downloaded / updateProgress.total) * 100)}%`
+                      : "…"}
                   </div>
                 )}
                 {updateStatus === "downloading" && !updateInfo && (
@@ -814,6 +1161,47 @@ export default function Settings({ onOpenWizard }: SettingsProps) {
                 </div>
               )}
 
+              {/* Data Directory */}
+              <div className="mb-8">
+                <h3 className="text-sm font-medium mb-2 text-text-base">Data Directory</h3>
+                <p className="text-[13px] text-text-muted mb-4 leading-relaxed">
+                  Where Automatic keeps settings, projects, skills, rules, and memory. Set <code>AUTOMATIC_DATA_DIR</code> to override it for a single launch.
+                </p>
+                <div className="flex items-center gap-2">
+                  <code className="flex-1 min-w-0 truncate px-3 py-2 rounded-lg border border-border-strong/40 bg-bg-input-dark text-[12px] text-text-base">
+                    {dataDir || "…"}
+                  </code>
+                  <button
+                    onClick={moveDataDir}
+                    className="px-4 py-2 rounded-lg border border-border-strong/40 bg-bg-input-dark text-[13px] text-text-base hover:border-border-strong hover:bg-surface-hover transition-all"
+                  >
+                    Move…
+                  </button>
+                </div>
+              </div>
+
+              {/* Backup */}
+              <div className="mb-8">
+                <h3 className="text-sm font-medium mb-2 text-text-base">Backup</h3>
+                <p className="text-[13px] text-text-muted mb-4 leading-relaxed">
+                  Save settings, projects, skills, rules, templates, MCP servers, and memory to a single file, or restore them on a new machine. API keys and sign-ins stay in your keychain and are not included.
+                </p>
+                <div className="flex gap-2">
+                  <button
+                    onClick={exportBackup}
+                    className="px-4 py-2 rounded-lg border border-border-strong/40 bg-bg-input-dark text-[13px] text-text-base hover:border-border-strong hover:bg-surface-hover transition-all"
+                  >
+                    Export Backup
+                  </button>
+                  <button
+                    onClick={restoreBackup}
+                    className="px-4 py-2 rounded-lg border border-border-strong/40 bg-bg-input-dark text-[13px] text-text-base hover:border-border-strong hover:bg-surface-hover transition-all"
+                  >
+                    Restore Backup
+                  </button>
+                </div>
+              </div>
+
               {/* Reset */}
               <div className="mb-8">
                 <h3 className="text-sm font-medium mb-2 text-text-base">Reset</h3>
@@ -22,6 +22,7 @@ interface RemoteSkillResult {
   name: string;
   installs: number;
   source: string;
+  license?: string;
 }
 
 function formatInstalls(count: number): string {
@@ -37,6 +37,15 @@ interface BundledProjectTemplate {
   icon?: string;
   /** Author/provider metadata — same shape as AuthorDescriptor. */
   _author?: AuthorDescriptor;
+  /** "local" for templates loaded from ~/.automatic/marketplace_templates/,
+   *  "remote" for templates from the remote template index. */
+  source?: "bundled" | "local" | "remote";
+}
+
+interface MarketplaceTemplates {
+  templates: BundledProjectTemplate[];
+  /** Local template files that were skipped, and remote index problems. */
+  warnings: string[];
 }
 
 interface SkillDependencyStatus {
@@ -51,6 +60,66 @@ interface TemplateDependencyReport {
   missing_mcp_servers: string[];
 }
 
+interface ListDiff {
+  added: string[];
+  removed: string[];
+}
+
+/** A local template that importing would overwrite. */
+interface TemplateImportConflict {
+  name: string;
+  diff: {
+    skills: ListDiff;
+    agents: ListDiff;
+    mcp_servers: ListDiff;
+    files: ListDiff;
+    changed_files: string[];
+    description_changed: boolean;
+    instructions_changed: boolean;
+  };
+  /** The bundled template changed since the local copy was imported. */
+  bundled_changed: boolean;
+}
+
+type TemplateImportOutcome =
+  | { status: "imported"; name: string }
+  | ({ status: "conflict" } & TemplateImportConflict);
+
+interface TemplateSkillInstall {
+  name: string;
+  status: "already_installed" | "installed_from_bundle" | "installed_from_remote" | "not_found" | "failed";
+  /** GitHub owner/repo or SKILL.md URL a remote skill was downloaded from. */
+  source?: string;
+  error?: string;
+}
+
+interface TemplateImportReport {
+  outcome: TemplateImportOutcome;
+  skills: TemplateSkillInstall[];
+}
+
+interface ImportOptions {
+  overwrite?: boolean;
+  importAs?: string;
+}
+
+function conflictSummary(conflict: TemplateImportConflict): string[] {
+  const { diff } = conflict;
+  const lines: string[] = [];
+  const list = (label: string, d: ListDiff) => {
+    if (d.added.length) lines.push(`${label} added: ${d.added.join(", ")}`);
+    if (d.removed.length) lines.push(`${label} removed: ${d.removed.join(", ")}`);
+  };
+  list("Skills", diff.skills);
+  list("Agents", diff.agents);
+  list("MCP servers", diff.mcp_servers);
+  list("Files", diff.files);
+  if (diff.changed_files.length) lines.push(`Files changed: ${diff.changed_files.join(", ")}`);
+  if (diff.description_changed) lines.push("Description changed");
+  if (diff.instructions_changed) lines.push("Instructions changed");
+  return lines;
+}
+
 // ── Helpers ──────────────────────────────────────────────────────────────────
 
 const CATEGORY_COLOURS: Record<string, { bg: string; text: string; dot: string }> = {
@@ -290,6 +359,7 @@ function TemplateDetail({
   imported,
   importing,
   error,
+  conflict,
   onImport,
   onClose,
   onGoToTemplate,
@@ -298,7 +368,8 @@ function TemplateDetail({
   imported: boolean;
   importing: boolean;
   error: string | null;
-  onImport: () => void;
+  conflict: TemplateImportConflict | null;
+  onImport: (options?: ImportOptions) => void;
   onClose: () => void;
   onGoToTemplate?: (name: string) => void;
 }) {
@@ -364,6 +435,14 @@ function TemplateDetail({
                 <CheckCircle2 size={12} />
                 Imported
               </div>
+              <button
+                onClick={() => onImport()}
+                disabled={importing}
+                className="flex h-[26px] items-center gap-1.5 px-2.5 rounded-md text-[11px] font-medium text-text-muted hover:text-text-base border border-border-strong/40 transition-colors disabled:opacity-50"
+              >
+                {importing ? <Loader2 size={12} className="animate-spin" /> : <Download size={12} />}
+                Import again
+              </button>
               {onGoToTemplate && (
                 <button
                   onClick={() => onGoToTemplate(template.name)}
@@ -376,7 +455,7 @@ function TemplateDetail({
             </div>
           ) : (
             <button
-              onClick={onImport}
+              onClick={() => onImport()}
               disabled={importing}
               className="flex h-[26px] items-center gap-1.5 px-2.5 rounded-md text-[11px] font-medium bg-brand hover:bg-brand-hover text-white transition-colors disabled:opacity-50 disabled:cursor-not-allowed shadow-sm border border-transparent"
             >
@@ -394,6 +473,49 @@ function TemplateDetail({
       <div className="flex-1 overflow-y-auto custom-scrollbar">
         <div className="max-w-3xl mx-auto px-8 py-8 space-y-8">
 
+          {conflict && (
+            <div className="rounded-lg border border-amber-500/30 bg-amber-500/5 px-4 py-3">
+              <div className="flex items-center gap-2 mb-2">
+                <AlertTriangle size={13} className="text-amber-400" />
+                <span className="text-[13px] font-medium text-text-base">
+                  Your template "{conflict.name}" differs from this one
+                </span>
+              </div>
+              {conflict.bundled_changed && (
+                <p className="text-[12px] text-text-muted mb-2">
+                  The bundled template has changed since you imported your copy.
+                </p>
+              )}
+              <ul className="text-[12px] text-text-muted space-y-0.5 mb-3">
+                {conflictSummary(conflict).map((line) => (
+                  <li key={line}>{line}</li>
+                ))}
+              </ul>
+              <div className="flex items-center gap-2">
+                <button
+                  onClick={onClose}
+                  className="h-[26px] px-2.5 rounded-md text-[11px] font-medium border border-border-strong/40 text-text-base hover:bg-bg-sidebar transition-colors"
+                >
+                  Keep mine
+                </button>
+                <button
+                  onClick={() => onImport({ overwrite: true })}
+                  disabled={importing}
+                  className="h-[26px] px-2.5 rounded-md text-[11px] font-medium border border-border-strong/40 text-text-base hover:bg-bg-sidebar transition-colors disabled:opacity-50"
+                >
+                  Replace
+                </button>
+                <button
+                  onClick={() => onImport({ importAs: `${template.name}-bundled` })}
+                  disabled={importing}
+                  className="h-[26px] px-2.5 rounded-md text-[11px] font-medium bg-brand hover:bg-brand-hover text-white transition-colors disabled:opacity-50"
+                >
+                  Import as {template.name}-bundled
+                </button>
+              </div>
+            </div>
+          )}
+
           {/* Description */}
           <p className="text-[14px] text-text-base leading-relaxed pb-8 border-b border-border-strong/40">
             {template.description}
@@ -568,6 +690,7 @@ export default function TemplateMarketplace({ resetKey, onNavigateToTemplate, in
   const [importedNames, setImportedNames] = useState<Set<string>>(new Set());
   const [importing, setImporting] = useState(false);
   const [importError, setImportError] = useState<string | null>(null);
+  const [importConflict, setImportConflict] = useState<TemplateImportConflict | null>(null);
   const [loading, setLoading] = useState(true);
   const debounceRef = useRef<ReturnType<typeof setTimeout> | null>(null);
 
@@ -583,10 +706,12 @@ export default function TemplateMarketplace({ resetKey, onNavigateToTemplate, in
   useEffect(() => {
     (async () => {
       try {
-        const raw: string = await invoke("list_bundled_project_templates");
-        const templates: BundledProjectTemplate[] = JSON.parse(raw);
-        setAllTemplates(templates);
-        setResults(templates);
+        const listing: MarketplaceTemplates = await invoke("list_bundled_project_templates");
+        for (const warning of listing.warnings) {
+          console.warn("Marketplace template warning:", warning);
+        }
+        setAllTemplates(listing.templates);
+        setResults(listing.templates);
       } catch (err) {
         console.error("Failed to load bundled templates:", err);
       }
@@ -613,8 +738,8 @@ export default function TemplateMarketplace({ resetKey, onNavigateToTemplate, in
     async (q: string) => {
       if (!q.trim()) { setResults(allTemplates); return; }
       try {
-        const raw: string = await invoke("search_bundled_project_templates", { query: q });
-        setResults(JSON.parse(raw));
+        const listing: MarketplaceTemplates = await invoke("search_bundled_project_templates", { query: q });
+        setResults(listing.templates);
       } catch {
         const lower = q.toLowerCase();
         setResults(allTemplates.filter((t) =>
@@ -635,13 +760,34 @@ export default function TemplateMarketplace({ resetKey, onNavigateToTemplate, in
     return () => { if (debounceRef.current) clearTimeout(debounceRef.current); };
   }, [query, doSearch]);
 
-  const handleImport = useCallback(async () => {
+  // A conflict belongs to the template it was reported for.
+  useEffect(() => { setImportConflict(null); }, [selected]);
+
+  const handleImport = useCallback(async (options: ImportOptions = {}) => {
     if (!selected) return;
     setImporting(true);
     setImportError(null);
     try {
-      await invoke("import_bundled_project_template", { name: selected.name });
-      setImportedNames((prev) => new Set([...prev, selected.name]));
+      const { outcome, skills } = await invoke<TemplateImportReport>(
+        "import_bundled_project_template_with_skills",
+        {
+          name: selected.name,
+          installMissing: true,
+          overwrite: options.overwrite ?? false,
+          importAs: options.importAs ?? null,
+        },
+      );
+      for (const skill of skills) {
+        if (skill.status === "not_found" || skill.status === "failed") {
+          console.warn(`Template skill '${skill.name}' not installed:`, skill.error ?? skill.status);
+        }
+      }
+      if (outcome.status === "conflict") {
+        setImportConflict(outcome);
+        return;
+      }
+      setImportConflict(null);
+      setImportedNames((prev) => new Set([...prev, selected.name, outcome.name]));
     } catch (err: any) {
       setImportError(`Import failed: ${err}`);
     } finally {
@@ -657,8 +803,9 @@ export default function TemplateMarketplace({ resetKey, onNavigateToTemplate, in
         imported={importedNames.has(selected.name)}
         importing={importing}
         error={importError}
+        conflict={importConflict}
         onImport={handleImport}
-        onClose={() => { setSelected(null); setImportError(null); }}
+        onClose={() => { setSelected(null); setImportError(null); setImportConflict(null); }}
         onGoToTemplate={onNavigateToTemplate}
       />
     );
@@ -36,6 +36,16 @@ const AGENT_OPTION_DEFS: Record<string, AgentOptionDef[]> = {
       hardDefault: true,
     },
   ],
+  gemini: [
+    {
+      key: "gemini_use_extension",
+      label: "Write as a Gemini CLI extension",
+      description:
+        "Write MCP servers and a rules context file to .gemini/extensions/automatic/ " +
+        "instead of merging them into .gemini/settings.json.",
+      hardDefault: false,
+    },
+  ],
 };
 
 /**
@@ -254,6 +264,8 @@ export default function Agents({ onNavigateToProject }: AgentsProps = {}) {
       const raw: any = await invoke("read_settings");
       const existing: AgentOptions = {
         claude_rules_in_dot_claude: true,
+        gemini_use_extension: false,
+        automatic_mcp_read_only: false,
         ...(raw.default_agent_options?.[agentId] ?? {}),
       };
       const updated = {
@@ -4,6 +4,7 @@ import { ask } from "@tauri-apps/plugin-dialog";
 import { trackMcpServerCreated, trackMcpServerUpdated, trackMcpServerDeleted } from "../../lib/analytics";
 import { AuthorSection, type AuthorDescriptor } from "../../components/AuthorPanel";
 import { KvEditor, inputClass, smallInputClass, addBtnClass } from "../../components/KvField";
+import McpSnippetImportDialog from "../../components/McpSnippetImportDialog";
 import {
   Plus,
   X,
@@ -20,6 +21,7 @@ import {
   ShieldCheck,
   Loader2,
   Info,
+  ClipboardPaste,
 } from "lucide-react";
 import { ICONS } from "../../lib/icons";
 
@@ -35,6 +37,17 @@ interface McpAuthorMeta {
   repository_url?: string;
 }
 
+/** Command used instead of the base one when syncing to a single agent. */
+interface McpCommandVariant {
+  command?: string;
+  args?: string[];
+}
+
+interface AgentInfo {
+  id: string;
+  label: string;
+}
+
 interface McpServerConfig {
   type: TransportType;
   // stdio fields
@@ -42,6 +55,8 @@ interface McpServerConfig {
   args?: string[];
   env?: Record<string, string>;
   cwd?: string;
+  /** Per-agent overrides of command/args, keyed by agent id. */
+  variants?: Record<string, McpCommandVariant>;
   // http/sse fields
   url?: string;
   headers?: Record<string, string>;
@@ -93,6 +108,7 @@ function normalizeConfig(data: Partial<McpServerConfig> & { oauth?: any }): McpS
     args: data.args || [],
     env: data.env || {},
     cwd: data.cwd || "",
+    variants: data.variants || {},
     url: data.url || "",
     headers: data.headers || {},
     oauth,
@@ -113,6 +129,14 @@ function cleanConfig(config: McpServerConfig): Record<string, unknown> {
     if (config.args && config.args.length > 0) out.args = config.args;
     if (config.env && Object.keys(config.env).length > 0) out.env = config.env;
     if (config.cwd) out.cwd = config.cwd;
+    const variants: Record<string, McpCommandVariant> = {};
+    for (const [agentId, variant] of Object.entries(config.variants || {})) {
+      const clean: McpCommandVariant = {};
+      if (variant.command) clean.command = variant.command;
+      if (variant.args) clean.args = variant.args;
+      if (clean.command || clean.args) variants[agentId] = clean;
+    }
+    if (Object.keys(variants).length > 0) out.variants = variants;
   } else {
     if (config.url) out.url = config.url;
     if (config.headers && Object.keys(config.headers).length > 0) out.headers = config.headers;
@@ -293,17 +317,23 @@ export default function McpServers({ initialServer = null, onInitialServerConsum
   const [config, setConfig] = useState<McpServerConfig | null>(null);
   const [dirty, setDirty] = useState(false);
   const [isCreating, setIsCreating] = useState(false);
+  const [showPasteDialog, setShowPasteDialog] = useState(false);
   const [newName, setNewName] = useState("");
   const [error, setError] = useState<string | null>(null);
   const [opencodeWarning, setOpencodeWarning] = useState<string[]>([]);
 
   // Inline add state
   const [newArg, setNewArg] = useState("");
+  const [agents, setAgents] = useState<AgentInfo[]>([]);
+  const [overrideAgent, setOverrideAgent] = useState("");
 
 
   useEffect(() => {
     loadServers();
     checkOpencodeProjects();
+    invoke<AgentInfo[]>("list_agents")
+      .then((list) => setAgents([...list].sort((a, b) => a.label.localeCompare(b.label))))
+      .catch(() => setAgents([]));
   }, []);
 
   // When navigated here from another page (e.g. Projects), auto-select the
@@ -364,6 +394,7 @@ export default function McpServers({ initialServer = null, onInitialServerConsum
 
   const resetInlineState = () => {
     setNewArg("");
+    setOverrideAgent("");
   };
 
   const updateConfig = (patch: Partial<McpServerConfig>) => {
@@ -437,6 +468,23 @@ export default function McpServers({ initialServer = null, onInitialServerConsum
     updateConfig({ args: (config.args || []).filter((_, i) => i !== idx) });
   };
 
+  const updateVariant = (agentId: string, variant: McpCommandVariant | null) => {
+    if (!config) return;
+    const variants = { ...(config.variants || {}) };
+    if (variant) {
+      variants[agentId] = variant;
+    } else {
+      delete variants[agentId];
+    }
+    updateConfig({ variants });
+  };
+
+  const addOverride = () => {
+    if (!config || !overrideAgent) return;
+    updateVariant(overrideAgent, { command: config.command || "", args: [...(config.args || [])] });
+    setOverrideAgent("");
+  };
+
   const setTransport = (type: TransportType) => {
     if (!config) return;
     setConfig({
@@ -464,6 +512,13 @@ export default function McpServers({ initialServer = null, onInitialServerConsum
             MCP Servers
           </span>
           <div className="flex items-center gap-1">
+            <button
+              onClick={() => setShowPasteDialog(true)}
+              className="text-text-muted hover:text-text-base transition-colors p-1 hover:bg-bg-sidebar rounded"
+              title="Paste MCP Config"
+            >
+              <ClipboardPaste size={14} />
+            </button>
             <button
               onClick={startCreate}
               className="text-text-muted hover:text-text-base transition-colors p-1 hover:bg-bg-sidebar rounded"
@@ -782,6 +837,68 @@ export default function McpServers({ initialServer = null, onInitialServerConsum
                     </section>
                     )}
 
+                    {!isManaged && (
+                    <section>
+                      <label className="block text-[11px] font-semibold text-text-muted tracking-wider uppercase mb-2">
+                        Agent Overrides
+                      </label>
+                      <p className="text-[12px] text-text-muted mb-2">
+                        Use a different command for one agent, e.g. an absolute path where that agent does not inherit your shell's PATH.
+                      </p>
+                      {Object.entries(config.variants || {}).length > 0 && (
+                      <ul className="space-y-2 mb-2">
+                        {Object.entries(config.variants || {}).map(([agentId, variant]) => (
+                          <li key={agentId} className="p-2 bg-bg-input rounded-md border border-border-strong/40 space-y-1.5">
+                            <div className="flex items-center justify-between text-[12px] text-text-base">
+                              <span>{agents.find((a) => a.id === agentId)?.label ?? agentId}</span>
+                              <button
+                                onClick={() => updateVariant(agentId, null)}
+                                className="text-text-muted hover:text-danger transition-colors"
+                                title="Remove override"
+                              >
+                                <Trash2 size={12} />
+                              </button>
+                            </div>
+                            <input
+                              type="text"
+                              value={variant.command || ""}
+                              onChange={(e) => updateVariant(agentId, { ...variant, command: e.target.value })}
+                              placeholder="Command"
+                              className={smallInputClass}
+                            />
+                            <input
+                              type="text"
+                              value={(variant.args || []).join(" ")}
+                              onChange={(e) =>
+                                updateVariant(agentId, { ...variant, args: e.target.value.split(" ").filter(Boolean) })
+                              }
+                              placeholder="Arguments, separated by spaces"
+                              className={`${smallInputClass} font-mono`}
+                            />
+                          </li>
+                        ))}
+                      </ul>
+                      )}
+                      <div className="flex gap-2">
+                        <select
+                          value={overrideAgent}
+                          onChange={(e) => setOverrideAgent(e.target.value)}
+                          className={smallInputClass}
+                        >
+                          <option value="">Override for agent…</option>
+                          {agents
+                            .filter((a) => !(config.variants || {})[a.id])
+                            .map((a) => (
+                              <option key={a.id} value={a.id}>{a.label}</option>
+                            ))}
+                        </select>
+                        <button onClick={addOverride} disabled={!overrideAgent} className={addBtnClass}>
+                          Add
+                        </button>
+                      </div>
+                    </section>
+                    )}
+
                   </>
                 )}
 
@@ -898,10 +1015,26 @@ export default function McpServers({ initialServer = null, onInitialServerConsum
               >
                 Add MCP Server
               </button>
+              <button
+                onClick={() => setShowPasteDialog(true)}
+                className="px-4 py-2 bg-bg-sidebar hover:bg-surface-hover text-text-base text-[13px] font-medium rounded border border-border-strong/40 transition-colors"
+              >
+                Paste Config
+              </button>
             </div>
           </div>
         )}
       </div>
+
+      <McpSnippetImportDialog
+        isOpen={showPasteDialog}
+        onClose={() => setShowPasteDialog(false)}
+        onImport={async (names) => {
+          names.forEach((name) => trackMcpServerCreated(name));
+          await loadServers();
+          if (names[0]) await selectServer(names[0]);
+        }}
+      />
     </div>
   );
 }
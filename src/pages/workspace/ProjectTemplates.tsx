@@ -5,6 +5,7 @@ import { SkillSelector } from "../../components/SkillSelector";
 import { AgentSelector, AgentInfo } from "../../components/AgentSelector";
 import { McpSelector } from "../../components/McpSelector";
 import { invoke } from "@tauri-apps/api/core";
+import { ask, message } from "@tauri-apps/plugin-dialog";
 import {
   Plus,
   X,
@@ -47,6 +48,25 @@ interface ProjectTemplate {
   _author?: AuthorDescriptor;
 }
 
+/** Result of `apply_project_template`. */
+interface TemplateApplyReport {
+  added_agents: string[];
+  existing_agents: string[];
+  added_skills: string[];
+  existing_skills: string[];
+  added_servers: string[];
+  existing_servers: string[];
+  removed_skills: string[];
+  removed_servers: string[];
+  attached_rules: string[];
+  written_files: string[];
+  skipped_files: string[];
+  installed_skills: string[];
+  unresolved_skills: string[];
+  unresolved_servers: string[];
+  unresolved_rules: string[];
+  warnings: string[];
+}
 
 interface UserCommandEntry {
   id: string;
@@ -399,6 +419,7 @@ export default function ProjectTemplates({
   // All projects (for "Applied to" + "Apply to project")
   const [allProjects, setAllProjects] = useState<Project[]>([]);
   const [showApplyPicker, setShowApplyPicker] = useState(false);
+  const [showFromProjectPicker, setShowFromProjectPicker] = useState(false);
   const [applyStatus, setApplyStatus] = useState<string | null>(null);
 
   // Inline delete confirmation — holds the name awaiting confirmation
@@ -635,6 +656,33 @@ export default function ProjectTemplates({
     }
   };
 
+  // Capture an existing project as a new template.  Local skills live in the
+  // project directory and cannot be carried over, so the user is told.
+  const createFromProject = async (projectName: string) => {
+    setShowFromProjectPicker(false);
+    let candidate = projectName;
+    let i = 2;
+    while (templates.includes(candidate)) candidate = `${projectName} ${i++}`;
+    try {
+      const raw: string = await invoke("save_project_as_template", {
+        projectName,
+        templateName: candidate,
+      });
+      const saved = JSON.parse(raw) as { skipped_local_skills: string[] };
+      await loadTemplates();
+      await selectTemplate(candidate);
+      setError(null);
+      if (saved.skipped_local_skills.length > 0) {
+        await message(
+          `Local skills stay with "${projectName}" and were not added to the template: ${saved.skipped_local_skills.join(", ")}`,
+          { title: "Template Created", kind: "warning" }
+        );
+      }
+    } catch (err: any) {
+      setError(`Failed to create template from project: ${err}`);
+    }
+  };
+
   const handleDuplicate = async () => {
     if (!template || !selectedName) return;
     let base = `${selectedName} Copy`;
@@ -652,81 +700,48 @@ export default function ProjectTemplates({
     }
   };
 
-  // Apply template to a project (merge, non-destructive)
+  // Apply template to a project (merge, non-destructive).  Agent configs are
+  // not synced until the user confirms after reviewing the report.
   const applyToProject = async (projectName: string) => {
-    if (!template) return;
-    const proj = allProjects.find((p) => p.name === projectName);
-    if (!proj) return;
+    if (!template || !selectedName) return;
+    if (dirty) {
+      setError("Save the template before applying it.");
+      return;
+    }
     try {
-      // Switch to unified mode when template has a unified instruction OR rules
-      const hasUnifiedContent = !!(template.unified_instruction && template.unified_instruction.trim());
-      const hasUnifiedRules = (template.unified_rules || []).length > 0;
-      const hasUnified = hasUnifiedContent || hasUnifiedRules;
-      const updated: Project = {
-        ...proj,
-        description: proj.description || template.description,
-        agents: [...new Set([...proj.agents, ...template.agents])],
-        skills: [...new Set([...proj.skills, ...template.skills])],
-        mcp_servers: [...new Set([...proj.mcp_servers, ...template.mcp_servers])],
-        providers: [...new Set([...proj.providers, ...template.providers])],
-        ...(template.user_agents.length > 0
-          ? { user_agents: [...new Set([...(proj.user_agents ?? []), ...template.user_agents])] }
-          : {}),
-        ...(template.user_commands.length > 0
-          ? { user_commands: [...new Set([...(proj.user_commands ?? []), ...template.user_commands])] }
-          : {}),
-        ...(hasUnified ? { instruction_mode: "unified" } : {}),
-      };
-      await invoke("save_project", { name: projectName, data: JSON.stringify(updated, null, 2) });
-
-      // Apply unified instruction and/or rules to the project.
-      // Rules-only templates (no instruction content) are valid — rules still
-      // need to be persisted into file_rules and then written out.
-      if (hasUnified) {
-        // Re-read the just-saved project so we have the exact on-disk state
-        // (save_project may have synced additional fields) before mutating it.
-        const latestRaw: string = await invoke("read_project", { name: projectName });
-        const latestProj = JSON.parse(latestRaw);
-        if (hasUnifiedRules) {
-          // Merge template rules into _project (the canonical key used by the
-          // Rules tab and sync engine).  Using _project ensures template rules
-          // are visible in the Rules UI and are not silently dropped when the
-          // user later toggles rules from the Rules tab (which only writes _project).
-          const existingProjectRules: string[] = (latestProj.file_rules || {})["_project"] || [];
-          const mergedRules = [...new Set([...existingProjectRules, ...(template.unified_rules || [])])];
-          const withRules = {
-            ...latestProj,
-            file_rules: {
-              ...(latestProj.file_rules || {}),
-              _project: mergedRules,
-            },
-          };
-          await invoke("save_project", { name: projectName, data: JSON.stringify(withRules, null, 2) });
-        }
-        // Write the content (may be empty string if rules-only) — backend fans
-        // out to all agent files and appends the rules section.
-        await invoke("save_project_file", {
-          name: projectName,
-          filename: "_unified",
-          content: template.unified_instruction || "",
-        });
-      }
-
-      // Write project files to the project's directory (non-destructive: only if file doesn't exist)
-      for (const pf of template.project_files) {
-        if (pf.filename && pf.content) {
-          try {
-            await invoke("save_project_file", {
-              name: projectName,
-              filename: pf.filename,
-              content: pf.content,
-            });
-          } catch { /* skip files that can't be written (e.g. no directory set) */ }
-        }
-      }
-
+      const raw: string = await invoke("apply_project_template", {
+        templateName: selectedName,
+        projectName,
+      });
+      const report = JSON.parse(raw) as TemplateApplyReport;
       await loadAllProjects();
       setShowApplyPicker(false);
+
+      const line = (label: string, items: string[]) =>
+        items.length > 0 ? `\n${label}: ${items.join(", ")}` : "";
+      const unresolved = [
+        ...report.unresolved_skills,
+        ...report.unresolved_servers,
+        ...report.unresolved_rules,
+      ];
+      const details =
+        line("Added agents", report.added_agents) +
+        line("Added skills", report.added_skills) +
+        line("Added MCP servers", report.added_servers) +
+        line("Installed from bundle", report.installed_skills) +
+        line("Attached rules", report.attached_rules) +
+        line("Written files", report.written_files) +
+        line("Skipped (already existed)", report.skipped_files) +
+        line("Not installed", unresolved) +
+        report.warnings.map((w) => `\n⚠ ${w}`).join("");
+
+      const syncNow = await ask(
+        `Applied "${selectedName}" to "${projectName}".${details || "\nNothing new was added."}\n\nSync agent configs now?`,
+        { title: "Template Applied", kind: "info", okLabel: "Sync now", cancelLabel: "Review first" }
+      );
+      if (syncNow) {
+        await invoke("sync_project", { name: projectName });
+      }
       setApplyStatus(`Applied to "${projectName}"`);
       setTimeout(() => setApplyStatus(null), 3000);
     } catch (err: any) {
@@ -812,13 +827,51 @@ export default function ProjectTemplates({
           <span className="text-[11px] font-semibold text-text-muted tracking-wider uppercase">
             Templates
           </span>
-          <button
-            onClick={startCreate}
-            className="text-text-muted hover:text-text-base transition-colors p-1 hover:bg-bg-sidebar rounded"
-            title="Create New Template"
-          >
-            <Plus size={14} />
-          </button>
+          <div className="flex items-center gap-0.5">
+            <div className="relative">
+              <button
+                onClick={() => {
+                  if (!showFromProjectPicker) loadAllProjects();
+                  setShowFromProjectPicker(!showFromProjectPicker);
+                }}
+                className="text-text-muted hover:text-text-base transition-colors p-1 hover:bg-bg-sidebar rounded"
+                title="Create Template from Project"
+              >
+                <FolderPlus size={14} />
+              </button>
+              {showFromProjectPicker && (
+                <div className="absolute right-0 top-full mt-1 w-56 bg-bg-input border border-border-strong/40 rounded-lg shadow-xl z-20 overflow-hidden">
+                  {allProjects.length === 0 ? (
+                    <div className="px-3 py-4 text-[12px] text-text-muted text-center">
+                      No projects yet
+                    </div>
+                  ) : (
+                    <div>
+                      <div className="px-3 pt-2.5 pb-1 text-[10px] font-semibold text-text-muted uppercase tracking-wider">
+                        Create from
+                      </div>
+                      {allProjects.map((p) => (
+                        <button
+                          key={p.name}
+                          onClick={() => createFromProject(p.name)}
+                          className="w-full text-left px-3 py-2 text-[13px] text-text-base hover:bg-bg-sidebar transition-colors"
+                        >
+                          {p.name}
+                        </button>
+                      ))}
+                    </div>
+                  )}
+                </div>
+              )}
+            </div>
+            <button
+              onClick={startCreate}
+              className="text-text-muted hover:text-text-base transition-colors p-1 hover:bg-bg-sidebar rounded"
+              title="Create New Template"
+            >
+              <Plus size={14} />
+            </button>
+          </div>
         </div>
 
         <div className="flex-1 overflow-y-auto py-2 custom-scrollbar">
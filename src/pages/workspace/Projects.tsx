@@ -14,6 +14,7 @@ import { MemoryBrowser } from "../../components/MemoryBrowser";
 import { ClaudeMemoryPanel } from "../../components/ClaudeMemoryPanel";
 import Features, { type Feature } from "./Features";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { ask } from "@tauri-apps/plugin-dialog";
 import { handleExternalLinkClick } from "../../lib/externalLinks";
 import {
@@ -74,6 +75,8 @@ import {
   Puzzle,
   Layers,
   MessagesSquare,
+  Eye,
+  EyeOff,
 } from "lucide-react";
 
 interface CustomRule {
@@ -81,6 +84,15 @@ interface CustomRule {
   content: string;
 }
 
+/** Rule text found in an instruction file that matches no registry rule. */
+interface RuleCandidate {
+  machine_name: string;
+  name: string;
+  content: string;
+  files: string[];
+  dismissed?: boolean;
+}
+
 interface CustomAgent {
   name: string;
   content: string;
@@ -111,6 +123,8 @@ interface Project {
   description: string;
   directory: string;
   skills: string[];
+  /** Skill sets applied as a unit; their skills are added at sync time. */
+  skill_sets?: string[];
   local_skills: string[];
   mcp_servers: string[];
   disabled_mcp_servers?: string[];
@@ -119,6 +133,8 @@ interface Project {
   created_at: string;
   updated_at: string;
   last_activity?: string;
+  locked?: boolean;
+  locked_reason?: string;
   created_by?: string;
   file_rules?: Record<string, string[]>;
   instruction_mode?: string;
@@ -126,6 +142,8 @@ interface Project {
   agent_options?: Record<string, AgentOptions>;
   /** Inline custom rules stored directly in this project (not in the global registry). */
   custom_rules?: CustomRule[];
+  /** Unrecognised rule text recovered from instruction files by autodetect. */
+  rule_candidates?: RuleCandidate[];
   /** Inline custom agents stored directly in this project. Written to .claude/agents/ (or equivalent) on sync. */
   custom_agents?: CustomAgent[];
   /** Tool names detected as present in this project (populated by autodetect). */
@@ -138,6 +156,19 @@ interface Project {
   custom_commands?: CustomCommand[];
   /** Inline custom skills stored directly in this project. Written to skill directories on sync. */
   custom_skills?: CustomSkill[];
+  /** Keep project.json in the repository's .automatic/ directory. Absent means true. */
+  store_config_in_repo?: boolean;
+}
+
+/** Payload of the `autodetect-progress` event: findings so far. */
+interface AutodetectProgress {
+  project: string;
+  step: "agents" | "skills" | "mcp_servers" | "tools";
+  agents: string[];
+  skills: string[];
+  local_skills: string[];
+  dirs_scanned: number;
+  truncated: boolean;
 }
 
 interface AgentInfo {
@@ -146,6 +177,27 @@ interface AgentInfo {
   description: string;
   /** Non-null when this agent cannot have MCP config written by Automatic. */
   mcp_note: string | null;
+  /** True when the agent's MCP settings accept a pasted config snippet. */
+  mcp_snippet?: boolean;
+}
+
+/** The project's MCP servers ready to paste into an agent (see get_agent_mcp_export). */
+interface AgentMcpExport {
+  format: string;
+  content: string;
+  instructions: string;
+}
+
+/** An MCP server left out of a sync because its keychain entries are missing. */
+interface McpSecretWarning {
+  server: string;
+  missing: string[];
+}
+
+/** What sync_project wrote, and the servers it had to leave out. */
+interface SyncReport {
+  files: string[];
+  secret_warnings: McpSecretWarning[];
 }
 
 interface DriftedFile {
@@ -155,6 +207,8 @@ interface DriftedFile {
   expected?: string;
   /** Content currently on disk. Present only when reason === "modified". */
   actual?: string;
+  /** Server-level differences for modified JSON MCP configs. */
+  details?: string[];
 }
 
 interface AgentDrift {
@@ -207,7 +261,10 @@ interface DriftReport {
 interface ProjectFileInfo {
   filename: string;
   agents: string[];
+  agent_ids?: string[];
   exists: boolean;
+  /** Read by more than one selected agent in per-agent mode. */
+  shared?: boolean;
   target_files?: string[];
 }
 
@@ -910,6 +967,13 @@ function DriftDiffModal({ file, agentLabel, projectName, onClose, onResolved }:
             <X size={16} />
           </button>
         </div>
+        {file.details && file.details.length > 0 && (
+          <ul className="px-5 py-2 border-b border-border-strong flex-shrink-0 text-[12px] text-text-muted space-y-0.5">
+            {file.details.map((detail) => (
+              <li key={detail}>{detail}</li>
+            ))}
+          </ul>
+        )}
 
         {/* Legend */}
         {diffLines && (
@@ -2336,6 +2400,7 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
   // Wizard state (used while isCreating === true)
   const [wizardStep, setWizardStep] = useState<1 | 2 | 3>(1);
   const [wizardDiscovering, setWizardDiscovering] = useState(false);
+  const [wizardProgress, setWizardProgress] = useState<AutodetectProgress | null>(null);
   const [wizardDiscoveredAgents, setWizardDiscoveredAgents] = useState<string[]>([]);
   /** Non-empty when the wizard was launched from a "New project from template" action. */
   const [wizardSourceTemplates, setWizardSourceTemplates] = useState<string[]>([]);
@@ -2348,6 +2413,7 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
   // Available items to pick from
   const [availableAgents, setAvailableAgents] = useState<AgentInfo[]>([]);
   const [availableSkills, setAvailableSkills] = useState<string[]>([]);
+  const [availableSkillSets, setAvailableSkillSets] = useState<{ name: string; description: string; skills: string[] }[]>([]);
   const [availableMcpServers, setAvailableMcpServers] = useState<string[]>([]);
 
   // Plugin-locked resources — skills/rules that cannot be removed because
@@ -2364,6 +2430,8 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
   // null = unknown/not yet checked, DriftReport = result of last check
   const [driftReport, setDriftReport] = useState<DriftReport | null>(null);
   const driftCheckInFlight = useRef(false);
+  // Projects whose config files are watched for hand edits (persisted in settings)
+  const [driftWatched, setDriftWatched] = useState<string[]>([]);
 
   // Per-project drift indicator: true = drifted, false = clean, undefined = unknown
   const [driftByProject, setDriftByProject] = useState<Record<string, boolean>>({});
@@ -2669,6 +2737,7 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
     loadProjects();
     loadAvailableAgents();
     loadAvailableSkills();
+    loadAvailableSkillSets();
     loadAvailableMcpServers();
     loadAvailableTemplates();
     loadAvailableRules();
@@ -2686,14 +2755,15 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
       const iconIds = editors.map((e) => e.id);
       Promise.all(
         iconIds.map((id) =>
-          invoke<string>("get_editor_icon", { editorId: id })
+          invoke<string | null>("get_editor_icon", { editorId: id })
             .then((path) => ({ id, path }))
             .catch(() => null)
         )
       ).then((results) => {
         const paths: Record<string, string> = {};
         for (const r of results) {
-          if (r) paths[r.id] = r.path;
+          // null: no icon on this machine — EditorIcon falls back to SVG.
+          if (r?.path) paths[r.id] = r.path;
         }
         setEditorIconPaths(paths);
       });
@@ -2851,6 +2921,22 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
     return () => clearInterval(interval);
   }, [selectedName, project?.directory, project?.agents.length, dirty, isCreating]);
 
+  useEffect(() => {
+    invoke<any>("read_settings")
+      .then((raw) => setDriftWatched(raw.drift_watch_projects ?? []))
+      .catch(() => {});
+  }, []);
+
+  const toggleDriftWatch = async (name: string) => {
+    const watching = driftWatched.includes(name);
+    try {
+      await invoke(watching ? "disable_drift_watch" : "enable_drift_watch", { name });
+      setDriftWatched((prev) => (watching ? prev.filter((p) => p !== name) : [...prev, name]));
+    } catch (err: any) {
+      setError(`Failed to ${watching ? "stop" : "start"} watching for drift: ${err}`);
+    }
+  };
+
   // Background drift check for all projects (for sidebar indicators)
   useEffect(() => {
     if (projects.length === 0) return;
@@ -3299,6 +3385,20 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
     }
   };
 
+  const loadAvailableSkillSets = async () => {
+    try {
+      setAvailableSkillSets(await invoke("list_skill_sets"));
+    } catch {
+      // Skill sets may not exist yet
+    }
+  };
+
+  const updateSkillSets = (skillSets: string[]) => {
+    if (!project) return;
+    updateField("skill_sets", skillSets);
+    saveProjectSnapshot({ ...project, skill_sets: skillSets });
+  };
+
   const loadAvailableMcpServers = async () => {
     try {
       const result: string[] = await invoke("list_mcp_server_configs");
@@ -4090,6 +4190,7 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
         instruction_mode: stored.instruction_mode || "per-agent",
         agent_options: stored.agent_options,
         custom_rules: stored.custom_rules || [],
+        rule_candidates: stored.rule_candidates || [],
         tools: stored.tools || [],
         custom_agents: stored.custom_agents || [],
         user_agents: stored.user_agents || [],
@@ -4168,6 +4269,7 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
         instruction_mode: parsed.instruction_mode || "per-agent",
         agent_options: parsed.agent_options,
         custom_rules: parsed.custom_rules || [],
+        rule_candidates: parsed.rule_candidates || [],
         custom_agents: parsed.custom_agents || [],
         user_agents: parsed.user_agents || [],
         custom_commands: parsed.custom_commands || [],
@@ -4450,6 +4552,9 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
    */
   const cancelCreate = async () => {
     const stub = wizardStubName.current;
+    if (stub && wizardDiscovering) {
+      await invoke("cancel_autodetect", { name: stub }).catch(() => {});
+    }
     wizardStubName.current = null;
     setIsCreating(false);
     setProject(null);
@@ -4638,9 +4743,12 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
 
     // Fetch the list of files that would be cleaned up (read-only preview)
     let preview: string[] = [];
+    let uniqueServers: string[] = [];
     try {
       const raw: string = await invoke("get_agent_cleanup_preview", { name, agentId });
-      preview = JSON.parse(raw);
+      const parsed = JSON.parse(raw) as { files: string[]; unique_mcp_servers: string[] };
+      preview = parsed.files;
+      uniqueServers = parsed.unique_mcp_servers;
     } catch {
       // Non-fatal — proceed with a generic message if the preview fails
     }
@@ -4656,8 +4764,19 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
     );
     if (!confirmed) return;
 
+    // Servers discovered only from this agent: keep them (now user-owned)
+    // or remove them along with the agent.
+    let uniqueServersAction: "keep" | "remove" = "keep";
+    if (uniqueServers.length > 0) {
+      const keep = await ask(
+        `These MCP servers were only found in ${agentLabel}'s config:\n${uniqueServers.map((s) => `  • ${s}`).join("\n")}\n\nKeep them in the project for the remaining agents?`,
+        { title: "Remove Agent", kind: "info", okLabel: "Keep", cancelLabel: "Remove" }
+      );
+      uniqueServersAction = keep ? "keep" : "remove";
+    }
+
     try {
-      await invoke("remove_agent_from_project", { name, agentId });
+      await invoke("remove_agent_from_project", { name, agentId, uniqueServers: uniqueServersAction });
       trackProjectAgentRemoved(name, agentId);
       await reloadProject(name);
       setDirty(false);
@@ -4734,6 +4853,14 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
     }
   };
 
+  /** Surface servers a sync left out so a missing keychain entry is not silent. */
+  const logSecretWarnings = (name: string, warnings: McpSecretWarning[]) => {
+    for (const warning of warnings) {
+      const missing = warning.missing.map((key) => `'${key}'`).join(", ");
+      log(`${name}: MCP server '${warning.server}' was not written: no keychain entry for ${missing}`, "error");
+    }
+  };
+
   const handleSync = async () => {
     const name = isCreating ? newName.trim() : selectedName;
     if (!name || !project) return;
@@ -4747,8 +4874,9 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
     // Clean state: just re-sync from what's on disk
     try {
       setSyncStatus("syncing");
-      const result: string = await invoke("sync_project", { name });
-      const files: string[] = JSON.parse(result);
+      const report = await invoke<SyncReport>("sync_project", { name });
+      const files = report.files;
+      logSecretWarnings(name, report.secret_warnings);
       trackProjectSynced(name);
       setSyncStatus(`Synced ${files.length} config${files.length !== 1 ? "s" : ""}`);
       setDriftReport({ drifted: false, agents: [] });
@@ -4811,8 +4939,9 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
       // Sync each drifted project sequentially so we don't flood the backend
       for (const name of driftedProjects) {
         try {
-          const result: string = await invoke("sync_project", { name });
-          const files: string[] = JSON.parse(result);
+          const report = await invoke<SyncReport>("sync_project", { name });
+          const files = report.files;
+          logSecretWarnings(name, report.secret_warnings);
           trackProjectSynced(name);
           // Mark this project as clean immediately so the UI reflects progress
           setDriftByProject((prev) => ({ ...prev, [name]: false }));
@@ -4828,6 +4957,19 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
     }
   };
 
+  /** Copy the project's MCP servers in the format `agentId` accepts when pasted. */
+  const copyMcpSnippet = async (agentId: string, label: string) => {
+    if (!selectedName) return;
+    try {
+      const setup = await invoke<AgentMcpExport | null>("get_agent_mcp_export", { name: selectedName, agentId });
+      if (!setup) return;
+      await navigator.clipboard.writeText(setup.content);
+      log(`Copied MCP config for ${label}. ${setup.instructions}`, "success");
+    } catch (err: any) {
+      setError(`Failed to copy MCP config: ${err}`);
+    }
+  };
+
   const handleOpenInEditor = async (editorId: string) => {
     if (!project?.directory) return;
     setOpenInDropdownOpen(false);
@@ -5605,6 +5747,15 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                     <Trash2 size={12} /> Remove
                   </button>
                 )}
+                {!isCreating && selectedName && !dirty && project.directory && project.agents.length > 0 && (
+                  <button
+                    onClick={() => toggleDriftWatch(selectedName)}
+                    className={`flex items-center gap-1.5 px-3 py-1 bg-bg-input hover:bg-surface-hover rounded text-[12px] font-medium border border-border-strong transition-colors shadow-sm ${driftWatched.includes(selectedName) ? "text-brand" : "text-text-muted hover:text-text-base"}`}
+                    title={driftWatched.includes(selectedName) ? "Watching config files for hand edits — click to stop" : "Notify me when config files are edited outside Automatic"}
+                  >
+                    {driftWatched.includes(selectedName) ? <Eye size={12} /> : <EyeOff size={12} />} Watch
+                  </button>
+                )}
                 {/* Sync / in-sync indicator — shown when project has directory + agents configured */}
                 {!dirty && project.directory && project.agents.length > 0 && (
                   syncStatus === "syncing" ? (
@@ -5954,6 +6105,19 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                           </button>
                         </div>
 
+                        <label className="flex items-start gap-2 cursor-pointer">
+                          <input
+                            type="checkbox"
+                            checked={project.store_config_in_repo !== false}
+                            onChange={(e) => updateField("store_config_in_repo", e.target.checked)}
+                            className="w-4 h-4 mt-0.5 accent-brand cursor-pointer"
+                          />
+                          <span className="text-[12px] text-text-muted leading-snug">
+                            Store project config in the repository (<span className="font-mono">.automatic/project.json</span>).
+                            Turn off to keep it in Automatic's own data directory.
+                          </span>
+                        </label>
+
                         {project.directory && (
                           <button
                             disabled={wizardDiscovering}
@@ -5967,13 +6131,21 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                               setError(null);
                               try {
                                 // Save minimal stub so autodetect can read it back
-                                const stub = { ...emptyProject(name), directory: dir, name };
+                                const stub = { ...emptyProject(name), directory: dir, name, store_config_in_repo: project.store_config_in_repo };
                                 if (userId && !stub.created_by) stub.created_by = userId;
                                 await invoke("save_project", { name, data: JSON.stringify(stub, null, 2) });
                                 // Track stub name so cancelCreate can clean it up if the user navigates away
                                 wizardStubName.current = name;
-                                // Run read-only autodetection
-                                const raw: string = await invoke("autodetect_project_dependencies", { name });
+                                // Run read-only autodetection, showing findings as they arrive
+                                const unlisten = await listen<AutodetectProgress>("autodetect-progress", (event) => {
+                                  if (event.payload.project === name) setWizardProgress(event.payload);
+                                });
+                                let raw: string;
+                                try {
+                                  raw = await invoke("autodetect_project_dependencies", { name });
+                                } finally {
+                                  unlisten();
+                                }
                                 const detected = JSON.parse(raw) as Project;
                                 // Merge: start from current project state (which holds any
                                 // template-applied skills/MCP/agents), then add autodetected
@@ -6003,9 +6175,12 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                                 setWizardDiscoveredAgents(detected.agents);
                                 setWizardStep(2);
                               } catch (err: any) {
-                                setError(`Autodetect failed: ${err}`);
+                                if (!String(err).includes("cancelled")) {
+                                  setError(`Autodetect failed: ${err}`);
+                                }
                               } finally {
                                 setWizardDiscovering(false);
+                                setWizardProgress(null);
                               }
                             }}
                             className="w-full flex items-center justify-center gap-2 px-4 py-2.5 bg-brand hover:bg-brand-hover disabled:opacity-50 disabled:cursor-not-allowed text-white text-[13px] font-medium rounded shadow-sm transition-colors"
@@ -6017,6 +6192,28 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                             )}
                           </button>
                         )}
+                        {wizardDiscovering && (
+                          <div className="flex items-center justify-between text-[12px] text-text-muted">
+                            <span>
+                              {wizardProgress
+                                ? `${wizardProgress.agents.length} agent${wizardProgress.agents.length !== 1 ? "s" : ""}, ${
+                                    wizardProgress.skills.length + wizardProgress.local_skills.length
+                                  } skill${wizardProgress.skills.length + wizardProgress.local_skills.length !== 1 ? "s" : ""} found so far${
+                                    wizardProgress.truncated ? " (skills search stopped at its directory limit)" : ""
+                                  }`
+                                : "Looking for agents…"}
+                            </span>
+                            <button
+                              onClick={() => {
+                                const stub = wizardStubName.current;
+                                if (stub) invoke("cancel_autodetect", { name: stub }).catch(() => {});
+                              }}
+                              className="text-text-muted hover:text-danger transition-colors"
+                            >
+                              Cancel scan
+                            </button>
+                          </div>
+                        )}
                       </div>
                     </>
                   )}
@@ -6375,7 +6572,9 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                                   <FileText size={13} className={activeProjectFile === f.filename ? "text-text-base" : f.exists ? "text-text-muted" : "text-text-muted"} />
                                   <div className="min-w-0">
                                     <div className={`truncate ${!f.exists ? "opacity-50" : ""}`}>{f.filename}</div>
-                                    <div className="text-[10px] text-text-muted truncate">{f.agents.join(", ")}</div>
+                                    <div className="text-[10px] text-text-muted truncate" title={f.shared ? `Shared file: edits apply to ${f.agents.join(" & ")}` : undefined}>
+                                      {f.shared ? `Shared · ${f.agents.join(", ")}` : f.agents.join(", ")}
+                                    </div>
                                   </div>
                                 </button>
                               </li>
@@ -7035,6 +7234,62 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                         )}
                       </div>
 
+                      {/* ── Recovered rules ── */}
+                      {(project.rule_candidates || []).some(c => !c.dismissed) && (
+                        <section>
+                          <div className="flex items-center gap-2 mb-3">
+                            <AlertCircle size={13} className="text-text-muted" />
+                            <span className="text-[11px] font-semibold text-text-muted tracking-wider uppercase">Found In Instruction Files</span>
+                          </div>
+                          <p className="text-[12px] text-text-muted mb-3">
+                            These rules were synced on another machine and are not in your registry. Import them to keep them, or dismiss them to let the next sync remove them.
+                          </p>
+                          <div className="space-y-2">
+                            {(project.rule_candidates || []).filter(c => !c.dismissed).map((candidate) => (
+                              <div key={candidate.machine_name} className="flex items-center gap-3 px-3 py-2.5 rounded-lg border border-border-strong/40 bg-bg-input">
+                                <ScrollText size={14} className="flex-shrink-0 text-text-muted" />
+                                <div className="flex-1 min-w-0">
+                                  <div className="text-[13px] font-medium text-text-base truncate">{candidate.name}</div>
+                                  <div className="text-[11px] text-text-muted truncate mt-0.5">
+                                    {candidate.machine_name} · {candidate.files.join(", ")}
+                                  </div>
+                                </div>
+                                <div className="flex items-center gap-1 flex-shrink-0">
+                                  <button
+                                    onClick={async () => {
+                                      try {
+                                        await invoke("import_rule_candidate", { name: project.name, machineName: candidate.machine_name });
+                                        await loadAvailableRules();
+                                        await reloadProject(project.name);
+                                      } catch (err: any) {
+                                        setError(`Failed to import rule: ${err}`);
+                                      }
+                                    }}
+                                    className="flex items-center gap-1 px-2.5 py-1 text-[12px] text-brand hover:text-brand-hover font-medium transition-colors"
+                                  >
+                                    <Plus size={12} /> Import
+                                  </button>
+                                  <button
+                                    onClick={async () => {
+                                      try {
+                                        await invoke("dismiss_rule_candidate", { name: project.name, machineName: candidate.machine_name });
+                                        await reloadProject(project.name);
+                                      } catch (err: any) {
+                                        setError(`Failed to dismiss rule: ${err}`);
+                                      }
+                                    }}
+                                    className="p-1.5 text-text-muted hover:text-text-base hover:bg-bg-sidebar rounded transition-colors"
+                                    title="Dismiss"
+                                  >
+                                    <X size={12} />
+                                  </button>
+                                </div>
+                              </div>
+                            ))}
+                          </div>
+                        </section>
+                      )}
+
                       {/* ── Custom Rules ── */}
                       <section>
                         <div className="flex items-center justify-between mb-3">
@@ -8260,7 +8515,7 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                         emptyMessage="No agent tools selected. Add tools to enable config sync."
                         agentOptions={project.agent_options}
                         onOptionChange={(agentId, patch) => {
-                          const current = project.agent_options?.[agentId] ?? { claude_rules_in_dot_claude: true };
+                          const current = project.agent_options?.[agentId] ?? { claude_rules_in_dot_claude: true, gemini_use_extension: false, automatic_mcp_read_only: false };
                           setProject({
                             ...project,
                             agent_options: {
@@ -8492,6 +8747,49 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                       );
                     })()}
 
+                    {/* Skill Sets */}
+                    {(availableSkillSets.length > 0 || (project.skill_sets ?? []).length > 0) && (
+                      <section>
+                        <h4 className="text-[11px] font-semibold text-text-muted tracking-wider uppercase mb-2">Skill Sets</h4>
+                        <div className="flex flex-wrap items-center gap-2">
+                          {(project.skill_sets ?? []).map((setName) => {
+                            const set = availableSkillSets.find((s) => s.name === setName);
+                            return (
+                              <span
+                                key={setName}
+                                className={`flex items-center gap-1 px-2 py-1 rounded text-[12px] border ${set ? "border-border-strong/40 text-text-base" : "border-danger/40 text-danger"}`}
+                                title={set ? `${set.description ? set.description + " — " : ""}${set.skills.join(", ")}` : "This skill set no longer exists"}
+                              >
+                                {setName}
+                                {set && <span className="text-text-muted">({set.skills.length})</span>}
+                                <button
+                                  onClick={() => updateSkillSets((project.skill_sets ?? []).filter((s) => s !== setName))}
+                                  className="text-text-muted hover:text-danger"
+                                  title="Remove skill set"
+                                >
+                                  <X size={10} />
+                                </button>
+                              </span>
+                            );
+                          })}
+                          {availableSkillSets.some((s) => !(project.skill_sets ?? []).includes(s.name)) && (
+                            <select
+                              value=""
+                              onChange={(e) => e.target.value && updateSkillSets([...(project.skill_sets ?? []), e.target.value])}
+                              className="px-2 py-1 rounded border border-border-strong/40 bg-bg-input-dark text-[12px] text-text-base"
+                            >
+                              <option value="">Add skill set…</option>
+                              {availableSkillSets
+                                .filter((s) => !(project.skill_sets ?? []).includes(s.name))
+                                .map((s) => (
+                                  <option key={s.name} value={s.name}>{s.name}</option>
+                                ))}
+                            </select>
+                          )}
+                        </div>
+                      </section>
+                    )}
+
                     {/* Global Skills */}
                      <section>
                         <SkillSelector
@@ -8656,7 +8954,7 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
 
                 {/* ── MCP Servers tab ──────────────────────────────────── */}
                 {projectTab === "mcp_servers" && (() => {
-                  // Agents that cannot have MCP config written by Automatic (e.g. Warp, Goose).
+                  // Agents that cannot have MCP config written by Automatic (e.g. Warp).
                   const noMcpAgents = availableAgents.filter(
                     (a) => project.agents.includes(a.id) && a.mcp_note
                   );
@@ -8672,7 +8970,17 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                         <div>
                           <p className="text-[13px] font-medium text-text-base mb-0.5">MCP not configurable via Automatic</p>
                           {noMcpAgents.map((a) => (
-                            <p key={a.id} className="text-[12px] text-text-muted leading-relaxed">{a.mcp_note}</p>
+                            <p key={a.id} className="text-[12px] text-text-muted leading-relaxed">
+                              {a.mcp_note}
+                              {a.mcp_snippet && (
+                                <button
+                                  onClick={() => copyMcpSnippet(a.id, a.label)}
+                                  className="ml-1.5 text-brand hover:underline"
+                                >
+                                  Copy {a.label.replace(/ \(Beta\)$/, "")} config
+                                </button>
+                              )}
+                            </p>
                           ))}
                         </div>
                       </div>
@@ -8687,6 +8995,14 @@ export default function Projects({ resetKey, initialProject = null, onInitialPro
                           {noMcpAgents.map((a) => (
                             <p key={a.id} className="text-[12px] text-warning/80 leading-relaxed">
                               <span className="font-medium">{a.label}:</span> {a.mcp_note}
+                              {a.mcp_snippet && (
+                                <button
+                                  onClick={() => copyMcpSnippet(a.id, a.label)}
+                                  className="ml-1.5 text-brand hover:underline"
+                                >
+                                  Copy {a.label.replace(/ \(Beta\)$/, "")} config
+                                </button>
+                              )}
                             </p>
                           ))}
                         </div>
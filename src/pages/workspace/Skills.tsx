@@ -28,11 +28,13 @@ import {
   Tag,
   ChevronDown,
   Puzzle,
+  Library,
 } from "lucide-react";
 import { ICONS } from "../../lib/icons";
 import { SkillAvatar } from "../../components/SkillAvatar";
 import { TokenPill } from "../../components/TokenPill";
 import SkillImportDialog from "../../components/SkillImportDialog";
+import SkillResourceEditor from "../../components/SkillResourceEditor";
 
 interface SkillSource {
   source: string; // "owner/repo"
@@ -45,9 +47,12 @@ interface SkillEntry {
   sources: string[]; // e.g., ["agents", "claude", "codex", "cline"]
   source?: SkillSource;
   has_resources: boolean;
+  description?: string;
   license?: string;
   plugin_id?: string;
   collection?: string;
+  /** "library" when the skill lives only in the skill library, out of agents' global paths. */
+  scope?: "global" | "library";
 }
 
 interface SkillCollection {
@@ -129,6 +134,8 @@ interface SkillPreviewProps {
   sources?: string[];
   resources?: SkillResources | null;
   license?: string;
+  /** Open a companion file (path relative to the skill directory) for editing. */
+  onOpenResource?: (path: string) => void;
 }
 
 function resolveSkillAuthorDescriptor(source?: SkillSource, sources?: string[]): AuthorDescriptor {
@@ -145,7 +152,7 @@ function resolveSkillAuthorDescriptor(source?: SkillSource, sources?: string[]):
   return { type: "local" };
 }
 
-function SkillPreview({ content, source, sources, resources, license }: SkillPreviewProps) {
+function SkillPreview({ content, source, sources, resources, license, onOpenResource }: SkillPreviewProps) {
   const { meta, body } = parseFrontmatter(content);
   const displayName = meta.name || "";
   const description = meta.description || "";
@@ -239,12 +246,16 @@ function SkillPreview({ content, source, sources, resources, license }: SkillPre
                     {isOpen && (
                       <div className="bg-bg-input/40 border-t border-border-strong/50">
                         {dir.files.map(f => (
-                          <div key={f.path} className="flex items-center gap-2 pl-9 pr-3 py-1.5">
+                          <button
+                            key={f.path}
+                            onClick={() => onOpenResource?.(`${dir.name}/${f.path}`)}
+                            className="w-full flex items-center gap-2 pl-9 pr-3 py-1.5 hover:bg-bg-sidebar/60 transition-colors text-left"
+                          >
                             <svg width="11" height="11" viewBox="0 0 16 16" fill="currentColor" className="shrink-0 text-text-muted">
                               <path d="M2 1.75C2 .784 2.784 0 3.75 0h6.586c.464 0 .909.184 1.237.513l2.914 2.914c.329.328.513.773.513 1.237v9.586A1.75 1.75 0 0 1 13.25 16h-9.5A1.75 1.75 0 0 1 2 14.25V1.75z"/>
                             </svg>
                             <span className="text-[12px] font-mono text-text-muted">{f.path}</span>
-                          </div>
+                          </button>
                         ))}
                       </div>
                     )}
@@ -254,12 +265,16 @@ function SkillPreview({ content, source, sources, resources, license }: SkillPre
 
               {/* Root-level files */}
               {resources!.root_files.map(f => (
-                <div key={f.path} className="flex items-center gap-2 px-3 py-2">
+                <button
+                  key={f.path}
+                  onClick={() => onOpenResource?.(f.path)}
+                  className="w-full flex items-center gap-2 px-3 py-2 hover:bg-bg-sidebar/60 transition-colors text-left"
+                >
                   <svg width="12" height="12" viewBox="0 0 16 16" fill="currentColor" className="shrink-0 text-text-muted ml-[22px]">
                     <path d="M2 1.75C2 .784 2.784 0 3.75 0h6.586c.464 0 .909.184 1.237.513l2.914 2.914c.329.328.513.773.513 1.237v9.586A1.75 1.75 0 0 1 13.25 16h-9.5A1.75 1.75 0 0 1 2 14.25V1.75z"/>
                   </svg>
                   <span className="text-[12px] font-mono text-text-muted">{f.path}</span>
-                </div>
+                </button>
               ))}
             </div>
           </div>
@@ -466,6 +481,7 @@ export default function Skills({ initialSkill = null, onInitialSkillConsumed, on
 
   // Companion resources for the selected skill
   const [skillResources, setSkillResources] = useState<SkillResources | null>(null);
+  const [openResource, setOpenResource] = useState<string | null>(null);
 
   // Projects and templates that reference the selected skill
   const [skillUsedBy, setSkillUsedBy] = useState<SkillUsedBy | null>(null);
@@ -635,6 +651,16 @@ export default function Skills({ initialSkill = null, onInitialSkillConsumed, on
 
 
 
+  const handleScopeChange = async (name: string, toLibrary: boolean) => {
+    try {
+      await invoke(toLibrary ? "demote_skill_to_library" : "promote_skill_to_global", { name });
+      await loadSkills();
+      setError(null);
+    } catch (err: any) {
+      setError(`Failed to move skill: ${err}`);
+    }
+  };
+
   const handleDuplicate = async (name: string) => {
     // Build a unique local name: "<name>-local", or "<name>-local-2", etc.
     const base = `${name}-local`;
@@ -1090,6 +1116,19 @@ export default function Skills({ initialSkill = null, onInitialSkillConsumed, on
                     <span>Read-only</span>
                   </span>
                 )}
+                {!isEditing && selectedEntry && !selectedEntry.plugin_id && selectedSkill !== "automatic" && (
+                  <button
+                    onClick={() => handleScopeChange(selectedSkill!, selectedEntry.scope !== "library")}
+                    className="flex items-center gap-1.5 px-3 py-1.5 hover:bg-bg-sidebar text-text-muted hover:text-text-base rounded text-[12px] font-medium transition-colors"
+                    title={
+                      selectedEntry.scope === "library"
+                        ? "Install globally so every agent sees this skill"
+                        : "Keep this skill out of agents' global skill folders; projects that use it still get a copy"
+                    }
+                  >
+                    {selectedEntry.scope === "library" ? <><Globe size={12} /> Make Global</> : <><Library size={12} /> Move to Library</>}
+                  </button>
+                )}
                 {!isEditing && (
                   <button
                     onClick={() => handleDuplicate(selectedSkill!)}
@@ -1229,6 +1268,7 @@ export default function Skills({ initialSkill = null, onInitialSkillConsumed, on
                       sources={selectedEntry?.sources}
                       resources={skillResources}
                       license={selectedEntry?.license}
+                      onOpenResource={setOpenResource}
                     />
                   </div>
 
@@ -1323,6 +1363,17 @@ export default function Skills({ initialSkill = null, onInitialSkillConsumed, on
         )}
       </div>
 
+      {selectedSkill && (
+        <SkillResourceEditor
+          skill={selectedSkill}
+          path={openResource}
+          onClose={() => setOpenResource(null)}
+          onChanged={async () => {
+            setSkillResources(await invoke<SkillResources>("get_skill_resources", { name: selectedSkill }));
+          }}
+        />
+      )}
+
       <SkillImportDialog
         isOpen={showImportDialog}
         onClose={() => setShowImportDialog(false)}